@@ -4,6 +4,7 @@
 //! Enables frontend to render complex multi-track segments and classify segment types.
 
 use crate::models::timeline::Clip;
+use crate::services::safe_area::{compute_safe_areas, SafeAreas};
 use crate::services::segment_renderer::{CanvasSize, Segment, SegmentRenderer, SegmentType, VideoLayer};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
@@ -172,6 +173,22 @@ pub fn cmd_classify_segment_type(active_clips: Vec<ActiveClip>) -> CompositionRe
     )
 }
 
+/// Compute safe-area guides for a social export aspect ratio
+///
+/// # Arguments
+///
+/// * `width`, `height` - Timeline resolution
+/// * `target_aspect` - Target width / height (e.g. 0.5625 for 9:16)
+///
+/// # Returns
+///
+/// The target crop region with its action-safe and title-safe rectangles
+#[tauri::command]
+pub fn cmd_get_safe_areas(width: u32, height: u32, target_aspect: f64) -> Result<SafeAreas, String> {
+    debug!("[Command] cmd_get_safe_areas called for {}x{} at aspect {}", width, height, target_aspect);
+    compute_safe_areas(width, height, target_aspect)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,6 +209,9 @@ mod tests {
                 muted: None,
                 audio_tracks: None,
                 transform: None,
+                linked_clip_id: None,
+                group_id: None,
+                transition_out: None,
             },
             track_id: "track1".to_string(),
             track_number: 1,
@@ -223,6 +243,9 @@ mod tests {
                     muted: None,
                     audio_tracks: None,
                     transform: None,
+                    linked_clip_id: None,
+                    group_id: None,
+                    transition_out: None,
                 },
                 track_id: "track1".to_string(),
                 track_number: 1,
@@ -242,6 +265,9 @@ mod tests {
                     muted: None,
                     audio_tracks: None,
                     transform: None,
+                    linked_clip_id: None,
+                    group_id: None,
+                    transition_out: None,
                 },
                 track_id: "track2".to_string(),
                 track_number: 2,
@@ -1,14 +1,31 @@
-use crate::models::{ExportConfig, ExportProgress, Timeline};
+use crate::models::{
+    BatchJobResult, ExportConfig, ExportJob, ExportPreset, ExportProgress, ExportStatus, ReencodePreset, Timeline,
+    EXPORT_PRESETS,
+};
 use crate::services::VideoExporter;
+use crate::services::audio_phase::{check_audio_phase, PhaseReport};
+use crate::services::export_history::{
+    default_history_path, load_history, record_completed_export, ExportRecord,
+};
+use crate::services::ffmpeg::formats::{get_supported_formats, FormatInfo};
+use crate::services::ffmpeg::{
+    run_export_batch, running_job_progress, validate_batch, validate_batch_jobs, validate_export_config, ExportError,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tauri::State;
+use tauri::{Emitter, State};
 use tokio::sync::Mutex;
 
+/// Active exports mapped by export ID
+type ExportMap = Arc<Mutex<HashMap<String, Arc<VideoExporter>>>>;
+
 /// Application state for managing export operations
 pub struct ExportState {
     /// Active export operations mapped by export ID
-    pub exports: Arc<Mutex<HashMap<String, Arc<Mutex<VideoExporter>>>>>,
+    ///
+    /// Exporters are shared without a lock of their own, so progress queries and
+    /// cancellation work while an export is running.
+    pub exports: ExportMap,
 }
 
 impl ExportState {
@@ -19,6 +36,41 @@ impl ExportState {
     }
 }
 
+/// How long a failed or cancelled export stays registered after its task ends
+const STOPPED_EXPORT_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Remove a finished export from the active exports
+///
+/// Failed and cancelled exports stay registered for `STOPPED_EXPORT_TTL` so
+/// `cmd_get_export_progress` keeps reporting `Failed` (with its error code) or
+/// `Cancelled` after FFmpeg has exited, then are removed too.
+async fn unregister_export(exports: &ExportMap, export_id: &str) {
+    unregister_export_after(exports, export_id, STOPPED_EXPORT_TTL).await;
+}
+
+async fn unregister_export_after(exports: &ExportMap, export_id: &str, stopped_ttl: std::time::Duration) {
+    let Some(exporter) = exports.lock().await.get(export_id).cloned() else {
+        return;
+    };
+
+    let failed = exporter.progress_handle().lock().await.status == ExportStatus::Failed;
+    if !failed && !exporter.is_cancelled() {
+        exports.lock().await.remove(export_id);
+        return;
+    }
+
+    let exports = Arc::clone(exports);
+    let export_id = export_id.to_string();
+    tokio::spawn(async move {
+        tokio::time::sleep(stopped_ttl).await;
+        let mut map = exports.lock().await;
+        // The ID may have been reused by a later export in the meantime
+        if map.get(&export_id).is_some_and(|current| Arc::ptr_eq(current, &exporter)) {
+            map.remove(&export_id);
+        }
+    });
+}
+
 /// Start a new video export operation
 ///
 /// This command initiates the export of a timeline to an MP4 file.
@@ -28,17 +80,30 @@ impl ExportState {
 /// # Arguments
 /// * `timeline` - Timeline with tracks and clips to export
 /// * `config` - Export configuration including output path
+/// * `preset` - Optional `ExportPreset` id whose encoding settings replace `config`'s
 /// * `state` - Application state for tracking exports
 ///
 /// # Returns
 /// * `Ok(String)` - Export ID for tracking progress
-/// * `Err(String)` - Error message if export failed to start
+/// * `Err(ExportError)` - `{ code, message }` if the export settings or preset are invalid,
+///   with `timelineErrors` listing every problem when the timeline itself is (`invalid_timeline`)
 #[tauri::command]
 pub async fn cmd_start_export(
     timeline: Timeline,
     config: ExportConfig,
+    preset: Option<String>,
     state: State<'_, ExportState>,
-) -> Result<String, String> {
+) -> Result<String, ExportError> {
+    // Overlaps and missing media otherwise fail deep inside FFmpeg
+    timeline.validate().map_err(ExportError::InvalidTimeline)?;
+
+    let config = match preset.as_deref() {
+        Some(id) => ExportPreset::find(id)
+            .ok_or_else(|| ExportError::InvalidConfig(format!("Unknown export preset '{}'", id)))?
+            .apply(config),
+        None => config,
+    };
+
     tracing::info!(
         event = "cmd_start_export",
         output_path = %config.output_path,
@@ -59,6 +124,9 @@ pub async fn cmd_start_export(
         );
     }
 
+    // Reject bad settings now rather than from the background task
+    validate_export_config(&config).await?;
+
     // Generate export ID
     let export_id = uuid::Uuid::new_v4().to_string();
 
@@ -66,7 +134,7 @@ pub async fn cmd_start_export(
     let exporter = VideoExporter::new(export_id.clone(), timeline.total_duration);
 
     // Store exporter in state
-    let exporter_arc = Arc::new(Mutex::new(exporter));
+    let exporter_arc = Arc::new(exporter);
     {
         let mut exports = state.exports.lock().await;
         exports.insert(export_id.clone(), exporter_arc.clone());
@@ -79,10 +147,7 @@ pub async fn cmd_start_export(
     let exports_map = Arc::clone(&state.exports);
 
     tokio::spawn(async move {
-        let result = {
-            let mut exporter = exporter_arc.lock().await;
-            exporter.export_timeline(&timeline_clone, &config_clone).await
-        };
+        let result = exporter_arc.export_timeline(&timeline_clone, &config_clone).await;
 
         match result {
             Ok(()) => {
@@ -91,6 +156,7 @@ pub async fn cmd_start_export(
                     export_id = %export_id_clone,
                     "Export completed successfully"
                 );
+                record_completed_export(&export_id_clone, &config_clone, timeline_clone.total_duration);
             }
             Err(e) => {
                 tracing::error!(
@@ -103,8 +169,7 @@ pub async fn cmd_start_export(
         }
 
         // Clean up export from state after completion
-        let mut exports = exports_map.lock().await;
-        exports.remove(&export_id_clone);
+        unregister_export(&exports_map, &export_id_clone).await;
     });
 
     tracing::info!(
@@ -124,12 +189,12 @@ pub async fn cmd_start_export(
 ///
 /// # Returns
 /// * `Ok(ExportProgress)` - Current progress information
-/// * `Err(String)` - Error if export not found or progress unavailable
+/// * `Err(ExportError)` - `not_found` if no export is registered under the ID
 #[tauri::command]
 pub async fn cmd_get_export_progress(
     export_id: String,
     state: State<'_, ExportState>,
-) -> Result<ExportProgress, String> {
+) -> Result<ExportProgress, ExportError> {
     tracing::debug!(
         event = "cmd_get_export_progress",
         export_id = %export_id,
@@ -140,26 +205,24 @@ pub async fn cmd_get_export_progress(
 
     let exporter_arc = exports
         .get(&export_id)
-        .ok_or_else(|| format!("Export not found: {}", export_id))?
+        .ok_or_else(|| ExportError::NotFound(export_id.clone()))?
         .clone();
 
-    // Drop exports lock before locking exporter
     drop(exports);
 
-    let exporter = exporter_arc.lock().await;
-
-    let progress = exporter
+    let progress = exporter_arc
         .get_progress()
         .await
-        .map_err(|e| format!("Failed to get progress: {}", e))?;
+        .map_err(|e| ExportError::ProcessFailed(format!("Failed to get progress: {}", e)))?;
 
     Ok(progress)
 }
 
 /// Cancel an ongoing export operation
 ///
-/// This command terminates the FFmpeg process for a running export
-/// and removes it from the active exports list.
+/// This command terminates the FFmpeg process for a running export (SIGTERM,
+/// then SIGKILL after a grace period) and deletes the partial output. The
+/// export stays registered so `cmd_get_export_progress` reports `Cancelled`.
 ///
 /// # Arguments
 /// * `export_id` - ID of the export to cancel
@@ -168,13 +231,14 @@ pub async fn cmd_get_export_progress(
 ///
 /// # Returns
 /// * `Ok(())` - Export cancelled successfully
-/// * `Err(String)` - Error if export not found or cancellation failed
+/// * `Err(ExportError)` - `not_found` if no export is registered under the ID,
+///   `process_failed` if FFmpeg could not be stopped
 #[tauri::command]
 pub async fn cmd_cancel_export(
     export_id: String,
     output_path: String,
     state: State<'_, ExportState>,
-) -> Result<(), String> {
+) -> Result<(), ExportError> {
     tracing::info!(
         event = "cmd_cancel_export",
         export_id = %export_id,
@@ -190,23 +254,16 @@ pub async fn cmd_cancel_export(
     match exporter_arc {
         Some(exporter_arc) => {
             // Call the cancel method on the exporter
-            {
-                let mut exporter = exporter_arc.lock().await;
-                if let Err(e) = exporter.cancel(&output_path).await {
-                    tracing::error!(
-                        event = "export_cancel_failed",
-                        export_id = %export_id,
-                        error = %e,
-                        "Failed to cancel export"
-                    );
-                    return Err(format!("Failed to cancel export: {}", e));
-                }
+            if let Err(e) = exporter_arc.cancel(&output_path).await {
+                tracing::error!(
+                    event = "export_cancel_failed",
+                    export_id = %export_id,
+                    error = %e,
+                    "Failed to cancel export"
+                );
+                return Err(ExportError::ProcessFailed(format!("Failed to cancel export: {}", e)));
             }
 
-            // Remove export from active exports after successful cancellation
-            let mut exports = state.exports.lock().await;
-            exports.remove(&export_id);
-
             tracing::info!(
                 event = "export_cancelled",
                 export_id = %export_id,
@@ -214,8 +271,331 @@ pub async fn cmd_cancel_export(
             );
             Ok(())
         }
-        None => Err(format!("Export not found: {}", export_id)),
+        None => Err(ExportError::NotFound(export_id)),
+    }
+}
+
+/// Interval between `batch-export-progress` events while a job renders
+const BATCH_PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Render several timelines one after another
+///
+/// Each job has its own timeline and `ExportConfig` (e.g. 16:9, 9:16 and a
+/// 30-second cut of the same project). Jobs are queued and rendered in order;
+/// each is registered under its own export ID while it runs, so
+/// `cmd_cancel_export` works per job. A failed job doesn't stop the batch.
+///
+/// Every job's timeline and settings are validated before the first job starts.
+///
+/// Emits `batch-export-progress` (`BatchExportProgress`) as each job starts,
+/// every `BATCH_PROGRESS_INTERVAL` while it renders (the percentage includes
+/// the running job's progress) and when the batch finishes.
+///
+/// # Arguments
+/// * `jobs` - Jobs to render
+/// * `state` - Application state for tracking exports
+///
+/// # Returns
+/// * `Ok(Vec<BatchJobResult>)` - One result per job, in submission order
+/// * `Err(ExportError)` - `invalid_config` if the batch is empty or two jobs share an
+///   output path; a job's `invalid_timeline`/`invalid_config` error, with `jobIndex`
+#[tauri::command]
+pub async fn cmd_batch_export(
+    jobs: Vec<ExportJob>,
+    state: State<'_, ExportState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<BatchJobResult>, ExportError> {
+    validate_batch(&jobs)?;
+    validate_batch_jobs(&jobs).await?;
+
+    let batch_id = uuid::Uuid::new_v4().to_string();
+    tracing::info!(
+        event = "cmd_batch_export",
+        batch_id = %batch_id,
+        job_count = jobs.len(),
+        "Starting batch export"
+    );
+
+    let exports_map = Arc::clone(&state.exports);
+    let total_jobs = jobs.len();
+    let results = run_export_batch(
+        &batch_id,
+        jobs,
+        |index, export_id, job| {
+            let exports_map = Arc::clone(&exports_map);
+            let batch_id = batch_id.clone();
+            let app_handle = app_handle.clone();
+            async move {
+                let exporter = Arc::new(VideoExporter::new(
+                    export_id.clone(),
+                    job.timeline.total_duration,
+                ));
+                exports_map.lock().await.insert(export_id.clone(), exporter.clone());
+
+                let job_progress = exporter.progress_handle();
+                let ticker = tokio::spawn(async move {
+                    loop {
+                        tokio::time::sleep(BATCH_PROGRESS_INTERVAL).await;
+                        let job_percentage = job_progress.lock().await.percentage;
+                        let progress = running_job_progress(&batch_id, index, total_jobs, job_percentage);
+                        let _ = app_handle.emit("batch-export-progress", progress);
+                    }
+                });
+
+                let result = exporter.export_timeline(&job.timeline, &job.config).await;
+                ticker.abort();
+
+                unregister_export(&exports_map, &export_id).await;
+                if result.is_ok() {
+                    record_completed_export(&export_id, &job.config, job.timeline.total_duration);
+                }
+                result.map_err(|e| e.to_string())
+            }
+        },
+        |progress| {
+            if let Err(e) = app_handle.emit("batch-export-progress", progress) {
+                tracing::warn!(event = "batch_export_progress_emit_failed", error = %e);
+            }
+        },
+    )
+    .await;
+
+    tracing::info!(
+        event = "batch_export_completed",
+        batch_id = %batch_id,
+        failed_jobs = results.iter().filter(|r| r.error_message.is_some()).count(),
+        "Batch export finished"
+    );
+
+    Ok(results)
+}
+
+/// Interval between `reencode-progress` events
+const REENCODE_PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Re-encode a finished recording with a slower, smaller preset
+///
+/// Recordings are captured with `ultrafast`; this re-encodes the video with
+/// libx264 at the chosen preset (default `slow`, CRF 23) and copies the audio.
+/// The output is written next to the source as `<name>-reencoded.mp4`.
+///
+/// Runs in the background and is registered like an export, so
+/// `cmd_get_export_progress` and `cmd_cancel_export` work with its ID. Emits
+/// `reencode-progress` (`ExportProgress`) periodically and once when done.
+///
+/// # Arguments
+/// * `path` - Absolute path to the recording
+/// * `preset` - Re-encode preset (defaults to `smaller`)
+/// * `state` - Application state for tracking exports
+///
+/// # Returns
+/// * `Ok(String)` - Path the re-encoded file will be written to
+/// * `Err(ExportError)` - `invalid_config` if the source file does not exist or can't be read
+#[tauri::command]
+pub async fn cmd_reencode_recording(
+    path: String,
+    preset: Option<ReencodePreset>,
+    state: State<'_, ExportState>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, ExportError> {
+    let preset = preset.unwrap_or_default();
+    tracing::info!(event = "cmd_reencode_recording", path = %path, preset = ?preset);
+
+    let input = std::path::PathBuf::from(&path);
+    if !input.exists() {
+        let error_msg = format!("File not found: {}", path);
+        tracing::error!(event = "file_not_found", path = %path, error_msg = %error_msg);
+        return Err(ExportError::InvalidConfig(error_msg));
+    }
+
+    let duration_ms = crate::utils::ffmpeg::extract_metadata(&path)
+        .await
+        .map(|media| media.duration)
+        .map_err(|e| ExportError::InvalidConfig(format!("Failed to read recording: {}", e)))?;
+
+    let stem = input
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    let output_path = input.with_file_name(format!("{}-reencoded.mp4", stem));
+
+    let export_id = uuid::Uuid::new_v4().to_string();
+    let exporter = VideoExporter::new(export_id.clone(), duration_ms);
+    let progress = exporter.progress_handle();
+    let exporter = Arc::new(exporter);
+    state.exports.lock().await.insert(export_id.clone(), exporter.clone());
+
+    let exports_map = Arc::clone(&state.exports);
+    let task_output_path = output_path.clone();
+    tokio::spawn(async move {
+        let ticker_progress = Arc::clone(&progress);
+        let ticker_handle = app_handle.clone();
+        let ticker = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(REENCODE_PROGRESS_INTERVAL).await;
+                let snapshot = ticker_progress.lock().await.clone();
+                let _ = ticker_handle.emit("reencode-progress", snapshot);
+            }
+        });
+
+        let result = exporter.reencode_file(&input, preset, &task_output_path).await;
+        ticker.abort();
+
+        let mut snapshot = progress.lock().await.clone();
+        if let (Err(e), false) = (&result, exporter.is_cancelled()) {
+            tracing::error!(event = "reencode_failed", export_id = %export_id, error = %e);
+            snapshot = snapshot.mark_failed(e.code(), e.to_string());
+        }
+        if let Err(e) = app_handle.emit("reencode-progress", snapshot) {
+            tracing::warn!(event = "reencode_progress_emit_failed", error = %e);
+        }
+
+        unregister_export(&exports_map, &export_id).await;
+    });
+
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+/// Trim a recording and export the range in one step
+///
+/// Skips the timeline: the range is cut with input seeking and encoded with
+/// `config` in a single FFmpeg pass, with no intermediate file. Runs in the
+/// background like `cmd_start_export`; track it with `cmd_get_export_progress`
+/// and stop it with `cmd_cancel_export`.
+///
+/// # Arguments
+/// * `input_path` - Absolute path to the recording
+/// * `start_ms` - Range start in milliseconds
+/// * `end_ms` - Range end in milliseconds
+/// * `export_config` - Export configuration including output path
+/// * `state` - Application state for tracking exports
+///
+/// # Returns
+/// * `Ok(String)` - Export ID for tracking progress
+/// * `Err(ExportError)` - `{ code, message }` if the export could not start
+#[tauri::command]
+pub async fn cmd_quick_clip(
+    input_path: String,
+    start_ms: u64,
+    end_ms: u64,
+    export_config: ExportConfig,
+    state: State<'_, ExportState>,
+) -> Result<String, ExportError> {
+    tracing::info!(
+        event = "cmd_quick_clip",
+        input_path = %input_path,
+        start_ms,
+        end_ms,
+        output_path = %export_config.output_path,
+        "Starting quick clip command"
+    );
+
+    if !std::path::Path::new(&input_path).exists() {
+        return Err(ExportError::InvalidConfig(format!("File not found: {}", input_path)));
+    }
+    if end_ms <= start_ms {
+        return Err(ExportError::InvalidConfig(format!(
+            "Invalid clip range: end ({}ms) must be after start ({}ms)",
+            end_ms, start_ms
+        )));
     }
+    validate_export_config(&export_config).await?;
+
+    let export_id = uuid::Uuid::new_v4().to_string();
+    let duration_ms = end_ms - start_ms;
+    let exporter_arc = Arc::new(VideoExporter::new(export_id.clone(), duration_ms));
+    state.exports.lock().await.insert(export_id.clone(), exporter_arc.clone());
+
+    let export_id_clone = export_id.clone();
+    let exports_map = Arc::clone(&state.exports);
+
+    tokio::spawn(async move {
+        let result = exporter_arc
+            .quick_clip(std::path::Path::new(&input_path), start_ms, end_ms, &export_config)
+            .await;
+
+        match result {
+            Ok(()) => {
+                tracing::info!(event = "quick_clip_completed", export_id = %export_id_clone);
+                record_completed_export(&export_id_clone, &export_config, duration_ms);
+            }
+            Err(e) => {
+                tracing::error!(event = "quick_clip_failed", export_id = %export_id_clone, error = %e);
+            }
+        }
+
+        unregister_export(&exports_map, &export_id_clone).await;
+    });
+
+    Ok(export_id)
+}
+
+/// Get recently completed exports, newest first
+///
+/// Records are appended on every successful export (single or batch) and
+/// capped at `MAX_EXPORT_HISTORY`. Home directory paths are shown as `~`.
+///
+/// # Returns
+/// * `Ok(Vec<ExportRecord>)` - Recent exports (empty if none were recorded)
+/// * `Err(String)` - Error if the history file could not be read
+#[tauri::command]
+pub async fn cmd_get_export_history() -> Result<Vec<ExportRecord>, String> {
+    let path = default_history_path().map_err(|e| e.to_string())?;
+    let mut records = load_history(&path).map_err(|e| {
+        tracing::error!(event = "export_history_read_failed", error = %e);
+        format!("Failed to read export history: {}", e)
+    })?;
+    records.reverse();
+    Ok(records)
+}
+
+/// Get the export formats supported by the installed FFmpeg
+///
+/// Formats whose encoders are missing from the FFmpeg build (e.g. libx265)
+/// are omitted. The encoder probe runs once and is cached.
+///
+/// # Returns
+/// * `Ok(Vec<FormatInfo>)` - Formats that can be exported
+/// * `Err(String)` - Error if FFmpeg could not be probed
+#[tauri::command]
+pub async fn cmd_get_supported_export_formats() -> Result<Vec<FormatInfo>, String> {
+    tracing::debug!(event = "cmd_get_supported_export_formats", "Getting supported export formats");
+
+    get_supported_formats().await.map_err(|e| {
+        tracing::error!(event = "export_formats_probe_failed", error = %e);
+        format!("Failed to detect supported export formats: {}", e)
+    })
+}
+
+/// List the built-in export presets
+///
+/// # Returns
+/// * `Ok(Vec<ExportPreset>)` - Presets with their descriptions and settings
+#[tauri::command]
+pub async fn cmd_list_export_presets() -> Result<Vec<ExportPreset>, String> {
+    Ok(EXPORT_PRESETS.to_vec())
+}
+
+/// Check a file's stereo audio for mono compatibility before export
+///
+/// Measures the correlation between left and right channels. Strongly negative
+/// correlation means the channels are out of phase and will cancel when the
+/// export is played back in mono (phone speakers, some social platforms).
+///
+/// # Arguments
+/// * `path` - Media file whose audio to analyze
+///
+/// # Returns
+/// * `Ok(PhaseReport)` - Correlation, mono-sum loss and an optional warning
+/// * `Err(String)` - Error if the audio could not be decoded
+#[tauri::command]
+pub async fn cmd_check_audio_phase(path: String) -> Result<PhaseReport, String> {
+    tracing::debug!(event = "cmd_check_audio_phase", path = %path, "Checking audio phase");
+
+    check_audio_phase(std::path::Path::new(&path)).await.map_err(|e| {
+        tracing::error!(event = "audio_phase_check_failed", path = %path, error = %e);
+        format!("Failed to check audio phase: {}", e)
+    })
 }
 
 #[cfg(test)]
@@ -229,6 +609,42 @@ mod tests {
         assert_eq!(exports.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_cancelled_export_unregistered_after_ttl() {
+        let ttl = std::time::Duration::from_millis(50);
+        let state = ExportState::new();
+        let finished = Arc::new(VideoExporter::new("export-1".to_string(), 1_000));
+        let cancelled = Arc::new(VideoExporter::new("export-2".to_string(), 1_000));
+        cancelled.cancel("/nonexistent/out.mp4").await.unwrap();
+        let failed = Arc::new(VideoExporter::new("export-3".to_string(), 1_000));
+        {
+            let progress = failed.progress_handle();
+            let mut progress = progress.lock().await;
+            *progress = progress.clone().mark_failed("disk_full", "No space left".to_string());
+        }
+        {
+            let mut exports = state.exports.lock().await;
+            exports.insert("export-1".to_string(), finished);
+            exports.insert("export-2".to_string(), cancelled);
+            exports.insert("export-3".to_string(), failed);
+        }
+
+        unregister_export_after(&state.exports, "export-1", ttl).await;
+        unregister_export_after(&state.exports, "export-2", ttl).await;
+        unregister_export_after(&state.exports, "export-3", ttl).await;
+        {
+            let exports = state.exports.lock().await;
+            assert!(!exports.contains_key("export-1"));
+            assert!(exports.contains_key("export-2"), "cancelled status stays readable for a while");
+            let failed = exports.get("export-3").expect("failure stays readable for a while");
+            let progress = failed.progress_handle();
+            assert_eq!(progress.lock().await.error_code.as_deref(), Some("disk_full"));
+        }
+
+        tokio::time::sleep(ttl * 4).await;
+        assert!(state.exports.lock().await.is_empty());
+    }
+
     // Note: Tests for cmd_start_export, cmd_get_export_progress, and cmd_cancel_export
     // require integration testing with Tauri's State system.
     // These are tested through end-to-end tests with the running application.
@@ -0,0 +1,114 @@
+//! Logging-related Tauri commands
+//!
+//! Provides runtime control over the tracing filter so debug logging can be
+//! enabled without restarting the app, and access to recent log lines for the
+//! built-in log viewer.
+
+use crate::services::log_buffer::{LogBuffer, LogLine};
+use std::sync::Arc;
+use tauri::State;
+use tracing::{error, info};
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+/// Reload handle for the global EnvFilter installed by `init_logging`
+pub type LogReloadHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Global log level state
+///
+/// `None` if logging failed to initialize (no subscriber to reconfigure).
+pub struct LogLevelState(pub Option<LogReloadHandle>);
+
+/// Global in-memory log buffer state
+pub struct LogBufferState(pub Arc<LogBuffer>);
+
+/// Log levels accepted by `cmd_set_log_level`
+const VALID_LOG_LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "off"];
+
+/// Validate a log level string and build the matching EnvFilter
+fn parse_log_level(level: &str) -> Result<EnvFilter, String> {
+    let normalized = level.trim().to_lowercase();
+
+    if !VALID_LOG_LEVELS.contains(&normalized.as_str()) {
+        return Err(format!(
+            "Invalid log level '{}'. Expected one of: {}",
+            level,
+            VALID_LOG_LEVELS.join(", ")
+        ));
+    }
+
+    EnvFilter::try_new(&normalized).map_err(|e| format!("Invalid log level '{}': {}", level, e))
+}
+
+/// Swap the active filter behind a reload handle
+fn apply_log_level(handle: &LogReloadHandle, level: &str) -> Result<(), String> {
+    let filter = parse_log_level(level)?;
+
+    handle
+        .reload(filter)
+        .map_err(|e| format!("Failed to update log level: {}", e))
+}
+
+/// Change the tracing log level at runtime
+///
+/// # Arguments
+///
+/// * `level` - One of trace, debug, info, warn, error, off (case-insensitive)
+///
+/// # Returns
+///
+/// - `Ok(String)` with the applied level
+/// - `Err(String)` if the level is invalid or logging is not initialized
+#[tauri::command]
+pub fn cmd_set_log_level(level: String, state: State<LogLevelState>) -> Result<String, String> {
+    info!("[Command] cmd_set_log_level called with level: {}", level);
+
+    let handle = state.0.as_ref().ok_or_else(|| {
+        error!("[Command] Logging not initialized, cannot change log level");
+        "Logging not initialized".to_string()
+    })?;
+
+    apply_log_level(handle, &level).map_err(|e| {
+        error!("[Command] {}", e);
+        e
+    })?;
+
+    let applied = level.trim().to_lowercase();
+    info!("[Command] Log level set to {}", applied);
+    Ok(applied)
+}
+
+/// Get the most recent log lines, newest first
+///
+/// # Arguments
+///
+/// * `count` - Maximum number of lines to return (bounded by the buffer capacity)
+#[tauri::command]
+pub fn cmd_get_recent_logs(count: usize, state: State<LogBufferState>) -> Vec<LogLine> {
+    state.0.recent(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_parse_log_level_rejects_invalid() {
+        assert!(parse_log_level("verbose").is_err());
+        assert!(parse_log_level("").is_err());
+        assert!(parse_log_level("DEBUG").is_ok());
+    }
+
+    #[test]
+    fn test_apply_log_level_updates_filter() {
+        let (filter_layer, handle) = reload::Layer::new(EnvFilter::new("info"));
+        // Subscriber must stay alive for the handle to be usable
+        let _subscriber = Registry::default().with(filter_layer);
+
+        assert!(apply_log_level(&handle, "nonsense").is_err());
+        assert_eq!(handle.with_current(|f| f.to_string()).unwrap(), "info");
+
+        apply_log_level(&handle, "debug").unwrap();
+        assert_eq!(handle.with_current(|f| f.to_string()).unwrap(), "debug");
+    }
+}
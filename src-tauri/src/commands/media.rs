@@ -1,5 +1,23 @@
-use crate::models::MediaFile;
+use crate::models::{Clip, EdlImport, MediaFile, MediaMetadata, Timeline};
+use crate::services::clip_frame::render_clip_frame;
+use crate::services::contact_sheet::{generate_contact_sheet, ContactSheetLayout};
+use crate::services::edl_importer;
+use crate::services::media_thumbnail::generate_thumbnail;
+use crate::services::ffmpeg::{
+    get_keyframes, measure_av_sync, optimize_for_streaming, repair_recording, split_by_silence,
+    AvSyncReport,
+};
+use crate::services::media_relink::{relink_clips, RelinkResult};
+use crate::services::waveform::{extract_waveform, WaveformData};
+use crate::services::waveform_peaks::{extract_waveform_peaks, WaveformPeaks};
 use crate::utils::ffmpeg;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static::lazy_static! {
+    /// Media imported this session, by ID (thumbnails are requested by media ID)
+    static ref IMPORTED_MEDIA: Mutex<HashMap<String, MediaFile>> = Mutex::new(HashMap::new());
+}
 
 /// Import a video file and extract its metadata
 ///
@@ -62,6 +80,9 @@ pub async fn cmd_import_media(file_path: String) -> Result<MediaFile, String> {
                 media_id = %media_file.id,
                 "Successfully imported media file"
             );
+            if let Ok(mut imported) = IMPORTED_MEDIA.lock() {
+                imported.insert(media_file.id.clone(), media_file.clone());
+            }
             Ok(media_file)
         }
         Err(e) => {
@@ -77,6 +98,495 @@ pub async fn cmd_import_media(file_path: String) -> Result<MediaFile, String> {
     }
 }
 
+/// Probe a media file's duration, dimensions, frame rate, codecs and bitrate
+///
+/// Uses the bundled ffprobe. Audio-only files are supported: their video
+/// fields are `None`.
+///
+/// # Arguments
+/// * `path` - Absolute path to the media file
+///
+/// # Returns
+/// * `Ok(MediaMetadata)` - Stream details of the file
+/// * `Err(String)` - File not found or ffprobe could not read it
+#[tauri::command]
+pub async fn cmd_probe_media(path: String) -> Result<MediaMetadata, String> {
+    if !std::path::Path::new(&path).exists() {
+        let error_msg = format!("File not found: {}", path);
+        tracing::error!(event = "file_not_found", file_path = %path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+
+    ffmpeg::probe_media(std::path::Path::new(&path)).await.map_err(|e| {
+        tracing::error!(event = "cmd_probe_media_failed", file_path = %path, error = %e);
+        format!("Failed to read media details: {}", e)
+    })
+}
+
+/// Import a CMX3600 EDL and reconstruct it as a Timeline
+///
+/// Parses the EDL, resolves each event's media relative to the EDL's directory,
+/// and places clips at their record positions with source in/out as trim points.
+/// Media that cannot be found is kept on the timeline so it can be relinked
+/// later, and listed in `unresolved_media` so the user can be told which.
+///
+/// # Arguments
+/// * `path` - Absolute path to the .edl file
+///
+/// # Returns
+/// * `Ok(EdlImport)` - Reconstructed timeline and the media that could not be found
+/// * `Err(String)` - User-friendly error message if the EDL can't be read or parsed
+#[tauri::command]
+pub async fn cmd_import_edl(path: String) -> Result<EdlImport, String> {
+    tracing::info!(event = "cmd_import_edl", path = %path, "Import EDL command invoked");
+
+    let edl_path = std::path::PathBuf::from(&path);
+    if !edl_path.exists() {
+        let error_msg = format!("File not found: {}", path);
+        tracing::error!(event = "file_not_found", path = %path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+
+    let result = tokio::task::spawn_blocking(move || edl_importer::import_edl_file(&edl_path))
+        .await
+        .map_err(|e| format!("EDL import task failed: {}", e))?;
+
+    match result {
+        Ok((timeline, unresolved)) => {
+            if !unresolved.is_empty() {
+                tracing::warn!(
+                    event = "edl_unresolved_media",
+                    path = %path,
+                    count = unresolved.len(),
+                    media = ?unresolved,
+                    "EDL references media that could not be found"
+                );
+            }
+            tracing::info!(
+                event = "cmd_import_edl_success",
+                path = %path,
+                total_duration = timeline.total_duration,
+                "Successfully imported EDL"
+            );
+            Ok(EdlImport {
+                timeline,
+                unresolved_media: unresolved,
+            })
+        }
+        Err(e) => {
+            tracing::error!(event = "cmd_import_edl_failed", path = %path, error = %e);
+            Err(format!("Failed to import EDL: {}", e))
+        }
+    }
+}
+
+/// Repair a recording left unplayable by a crash (missing moov atom)
+///
+/// Probes the file first and returns it unchanged if it already plays.
+/// Otherwise remuxes it with FFmpeg stream copy into `<name>-repaired.mp4`.
+///
+/// # Arguments
+/// * `path` - Absolute path to the recording
+///
+/// # Returns
+/// * `Ok(String)` - Path of the playable file (original or repaired copy)
+/// * `Err(String)` - User-friendly error message if the file can't be recovered
+#[tauri::command]
+pub async fn cmd_repair_recording(path: String) -> Result<String, String> {
+    tracing::info!(event = "cmd_repair_recording", path = %path, "Repair recording command invoked");
+
+    let recording_path = std::path::Path::new(&path);
+    if !recording_path.exists() {
+        let error_msg = format!("File not found: {}", path);
+        tracing::error!(event = "file_not_found", path = %path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+
+    match repair_recording(recording_path).await {
+        Ok(repaired) => Ok(repaired.to_string_lossy().to_string()),
+        Err(e) => {
+            tracing::error!(event = "cmd_repair_recording_failed", path = %path, error = %e);
+            Err(format!("Failed to repair recording: {}", e))
+        }
+    }
+}
+
+/// Rewrite a recording so it seeks quickly (moov atom at the front)
+///
+/// Real-time recordings are written with the moov atom last. This remuxes the
+/// file with stream copy and `-movflags +faststart`, writing to a temporary file
+/// and renaming it over the original. Files that are already faststart are
+/// left unchanged.
+///
+/// # Arguments
+/// * `path` - Absolute path to the MP4 recording
+///
+/// # Returns
+/// * `Ok(())` - The file at `path` is faststart
+/// * `Err(String)` - User-friendly error message for non-MP4 input or a failed remux
+#[tauri::command]
+pub async fn cmd_optimize_for_streaming(path: String) -> Result<(), String> {
+    tracing::info!(event = "cmd_optimize_for_streaming", path = %path, "Optimize for streaming command invoked");
+
+    let recording_path = std::path::Path::new(&path);
+    if !recording_path.exists() {
+        let error_msg = format!("File not found: {}", path);
+        tracing::error!(event = "file_not_found", path = %path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+
+    optimize_for_streaming(recording_path).await.map_err(|e| {
+        tracing::error!(event = "cmd_optimize_for_streaming_failed", path = %path, error = %e);
+        format!("Failed to optimize recording: {}", e)
+    })
+}
+
+/// Relink missing media by pointing clips at a replacement file
+///
+/// Every clip whose file path is `old_file_path` is updated to `new_path`.
+/// The new file is probed for its duration; if it differs from what the clips
+/// expected, a warning is returned and trim points are clamped to the new length.
+///
+/// # Arguments
+/// * `timeline` - Timeline loaded with missing media
+/// * `old_file_path` - Path the clips currently reference
+/// * `new_path` - Absolute path to the replacement file
+///
+/// # Returns
+/// * `Ok(RelinkResult)` - Updated timeline, relinked clip count and warnings
+/// * `Err(String)` - User-friendly error message if the new file is unusable
+#[tauri::command]
+pub async fn cmd_relink_media(
+    timeline: Timeline,
+    old_file_path: String,
+    new_path: String,
+) -> Result<RelinkResult, String> {
+    tracing::info!(
+        event = "cmd_relink_media",
+        old_file_path = %old_file_path,
+        new_path = %new_path,
+        "Relink media command invoked"
+    );
+
+    if !std::path::Path::new(&new_path).exists() {
+        let error_msg = format!("File not found: {}", new_path);
+        tracing::error!(event = "file_not_found", path = %new_path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+
+    let media_file = ffmpeg::extract_metadata(&new_path).await.map_err(|e| {
+        tracing::error!(event = "relink_probe_failed", path = %new_path, error = %e);
+        format!("Failed to read replacement file: {}", e)
+    })?;
+
+    let result = relink_clips(timeline, &old_file_path, &new_path, media_file.duration);
+    if result.relinked_clips == 0 {
+        return Err(format!("No clips reference {}", old_file_path));
+    }
+
+    for warning in &result.warnings {
+        tracing::warn!(event = "relink_duration_mismatch", new_path = %new_path, "{}", warning);
+    }
+    tracing::info!(
+        event = "cmd_relink_media_success",
+        relinked_clips = result.relinked_clips,
+        "Relinked media"
+    );
+
+    Ok(result)
+}
+
+/// Render a thumbnail of a clip for the inspector
+///
+/// The frame is taken at the clip's in-point and passed through the same
+/// per-clip adjustments (e.g. rotation) used on export. Results are cached
+/// by clip content, so repeated requests are cheap.
+///
+/// # Arguments
+/// * `clip` - Clip to render
+///
+/// # Returns
+/// * `Ok(String)` - Base64 data URL of the JPEG thumbnail
+/// * `Err(String)` - User-friendly error message if the frame could not be rendered
+#[tauri::command]
+pub async fn cmd_get_clip_frame(clip: Clip) -> Result<String, String> {
+    tracing::debug!(event = "cmd_get_clip_frame", clip_id = %clip.id, file_path = %clip.file_path);
+
+    if !std::path::Path::new(&clip.file_path).exists() {
+        let error_msg = format!("File not found: {}", clip.file_path);
+        tracing::error!(event = "file_not_found", path = %clip.file_path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+
+    let cache_dir = dirs::data_dir()
+        .ok_or_else(|| "Failed to resolve app data directory".to_string())?
+        .join("com.clippy.app")
+        .join("thumbnails");
+
+    render_clip_frame(&clip, &cache_dir).await.map_err(|e| {
+        tracing::error!(event = "cmd_get_clip_frame_failed", clip_id = %clip.id, error = %e);
+        format!("Failed to render clip frame: {}", e)
+    })
+}
+
+/// Render a thumbnail of imported media at a timestamp
+///
+/// The frame is scaled to at most 320px wide and cached by media ID and
+/// timestamp. Timestamps past the end of the media show the last frame.
+///
+/// # Arguments
+/// * `media_id` - ID of a `MediaFile` returned by `cmd_import_media`
+/// * `timestamp_secs` - Time of the frame in seconds
+///
+/// # Returns
+/// * `Ok(String)` - Base64 data URL of the PNG thumbnail
+/// * `Err(String)` - User-friendly error message if the frame could not be rendered
+#[tauri::command]
+pub async fn cmd_generate_thumbnail(media_id: String, timestamp_secs: f64) -> Result<String, String> {
+    tracing::debug!(event = "cmd_generate_thumbnail", media_id = %media_id, timestamp_secs);
+
+    let media = IMPORTED_MEDIA
+        .lock()
+        .map_err(|e| format!("Media registry lock poisoned: {}", e))?
+        .get(&media_id)
+        .cloned()
+        .ok_or_else(|| format!("Media not found: {}. Please re-import the file.", media_id))?;
+
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| "Failed to resolve caches directory".to_string())?
+        .join("com.clippy.app")
+        .join("thumbnails");
+
+    generate_thumbnail(&media, timestamp_secs, &cache_dir).await.map_err(|e| {
+        tracing::error!(event = "cmd_generate_thumbnail_failed", media_id = %media_id, error = %e);
+        format!("Failed to generate thumbnail: {}", e)
+    })
+}
+
+/// Render a contact sheet (grid of evenly spaced frames) for a media file
+///
+/// Short videos get fewer rows (then columns) so frames stay at least a second
+/// apart. Sheets are cached by file and layout.
+///
+/// # Arguments
+/// * `file_path` - Absolute path to the media file
+/// * `rows` - Grid rows
+/// * `cols` - Grid columns
+/// * `spacing` - Pixels between and around tiles (defaults to 0)
+///
+/// # Returns
+/// * `Ok(String)` - Path of the JPEG contact sheet
+/// * `Err(String)` - User-friendly error message if the sheet could not be rendered
+#[tauri::command]
+pub async fn cmd_generate_contact_sheet(
+    file_path: String,
+    rows: u32,
+    cols: u32,
+    spacing: Option<u32>,
+) -> Result<String, String> {
+    tracing::debug!(event = "cmd_generate_contact_sheet", file_path = %file_path, rows, cols);
+
+    let path = std::path::Path::new(&file_path);
+    if !path.exists() {
+        let error_msg = format!("File not found: {}", file_path);
+        tracing::error!(event = "file_not_found", path = %file_path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+    if rows == 0 || cols == 0 {
+        return Err(format!("Invalid contact sheet grid: {}x{}", cols, rows));
+    }
+
+    let cache_dir = dirs::data_dir()
+        .ok_or_else(|| "Failed to resolve app data directory".to_string())?
+        .join("com.clippy.app")
+        .join("thumbnails");
+    let layout = ContactSheetLayout { rows, cols, spacing: spacing.unwrap_or(0) };
+
+    let sheet = generate_contact_sheet(path, layout, &cache_dir).await.map_err(|e| {
+        tracing::error!(event = "cmd_generate_contact_sheet_failed", file_path = %file_path, error = %e);
+        format!("Failed to generate contact sheet: {}", e)
+    })?;
+
+    Ok(sheet.to_string_lossy().to_string())
+}
+
+/// Extract waveform peaks for a media file at a timeline zoom detail
+///
+/// Detail `0` is an overview (25 peaks/s); each level doubles the resolution
+/// up to `MAX_WAVEFORM_DETAIL`. Levels are cached per file: zooming out reuses
+/// a finer cached level, zooming in past it decodes the audio again.
+///
+/// # Arguments
+/// * `file_path` - Absolute path to the media file
+/// * `detail` - Detail level (defaults to 0)
+///
+/// # Returns
+/// * `Ok(WaveformData)` - Peaks and metadata
+/// * `Err(String)` - User-friendly error message if the audio could not be decoded
+#[tauri::command]
+pub async fn cmd_extract_waveform(file_path: String, detail: Option<u8>) -> Result<WaveformData, String> {
+    tracing::debug!(event = "cmd_extract_waveform", file_path = %file_path, detail = ?detail);
+
+    let path = std::path::Path::new(&file_path);
+    if !path.exists() {
+        let error_msg = format!("File not found: {}", file_path);
+        tracing::error!(event = "file_not_found", path = %file_path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+
+    extract_waveform(path, detail.unwrap_or(0)).await.map_err(|e| {
+        tracing::error!(event = "cmd_extract_waveform_failed", path = %file_path, error = %e);
+        format!("Failed to extract waveform: {}", e)
+    })
+}
+
+/// Extract min/max waveform peaks of imported media for drawing timeline clips
+///
+/// Each bucket of `1 / samples_per_second` seconds becomes a `(min, max)`
+/// pair. Peaks are cached on disk by media ID and resolution. Media without
+/// an audio track returns no peaks and `hasAudio: false`.
+///
+/// # Arguments
+/// * `media_id` - ID of a `MediaFile` returned by `cmd_import_media`
+/// * `samples_per_second` - Peak pairs per second (1 to `MAX_PEAK_PAIRS_PER_SECOND`)
+///
+/// # Returns
+/// * `Ok(WaveformPeaks)` - Peaks and whether the media has audio
+/// * `Err(String)` - User-friendly error message if the audio could not be decoded
+#[tauri::command]
+pub async fn cmd_extract_waveform_peaks(media_id: String, samples_per_second: u32) -> Result<WaveformPeaks, String> {
+    tracing::debug!(event = "cmd_extract_waveform_peaks", media_id = %media_id, samples_per_second);
+
+    let media = IMPORTED_MEDIA
+        .lock()
+        .map_err(|e| format!("Media registry lock poisoned: {}", e))?
+        .get(&media_id)
+        .cloned()
+        .ok_or_else(|| format!("Media not found: {}. Please re-import the file.", media_id))?;
+
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| "Failed to resolve caches directory".to_string())?
+        .join("com.clippy.app")
+        .join("waveforms");
+
+    extract_waveform_peaks(&media, samples_per_second, &cache_dir).await.map_err(|e| {
+        tracing::error!(event = "cmd_extract_waveform_peaks_failed", media_id = %media_id, error = %e);
+        format!("Failed to extract waveform: {}", e)
+    })
+}
+
+/// Split a long recording into chapters at long silences
+///
+/// Silences of at least `min_silence_ms` become cuts; segments are written
+/// next to the source as `<name>-partNN.<ext>` with stream copy. Pieces
+/// shorter than `min_segment_ms` are merged into a neighbour.
+///
+/// # Arguments
+/// * `path` - Absolute path to the audio/video file
+/// * `min_silence_ms` - Shortest silence that splits the file
+/// * `min_segment_ms` - Shortest segment to produce
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Segment paths in order
+/// * `Err(String)` - User-friendly error message if detection or splitting failed
+#[tauri::command]
+pub async fn cmd_split_by_silence(
+    path: String,
+    min_silence_ms: u64,
+    min_segment_ms: u64,
+) -> Result<Vec<String>, String> {
+    tracing::info!(
+        event = "cmd_split_by_silence",
+        path = %path,
+        min_silence_ms,
+        min_segment_ms,
+        "Split by silence command invoked"
+    );
+
+    let source = std::path::Path::new(&path);
+    if !source.exists() {
+        let error_msg = format!("File not found: {}", path);
+        tracing::error!(event = "file_not_found", path = %path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+
+    let segments = split_by_silence(source, min_silence_ms, min_segment_ms)
+        .await
+        .map_err(|e| {
+            tracing::error!(event = "cmd_split_by_silence_failed", path = %path, error = %e);
+            format!("Failed to split recording: {}", e)
+        })?;
+
+    Ok(segments
+        .into_iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect())
+}
+
+/// Measure the audio/video start offset of a recording
+///
+/// Compares the `start_time` of the first audio and video streams as reported
+/// by ffprobe, to catch sync regressions in recordings.
+///
+/// # Arguments
+/// * `path` - Absolute path to the recording
+///
+/// # Returns
+/// * `Ok(AvSyncReport)` - Stream start times and their offset
+/// * `Err(String)` - User-friendly error message if the file can't be probed
+#[tauri::command]
+pub async fn cmd_measure_av_sync(path: String) -> Result<AvSyncReport, String> {
+    tracing::info!(event = "cmd_measure_av_sync", path = %path, "Measure A/V sync command invoked");
+
+    let recording_path = std::path::Path::new(&path);
+    if !recording_path.exists() {
+        let error_msg = format!("File not found: {}", path);
+        tracing::error!(event = "file_not_found", path = %path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+
+    let report = measure_av_sync(recording_path).await.map_err(|e| {
+        tracing::error!(event = "cmd_measure_av_sync_failed", path = %path, error = %e);
+        format!("Failed to measure A/V sync: {}", e)
+    })?;
+
+    if !report.in_sync {
+        tracing::warn!(event = "av_sync_offset", path = %path, offset_ms = report.offset_ms);
+    }
+
+    Ok(report)
+}
+
+/// List keyframe times of a media file so trim points can snap to them
+///
+/// Stream-copy trims only cut cleanly on keyframes. Results are cached per
+/// file, so calling this on every trim drag is cheap after the first time.
+///
+/// # Arguments
+/// * `file_path` - Absolute path to the media file
+///
+/// # Returns
+/// * `Ok(Vec<u64>)` - Sorted keyframe times in milliseconds
+/// * `Err(String)` - User-friendly error message if the file can't be probed
+#[tauri::command]
+pub async fn cmd_get_media_keyframes(file_path: String) -> Result<Vec<u64>, String> {
+    tracing::debug!(event = "cmd_get_media_keyframes", file_path = %file_path);
+
+    let path = std::path::Path::new(&file_path);
+    if !path.exists() {
+        let error_msg = format!("File not found: {}", file_path);
+        tracing::error!(event = "file_not_found", path = %file_path, error_msg = %error_msg);
+        return Err(error_msg);
+    }
+
+    let keyframes = get_keyframes(path).await.map_err(|e| {
+        tracing::error!(event = "cmd_get_media_keyframes_failed", path = %file_path, error = %e);
+        format!("Failed to list keyframes: {}", e)
+    })?;
+
+    Ok(keyframes.to_vec())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,3 +652,4 @@ mod tests {
         let _ = std::fs::remove_file(&test_file);
     }
 }
+
@@ -7,32 +7,59 @@ pub mod composition;
 pub mod timeline_render;
 pub mod ai_analysis;
 pub mod test_avfoundation_camera;
+pub mod logging;
+pub mod streaming;
 
-pub use media::cmd_import_media;
-pub use export::{ExportState, cmd_start_export, cmd_get_export_progress, cmd_cancel_export};
+pub use media::{cmd_import_media, cmd_import_edl, cmd_repair_recording, cmd_optimize_for_streaming, cmd_relink_media, cmd_get_clip_frame, cmd_extract_waveform, cmd_extract_waveform_peaks, cmd_probe_media, cmd_split_by_silence, cmd_measure_av_sync, cmd_generate_contact_sheet, cmd_get_media_keyframes, cmd_generate_thumbnail};
+pub use export::{
+    ExportState,
+    cmd_start_export,
+    cmd_get_export_progress,
+    cmd_cancel_export,
+    cmd_batch_export,
+    cmd_get_export_history,
+    cmd_reencode_recording,
+    cmd_quick_clip,
+    cmd_get_supported_export_formats,
+    cmd_list_export_presets,
+    cmd_check_audio_phase,
+};
 pub use mpv::{
     MpvPlayerState,
     mpv_init,
     mpv_load_file,
+    mpv_load_playlist,
+    mpv_playlist_next,
+    mpv_playlist_prev,
+    mpv_get_playlist_position,
     mpv_play,
     mpv_pause,
     mpv_seek,
+    mpv_step_frame,
+    mpv_seek_to_frame,
     mpv_get_time,
     mpv_get_duration,
     mpv_stop,
     mpv_is_playing,
     mpv_get_video_dimensions,
     mpv_capture_frame,
+    mpv_save_screenshot,
     mpv_set_volume,
     mpv_apply_fade_filters,
     mpv_clear_audio_filters,
+    mpv_set_speed,
+    mpv_get_speed,
 };
 pub use recording::{
     cmd_check_screen_recording_permission,
     cmd_request_screen_recording_permission,
     cmd_check_camera_permission,
     cmd_request_camera_permission,
+    cmd_check_all_permissions,
+    cmd_open_privacy_settings,
+    cmd_run_capture_selftest,
     cmd_list_cameras,
+    cmd_set_camera_torch,
     cmd_list_microphones,
     cmd_start_camera_preview,
     cmd_stop_camera_preview,
@@ -43,12 +70,16 @@ pub use recording::{
     cmd_stop_pip_recording,
     cmd_stop_recording,
     cmd_pause_recording,
+    cmd_set_audio_track_muted,
     cmd_resume_recording,
+    cmd_get_recording_status,
     cmd_cancel_recording,
+    cmd_get_live_waveform,
     cmd_check_disk_space,
     cmd_send_recording_notification,
     cmd_get_home_dir,
     cmd_get_available_windows,
+    cmd_get_available_displays,
 };
 pub use performance::{
     FpsCounterState,
@@ -62,6 +93,7 @@ pub use composition::{
     SegmentRendererState,
     cmd_render_segment,
     cmd_classify_segment_type,
+    cmd_get_safe_areas,
 };
 pub use timeline_render::{
     TimelineRendererState,
@@ -70,3 +102,5 @@ pub use timeline_render::{
 };
 pub use ai_analysis::cmd_analyze_recording;
 pub use test_avfoundation_camera::test_avfoundation_camera;
+pub use streaming::{cmd_start_stream, cmd_stop_stream};
+pub use logging::{LogLevelState, LogReloadHandle, LogBufferState, cmd_set_log_level, cmd_get_recent_logs};
@@ -1,3 +1,5 @@
+use crate::commands::performance::FpsCounterState;
+use crate::services::mpv_player::HdrToneMapping;
 use crate::services::MpvPlayer;
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
@@ -40,6 +42,14 @@ impl MpvResponse {
             data: None,
         }
     }
+
+    fn error_with_data(message: impl Into<String>, data: serde_json::Value) -> Self {
+        Self {
+            success: false,
+            message: message.into(),
+            data: Some(data),
+        }
+    }
 }
 
 /// Initialize MPV player
@@ -83,6 +93,104 @@ pub fn mpv_load_file(file_path: String, state: State<MpvPlayerState>) -> MpvResp
     }
 }
 
+/// Replace the playlist with `paths` and load the first one
+#[tauri::command]
+pub fn mpv_load_playlist(paths: Vec<String>, state: State<MpvPlayerState>) -> MpvResponse {
+    info!("[Command] mpv_load_playlist called with {} files", paths.len());
+
+    let player = state.0.lock().unwrap();
+
+    match player.as_ref() {
+        Some(mpv) => match mpv.load_playlist(&paths) {
+            Ok(_) => MpvResponse::success_with_data(
+                "Playlist loaded successfully",
+                serde_json::json!({ "position": 0, "count": paths.len() }),
+            ),
+            Err(e) => {
+                error!("[Command] Failed to load playlist: {}", e);
+                MpvResponse::error(format!("Failed to load playlist: {}", e))
+            }
+        },
+        None => {
+            error!("[Command] MPV player not initialized");
+            MpvResponse::error("MPV player not initialized")
+        }
+    }
+}
+
+/// Switch to the next playlist item (no-op at the last item); returns the new position
+#[tauri::command]
+pub fn mpv_playlist_next(state: State<MpvPlayerState>) -> MpvResponse {
+    info!("[Command] mpv_playlist_next called");
+
+    let player = state.0.lock().unwrap();
+
+    match player.as_ref() {
+        Some(mpv) => match mpv.playlist_next() {
+            Ok(position) => MpvResponse::success_with_data(
+                "Playlist position updated",
+                serde_json::json!({ "position": position }),
+            ),
+            Err(e) => {
+                error!("[Command] Failed to switch to next playlist item: {}", e);
+                MpvResponse::error(format!("Failed to switch to next playlist item: {}", e))
+            }
+        },
+        None => {
+            error!("[Command] MPV player not initialized");
+            MpvResponse::error("MPV player not initialized")
+        }
+    }
+}
+
+/// Switch to the previous playlist item (no-op at the first item); returns the new position
+#[tauri::command]
+pub fn mpv_playlist_prev(state: State<MpvPlayerState>) -> MpvResponse {
+    info!("[Command] mpv_playlist_prev called");
+
+    let player = state.0.lock().unwrap();
+
+    match player.as_ref() {
+        Some(mpv) => match mpv.playlist_prev() {
+            Ok(position) => MpvResponse::success_with_data(
+                "Playlist position updated",
+                serde_json::json!({ "position": position }),
+            ),
+            Err(e) => {
+                error!("[Command] Failed to switch to previous playlist item: {}", e);
+                MpvResponse::error(format!("Failed to switch to previous playlist item: {}", e))
+            }
+        },
+        None => {
+            error!("[Command] MPV player not initialized");
+            MpvResponse::error("MPV player not initialized")
+        }
+    }
+}
+
+/// Get the current playlist position and number of items
+#[tauri::command]
+pub fn mpv_get_playlist_position(state: State<MpvPlayerState>) -> MpvResponse {
+    let player = state.0.lock().unwrap();
+
+    match player.as_ref() {
+        Some(mpv) => match (mpv.get_playlist_position(), mpv.get_playlist_count()) {
+            (Ok(position), Ok(count)) => MpvResponse::success_with_data(
+                "Playlist position retrieved",
+                serde_json::json!({ "position": position, "count": count }),
+            ),
+            (Err(e), _) | (_, Err(e)) => {
+                error!("[Command] Failed to get playlist position: {}", e);
+                MpvResponse::error(format!("Failed to get playlist position: {}", e))
+            }
+        },
+        None => {
+            error!("[Command] MPV player not initialized");
+            MpvResponse::error("MPV player not initialized")
+        }
+    }
+}
+
 /// Play the loaded video
 #[tauri::command]
 pub fn mpv_play(state: State<MpvPlayerState>) -> MpvResponse {
@@ -149,6 +257,56 @@ pub fn mpv_seek(time_seconds: f64, state: State<MpvPlayerState>) -> MpvResponse
     }
 }
 
+/// Step exactly one frame forward or back; returns the new time (in seconds)
+#[tauri::command]
+pub fn mpv_step_frame(forward: bool, state: State<MpvPlayerState>) -> MpvResponse {
+    info!("[Command] mpv_step_frame called (forward: {})", forward);
+
+    let player = state.0.lock().unwrap();
+
+    match player.as_ref() {
+        Some(mpv) => match mpv.step_frame(forward) {
+            Ok(time) => MpvResponse::success_with_data(
+                "Frame step completed",
+                serde_json::json!({ "time": time }),
+            ),
+            Err(e) => {
+                error!("[Command] Failed to step frame: {}", e);
+                MpvResponse::error(format!("Failed to step frame: {}", e))
+            }
+        },
+        None => {
+            error!("[Command] MPV player not initialized");
+            MpvResponse::error("MPV player not initialized")
+        }
+    }
+}
+
+/// Seek exactly to a frame number at the given frame rate; returns the resulting time (in seconds)
+#[tauri::command]
+pub fn mpv_seek_to_frame(frame_number: u64, fps: f64, state: State<MpvPlayerState>) -> MpvResponse {
+    info!("[Command] mpv_seek_to_frame called with frame: {} at {} fps", frame_number, fps);
+
+    let player = state.0.lock().unwrap();
+
+    match player.as_ref() {
+        Some(mpv) => match mpv.seek_to_frame(frame_number, fps) {
+            Ok(time) => MpvResponse::success_with_data(
+                "Frame seek completed",
+                serde_json::json!({ "time": time }),
+            ),
+            Err(e) => {
+                error!("[Command] Failed to seek to frame: {}", e);
+                MpvResponse::error(format!("Failed to seek to frame: {}", e))
+            }
+        },
+        None => {
+            error!("[Command] MPV player not initialized");
+            MpvResponse::error("MPV player not initialized")
+        }
+    }
+}
+
 /// Get current playback time (in seconds)
 #[tauri::command]
 pub fn mpv_get_time(state: State<MpvPlayerState>) -> MpvResponse {
@@ -172,7 +330,7 @@ pub fn mpv_get_time(state: State<MpvPlayerState>) -> MpvResponse {
     }
 }
 
-/// Get video duration (in seconds)
+/// Get video duration (in seconds) of the current playlist item
 #[tauri::command]
 pub fn mpv_get_duration(state: State<MpvPlayerState>) -> MpvResponse {
     let player = state.0.lock().unwrap();
@@ -266,12 +424,14 @@ pub fn mpv_get_video_dimensions(state: State<MpvPlayerState>) -> MpvResponse {
 }
 
 /// Capture current video frame as JPEG image
+///
+/// `tone_map` controls HDR handling (defaults to `auto`: tone-map HDR sources only).
 #[tauri::command]
-pub fn mpv_capture_frame(state: State<MpvPlayerState>) -> MpvResponse {
+pub fn mpv_capture_frame(tone_map: Option<HdrToneMapping>, state: State<MpvPlayerState>) -> MpvResponse {
     let player = state.0.lock().unwrap();
 
     match player.as_ref() {
-        Some(mpv) => match mpv.capture_frame() {
+        Some(mpv) => match mpv.capture_frame_with_tone_mapping(tone_map.unwrap_or_default()) {
             Ok(image_data) => {
                 // Encode as base64 for transmission
                 use base64::{engine::general_purpose, Engine as _};
@@ -294,6 +454,34 @@ pub fn mpv_capture_frame(state: State<MpvPlayerState>) -> MpvResponse {
     }
 }
 
+/// Save the current frame as a full-resolution PNG
+///
+/// Returns the written path in `data.path` (always with a `.png` extension).
+/// Failures carry `data.code`: `no_file_loaded`, `missing_directory` or `write_failed`.
+#[tauri::command]
+pub fn mpv_save_screenshot(output_path: String, state: State<MpvPlayerState>) -> MpvResponse {
+    info!("[Command] mpv_save_screenshot called with path: {}", output_path);
+
+    let player = state.0.lock().unwrap();
+
+    match player.as_ref() {
+        Some(mpv) => match mpv.save_screenshot(std::path::Path::new(&output_path)) {
+            Ok(path) => MpvResponse::success_with_data(
+                "Screenshot saved",
+                serde_json::json!({ "path": path.to_string_lossy() }),
+            ),
+            Err(e) => {
+                error!("[Command] Failed to save screenshot: {}", e);
+                MpvResponse::error_with_data(e.to_string(), serde_json::json!({ "code": e.code() }))
+            }
+        },
+        None => {
+            error!("[Command] MPV player not initialized");
+            MpvResponse::error("MPV player not initialized")
+        }
+    }
+}
+
 /// Set volume for current playback (Story 3.9.1/3.10.1)
 #[tauri::command]
 pub fn mpv_set_volume(volume: f32, muted: bool, state: State<MpvPlayerState>) -> MpvResponse {
@@ -346,6 +534,69 @@ pub fn mpv_apply_fade_filters(
     }
 }
 
+/// Set the playback speed multiplier (0.25x to 4x)
+///
+/// The FPS counter is reset on a change so `get_playback_fps` doesn't average
+/// frames rendered at the old speed with the new one.
+#[tauri::command]
+pub fn mpv_set_speed(
+    speed: f64,
+    state: State<MpvPlayerState>,
+    fps_state: State<FpsCounterState>,
+) -> MpvResponse {
+    info!("[Command] mpv_set_speed called with speed: {}", speed);
+
+    let player = state.0.lock().unwrap();
+
+    match player.as_ref() {
+        Some(mpv) => {
+            let previous = mpv.get_speed().ok();
+            match mpv.set_speed(speed) {
+                Ok(_) => {
+                    if previous != Some(speed) {
+                        fps_state.0.lock().unwrap().reset();
+                    }
+                    MpvResponse::success_with_data(
+                        "Playback speed set successfully",
+                        serde_json::json!({ "speed": speed }),
+                    )
+                }
+                Err(e) => {
+                    error!("[Command] Failed to set playback speed: {}", e);
+                    MpvResponse::error(format!("Failed to set playback speed: {}", e))
+                }
+            }
+        }
+        None => {
+            error!("[Command] MPV player not initialized");
+            MpvResponse::error("MPV player not initialized")
+        }
+    }
+}
+
+/// Get the playback speed multiplier
+#[tauri::command]
+pub fn mpv_get_speed(state: State<MpvPlayerState>) -> MpvResponse {
+    let player = state.0.lock().unwrap();
+
+    match player.as_ref() {
+        Some(mpv) => match mpv.get_speed() {
+            Ok(speed) => MpvResponse::success_with_data(
+                "Playback speed retrieved",
+                serde_json::json!({ "speed": speed }),
+            ),
+            Err(e) => {
+                error!("[Command] Failed to get playback speed: {}", e);
+                MpvResponse::error(format!("Failed to get playback speed: {}", e))
+            }
+        },
+        None => {
+            error!("[Command] MPV player not initialized");
+            MpvResponse::error("MPV player not initialized")
+        }
+    }
+}
+
 /// Clear all audio filters (Story 3.10.1)
 #[tauri::command]
 pub fn mpv_clear_audio_filters(state: State<MpvPlayerState>) -> MpvResponse {
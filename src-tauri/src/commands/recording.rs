@@ -2,15 +2,38 @@
 //!
 //! This module provides Tauri commands for screen recording, camera, and audio capture operations.
 
-use crate::models::recording::RecordingConfig;
+use crate::models::recording::{RecordingConfig, RecordingState, RecordingStatus};
 use crate::services::permissions::{
     check_camera_permission, check_screen_recording_permission, request_camera_permission,
-    request_screen_recording_permission, check_microphone_permission,
+    request_screen_recording_permission, check_microphone_permission, check_all_permissions,
+    open_privacy_settings, PermissionKind, PermissionStatus,
+};
+use crate::services::camera::{CameraBackend, CameraError, CameraInfo, CameraService};
+use crate::services::camera::avfoundation_camera::{set_camera_lighting, CameraLightingPlan};
+use crate::services::ffmpeg::{
+    build_pip_composition_args, build_styled_pip_filter, raw_dump_max_bytes, raw_dump_path,
+    run_pip_composition, scale_to_height, verify_recording, CompositionProgress, FFmpegEncoder, IntegrityStatus, PipAudioInputs, PipConfig, PipEncoding, PipShape,
+    RawDumpSink, RawDumpWriter, TimestampedFrame, PIP_SYSTEM_AUDIO_CHANNELS,
+    PIP_SYSTEM_AUDIO_SAMPLE_RATE,
+};
+use crate::services::screen_capture::{
+    display_infos, CaptureAbort, CaptureAbortReason, DisplayInfo, FrameHandler, MultiDisplayCapture, ScreenCapture, ScreenCaptureError,
+};
+use crate::services::screen_capture::multi_display::list_display_bounds;
+use crate::services::audio_capture::{remove_pcm_file, AudioCapture, AudioCaptureGuard, AudioSample};
+use crate::services::capture_selftest::{run_capture_selftest, SelfTestReport};
+use crate::services::recording::PausableClock;
+use crate::utils::ffmpeg::ensure_ffmpeg_ready;
+use crate::utils::disk_space::{
+    available_bytes, ensure_free_space, estimate_bytes_per_minute, required_free_bytes, InsufficientDiskSpace,
+};
+use crate::utils::output_dir::{prepare_output_dir, prepare_output_path, OutputDirError};
+use crate::utils::scratch::{move_into_output, prepare_scratch_dir, scratch_file};
+use crate::utils::wav::WavWriter;
+use crate::services::live_waveform::{
+    get_live_waveform, register_live_waveform, remove_live_waveform, LiveAudioSource,
+    LiveWaveformSnapshot,
 };
-use crate::services::camera::{CameraBackend, CameraInfo, CameraService};
-use crate::services::ffmpeg::{FFmpegEncoder, PipConfig, TimestampedFrame};
-use crate::services::screen_capture::{FrameHandler, ScreenCapture};
-use crate::services::audio_capture::{AudioCapture, AudioSample};
 use anyhow::Result as AnyhowResult;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -22,6 +45,134 @@ use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// Why a recording could not be started
+///
+/// Serialized as `{ code, message }` so the frontend can branch on `code`;
+/// `insufficient_disk_space` adds `path`, `availableBytes` and `requiredBytes`;
+/// `output_dir` adds `path` and the `kind` of [`OutputDirError`].
+#[derive(Debug, thiserror::Error)]
+pub enum RecordingStartError {
+    /// The output or scratch volume is below the free space floor
+    #[error(transparent)]
+    InsufficientDiskSpace(#[from] InsufficientDiskSpace),
+
+    /// The output or scratch folder could not be created or written to
+    #[error(transparent)]
+    OutputDir(#[from] OutputDirError),
+
+    /// Anything else, with a user-friendly message
+    #[error("{0}")]
+    Failed(String),
+}
+
+impl RecordingStartError {
+    /// Stable identifier for the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            RecordingStartError::InsufficientDiskSpace(_) => "insufficient_disk_space",
+            RecordingStartError::OutputDir(_) => "output_dir",
+            RecordingStartError::Failed(_) => "failed",
+        }
+    }
+}
+
+impl From<String> for RecordingStartError {
+    fn from(message: String) -> Self {
+        RecordingStartError::Failed(message)
+    }
+}
+
+impl serde::Serialize for RecordingStartError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut error = serializer.serialize_struct("RecordingStartError", 6)?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        match self {
+            RecordingStartError::InsufficientDiskSpace(e) => {
+                error.serialize_field("path", &e.path)?;
+                error.serialize_field("availableBytes", &e.available_bytes)?;
+                error.serialize_field("requiredBytes", &e.required_bytes)?;
+                error.skip_field("kind")?;
+            }
+            RecordingStartError::OutputDir(e) => {
+                error.serialize_field("path", e.path())?;
+                error.skip_field("availableBytes")?;
+                error.skip_field("requiredBytes")?;
+                error.serialize_field("kind", e.kind())?;
+            }
+            RecordingStartError::Failed(_) => {
+                error.skip_field("path")?;
+                error.skip_field("availableBytes")?;
+                error.skip_field("requiredBytes")?;
+                error.skip_field("kind")?;
+            }
+        }
+        error.end()
+    }
+}
+
+/// Why stopping a screen recording failed
+///
+/// Serialized as `{ code, message }` like [`RecordingStartError`]; a capture
+/// abort is reported with the abort's code (e.g. `pixel_format_incompatible`)
+/// and adds `suggestion`, plus `outputPath` when the frames captured before the
+/// abort were saved.
+#[derive(Debug, thiserror::Error)]
+pub enum RecordingStopError {
+    /// Capture stopped on its own; what was captured has been finalized if possible
+    #[error("{}", ScreenCaptureError::from(*.reason))]
+    CaptureAborted {
+        reason: CaptureAbortReason,
+        output_path: Option<String>,
+    },
+
+    /// Anything else, with a user-friendly message
+    #[error("{0}")]
+    Failed(String),
+}
+
+impl RecordingStopError {
+    /// Stable identifier for the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            RecordingStopError::CaptureAborted { reason, .. } => reason.code(),
+            RecordingStopError::Failed(_) => "failed",
+        }
+    }
+}
+
+impl From<String> for RecordingStopError {
+    fn from(message: String) -> Self {
+        RecordingStopError::Failed(message)
+    }
+}
+
+impl serde::Serialize for RecordingStopError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut error = serializer.serialize_struct("RecordingStopError", 4)?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        match self {
+            RecordingStopError::CaptureAborted { reason, output_path } => {
+                error.serialize_field("suggestion", reason.suggestion())?;
+                match output_path {
+                    Some(path) => error.serialize_field("outputPath", path)?,
+                    None => error.skip_field("outputPath")?,
+                }
+            }
+            RecordingStopError::Failed(_) => {
+                error.skip_field("suggestion")?;
+                error.skip_field("outputPath")?;
+            }
+        }
+        error.end()
+    }
+}
+
 /// Global state for managing active recordings
 ///
 /// Maps recording ID to capture task handle, frame writer handle, output path, and pause flag
@@ -37,6 +188,8 @@ type RecordingHandle = (
     Option<PathBuf>,                       // Microphone audio PCM file path
     Option<Arc<AtomicBool>>,               // Microphone pause flag (Story 4.8)
     Option<u16>,                           // Microphone channel count (1=mono, 2=stereo)
+    CaptureAbort,                          // Why capture stopped on its own, if it did
+    bool,                                  // Normalize loudness (EBU R128) when muxing
 );
 
 lazy_static::lazy_static! {
@@ -44,9 +197,10 @@ lazy_static::lazy_static! {
         Arc::new(Mutex::new(HashMap::new()));
 }
 
-// Storage for microphone audio channel senders (needed to close channels on stop)
+// Microphone captures of screen and PiP recordings; dropping one stops the
+// stream and closes its sample channel, which lets the writer task finish
 lazy_static::lazy_static! {
-    static ref MICROPHONE_SENDERS: Arc<tokio::sync::Mutex<HashMap<String, tokio::sync::mpsc::Sender<AudioSample>>>> =
+    static ref MICROPHONE_CAPTURES: Arc<tokio::sync::Mutex<HashMap<String, AudioCaptureGuard>>> =
         Arc::new(tokio::sync::Mutex::new(HashMap::new()));
 }
 
@@ -80,7 +234,7 @@ struct CameraFramePayload {
 type WebcamRecordingHandle = (
     CameraBackend,                               // Camera backend instance (for graceful stop)
     tokio::task::JoinHandle<()>,                 // Camera capture task
-    Option<tokio::task::JoinHandle<()>>,         // Audio capture task (optional)
+    Option<AudioWriterTask>,                     // Audio capture task (optional)
     tokio::task::JoinHandle<AnyhowResult<()>>,   // Encoding task
     PathBuf,                                      // Video output file path
     Option<PathBuf>,                              // Audio output file path (optional)
@@ -105,7 +259,10 @@ type PipRecordingHandle = (
     PathBuf,                                      // Temp screen file path
     PathBuf,                                      // Temp webcam file path
     Option<PathBuf>,                              // Microphone audio WAV path
+    Option<tokio::task::JoinHandle<Result<(), String>>>, // System audio writer task
+    Option<PathBuf>,                              // System audio PCM path
     PipConfig,                                    // PiP configuration for composition
+    PipEncoding,                                  // Encoder settings for composition
     Arc<AtomicBool>,                             // Screen pause flag
     Arc<AtomicBool>,                             // Screen stop signal
     CameraBackend,                                // Camera capture object (must stay alive to prevent Drop)
@@ -116,6 +273,214 @@ lazy_static::lazy_static! {
         Arc::new(Mutex::new(HashMap::new()));
 }
 
+// Elapsed-time clocks of active recordings (all kinds), paused with the recording
+lazy_static::lazy_static! {
+    static ref RECORDING_CLOCKS: Arc<Mutex<HashMap<String, Arc<PausableClock>>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+// Cancel flags of screen recordings still counting down (set by `cmd_cancel_recording`)
+lazy_static::lazy_static! {
+    static ref COUNTDOWN_CANCEL_FLAGS: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+// Per-track mute flags of active screen recordings (set by `cmd_set_audio_track_muted`)
+lazy_static::lazy_static! {
+    static ref AUDIO_TRACK_MUTES: Arc<Mutex<HashMap<String, AudioTrackMutes>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+/// Audio track of a screen recording that can be muted on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MutableAudioTrack {
+    System,
+    Microphone,
+}
+
+/// Mute flags of a screen recording's audio tracks
+///
+/// Separate from the pause flags: pausing drops samples (and time) from every
+/// track, while a muted track keeps recording silence so it stays in sync.
+#[derive(Debug, Clone, Default)]
+struct AudioTrackMutes {
+    system: Arc<AtomicBool>,
+    microphone: Arc<AtomicBool>,
+}
+
+impl AudioTrackMutes {
+    fn flag(&self, track: MutableAudioTrack) -> &Arc<AtomicBool> {
+        match track {
+            MutableAudioTrack::System => &self.system,
+            MutableAudioTrack::Microphone => &self.microphone,
+        }
+    }
+}
+
+/// Replace a muted track's samples with silence of the same length
+fn silence_if_muted(sample: &mut AudioSample, muted: &AtomicBool) {
+    if muted.load(std::sync::atomic::Ordering::Relaxed) {
+        sample.data.fill(0.0);
+    }
+}
+
+// Free space monitors of active recordings, aborted when the recording stops
+lazy_static::lazy_static! {
+    static ref LOW_DISK_MONITORS: Arc<Mutex<HashMap<String, tokio::task::JoinHandle<()>>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+/// How often a start countdown checks its cancel flag
+const COUNTDOWN_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// How often an idle audio writer checks its stop flag
+const AUDIO_WRITER_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// How long a stopping audio writer may take to drain and finalize before it is abandoned
+const AUDIO_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Blocking audio writer task that finalizes its file when asked to stop
+struct AudioWriterTask {
+    handle: tokio::task::JoinHandle<()>,
+    stop: Arc<AtomicBool>,
+}
+
+impl AudioWriterTask {
+    /// Ask the writer to stop its source, then wait (up to `timeout`) for it to
+    /// write the samples still queued and finalize the file
+    ///
+    /// Like `FFmpegEncoder::stop_encoding`, a writer that doesn't finish in time
+    /// is given up on: it is aborted, and its file may be truncated.
+    ///
+    /// # Returns
+    /// `true` if the writer finished, `false` if it timed out
+    async fn stop_and_drain(mut self, timeout: std::time::Duration) -> bool {
+        self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+
+        match tokio::time::timeout(timeout, &mut self.handle).await {
+            Ok(Ok(())) => true,
+            Ok(Err(e)) => {
+                warn!("Audio writer task join error: {}", e);
+                true
+            }
+            Err(_) => {
+                warn!("Audio writer did not finish within {:?}, aborting", timeout);
+                self.handle.abort();
+                false
+            }
+        }
+    }
+}
+
+/// Write microphone samples to a WAV file until the sample channel closes
+///
+/// The WAV is created on the first sample (so it uses the device's actual
+/// rate and channel count). Once `stop` is set, `stop_source` is called to stop
+/// the capture, which closes the channel; samples already queued are still
+/// written before the WAV header is finalized.
+///
+/// # Returns
+/// Number of samples written
+fn write_mic_samples_to_wav(
+    audio_rx: &mut mpsc::Receiver<AudioSample>,
+    stop: &AtomicBool,
+    mut stop_source: impl FnMut(),
+    wav_path: &std::path::Path,
+    mic_gain: f32,
+) -> usize {
+    use tokio::sync::mpsc::error::TryRecvError;
+
+    let mut sample_count = 0;
+    let mut writer_opt: Option<WavWriter> = None;
+    let mut source_stopped = false;
+
+    loop {
+        if !source_stopped && stop.load(std::sync::atomic::Ordering::Relaxed) {
+            debug!("Audio stop requested, draining queued samples");
+            stop_source();
+            source_stopped = true;
+        }
+
+        let sample = match audio_rx.try_recv() {
+            Ok(sample) => sample,
+            Err(TryRecvError::Empty) => {
+                std::thread::sleep(AUDIO_WRITER_POLL_INTERVAL);
+                continue;
+            }
+            Err(TryRecvError::Disconnected) => break,
+        };
+
+        // Create WAV writer after receiving first sample (so we know the actual sample rate)
+        if writer_opt.is_none() {
+            info!("First audio sample received: {} Hz, {} channels",
+                sample.sample_rate, sample.channels);
+
+            writer_opt = match WavWriter::new(wav_path.to_path_buf(), sample.sample_rate, sample.channels) {
+                Ok(w) => {
+                    info!("WAV writer created with {} Hz, {} channels",
+                        sample.sample_rate, sample.channels);
+                    Some(w)
+                },
+                Err(e) => {
+                    error!("Failed to create WAV writer: {}", e);
+                    return 0;
+                }
+            };
+        }
+
+        if let Some(ref mut writer) = writer_opt {
+            let boosted_samples = apply_mic_gain(&sample.data, mic_gain);
+
+            if let Err(e) = writer.write_samples(&boosted_samples) {
+                error!("Failed to write audio samples: {}", e);
+                break;
+            }
+            sample_count += sample.data.len();
+        }
+    }
+
+    info!("Audio channel closed, finalizing WAV file ({} samples)", sample_count);
+    if let Some(writer) = writer_opt {
+        if let Err(e) = writer.finalize() {
+            error!("Failed to finalize WAV file: {}", e);
+        } else {
+            info!("Audio file finalized successfully");
+        }
+    } else {
+        warn!("No audio samples received, WAV file not created");
+    }
+
+    sample_count
+}
+
+/// Folder finished recordings are saved to: ~/Documents/clippy/recordings
+fn recordings_output_dir() -> Result<PathBuf, String> {
+    let home_dir = dirs::home_dir().ok_or_else(|| {
+        error!("Could not determine home directory");
+        "Could not determine home directory".to_string()
+    })?;
+
+    Ok(home_dir.join("Documents").join("clippy").join("recordings"))
+}
+
+/// Where a finished screen recording is saved (`recording-{id}.mp4` in the output folder)
+fn screen_recording_output_path(recording_id: &str) -> Result<PathBuf, String> {
+    Ok(recordings_output_dir()?.join(format!("recording-{}.mp4", recording_id)))
+}
+
+/// Where a finished webcam recording is saved (`webcam-{id}.mp4` in the output folder)
+fn webcam_recording_output_path(recording_id: &str) -> Result<PathBuf, String> {
+    Ok(recordings_output_dir()?.join(format!("webcam-{}.mp4", recording_id)))
+}
+
+/// Move a finished intermediate from scratch into the output folder
+fn promote_to_output(intermediate: &std::path::Path, output: &std::path::Path) -> Result<(), String> {
+    move_into_output(intermediate, output).map_err(|e| {
+        error!("Failed to move {} to {}: {}", intermediate.display(), output.display(), e);
+        format!("Failed to save recording to {}: {}", output.display(), e)
+    })
+}
 
 /// Check if the app has screen recording permission
 ///
@@ -233,6 +598,58 @@ pub async fn cmd_request_camera_permission() -> Result<(), String> {
     }
 }
 
+/// Check screen recording, camera, and microphone permissions in one call
+///
+/// Unlike the individual check commands, each status is tri-state
+/// (granted / denied / notDetermined) so the UI can tell whether to prompt
+/// or send the user to System Settings.
+///
+/// # Returns
+///
+/// - `Ok(PermissionStatus)` with the status of each permission
+#[tauri::command]
+pub async fn cmd_check_all_permissions() -> Result<PermissionStatus, String> {
+    debug!("Command: check all permissions");
+
+    let status = check_all_permissions();
+    info!(
+        "Permission status: screen={:?}, camera={:?}, microphone={:?}",
+        status.screen_recording, status.camera, status.microphone
+    );
+
+    Ok(status)
+}
+
+/// Open the System Settings privacy pane for a permission
+///
+/// Deep-links to the exact pane (Screen Recording, Camera, or Microphone) so the user
+/// can grant access after a denial. Unknown kinds open the general Privacy pane.
+///
+/// # Arguments
+///
+/// * `kind` - "screenRecording", "camera", or "microphone"
+#[tauri::command]
+pub async fn cmd_open_privacy_settings(kind: PermissionKind) -> Result<(), String> {
+    debug!("Command: open privacy settings for {:?}", kind);
+
+    open_privacy_settings(kind).map_err(|e| {
+        error!("Failed to open privacy settings: {}", e);
+        e.to_string()
+    })
+}
+
+/// Run the capture self-test
+///
+/// Exercises display enumeration, a single screen frame, camera enumeration,
+/// a single camera frame, and one second of microphone audio. Each stage is
+/// reported independently with pass/fail and timing, so one failure doesn't
+/// hide the others.
+#[tauri::command]
+pub async fn cmd_run_capture_selftest() -> Result<SelfTestReport, String> {
+    debug!("Command: run capture self-test");
+    Ok(run_capture_selftest().await)
+}
+
 /// List all available cameras
 ///
 /// This command enumerates all available cameras on the system.
@@ -280,6 +697,41 @@ pub async fn cmd_list_cameras() -> Result<Vec<CameraInfo>, String> {
     }
 }
 
+/// Set the torch and low-light boost of a camera
+///
+/// For external cameras with controllable illumination. Cameras that lack a
+/// torch or low-light boost are left unchanged; the skipped settings are
+/// listed in `unsupported` rather than returned as an error.
+///
+/// # Arguments
+///
+/// * `camera_index` - The index of the camera (from `cmd_list_cameras`)
+/// * `on` - Turn the torch on or off
+/// * `level` - Torch brightness, greater than 0.0 and at most 1.0 (ignored when off)
+/// * `low_light_boost` - Enable or disable automatic low-light boost (unchanged if omitted)
+///
+/// # Returns
+///
+/// - `Ok(CameraLightingPlan)` with the applied settings and any unsupported ones
+/// - `Err(String)` if the level is out of range or the camera can't be configured
+#[tauri::command]
+pub async fn cmd_set_camera_torch(
+    camera_index: u32,
+    on: bool,
+    level: f32,
+    low_light_boost: Option<bool>,
+) -> Result<CameraLightingPlan, String> {
+    debug!(
+        "Command: set camera torch (camera {}, on: {}, level: {}, low-light boost: {:?})",
+        camera_index, on, level, low_light_boost
+    );
+
+    set_camera_lighting(camera_index, on, level, low_light_boost).map_err(|e| {
+        error!("Failed to set camera torch: {}", e);
+        e.to_string()
+    })
+}
+
 /// Start camera preview
 ///
 /// This command starts a camera preview stream for the given camera index.
@@ -390,15 +842,8 @@ pub async fn cmd_start_camera_preview(
                 }
             }
 
-            // Convert BGRA to RGB for frontend (simple conversion, can be optimized)
-            let mut rgb_data = Vec::with_capacity((frame.width * frame.height * 3) as usize);
-            for chunk in frame.data.chunks(4) {
-                if chunk.len() == 4 {
-                    rgb_data.push(chunk[2]); // R (from B in BGRA)
-                    rgb_data.push(chunk[1]); // G
-                    rgb_data.push(chunk[0]); // B (from R in BGRA)
-                }
-            }
+            // Convert BGRA to RGB for frontend
+            let rgb_data = crate::utils::color::bgra_to_rgb(&frame.data);
 
             // Base64 encode the RGB frame data
             let encoded = base64::engine::general_purpose::STANDARD.encode(&rgb_data);
@@ -489,93 +934,6 @@ pub async fn cmd_stop_camera_preview(camera_index: u32) -> Result<(), String> {
     Ok(())
 }
 
-/// Simple WAV file writer for audio samples
-///
-/// Writes audio samples to a WAV file incrementally during recording.
-/// The WAV header is written at the start with placeholder values,
-/// then updated with actual sizes when finalized.
-struct WavWriter {
-    file: std::fs::File,
-    sample_rate: u32,
-    channels: u16,
-    samples_written: u64,
-}
-
-impl WavWriter {
-    /// Create new WAV file and write initial header
-    fn new(path: PathBuf, sample_rate: u32, channels: u16) -> std::io::Result<Self> {
-        let mut file = std::fs::File::create(path)?;
-
-        // Write WAV header with placeholder sizes (will update later)
-        Self::write_wav_header(&mut file, sample_rate, channels, 0)?;
-
-        Ok(Self {
-            file,
-            sample_rate,
-            channels,
-            samples_written: 0,
-        })
-    }
-
-    /// Write audio samples to file
-    fn write_samples(&mut self, samples: &[f32]) -> std::io::Result<()> {
-        use std::io::Write;
-
-        // Convert f32 samples to i16 PCM
-        for sample in samples {
-            let pcm_sample = (*sample * 32767.0).clamp(-32768.0, 32767.0) as i16;
-            self.file.write_all(&pcm_sample.to_le_bytes())?;
-        }
-
-        self.samples_written += samples.len() as u64;
-        Ok(())
-    }
-
-    /// Finalize WAV file by updating header with actual sizes
-    fn finalize(mut self) -> std::io::Result<()> {
-        use std::io::{Seek, SeekFrom, Write};
-
-        // Seek back to start and write correct header
-        self.file.seek(SeekFrom::Start(0))?;
-        Self::write_wav_header(&mut self.file, self.sample_rate, self.channels, self.samples_written)?;
-        self.file.flush()?;
-
-        Ok(())
-    }
-
-    /// Write WAV file header
-    fn write_wav_header(file: &mut std::fs::File, sample_rate: u32, channels: u16, total_samples: u64) -> std::io::Result<()> {
-        use std::io::Write;
-
-        let bytes_per_sample = 2u16; // i16 PCM
-        let byte_rate = sample_rate * channels as u32 * bytes_per_sample as u32;
-        let block_align = channels * bytes_per_sample;
-        let data_size = (total_samples * bytes_per_sample as u64) as u32;
-        let file_size = 36 + data_size;
-
-        // RIFF header
-        file.write_all(b"RIFF")?;
-        file.write_all(&file_size.to_le_bytes())?;
-        file.write_all(b"WAVE")?;
-
-        // fmt chunk
-        file.write_all(b"fmt ")?;
-        file.write_all(&16u32.to_le_bytes())?; // chunk size
-        file.write_all(&1u16.to_le_bytes())?;  // PCM format
-        file.write_all(&channels.to_le_bytes())?;
-        file.write_all(&sample_rate.to_le_bytes())?;
-        file.write_all(&byte_rate.to_le_bytes())?;
-        file.write_all(&block_align.to_le_bytes())?;
-        file.write_all(&(bytes_per_sample * 8).to_le_bytes())?; // bits per sample
-
-        // data chunk
-        file.write_all(b"data")?;
-        file.write_all(&data_size.to_le_bytes())?;
-
-        Ok(())
-    }
-}
-
 /// Start webcam recording with microphone audio
 ///
 /// This command starts webcam recording with microphone audio capture at the camera's
@@ -584,12 +942,16 @@ impl WavWriter {
 /// # Arguments
 ///
 /// * `camera_index` - The index of the camera to record from (from `cmd_list_cameras`)
-/// * `enable_microphone` - Whether to capture microphone audio along with video
+/// * `enable_microphone` - Whether to capture audio along with video. When false,
+///   no audio device is opened, no WAV is written and the video is saved as-is
+/// * `microphone_device` - Audio input to record (from `cmd_list_microphones`);
+///   defaults to the system default input
+/// * `mic_gain` - Linear gain applied to microphone samples, 0.0 to 10.0 (defaults to 1.0)
 ///
 /// # Returns
 ///
 /// - `Ok(String)` with recording ID on success
-/// - `Err(String)` with user-friendly error message if recording fails
+/// - `Err(RecordingStartError)` - `{ code, message }` if recording fails to start
 ///
 /// # Implementation
 ///
@@ -604,12 +966,20 @@ impl WavWriter {
 pub async fn cmd_start_webcam_recording(
     camera_index: u32,
     enable_microphone: bool,
-) -> Result<String, String> {
+    microphone_device: Option<String>,
+    mic_gain: Option<f32>,
+    min_free_space_mb: Option<u64>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, RecordingStartError> {
     debug!(
         "Command: start webcam recording (camera: {}, mic: {})",
         camera_index, enable_microphone
     );
 
+    ensure_ffmpeg_ready()?;
+    let mic_gain = resolve_mic_gain(mic_gain)?;
+    let min_free_space_mb = min_free_space_mb.unwrap_or(RecordingConfig::default().min_free_space_mb);
+
     // Check camera permission first
     match check_camera_permission() {
         Ok(true) => {
@@ -617,11 +987,11 @@ pub async fn cmd_start_webcam_recording(
         }
         Ok(false) => {
             error!("Camera permission not granted");
-            return Err("Camera permission required. Please enable in System Preferences → Privacy & Security → Camera".to_string());
+            return Err(RecordingStartError::Failed("Camera permission required. Please enable in System Preferences → Privacy & Security → Camera".to_string()));
         }
         Err(e) => {
             error!("Camera permission check failed: {}", e);
-            return Err(format!("Permission check failed: {}", e));
+            return Err(RecordingStartError::Failed(format!("Permission check failed: {}", e)));
         }
     }
 
@@ -632,37 +1002,31 @@ pub async fn cmd_start_webcam_recording(
         recording_id, camera_index, enable_microphone
     );
 
-    // Create output path: ~/Documents/clippy/recordings/webcam-{uuid}.mp4
-    let home_dir = dirs::home_dir().ok_or_else(|| {
-        error!("Could not determine home directory");
-        "Could not determine home directory".to_string()
-    })?;
+    // Encode into scratch space; the finished file lands in
+    // ~/Documents/clippy/recordings/webcam-{uuid}.mp4 on stop
+    let recordings_dir = recordings_output_dir()?;
+    prepare_output_dir(&recordings_dir)?;
+    let scratch_dir = prepare_scratch_dir(None)?;
+    let output_path = scratch_file(&scratch_dir, &format!("webcam-{}", recording_id), "video.mp4");
 
-    let output_path = home_dir
-        .join("Documents")
-        .join("clippy")
-        .join("recordings")
-        .join(format!("webcam-{}.mp4", recording_id));
-
-    // Ensure output directory exists
-    if let Some(parent) = output_path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| {
-            error!("Failed to create output directory: {}", e);
-            format!("Failed to create output directory: {}", e)
-        })?;
-    }
-
-    info!("Output path: {}", output_path.display());
+    info!("Intermediate path: {}", output_path.display());
 
     // Get camera resolution (we'll use 1920x1080 for real-time capture)
     let (width, height) = (1920u32, 1080u32);
 
+    // Refuse to start rather than fill the disk mid-recording
+    let required_bytes = required_free_bytes(
+        estimate_bytes_per_minute(width, height, 30, enable_microphone as u32),
+        min_free_space_mb,
+    );
+    ensure_free_space(&[&scratch_dir, &recordings_dir], required_bytes)?;
+
     // Create unified camera backend
     let mut camera_backend = CameraBackend::new(camera_index, width, height)
         .await
         .map_err(|e| {
             error!("Failed to initialize camera backend: {}", e);
-            format!("Camera initialization failed: {}", e)
+            camera_init_error(&e)
         })?;
 
     info!("Recording at {}x{} @ 30 FPS", width, height);
@@ -691,17 +1055,18 @@ pub async fn cmd_start_webcam_recording(
         })?;
 
     // Start microphone capture task if enabled
-    let (audio_handle, audio_path) = if enable_microphone {
+    let (audio_handle, audio_path) = if let Some(audio_path) =
+        webcam_audio_path(enable_microphone, &scratch_dir, &recording_id)
+    {
         info!("Initializing microphone audio capture");
 
-        // Create audio output path
-        let audio_path = output_path.with_extension("wav");
-
         // Create bounded channel for audio samples (100 samples buffer)
         let (audio_tx, mut audio_rx) = mpsc::channel::<AudioSample>(100);
 
         // Spawn blocking task to manage AudioCapture (not Send)
         let audio_path_clone = audio_path.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_clone = Arc::clone(&stop);
         let handle = tokio::task::spawn_blocking(move || {
             // Initialize audio capture in blocking context
             let mut audio_capture = match AudioCapture::new() {
@@ -712,8 +1077,12 @@ pub async fn cmd_start_webcam_recording(
                 }
             };
 
-            // Select default microphone
-            let device_name = match audio_capture.select_default_device() {
+            // Select the requested microphone, or the system default
+            let selected = match &microphone_device {
+                Some(name) => audio_capture.select_device_by_name(name),
+                None => audio_capture.select_default_device(),
+            };
+            let device_name = match selected {
                 Ok(name) => name,
                 Err(e) => {
                     error!("Failed to select microphone: {}", e);
@@ -742,64 +1111,18 @@ pub async fn cmd_start_webcam_recording(
             }
 
             info!("Audio capture started, waiting for first sample to detect sample rate");
-            let mut sample_count = 0;
-            let mut writer_opt: Option<WavWriter> = None;
-
-            // Block and process audio samples
-            while let Some(sample) = audio_rx.blocking_recv() {
-                // Create WAV writer after receiving first sample (so we know the actual sample rate)
-                if writer_opt.is_none() {
-                    info!("First audio sample received: {} Hz, {} channels",
-                        sample.sample_rate, sample.channels);
-
-                    writer_opt = match WavWriter::new(
-                        audio_path_clone.clone(),
-                        sample.sample_rate,
-                        sample.channels
-                    ) {
-                        Ok(w) => {
-                            info!("WAV writer created with {} Hz, {} channels",
-                                sample.sample_rate, sample.channels);
-                            Some(w)
-                        },
-                        Err(e) => {
-                            error!("Failed to create WAV writer: {}", e);
-                            return;
-                        }
-                    };
-                }
 
-                if let Some(ref mut writer) = writer_opt {
-                    // Apply gain to increase volume (2.5x boost)
-                    // Clamp to [-1.0, 1.0] to prevent clipping distortion
-                    let gain = 2.5;
-                    let boosted_samples: Vec<f32> = sample.data.iter()
-                        .map(|&s| (s * gain).max(-1.0).min(1.0))
-                        .collect();
-
-                    if let Err(e) = writer.write_samples(&boosted_samples) {
-                        error!("Failed to write audio samples: {}", e);
-                        break;
-                    }
-                    sample_count += sample.data.len();
-                }
-            }
-
-            info!("Audio channel closed, finalizing WAV file ({} samples)", sample_count);
-            if let Some(writer) = writer_opt {
-                if let Err(e) = writer.finalize() {
-                    error!("Failed to finalize WAV file: {}", e);
-                } else {
-                    info!("Audio file finalized successfully");
-                }
-            } else {
-                warn!("No audio samples received, WAV file not created");
-            }
-
-            // audio_capture is dropped here, stopping the stream
+            // Stopping the stream drops its sender, so the writer drains and finalizes
+            write_mic_samples_to_wav(
+                &mut audio_rx,
+                &stop_clone,
+                || audio_capture.stop_capture(),
+                &audio_path_clone,
+                mic_gain,
+            );
         });
 
-        (Some(handle), Some(audio_path))
+        (Some(AudioWriterTask { handle, stop }), Some(audio_path))
     } else {
         (None, None)
     };
@@ -839,12 +1162,87 @@ pub async fn cmd_start_webcam_recording(
         recording_id.clone(),
         (camera_backend, camera_handle, audio_handle, encoding_handle, output_path, audio_path),
     );
+    drop(recordings);
+    RECORDING_CLOCKS.lock().await.insert(recording_id.clone(), Arc::new(PausableClock::new()));
 
     info!("Webcam recording started successfully: {}", recording_id);
 
+    // Stop before the disk fills up and leaves a truncated file
+    spawn_low_disk_monitor(
+        ShutdownStop::Webcam(recording_id.clone()),
+        vec![scratch_dir, recordings_dir],
+        min_free_space_mb.saturating_mul(1024 * 1024),
+        std::time::Duration::from_secs(RecordingConfig::default().disk_check_interval_secs),
+        app_handle,
+    )
+    .await;
+
     Ok(recording_id)
 }
 
+/// User-facing message for a camera backend that failed to open
+///
+/// "No camera connected" is passed through unchanged so the frontend can tell
+/// it apart from permission and device errors.
+fn camera_init_error(e: &anyhow::Error) -> String {
+    match e.downcast_ref::<CameraError>() {
+        Some(no_camera @ CameraError::NoCamerasAvailable) => no_camera.to_string(),
+        _ => format!("Camera initialization failed: {}", e),
+    }
+}
+
+/// Size of a WAV header; a file this small holds no samples
+const WAV_HEADER_BYTES: u64 = 44;
+
+/// Microphone gain when `mic_gain` is not given (unity: samples are written as captured)
+const DEFAULT_MIC_GAIN: f32 = 1.0;
+
+/// Largest accepted `mic_gain`
+const MAX_MIC_GAIN: f32 = 10.0;
+
+/// Validate a requested microphone gain, defaulting to `DEFAULT_MIC_GAIN`
+fn resolve_mic_gain(mic_gain: Option<f32>) -> Result<f32, String> {
+    let gain = mic_gain.unwrap_or(DEFAULT_MIC_GAIN);
+    if !gain.is_finite() || !(0.0..=MAX_MIC_GAIN).contains(&gain) {
+        return Err(format!(
+            "Microphone gain must be between 0.0 and {}, got {}",
+            MAX_MIC_GAIN, gain
+        ));
+    }
+    Ok(gain)
+}
+
+/// Scale microphone samples by `gain`, clamped to [-1.0, 1.0]
+fn apply_mic_gain(samples: &[f32], gain: f32) -> Vec<f32> {
+    samples.iter().map(|&s| (s * gain).clamp(-1.0, 1.0)).collect()
+}
+
+/// Scratch WAV path for a webcam recording's audio, or `None` when audio is disabled
+fn webcam_audio_path(enable_microphone: bool, scratch_dir: &std::path::Path, recording_id: &str) -> Option<PathBuf> {
+    enable_microphone.then(|| scratch_file(scratch_dir, &format!("webcam-{}", recording_id), "audio.wav"))
+}
+
+/// How a stopped webcam recording is finalized
+#[derive(Debug, PartialEq)]
+enum WebcamFinalize {
+    /// Mux the captured WAV into the video
+    MuxAudio(PathBuf),
+    /// Save the video as-is (audio disabled, or nothing was captured)
+    VideoOnly,
+}
+
+/// Decide whether a webcam recording needs an audio mux
+///
+/// A missing or header-only WAV is never muxed: it would add an empty audio track.
+fn webcam_finalize_step(audio_path: Option<&std::path::Path>) -> WebcamFinalize {
+    match audio_path {
+        Some(path) if std::fs::metadata(path).map(|m| m.len() > WAV_HEADER_BYTES).unwrap_or(false) => {
+            WebcamFinalize::MuxAudio(path.to_path_buf())
+        }
+        _ => WebcamFinalize::VideoOnly,
+    }
+}
+
 /// Stop webcam recording
 ///
 /// This command stops the active webcam recording, finalizes encoding, and returns
@@ -863,7 +1261,7 @@ pub async fn cmd_start_webcam_recording(
 ///
 /// 1. Look up recording handles by ID from ACTIVE_WEBCAM_RECORDINGS
 /// 2. Stop camera capture task (if still running)
-/// 3. Stop audio capture task (if enabled and still running)
+/// 3. Stop audio capture and wait for the WAV to be finalized (if enabled)
 /// 4. Wait for encoding task to finish (finalize MP4)
 /// 5. Return file path
 ///
@@ -877,6 +1275,7 @@ pub async fn cmd_stop_webcam_recording(
     app_handle: tauri::AppHandle,
 ) -> Result<String, String> {
     debug!("Command: stop webcam recording {}", recording_id);
+    abort_low_disk_monitor(&recording_id).await;
 
     // Remove recording from active state
     let mut recordings = ACTIVE_WEBCAM_RECORDINGS.lock().await;
@@ -889,6 +1288,7 @@ pub async fn cmd_stop_webcam_recording(
 
     // Release lock before awaiting
     drop(recordings);
+    RECORDING_CLOCKS.lock().await.remove(&recording_id);
 
     info!("Stopping webcam recording: {}", recording_id);
 
@@ -902,10 +1302,11 @@ pub async fn cmd_stop_webcam_recording(
     }
     debug!("Camera capture task finished");
 
-    // Abort audio capture task if present (this closes the audio channel)
-    if let Some(handle) = audio_handle {
-        handle.abort();
-        debug!("Audio capture task aborted");
+    // Stop audio capture and let the writer finalize the WAV with every queued sample
+    if let Some(audio_task) = audio_handle {
+        if audio_task.stop_and_drain(AUDIO_DRAIN_TIMEOUT).await {
+            debug!("Audio capture task finished");
+        }
     }
 
     // Wait for encoding task to complete (finalize MP4)
@@ -929,15 +1330,16 @@ pub async fn cmd_stop_webcam_recording(
         return Err(format!("Video file not found: {}", video_path.display()));
     }
 
+    // Only the finished file goes to the output folder; intermediates stay in scratch
+    let webcam_final_path = webcam_recording_output_path(&recording_id)?;
+
     // If audio was recorded, mux video + audio
-    let final_output_path = if let Some(audio_path_val) = audio_path {
-        if audio_path_val.exists() {
+    let final_output_path = match webcam_finalize_step(audio_path.as_deref()) {
+        WebcamFinalize::MuxAudio(audio_path_val) => {
             info!("Muxing video and audio with FFmpeg");
 
-            // Create final output path (replace .mp4 with -final.mp4)
-            let final_path = video_path.with_file_name(
-                format!("{}-final.mp4", video_path.file_stem().unwrap().to_string_lossy())
-            );
+            // Mux straight into the output folder
+            let final_path = webcam_final_path.clone();
 
             // Use FFmpeg to mux video + audio
             let status = std::process::Command::new("ffmpeg")
@@ -971,12 +1373,15 @@ pub async fn cmd_stop_webcam_recording(
             }
 
             final_path
-        } else {
-            warn!("Audio file not found, returning video-only file");
-            video_path
         }
-    } else {
-        video_path
+        WebcamFinalize::VideoOnly => {
+            if let Some(audio_path_val) = &audio_path {
+                warn!("No audio was captured, saving video-only file");
+                let _ = std::fs::remove_file(audio_path_val);
+            }
+            promote_to_output(&video_path, &webcam_final_path)?;
+            webcam_final_path
+        }
     };
 
     let output_path_str = final_output_path.to_string_lossy().to_string();
@@ -1002,15 +1407,189 @@ pub async fn cmd_stop_webcam_recording(
     Ok(output_path_str)
 }
 
-/// Start screen recording with real-time H.264 encoding
-///
-/// This command initializes ScreenCaptureKit, starts continuous frame capture at 30 FPS,
-/// and encodes frames in real-time to H.264 MP4 using FFmpeg.
-///
-/// # Returns
-///
+/// Payload for the `audio-unavailable` event
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AudioUnavailablePayload {
+    source: String,  // "system" or "microphone"
+    reason: String,
+}
+
+/// Payload for the `recording-countdown` event
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordingCountdownPayload {
+    recording_id: String,
+    remaining_secs: u64,
+}
+
+/// Wait out a start countdown, calling `on_tick` with the whole seconds left
+///
+/// Ticks once per second (3, 2, 1 for a 3000 ms delay) and checks `cancelled`
+/// every `COUNTDOWN_POLL_INTERVAL`.
+///
+/// # Returns
+/// `true` once the delay has elapsed, `false` if cancelled first
+async fn run_start_countdown(
+    delay: std::time::Duration,
+    cancelled: &AtomicBool,
+    mut on_tick: impl FnMut(u64),
+) -> bool {
+    let deadline = tokio::time::Instant::now() + delay;
+    let mut last_tick = None;
+
+    loop {
+        if cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+            return false;
+        }
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            return true;
+        }
+
+        let remaining_secs = remaining.as_millis().div_ceil(1000) as u64;
+        if last_tick != Some(remaining_secs) {
+            on_tick(remaining_secs);
+            last_tick = Some(remaining_secs);
+        }
+        tokio::time::sleep(remaining.min(COUNTDOWN_POLL_INTERVAL)).await;
+    }
+}
+
+/// Payload for the `pip-composition-progress` event
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PipCompositionProgressPayload {
+    recording_id: String,
+    #[serde(flatten)]
+    progress: CompositionProgress,
+}
+
+/// Treat an audio setup failure as non-fatal
+///
+/// Logs the failure and emits `audio-unavailable` so the UI can tell the user the
+/// recording continues video-only. Only video failures abort a recording.
+fn degrade_audio_failure<T>(
+    source: &str,
+    result: Result<T, String>,
+    app_handle: Option<&tauri::AppHandle>,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(reason) => {
+            warn!("{} audio unavailable, continuing video-only: {}", source, reason);
+            if let Some(handle) = app_handle {
+                let payload = AudioUnavailablePayload {
+                    source: source.to_string(),
+                    reason,
+                };
+                if let Err(e) = handle.emit("audio-unavailable", &payload) {
+                    warn!("Failed to emit audio-unavailable event: {}", e);
+                }
+            }
+            None
+        }
+    }
+}
+
+/// What a screen recording captures: one display, region or window, or every display side by side
+enum ScreenSource {
+    Single(ScreenCapture),
+    AllDisplays(MultiDisplayCapture),
+}
+
+impl ScreenSource {
+    fn enable_system_audio(&mut self, sample_rate: u32, channels: u16) -> Result<(), ScreenCaptureError> {
+        match self {
+            Self::Single(capture) => capture.enable_system_audio(sample_rate, channels),
+            Self::AllDisplays(capture) => capture.enable_system_audio(sample_rate, channels),
+        }
+    }
+
+    fn is_system_audio_enabled(&self) -> bool {
+        match self {
+            Self::Single(capture) => capture.is_system_audio_enabled(),
+            Self::AllDisplays(capture) => capture.is_system_audio_enabled(),
+        }
+    }
+
+    fn set_frame_rate(&mut self, fps: u32) -> Result<(), ScreenCaptureError> {
+        match self {
+            Self::Single(capture) => capture.set_frame_rate(fps),
+            Self::AllDisplays(capture) => capture.set_frame_rate(fps),
+        }
+    }
+
+    fn get_dimensions(&self) -> (u32, u32) {
+        match self {
+            Self::Single(capture) => capture.get_dimensions(),
+            Self::AllDisplays(capture) => capture.get_dimensions(),
+        }
+    }
+
+    fn start_continuous_capture(
+        &mut self,
+        frame_tx: mpsc::Sender<crate::services::ffmpeg::TimestampedFrame>,
+        audio_tx: Option<mpsc::Sender<AudioSample>>,
+        app_handle: Option<tauri::AppHandle>,
+    ) -> Result<tokio::task::JoinHandle<()>, ScreenCaptureError> {
+        match self {
+            Self::Single(capture) => capture.start_continuous_capture(frame_tx, audio_tx, app_handle),
+            Self::AllDisplays(capture) => capture.start_continuous_capture(frame_tx, audio_tx, app_handle),
+        }
+    }
+
+    fn get_pause_flag(&self) -> Arc<AtomicBool> {
+        match self {
+            Self::Single(capture) => capture.get_pause_flag(),
+            Self::AllDisplays(capture) => capture.get_pause_flag(),
+        }
+    }
+
+    fn get_stop_signal(&self) -> Arc<AtomicBool> {
+        match self {
+            Self::Single(capture) => capture.get_stop_signal(),
+            Self::AllDisplays(capture) => capture.get_stop_signal(),
+        }
+    }
+
+    fn get_abort_signal(&self) -> CaptureAbort {
+        match self {
+            Self::Single(capture) => capture.get_abort_signal(),
+            Self::AllDisplays(capture) => capture.get_abort_signal(),
+        }
+    }
+}
+
+/// Apply the single-capture settings of `config` (region only outside window mode)
+fn configure_screen_capture(
+    capture: &mut ScreenCapture,
+    config: &crate::models::recording::RecordingConfig,
+    window_id: Option<u32>,
+) -> Result<(), String> {
+    capture.set_overflow_policy(config.overflow_policy);
+    capture.set_display_fallback(config.display_fallback);
+    capture.set_scope_audio_to_window(config.scope_audio_to_window);
+
+    // Region capture shrinks the capture dimensions to the region before the encoder is sized
+    if window_id.is_none() {
+        capture.set_region(config.capture_region).map_err(|e| {
+            error!("Invalid capture region: {}", e);
+            e.to_string()
+        })?;
+    }
+    Ok(())
+}
+
+/// Start screen recording with real-time H.264 encoding
+///
+/// This command initializes ScreenCaptureKit, starts continuous frame capture at 30 FPS,
+/// and encodes frames in real-time to H.264 MP4 using FFmpeg.
+///
+/// # Returns
+///
 /// - `Ok(String)` with recording ID (UUID) on success
-/// - `Err(String)` with user-friendly error message on failure
+/// - `Err(RecordingStartError)` - `{ code, message }` on failure
 ///
 /// # Flow
 ///
@@ -1035,8 +1614,9 @@ pub async fn cmd_stop_webcam_recording(
 pub async fn cmd_start_screen_recording(
     config: Option<RecordingConfig>,
     app_handle: tauri::AppHandle,
-) -> Result<String, String> {
+) -> Result<String, RecordingStartError> {
     debug!("Command: start screen recording");
+    ensure_ffmpeg_ready()?;
 
     // Use provided config or default
     let config = config.unwrap_or_default();
@@ -1050,39 +1630,37 @@ pub async fn cmd_start_screen_recording(
         }
         Ok(false) => {
             error!("Screen recording permission not granted");
-            return Err("Screen recording permission required. Please enable in System Preferences → Privacy & Security → Screen Recording".to_string());
+            return Err(RecordingStartError::Failed("Screen recording permission required. Please enable in System Preferences → Privacy & Security → Screen Recording".to_string()));
         }
         Err(e) => {
             error!("Permission check failed: {}", e);
-            return Err(format!("Permission check failed: {}", e));
+            return Err(RecordingStartError::Failed(format!("Permission check failed: {}", e)));
         }
     }
 
+    // A named microphone that doesn't exist is a caller error, not a degraded-audio case
+    if let (true, Some(name)) = (config.microphone, &config.microphone_device) {
+        AudioCapture::new()
+            .and_then(|mut capture| capture.select_device_by_name(name))
+            .map_err(|e| {
+                error!("Failed to select microphone device: {}", e);
+                e.to_string()
+            })?;
+    }
+
     // Generate recording ID
     let recording_id = Uuid::new_v4().to_string();
     info!("Starting screen recording: {}", recording_id);
 
-    // Create output path: ~/Documents/clippy/recordings/recording-{uuid}.mp4
-    let home_dir = dirs::home_dir().ok_or_else(|| {
-        error!("Could not determine home directory");
-        "Could not determine home directory".to_string()
-    })?;
-
-    let output_path = home_dir
-        .join("Documents")
-        .join("clippy")
-        .join("recordings")
-        .join(format!("recording-{}.mp4", recording_id));
+    // Encode video-only into scratch space; the finished file lands in
+    // ~/Documents/clippy/recordings/recording-{uuid}.mp4 on stop
+    let recordings_dir = recordings_output_dir()?;
+    prepare_output_dir(&recordings_dir)?;
+    let scratch_dir = prepare_scratch_dir(config.scratch_dir.as_deref())?;
+    let scratch_stem = format!("recording-{}", recording_id);
+    let output_path = scratch_file(&scratch_dir, &scratch_stem, "video.mp4");
 
-    info!("Output path: {}", output_path.display());
-
-    // Ensure output directory exists
-    if let Some(parent) = output_path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| {
-            error!("Failed to create output directory: {}", e);
-            format!("Failed to create output directory: {}", e)
-        })?;
-    }
+    info!("Intermediate path: {}", output_path.display());
 
     // Story 4.1: Determine window ID based on recording mode (AC #3)
     let window_id = if config.screen_recording_mode == crate::models::recording::ScreenRecordingMode::Window {
@@ -1091,21 +1669,39 @@ pub async fn cmd_start_screen_recording(
         None
     };
 
-    // Initialize ScreenCapture with optional window ID
-    let mut screen_capture = ScreenCapture::new(window_id).map_err(|e| {
-        error!("Failed to initialize ScreenCapture: {}", e);
-        format!("Screen capture initialization failed: {}", e)
-    })?;
+    // Fullscreen mode can record every display onto one canvas; otherwise initialize
+    // ScreenCapture with optional window ID (or the chosen display in fullscreen mode)
+    let mut screen_capture = if config.capture_all_displays && window_id.is_none() {
+        ScreenSource::AllDisplays(MultiDisplayCapture::new().map_err(|e| {
+            error!("Failed to initialize multi-display capture: {}", e);
+            format!("Screen capture initialization failed: {}", e)
+        })?)
+    } else {
+        let mut capture = ScreenCapture::new(window_id, config.display_id).map_err(|e| {
+            error!("Failed to initialize ScreenCapture: {}", e);
+            format!("Screen capture initialization failed: {}", e)
+        })?;
+        configure_screen_capture(&mut capture, &config, window_id)?;
+        ScreenSource::Single(capture)
+    };
 
     // Enable system audio if requested (Story 2.4)
+    // Audio failures are non-fatal: the recording continues video-only
     if config.system_audio {
-        screen_capture.enable_system_audio(48000, 2).map_err(|e| {
-            error!("Failed to enable system audio: {}", e);
-            format!("Failed to enable system audio: {}", e)
-        })?;
-        info!("System audio enabled for recording");
+        let enabled = screen_capture
+            .enable_system_audio(48000, 2)
+            .map_err(|e| format!("Failed to enable system audio: {}", e));
+        if degrade_audio_failure("system", enabled, Some(&app_handle)).is_some() {
+            info!("System audio enabled for recording");
+        }
     }
 
+    // Capture at the configured rate; the encoder below is created with the same rate
+    screen_capture.set_frame_rate(config.frame_rate).map_err(|e| {
+        error!("Invalid frame rate: {}", e);
+        e.to_string()
+    })?;
+
     // Get capture dimensions
     let (capture_width, capture_height) = screen_capture.get_dimensions();
     info!("Capture dimensions: {}x{}", capture_width, capture_height);
@@ -1113,18 +1709,14 @@ pub async fn cmd_start_screen_recording(
     // Map resolution string to output dimensions (Story 4.2)
     let (width, height) = match config.resolution.as_str() {
         "720p" => {
-            // Scale to 720p while preserving aspect ratio
-            let aspect_ratio = capture_width as f32 / capture_height as f32;
-            let height = 720_u32;
-            let width = (height as f32 * aspect_ratio) as u32;
+            // Scale to 720p while preserving aspect ratio (even dimensions for libx264)
+            let (width, height) = scale_to_height(capture_width, capture_height, 720);
             info!("Scaling to 720p with aspect ratio preservation: {}x{}", width, height);
             (width, height)
         }
         "1080p" => {
-            // Scale to 1080p while preserving aspect ratio
-            let aspect_ratio = capture_width as f32 / capture_height as f32;
-            let height = 1080_u32;
-            let width = (height as f32 * aspect_ratio) as u32;
+            // Scale to 1080p while preserving aspect ratio (even dimensions for libx264)
+            let (width, height) = scale_to_height(capture_width, capture_height, 1080);
             info!("Scaling to 1080p with aspect ratio preservation: {}x{}", width, height);
             (width, height)
         }
@@ -1137,6 +1729,14 @@ pub async fn cmd_start_screen_recording(
 
     info!("Output resolution: {}x{} at {} FPS", width, height, config.frame_rate);
 
+    // Refuse to start rather than fill the disk mid-recording
+    let audio_tracks = config.system_audio as u32 + config.microphone as u32;
+    let required_bytes = required_free_bytes(
+        estimate_bytes_per_minute(width, height, config.frame_rate, audio_tracks),
+        config.min_free_space_mb,
+    );
+    ensure_free_space(&[&scratch_dir, &recordings_dir], required_bytes)?;
+
     // Create FrameHandler with bounded channel for real-time encoding
     let mut frame_handler = FrameHandler::new_for_encoding(config.frame_rate as usize);
     let frame_tx = frame_handler.get_sender();
@@ -1152,26 +1752,115 @@ pub async fn cmd_start_screen_recording(
         format!("Failed to create encoder: {}", e)
     })?;
 
+    // Backend: unset picks VideoToolbox on macOS when available; its quality is tunable
+    if let Some(backend) = config.resolved_encoder_backend() {
+        encoder.set_backend(backend);
+    }
+    if let Some(quality) = config.hardware_quality {
+        encoder.set_hardware_quality(quality).map_err(|e| {
+            error!("Invalid hardware encoding quality: {}", e);
+            format!("Invalid hardware encoding quality: {}", e)
+        })?;
+    }
+
+    // H.265 for smaller files (availability is checked when encoding starts)
+    encoder.set_video_codec(config.video_codec);
+
+    // Software quality and speed, applied over the codec's real-time defaults
+    if let Some(crf) = config.crf {
+        encoder.set_crf(crf).map_err(|e| {
+            error!("Invalid encoder CRF: {}", e);
+            format!("Invalid encoder CRF: {}", e)
+        })?;
+    }
+    if let Some(preset) = config.preset {
+        encoder.set_preset(preset);
+    }
+
+    // Cap encode threads so FFmpeg leaves room for capture and the UI
+    if let Some(threads) = config.threads {
+        encoder.set_threads(threads).map_err(|e| {
+            error!("Invalid encode thread count: {}", e);
+            format!("Invalid encode thread count: {}", e)
+        })?;
+    }
+
     // Start FFmpeg encoding process
     encoder.start_encoding().await.map_err(|e| {
         error!("Failed to start FFmpeg encoding: {}", e);
         format!("Failed to start encoding: {}", e)
     })?;
 
+    // Developer mode: also dump raw frames for offline replay (CLIPPY_RAW_CAPTURE_DUMP)
+    let raw_dump = raw_dump_max_bytes().and_then(|max_bytes| {
+        let dump_path = raw_dump_path(&output_path);
+        match RawDumpWriter::create(&dump_path, max_bytes) {
+            Ok(writer) => {
+                warn!("Dumping raw capture frames to {}", dump_path.display());
+                Some(writer)
+            }
+            Err(e) => {
+                warn!("Raw capture dump disabled: {}", e);
+                None
+            }
+        }
+    });
+
     // Start encoder task (consumes frames and streams to FFmpeg)
-    let encoder_handle = frame_handler.start_encoder(encoder).await.map_err(|e| {
+    let encoder_handle = match raw_dump {
+        Some(dump) => frame_handler.start_encoder(RawDumpSink::new(encoder, dump)).await,
+        None => frame_handler.start_encoder(encoder).await,
+    }
+    .map_err(|e| {
         error!("Failed to start encoder task: {}", e);
         format!("Failed to start encoder task: {}", e)
     })?;
 
+    // Countdown with the encoder ready but before any audio or video is captured
+    if let Some(delay_ms) = config.start_delay_ms.filter(|&ms| ms > 0) {
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        COUNTDOWN_CANCEL_FLAGS.lock().await.insert(recording_id.clone(), cancel_flag.clone());
+
+        info!("Starting {}ms countdown for recording {}", delay_ms, recording_id);
+        let completed = run_start_countdown(std::time::Duration::from_millis(delay_ms), &cancel_flag, |remaining_secs| {
+            let payload = RecordingCountdownPayload {
+                recording_id: recording_id.clone(),
+                remaining_secs,
+            };
+            if let Err(e) = app_handle.emit("recording-countdown", &payload) {
+                warn!("Failed to emit recording-countdown event: {}", e);
+            }
+        })
+        .await;
+
+        COUNTDOWN_CANCEL_FLAGS.lock().await.remove(&recording_id);
+
+        if !completed {
+            info!("Recording cancelled during countdown: {}", recording_id);
+
+            // Closing the frame channel lets the encoder exit without frames
+            drop(frame_tx);
+            drop(frame_handler);
+            if let Err(e) = encoder_handle.await {
+                warn!("Encoder task failed after countdown cancel: {}", e);
+            }
+            let _ = std::fs::remove_file(&output_path);
+
+            return Err(RecordingStartError::Failed("Recording cancelled during countdown".to_string()));
+        }
+    }
+
+    // Rolling peak history for the live meter, fed by the audio writer tasks
+    let live_waveform = register_live_waveform(&recording_id);
+
+    // Mute flags, checked by each audio writer independently
+    let track_mutes = AudioTrackMutes::default();
+
     // Setup audio capture if system audio is enabled
-    let (audio_tx_opt, audio_writer_handle_opt, audio_pcm_path_opt) = if config.system_audio {
+    let system_audio_active = screen_capture.is_system_audio_enabled();
+    let (audio_tx_opt, audio_writer_handle_opt, audio_pcm_path_opt) = if system_audio_active {
         // Create PCM file path for audio
-        let audio_pcm_path = home_dir
-            .join("Documents")
-            .join("clippy")
-            .join("recordings")
-            .join(format!("recording-{}-audio.pcm", recording_id));
+        let audio_pcm_path = scratch_file(&scratch_dir, &scratch_stem, "audio.pcm");
 
         info!("Audio PCM path: {}", audio_pcm_path.display());
 
@@ -1180,6 +1869,8 @@ pub async fn cmd_start_screen_recording(
 
         // Spawn audio writer task
         let pcm_path = audio_pcm_path.clone();
+        let system_waveform = live_waveform.clone();
+        let system_muted = Arc::clone(&track_mutes.system);
         let audio_writer_handle = tokio::spawn(async move {
             let mut writer = crate::services::audio_capture::PcmFileWriter::new(&pcm_path)
                 .map_err(|e| format!("Failed to create PCM writer: {}", e))?;
@@ -1188,7 +1879,9 @@ pub async fn cmd_start_screen_recording(
 
             // Receive audio samples and write to PCM file
             let mut rx = audio_rx;
-            while let Some(sample) = rx.recv().await {
+            while let Some(mut sample) = rx.recv().await {
+                silence_if_muted(&mut sample, &system_muted);
+                system_waveform.push(LiveAudioSource::SystemAudio, &sample);
                 writer.write_sample(&sample)
                     .map_err(|e| {
                         error!("Failed to write audio sample: {}", e);
@@ -1213,128 +1906,143 @@ pub async fn cmd_start_screen_recording(
     };
 
     // Setup microphone capture if enabled
-    let (mic_writer_handle_opt, mic_pcm_path_opt, mic_pause_flag_opt, mic_tx_opt, mic_channels_opt) = if config.microphone {
-        info!("Initializing microphone capture");
-
-        // Check microphone permission
-        match check_microphone_permission() {
-            Ok(true) => {
-                info!("Microphone permission granted");
-            }
-            Ok(false) => {
-                error!("Microphone permission not granted");
-                return Err("Microphone permission required. Please enable in System Preferences → Privacy & Security → Microphone".to_string());
-            }
-            Err(e) => {
-                error!("Microphone permission check failed: {}", e);
-                return Err(format!("Microphone permission check failed: {}", e));
+    // Microphone failures are non-fatal: log, emit `audio-unavailable`, continue video-only
+    let mic_setup = if config.microphone {
+        let setup: Result<_, String> = async {
+            info!("Initializing microphone capture");
+
+            // Check microphone permission
+            match check_microphone_permission() {
+                Ok(true) => {
+                    info!("Microphone permission granted");
+                }
+                Ok(false) => {
+                    error!("Microphone permission not granted");
+                    return Err("Microphone permission required. Please enable in System Preferences → Privacy & Security → Microphone".to_string());
+                }
+                Err(e) => {
+                    error!("Microphone permission check failed: {}", e);
+                    return Err(format!("Microphone permission check failed: {}", e));
+                }
             }
-        }
 
-        // Create AudioCapture instance
-        let mut audio_capture = AudioCapture::new().map_err(|e| {
-            error!("Failed to initialize microphone capture: {}", e);
-            format!("Failed to initialize microphone capture: {}", e)
-        })?;
+            // Create PCM file path for microphone audio
+            let mic_pcm_path = scratch_file(&scratch_dir, &scratch_stem, "microphone.pcm");
 
-        // Select default microphone device
-        let device_name = audio_capture.select_default_device().map_err(|e| {
-            error!("Failed to select default microphone device: {}", e);
-            format!("Failed to select microphone device: {}", e)
-        })?;
+            info!("Microphone PCM path: {}", mic_pcm_path.display());
 
-        info!("Selected microphone device: {}", device_name);
+            // Create microphone audio channel
+            let (mic_tx, mic_rx) = tokio::sync::mpsc::channel::<AudioSample>(100);
 
-        // Get channel count (1=mono, 2=stereo)
-        let mic_channels = audio_capture.get_channels().map_err(|e| {
-            error!("Failed to get microphone channel count: {}", e);
-            format!("Failed to get microphone channel count: {}", e)
-        })?;
+            // Create pause flag for microphone
+            let mic_pause_flag = Arc::new(AtomicBool::new(false));
+            let mic_pause_flag_clone = mic_pause_flag.clone();
+            let mic_muted = Arc::clone(&track_mutes.microphone);
 
-        info!("Microphone audio format: {} channels ({})",
-            mic_channels,
-            if mic_channels == 1 { "mono" } else { "stereo" });
+            // Spawn microphone writer task
+            let pcm_path = mic_pcm_path.clone();
+            let mic_waveform = live_waveform.clone();
+            let mic_writer_handle = tokio::spawn(async move {
+                let mut writer = crate::services::audio_capture::PcmFileWriter::new(&pcm_path)
+                    .map_err(|e| format!("Failed to create microphone PCM writer: {}", e))?;
 
-        // Create PCM file path for microphone audio
-        let mic_pcm_path = home_dir
-            .join("Documents")
-            .join("clippy")
-            .join("recordings")
-            .join(format!("recording-{}-microphone.pcm", recording_id));
+                info!("Microphone writer task started");
 
-        info!("Microphone PCM path: {}", mic_pcm_path.display());
+                // Receive audio samples and write to PCM file
+                let mut rx = mic_rx;
+                let mut sample_count = 0;
+                let mut paused_count = 0;
 
-        // Create microphone audio channel
-        let (mic_tx, mic_rx) = tokio::sync::mpsc::channel::<AudioSample>(100);
+                while let Some(mut sample) = rx.recv().await {
+                    sample_count += 1;
 
-        // Create pause flag for microphone
-        let mic_pause_flag = Arc::new(AtomicBool::new(false));
-        let mic_pause_flag_clone = mic_pause_flag.clone();
+                    // Log first few samples to verify we're receiving data
+                    if sample_count <= 3 {
+                        info!("Microphone sample #{}: {} samples, {} Hz, {} channels",
+                            sample_count, sample.data.len(), sample.sample_rate, sample.channels);
+                    }
 
-        // Spawn microphone writer task
-        let pcm_path = mic_pcm_path.clone();
-        let mic_writer_handle = tokio::spawn(async move {
-            let mut writer = crate::services::audio_capture::PcmFileWriter::new(&pcm_path)
-                .map_err(|e| format!("Failed to create microphone PCM writer: {}", e))?;
+                    // Check pause flag
+                    if !mic_pause_flag_clone.load(std::sync::atomic::Ordering::Relaxed) {
+                        silence_if_muted(&mut sample, &mic_muted);
+                        mic_waveform.push(LiveAudioSource::Microphone, &sample);
+                        writer.write_sample(&sample)
+                            .map_err(|e| {
+                                error!("Failed to write microphone sample: {}", e);
+                                format!("Failed to write microphone sample: {}", e)
+                            })?;
+                    } else {
+                        paused_count += 1;
+                    }
+                }
 
-            info!("Microphone writer task started");
+                // Finalize PCM file
+                writer.finalize()
+                    .map_err(|e| {
+                        error!("Failed to finalize microphone PCM file: {}", e);
+                        format!("Failed to finalize microphone PCM file: {}", e)
+                    })?;
 
-            // Receive audio samples and write to PCM file
-            let mut rx = mic_rx;
-            let mut sample_count = 0;
-            let mut paused_count = 0;
+                info!("Microphone writer task completed: {} total samples ({} written, {} paused)",
+                    sample_count, sample_count - paused_count, paused_count);
+                Ok::<(), String>(())
+            });
 
-            while let Some(sample) = rx.recv().await {
-                sample_count += 1;
+            // AudioCapture isn't Send, so the guard creates and owns it on its own thread
+            let microphone_device = config.microphone_device.clone();
+            let (mic_capture, mic_channels) = AudioCaptureGuard::spawn(move || {
+                let mut audio_capture = AudioCapture::new().map_err(|e| {
+                    error!("Failed to initialize microphone capture: {}", e);
+                    format!("Failed to initialize microphone capture: {}", e)
+                })?;
 
-                // Log first few samples to verify we're receiving data
-                if sample_count <= 3 {
-                    info!("Microphone sample #{}: {} samples, {} Hz, {} channels",
-                        sample_count, sample.data.len(), sample.sample_rate, sample.channels);
+                // Select the requested microphone, or the system default
+                let device_name = match &microphone_device {
+                    Some(name) => audio_capture.select_device_by_name(name),
+                    None => audio_capture.select_default_device(),
                 }
+                .map_err(|e| {
+                    error!("Failed to select microphone device: {}", e);
+                    format!("Failed to select microphone device: {}", e)
+                })?;
 
-                // Check pause flag
-                if !mic_pause_flag_clone.load(std::sync::atomic::Ordering::Relaxed) {
-                    writer.write_sample(&sample)
-                        .map_err(|e| {
-                            error!("Failed to write microphone sample: {}", e);
-                            format!("Failed to write microphone sample: {}", e)
-                        })?;
-                } else {
-                    paused_count += 1;
-                }
-            }
+                info!("Selected microphone device: {}", device_name);
 
-            // Finalize PCM file
-            writer.finalize()
-                .map_err(|e| {
-                    error!("Failed to finalize microphone PCM file: {}", e);
-                    format!("Failed to finalize microphone PCM file: {}", e)
+                // Get channel count (1=mono, 2=stereo)
+                let mic_channels = audio_capture.get_channels().map_err(|e| {
+                    error!("Failed to get microphone channel count: {}", e);
+                    format!("Failed to get microphone channel count: {}", e)
                 })?;
 
-            info!("Microphone writer task completed: {} total samples ({} written, {} paused)",
-                sample_count, sample_count - paused_count, paused_count);
-            Ok::<(), String>(())
-        });
+                info!("Microphone audio format: {} channels ({})",
+                    mic_channels,
+                    if mic_channels == 1 { "mono" } else { "stereo" });
 
-        // Clone mic_tx before passing to start_capture (we need to keep one to close the channel later)
-        let mic_tx_for_storage = mic_tx.clone();
+                audio_capture.start_capture(mic_tx).map_err(|e| {
+                    error!("Failed to start microphone capture: {}", e);
+                    format!("Failed to start microphone capture: {}", e)
+                })?;
 
-        // Start microphone capture
-        audio_capture.start_capture(mic_tx).map_err(|e| {
-            error!("Failed to start microphone capture: {}", e);
-            format!("Failed to start microphone capture: {}", e)
-        })?;
+                Ok((audio_capture, mic_channels))
+            })
+            .await?;
 
-        info!("Microphone capture started successfully");
+            info!("Microphone capture started successfully");
 
-        // Leak AudioCapture to keep it alive for the recording duration
-        // This is intentional - the stream must stay alive or it stops immediately
-        Box::leak(Box::new(audio_capture));
+            Ok((mic_writer_handle, mic_pcm_path, mic_pause_flag, mic_capture, mic_channels))
+        }
+        .await;
 
-        (Some(mic_writer_handle), Some(mic_pcm_path), Some(mic_pause_flag), Some(mic_tx_for_storage), Some(mic_channels))
+        degrade_audio_failure("microphone", setup, Some(&app_handle))
     } else {
-        (None, None, None, None, None)
+        None
+    };
+
+    let (mic_writer_handle_opt, mic_pcm_path_opt, mic_pause_flag_opt, mic_capture_opt, mic_channels_opt) = match mic_setup {
+        Some((handle, path, pause_flag, capture, channels)) => {
+            (Some(handle), Some(path), Some(pause_flag), Some(capture), Some(channels))
+        }
+        None => (None, None, None, None, None),
     };
 
     // Start continuous capture (with app_handle for window-closed events)
@@ -1350,6 +2058,7 @@ pub async fn cmd_start_screen_recording(
 
     // Get stop signal for command layer access
     let stop_signal = screen_capture.get_stop_signal();
+    let capture_abort = screen_capture.get_abort_signal();
 
     // Store handles in global state
     let mut recordings = ACTIVE_RECORDINGS.lock().await;
@@ -1367,16 +2076,22 @@ pub async fn cmd_start_screen_recording(
             mic_pcm_path_opt,
             mic_pause_flag_opt,
             mic_channels_opt,
+            capture_abort,
+            config.normalize_loudness,
         ),
     );
 
-    // Store microphone sender for channel cleanup
-    if let Some(mic_tx) = mic_tx_opt {
-        MICROPHONE_SENDERS.lock().await.insert(recording_id.clone(), mic_tx);
+    drop(recordings);
+    RECORDING_CLOCKS.lock().await.insert(recording_id.clone(), Arc::new(PausableClock::new()));
+    AUDIO_TRACK_MUTES.lock().await.insert(recording_id.clone(), track_mutes);
+
+    // Keep the microphone capture until the recording is stopped or cancelled
+    if let Some(mic_capture) = mic_capture_opt {
+        MICROPHONE_CAPTURES.lock().await.insert(recording_id.clone(), mic_capture);
     }
 
     // Build audio capture status message
-    let audio_status = match (config.system_audio, config.microphone) {
+    let audio_status = match (system_audio_active, mic_channels_opt.is_some()) {
         (true, true) => " + system audio + microphone",
         (true, false) => " + system audio",
         (false, true) => " + microphone",
@@ -1387,6 +2102,16 @@ pub async fn cmd_start_screen_recording(
         audio_status,
         recording_id);
 
+    // Stop before the disk fills up and leaves a truncated file
+    spawn_low_disk_monitor(
+        ShutdownStop::Screen(recording_id.clone()),
+        vec![scratch_dir, recordings_dir],
+        config.min_free_space_mb.saturating_mul(1024 * 1024),
+        std::time::Duration::from_secs(config.disk_check_interval_secs.max(1)),
+        app_handle.clone(),
+    )
+    .await;
+
     Ok(recording_id)
 }
 
@@ -1402,7 +2127,8 @@ pub async fn cmd_start_screen_recording(
 /// # Returns
 ///
 /// - `Ok(String)` with file path to saved MP4 recording
-/// - `Err(String)` with user-friendly error message on failure
+/// - `Err(RecordingStopError)` - `{ code, message }` on failure; a capture abort
+///   (e.g. `pixel_format_incompatible`) adds `suggestion` and the saved `outputPath`
 ///
 /// # Flow
 ///
@@ -1410,7 +2136,8 @@ pub async fn cmd_start_screen_recording(
 /// 2. Drop frame sender (signals capture to stop)
 /// 3. Wait for capture task to complete
 /// 4. Wait for encoder task to finish (flushes remaining frames to FFmpeg and finalizes MP4)
-/// 5. Return file path
+/// 5. Verify the file is playable, repairing it if not (emits `recording-integrity`)
+/// 6. Return file path
 ///
 /// # Errors
 ///
@@ -1418,8 +2145,12 @@ pub async fn cmd_start_screen_recording(
 /// - Capture task failed
 /// - Encoder task failed (FFmpeg encoding error)
 #[tauri::command]
-pub async fn cmd_stop_recording(recording_id: String) -> Result<String, String> {
+pub async fn cmd_stop_recording(
+    recording_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<String, RecordingStopError> {
     debug!("Command: stop recording {}", recording_id);
+    abort_low_disk_monitor(&recording_id).await;
 
     // Remove recording from active state
     let mut recordings = ACTIVE_RECORDINGS.lock().await;
@@ -1435,18 +2166,22 @@ pub async fn cmd_stop_recording(recording_id: String) -> Result<String, String>
         mic_pcm_path_opt,
         _mic_pause_flag_opt,
         mic_channels_opt,
+        capture_abort,
+        normalize_loudness,
     ) = recordings
         .remove(&recording_id)
         .ok_or_else(|| {
             error!("Recording not found: {}", recording_id);
             format!("Recording not found: {}", recording_id)
         })?;
-
-    // Note: AudioCapture is intentionally leaked (see start_recording)
-    // Close the microphone channel by dropping the sender - this allows writer task to finish
-    if let Some(_) = mic_writer_handle_opt {
-        let mic_sender = MICROPHONE_SENDERS.lock().await.remove(&recording_id);
-        drop(mic_sender);
+    remove_live_waveform(&recording_id);
+    RECORDING_CLOCKS.lock().await.remove(&recording_id);
+    AUDIO_TRACK_MUTES.lock().await.remove(&recording_id);
+
+    // Stop the microphone stream, closing its channel so the writer task can finish
+    let mic_capture = MICROPHONE_CAPTURES.lock().await.remove(&recording_id);
+    if let Some(mic_capture) = mic_capture {
+        mic_capture.release().await;
         info!("Microphone channel closed");
     }
 
@@ -1481,6 +2216,9 @@ pub async fn cmd_stop_recording(recording_id: String) -> Result<String, String>
         }
     }
 
+    // Capture may have stopped on its own before the user pressed stop
+    let capture_abort_reason = capture_abort.reason();
+
     // Wait for encoder task to finish (flushes remaining frames and finalizes MP4)
     match encoder_handle.await {
         Ok(Ok(())) => {
@@ -1488,166 +2226,214 @@ pub async fn cmd_stop_recording(recording_id: String) -> Result<String, String>
         }
         Ok(Err(e)) => {
             error!("FFmpeg encoder failed: {}", e);
-            return Err(format!("Failed to encode recording: {}", e));
+            return Err(format!("Failed to encode recording: {}", e).into());
         }
         Err(e) => {
             error!("Encoder task join error: {}", e);
-            return Err(format!("Encoder task error: {}", e));
+            return Err(format!("Encoder task error: {}", e).into());
         }
     }
 
-    // Wait for audio writer tasks and collect successful PCM paths
-    let mut audio_inputs: Vec<crate::services::ffmpeg::AudioInputConfig> = Vec::new();
-    let mut pcm_files_to_cleanup: Vec<PathBuf> = Vec::new();
+    // Finalize whatever was captured, even if capture aborted, so the frames
+    // already encoded are kept and the scratch intermediates are cleaned up
+    let finalized: Result<String, String> = async {
+        // Wait for audio writer tasks and collect successful PCM paths
+        let mut audio_inputs: Vec<crate::services::ffmpeg::AudioInputConfig> = Vec::new();
+        let mut pcm_files_to_cleanup: Vec<PathBuf> = Vec::new();
 
-    // Wait for system audio writer task if present
-    if let (Some(audio_writer_handle), Some(audio_pcm_path)) =
-        (audio_writer_handle_opt, audio_pcm_path_opt)
-    {
-        info!("Waiting for system audio writer task to complete");
-
-        match audio_writer_handle.await {
-            Ok(Ok(())) => {
-                info!("System audio writer task completed successfully");
-                if audio_pcm_path.exists() {
-                    audio_inputs.push(crate::services::ffmpeg::AudioInputConfig {
-                        pcm_path: audio_pcm_path.clone(),
-                        sample_rate: 48000,
-                        channels: 2,
-                        label: "System Audio".to_string(),
-                    });
-                    pcm_files_to_cleanup.push(audio_pcm_path);
-                } else {
-                    warn!("System audio PCM file not found");
+        // Wait for system audio writer task if present
+        if let (Some(audio_writer_handle), Some(audio_pcm_path)) =
+            (audio_writer_handle_opt, audio_pcm_path_opt)
+        {
+            info!("Waiting for system audio writer task to complete");
+
+            match audio_writer_handle.await {
+                Ok(Ok(())) => {
+                    info!("System audio writer task completed successfully");
+                    if audio_pcm_path.exists() {
+                        audio_inputs.push(crate::services::ffmpeg::AudioInputConfig {
+                            pcm_path: audio_pcm_path.clone(),
+                            sample_rate: 48000,
+                            channels: 2,
+                            label: "System Audio".to_string(),
+                        });
+                        pcm_files_to_cleanup.push(audio_pcm_path);
+                    } else {
+                        warn!("System audio PCM file not found");
+                    }
+                }
+                Ok(Err(e)) => {
+                    error!("System audio writer task failed: {}", e);
+                }
+                Err(e) => {
+                    error!("System audio writer task join error: {}", e);
                 }
-            }
-            Ok(Err(e)) => {
-                error!("System audio writer task failed: {}", e);
-            }
-            Err(e) => {
-                error!("System audio writer task join error: {}", e);
             }
         }
-    }
-
-    // Wait for microphone writer task if present (with timeout)
-    if let (Some(mic_writer_handle), Some(mic_pcm_path)) =
-        (mic_writer_handle_opt, mic_pcm_path_opt)
-    {
-        info!("Waiting for microphone writer task to complete");
 
-        // Use timeout to avoid hanging if stream doesn't close properly
-        match tokio::time::timeout(
-            std::time::Duration::from_secs(5),
-            mic_writer_handle
-        ).await {
-            Ok(Ok(Ok(()))) => {
-                info!("Microphone writer task completed successfully");
-                if mic_pcm_path.exists() {
-                    let mic_channels = mic_channels_opt.unwrap_or(1); // Default to mono if not set
-                    info!("Using microphone PCM file with {} channels", mic_channels);
-                    audio_inputs.push(crate::services::ffmpeg::AudioInputConfig {
-                        pcm_path: mic_pcm_path.clone(),
-                        sample_rate: 48000,
-                        channels: mic_channels,
-                        label: "Microphone".to_string(),
-                    });
-                    pcm_files_to_cleanup.push(mic_pcm_path);
-                } else {
-                    warn!("Microphone PCM file not found");
+        // Wait for microphone writer task if present (with timeout)
+        if let (Some(mic_writer_handle), Some(mic_pcm_path)) =
+            (mic_writer_handle_opt, mic_pcm_path_opt)
+        {
+            info!("Waiting for microphone writer task to complete");
+
+            // Use timeout to avoid hanging if stream doesn't close properly
+            match tokio::time::timeout(
+                std::time::Duration::from_secs(5),
+                mic_writer_handle
+            ).await {
+                Ok(Ok(Ok(()))) => {
+                    info!("Microphone writer task completed successfully");
+                    if mic_pcm_path.exists() {
+                        // Only a fallback: finalize uses the format the writer recorded in the PCM sidecar
+                        let mic_channels = mic_channels_opt.unwrap_or(1);
+                        info!("Using microphone PCM file with {} channels", mic_channels);
+                        audio_inputs.push(crate::services::ffmpeg::AudioInputConfig {
+                            pcm_path: mic_pcm_path.clone(),
+                            sample_rate: 48000,
+                            channels: mic_channels,
+                            label: "Microphone".to_string(),
+                        });
+                        pcm_files_to_cleanup.push(mic_pcm_path);
+                    } else {
+                        warn!("Microphone PCM file not found");
+                    }
                 }
-            }
-            Ok(Ok(Err(e))) => {
-                error!("Microphone writer task failed: {}", e);
-            }
-            Ok(Err(e)) => {
-                error!("Microphone writer task join error: {}", e);
-            }
-            Err(_) => {
-                warn!("Microphone writer task timed out after 5 seconds - continuing with available audio");
-                // Still try to use the PCM file if it exists
-                if mic_pcm_path.exists() {
-                    let mic_channels = mic_channels_opt.unwrap_or(1);
-                    info!("Microphone PCM file exists ({}), will attempt to use it",
-                        if mic_channels == 1 { "mono" } else { "stereo" });
-                    audio_inputs.push(crate::services::ffmpeg::AudioInputConfig {
-                        pcm_path: mic_pcm_path.clone(),
-                        sample_rate: 48000,
-                        channels: mic_channels,
-                        label: "Microphone".to_string(),
-                    });
-                    pcm_files_to_cleanup.push(mic_pcm_path);
+                Ok(Ok(Err(e))) => {
+                    error!("Microphone writer task failed: {}", e);
+                }
+                Ok(Err(e)) => {
+                    error!("Microphone writer task join error: {}", e);
+                }
+                Err(_) => {
+                    warn!("Microphone writer task timed out after 5 seconds - continuing with available audio");
+                    // Still try to use the PCM file if it exists
+                    if mic_pcm_path.exists() {
+                        let mic_channels = mic_channels_opt.unwrap_or(1);
+                        info!("Microphone PCM file exists ({}), will attempt to use it",
+                            if mic_channels == 1 { "mono" } else { "stereo" });
+                        audio_inputs.push(crate::services::ffmpeg::AudioInputConfig {
+                            pcm_path: mic_pcm_path.clone(),
+                            sample_rate: 48000,
+                            channels: mic_channels,
+                            label: "Microphone".to_string(),
+                        });
+                        pcm_files_to_cleanup.push(mic_pcm_path);
+                    }
                 }
             }
         }
-    }
 
-    // Perform audio muxing if we have any audio inputs
-    let final_output_path = if !audio_inputs.is_empty() {
-        info!("Muxing {} audio track(s) with video", audio_inputs.len());
+        // Only the finished file goes to the output folder; intermediates stay in scratch
+        let final_recording_path = screen_recording_output_path(&recording_id)?;
 
-        // Create final output path (replace video-only file)
-        let final_path = output_path
-            .parent()
-            .unwrap()
-            .join(format!(
-                "recording-{}-final.mp4",
-                recording_id
-            ));
+        // Perform audio muxing if we have any audio inputs
+        let final_output_path = if !audio_inputs.is_empty() {
+            info!("Muxing {} audio track(s) with video", audio_inputs.len());
 
-        // Mux video + audio(s)
-        match crate::services::ffmpeg::FFmpegEncoder::finalize_with_audio(
-            output_path.clone(),
-            audio_inputs,
-            final_path.clone(),
-        )
-        .await
-        {
-            Ok(_) => {
-                info!("Audio muxing completed successfully");
+            // Mux straight into the output folder
+            let final_path = final_recording_path.clone();
 
-                // Delete temporary files
-                if let Err(e) = std::fs::remove_file(&output_path) {
-                    warn!("Failed to remove video-only file: {}", e);
-                }
-                for pcm_file in pcm_files_to_cleanup {
-                    if let Err(e) = std::fs::remove_file(&pcm_file) {
-                        warn!("Failed to remove PCM audio file {}: {}", pcm_file.display(), e);
+            // Mux video + audio(s)
+            match crate::services::ffmpeg::FFmpegEncoder::finalize_with_audio(
+                output_path.clone(),
+                audio_inputs,
+                final_path.clone(),
+                normalize_loudness,
+            )
+            .await
+            {
+                Ok(_) => {
+                    info!("Audio muxing completed successfully");
+
+                    // Delete temporary files
+                    if let Err(e) = std::fs::remove_file(&output_path) {
+                        warn!("Failed to remove video-only file: {}", e);
+                    }
+                    for pcm_file in pcm_files_to_cleanup {
+                        if let Err(e) = remove_pcm_file(&pcm_file) {
+                            warn!("Failed to remove PCM audio file {}: {}", pcm_file.display(), e);
+                        }
                     }
-                }
 
-                final_path
-            }
-            Err(e) => {
-                error!("Audio muxing failed: {}", e);
-                warn!("Returning video-only file");
+                    final_path
+                }
+                Err(e) => {
+                    error!("Audio muxing failed: {}", e);
+                    warn!("Returning video-only file");
 
-                // Clean up PCM files even if muxing failed
-                for pcm_file in pcm_files_to_cleanup {
-                    if let Err(e) = std::fs::remove_file(&pcm_file) {
-                        warn!("Failed to remove PCM audio file {}: {}", pcm_file.display(), e);
+                    // Clean up PCM files even if muxing failed
+                    for pcm_file in pcm_files_to_cleanup {
+                        if let Err(e) = remove_pcm_file(&pcm_file) {
+                            warn!("Failed to remove PCM audio file {}: {}", pcm_file.display(), e);
+                        }
                     }
+
+                    promote_to_output(&output_path, &final_recording_path)?;
+                    final_recording_path
                 }
+            }
+        } else {
+            // No audio capture, return video-only file
+            info!("No audio tracks captured, returning video-only file");
+            promote_to_output(&output_path, &final_recording_path)?;
+            final_recording_path
+        };
+
+        // Verify file exists
+        if !final_output_path.exists() {
+            error!("Recording file not found: {}", final_output_path.display());
+            return Err(format!("Recording file not found: {}", final_output_path.display()));
+        }
 
-                output_path.clone()
+        // Encoding can "succeed" yet leave an unplayable file; check before reporting success
+        let (final_output_path, integrity) = verify_recording(&final_output_path).await;
+        match integrity {
+            IntegrityStatus::Verified | IntegrityStatus::Unverified => {}
+            IntegrityStatus::Repaired => {
+                warn!("Recording was unplayable and has been repaired: {}", final_output_path.display());
+            }
+            IntegrityStatus::Degraded => {
+                error!("Recording is unplayable and repair failed: {}", final_output_path.display());
             }
         }
-    } else {
-        // No audio capture, return video-only file
-        info!("No audio tracks captured, returning video-only file");
-        output_path.clone()
-    };
 
-    // Verify file exists
-    if !final_output_path.exists() {
-        error!("Recording file not found: {}", final_output_path.display());
-        return Err(format!("Recording file not found: {}", final_output_path.display()));
-    }
+        let output_path_str = final_output_path.to_string_lossy().to_string();
+        let payload = RecordingIntegrityPayload {
+            recording_id: recording_id.clone(),
+            path: output_path_str.clone(),
+            status: integrity,
+        };
+        if let Err(e) = app_handle.emit("recording-integrity", &payload) {
+            warn!("Failed to emit recording-integrity event: {}", e);
+        }
 
-    let output_path_str = final_output_path.to_string_lossy().to_string();
-    info!("Recording saved successfully: {}", output_path_str);
+        info!("Recording saved successfully ({:?}): {}", integrity, output_path_str);
 
-    Ok(output_path_str)
+        Ok(output_path_str)
+    }
+    .await;
+
+    match capture_abort_reason {
+        Some(reason) => {
+            error!(
+                "Recording {} was aborted by capture: {}",
+                recording_id,
+                ScreenCaptureError::from(reason)
+            );
+            Err(RecordingStopError::CaptureAborted { reason, output_path: finalized.ok() })
+        }
+        None => finalized.map_err(RecordingStopError::from),
+    }
+}
+
+/// Payload for the `recording-integrity` event
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordingIntegrityPayload {
+    recording_id: String,
+    path: String,
+    status: IntegrityStatus,
 }
 
 /// Pause the current recording (Story 4.8 - AC #1)
@@ -1681,6 +2467,8 @@ pub async fn cmd_pause_recording(recording_id: String) -> Result<(), String> {
             _temp_screen_path,
             _temp_webcam_path,
             _mic_audio_path_opt,
+            _system_audio_writer_handle_opt,
+            _system_audio_path_opt,
             _pip_config,
             screen_pause,
             _screen_stop_signal,
@@ -1689,6 +2477,7 @@ pub async fn cmd_pause_recording(recording_id: String) -> Result<(), String> {
         {
             // Pause screen capture (pauses both screen and webcam capture)
             screen_pause.store(true, std::sync::atomic::Ordering::Relaxed);
+            set_recording_clock_paused(&recording_id, true).await;
             info!("PiP recording paused (frame discard enabled): {}", recording_id);
             return Ok(());
         }
@@ -1708,6 +2497,8 @@ pub async fn cmd_pause_recording(recording_id: String) -> Result<(), String> {
         _mic_pcm_path,
         mic_pause_flag,
         _mic_channels,
+        _capture_abort,
+        _normalize_loudness,
     ) = recordings
         .get(&recording_id)
         .ok_or_else(|| {
@@ -1723,6 +2514,8 @@ pub async fn cmd_pause_recording(recording_id: String) -> Result<(), String> {
         mic_pause.store(true, std::sync::atomic::Ordering::Relaxed);
     }
 
+    set_recording_clock_paused(&recording_id, true).await;
+
     info!("Recording paused (frame discard enabled): {}", recording_id);
 
     Ok(())
@@ -1758,6 +2551,8 @@ pub async fn cmd_resume_recording(recording_id: String) -> Result<(), String> {
             _temp_screen_path,
             _temp_webcam_path,
             _mic_audio_path_opt,
+            _system_audio_writer_handle_opt,
+            _system_audio_path_opt,
             _pip_config,
             screen_pause,
             _screen_stop_signal,
@@ -1766,6 +2561,7 @@ pub async fn cmd_resume_recording(recording_id: String) -> Result<(), String> {
         {
             // Resume screen capture (resumes both screen and webcam capture)
             screen_pause.store(false, std::sync::atomic::Ordering::Relaxed);
+            set_recording_clock_paused(&recording_id, false).await;
             info!("PiP recording resumed: {}", recording_id);
             return Ok(());
         }
@@ -1785,6 +2581,8 @@ pub async fn cmd_resume_recording(recording_id: String) -> Result<(), String> {
         _mic_pcm_path,
         mic_pause_flag,
         _mic_channels,
+        _capture_abort,
+        _normalize_loudness,
     ) = recordings
         .get(&recording_id)
         .ok_or_else(|| {
@@ -1800,11 +2598,153 @@ pub async fn cmd_resume_recording(recording_id: String) -> Result<(), String> {
         mic_pause.store(false, std::sync::atomic::Ordering::Relaxed);
     }
 
+    set_recording_clock_paused(&recording_id, false).await;
+
     info!("Recording resumed: {}", recording_id);
 
     Ok(())
 }
 
+/// Mute or unmute one audio track of a screen recording
+///
+/// Unlike pausing, the recording keeps running: the muted track records
+/// silence (so it stays in sync with the video) while the other track is
+/// unaffected, e.g. to cough without cutting system audio. Mutes persist
+/// across pause and resume.
+///
+/// # Arguments
+///
+/// * `recording_id` - The UUID of the screen recording
+/// * `track` - `"system"` or `"microphone"`
+/// * `muted` - Whether the track records silence
+///
+/// # Returns
+///
+/// - `Ok(())` if the track's mute state was set
+/// - `Err(String)` if the recording is not found or isn't recording that track
+#[tauri::command]
+pub async fn cmd_set_audio_track_muted(
+    recording_id: String,
+    track: MutableAudioTrack,
+    muted: bool,
+) -> Result<(), String> {
+    debug!("Command: set {:?} audio muted={} for recording {}", track, muted, recording_id);
+
+    let recordings = ACTIVE_RECORDINGS.lock().await;
+    let (
+        _capture_handle,
+        _encoder_handle,
+        _output_path,
+        _pause_flag,
+        _stop_signal,
+        audio_writer,
+        _audio_pcm_path,
+        mic_writer,
+        _mic_pcm_path,
+        _mic_pause_flag,
+        _mic_channels,
+        _capture_abort,
+        _normalize_loudness,
+    ) = recordings
+        .get(&recording_id)
+        .ok_or_else(|| {
+            error!("Recording not found: {}", recording_id);
+            format!("Recording not found: {}", recording_id)
+        })?;
+
+    let recorded = match track {
+        MutableAudioTrack::System => audio_writer.is_some(),
+        MutableAudioTrack::Microphone => mic_writer.is_some(),
+    };
+    if !recorded {
+        return Err(format!("Recording {} has no {:?} audio track", recording_id, track));
+    }
+    drop(recordings);
+
+    let mutes = AUDIO_TRACK_MUTES.lock().await;
+    let mutes = mutes.get(&recording_id).ok_or_else(|| {
+        error!("Audio track mutes not found: {}", recording_id);
+        format!("Recording not found: {}", recording_id)
+    })?;
+    mutes.flag(track).store(muted, std::sync::atomic::Ordering::Relaxed);
+
+    info!("{:?} audio {} for recording {}", track, if muted { "muted" } else { "unmuted" }, recording_id);
+
+    Ok(())
+}
+
+/// Pause or resume a recording's elapsed-time clock
+async fn set_recording_clock_paused(recording_id: &str, paused: bool) {
+    if let Some(clock) = RECORDING_CLOCKS.lock().await.get(recording_id) {
+        clock.sync_paused(paused);
+    }
+}
+
+/// Look up a recording ID in the screen, PiP and webcam recording maps
+async fn recording_status(recording_id: &str) -> RecordingStatus {
+    // (pause flag, final output path) of whichever kind of recording owns the ID.
+    // Screen and webcam handles only hold scratch intermediates, so report where
+    // stopping will save the file, as PiP does
+    let mut found = ACTIVE_RECORDINGS
+        .lock()
+        .await
+        .get(recording_id)
+        .map(|handle| {
+            let paused = handle.3.load(std::sync::atomic::Ordering::Relaxed);
+            (paused, screen_recording_output_path(recording_id).ok())
+        });
+    if found.is_none() {
+        found = ACTIVE_PIP_RECORDINGS
+            .lock()
+            .await
+            .get(recording_id)
+            .map(|handle| (handle.12.load(std::sync::atomic::Ordering::Relaxed), Some(handle.5.clone())));
+    }
+    if found.is_none() {
+        // Webcam recordings can't be paused
+        found = ACTIVE_WEBCAM_RECORDINGS
+            .lock()
+            .await
+            .get(recording_id)
+            .map(|_| (false, webcam_recording_output_path(recording_id).ok()));
+    }
+
+    let Some((paused, output_path)) = found else {
+        return RecordingStatus::not_found();
+    };
+
+    let elapsed_ms = match RECORDING_CLOCKS.lock().await.get(recording_id) {
+        Some(clock) => {
+            clock.sync_paused(paused);
+            clock.elapsed().as_millis() as u64
+        }
+        None => 0,
+    };
+
+    RecordingStatus {
+        state: if paused { RecordingState::Paused } else { RecordingState::Recording },
+        elapsed_ms,
+        output_path: output_path.map(|path| path.to_string_lossy().to_string()),
+    }
+}
+
+/// Get whether a recording is running or paused, and for how long
+///
+/// Works for screen, webcam and PiP recordings. Elapsed time excludes paused spans.
+///
+/// # Arguments
+///
+/// * `recording_id` - The UUID returned when the recording was started
+///
+/// # Returns
+///
+/// - `Ok(RecordingStatus)` with state `not_found` for unknown or stopped recordings
+#[tauri::command]
+pub async fn cmd_get_recording_status(recording_id: String) -> Result<RecordingStatus, String> {
+    debug!("Command: get recording status {}", recording_id);
+    Ok(recording_status(&recording_id).await)
+}
+
 /// Cancel the current recording (discards partial recording)
 ///
 /// This command cancels the active recording and deletes the partial MP4 file.
@@ -1821,6 +2761,14 @@ pub async fn cmd_resume_recording(recording_id: String) -> Result<(), String> {
 pub async fn cmd_cancel_recording(recording_id: String) -> Result<(), String> {
     debug!("Command: cancel recording {}", recording_id);
 
+    // Still counting down: cmd_start_screen_recording cleans up and returns an error
+    if let Some(cancel_flag) = COUNTDOWN_CANCEL_FLAGS.lock().await.get(&recording_id) {
+        info!("Cancelling recording countdown: {}", recording_id);
+        cancel_flag.store(true, std::sync::atomic::Ordering::Relaxed);
+        return Ok(());
+    }
+    abort_low_disk_monitor(&recording_id).await;
+
     let mut recordings = ACTIVE_RECORDINGS.lock().await;
     let (
         capture_handle,
@@ -1834,16 +2782,20 @@ pub async fn cmd_cancel_recording(recording_id: String) -> Result<(), String> {
         mic_pcm_path_opt,
         _mic_pause_flag,
         _mic_channels_opt,
+        _capture_abort,
+        _normalize_loudness,
     ) = recordings.remove(&recording_id).ok_or_else(|| {
         error!("Recording not found: {}", recording_id);
         format!("Recording not found: {}", recording_id)
     })?;
-
-    // Note: AudioCapture is intentionally leaked (see start_recording)
-    // Close the microphone channel by dropping the sender
-    if let Some(_) = mic_writer_handle_opt {
-        let mic_sender = MICROPHONE_SENDERS.lock().await.remove(&recording_id);
-        drop(mic_sender);
+    remove_live_waveform(&recording_id);
+    RECORDING_CLOCKS.lock().await.remove(&recording_id);
+    AUDIO_TRACK_MUTES.lock().await.remove(&recording_id);
+
+    // Stop the microphone stream
+    let mic_capture = MICROPHONE_CAPTURES.lock().await.remove(&recording_id);
+    if let Some(mic_capture) = mic_capture {
+        mic_capture.release().await;
     }
 
     // Signal capture task to stop
@@ -1884,7 +2836,7 @@ pub async fn cmd_cancel_recording(recording_id: String) -> Result<(), String> {
     // Delete the partial audio PCM file if present
     if let Some(audio_pcm_path) = audio_pcm_path_opt {
         if audio_pcm_path.exists() {
-            match std::fs::remove_file(&audio_pcm_path) {
+            match remove_pcm_file(&audio_pcm_path) {
                 Ok(()) => {
                     info!("Deleted partial audio PCM file: {}", audio_pcm_path.display());
                 }
@@ -1898,7 +2850,7 @@ pub async fn cmd_cancel_recording(recording_id: String) -> Result<(), String> {
     // Delete the partial microphone PCM file if present
     if let Some(mic_pcm_path) = mic_pcm_path_opt {
         if mic_pcm_path.exists() {
-            match std::fs::remove_file(&mic_pcm_path) {
+            match remove_pcm_file(&mic_pcm_path) {
                 Ok(()) => {
                     info!("Deleted partial microphone PCM file: {}", mic_pcm_path.display());
                 }
@@ -1937,6 +2889,27 @@ pub async fn cmd_list_microphones() -> Result<Vec<String>, String> {
     Ok(device_names)
 }
 
+/// Get the rolling waveform of the last few seconds of recording audio
+///
+/// Peaks are max-abs levels (0.0 to 1.0), oldest first, at
+/// `peaksPerSecond` resolution, for drawing a scrolling meter. A source that
+/// isn't being recorded returns an empty array.
+///
+/// # Arguments
+///
+/// * `recording_id` - ID of an active screen recording
+///
+/// # Returns
+///
+/// - `Ok(LiveWaveformSnapshot)` with microphone and system audio peaks
+/// - `Err(String)` if the recording is not active
+#[tauri::command]
+pub async fn cmd_get_live_waveform(recording_id: String) -> Result<LiveWaveformSnapshot, String> {
+    get_live_waveform(&recording_id)
+        .map(|waveform| waveform.snapshot())
+        .ok_or_else(|| format!("Recording not found: {}", recording_id))
+}
+
 /// Check available disk space at the given path
 ///
 /// This command checks how much disk space is available at the specified path.
@@ -1953,52 +2926,144 @@ pub async fn cmd_list_microphones() -> Result<Vec<String>, String> {
 pub async fn cmd_check_disk_space(path: String) -> Result<u64, String> {
     debug!("Command: check disk space at {}", path);
 
-    use std::path::Path;
+    use std::path::Path;
+
+    let path = Path::new(&path);
+
+    // Ensure path exists
+    if !path.exists() {
+        // Try to create it
+        prepare_output_dir(path).map_err(|e| e.to_string())?;
+    }
+
+    match available_bytes(path) {
+        Ok(available_bytes) => {
+            info!("Available disk space: {} bytes ({} MB)", available_bytes, available_bytes / (1024 * 1024));
+            Ok(available_bytes)
+        }
+        Err(e) => {
+            error!("statvfs failed: {}", e);
+            Err(format!("Failed to get disk space: {}", e))
+        }
+    }
+}
+
+/// Payload for the `recording-auto-stopped` event (and the deprecated `stopped-low-disk`)
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordingAutoStoppedPayload {
+    recording_id: String,
+    /// Why the recording was stopped (`"low_disk"`)
+    reason: &'static str,
+    available_bytes: u64,
+    floor_bytes: u64,
+    /// Saved recording, if the graceful stop succeeded
+    output_path: Option<String>,
+}
+
+/// Whether free space has dropped below the configured floor (a floor of 0 never stops)
+fn below_free_space_floor(available_bytes: u64, floor_bytes: u64) -> bool {
+    floor_bytes > 0 && available_bytes < floor_bytes
+}
+
+/// Poll `read_free_bytes` every `interval` until it drops below `floor_bytes`
+///
+/// Returns the reading that crossed the floor, or `None` once a reading fails
+/// (the monitor gives up rather than stopping a recording it can't measure).
+async fn wait_for_low_disk<F>(mut read_free_bytes: F, floor_bytes: u64, interval: std::time::Duration) -> Option<u64>
+where
+    F: FnMut() -> Result<u64, String>,
+{
+    loop {
+        tokio::time::sleep(interval).await;
+        match read_free_bytes() {
+            Ok(available_bytes) if below_free_space_floor(available_bytes, floor_bytes) => {
+                return Some(available_bytes)
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!("Low disk monitor stopped: {}", e);
+                return None;
+            }
+        }
+    }
+}
+
+/// Watch free space while a recording is active
+///
+/// Checks `dirs` every `interval`. When the lowest reading drops below
+/// `floor_bytes` the recording is stopped through its normal stop command (so
+/// the file is finalized rather than truncated) and `recording-auto-stopped`
+/// is emitted with reason `"low_disk"`, followed by the deprecated
+/// `stopped-low-disk` for listeners that predate it. Stopping the recording any
+/// other way aborts the monitor (see `abort_low_disk_monitor`).
+async fn spawn_low_disk_monitor(
+    recording: ShutdownStop,
+    dirs: Vec<PathBuf>,
+    floor_bytes: u64,
+    interval: std::time::Duration,
+    app_handle: tauri::AppHandle,
+) {
+    if floor_bytes == 0 {
+        return;
+    }
+
+    let recording_id = recording.id().to_string();
+    let monitor_id = recording_id.clone();
+    let handle = tokio::spawn(async move {
+        let read_free_bytes = || {
+            dirs.iter().try_fold(u64::MAX, |lowest, dir| {
+                available_bytes(dir)
+                    .map(|bytes| lowest.min(bytes))
+                    .map_err(|e| format!("{}: {}", dir.display(), e))
+            })
+        };
+        let Some(available) = wait_for_low_disk(read_free_bytes, floor_bytes, interval).await else {
+            LOW_DISK_MONITORS.lock().await.remove(&monitor_id);
+            return;
+        };
 
-    let path = Path::new(&path);
+        // Deregister first: the stop below would otherwise abort this task mid-stop
+        LOW_DISK_MONITORS.lock().await.remove(&monitor_id);
 
-    // Ensure path exists
-    if !path.exists() {
-        // Try to create it
-        if let Err(e) = std::fs::create_dir_all(path) {
-            error!("Failed to create directory {}: {}", path.display(), e);
-            return Err(format!("Failed to create directory: {}", e));
-        }
-    }
+        warn!(
+            "Free space {} MB below floor {} MB, stopping recording {}",
+            available / (1024 * 1024),
+            floor_bytes / (1024 * 1024),
+            monitor_id
+        );
 
-    // Get disk space info using statvfs on macOS
-    #[cfg(target_os = "macos")]
-    {
-        use std::ffi::CString;
-        use std::os::unix::ffi::OsStrExt;
-
-        let path_cstr = CString::new(path.as_os_str().as_bytes())
-            .map_err(|e| format!("Invalid path: {}", e))?;
-
-        // SAFETY: FFI call to libc::statvfs is safe because:
-        // 1. statvfs is a well-defined POSIX API with stable ABI
-        // 2. path_cstr is a valid, null-terminated CString with no interior nulls
-        // 3. stat is properly zero-initialized memory of the correct size
-        // 4. The function only reads from path_cstr and writes to stat
-        unsafe {
-            let mut stat: libc::statvfs = std::mem::zeroed();
-            if libc::statvfs(path_cstr.as_ptr(), &mut stat) == 0 {
-                // Convert to u64 to avoid overflow and match return type
-                let available_bytes = stat.f_bavail as u64 * stat.f_frsize as u64;
-                info!("Available disk space: {} bytes ({} MB)", available_bytes, available_bytes / (1024 * 1024));
-                Ok(available_bytes)
-            } else {
-                let err = std::io::Error::last_os_error();
-                error!("statvfs failed: {}", err);
-                Err(format!("Failed to get disk space: {}", err))
+        let output_path = match stop_recording_entry(recording, app_handle.clone()).await {
+            Ok(path) => Some(path),
+            Err(e) => {
+                error!("Low disk stop failed for {}: {}", monitor_id, e);
+                None
             }
+        };
+
+        let payload = RecordingAutoStoppedPayload {
+            recording_id: monitor_id,
+            reason: "low_disk",
+            available_bytes: available,
+            floor_bytes,
+            output_path,
+        };
+        if let Err(e) = app_handle.emit("recording-auto-stopped", payload.clone()) {
+            warn!("Failed to emit recording-auto-stopped event: {}", e);
         }
-    }
+        // Deprecated name, kept until every listener has moved to recording-auto-stopped
+        if let Err(e) = app_handle.emit("stopped-low-disk", payload) {
+            warn!("Failed to emit stopped-low-disk event: {}", e);
+        }
+    });
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        error!("Disk space check not implemented for this platform");
-        Err("Disk space check only supported on macOS".to_string())
+    LOW_DISK_MONITORS.lock().await.insert(recording_id, handle);
+}
+
+/// Abort the low disk monitor of a recording that is being stopped
+async fn abort_low_disk_monitor(recording_id: &str) {
+    if let Some(handle) = LOW_DISK_MONITORS.lock().await.remove(recording_id) {
+        handle.abort();
     }
 }
 
@@ -2108,6 +3173,29 @@ pub async fn cmd_get_available_windows() -> Result<Vec<crate::models::recording:
     }
 }
 
+/// Get list of connected displays for fullscreen recording
+///
+/// Pass a display's `display_id` as `RecordingConfig::display_id` to record it
+/// instead of the primary display.
+///
+/// # Returns
+///
+/// - `Ok(Vec<DisplayInfo>)` with one entry per display, exactly one marked primary
+/// - `Err(String)` with user-friendly error message if enumeration fails
+#[tauri::command]
+pub async fn cmd_get_available_displays() -> Result<Vec<DisplayInfo>, String> {
+    debug!("Command: get available displays");
+
+    let displays = list_display_bounds().map_err(|e| {
+        error!("Failed to enumerate displays: {}", e);
+        format!("Failed to enumerate displays: {}", e)
+    })?;
+
+    let display_list = display_infos(&displays);
+    info!("Found {} available displays", display_list.len());
+    Ok(display_list)
+}
+
 /// Start Picture-in-Picture (PiP) recording - screen + webcam simultaneously
 ///
 /// This command starts a PiP recording session that captures both the screen and webcam
@@ -2121,11 +3209,18 @@ pub async fn cmd_get_available_windows() -> Result<Vec<crate::models::recording:
 /// * `pip_width` - Width of the PiP overlay in pixels
 /// * `pip_height` - Height of the PiP overlay in pixels
 /// * `output_path` - Path where the composited MP4 will be saved
+/// * `pip_shape` - Optional webcam overlay shape (defaults to rectangle)
+/// * `system_audio` - Capture system audio (defaults to `RecordingConfig::default().system_audio`)
+/// * `microphone` - Capture the default microphone (defaults to on when permission is granted)
+/// * `mic_gain` - Linear gain applied to microphone samples, 0.0 to 10.0 (defaults to 1.0)
+///
+/// When both audio sources are captured they are mixed into one track during
+/// the final composition.
 ///
 /// # Returns
 ///
 /// * `Ok(recording_id)` - Unique ID for this recording session
-/// * `Err(String)` - User-friendly error message if recording fails to start
+/// * `Err(RecordingStartError)` - `{ code, message }` if recording fails to start
 ///
 /// # Story 4.6 - Acceptance Criteria
 ///
@@ -2139,7 +3234,41 @@ pub async fn cmd_start_pip_recording(
     pip_width: u32,
     pip_height: u32,
     output_path: String,
-) -> Result<String, String> {
+    pip_shape: Option<PipShape>,
+    system_audio: Option<bool>,
+    microphone: Option<bool>,
+    mic_gain: Option<f32>,
+    pip_corner_radius: Option<u32>,
+    pip_border: Option<(u32, String)>,
+    pip_scale: Option<f32>,
+    min_free_space_mb: Option<u64>,
+    crf: Option<u8>,
+    preset: Option<crate::models::Preset>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, RecordingStartError> {
+    ensure_ffmpeg_ready()?;
+    let system_audio = system_audio.unwrap_or(RecordingConfig::default().system_audio);
+    let microphone = microphone.unwrap_or(true);
+    let mic_gain = resolve_mic_gain(mic_gain)?;
+    let min_free_space_mb = min_free_space_mb.unwrap_or(RecordingConfig::default().min_free_space_mb);
+
+    // Create PiP configuration (saved for later composition)
+    let pip_config = PipConfig {
+        x: pip_x,
+        y: pip_y,
+        width: pip_width,
+        height: pip_height,
+        shape: pip_shape.unwrap_or_default(),
+        corner_radius: pip_corner_radius.unwrap_or(0),
+        border: pip_border,
+    };
+    pip_config.validate_style()?;
+    let pip_scale = pip_scale.unwrap_or(1.0);
+    if !pip_scale.is_finite() || pip_scale <= 0.0 {
+        return Err(RecordingStartError::Failed(format!("Invalid PiP scale {}: must be greater than 0", pip_scale)));
+    }
+    let pip_encoding = PipEncoding::new(crf, preset)?;
+
     debug!(
         "Command: start PiP recording (camera: {}, pip: {}x{} at ({},{}), output: {})",
         camera_index, pip_width, pip_height, pip_x, pip_y, output_path
@@ -2147,11 +3276,11 @@ pub async fn cmd_start_pip_recording(
 
     // Check permissions
     if !check_screen_recording_permission().map_err(|e| e.to_string())? {
-        return Err("Screen recording permission required. Please enable in System Preferences → Privacy & Security → Screen Recording".to_string());
+        return Err(RecordingStartError::Failed("Screen recording permission required. Please enable in System Preferences → Privacy & Security → Screen Recording".to_string()));
     }
 
     if !check_camera_permission().map_err(|e| e.to_string())? {
-        return Err("Camera permission required. Please enable in System Preferences → Privacy & Security → Camera".to_string());
+        return Err(RecordingStartError::Failed("Camera permission required. Please enable in System Preferences → Privacy & Security → Camera".to_string()));
     }
 
     // Generate recording ID early
@@ -2163,24 +3292,32 @@ pub async fn cmd_start_pip_recording(
 
     // Create output path with proper directory
     let output_path_buf = PathBuf::from(&output_path);
-    if let Some(parent) = output_path_buf.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| {
-            error!("Failed to create output directory: {}", e);
-            format!("Failed to create output directory: {}", e)
-        })?;
-    }
+    prepare_output_path(&output_path_buf)?;
 
     info!("Output path: {}", output_path);
 
     // Initialize screen capture
-    let mut screen_capture = ScreenCapture::new(None).map_err(|e| {
+    let mut screen_capture = ScreenCapture::new(None, None).map_err(|e| {
         error!("Failed to initialize screen capture: {}", e);
         format!("Screen capture initialization failed: {}", e)
     })?;
 
+    // System audio failures are non-fatal: the composite keeps whatever audio is left
+    if system_audio {
+        let enabled = screen_capture
+            .enable_system_audio(PIP_SYSTEM_AUDIO_SAMPLE_RATE, PIP_SYSTEM_AUDIO_CHANNELS)
+            .map_err(|e| format!("Failed to enable system audio: {}", e));
+        if degrade_audio_failure("system", enabled, Some(&app_handle)).is_some() {
+            info!("System audio enabled for PiP recording");
+        }
+    }
+
     let (screen_width, screen_height) = screen_capture.get_dimensions();
     info!("Screen dimensions: {}x{}", screen_width, screen_height);
 
+    // Final overlay geometry, kept on screen; composition uses it as is
+    let pip_config = pip_config.fit_to_screen(pip_scale, screen_width, screen_height);
+
     // Initialize camera capture using CameraBackend (AVFoundation on macOS)
     // Use 1080p resolution for webcam capture (sufficient for PiP overlay)
     let webcam_width = 1920u32;
@@ -2190,31 +3327,30 @@ pub async fn cmd_start_pip_recording(
         .await
         .map_err(|e| {
             error!("Failed to initialize camera: {}", e);
-            format!("Camera initialization failed: {}", e)
+            camera_init_error(&e)
         })?;
 
     info!("Camera dimensions: {}x{} @ 30fps", webcam_width, webcam_height);
 
-    // Create PiP configuration (saved for later composition)
-    let pip_config = PipConfig {
-        x: pip_x,
-        y: pip_y,
-        width: pip_width,
-        height: pip_height,
-    };
-
     // NEW ARCHITECTURE: Record screen and webcam to SEPARATE temporary files
     // Then composite them when stopping (avoids FIFO deadlock issues)
 
-    // Create temporary file paths
-    let temp_screen_path = output_path_buf.with_file_name(format!(
-        "{}-screen-temp.mp4",
-        output_path_buf.file_stem().unwrap().to_string_lossy()
-    ));
-    let temp_webcam_path = output_path_buf.with_file_name(format!(
-        "{}-webcam-temp.mp4",
-        output_path_buf.file_stem().unwrap().to_string_lossy()
-    ));
+    // Create temporary file paths in scratch space (the output folder only gets the composite)
+    let scratch_dir = prepare_scratch_dir(None)?;
+    let scratch_stem = format!("pip-{}", recording_id);
+
+    // Refuse to start rather than fill the disk mid-recording (screen and webcam
+    // are encoded separately, both at 30 FPS, and composited on stop)
+    let audio_tracks = system_audio as u32 + microphone as u32;
+    let required_bytes = required_free_bytes(
+        estimate_bytes_per_minute(screen_width, screen_height, 30, audio_tracks)
+            + estimate_bytes_per_minute(webcam_width, webcam_height, 30, 0),
+        min_free_space_mb,
+    );
+    let output_dir = output_path_buf.parent().unwrap_or(&scratch_dir).to_path_buf();
+    ensure_free_space(&[&scratch_dir, &output_dir], required_bytes)?;
+    let temp_screen_path = scratch_file(&scratch_dir, &scratch_stem, "screen-temp.mp4");
+    let temp_webcam_path = scratch_file(&scratch_dir, &scratch_stem, "webcam-temp.mp4");
 
     info!(
         "Recording to temp files: screen={}, webcam={}",
@@ -2264,9 +3400,42 @@ pub async fn cmd_start_pip_recording(
     let (screen_video_tx, mut screen_video_rx) = mpsc::channel::<TimestampedFrame>(30);
     let (webcam_video_tx, mut webcam_video_rx) = mpsc::channel::<TimestampedFrame>(30);
 
+    // System audio goes to a raw PCM file in scratch space, mixed in at composition
+    let (system_audio_tx_opt, system_audio_writer_handle_opt, system_audio_path_opt) =
+        if screen_capture.is_system_audio_enabled() {
+            let system_audio_path = scratch_file(&scratch_dir, &scratch_stem, "system-audio.pcm");
+            info!("System audio PCM path: {}", system_audio_path.display());
+
+            let (audio_tx, mut audio_rx) = mpsc::channel::<AudioSample>(100);
+            let pcm_path = system_audio_path.clone();
+            let writer_handle = tokio::spawn(async move {
+                let mut writer = crate::services::audio_capture::PcmFileWriter::new(&pcm_path)
+                    .map_err(|e| format!("Failed to create PCM writer: {}", e))?;
+
+                while let Some(sample) = audio_rx.recv().await {
+                    writer.write_sample(&sample).map_err(|e| {
+                        error!("Failed to write system audio sample: {}", e);
+                        format!("Failed to write system audio sample: {}", e)
+                    })?;
+                }
+
+                writer.finalize().map_err(|e| {
+                    error!("Failed to finalize system audio PCM file: {}", e);
+                    format!("Failed to finalize system audio PCM file: {}", e)
+                })?;
+
+                info!("System audio writer task completed");
+                Ok::<(), String>(())
+            });
+
+            (Some(audio_tx), Some(writer_handle), Some(system_audio_path))
+        } else {
+            (None, None, None)
+        };
+
     // Start screen capture task
     let screen_capture_handle = screen_capture
-        .start_continuous_capture(screen_video_tx, None, None)
+        .start_continuous_capture(screen_video_tx, system_audio_tx_opt, None)
         .map_err(|e| {
             error!("Failed to start screen capture: {}", e);
             format!("Failed to start screen capture: {}", e)
@@ -2325,48 +3494,49 @@ pub async fn cmd_start_pip_recording(
     });
 
     // Capture microphone audio for voiceover
-    let mic_audio_path = output_path_buf.with_file_name(format!(
-        "{}-mic-audio.wav",
-        output_path_buf.file_stem().unwrap().to_string_lossy()
-    ));
+    let mic_audio_path = scratch_file(&scratch_dir, &scratch_stem, "mic-audio.wav");
 
-    let mic_writer_handle_opt = if check_microphone_permission().map_err(|e| e.to_string())? {
+    let mic_writer_handle_opt = if !microphone {
+        info!("Microphone disabled for PiP recording");
+        None
+    } else if check_microphone_permission().map_err(|e| e.to_string())? {
         info!("Starting microphone audio capture for PiP");
 
-        let (mic_tx, handle) = {
-            // Create AudioCapture instance in a block to limit its scope
-            let mut audio_capture = AudioCapture::new().map_err(|e| {
-                error!("Failed to initialize microphone capture: {}", e);
-                format!("Failed to initialize microphone capture: {}", e)
-            })?;
+        let (mic_capture, handle) = {
+            // Create bounded channel for audio samples
+            let (mic_tx, mut mic_rx) = mpsc::channel::<AudioSample>(100);
 
-            // Select default microphone device
-            let device_name = audio_capture.select_default_device().map_err(|e| {
-                error!("Failed to select default microphone device: {}", e);
-                format!("Failed to select microphone device: {}", e)
-            })?;
+            // AudioCapture isn't Send, so the guard creates and owns it on its own thread
+            let (mic_capture, ()) = AudioCaptureGuard::spawn(move || {
+                let mut audio_capture = AudioCapture::new().map_err(|e| {
+                    error!("Failed to initialize microphone capture: {}", e);
+                    format!("Failed to initialize microphone capture: {}", e)
+                })?;
 
-            info!("Selected microphone: {}", device_name);
+                // Select default microphone device
+                let device_name = audio_capture.select_default_device().map_err(|e| {
+                    error!("Failed to select default microphone device: {}", e);
+                    format!("Failed to select microphone device: {}", e)
+                })?;
 
-            // Get channel count and sample rate from audio capture
-            let mic_channels = audio_capture.get_channels().map_err(|e| {
-                error!("Failed to get microphone channel count: {}", e);
-                format!("Failed to get microphone channel count: {}", e)
-            })?;
+                info!("Selected microphone: {}", device_name);
 
-            info!("Microphone channels: {}", mic_channels);
+                // Get channel count and sample rate from audio capture
+                let mic_channels = audio_capture.get_channels().map_err(|e| {
+                    error!("Failed to get microphone channel count: {}", e);
+                    format!("Failed to get microphone channel count: {}", e)
+                })?;
 
-            // Create bounded channel for audio samples
-            let (mic_tx, mut mic_rx) = mpsc::channel::<AudioSample>(100);
+                info!("Microphone channels: {}", mic_channels);
 
-            // Clone mic_tx before passing to start_capture (we need to keep one to close the channel later)
-            let mic_tx_for_storage = mic_tx.clone();
+                audio_capture.start_capture(mic_tx).map_err(|e| {
+                    error!("Failed to start microphone capture: {}", e);
+                    format!("Failed to start microphone capture: {}", e)
+                })?;
 
-            // Start capture with the original sender
-            audio_capture.start_capture(mic_tx).map_err(|e| {
-                error!("Failed to start microphone capture: {}", e);
-                format!("Failed to start microphone capture: {}", e)
-            })?;
+                Ok((audio_capture, ()))
+            })
+            .await?;
 
             // Spawn blocking task to handle audio capture
             let mic_audio_path_clone = mic_audio_path.clone();
@@ -2394,13 +3564,7 @@ pub async fn cmd_start_pip_recording(
                     }
 
                     if let Some(ref mut writer) = writer_opt {
-                        // Apply gain to increase volume (2.5x boost like webcam recording)
-                        let gain = 2.5;
-                        let boosted_samples: Vec<f32> = sample
-                            .data
-                            .iter()
-                            .map(|&s| (s * gain).max(-1.0).min(1.0))
-                            .collect();
+                        let boosted_samples = apply_mic_gain(&sample.data, mic_gain);
 
                         if let Err(e) = writer.write_samples(&boosted_samples) {
                             error!("Failed to write microphone audio samples: {}", e);
@@ -2419,15 +3583,11 @@ pub async fn cmd_start_pip_recording(
                 }
             });
 
-            // Leak AudioCapture (it's not Send, so we can't store it)
-            // It will be cleaned up when the process exits
-            std::mem::forget(audio_capture);
-
-            (mic_tx_for_storage, handle)
-        }; // audio_capture binding is dropped here, safe to await
+            (mic_capture, handle)
+        };
 
-        // Store mic sender in global state so it can be closed on stop
-        MICROPHONE_SENDERS.lock().await.insert(recording_id.clone(), mic_tx);
+        // Keep the microphone capture until the recording is stopped
+        MICROPHONE_CAPTURES.lock().await.insert(recording_id.clone(), mic_capture);
 
         Some(handle)
     } else {
@@ -2457,15 +3617,30 @@ pub async fn cmd_start_pip_recording(
             temp_screen_path.clone(),      // Temp screen file
             temp_webcam_path.clone(),      // Temp webcam file
             mic_audio_path_opt,            // Mic audio path
+            system_audio_writer_handle_opt, // System audio writer
+            system_audio_path_opt,         // System audio PCM path
             pip_config.clone(),            // PiP config for composition
+            pip_encoding,                  // Encoder settings for composition
             screen_pause_flag,             // Pause flag
             screen_stop_signal,            // Stop signal
             camera_capture,                // Camera object (prevents Drop)
         ),
     );
+    drop(recordings);
+    RECORDING_CLOCKS.lock().await.insert(recording_id.clone(), Arc::new(PausableClock::new()));
 
     info!("PiP recording started successfully: {}", recording_id);
 
+    // Stop before the disk fills up and leaves a truncated file
+    spawn_low_disk_monitor(
+        ShutdownStop::Pip(recording_id.clone()),
+        vec![scratch_dir, output_dir],
+        min_free_space_mb.saturating_mul(1024 * 1024),
+        std::time::Duration::from_secs(RecordingConfig::default().disk_check_interval_secs),
+        app_handle,
+    )
+    .await;
+
     Ok(recording_id)
 }
 
@@ -2491,12 +3666,16 @@ pub async fn cmd_start_pip_recording(
 /// 4. Wait for webcam capture task to complete
 /// 5. Wait for screen encoding task to complete (finalizes temp screen file)
 /// 6. Wait for webcam encoding task to complete (finalizes temp webcam file)
-/// 7. Use FFmpeg to composite temp files into final PiP video
+/// 7. Use FFmpeg to composite temp files into final PiP video (emits `pip-composition-progress`)
 /// 8. Clean up temp files
 /// 9. Return final file path
 #[tauri::command]
-pub async fn cmd_stop_pip_recording(recording_id: String) -> Result<String, String> {
+pub async fn cmd_stop_pip_recording(
+    recording_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
     debug!("Command: stop PiP recording {}", recording_id);
+    abort_low_disk_monitor(&recording_id).await;
 
     // Remove recording from active state (NEW ARCHITECTURE - separate encoding tasks)
     let mut recordings = ACTIVE_PIP_RECORDINGS.lock().await;
@@ -2510,7 +3689,10 @@ pub async fn cmd_stop_pip_recording(recording_id: String) -> Result<String, Stri
         temp_screen_path,
         temp_webcam_path,
         mic_audio_path_opt,
+        system_audio_writer_handle_opt,
+        mut system_audio_path_opt,
         pip_config,
+        pip_encoding,
         _screen_pause,
         screen_stop_signal,
         mut camera_capture,  // Take ownership to trigger Drop after recording completes
@@ -2523,6 +3705,7 @@ pub async fn cmd_stop_pip_recording(recording_id: String) -> Result<String, Stri
 
     // Release lock before awaiting
     drop(recordings);
+    RECORDING_CLOCKS.lock().await.remove(&recording_id);
 
     info!("Stopping PiP recording: {}", recording_id);
 
@@ -2577,14 +3760,14 @@ pub async fn cmd_stop_pip_recording(recording_id: String) -> Result<String, Stri
 
     // Close microphone channel and wait for audio writer task
     if let Some(mic_writer_handle) = mic_writer_handle_opt {
-        // Close the microphone channel by dropping the sender
-        let mic_sender = MICROPHONE_SENDERS.lock().await.remove(&recording_id);
-        drop(mic_sender);
+        // Stop the microphone stream, closing its channel so the writer task can finish
+        let mic_capture = MICROPHONE_CAPTURES.lock().await.remove(&recording_id);
+        if let Some(mic_capture) = mic_capture {
+            mic_capture.release().await;
+        }
         info!("Microphone channel closed");
 
         // Wait for microphone audio writer task to complete (with timeout)
-        // Note: AudioCapture is leaked with std::mem::forget, so the channel may never close
-        // However, the WAV file should be complete since we already closed our sender clone
         let mic_timeout = tokio::time::timeout(
             std::time::Duration::from_secs(5),
             mic_writer_handle
@@ -2598,12 +3781,28 @@ pub async fn cmd_stop_pip_recording(recording_id: String) -> Result<String, Stri
                 warn!("Microphone audio writer task join error: {}", e);
             }
             Err(_) => {
-                warn!("Microphone audio writer task timed out after 5s (AudioCapture is leaked, channel won't close)");
+                warn!("Microphone audio writer task timed out after 5s");
                 warn!("WAV file should be complete - proceeding with composition");
             }
         }
     }
 
+    // Screen capture has ended, so its audio sender is gone and the PCM writer finalizes
+    if let Some(system_audio_writer_handle) = system_audio_writer_handle_opt {
+        match system_audio_writer_handle.await {
+            Ok(Ok(())) => info!("System audio writer task completed"),
+            Ok(Err(e)) => {
+                warn!("System audio writer task failed, composing without it: {}", e);
+                system_audio_path_opt = None;
+            }
+            Err(e) => {
+                warn!("System audio writer task join error, composing without it: {}", e);
+                system_audio_path_opt = None;
+            }
+        }
+    }
+    let system_audio_path_opt = system_audio_path_opt.filter(|path| path.exists());
+
     // Verify temp files exist
     if !temp_screen_path.exists() {
         error!("Temp screen file not found: {}", temp_screen_path.display());
@@ -2617,72 +3816,54 @@ pub async fn cmd_stop_pip_recording(recording_id: String) -> Result<String, Stri
     info!("Both temp files exist, starting composition");
     info!("  Screen: {}", temp_screen_path.display());
     info!("  Webcam: {}", temp_webcam_path.display());
+    if let Some(ref system_audio_path) = system_audio_path_opt {
+        info!("  System audio: {}", system_audio_path.display());
+    }
     if let Some(ref mic_path) = mic_audio_path_opt {
         info!("  Microphone: {}", mic_path.display());
     }
     info!("  Output: {}", output_path.display());
 
     // Composite the two temp files using FFmpeg
-    // Scale webcam to 1.5x larger for better visibility
-    let scaled_pip_width = (pip_config.width as f32 * 1.5) as u32;
-    let scaled_pip_height = (pip_config.height as f32 * 1.5) as u32;
-
-    info!("Scaling webcam from {}x{} to {}x{} (1.5x larger)",
-        pip_config.width, pip_config.height, scaled_pip_width, scaled_pip_height);
-
-    // Use filter_complex to overlay webcam on screen at specified position
-    let mut ffmpeg_command = tokio::process::Command::new("ffmpeg");
-
-    ffmpeg_command
-        .arg("-i").arg(&temp_screen_path)      // Input 0: screen video
-        .arg("-i").arg(&temp_webcam_path);     // Input 1: webcam video
-
-    // Add microphone audio as input 2 if available
-    if let Some(ref mic_path) = mic_audio_path_opt {
-        ffmpeg_command.arg("-i").arg(mic_path);  // Input 2: microphone audio
-    }
-
-    ffmpeg_command
-        .arg("-filter_complex")
-        .arg(format!(
-            "[1:v]scale={}:{}[pip];[0:v][pip]overlay={}:{}",
-            scaled_pip_width,
-            scaled_pip_height,
-            pip_config.x,
-            pip_config.y
-        ))
-        .arg("-c:v").arg("libx264")            // Video codec
-        .arg("-preset").arg("ultrafast")        // Fast encoding
-        .arg("-crf").arg("23");                 // Quality
-
-    // Map audio: use microphone if available, otherwise no audio
-    // Note: Don't use -map for video because filter_complex output is used automatically
-    if mic_audio_path_opt.is_some() {
-        ffmpeg_command
-            .arg("-map").arg("2:a")             // Map audio from microphone (input 2)
-            .arg("-c:a").arg("aac")             // Audio codec
-            .arg("-b:a").arg("192k");           // Audio bitrate
-    } else {
-        ffmpeg_command.arg("-an");              // No audio
-    }
+    // Overlay webcam on screen; system audio and microphone are mixed when both exist
+    let composition_args = build_pip_composition_args(
+        &temp_screen_path,
+        &temp_webcam_path,
+        PipAudioInputs {
+            system_audio_pcm: system_audio_path_opt.as_deref(),
+            microphone_wav: mic_audio_path_opt.as_deref(),
+        },
+        &build_styled_pip_filter(&pip_config, pip_config.width, pip_config.height),
+        pip_encoding,
+        &output_path,
+    );
 
-    ffmpeg_command
-        .arg("-y")                              // Overwrite output
-        .arg(&output_path);
+    // The screen file's length is the composite's length; without it progress is indeterminate
+    let duration_ms = match crate::services::ffmpeg::repair::probe_recording(&temp_screen_path).await {
+        Ok(probe) => probe.duration_ms,
+        Err(e) => {
+            warn!("Could not probe PiP screen file, composition progress is indeterminate: {}", e);
+            None
+        }
+    };
 
     info!("Running FFmpeg composition command");
 
-    let output = ffmpeg_command.output().await.map_err(|e| {
-        error!("Failed to run FFmpeg composition: {}", e);
-        format!("Failed to run FFmpeg composition: {}", e)
+    run_pip_composition(&composition_args, duration_ms, |progress| {
+        let payload = PipCompositionProgressPayload {
+            recording_id: recording_id.clone(),
+            progress,
+        };
+        if let Err(e) = app_handle.emit("pip-composition-progress", &payload) {
+            warn!("Failed to emit pip-composition-progress event: {}", e);
+        }
+    })
+    .await
+    .map_err(|e| {
+        error!("{}", e);
+        e.to_string()
     })?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        error!("FFmpeg composition failed: {}", stderr);
-        return Err(format!("FFmpeg composition failed: {}", stderr));
-    }
-
     info!("FFmpeg composition completed successfully");
 
     // Clean up temp files
@@ -2698,6 +3879,14 @@ pub async fn cmd_stop_pip_recording(recording_id: String) -> Result<String, Stri
         debug!("Removed temp webcam file");
     }
 
+    if let Some(system_audio_path) = system_audio_path_opt {
+        if let Err(e) = remove_pcm_file(&system_audio_path) {
+            warn!("Failed to remove system audio file: {}", e);
+        } else {
+            debug!("Removed system audio file");
+        }
+    }
+
     // Clean up microphone audio file if it was created
     if let Some(mic_path) = mic_audio_path_opt {
         if let Err(e) = tokio::fs::remove_file(&mic_path).await {
@@ -2719,10 +3908,513 @@ pub async fn cmd_stop_pip_recording(recording_id: String) -> Result<String, Stri
     Ok(output_path_str)
 }
 
+/// Longest time app exit waits for active recordings to finalize
+pub const SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Stop path taken for an active recording when the app quits (or runs low on disk)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShutdownStop {
+    Screen(String),
+    Webcam(String),
+    Pip(String),
+}
+
+impl ShutdownStop {
+    /// Recording ID
+    pub fn id(&self) -> &str {
+        match self {
+            ShutdownStop::Screen(id) | ShutdownStop::Webcam(id) | ShutdownStop::Pip(id) => id,
+        }
+    }
+}
+
+/// Stop and finalize a recording through the stop command for its kind
+async fn stop_recording_entry(entry: ShutdownStop, app_handle: tauri::AppHandle) -> Result<String, String> {
+    match entry {
+        ShutdownStop::Screen(id) => cmd_stop_recording(id, app_handle).await.map_err(|e| e.to_string()),
+        ShutdownStop::Webcam(id) => cmd_stop_webcam_recording(id, app_handle).await,
+        ShutdownStop::Pip(id) => cmd_stop_pip_recording(id, app_handle).await,
+    }
+}
+
+/// Stop every recording in `stops`, giving up after `timeout`
+///
+/// Recordings are finalized one at a time through `stop`; whatever hasn't
+/// finished when the timeout expires is abandoned so quitting never hangs.
+///
+/// # Returns
+///
+/// Number of recordings that were stopped (successfully or not) within the timeout
+async fn stop_all_recordings<F, Fut>(stops: Vec<ShutdownStop>, timeout: std::time::Duration, stop: F) -> usize
+where
+    F: Fn(ShutdownStop) -> Fut,
+    Fut: std::future::Future<Output = Result<String, String>>,
+{
+    let total = stops.len();
+    let mut stopped = 0;
+
+    let finalize_all = async {
+        for entry in stops {
+            if let Err(e) = stop(entry.clone()).await {
+                warn!("Failed to finalize {:?} on shutdown: {}", entry, e);
+            }
+            stopped += 1;
+        }
+    };
+
+    if tokio::time::timeout(timeout, finalize_all).await.is_err() {
+        warn!("Shutdown timed out; {} of {} recordings left unfinalized", total - stopped, total);
+    }
+
+    stopped
+}
+
+/// Finalize every active screen, webcam and PiP recording before the app exits
+///
+/// Called from the Tauri `Exit` event. Bounded by `SHUTDOWN_TIMEOUT`.
+pub async fn shutdown_active_recordings(app_handle: tauri::AppHandle) {
+    let mut stops: Vec<ShutdownStop> =
+        ACTIVE_RECORDINGS.lock().await.keys().cloned().map(ShutdownStop::Screen).collect();
+    stops.extend(ACTIVE_WEBCAM_RECORDINGS.lock().await.keys().cloned().map(ShutdownStop::Webcam));
+    stops.extend(ACTIVE_PIP_RECORDINGS.lock().await.keys().cloned().map(ShutdownStop::Pip));
+
+    if stops.is_empty() {
+        return;
+    }
+
+    info!("Finalizing {} active recording(s) before exit", stops.len());
+    stop_all_recordings(stops, SHUTDOWN_TIMEOUT, |entry| {
+        stop_recording_entry(entry, app_handle.clone())
+    })
+    .await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_webcam_without_microphone_skips_wav_and_mux() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // Mic disabled: no WAV path, so no capture task and nothing to mux
+        assert_eq!(webcam_audio_path(false, dir.path(), "rec-1"), None);
+        assert_eq!(webcam_finalize_step(None), WebcamFinalize::VideoOnly);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        // Mic enabled but silent: header-only or missing WAVs aren't muxed
+        let wav = webcam_audio_path(true, dir.path(), "rec-2").unwrap();
+        assert_eq!(webcam_finalize_step(Some(&wav)), WebcamFinalize::VideoOnly);
+        std::fs::write(&wav, [0u8; WAV_HEADER_BYTES as usize]).unwrap();
+        assert_eq!(webcam_finalize_step(Some(&wav)), WebcamFinalize::VideoOnly);
+
+        std::fs::write(&wav, [0u8; 4096]).unwrap();
+        assert_eq!(webcam_finalize_step(Some(&wav)), WebcamFinalize::MuxAudio(wav.clone()));
+    }
+
+    #[tokio::test]
+    async fn test_graceful_audio_stop_writes_every_queued_sample() {
+        let dir = tempfile::tempdir().unwrap();
+        let buffers = 40;
+        let samples_per_buffer = 480;
+
+        // Mock microphone: buffers are queued before the stop, as a live stream would have
+        let queue_samples = |audio_tx: &mpsc::Sender<AudioSample>| {
+            for i in 0..buffers {
+                audio_tx
+                    .try_send(AudioSample {
+                        data: vec![0.25; samples_per_buffer],
+                        sample_rate: 48_000,
+                        channels: 1,
+                        timestamp_ns: i as u64 * 10_000_000,
+                    })
+                    .unwrap();
+            }
+        };
+
+        // Graceful stop: the source is stopped and the writer drains the queue
+        let wav_path = dir.path().join("graceful.wav");
+        let (audio_tx, mut audio_rx) = mpsc::channel::<AudioSample>(100);
+        queue_samples(&audio_tx);
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_clone = Arc::clone(&stop);
+        let path = wav_path.clone();
+        let handle = tokio::task::spawn_blocking(move || {
+            let mut source = Some(audio_tx);
+            write_mic_samples_to_wav(&mut audio_rx, &stop_clone, || drop(source.take()), &path, 1.0);
+        });
+        assert!(AudioWriterTask { handle, stop }.stop_and_drain(AUDIO_DRAIN_TIMEOUT).await);
+        let wav = crate::utils::wav::WavReader::open(&wav_path).unwrap();
+        assert_eq!(wav.sample_count(), buffers * samples_per_buffer);
+
+        // Abort: the source keeps its sender, so the WAV is never finalized
+        let wav_path = dir.path().join("aborted.wav");
+        let (audio_tx, mut audio_rx) = mpsc::channel::<AudioSample>(100);
+        queue_samples(&audio_tx);
+        let path = wav_path.clone();
+        let handle = tokio::task::spawn_blocking(move || {
+            write_mic_samples_to_wav(&mut audio_rx, &AtomicBool::new(false), || {}, &path, 1.0);
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        handle.abort();
+        let written = crate::utils::wav::WavReader::open(&wav_path).map(|wav| wav.sample_count());
+        assert!(!matches!(written, Ok(count) if count == buffers * samples_per_buffer));
+
+        // Let the detached writer exit
+        drop(audio_tx);
+    }
+
+    #[test]
+    fn test_mic_gain_unity_passthrough_and_clamping() {
+        // 0.4-amplitude 440 Hz sine at 48 kHz
+        let sine: Vec<f32> = (0..480)
+            .map(|i| 0.4 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 48_000.0).sin())
+            .collect();
+
+        // Default gain leaves samples untouched
+        let gain = resolve_mic_gain(None).unwrap();
+        assert_eq!(apply_mic_gain(&sine, gain), sine);
+
+        // 4x: samples below 0.25 scale linearly, louder ones clamp at full scale
+        let boosted = apply_mic_gain(&sine, 4.0);
+        for (&input, &output) in sine.iter().zip(&boosted) {
+            if input.abs() < 0.25 {
+                assert!((output - input * 4.0).abs() < 1e-6);
+            } else {
+                assert_eq!(output.abs(), 1.0);
+            }
+        }
+        assert!(boosted.iter().any(|s| s.abs() == 1.0));
+
+        assert!(resolve_mic_gain(Some(10.5)).is_err());
+        assert!(resolve_mic_gain(Some(-0.5)).is_err());
+        assert!(resolve_mic_gain(Some(f32::NAN)).is_err());
+        assert_eq!(resolve_mic_gain(Some(0.0)), Ok(0.0));
+    }
+
+    #[test]
+    fn test_no_camera_error_is_distinguishable() {
+        let no_camera = anyhow::Error::from(CameraError::NoCamerasAvailable);
+        assert_eq!(camera_init_error(&no_camera), "No camera connected");
+
+        let denied = anyhow::Error::from(CameraError::AccessDenied);
+        assert!(camera_init_error(&denied).starts_with("Camera initialization failed: Camera access denied"));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_recordings_from_each_map() {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let stops = vec![
+            ShutdownStop::Screen("screen-1".to_string()),
+            ShutdownStop::Webcam("webcam-1".to_string()),
+            ShutdownStop::Pip("pip-1".to_string()),
+        ];
+
+        let recorded = calls.clone();
+        let stopped = stop_all_recordings(stops.clone(), SHUTDOWN_TIMEOUT, move |entry| {
+            let recorded = recorded.clone();
+            async move {
+                recorded.lock().unwrap().push(entry.clone());
+                match entry {
+                    // A failing stop doesn't prevent the others
+                    ShutdownStop::Webcam(_) => Err("encoder failed".to_string()),
+                    _ => Ok("/tmp/out.mp4".to_string()),
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(stopped, 3);
+        assert_eq!(*calls.lock().unwrap(), stops);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_wait_is_bounded() {
+        let stops = vec![
+            ShutdownStop::Screen("fast".to_string()),
+            ShutdownStop::Screen("hung".to_string()),
+        ];
+
+        let stopped = stop_all_recordings(stops, std::time::Duration::from_millis(50), |entry| async move {
+            if entry == ShutdownStop::Screen("hung".to_string()) {
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            }
+            Ok(String::new())
+        })
+        .await;
+
+        assert_eq!(stopped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_recording_status_tracks_elapsed_and_pause() {
+        let recording_id = "status-test-recording".to_string();
+        let pause_flag = Arc::new(AtomicBool::new(false));
+        ACTIVE_RECORDINGS.lock().await.insert(
+            recording_id.clone(),
+            (
+                tokio::spawn(async {}),
+                tokio::spawn(async { Ok(()) }),
+                PathBuf::from("/tmp/status-test.mp4"),
+                pause_flag.clone(),
+                Arc::new(AtomicBool::new(false)),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                CaptureAbort::default(),
+                false,
+            ),
+        );
+        RECORDING_CLOCKS.lock().await.insert(recording_id.clone(), Arc::new(PausableClock::new()));
+
+        let first = cmd_get_recording_status(recording_id.clone()).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+        let second = cmd_get_recording_status(recording_id.clone()).await.unwrap();
+        assert_eq!(first.state, RecordingState::Recording);
+        assert_eq!(second.state, RecordingState::Recording);
+        assert!(second.elapsed_ms > first.elapsed_ms, "{:?} then {:?}", first, second);
+        // The final destination, not the scratch intermediate in the handle
+        let final_path = screen_recording_output_path(&recording_id).unwrap();
+        assert_eq!(second.output_path, Some(final_path.to_string_lossy().to_string()));
+
+        // Paused time doesn't count
+        cmd_pause_recording(recording_id.clone()).await.unwrap();
+        let paused = cmd_get_recording_status(recording_id.clone()).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+        let still_paused = cmd_get_recording_status(recording_id.clone()).await.unwrap();
+        assert_eq!(paused.state, RecordingState::Paused);
+        assert_eq!(still_paused.elapsed_ms, paused.elapsed_ms);
+        assert!(pause_flag.load(std::sync::atomic::Ordering::Relaxed));
+
+        ACTIVE_RECORDINGS.lock().await.remove(&recording_id);
+        RECORDING_CLOCKS.lock().await.remove(&recording_id);
+        let gone = cmd_get_recording_status(recording_id).await.unwrap();
+        assert_eq!(gone, RecordingStatus::not_found());
+    }
+
+    #[tokio::test]
+    async fn test_muting_microphone_keeps_system_audio_flowing() {
+        let recording_id = "mute-test-recording".to_string();
+        ACTIVE_RECORDINGS.lock().await.insert(
+            recording_id.clone(),
+            (
+                tokio::spawn(async {}),
+                tokio::spawn(async { Ok(()) }),
+                PathBuf::from("/tmp/mute-test.mp4"),
+                Arc::new(AtomicBool::new(false)),
+                Arc::new(AtomicBool::new(false)),
+                Some(tokio::spawn(async { Ok(()) })),
+                None,
+                Some(tokio::spawn(async { Ok(()) })),
+                None,
+                None,
+                None,
+                CaptureAbort::default(),
+                false,
+            ),
+        );
+        let mutes = AudioTrackMutes::default();
+        AUDIO_TRACK_MUTES.lock().await.insert(recording_id.clone(), mutes.clone());
+
+        let sample = |value: f32| AudioSample {
+            data: vec![value; 960],
+            sample_rate: 48_000,
+            channels: 2,
+            timestamp_ns: 0,
+        };
+
+        cmd_set_audio_track_muted(recording_id.clone(), MutableAudioTrack::Microphone, true)
+            .await
+            .unwrap();
+
+        // Mic samples become silence of the same length; system audio passes through
+        let mut mic = sample(0.5);
+        let mut system = sample(0.5);
+        silence_if_muted(&mut mic, &mutes.microphone);
+        silence_if_muted(&mut system, &mutes.system);
+        assert_eq!(mic.data, vec![0.0; 960]);
+        assert_eq!(system.data, vec![0.5; 960]);
+
+        cmd_set_audio_track_muted(recording_id.clone(), MutableAudioTrack::Microphone, false)
+            .await
+            .unwrap();
+        let mut mic = sample(0.5);
+        silence_if_muted(&mut mic, &mutes.microphone);
+        assert_eq!(mic.data, vec![0.5; 960]);
+
+        ACTIVE_RECORDINGS.lock().await.remove(&recording_id);
+        AUDIO_TRACK_MUTES.lock().await.remove(&recording_id);
+        assert!(cmd_set_audio_track_muted(recording_id, MutableAudioTrack::System, true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_recording_status_is_not_found() {
+        let status = cmd_get_recording_status("no-such-recording".to_string()).await.unwrap();
+        assert_eq!(status.state, RecordingState::NotFound);
+        assert_eq!(status.elapsed_ms, 0);
+        assert_eq!(serde_json::to_value(&status).unwrap()["state"], "not_found");
+    }
+
+    #[tokio::test]
+    async fn test_no_frames_captured_during_start_countdown() {
+        let delay = std::time::Duration::from_millis(1_500);
+        let (frame_tx, mut frame_rx) = mpsc::channel::<std::time::Instant>(8);
+        let started = std::time::Instant::now();
+
+        // Stand-in for start_continuous_capture: frames only flow after the countdown
+        let capture = tokio::spawn(async move {
+            let mut ticks = Vec::new();
+            let cancelled = AtomicBool::new(false);
+            if run_start_countdown(delay, &cancelled, |secs| ticks.push(secs)).await {
+                frame_tx.send(std::time::Instant::now()).await.unwrap();
+            }
+            ticks
+        });
+
+        tokio::time::sleep(std::time::Duration::from_millis(750)).await;
+        assert!(frame_rx.try_recv().is_err(), "frame captured mid-countdown");
+
+        let first_frame = frame_rx.recv().await.expect("capture should start after the countdown");
+        assert!(first_frame.duration_since(started) >= delay);
+        assert_eq!(capture.await.unwrap(), vec![2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_during_countdown_aborts_start() {
+        let recording_id = "countdown-cancel-test".to_string();
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        COUNTDOWN_CANCEL_FLAGS.lock().await.insert(recording_id.clone(), cancel_flag.clone());
+
+        let countdown = {
+            let cancel_flag = cancel_flag.clone();
+            tokio::spawn(async move {
+                run_start_countdown(std::time::Duration::from_secs(30), &cancel_flag, |_| {}).await
+            })
+        };
+
+        cmd_cancel_recording(recording_id.clone()).await.unwrap();
+        let completed = tokio::time::timeout(std::time::Duration::from_secs(1), countdown)
+            .await
+            .expect("cancel should end the countdown promptly")
+            .unwrap();
+        assert!(!completed);
+
+        COUNTDOWN_CANCEL_FLAGS.lock().await.remove(&recording_id);
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn test_scope_audio_to_window_reaches_screen_capture() {
+        // Requires screen recording permission; skipped when capture can't be created
+        if let Ok(mut capture) = ScreenCapture::new(None, None) {
+            let config = crate::models::recording::RecordingConfig {
+                scope_audio_to_window: true,
+                ..Default::default()
+            };
+            configure_screen_capture(&mut capture, &config, Some(42)).unwrap();
+            assert!(capture.is_audio_scoped_to_window());
+
+            configure_screen_capture(&mut capture, &Default::default(), None).unwrap();
+            assert!(!capture.is_audio_scoped_to_window());
+        }
+    }
+
+    #[test]
+    fn test_recording_start_error_serializes_disk_space_fields() {
+        let error = RecordingStartError::from(InsufficientDiskSpace {
+            path: "/Volumes/Scratch".to_string(),
+            available_bytes: 100 * 1024 * 1024,
+            required_bytes: 500 * 1024 * 1024,
+        });
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "insufficient_disk_space");
+        assert_eq!(json["path"], "/Volumes/Scratch");
+        assert_eq!(json["availableBytes"], 100 * 1024 * 1024);
+        assert_eq!(json["requiredBytes"], 500 * 1024 * 1024);
+        assert!(json["message"].as_str().unwrap().contains("100 MB free, 500 MB required"));
+
+        let json = serde_json::to_value(RecordingStartError::from("No camera connected".to_string())).unwrap();
+        assert_eq!(json, serde_json::json!({ "code": "failed", "message": "No camera connected" }));
+    }
+
+    #[test]
+    fn test_capture_abort_stop_error_keeps_code_suggestion_and_saved_path() {
+        let reason = CaptureAbortReason::PixelFormatIncompatible { consecutive_failures: 100 };
+        let error = RecordingStopError::CaptureAborted {
+            reason,
+            output_path: Some("/Users/me/Documents/clippy/recordings/recording-1.mp4".to_string()),
+        };
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "pixel_format_incompatible");
+        assert_eq!(json["message"], ScreenCaptureError::from(reason).to_string());
+        assert_eq!(json["suggestion"], reason.suggestion());
+        assert_eq!(json["outputPath"], "/Users/me/Documents/clippy/recordings/recording-1.mp4");
+
+        // Nothing could be saved: no outputPath
+        let json = serde_json::to_value(RecordingStopError::CaptureAborted { reason, output_path: None }).unwrap();
+        assert!(json.get("outputPath").is_none());
+
+        let json = serde_json::to_value(RecordingStopError::from("Recording not found: x".to_string())).unwrap();
+        assert_eq!(json, serde_json::json!({ "code": "failed", "message": "Recording not found: x" }));
+    }
+
+    #[test]
+    fn test_recording_start_error_serializes_output_dir_kind() {
+        let error = RecordingStartError::from(OutputDirError::PermissionDenied {
+            path: "/Volumes/Backup".to_string(),
+        });
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "output_dir");
+        assert_eq!(json["kind"], "permissionDenied");
+        assert_eq!(json["path"], "/Volumes/Backup");
+        assert!(json["message"].as_str().unwrap().starts_with("Permission denied for /Volumes/Backup"));
+        assert!(json.get("availableBytes").is_none());
+
+        // The kind tag matches OutputDirError's own serialization
+        let other = OutputDirError::Other { path: "/tmp/x".to_string(), message: "I/O error".to_string() };
+        assert_eq!(serde_json::to_value(&other).unwrap()["kind"], other.kind());
+        assert_eq!(serde_json::to_value(RecordingStartError::from(other)).unwrap()["kind"], "other");
+    }
+
+    #[test]
+    fn test_below_free_space_floor_triggers_stop() {
+        let floor = 500 * 1024 * 1024;
+        assert!(below_free_space_floor(floor - 1, floor));
+        assert!(!below_free_space_floor(floor, floor));
+        assert!(!below_free_space_floor(floor * 4, floor));
+
+        // A zero floor disables the auto-stop
+        assert!(!below_free_space_floor(0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_low_disk_monitor_fires_at_floor() {
+        const MB: u64 = 1024 * 1024;
+        let interval = std::time::Duration::from_millis(1);
+
+        // Free space shrinking while recording: stops on the first reading under 500 MB
+        let mut readings = vec![2_000 * MB, 900 * MB, 500 * MB, 499 * MB, 100 * MB].into_iter();
+        let mut polls = 0;
+        let fired = wait_for_low_disk(
+            || {
+                polls += 1;
+                Ok(readings.next().unwrap())
+            },
+            500 * MB,
+            interval,
+        )
+        .await;
+        assert_eq!(fired, Some(499 * MB));
+        assert_eq!(polls, 4);
+
+        // A failed reading ends the monitor without stopping the recording
+        let mut readings = vec![Ok(900 * MB), Err("statvfs failed".to_string())].into_iter();
+        assert_eq!(wait_for_low_disk(|| readings.next().unwrap(), 500 * MB, interval).await, None);
+    }
+
     #[tokio::test]
     #[cfg(target_os = "macos")]
     async fn test_check_permission_command_returns_result() {
@@ -2900,4 +4592,18 @@ mod tests {
         assert_eq!(variance_1, 80, "Both should calculate 80ms variance");
         assert!(variance_1 <= 100 && variance_2 <= 100, "Both should be within threshold");
     }
+
+    #[test]
+    fn test_simulated_mic_failure_keeps_video_recording() {
+        // A failed microphone setup degrades to "no mic" instead of an error,
+        // so the caller keeps going with the video-only recording
+        let mic_setup: Result<(u16, PathBuf), String> =
+            Err("Failed to initialize microphone capture: no input device".to_string());
+        let mic = degrade_audio_failure("microphone", mic_setup, None);
+        assert!(mic.is_none());
+
+        // A successful setup is passed through untouched
+        let ok_setup: Result<(u16, PathBuf), String> = Ok((2, PathBuf::from("/tmp/mic.pcm")));
+        assert_eq!(degrade_audio_failure("microphone", ok_setup, None).map(|(c, _)| c), Some(2));
+    }
 }
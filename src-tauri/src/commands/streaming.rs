@@ -0,0 +1,207 @@
+//! Live-streaming Tauri commands
+//!
+//! Streams a screen capture to an RTMP server through `RtmpSink`. The capture
+//! side is the same as a screen recording; only the frame sink differs.
+
+use crate::models::recording::{RecordingConfig, ScreenRecordingMode};
+use crate::services::ffmpeg::{scale_to_height, RtmpSink};
+use crate::services::permissions::check_screen_recording_permission;
+use crate::services::screen_capture::{FrameHandler, ScreenCapture};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::Emitter;
+use tokio::sync::Mutex;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// Handles for an active live stream
+struct StreamHandle {
+    /// Screen capture task
+    capture_handle: tokio::task::JoinHandle<()>,
+    /// Watches the encoder task and tears the stream down if it fails
+    monitor_handle: tokio::task::JoinHandle<Result<(), String>>,
+    /// Capture stop signal
+    stop_signal: Arc<AtomicBool>,
+}
+
+lazy_static::lazy_static! {
+    static ref ACTIVE_STREAMS: Arc<Mutex<HashMap<String, StreamHandle>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+/// Payload for `stream-failed`
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StreamFailedPayload {
+    stream_id: String,
+    error: String,
+}
+
+/// Start streaming the screen to an RTMP server
+///
+/// # Arguments
+///
+/// * `config` - Recording configuration (frame rate, resolution, window mode)
+/// * `rtmp_url` - Ingest URL including the stream key (`rtmp://` or `rtmps://`)
+///
+/// # Returns
+///
+/// - `Ok(String)` with the stream ID
+/// - `Err(String)` if the URL is invalid or capture/FFmpeg failed to start
+///
+/// If the connection drops mid-stream the sink reconnects automatically
+/// (`stream-reconnecting` / `stream-reconnected`). Once reconnect attempts are
+/// exhausted, capture is stopped and a `stream-failed` event is emitted with
+/// FFmpeg's error message.
+#[tauri::command]
+pub async fn cmd_start_stream(
+    config: Option<RecordingConfig>,
+    rtmp_url: String,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    debug!("Command: start stream");
+
+    let config = config.unwrap_or_default();
+
+    // Validate the URL before touching capture so typos fail fast
+    crate::services::ffmpeg::validate_rtmp_url(&rtmp_url).map_err(|e| e.to_string())?;
+
+    match check_screen_recording_permission() {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err("Screen recording permission required. Please enable in System Preferences → Privacy & Security → Screen Recording".to_string());
+        }
+        Err(e) => {
+            error!("Permission check failed: {}", e);
+            return Err(format!("Permission check failed: {}", e));
+        }
+    }
+
+    let stream_id = Uuid::new_v4().to_string();
+    info!("Starting stream: {}", stream_id);
+
+    let window_id = if config.screen_recording_mode == ScreenRecordingMode::Window {
+        config.selected_window_id
+    } else {
+        None
+    };
+
+    let mut screen_capture = ScreenCapture::new(window_id, None).map_err(|e| {
+        error!("Failed to initialize ScreenCapture: {}", e);
+        format!("Screen capture initialization failed: {}", e)
+    })?;
+
+    screen_capture.set_overflow_policy(config.overflow_policy);
+
+    let (capture_width, capture_height) = screen_capture.get_dimensions();
+    let (width, height) = match config.resolution.as_str() {
+        "720p" => scale_to_height(capture_width, capture_height, 720),
+        "1080p" => scale_to_height(capture_width, capture_height, 1080),
+        _ => (capture_width, capture_height),
+    };
+
+    let mut sink = RtmpSink::new(&rtmp_url, width, height, config.frame_rate)
+        .map_err(|e| e.to_string())?;
+    sink.set_event_target(app_handle.clone(), stream_id.clone());
+    sink.start().await.map_err(|e| {
+        error!("Failed to start stream: {}", e);
+        format!("Failed to start stream: {}", e)
+    })?;
+
+    let mut frame_handler = FrameHandler::new_for_encoding(config.frame_rate as usize);
+    let frame_tx = frame_handler.get_sender();
+
+    let encoder_handle = frame_handler.start_encoder(sink).await.map_err(|e| {
+        error!("Failed to start stream encoder task: {}", e);
+        format!("Failed to start stream encoder task: {}", e)
+    })?;
+
+    let capture_handle = screen_capture
+        .start_continuous_capture(frame_tx, None, Some(app_handle.clone()))
+        .map_err(|e| {
+            error!("Failed to start screen capture: {}", e);
+            format!("Failed to start screen capture: {}", e)
+        })?;
+
+    let stop_signal = screen_capture.get_stop_signal();
+
+    // Tear down capture if the sink fails (connection refused or dropped). The
+    // stream is registered before the monitor can run, so a quick failure
+    // removes the entry instead of racing ahead of the insert
+    let mut streams = ACTIVE_STREAMS.lock().await;
+    let monitor_stream_id = stream_id.clone();
+    let monitor_stop_signal = stop_signal.clone();
+    let monitor_handle = tokio::spawn(async move {
+        let result = match encoder_handle.await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(e) => Err(format!("Stream encoder task failed: {}", e)),
+        };
+
+        if let Err(e) = &result {
+            error!("Stream {} failed: {}", monitor_stream_id, e);
+            monitor_stop_signal.store(true, Ordering::Relaxed);
+            ACTIVE_STREAMS.lock().await.remove(&monitor_stream_id);
+
+            let payload = StreamFailedPayload {
+                stream_id: monitor_stream_id.clone(),
+                error: e.clone(),
+            };
+            if let Err(emit_err) = app_handle.emit("stream-failed", payload) {
+                warn!("Failed to emit stream-failed event: {}", emit_err);
+            }
+        }
+
+        result
+    });
+
+    streams.insert(
+        stream_id.clone(),
+        StreamHandle {
+            capture_handle,
+            monitor_handle,
+            stop_signal,
+        },
+    );
+    drop(streams);
+
+    info!("Stream started: {} ({}x{} @ {} FPS)", stream_id, width, height, config.frame_rate);
+    Ok(stream_id)
+}
+
+/// Stop an active live stream
+///
+/// # Arguments
+///
+/// * `stream_id` - ID returned by `cmd_start_stream`
+///
+/// # Returns
+///
+/// - `Ok(())` once capture has stopped and FFmpeg has closed the connection
+/// - `Err(String)` if the stream is unknown or already failed
+#[tauri::command]
+pub async fn cmd_stop_stream(stream_id: String) -> Result<(), String> {
+    debug!("Command: stop stream {}", stream_id);
+
+    let handle = ACTIVE_STREAMS
+        .lock()
+        .await
+        .remove(&stream_id)
+        .ok_or_else(|| format!("Stream not found: {}", stream_id))?;
+
+    handle.stop_signal.store(true, Ordering::Relaxed);
+
+    if let Err(e) = handle.capture_handle.await {
+        warn!("Stream capture task join error: {}", e);
+    }
+
+    // Capture ending closes the frame channel, which finalizes the sink
+    match handle.monitor_handle.await {
+        Ok(result) => result?,
+        Err(e) => return Err(format!("Stream monitor task failed: {}", e)),
+    }
+
+    info!("Stream stopped: {}", stream_id);
+    Ok(())
+}
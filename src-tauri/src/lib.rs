@@ -9,31 +9,64 @@ pub mod test_utils;
 
 use std::fs;
 use tauri::Manager;
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use tracing_subscriber::{fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter};
 use commands::{
     cmd_import_media,
+    cmd_import_edl,
+    cmd_repair_recording,
+    cmd_optimize_for_streaming,
+    cmd_relink_media,
+    cmd_get_clip_frame,
+    cmd_generate_thumbnail,
+    cmd_extract_waveform,
+    cmd_extract_waveform_peaks,
+    cmd_probe_media,
+    cmd_split_by_silence,
+    cmd_measure_av_sync,
+    cmd_get_media_keyframes,
+    cmd_generate_contact_sheet,
     cmd_start_export,
     cmd_get_export_progress,
     cmd_cancel_export,
+    cmd_batch_export,
+    cmd_get_export_history,
+    cmd_reencode_recording,
+    cmd_quick_clip,
+    cmd_get_supported_export_formats,
+    cmd_list_export_presets,
+    cmd_check_audio_phase,
     mpv_init,
     mpv_load_file,
+    mpv_load_playlist,
+    mpv_playlist_next,
+    mpv_playlist_prev,
+    mpv_get_playlist_position,
     mpv_play,
     mpv_pause,
     mpv_seek,
+    mpv_step_frame,
+    mpv_seek_to_frame,
     mpv_get_time,
     mpv_get_duration,
     mpv_stop,
     mpv_is_playing,
     mpv_get_video_dimensions,
     mpv_capture_frame,
+    mpv_save_screenshot,
     mpv_set_volume,
     mpv_apply_fade_filters,
     mpv_clear_audio_filters,
+    mpv_set_speed,
+    mpv_get_speed,
     cmd_check_screen_recording_permission,
     cmd_request_screen_recording_permission,
     cmd_check_camera_permission,
     cmd_request_camera_permission,
+    cmd_check_all_permissions,
+    cmd_open_privacy_settings,
+    cmd_run_capture_selftest,
     cmd_list_cameras,
+    cmd_set_camera_torch,
     cmd_list_microphones,
     cmd_start_camera_preview,
     cmd_stop_camera_preview,
@@ -42,27 +75,41 @@ use commands::{
     cmd_start_screen_recording,
     cmd_start_pip_recording,
     cmd_stop_pip_recording,
+    cmd_start_stream,
+    cmd_stop_stream,
     cmd_stop_recording,
     cmd_pause_recording,
+    cmd_set_audio_track_muted,
     cmd_resume_recording,
+    cmd_get_recording_status,
     cmd_cancel_recording,
+    cmd_get_live_waveform,
     cmd_check_disk_space,
     cmd_send_recording_notification,
     cmd_get_home_dir,
     cmd_get_available_windows,
+    cmd_get_available_displays,
     get_playback_fps,
     record_playback_frame,
     reset_fps_counter,
     get_buffer_status,
     cmd_render_segment,
     cmd_classify_segment_type,
+    cmd_get_safe_areas,
     cmd_render_timeline,
     cmd_clear_timeline_cache,
     cmd_analyze_recording,
+    cmd_set_log_level,
+    cmd_get_recent_logs,
 };
 
 /// Initialize logging system with file output to ~/Library/Logs/clippy/app.log
-fn init_logging() -> anyhow::Result<()> {
+///
+/// Returns a reload handle for the EnvFilter so the level can be changed at runtime.
+/// Log lines are also mirrored into `log_buffer` for the in-app log viewer.
+fn init_logging(
+    log_buffer: std::sync::Arc<services::log_buffer::LogBuffer>,
+) -> anyhow::Result<commands::LogReloadHandle> {
     let log_dir = dirs::home_dir()
         .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
         .join("Library")
@@ -88,10 +135,15 @@ fn init_logging() -> anyhow::Result<()> {
         .with_writer(std::io::stdout)
         .with_target(false);
 
+    let (filter_layer, reload_handle) = reload::Layer::new(
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+    );
+
     tracing_subscriber::registry()
-        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .with(filter_layer)
         .with(file_layer)
         .with(stdout_layer)
+        .with(services::log_buffer::LogBufferLayer::new(log_buffer))
         .init();
 
     tracing::info!(
@@ -100,15 +152,29 @@ fn init_logging() -> anyhow::Result<()> {
         "Logging system initialized"
     );
 
-    Ok(())
+    Ok(reload_handle)
 }
 
 /// Initialize FFmpeg by downloading binaries if needed
+///
+/// The download is retried with backoff since it fails transiently on flaky
+/// networks.
 fn init_ffmpeg() -> anyhow::Result<()> {
     tracing::info!(event = "ffmpeg_init_start", "Initializing FFmpeg");
 
-    ffmpeg_sidecar::download::auto_download()
-        .map_err(|e| anyhow::anyhow!("Failed to initialize FFmpeg: {}", e))?;
+    utils::ffmpeg::retry_with_backoff(
+        utils::ffmpeg::FFMPEG_DOWNLOAD_ATTEMPTS,
+        utils::ffmpeg::FFMPEG_DOWNLOAD_BASE_DELAY,
+        std::thread::sleep,
+        |_| ffmpeg_sidecar::download::auto_download(),
+    )
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to download FFmpeg after {} attempts ({}). Recording is disabled until Clippy is restarted with a working internet connection",
+            utils::ffmpeg::FFMPEG_DOWNLOAD_ATTEMPTS,
+            e
+        )
+    })?;
 
     tracing::info!(event = "ffmpeg_init_success", "FFmpeg initialized successfully");
     Ok(())
@@ -124,14 +190,22 @@ fn greet(name: &str) -> String {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize logging first
-    if let Err(e) = init_logging() {
-        eprintln!("Failed to initialize logging: {}", e);
-    }
+    let log_buffer = std::sync::Arc::new(services::log_buffer::LogBuffer::new(
+        services::log_buffer::DEFAULT_LOG_BUFFER_CAPACITY,
+    ));
+    let log_reload_handle = match init_logging(log_buffer.clone()) {
+        Ok(handle) => Some(handle),
+        Err(e) => {
+            eprintln!("Failed to initialize logging: {}", e);
+            None
+        }
+    };
 
     // Initialize FFmpeg
     if let Err(e) = init_ffmpeg() {
         tracing::error!(error = %e, "Failed to initialize FFmpeg");
         eprintln!("Warning: FFmpeg initialization failed: {}", e);
+        utils::ffmpeg::set_ffmpeg_unavailable(true);
     }
 
     tracing::info!(
@@ -176,31 +250,66 @@ pub fn run() {
         .manage(commands::SegmentPreloaderState(std::sync::Arc::new(tokio::sync::Mutex::new(services::SegmentPreloader::new(segment_cache_dir)))))
         .manage(commands::SegmentRendererState(std::sync::Arc::new(std::sync::Mutex::new(segment_renderer))))
         .manage(commands::TimelineRendererState(std::sync::Arc::new(std::sync::Mutex::new(timeline_renderer))))
+        .manage(commands::LogLevelState(log_reload_handle))
+        .manage(commands::LogBufferState(log_buffer))
         .invoke_handler(tauri::generate_handler![
             greet,
             cmd_import_media,
+            cmd_import_edl,
+            cmd_repair_recording,
+            cmd_optimize_for_streaming,
+            cmd_relink_media,
+            cmd_get_clip_frame,
+            cmd_generate_thumbnail,
+            cmd_extract_waveform,
+            cmd_extract_waveform_peaks,
+            cmd_probe_media,
+            cmd_split_by_silence,
+            cmd_measure_av_sync,
+            cmd_get_media_keyframes,
+            cmd_generate_contact_sheet,
             cmd_start_export,
             cmd_get_export_progress,
             cmd_cancel_export,
+            cmd_batch_export,
+            cmd_get_export_history,
+            cmd_reencode_recording,
+            cmd_quick_clip,
+            cmd_get_supported_export_formats,
+            cmd_list_export_presets,
+            cmd_check_audio_phase,
             mpv_init,
             mpv_load_file,
+            mpv_load_playlist,
+            mpv_playlist_next,
+            mpv_playlist_prev,
+            mpv_get_playlist_position,
             mpv_play,
             mpv_pause,
             mpv_seek,
+            mpv_step_frame,
+            mpv_seek_to_frame,
             mpv_get_time,
             mpv_get_duration,
             mpv_stop,
             mpv_is_playing,
             mpv_get_video_dimensions,
             mpv_capture_frame,
+            mpv_save_screenshot,
             mpv_set_volume,
             mpv_apply_fade_filters,
             mpv_clear_audio_filters,
+            mpv_set_speed,
+            mpv_get_speed,
             cmd_check_screen_recording_permission,
             cmd_request_screen_recording_permission,
             cmd_check_camera_permission,
             cmd_request_camera_permission,
+            cmd_check_all_permissions,
+            cmd_open_privacy_settings,
+            cmd_run_capture_selftest,
             cmd_list_cameras,
+            cmd_set_camera_torch,
             cmd_list_microphones,
             cmd_start_camera_preview,
             cmd_stop_camera_preview,
@@ -209,23 +318,32 @@ pub fn run() {
             cmd_start_screen_recording,
             cmd_start_pip_recording,
             cmd_stop_pip_recording,
+            cmd_start_stream,
+            cmd_stop_stream,
             cmd_stop_recording,
             cmd_pause_recording,
+            cmd_set_audio_track_muted,
             cmd_resume_recording,
+            cmd_get_recording_status,
             cmd_cancel_recording,
+            cmd_get_live_waveform,
             cmd_check_disk_space,
             cmd_send_recording_notification,
             cmd_get_home_dir,
             cmd_get_available_windows,
+            cmd_get_available_displays,
             get_playback_fps,
             record_playback_frame,
             reset_fps_counter,
             get_buffer_status,
             cmd_render_segment,
             cmd_classify_segment_type,
+            cmd_get_safe_areas,
             cmd_render_timeline,
             cmd_clear_timeline_cache,
-            cmd_analyze_recording
+            cmd_analyze_recording,
+            cmd_set_log_level,
+            cmd_get_recent_logs
         ])
         .setup(|app| {
             use tauri::menu::*;
@@ -289,6 +407,11 @@ pub fn run() {
         .expect("error while building tauri application")
         .run(|app_handle, event| match event {
             tauri::RunEvent::Exit => {
+                // Finalize recordings so files aren't left half-written in scratch space
+                tauri::async_runtime::block_on(commands::recording::shutdown_active_recordings(
+                    app_handle.clone(),
+                ));
+
                 // Clean up timeline cache on app exit
                 tracing::info!(event = "app_exit", "Cleaning up timeline cache");
 
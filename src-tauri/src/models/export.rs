@@ -1,4 +1,423 @@
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Audio codec used when muxing the export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioCodec {
+    /// AAC (lossy, works in every supported container)
+    #[default]
+    Aac,
+    /// Apple Lossless (requires mov/m4a)
+    Alac,
+    /// 16-bit little-endian PCM (requires mov/mkv)
+    Pcm,
+}
+
+impl AudioCodec {
+    /// FFmpeg encoder name passed to `-c:a`
+    pub fn ffmpeg_codec(&self) -> &'static str {
+        match self {
+            AudioCodec::Aac => "aac",
+            AudioCodec::Alac => "alac",
+            AudioCodec::Pcm => "pcm_s16le",
+        }
+    }
+
+    /// Whether the codec takes a target bitrate (`-b:a`)
+    pub fn is_lossy(&self) -> bool {
+        matches!(self, AudioCodec::Aac)
+    }
+
+    /// Whether the codec can be muxed into a container (lowercase file extension)
+    pub fn supports_container(&self, container: &str) -> bool {
+        match self {
+            AudioCodec::Aac => matches!(container, "mp4" | "mov" | "m4a" | "mkv"),
+            AudioCodec::Alac => matches!(container, "mov" | "m4a"),
+            AudioCodec::Pcm => matches!(container, "mov" | "mkv"),
+        }
+    }
+}
+
+/// Video codec family for recordings and exports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoCodec {
+    /// H.264/AVC (plays everywhere)
+    #[default]
+    H264,
+    /// H.265/HEVC (roughly half the size at the same quality, slower to encode)
+    H265,
+}
+
+impl VideoCodec {
+    /// Software FFmpeg encoder passed to `-c:v`
+    pub fn ffmpeg_codec(&self) -> &'static str {
+        match self {
+            VideoCodec::H264 => "libx264",
+            VideoCodec::H265 => "libx265",
+        }
+    }
+
+    /// Default `-crf`; x265 CRF 28 is roughly x264 CRF 23
+    pub fn default_crf(&self) -> u8 {
+        match self {
+            VideoCodec::H264 => 23,
+            VideoCodec::H265 => 28,
+        }
+    }
+
+    /// VideoToolbox encoder for hardware encoding
+    pub fn videotoolbox_codec(&self) -> &'static str {
+        match self {
+            VideoCodec::H264 => "h264_videotoolbox",
+            VideoCodec::H265 => "hevc_videotoolbox",
+        }
+    }
+
+    /// MP4 sample entry tag (`-tag:v`), if the FFmpeg default needs overriding
+    ///
+    /// FFmpeg tags HEVC as `hev1`, which QuickTime and iOS refuse to play; `hvc1` works everywhere.
+    pub fn mp4_tag(&self) -> Option<&'static str> {
+        match self {
+            VideoCodec::H264 => None,
+            VideoCodec::H265 => Some("hvc1"),
+        }
+    }
+}
+
+/// Video encoder implementation used for recordings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EncoderBackend {
+    /// libx264/libx265 on the CPU, quality set with `-crf`
+    Software,
+    /// Apple VideoToolbox hardware encoder; ignores CRF, quality set with `-q:v`/`-b:v`
+    VideoToolbox,
+}
+
+impl EncoderBackend {
+    /// FFmpeg encoder passed to `-c:v` for `codec` on this backend
+    pub fn encoder_name(&self, codec: VideoCodec) -> &'static str {
+        match self {
+            EncoderBackend::Software => codec.ffmpeg_codec(),
+            EncoderBackend::VideoToolbox => codec.videotoolbox_codec(),
+        }
+    }
+}
+
+/// Valid x264/x265 `-crf` values (lower = better quality, larger file)
+pub const CRF_RANGE: std::ops::RangeInclusive<u8> = 0..=51;
+
+/// Reject CRF values outside `CRF_RANGE`
+pub fn validate_crf(crf: u8) -> Result<u8, String> {
+    if CRF_RANGE.contains(&crf) {
+        Ok(crf)
+    } else {
+        Err(format!(
+            "CRF must be between {} and {}, got {}",
+            CRF_RANGE.start(),
+            CRF_RANGE.end(),
+            crf
+        ))
+    }
+}
+
+/// x264/x265 speed preset (`-preset`); slower presets compress better
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    Ultrafast,
+    Superfast,
+    Veryfast,
+    Faster,
+    Fast,
+    Medium,
+    Slow,
+    Slower,
+    Veryslow,
+    Placebo,
+}
+
+impl Preset {
+    /// Every preset, fastest first
+    pub const ALL: [Preset; 10] = [
+        Preset::Ultrafast,
+        Preset::Superfast,
+        Preset::Veryfast,
+        Preset::Faster,
+        Preset::Fast,
+        Preset::Medium,
+        Preset::Slow,
+        Preset::Slower,
+        Preset::Veryslow,
+        Preset::Placebo,
+    ];
+
+    /// Preset name passed to `-preset`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Ultrafast => "ultrafast",
+            Preset::Superfast => "superfast",
+            Preset::Veryfast => "veryfast",
+            Preset::Faster => "faster",
+            Preset::Fast => "fast",
+            Preset::Medium => "medium",
+            Preset::Slow => "slow",
+            Preset::Slower => "slower",
+            Preset::Veryslow => "veryslow",
+            Preset::Placebo => "placebo",
+        }
+    }
+}
+
+impl std::str::FromStr for Preset {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Preset::ALL
+            .into_iter()
+            .find(|preset| preset.name() == name)
+            .ok_or_else(|| format!("Unknown encoder preset '{}'", name))
+    }
+}
+
+/// Pixel format of the exported video (`-pix_fmt`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PixelFormat {
+    /// 8-bit 4:2:0 (plays everywhere)
+    Yuv420p,
+    /// 8-bit 4:2:2 (broadcast)
+    Yuv422p,
+    /// 8-bit 4:4:4 (no chroma subsampling, sharp text)
+    Yuv444p,
+    /// 10-bit 4:2:0
+    Yuv420p10le,
+    /// 10-bit 4:2:2 (ProRes 422)
+    Yuv422p10le,
+    /// 10-bit 4:4:4 (ProRes 4444)
+    Yuv444p10le,
+}
+
+impl PixelFormat {
+    /// FFmpeg pixel format name passed to `-pix_fmt`
+    pub fn ffmpeg_name(&self) -> &'static str {
+        match self {
+            PixelFormat::Yuv420p => "yuv420p",
+            PixelFormat::Yuv422p => "yuv422p",
+            PixelFormat::Yuv444p => "yuv444p",
+            PixelFormat::Yuv420p10le => "yuv420p10le",
+            PixelFormat::Yuv422p10le => "yuv422p10le",
+            PixelFormat::Yuv444p10le => "yuv444p10le",
+        }
+    }
+
+    /// Whether a video encoder accepts this pixel format
+    ///
+    /// Unknown encoders are allowed through; FFmpeg reports the problem itself.
+    pub fn supports_codec(&self, codec: &str) -> bool {
+        match codec {
+            // Software encoders take every subsampling at 8 and 10 bit
+            "libx264" | "libx265" | "libvpx-vp9" => true,
+            // VideoToolbox only accepts 8-bit 4:2:0 input here
+            "h264_videotoolbox" | "hevc_videotoolbox" => matches!(self, PixelFormat::Yuv420p),
+            // ProRes is always 10-bit 4:2:2 or 4:4:4
+            "prores" | "prores_ks" | "prores_videotoolbox" => {
+                matches!(self, PixelFormat::Yuv422p10le | PixelFormat::Yuv444p10le)
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Named bundle of export settings, so casual users don't pick CRF/preset/codec
+///
+/// Presets are the rows of `EXPORT_PRESETS`; adding one is a new row.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPreset {
+    /// Identifier passed to `cmd_start_export`
+    pub id: &'static str,
+    /// Display name
+    pub name: &'static str,
+    /// One-line summary for the export dialog
+    pub description: &'static str,
+    /// Output size; None keeps the timeline resolution
+    pub resolution: Option<(u32, u32)>,
+    pub video_codec: VideoCodec,
+    /// Software encoder `-crf`
+    pub crf: u8,
+    /// Software encoder `-preset`
+    pub encoder_preset: Preset,
+    /// AAC `-b:a`
+    pub audio_bitrate: &'static str,
+    /// `-movflags +faststart`
+    pub faststart: bool,
+}
+
+/// Built-in export presets
+///
+/// Every preset encodes yuv420p video with AAC audio, so the result plays everywhere.
+pub const EXPORT_PRESETS: [ExportPreset; 4] = [
+    ExportPreset {
+        id: "web",
+        name: "Web",
+        description: "720p H.264 that starts playing quickly in a browser",
+        resolution: Some((1280, 720)),
+        video_codec: VideoCodec::H264,
+        crf: 23,
+        encoder_preset: Preset::Medium,
+        audio_bitrate: "128k",
+        faststart: true,
+    },
+    ExportPreset {
+        id: "youtube1080p",
+        name: "YouTube 1080p",
+        description: "1080p H.264 at high quality for uploading",
+        resolution: Some((1920, 1080)),
+        video_codec: VideoCodec::H264,
+        crf: 18,
+        encoder_preset: Preset::Slow,
+        audio_bitrate: "192k",
+        faststart: true,
+    },
+    ExportPreset {
+        id: "highQuality",
+        name: "High Quality",
+        description: "Near-lossless H.264 at the original resolution, for archiving or further editing",
+        resolution: None,
+        video_codec: VideoCodec::H264,
+        crf: 16,
+        encoder_preset: Preset::Slower,
+        audio_bitrate: "320k",
+        faststart: false,
+    },
+    ExportPreset {
+        id: "smallFile",
+        name: "Small File",
+        description: "720p H.265 for sharing where size matters more than quality",
+        resolution: Some((1280, 720)),
+        video_codec: VideoCodec::H265,
+        crf: 30,
+        encoder_preset: Preset::Medium,
+        audio_bitrate: "96k",
+        faststart: true,
+    },
+];
+
+impl ExportPreset {
+    /// Look up a preset by `id`
+    pub fn find(id: &str) -> Option<&'static ExportPreset> {
+        EXPORT_PRESETS.iter().find(|preset| preset.id == id)
+    }
+
+    /// `config` with its encoding settings replaced by the preset's
+    ///
+    /// The output path, scratch dir, background, watermark, threads and
+    /// seeking mode are kept.
+    pub fn apply(&self, config: ExportConfig) -> ExportConfig {
+        ExportConfig {
+            resolution: self.resolution,
+            codec: Some(self.video_codec.ffmpeg_codec().to_string()),
+            video_codec: self.video_codec,
+            audio_codec: AudioCodec::Aac,
+            video_bitrate: None,
+            audio_bitrate: Some(self.audio_bitrate.to_string()),
+            pixel_format: Some(PixelFormat::Yuv420p),
+            crf: Some(self.crf),
+            preset: Some(self.encoder_preset),
+            faststart: self.faststart,
+            ..config
+        }
+    }
+}
+
+/// One-shot libx264 settings for re-encoding a finished recording
+///
+/// Recordings are captured with `ultrafast`; re-encoding with a slower preset
+/// at the same CRF gives a much smaller file at similar quality.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReencodePreset {
+    /// `slow`, CRF 23: same quality as the recording, noticeably smaller
+    #[default]
+    Smaller,
+    /// `veryslow`, CRF 26: smallest file, slight quality loss
+    Smallest,
+    /// `slow`, CRF 18: visually lossless archive copy
+    HighQuality,
+}
+
+impl ReencodePreset {
+    /// libx264 `-preset`
+    pub fn x264_preset(&self) -> &'static str {
+        match self {
+            ReencodePreset::Smaller | ReencodePreset::HighQuality => "slow",
+            ReencodePreset::Smallest => "veryslow",
+        }
+    }
+
+    /// libx264 `-crf`
+    pub fn crf(&self) -> u8 {
+        match self {
+            ReencodePreset::Smaller => 23,
+            ReencodePreset::Smallest => 26,
+            ReencodePreset::HighQuality => 18,
+        }
+    }
+}
+
+/// Backdrop behind letterboxed/pillarboxed video
+///
+/// Used when the source doesn't fill the output frame (e.g. a 16:9 recording
+/// exported at 9:16). Defaults to solid black.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Background {
+    /// Solid color: `#RRGGBB` or an FFmpeg color name ("black", "white")
+    Solid { color: String },
+    /// Scaled-to-fill, blurred copy of the video itself
+    BlurredSource { sigma: f32 },
+    /// Still image scaled to fill the frame
+    Image { path: String },
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Background::Solid { color: "black".to_string() }
+    }
+}
+
+/// Frame corner a watermark is pinned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// Logo overlaid on every frame of the export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatermarkConfig {
+    /// PNG image; its alpha channel is kept
+    pub path: String,
+
+    /// Corner the logo is pinned to (defaults to bottom-right)
+    #[serde(default)]
+    pub position: Corner,
+
+    /// 0.0 (invisible) to 1.0 (as is)
+    pub opacity: f32,
+
+    /// Distance in pixels from the two edges of the corner
+    #[serde(default)]
+    pub margin: u32,
+}
 
 /// Configuration for video export operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,9 +434,14 @@ pub struct ExportConfig {
     /// If None, defaults to H.264 (libx264)
     pub codec: Option<String>,
 
-    /// Optional audio codec override
-    /// If None, defaults to AAC
-    pub audio_codec: Option<String>,
+    /// Video codec family (defaults to H.264)
+    /// H.265 takes precedence over `codec` and encodes with libx265
+    #[serde(default)]
+    pub video_codec: VideoCodec,
+
+    /// Audio codec (defaults to AAC)
+    #[serde(default)]
+    pub audio_codec: AudioCodec,
 
     /// Optional video bitrate (e.g., "5M" for 5 Mbps)
     /// If None, uses FFmpeg default
@@ -26,6 +450,48 @@ pub struct ExportConfig {
     /// Optional audio bitrate (e.g., "192k")
     /// If None, uses FFmpeg default (128k)
     pub audio_bitrate: Option<String>,
+
+    /// Optional pixel format override
+    /// If None, the encoder picks its default
+    pub pixel_format: Option<PixelFormat>,
+
+    /// Directory for the in-progress encode
+    /// If None, uses the local caches dir; the finished file is moved to `output_path`
+    pub scratch_dir: Option<String>,
+
+    /// Backdrop when the video doesn't fill `resolution` (defaults to black)
+    #[serde(default)]
+    pub background: Background,
+
+    /// FFmpeg encode threads (`-threads`, 1 to the number of cores)
+    /// If None, uses every core
+    #[serde(default)]
+    pub threads: Option<u32>,
+
+    /// Always re-encode, even for a plain trim that could be stream-copied
+    /// Stream copy is lossless and fast but can only cut on keyframes
+    #[serde(default)]
+    pub accurate_seek: bool,
+
+    /// Software encoder quality (`-crf`, 0-51)
+    /// If None, uses 23 for H.264 and 28 for H.265
+    #[serde(default)]
+    pub crf: Option<u8>,
+
+    /// Software encoder speed preset (`-preset`)
+    /// If None, uses "faster"
+    #[serde(default)]
+    pub preset: Option<Preset>,
+
+    /// Logo overlaid in a corner of the video
+    /// If None, no watermark
+    #[serde(default)]
+    pub watermark: Option<WatermarkConfig>,
+
+    /// Put the MP4/MOV index first (`-movflags +faststart`) so playback can
+    /// start before the whole file has downloaded
+    #[serde(default)]
+    pub faststart: bool,
 }
 
 impl Default for ExportConfig {
@@ -34,13 +500,101 @@ impl Default for ExportConfig {
             output_path: String::new(),
             resolution: None,
             codec: Some("libx264".to_string()),
-            audio_codec: Some("aac".to_string()),
+            video_codec: VideoCodec::H264,
+            audio_codec: AudioCodec::Aac,
             video_bitrate: None,
             audio_bitrate: Some("192k".to_string()),
+            pixel_format: None,
+            scratch_dir: None,
+            background: Background::default(),
+            threads: None,
+            accurate_seek: false,
+            crf: None,
+            preset: None,
+            watermark: None,
+            faststart: false,
         }
     }
 }
 
+impl ExportConfig {
+    /// Output container derived from the output path extension (lowercase, defaults to "mp4")
+    pub fn container(&self) -> String {
+        Path::new(&self.output_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .unwrap_or_else(|| "mp4".to_string())
+    }
+
+    /// FFmpeg muxer writing `container()` (`-f`)
+    pub fn muxer(&self) -> &'static str {
+        match self.container().as_str() {
+            "mov" => "mov",
+            "mkv" => "matroska",
+            "m4a" => "ipod",
+            _ => "mp4",
+        }
+    }
+
+    /// Whether `-movflags +faststart` applies (MP4-family muxers only)
+    pub fn wants_faststart(&self) -> bool {
+        self.faststart && self.muxer() != "matroska"
+    }
+
+    /// FFmpeg video encoder for this export (`-c:v`)
+    pub fn video_encoder(&self) -> &str {
+        match self.video_codec {
+            VideoCodec::H265 => VideoCodec::H265.ffmpeg_codec(),
+            VideoCodec::H264 => self.codec.as_deref().unwrap_or("libx264"),
+        }
+    }
+
+    /// `-crf` for the software encoder (explicit or the codec default)
+    pub fn crf(&self) -> u8 {
+        self.crf.unwrap_or_else(|| self.video_codec.default_crf())
+    }
+
+    /// `-preset` for the software encoder
+    pub fn preset(&self) -> Preset {
+        self.preset.unwrap_or(Preset::Faster)
+    }
+
+    /// Reject CRF values outside 0-51
+    pub fn validate_crf(&self) -> Result<(), String> {
+        self.crf.map_or(Ok(()), |crf| validate_crf(crf).map(|_| ()))
+    }
+
+    /// Reject audio codecs the output container cannot hold (e.g. ALAC into .webm)
+    pub fn validate_audio_codec(&self) -> Result<(), String> {
+        let container = self.container();
+        if !self.audio_codec.supports_container(&container) {
+            return Err(format!(
+                "Audio codec '{}' is not supported in .{} files",
+                self.audio_codec.ffmpeg_codec(),
+                container
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reject pixel formats the video encoder cannot produce (e.g. 4:4:4 via VideoToolbox)
+    pub fn validate_pixel_format(&self) -> Result<(), String> {
+        let Some(pixel_format) = self.pixel_format else {
+            return Ok(());
+        };
+        let codec = self.video_encoder();
+        if !pixel_format.supports_codec(codec) {
+            return Err(format!(
+                "Pixel format '{}' is not supported by the {} encoder",
+                pixel_format.ffmpeg_name(),
+                codec
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Export progress status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -83,6 +637,10 @@ pub struct ExportProgress {
 
     /// Optional error message if status is Failed
     pub error_message: Option<String>,
+
+    /// `ExportError` code if status is Failed (e.g. "disk_full"), for the frontend to branch on
+    #[serde(default)]
+    pub error_code: Option<String>,
 }
 
 impl ExportProgress {
@@ -96,6 +654,7 @@ impl ExportProgress {
             current_time_ms: Some(0),
             total_duration_ms,
             error_message: None,
+            error_code: None,
         }
     }
 
@@ -125,10 +684,11 @@ impl ExportProgress {
         self
     }
 
-    /// Mark export as failed
-    pub fn mark_failed(mut self, error: String) -> Self {
+    /// Mark export as failed with an `ExportError` code and message
+    pub fn mark_failed(mut self, code: &str, error: String) -> Self {
         self.status = ExportStatus::Failed;
         self.error_message = Some(error);
+        self.error_code = Some(code.to_string());
         self
     }
 
@@ -139,6 +699,57 @@ impl ExportProgress {
     }
 }
 
+/// One render in a batch export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportJob {
+    /// Timeline to render (e.g. a cut-down or re-framed version of the project)
+    pub timeline: crate::models::Timeline,
+
+    /// Export settings, including this job's output path
+    pub config: ExportConfig,
+}
+
+/// Outcome of one job in a batch export
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchJobResult {
+    /// Position of the job in the submitted batch
+    pub index: usize,
+
+    /// Export ID the job ran under
+    pub export_id: String,
+
+    /// Output file path
+    pub output_path: String,
+
+    /// Final status (completed or failed)
+    pub status: ExportStatus,
+
+    /// Error message if the job failed
+    pub error_message: Option<String>,
+}
+
+/// Aggregate progress across a batch export
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchExportProgress {
+    /// Batch ID
+    pub batch_id: String,
+
+    /// Jobs finished so far (completed or failed)
+    pub finished_jobs: usize,
+
+    /// Total jobs in the batch
+    pub total_jobs: usize,
+
+    /// Index of the job currently rendering, None once the batch is done
+    pub current_job: Option<usize>,
+
+    /// Percentage of the batch finished (0-100)
+    pub percentage: f32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,7 +758,9 @@ mod tests {
     fn test_export_config_default() {
         let config = ExportConfig::default();
         assert_eq!(config.codec, Some("libx264".to_string()));
-        assert_eq!(config.audio_codec, Some("aac".to_string()));
+        assert_eq!(config.video_codec, VideoCodec::H264);
+        assert_eq!(config.video_encoder(), "libx264");
+        assert_eq!(config.audio_codec, AudioCodec::Aac);
         assert_eq!(config.audio_bitrate, Some("192k".to_string()));
     }
 
@@ -176,12 +789,87 @@ mod tests {
         assert_eq!(progress.percentage, 100.0);
 
         let progress = ExportProgress::new("test-id".to_string(), 10000);
-        let progress = progress.mark_failed("Test error".to_string());
+        let progress = progress.mark_failed("disk_full", "Test error".to_string());
         assert_eq!(progress.status, ExportStatus::Failed);
         assert_eq!(progress.error_message, Some("Test error".to_string()));
+        assert_eq!(progress.error_code.as_deref(), Some("disk_full"));
+        let json = serde_json::to_value(&progress).unwrap();
+        assert_eq!(json["errorCode"], "disk_full");
 
         let progress = ExportProgress::new("test-id".to_string(), 10000);
         let progress = progress.mark_cancelled();
         assert_eq!(progress.status, ExportStatus::Cancelled);
     }
+
+    #[test]
+    fn test_audio_codec_ffmpeg_args() {
+        assert_eq!(AudioCodec::Aac.ffmpeg_codec(), "aac");
+        assert_eq!(AudioCodec::Alac.ffmpeg_codec(), "alac");
+        assert_eq!(AudioCodec::Pcm.ffmpeg_codec(), "pcm_s16le");
+
+        let config: ExportConfig =
+            serde_json::from_str(r#"{"outputPath":"/tmp/out.mov","audioCodec":"alac"}"#).unwrap();
+        assert_eq!(config.audio_codec, AudioCodec::Alac);
+        assert!(config.validate_audio_codec().is_ok());
+    }
+
+    #[test]
+    fn test_audio_codec_container_compatibility() {
+        let mut config = ExportConfig {
+            output_path: "/tmp/out.webm".to_string(),
+            audio_codec: AudioCodec::Alac,
+            ..Default::default()
+        };
+        let err = config.validate_audio_codec().unwrap_err();
+        assert!(err.contains("alac"));
+        assert!(err.contains(".webm"));
+
+        config.output_path = "/tmp/out.MP4".to_string();
+        assert!(config.validate_audio_codec().is_err());
+
+        config.audio_codec = AudioCodec::Aac;
+        assert!(config.validate_audio_codec().is_ok());
+
+        // Every container an audio codec accepts has a muxer that can write it
+        for (path, muxer) in [
+            ("out.mp4", "mp4"),
+            ("out.MOV", "mov"),
+            ("out.mkv", "matroska"),
+            ("out.m4a", "ipod"),
+            ("out", "mp4"),
+        ] {
+            config.output_path = format!("/tmp/{}", path);
+            assert_eq!(config.muxer(), muxer, "{}", path);
+        }
+        config.faststart = true;
+        assert!(!config.wants_faststart());
+    }
+
+    #[test]
+    fn test_pixel_format_codec_compatibility() {
+        let mut config = ExportConfig {
+            pixel_format: Some(PixelFormat::Yuv444p),
+            ..Default::default()
+        };
+        assert!(config.validate_pixel_format().is_ok());
+
+        config.codec = Some("h264_videotoolbox".to_string());
+        let err = config.validate_pixel_format().unwrap_err();
+        assert!(err.contains("yuv444p"));
+        assert!(err.contains("h264_videotoolbox"));
+
+        config.codec = Some("prores_ks".to_string());
+        assert!(config.validate_pixel_format().is_err());
+        config.pixel_format = Some(PixelFormat::Yuv422p10le);
+        assert!(config.validate_pixel_format().is_ok());
+
+        // No override: nothing to validate
+        config.pixel_format = None;
+        config.codec = Some("h264_videotoolbox".to_string());
+        assert!(config.validate_pixel_format().is_ok());
+
+        let config: ExportConfig =
+            serde_json::from_str(r#"{"outputPath":"/tmp/out.mp4","pixelFormat":"yuv420p10le"}"#).unwrap();
+        assert_eq!(config.pixel_format, Some(PixelFormat::Yuv420p10le));
+    }
 }
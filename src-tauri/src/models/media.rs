@@ -42,6 +42,48 @@ pub struct MediaFile {
     pub imported_at: String,
 }
 
+/// Stream details of a media file, as reported by ffprobe
+///
+/// Video fields are `None` for audio-only files.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaMetadata {
+    /// Container duration in seconds
+    pub duration_secs: f64,
+
+    /// Width of the first video stream in pixels
+    pub width: Option<u32>,
+
+    /// Height of the first video stream in pixels
+    pub height: Option<u32>,
+
+    /// Average frame rate of the first video stream
+    pub fps: Option<f64>,
+
+    /// Codec of the first video stream (e.g., "h264", "hevc")
+    pub video_codec: Option<String>,
+
+    /// Codec of the first audio stream (e.g., "aac")
+    pub audio_codec: Option<String>,
+
+    /// Whether the file has at least one audio stream
+    pub has_audio: bool,
+
+    /// Overall bitrate in bits per second
+    pub bitrate: Option<u64>,
+}
+
+/// Result of importing an EDL (`cmd_import_edl`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EdlImport {
+    /// Reconstructed timeline; clips whose media is missing are kept so they can be relinked
+    pub timeline: crate::models::Timeline,
+
+    /// Media paths referenced by the EDL that could not be found, without duplicates
+    pub unresolved_media: Vec<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,6 +120,18 @@ mod tests {
         assert_eq!(media_file, deserialized);
     }
 
+    #[test]
+    fn test_edl_import_lists_unresolved_media() {
+        let import = EdlImport {
+            timeline: crate::models::Timeline { tracks: Vec::new(), total_duration: 0 },
+            unresolved_media: vec!["/edl/missing.mov".to_string()],
+        };
+
+        let json = serde_json::to_value(&import).unwrap();
+        assert_eq!(json["unresolvedMedia"], serde_json::json!(["/edl/missing.mov"]));
+        assert_eq!(json["timeline"]["totalDuration"], 0);
+    }
+
     #[test]
     fn test_resolution_serialization() {
         let resolution = Resolution {
@@ -3,7 +3,7 @@ pub mod timeline;
 pub mod export;
 pub mod recording;
 
-pub use media::{MediaFile, Resolution};
-pub use timeline::{Timeline, Track, Clip, TrackType};
-pub use export::{ExportConfig, ExportProgress, ExportStatus};
-pub use recording::{WindowInfo, RecordingConfig, ScreenRecordingMode};
+pub use media::{EdlImport, MediaFile, MediaMetadata, Resolution};
+pub use timeline::{Timeline, TimelineError, Track, Clip, TrackType, Transition, TransitionKind};
+pub use export::{AudioCodec, Background, BatchExportProgress, BatchJobResult, Corner, EncoderBackend, ExportConfig, ExportJob, ExportPreset, ExportProgress, ExportStatus, PixelFormat, Preset, ReencodePreset, VideoCodec, WatermarkConfig, EXPORT_PRESETS};
+pub use recording::{CaptureRegion, WindowInfo, RecordingConfig, RecordingState, RecordingStatus, ScreenRecordingMode, OverlayAnchor, OverlayPosition, OverflowPolicy};
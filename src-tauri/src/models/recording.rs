@@ -4,6 +4,7 @@
 //! including screen recording mode and window selection, and multi-audio
 //! track recording architecture (Story 4.3).
 
+use crate::models::export::{EncoderBackend, Preset, VideoCodec};
 use serde::{Deserialize, Serialize};
 
 /// Audio source type for multi-track recording (Story 4.3)
@@ -95,6 +96,102 @@ pub struct PipSize {
     pub height: u32,
 }
 
+/// Nine-grid anchor for on-screen overlays (keystrokes, clicks)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverlayAnchor {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    #[default]
+    BottomCenter,
+    BottomRight,
+}
+
+/// Overlay placement: an anchor plus a margin from the frame edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayPosition {
+    /// Which of the nine grid positions the overlay snaps to
+    #[serde(default)]
+    pub anchor: OverlayAnchor,
+    /// Distance in pixels from the anchored edge(s); ignored on centered axes
+    #[serde(default = "default_overlay_margin")]
+    pub margin: u32,
+}
+
+fn default_overlay_margin() -> u32 {
+    40
+}
+
+impl Default for OverlayPosition {
+    fn default() -> Self {
+        Self {
+            anchor: OverlayAnchor::default(),
+            margin: default_overlay_margin(),
+        }
+    }
+}
+
+impl OverlayPosition {
+    /// Top-left pixel coordinates of an overlay of the given size within a frame
+    ///
+    /// Coordinates may be negative if the overlay plus margin does not fit the frame.
+    pub fn coordinates(
+        &self,
+        frame_width: u32,
+        frame_height: u32,
+        overlay_width: u32,
+        overlay_height: u32,
+    ) -> (i32, i32) {
+        let margin = self.margin as i32;
+        let free_x = frame_width as i32 - overlay_width as i32;
+        let free_y = frame_height as i32 - overlay_height as i32;
+
+        let (start_x, center_x, end_x) = (margin, free_x / 2, free_x - margin);
+        let (start_y, center_y, end_y) = (margin, free_y / 2, free_y - margin);
+
+        match self.anchor {
+            OverlayAnchor::TopLeft => (start_x, start_y),
+            OverlayAnchor::TopCenter => (center_x, start_y),
+            OverlayAnchor::TopRight => (end_x, start_y),
+            OverlayAnchor::CenterLeft => (start_x, center_y),
+            OverlayAnchor::Center => (center_x, center_y),
+            OverlayAnchor::CenterRight => (end_x, center_y),
+            OverlayAnchor::BottomLeft => (start_x, end_y),
+            OverlayAnchor::BottomCenter => (center_x, end_y),
+            OverlayAnchor::BottomRight => (end_x, end_y),
+        }
+    }
+}
+
+/// What the capture path does when the encoder's frame queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverflowPolicy {
+    /// Wait for space; every captured frame is encoded
+    #[default]
+    Block,
+    /// Discard the incoming frame
+    DropNewest,
+    /// Discard the oldest queued frame (lowest latency, for live streaming)
+    DropOldest,
+}
+
+/// Part of a display to record, in display points relative to its top-left corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
 /// Recording configuration (Story 4.2, extended in Story 4.3 for multi-track, Story 4.5 for PiP, Story 4.6 for recording mode)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -103,11 +200,11 @@ pub struct RecordingConfig {
     #[serde(default)]
     pub mode: RecordingMode,
 
-    /// Frame rate in FPS (30 or 60)
+    /// Frame rate in FPS (30 or 60); defaults to `CLIPPY_DEFAULT_FRAME_RATE` if set
     #[serde(default = "default_frame_rate")]
     pub frame_rate: u32,
 
-    /// Resolution (source, 1080p, 720p)
+    /// Resolution (source, 1080p, 720p); defaults to `CLIPPY_DEFAULT_RESOLUTION` if set
     #[serde(default = "default_resolution")]
     pub resolution: String,
 
@@ -119,6 +216,10 @@ pub struct RecordingConfig {
     #[serde(default)]
     pub microphone: bool,
 
+    /// Microphone to record (from `cmd_list_microphones`); defaults to the system default input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub microphone_device: Option<String>,
+
     /// Screen recording mode (fullscreen or window) from Story 4.1
     #[serde(default)]
     pub screen_recording_mode: ScreenRecordingMode,
@@ -127,6 +228,24 @@ pub struct RecordingConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub selected_window_id: Option<u32>,
 
+    /// In window mode, record only the window's application audio (macOS 13+; older
+    /// systems fall back to all system audio). The video is then cropped to where the
+    /// window was at the start and doesn't follow it if it moves.
+    #[serde(default)]
+    pub scope_audio_to_window: bool,
+
+    /// Display to record in fullscreen mode (from `cmd_get_available_displays`); defaults to the primary display
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_id: Option<u32>,
+
+    /// Record only this part of the display in fullscreen mode; output matches its size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_region: Option<CaptureRegion>,
+
+    /// Record every display side by side in fullscreen mode (ignores `display_id` and `capture_region`)
+    #[serde(default)]
+    pub capture_all_displays: bool,
+
     /// Audio tracks configuration (Story 4.3 - multi-track recording)
     /// Populated during recording based on enabled audio sources
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -139,33 +258,200 @@ pub struct RecordingConfig {
     /// PiP size in pixels (Story 4.5)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pip_size: Option<PipSize>,
+
+    /// Video encoder backend; unset picks VideoToolbox on macOS when the bundled FFmpeg has it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoder_backend: Option<EncoderBackend>,
+
+    /// Deprecated `hardwareEncoding` flag from before `encoder_backend`; `true` means
+    /// VideoToolbox and `false` software. Read through [`RecordingConfig::resolved_encoder_backend`]
+    #[serde(default, skip_serializing)]
+    pub hardware_encoding: Option<bool>,
+
+    /// VideoToolbox quality 1-100 (`-q:v`), only used with the VideoToolbox backend
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hardware_quality: Option<u8>,
+
+    /// Video codec (defaults to H.264; H.265 gives smaller files for large recordings)
+    #[serde(default)]
+    pub video_codec: VideoCodec,
+
+    /// Software encoder quality (`-crf`, 0-51); defaults to 23 for H.264 and 28 for H.265
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crf: Option<u8>,
+
+    /// Software encoder speed preset (`-preset`); defaults to fast for H.264 and ultrafast for H.265
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<Preset>,
+
+    /// FFmpeg encode threads (`-threads`); defaults to all cores but one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<u32>,
+
+    /// Placement of keystroke/click overlays (defaults to bottom-center)
+    #[serde(default)]
+    pub overlay_position: OverlayPosition,
+
+    /// Frame queue overflow behaviour (defaults to blocking)
+    #[serde(default)]
+    pub overflow_policy: OverflowPolicy,
+
+    /// Directory for intermediates (video-only encode, PCM audio); defaults to the local caches dir
+    #[serde(default)]
+    pub scratch_dir: Option<String>,
+
+    /// Free space floor in MB; the recording stops gracefully below it (0 disables).
+    /// Starting also needs this much free, or about a minute of recording if larger
+    #[serde(default = "default_min_free_space_mb")]
+    pub min_free_space_mb: u64,
+
+    /// How often the free space floor is checked, in seconds
+    #[serde(default = "default_disk_check_interval_secs")]
+    pub disk_check_interval_secs: u64,
+
+    /// Normalize the mixed audio loudness (EBU R128) when muxing the recording
+    #[serde(default)]
+    pub normalize_loudness: bool,
+
+    /// Continue on another display if the captured one is unplugged (otherwise stop)
+    #[serde(default)]
+    pub display_fallback: bool,
+
+    /// Countdown before screen capture starts, in milliseconds (`recording-countdown` ticks each second)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_delay_ms: Option<u64>,
+}
+
+/// Environment variable overriding the default frame rate
+pub const DEFAULT_FRAME_RATE_ENV: &str = "CLIPPY_DEFAULT_FRAME_RATE";
+
+/// Environment variable overriding the default resolution
+pub const DEFAULT_RESOLUTION_ENV: &str = "CLIPPY_DEFAULT_RESOLUTION";
+
+const BUILTIN_FRAME_RATE: u32 = 30;
+const BUILTIN_RESOLUTION: &str = "1080p";
+
+/// Frame rate default from `lookup`, falling back to the built-in 30 FPS
+///
+/// Unparseable or out-of-range values are ignored rather than rejected so
+/// `RecordingConfig::default()` never fails.
+fn frame_rate_default_from(lookup: impl Fn(&str) -> Option<String>) -> u32 {
+    lookup(DEFAULT_FRAME_RATE_ENV)
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .filter(|fps| (1..=120).contains(fps))
+        .unwrap_or(BUILTIN_FRAME_RATE)
+}
+
+/// Resolution default from `lookup`, falling back to the built-in 1080p
+fn resolution_default_from(lookup: impl Fn(&str) -> Option<String>) -> String {
+    lookup(DEFAULT_RESOLUTION_ENV)
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| matches!(value.as_str(), "source" | "1080p" | "720p"))
+        .unwrap_or_else(|| BUILTIN_RESOLUTION.to_string())
+}
+
+fn env_lookup(key: &str) -> Option<String> {
+    std::env::var(key).ok()
 }
 
 fn default_frame_rate() -> u32 {
-    30
+    frame_rate_default_from(env_lookup)
 }
 
 fn default_resolution() -> String {
-    "1080p".to_string()
+    resolution_default_from(env_lookup)
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_min_free_space_mb() -> u64 {
+    500
+}
+
+fn default_disk_check_interval_secs() -> u64 {
+    5
+}
+
 impl Default for RecordingConfig {
     fn default() -> Self {
         Self {
             mode: RecordingMode::default(),
-            frame_rate: 30,
-            resolution: "1080p".to_string(),
+            frame_rate: default_frame_rate(),
+            resolution: default_resolution(),
             system_audio: true,
             microphone: false,
+            microphone_device: None,
             screen_recording_mode: ScreenRecordingMode::default(),
             selected_window_id: None,
+            scope_audio_to_window: false,
+            display_id: None,
+            capture_region: None,
+            capture_all_displays: false,
             audio_tracks: Vec::new(),
             pip_position: None,
             pip_size: None,
+            encoder_backend: None,
+            hardware_encoding: None,
+            hardware_quality: None,
+            video_codec: VideoCodec::default(),
+            crf: None,
+            preset: None,
+            threads: None,
+            overlay_position: OverlayPosition::default(),
+            overflow_policy: OverflowPolicy::default(),
+            scratch_dir: None,
+            min_free_space_mb: default_min_free_space_mb(),
+            disk_check_interval_secs: default_disk_check_interval_secs(),
+            normalize_loudness: false,
+            display_fallback: false,
+            start_delay_ms: None,
+        }
+    }
+}
+
+impl RecordingConfig {
+    /// Requested encoder backend, honouring the deprecated `hardwareEncoding` flag
+    ///
+    /// `encoder_backend` wins when both are set; `None` lets the encoder pick.
+    pub fn resolved_encoder_backend(&self) -> Option<EncoderBackend> {
+        self.encoder_backend.or(match self.hardware_encoding {
+            Some(true) => Some(EncoderBackend::VideoToolbox),
+            Some(false) => Some(EncoderBackend::Software),
+            None => None,
+        })
+    }
+}
+
+/// Whether a recording ID is live (`cmd_get_recording_status`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingState {
+    Recording,
+    Paused,
+    /// Never started, or already stopped
+    NotFound,
+}
+
+/// Live status of a screen, webcam or PiP recording
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingStatus {
+    pub state: RecordingState,
+    /// Time recorded so far, excluding paused spans
+    pub elapsed_ms: u64,
+    /// Where the finished recording will be saved once stopped
+    pub output_path: Option<String>,
+}
+
+impl RecordingStatus {
+    /// Status for an ID with no active recording
+    pub fn not_found() -> Self {
+        Self {
+            state: RecordingState::NotFound,
+            elapsed_ms: 0,
+            output_path: None,
         }
     }
 }
@@ -199,6 +485,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_frame_rate_and_resolution_defaults_honor_overrides() {
+        let overrides = |key: &str| match key {
+            DEFAULT_FRAME_RATE_ENV => Some("60".to_string()),
+            DEFAULT_RESOLUTION_ENV => Some(" 720P ".to_string()),
+            _ => None,
+        };
+        assert_eq!(frame_rate_default_from(overrides), 60);
+        assert_eq!(resolution_default_from(overrides), "720p");
+
+        let unset = |_: &str| None;
+        assert_eq!(frame_rate_default_from(unset), 30);
+        assert_eq!(resolution_default_from(unset), "1080p");
+
+        // Invalid overrides fall back to the built-ins
+        let invalid = |key: &str| match key {
+            DEFAULT_FRAME_RATE_ENV => Some("fast".to_string()),
+            _ => Some("4k".to_string()),
+        };
+        assert_eq!(frame_rate_default_from(invalid), 30);
+        assert_eq!(resolution_default_from(invalid), "1080p");
+    }
+
     #[test]
     fn test_recording_config_default() {
         let config = RecordingConfig::default();
@@ -222,17 +531,38 @@ mod tests {
             resolution: "source".to_string(),
             system_audio: false,
             microphone: true,
+            microphone_device: None,
             screen_recording_mode: ScreenRecordingMode::Window,
             selected_window_id: Some(12345),
+            scope_audio_to_window: false,
+            display_id: None,
+            capture_region: None,
+            capture_all_displays: false,
             audio_tracks: Vec::new(),
             pip_position: None,
             pip_size: None,
+            encoder_backend: None,
+            hardware_encoding: None,
+            hardware_quality: None,
+            video_codec: VideoCodec::default(),
+            crf: None,
+            preset: None,
+            threads: None,
+            overlay_position: OverlayPosition::default(),
+            overflow_policy: OverflowPolicy::DropOldest,
+            scratch_dir: None,
+            min_free_space_mb: 250,
+            disk_check_interval_secs: 10,
+            normalize_loudness: false,
+            display_fallback: true,
+            start_delay_ms: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"mode\":\"screen\""));
         assert!(json.contains("\"frameRate\":60"));
         assert!(json.contains("\"resolution\":\"source\""));
+        assert!(json.contains("\"overflowPolicy\":\"dropOldest\""));
         assert!(json.contains("\"systemAudio\":false"));
         assert!(json.contains("\"microphone\":true"));
         assert!(json.contains("\"screenRecordingMode\":\"window\""));
@@ -261,6 +591,24 @@ mod tests {
             ScreenRecordingMode::Fullscreen
         );
         assert_eq!(config.selected_window_id, None);
+        assert!(!config.normalize_loudness);
+
+        let normalized: RecordingConfig = serde_json::from_str(r#"{"normalizeLoudness": true}"#).unwrap();
+        assert!(normalized.normalize_loudness);
+    }
+
+    #[test]
+    fn test_legacy_hardware_encoding_flag_maps_to_backend() {
+        let parse = |json: &str| serde_json::from_str::<RecordingConfig>(json).unwrap();
+
+        assert_eq!(parse(r#"{"hardwareEncoding": true}"#).resolved_encoder_backend(), Some(EncoderBackend::VideoToolbox));
+        assert_eq!(parse(r#"{"hardwareEncoding": false}"#).resolved_encoder_backend(), Some(EncoderBackend::Software));
+        assert_eq!(parse("{}").resolved_encoder_backend(), None);
+
+        // The explicit backend wins, and the old flag is never written back
+        let config = parse(r#"{"hardwareEncoding": true, "encoderBackend": "software"}"#);
+        assert_eq!(config.resolved_encoder_backend(), Some(EncoderBackend::Software));
+        assert!(!serde_json::to_string(&config).unwrap().contains("hardwareEncoding"));
     }
 
     #[test]
@@ -673,4 +1021,53 @@ mod tests {
         assert!(config.pip_position.is_none());
         assert!(config.pip_size.is_none());
     }
+
+    #[test]
+    fn test_overlay_position_corner_and_center_coordinates() {
+        // 400x100 overlay on a 1920x1080 frame with a 40px margin
+        let at = |anchor| {
+            OverlayPosition { anchor, margin: 40 }.coordinates(1920, 1080, 400, 100)
+        };
+
+        assert_eq!(at(OverlayAnchor::TopLeft), (40, 40));
+        assert_eq!(at(OverlayAnchor::TopRight), (1480, 40));
+        assert_eq!(at(OverlayAnchor::BottomLeft), (40, 940));
+        assert_eq!(at(OverlayAnchor::BottomRight), (1480, 940));
+        assert_eq!(at(OverlayAnchor::Center), (760, 490));
+        assert_eq!(at(OverlayAnchor::BottomCenter), (760, 940));
+    }
+
+    #[test]
+    fn test_overlay_position_default_and_deserialization() {
+        let config = RecordingConfig::default();
+        assert_eq!(config.overlay_position.anchor, OverlayAnchor::BottomCenter);
+        assert_eq!(config.overlay_position.margin, 40);
+
+        let position: OverlayPosition = serde_json::from_str(r#"{"anchor":"topRight"}"#).unwrap();
+        assert_eq!(position.anchor, OverlayAnchor::TopRight);
+        assert_eq!(position.margin, 40);
+    }
+
+    #[test]
+    fn test_capture_all_displays_defaults_off() {
+        assert!(!RecordingConfig::default().capture_all_displays);
+        let config: RecordingConfig = serde_json::from_str(r#"{"captureAllDisplays":true}"#).unwrap();
+        assert!(config.capture_all_displays);
+        assert_eq!(config.display_id, None);
+    }
+
+    #[test]
+    fn test_scope_audio_to_window_flag_round_trips() {
+        // Off unless asked for, so existing window recordings keep system-wide audio
+        assert!(!RecordingConfig::default().scope_audio_to_window);
+        let config: RecordingConfig =
+            serde_json::from_str(r#"{"screenRecordingMode":"window","selectedWindowId":7}"#).unwrap();
+        assert!(!config.scope_audio_to_window);
+
+        let json = r#"{"screenRecordingMode":"window","selectedWindowId":7,"scopeAudioToWindow":true}"#;
+        let config: RecordingConfig = serde_json::from_str(json).unwrap();
+        assert!(config.scope_audio_to_window);
+        assert_eq!(config.selected_window_id, Some(7));
+        assert!(serde_json::to_string(&config).unwrap().contains("\"scopeAudioToWindow\":true"));
+    }
 }
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
 
 /// Audio track metadata for multi-audio clips (Story 4.7)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -35,6 +37,29 @@ pub struct ClipTransform {
 
     /// Opacity (0.0 to 1.0) for alpha channel support
     pub opacity: f64,
+
+    /// Rotation in degrees, clockwise (default 0)
+    #[serde(default)]
+    pub rotation: f64,
+}
+
+/// Kind of transition between two clips
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum TransitionKind {
+    /// Dissolve from the outgoing clip into the incoming one (video and audio)
+    Crossfade,
+}
+
+/// Transition from a clip into the next clip on the same track
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Transition {
+    pub kind: TransitionKind,
+
+    /// Overlap of the two clips in milliseconds
+    /// Clamped at render time so it never exceeds either clip
+    pub duration_ms: u64,
 }
 
 /// Timeline clip representation
@@ -85,6 +110,27 @@ pub struct Clip {
     /// Optional - only used for video clips with position/scale overrides
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transform: Option<ClipTransform>,
+
+    /// Clip linked to this one after an audio detach (video ↔ audio)
+    /// Linked clips share source timing; the editor may move them together
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linked_clip_id: Option<String>,
+
+    /// Group this clip belongs to; grouped clips move and ripple together
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+
+    /// Transition into the next clip on the track
+    /// Only applied when that clip starts exactly where this one ends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transition_out: Option<Transition>,
+}
+
+impl Clip {
+    /// Timeline position where the clip ends (start + trimmed length)
+    pub fn end_time(&self) -> u64 {
+        self.start_time + self.trim_out.saturating_sub(self.trim_in)
+    }
 }
 
 /// Track containing ordered clips
@@ -113,6 +159,31 @@ pub enum TrackType {
     Audio,
 }
 
+/// Problem that would make a timeline fail (or export wrongly) in FFmpeg
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TimelineError {
+    /// Two clips on the same track cover the same time
+    #[error("Clips {clip_id} and {other_clip_id} overlap on track {track_id}")]
+    Overlap {
+        track_id: String,
+        clip_id: String,
+        other_clip_id: String,
+    },
+
+    /// The clip's media file doesn't exist
+    #[error("Media file for clip {clip_id} not found: {file_path}")]
+    MissingMedia { clip_id: String, file_path: String },
+
+    /// Trim out is not after trim in, so nothing of the clip would play
+    #[error("Clip {clip_id} has no length (trim {trim_in}ms to {trim_out}ms)")]
+    EmptyClip { clip_id: String, trim_in: u64, trim_out: u64 },
+
+    /// Trim points past the end of the source media
+    #[error("Clip {clip_id} is trimmed to {trim_out}ms but its media is only {duration}ms long")]
+    TrimOutOfRange { clip_id: String, trim_out: u64, duration: u64 },
+}
+
 /// Timeline containing tracks
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -135,6 +206,325 @@ impl Timeline {
         self.tracks.iter().filter(|t| t.track_type == TrackType::Audio)
     }
 
+    /// Detach a video clip's audio onto an audio track
+    ///
+    /// The video clip is muted and a new audio clip referencing the same source
+    /// is placed at the same position with the same trim points. It goes on the
+    /// first audio track with room for it, or on a new audio track. Both clips
+    /// are linked to each other via `linked_clip_id`.
+    ///
+    /// # Returns
+    /// * `Ok((video_clip, audio_clip))` - The updated clips as stored in the timeline
+    /// * `Err(String)` - The track or clip doesn't exist, the track isn't a video track,
+    ///   or the clip is already linked (its audio was detached before)
+    pub fn detach_audio(&mut self, track_id: &str, clip_id: &str) -> Result<(Clip, Clip), String> {
+        let track = self
+            .tracks
+            .iter_mut()
+            .find(|t| t.id == track_id)
+            .ok_or_else(|| format!("Track not found: {}", track_id))?;
+        if track.track_type != TrackType::Video {
+            return Err(format!("Track {} is not a video track", track_id));
+        }
+        let video_clip = track
+            .clips
+            .iter_mut()
+            .find(|c| c.id == clip_id)
+            .ok_or_else(|| format!("Clip not found: {}", clip_id))?;
+        if let Some(linked) = &video_clip.linked_clip_id {
+            return Err(format!("Clip {} is already linked to {}", clip_id, linked));
+        }
+
+        let audio_clip = Clip {
+            id: uuid::Uuid::new_v4().to_string(),
+            transform: None,
+            linked_clip_id: Some(video_clip.id.clone()),
+            ..video_clip.clone()
+        };
+        video_clip.muted = Some(true);
+        video_clip.linked_clip_id = Some(audio_clip.id.clone());
+        let video_clip = video_clip.clone();
+
+        let (start, end) = (audio_clip.start_time, audio_clip.end_time());
+        let free_track = self.tracks.iter().position(|t| {
+            t.track_type == TrackType::Audio
+                && t.clips.iter().all(|c| end <= c.start_time || start >= c.end_time())
+        });
+        let audio_track_index = match free_track {
+            Some(index) => index,
+            None => {
+                let track_number = self.audio_tracks().map(|t| t.track_number).max().unwrap_or(0) + 1;
+                self.tracks.push(Track {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    track_number,
+                    clips: Vec::new(),
+                    track_type: TrackType::Audio,
+                });
+                self.tracks.len() - 1
+            }
+        };
+
+        let clips = &mut self.tracks[audio_track_index].clips;
+        let position = clips.partition_point(|c| c.start_time <= audio_clip.start_time);
+        clips.insert(position, audio_clip.clone());
+
+        Ok((video_clip, audio_clip))
+    }
+
+    /// All clips across tracks
+    fn clips(&self) -> impl Iterator<Item = &Clip> {
+        self.tracks.iter().flat_map(|t| t.clips.iter())
+    }
+
+    /// All clips across tracks, mutably
+    fn clips_mut(&mut self) -> impl Iterator<Item = &mut Clip> {
+        self.tracks.iter_mut().flat_map(|t| t.clips.iter_mut())
+    }
+
+    /// Whether two clips are edited together: same group, or linked to each other
+    fn edited_together(a: &Clip, b: &Clip) -> bool {
+        (a.group_id.is_some() && a.group_id == b.group_id)
+            || a.linked_clip_id.as_deref() == Some(b.id.as_str())
+            || b.linked_clip_id.as_deref() == Some(a.id.as_str())
+    }
+
+    /// IDs of the clips edited with `clip_id`: its group and linked clips, and theirs in turn
+    fn group_members(&self, clip_id: &str) -> Result<Vec<String>, String> {
+        if !self.clips().any(|c| c.id == clip_id) {
+            return Err(format!("Clip not found: {}", clip_id));
+        }
+
+        let mut members = vec![clip_id.to_string()];
+        loop {
+            let joining: Vec<String> = self
+                .clips()
+                .filter(|c| !members.contains(&c.id))
+                .filter(|c| {
+                    self.clips()
+                        .any(|m| members.contains(&m.id) && Self::edited_together(m, c))
+                })
+                .map(|c| c.id.clone())
+                .collect();
+            if joining.is_empty() {
+                return Ok(members);
+            }
+            members.extend(joining);
+        }
+    }
+
+    /// Keep clips in start order and `total_duration` at the last clip end
+    fn normalize(&mut self) {
+        for track in &mut self.tracks {
+            track.clips.sort_by_key(|c| c.start_time);
+        }
+        self.total_duration = self.clips().map(Clip::end_time).max().unwrap_or(0);
+    }
+
+    /// Group clips so they move and ripple together
+    ///
+    /// Clips already in another group leave it; a group left with a single
+    /// clip is dissolved.
+    ///
+    /// # Returns
+    /// * `Ok(group_id)` - ID of the new group
+    /// * `Err(String)` - Fewer than two clips, or a clip doesn't exist
+    pub fn group_clips(&mut self, clip_ids: &[String]) -> Result<String, String> {
+        if clip_ids.len() < 2 {
+            return Err("A group needs at least two clips".to_string());
+        }
+        if let Some(missing) = clip_ids.iter().find(|id| !self.clips().any(|c| &c.id == *id)) {
+            return Err(format!("Clip not found: {}", missing));
+        }
+
+        let group_id = uuid::Uuid::new_v4().to_string();
+        let mut previous_groups = Vec::new();
+        for clip in self.clips_mut().filter(|c| clip_ids.contains(&c.id)) {
+            if let Some(previous) = clip.group_id.replace(group_id.clone()) {
+                previous_groups.push(previous);
+            }
+        }
+
+        for previous in previous_groups {
+            let remaining: Vec<&Clip> = self
+                .clips()
+                .filter(|c| c.group_id.as_deref() == Some(previous.as_str()))
+                .collect();
+            if remaining.len() == 1 {
+                self.ungroup(&previous);
+            }
+        }
+
+        Ok(group_id)
+    }
+
+    /// Dissolve a group; returns how many clips were released
+    pub fn ungroup(&mut self, group_id: &str) -> usize {
+        let mut released = 0;
+        for clip in self.clips_mut().filter(|c| c.group_id.as_deref() == Some(group_id)) {
+            clip.group_id = None;
+            released += 1;
+        }
+        released
+    }
+
+    /// Move a clip to `start_time`, shifting its group and linked clips by the same delta
+    ///
+    /// The delta is clamped so no group member moves before 0.
+    pub fn move_clip(&mut self, clip_id: &str, start_time: u64) -> Result<(), String> {
+        let members = self.group_members(clip_id)?;
+        let current = self.clips().find(|c| c.id == clip_id).map(|c| c.start_time).unwrap_or(0);
+        let earliest = self
+            .clips()
+            .filter(|c| members.contains(&c.id))
+            .map(|c| c.start_time)
+            .min()
+            .unwrap_or(0);
+
+        let delta = (start_time as i64 - current as i64).max(-(earliest as i64));
+        for clip in self.clips_mut().filter(|c| members.contains(&c.id)) {
+            clip.start_time = (clip.start_time as i64 + delta) as u64;
+        }
+
+        self.normalize();
+        Ok(())
+    }
+
+    /// Trim a clip, changing its group and linked clips by the same amounts
+    ///
+    /// Moving the in point moves the clip start with it, so the media left in
+    /// the clip stays where it was on the timeline (and linked audio stays in
+    /// sync). The changes are clamped so every member keeps its trim points
+    /// within its media and doesn't start before 0.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Every member trimmed
+    /// * `Err(String)` - The clip doesn't exist, or a member would be left with no length
+    pub fn trim_clip(&mut self, clip_id: &str, trim_in: u64, trim_out: u64) -> Result<(), String> {
+        if trim_out <= trim_in {
+            return Err(format!("Trim out ({}ms) must be after trim in ({}ms)", trim_out, trim_in));
+        }
+        let members = self.group_members(clip_id)?;
+        let (current_in, current_out) = self
+            .clips()
+            .find(|c| c.id == clip_id)
+            .map(|c| (c.trim_in, c.trim_out))
+            .unwrap_or_default();
+
+        let mut delta_in = trim_in as i64 - current_in as i64;
+        let mut delta_out = trim_out as i64 - current_out as i64;
+        for clip in self.clips().filter(|c| members.contains(&c.id)) {
+            delta_in = delta_in.max(-(clip.trim_in as i64)).max(-(clip.start_time as i64));
+            delta_out = delta_out.min(clip.duration as i64 - clip.trim_out as i64);
+        }
+        if let Some(clip) = self.clips().find(|c| {
+            members.contains(&c.id) && c.trim_out as i64 + delta_out <= c.trim_in as i64 + delta_in
+        }) {
+            return Err(format!("Trimming would leave clip {} with no length", clip.id));
+        }
+
+        for clip in self.clips_mut().filter(|c| members.contains(&c.id)) {
+            clip.trim_in = (clip.trim_in as i64 + delta_in) as u64;
+            clip.trim_out = (clip.trim_out as i64 + delta_out) as u64;
+            clip.start_time = (clip.start_time as i64 + delta_in) as u64;
+        }
+
+        self.normalize();
+        Ok(())
+    }
+
+    /// Remove a clip (with its group and linked clips) and close the gaps on the affected tracks
+    ///
+    /// Clips after a removed clip on the same track shift left by its length.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Clip>)` - The removed clips
+    /// * `Err(String)` - The clip doesn't exist
+    pub fn ripple_delete(&mut self, clip_id: &str) -> Result<Vec<Clip>, String> {
+        let members = self.group_members(clip_id)?;
+        let mut removed = Vec::new();
+
+        for track in &mut self.tracks {
+            let (gone, kept): (Vec<Clip>, Vec<Clip>) = std::mem::take(&mut track.clips)
+                .into_iter()
+                .partition(|c| members.contains(&c.id));
+
+            track.clips = kept;
+            for clip in track.clips.iter_mut() {
+                let shift: u64 = gone
+                    .iter()
+                    .filter(|g| g.end_time() <= clip.start_time)
+                    .map(|g| g.end_time() - g.start_time)
+                    .sum();
+                clip.start_time -= shift.min(clip.start_time);
+            }
+            removed.extend(gone);
+        }
+
+        self.normalize();
+        Ok(removed)
+    }
+
+    /// Check the timeline can be exported
+    ///
+    /// Catches overlapping clips on a track, missing media files, clips with
+    /// no length and trim points past the end of the media, which otherwise
+    /// surface as confusing FFmpeg errors.
+    ///
+    /// # Returns
+    /// * `Ok(())` - Nothing found
+    /// * `Err(Vec<TimelineError>)` - Every problem found, in track order
+    pub fn validate(&self) -> Result<(), Vec<TimelineError>> {
+        self.validate_with(|path| Path::new(path).exists())
+    }
+
+    /// `validate` with the media existence check supplied by the caller
+    fn validate_with(&self, media_exists: impl Fn(&str) -> bool) -> Result<(), Vec<TimelineError>> {
+        let mut errors = Vec::new();
+
+        for track in &self.tracks {
+            for clip in &track.clips {
+                if !media_exists(&clip.file_path) {
+                    errors.push(TimelineError::MissingMedia {
+                        clip_id: clip.id.clone(),
+                        file_path: clip.file_path.clone(),
+                    });
+                }
+                if clip.trim_out <= clip.trim_in {
+                    errors.push(TimelineError::EmptyClip {
+                        clip_id: clip.id.clone(),
+                        trim_in: clip.trim_in,
+                        trim_out: clip.trim_out,
+                    });
+                } else if clip.trim_out > clip.duration {
+                    errors.push(TimelineError::TrimOutOfRange {
+                        clip_id: clip.id.clone(),
+                        trim_out: clip.trim_out,
+                        duration: clip.duration,
+                    });
+                }
+            }
+
+            // Clips are ordered by start, but don't rely on the frontend for it
+            let mut ordered: Vec<&Clip> = track.clips.iter().collect();
+            ordered.sort_by_key(|c| c.start_time);
+            for pair in ordered.windows(2) {
+                if pair[1].start_time < pair[0].end_time() {
+                    errors.push(TimelineError::Overlap {
+                        track_id: track.id.clone(),
+                        clip_id: pair[0].id.clone(),
+                        other_clip_id: pair[1].id.clone(),
+                    });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Convert milliseconds to FFmpeg time format (HH:MM:SS.mmm)
     pub fn ms_to_ffmpeg_time(ms: u64) -> String {
         let total_seconds = ms / 1000;
@@ -189,6 +579,296 @@ mod tests {
         assert_eq!(timeline.audio_tracks().count(), 1);
     }
 
+    fn clip(id: &str, start_time: u64, trim_in: u64, trim_out: u64) -> Clip {
+        Clip {
+            id: id.to_string(),
+            file_path: format!("/media/{}.mp4", id),
+            start_time,
+            duration: 10_000,
+            trim_in,
+            trim_out,
+            fade_in: None,
+            fade_out: None,
+            volume: Some(0.8),
+            muted: None,
+            audio_tracks: None,
+            transform: None,
+            linked_clip_id: None,
+            group_id: None,
+            transition_out: None,
+        }
+    }
+
+    fn track(id: &str, track_type: TrackType, clips: Vec<Clip>) -> Track {
+        Track {
+            id: id.to_string(),
+            track_number: 1,
+            clips,
+            track_type,
+        }
+    }
+
+    #[test]
+    fn test_validate_reports_overlaps_and_missing_media() {
+        let timeline = Timeline {
+            tracks: vec![
+                track("video", TrackType::Video, vec![clip("a", 0, 0, 5_000), clip("b", 4_000, 0, 3_000)]),
+                // Overlapping in time is fine across tracks, and touching clips don't overlap
+                track("music", TrackType::Audio, vec![clip("c", 2_000, 0, 3_000), clip("d", 5_000, 0, 1_000)]),
+            ],
+            total_duration: 7_000,
+        };
+        let all_present = |_: &str| true;
+
+        assert_eq!(
+            timeline.validate_with(all_present),
+            Err(vec![TimelineError::Overlap {
+                track_id: "video".to_string(),
+                clip_id: "a".to_string(),
+                other_clip_id: "b".to_string(),
+            }])
+        );
+
+        let errors = timeline.validate_with(|path| path != "/media/d.mp4").unwrap_err();
+        assert_eq!(
+            errors[1],
+            TimelineError::MissingMedia { clip_id: "d".to_string(), file_path: "/media/d.mp4".to_string() }
+        );
+        assert_eq!(errors.len(), 2);
+
+        // validate() checks the real filesystem
+        assert_eq!(timeline.validate().unwrap_err().len(), 5);
+        let empty = Timeline { tracks: vec![], total_duration: 0 };
+        assert_eq!(empty.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_reports_empty_clips_and_trims_past_media_end() {
+        let timeline = Timeline {
+            tracks: vec![track(
+                "video",
+                TrackType::Video,
+                vec![clip("ok", 0, 0, 10_000), clip("empty", 10_000, 3_000, 3_000), clip("long", 20_000, 2_000, 12_000)],
+            )],
+            total_duration: 30_000,
+        };
+
+        let errors = timeline.validate_with(|_| true).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                TimelineError::EmptyClip { clip_id: "empty".to_string(), trim_in: 3_000, trim_out: 3_000 },
+                TimelineError::TrimOutOfRange { clip_id: "long".to_string(), trim_out: 12_000, duration: 10_000 },
+            ]
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            "Clip long is trimmed to 12000ms but its media is only 10000ms long"
+        );
+        let json = serde_json::to_value(&errors[0]).unwrap();
+        assert_eq!(json["kind"], "emptyClip");
+        assert_eq!(json["clipId"], "empty");
+    }
+
+    #[test]
+    fn test_detach_audio_creates_linked_time_aligned_clips() {
+        use crate::test_utils::timeline_fixtures::ClipBuilder;
+
+        let interview = ClipBuilder::new("/media/interview.mp4", 4_000, 10_000)
+            .with_id("interview")
+            .with_trim(1_500, 9_000)
+            .with_volume(0.8)
+            .build();
+        let mut timeline = Timeline {
+            tracks: vec![
+                track("video", TrackType::Video, vec![interview]),
+                // Occupied where the detached audio goes: a new audio track is created
+                track("music", TrackType::Audio, vec![ClipBuilder::new("/media/music.mp3", 0, 10_000).build()]),
+            ],
+            total_duration: 11_500,
+        };
+
+        let (video, audio) = timeline.detach_audio("video", "interview").unwrap();
+
+        assert_eq!(video.muted, Some(true));
+        assert_eq!(audio.muted, None);
+        assert_eq!(video.linked_clip_id.as_deref(), Some(audio.id.as_str()));
+        assert_eq!(audio.linked_clip_id.as_deref(), Some("interview"));
+        assert_ne!(audio.id, video.id);
+
+        // Same source, same timing
+        assert_eq!(audio.file_path, video.file_path);
+        assert_eq!(
+            (audio.start_time, audio.trim_in, audio.trim_out, audio.duration),
+            (video.start_time, video.trim_in, video.trim_out, video.duration)
+        );
+        assert_eq!(audio.volume, Some(0.8));
+
+        let audio_tracks: Vec<&Track> = timeline.audio_tracks().collect();
+        assert_eq!(audio_tracks.len(), 2);
+        assert_eq!(audio_tracks[1].track_number, 2);
+        assert_eq!(audio_tracks[1].clips, vec![audio.clone()]);
+        assert_eq!(timeline.tracks[0].clips, vec![video]);
+    }
+
+    #[test]
+    fn test_grouped_clips_move_together_until_ungrouped() {
+        use crate::test_utils::timeline_fixtures::{ClipBuilder, TimelineFixtureBuilder};
+
+        let mut timeline = TimelineFixtureBuilder::new()
+            .add_video_track(vec![ClipBuilder::new("/media/v.mp4", 1_000, 4_000).with_id("v").build()])
+            .add_audio_track(vec![
+                ClipBuilder::new("/media/a.mp4", 1_500, 3_000).with_id("a").build(),
+                ClipBuilder::new("/media/music.mp3", 6_000, 2_000).with_id("music").build(),
+            ])
+            .build();
+        let group_id = timeline.group_clips(&["v".to_string(), "a".to_string()]).unwrap();
+
+        let start_of = |timeline: &Timeline, id: &str| {
+            timeline.clips().find(|c| c.id == id).unwrap().start_time
+        };
+
+        timeline.move_clip("v", 3_000).unwrap();
+        assert_eq!(start_of(&timeline, "v"), 3_000);
+        assert_eq!(start_of(&timeline, "a"), 3_500);
+        assert_eq!(start_of(&timeline, "music"), 6_000);
+
+        // Moving left is clamped so no member goes before 0
+        timeline.move_clip("a", 0).unwrap();
+        assert_eq!(start_of(&timeline, "v"), 0);
+        assert_eq!(start_of(&timeline, "a"), 500);
+
+        assert_eq!(timeline.ungroup(&group_id), 2);
+        timeline.move_clip("v", 2_000).unwrap();
+        assert_eq!(start_of(&timeline, "v"), 2_000);
+        assert_eq!(start_of(&timeline, "a"), 500);
+        assert_eq!(timeline.total_duration, 8_000);
+    }
+
+    #[test]
+    fn test_ripple_delete_removes_group_and_closes_gaps() {
+        use crate::test_utils::timeline_fixtures::{ClipBuilder, TimelineFixtureBuilder};
+
+        let mut timeline = TimelineFixtureBuilder::new()
+            .add_video_track(vec![
+                ClipBuilder::new("/media/v1.mp4", 0, 4_000).with_id("v1").build(),
+                ClipBuilder::new("/media/v2.mp4", 4_000, 2_000).with_id("v2").build(),
+            ])
+            .add_audio_track(vec![
+                ClipBuilder::new("/media/a1.mp4", 0, 4_000).with_id("a1").build(),
+                ClipBuilder::new("/media/a2.mp4", 5_000, 1_000).with_id("a2").build(),
+            ])
+            .build();
+        timeline.group_clips(&["v1".to_string(), "a1".to_string()]).unwrap();
+
+        let removed = timeline.ripple_delete("a1").unwrap();
+
+        let mut removed_ids: Vec<String> = removed.into_iter().map(|c| c.id).collect();
+        removed_ids.sort();
+        assert_eq!(removed_ids, ["a1", "v1"]);
+        assert_eq!(timeline.tracks[0].clips[0].start_time, 0);
+        assert_eq!(timeline.tracks[1].clips[0].start_time, 1_000);
+        assert_eq!(timeline.total_duration, 2_000);
+    }
+
+    #[test]
+    fn test_linked_clips_move_and_delete_together() {
+        use crate::test_utils::timeline_fixtures::{ClipBuilder, TimelineFixtureBuilder};
+
+        let mut timeline = TimelineFixtureBuilder::new()
+            .add_video_track(vec![ClipBuilder::new("/media/v.mp4", 2_000, 5_000).with_id("v").with_linked_clip("a").build()])
+            .add_audio_track(vec![
+                ClipBuilder::new("/media/v.mp4", 2_000, 5_000).with_id("a").with_linked_clip("v").build(),
+                ClipBuilder::new("/media/b.mp3", 8_000, 1_000).with_id("b").build(),
+            ])
+            .build();
+
+        timeline.move_clip("a", 3_000).unwrap();
+        assert_eq!(timeline.tracks[0].clips[0].start_time, 3_000);
+        assert_eq!(timeline.tracks[1].clips[0].start_time, 3_000);
+
+        let removed = timeline.ripple_delete("v").unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(timeline.tracks[0].clips.is_empty());
+        assert_eq!(timeline.tracks[1].clips.len(), 1);
+        assert_eq!(timeline.tracks[1].clips[0].start_time, 3_000);
+    }
+
+    #[test]
+    fn test_trim_applies_to_group_and_linked_clips() {
+        use crate::test_utils::timeline_fixtures::{ClipBuilder, TimelineFixtureBuilder};
+
+        let mut timeline = TimelineFixtureBuilder::new()
+            .add_video_track(vec![ClipBuilder::new("/media/v.mp4", 1_000, 10_000)
+                .with_id("v")
+                .with_trim(0, 6_000)
+                .with_linked_clip("a")
+                .with_group("g")
+                .build()])
+            .add_video_track(vec![ClipBuilder::new("/media/title.png", 1_000, 4_000).with_id("title").with_group("g").build()])
+            .add_audio_track(vec![ClipBuilder::new("/media/v.mp4", 1_000, 10_000)
+                .with_id("a")
+                .with_trim(0, 6_000)
+                .with_linked_clip("v")
+                .build()])
+            .build();
+        let trims = |timeline: &Timeline, id: &str| {
+            let clip = timeline.clips().find(|c| c.id == id).unwrap();
+            (clip.start_time, clip.trim_in, clip.trim_out)
+        };
+
+        // The in point moves the start with it, so the remaining media stays in place
+        timeline.trim_clip("v", 1_000, 5_000).unwrap();
+        assert_eq!(trims(&timeline, "v"), (2_000, 1_000, 5_000));
+        assert_eq!(trims(&timeline, "a"), (2_000, 1_000, 5_000));
+        assert_eq!(trims(&timeline, "title"), (2_000, 1_000, 3_000));
+
+        // Extending is clamped by the shortest media in the group
+        timeline.trim_clip("v", 1_000, 9_000).unwrap();
+        assert_eq!(trims(&timeline, "v"), (2_000, 1_000, 6_000));
+        assert_eq!(trims(&timeline, "title"), (2_000, 1_000, 4_000));
+
+        // A trim that would empty a member changes nothing
+        assert!(timeline.trim_clip("v", 4_500, 6_000).unwrap_err().contains("title"));
+        assert_eq!(trims(&timeline, "a"), (2_000, 1_000, 6_000));
+        assert!(timeline.trim_clip("v", 3_000, 3_000).is_err());
+    }
+
+    #[test]
+    fn test_detach_audio_rejects_missing_or_audio_clips() {
+        use crate::test_utils::timeline_fixtures::ClipBuilder;
+
+        let mut timeline = Timeline {
+            tracks: vec![
+                track("video", TrackType::Video, vec![ClipBuilder::new("/media/a.mp4", 0, 5_000).with_id("a").build()]),
+                track("audio", TrackType::Audio, vec![ClipBuilder::new("/media/b.mp3", 0, 5_000).with_id("b").build()]),
+            ],
+            total_duration: 5_000,
+        };
+
+        assert!(timeline.detach_audio("video", "missing").is_err());
+        assert!(timeline.detach_audio("audio", "b").unwrap_err().contains("not a video track"));
+        assert_eq!(timeline.tracks[0].clips[0].linked_clip_id, None);
+    }
+
+    #[test]
+    fn test_detach_audio_twice_is_rejected() {
+        use crate::test_utils::timeline_fixtures::ClipBuilder;
+
+        let mut timeline = Timeline {
+            tracks: vec![track("video", TrackType::Video, vec![ClipBuilder::new("/media/a.mp4", 0, 5_000).with_id("a").build()])],
+            total_duration: 5_000,
+        };
+
+        let (_, audio) = timeline.detach_audio("video", "a").unwrap();
+        let err = timeline.detach_audio("video", "a").unwrap_err();
+        assert!(err.contains("already linked"), "{}", err);
+
+        // Still exactly one detached audio clip, linked both ways
+        assert_eq!(timeline.audio_tracks().flat_map(|t| t.clips.iter()).count(), 1);
+        assert_eq!(timeline.tracks[0].clips[0].linked_clip_id.as_deref(), Some(audio.id.as_str()));
+    }
+
     #[test]
     fn test_4_7_unit_003_audio_track_serialization() {
         // Create clip with 3 audio tracks (Story 4.7)
@@ -226,6 +906,9 @@ mod tests {
             muted: Some(false),
             audio_tracks: Some(audio_tracks),
             transform: None,
+            linked_clip_id: None,
+            group_id: None,
+            transition_out: None,
         };
 
         // Serialize to JSON
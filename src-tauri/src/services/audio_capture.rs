@@ -5,7 +5,9 @@
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, Host, Sample, SampleFormat, Stream, StreamConfig, SupportedStreamConfig};
-use std::sync::atomic::{AtomicBool, Ordering};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::mpsc;
@@ -40,6 +42,9 @@ pub enum AudioCaptureError {
 
     #[error("Microphone permission denied or not available")]
     PermissionDenied,
+
+    #[error("Microphone \"{name}\" not found. Available: {}", available.join(", "))]
+    DeviceNotFound { name: String, available: Vec<String> },
 }
 
 /// Audio sample data in float32 format
@@ -178,12 +183,49 @@ impl AudioCapture {
                 AudioCaptureError::DefaultDeviceNotAvailable
             })?;
 
+        let device_name = self.use_device(device)?;
+        info!("Selected default device: {}", device_name);
+
+        Ok(device_name)
+    }
+
+    /// Select an input device by name (as listed by `enumerate_devices`)
+    ///
+    /// Returns `DeviceNotFound` with the available device names if no input
+    /// device has that name.
+    pub fn select_device_by_name(&mut self, name: &str) -> Result<String, AudioCaptureError> {
+        debug!("Selecting input device: {}", name);
+
+        let mut available = Vec::new();
+        let devices = self.host.input_devices().map_err(|e| {
+            error!("Failed to enumerate input devices: {}", e);
+            AudioCaptureError::NoDevicesFound
+        })?;
+
+        for device in devices {
+            match device.name() {
+                Ok(device_name) if device_name == name => {
+                    let device_name = self.use_device(device)?;
+                    info!("Selected device: {}", device_name);
+                    return Ok(device_name);
+                }
+                Ok(device_name) => available.push(device_name),
+                Err(e) => warn!("Failed to get device info: {}", e),
+            }
+        }
+
+        Err(AudioCaptureError::DeviceNotFound {
+            name: name.to_string(),
+            available,
+        })
+    }
+
+    /// Make `device` the capture device, loading its default input config
+    fn use_device(&mut self, device: Device) -> Result<String, AudioCaptureError> {
         let device_name = device
             .name()
             .map_err(|e| AudioCaptureError::DeviceNameError(e.to_string()))?;
 
-        info!("Selected default device: {}", device_name);
-
         // Get default config (prefer 48kHz for professional audio)
         let config = device
             .default_input_config()
@@ -449,13 +491,165 @@ impl Drop for AudioCapture {
     }
 }
 
+/// Number of captures currently held alive by an `AudioCaptureGuard`
+static LIVE_CAPTURES: AtomicUsize = AtomicUsize::new(0);
+
+/// Keeps an audio capture alive on its own thread until the guard is dropped
+///
+/// `AudioCapture` holds a `cpal::Stream`, which isn't `Send`, so it can't be
+/// stored in the recording state shared between commands. The guard owns the
+/// thread that created the capture instead: dropping the guard drops the
+/// capture on that thread, which stops the stream and closes its sample
+/// channel.
+///
+/// Dropping joins that thread, so async code should call `release` (and take
+/// the guard out of any shared map first) rather than drop it on a runtime
+/// worker or under a lock.
+pub struct AudioCaptureGuard {
+    release_tx: Option<std::sync::mpsc::Sender<()>>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl AudioCaptureGuard {
+    /// Run `setup` on a new thread and keep the capture it returns alive there
+    ///
+    /// `setup` returns the capture plus any values the caller needs from it
+    /// (e.g. the channel count), which are passed back with the guard.
+    ///
+    /// Setup (opening the audio device) runs on that thread; the caller awaits
+    /// its result without blocking the runtime.
+    ///
+    /// # Returns
+    ///
+    /// The guard and `setup`'s values, or `setup`'s error (no thread is kept).
+    pub async fn spawn<C, T, F>(setup: F) -> Result<(Self, T), String>
+    where
+        C: 'static,
+        T: Send + 'static,
+        F: FnOnce() -> Result<(C, T), String> + Send + 'static,
+    {
+        let (result_tx, result_rx) = tokio::sync::oneshot::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+        let thread = std::thread::spawn(move || {
+            let capture = match setup() {
+                Ok((capture, value)) => {
+                    LIVE_CAPTURES.fetch_add(1, Ordering::SeqCst);
+                    let _ = result_tx.send(Ok(value));
+                    capture
+                }
+                Err(e) => {
+                    let _ = result_tx.send(Err(e));
+                    return;
+                }
+            };
+
+            // Returns once the guard drops its sender
+            let _ = release_rx.recv();
+            drop(capture);
+            LIVE_CAPTURES.fetch_sub(1, Ordering::SeqCst);
+            debug!("Audio capture released");
+        });
+
+        match result_rx.await {
+            Ok(Ok(value)) => Ok((
+                Self {
+                    release_tx: Some(release_tx),
+                    thread: Some(thread),
+                },
+                value,
+            )),
+            failed => {
+                // The thread has returned or is about to; join it off the runtime
+                let _ = tokio::task::spawn_blocking(move || thread.join()).await;
+                match failed {
+                    Ok(Err(e)) => Err(e),
+                    _ => Err("Audio capture thread panicked during setup".to_string()),
+                }
+            }
+        }
+    }
+
+    /// Stop the capture and wait for its thread on the blocking pool
+    pub async fn release(self) {
+        if let Err(e) = tokio::task::spawn_blocking(move || drop(self)).await {
+            warn!("Audio capture release task failed: {}", e);
+        }
+    }
+
+    /// Number of captures currently kept alive by guards
+    pub fn live_count() -> usize {
+        LIVE_CAPTURES.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for AudioCaptureGuard {
+    fn drop(&mut self) {
+        drop(self.release_tx.take());
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                warn!("Audio capture thread panicked while stopping");
+            }
+        }
+    }
+}
+
+/// Format of a raw PCM file, stored next to it as `<name>.pcm.json`
+///
+/// Raw s16le has no header, so the writer records the format it actually
+/// received and muxing reads it back instead of assuming one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PcmMeta {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl PcmMeta {
+    /// Sidecar path for `pcm_path`
+    pub fn path_for(pcm_path: &Path) -> PathBuf {
+        let mut name = pcm_path.file_name().unwrap_or_default().to_os_string();
+        name.push(".json");
+        pcm_path.with_file_name(name)
+    }
+
+    /// Write the sidecar for `pcm_path`
+    pub fn write(&self, pcm_path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_vec(self).map_err(std::io::Error::other)?;
+        std::fs::write(Self::path_for(pcm_path), json)
+    }
+
+    /// Read the sidecar for `pcm_path`
+    pub fn read(pcm_path: &Path) -> std::io::Result<Self> {
+        let json = std::fs::read(Self::path_for(pcm_path))?;
+        serde_json::from_slice(&json)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Delete a PCM file and its `PcmMeta` sidecar
+pub fn remove_pcm_file(pcm_path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(PcmMeta::path_for(pcm_path)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            warn!("Failed to remove PCM metadata for {}: {}", pcm_path.display(), e);
+        }
+        _ => {}
+    }
+    std::fs::remove_file(pcm_path)
+}
+
 /// PCM File Writer
 ///
 /// Writes audio samples to a PCM file in s16le format (signed 16-bit little-endian).
-/// This format is compatible with FFmpeg's audio muxing.
+/// This format is compatible with FFmpeg's audio muxing. The format of the first
+/// sample is written to a `PcmMeta` sidecar as soon as it arrives, so it is there
+/// even if the writer never finalizes.
 pub struct PcmFileWriter {
     file: std::fs::File,
+    path: PathBuf,
     samples_written: usize,
+    meta: Option<PcmMeta>,
+    format_change_logged: bool,
 }
 
 impl PcmFileWriter {
@@ -473,7 +667,10 @@ impl PcmFileWriter {
         info!("Created PCM file writer: {}", path.display());
         Ok(Self {
             file,
+            path: path.to_path_buf(),
             samples_written: 0,
+            meta: None,
+            format_change_logged: false,
         })
     }
 
@@ -491,6 +688,25 @@ impl PcmFileWriter {
     pub fn write_sample(&mut self, sample: &AudioSample) -> std::io::Result<()> {
         use std::io::Write;
 
+        let format = PcmMeta {
+            sample_rate: sample.sample_rate,
+            channels: sample.channels,
+        };
+        match self.meta {
+            None => {
+                format.write(&self.path)?;
+                self.meta = Some(format);
+            }
+            Some(meta) if meta != format && !self.format_change_logged => {
+                warn!(
+                    "PCM format changed mid-recording ({:?} -> {:?}), keeping {:?}",
+                    meta, format, meta
+                );
+                self.format_change_logged = true;
+            }
+            Some(_) => {}
+        }
+
         // Convert f32 samples to s16le (signed 16-bit little-endian)
         let mut buffer = Vec::with_capacity(sample.data.len() * 2); // 2 bytes per sample
 
@@ -538,6 +754,90 @@ impl PcmFileWriter {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pcm_writer_records_format_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let pcm_path = dir.path().join("recording-microphone.pcm");
+
+        let mut writer = PcmFileWriter::new(&pcm_path).unwrap();
+        let stereo = |timestamp_ns| AudioSample {
+            data: vec![0.5, -0.5, 0.25, -0.25],
+            sample_rate: 44_100,
+            channels: 2,
+            timestamp_ns,
+        };
+        writer.write_sample(&stereo(0)).unwrap();
+
+        // Readable before finalize, e.g. when the writer task times out
+        let expected = PcmMeta { sample_rate: 44_100, channels: 2 };
+        assert_eq!(PcmMeta::read(&pcm_path).unwrap(), expected);
+        assert_eq!(PcmMeta::path_for(&pcm_path), dir.path().join("recording-microphone.pcm.json"));
+
+        writer.write_sample(&stereo(1)).unwrap();
+        writer.finalize().unwrap();
+        assert_eq!(std::fs::metadata(&pcm_path).unwrap().len(), 16);
+        assert_eq!(PcmMeta::read(&pcm_path).unwrap(), expected);
+
+        remove_pcm_file(&pcm_path).unwrap();
+        assert!(!pcm_path.exists());
+        assert!(!PcmMeta::path_for(&pcm_path).exists());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_slow_capture_setup_does_not_block_the_runtime() {
+        let ticks = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let ticker = {
+            let ticks = Arc::clone(&ticks);
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                    ticks.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        };
+
+        // Opening a device can take a while; fails so no capture stays live for other tests
+        let result = AudioCaptureGuard::spawn(|| {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            Err::<((), ()), _>("device busy".to_string())
+        })
+        .await;
+
+        assert_eq!(result.err().as_deref(), Some("device busy"));
+        assert!(ticks.load(Ordering::SeqCst) >= 5, "runtime was blocked during setup");
+        ticker.abort();
+    }
+
+    #[tokio::test]
+    async fn test_capture_guards_release_every_capture() {
+        // Stands in for AudioCapture: not Send, and holds the sample sender
+        struct FakeCapture {
+            _not_send: std::rc::Rc<()>,
+            _tx: mpsc::Sender<AudioSample>,
+        }
+
+        for _ in 0..5 {
+            let (tx, mut rx) = mpsc::channel::<AudioSample>(1);
+            let (guard, channels) = AudioCaptureGuard::spawn(move || {
+                Ok((FakeCapture { _not_send: std::rc::Rc::new(()), _tx: tx }, 2u16))
+            })
+            .await
+            .unwrap();
+            assert_eq!(channels, 2);
+            assert!(AudioCaptureGuard::live_count() >= 1);
+
+            // Releasing the guard drops the capture, which closes the channel
+            guard.release().await;
+            assert!(rx.recv().await.is_none());
+        }
+        assert_eq!(AudioCaptureGuard::live_count(), 0);
+
+        // A failed setup keeps nothing alive
+        let failed = AudioCaptureGuard::spawn(|| Err::<((), ()), _>("no device".to_string())).await;
+        assert_eq!(failed.err().as_deref(), Some("no device"));
+        assert_eq!(AudioCaptureGuard::live_count(), 0);
+    }
+
     #[test]
     fn test_audio_capture_new() {
         let capture = AudioCapture::new();
@@ -586,6 +886,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_select_device_by_name() {
+        let mut capture = AudioCapture::new().expect("Failed to create AudioCapture");
+
+        match capture.select_device_by_name("No Such Microphone") {
+            Err(AudioCaptureError::DeviceNotFound { name, available }) => {
+                assert_eq!(name, "No Such Microphone");
+                assert!(!available.iter().any(|d| d == "No Such Microphone"));
+            }
+            Err(AudioCaptureError::NoDevicesFound) => {
+                // Acceptable if the host can't enumerate inputs
+            }
+            other => panic!("Unknown device should not be selected: {:?}", other),
+        }
+
+        // The default device can be selected by its own name
+        let Ok(default_name) = capture.select_default_device() else {
+            // Skip if no microphone available
+            return;
+        };
+        let mut capture = AudioCapture::new().expect("Failed to create AudioCapture");
+        assert_eq!(capture.select_device_by_name(&default_name).unwrap(), default_name);
+        assert!(capture.device.is_some(), "Device should be set");
+    }
+
     #[tokio::test]
     async fn test_start_stop_capture() {
         let mut capture = AudioCapture::new().expect("Failed to create AudioCapture");
@@ -0,0 +1,321 @@
+//! Stereo phase / mono-compatibility analysis
+//!
+//! Summing out-of-phase left and right channels to mono cancels audio (a common
+//! broadcast problem, e.g. a mic wired with one polarity flipped). Before export
+//! the mixed audio is decoded to interleaved stereo f32 via FFmpeg and the
+//! correlation between channels is measured:
+//!
+//! - `+1.0` identical channels (mono-safe)
+//! - `0.0` unrelated channels (wide stereo, still mono-safe)
+//! - `-1.0` inverted channels (cancels to silence in mono)
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::AsyncReadExt;
+
+/// Sample rate used for analysis
+pub const PHASE_ANALYSIS_SAMPLE_RATE: u32 = 48000;
+
+/// Window length for local correlation (100ms at 48kHz)
+const WINDOW_FRAMES: usize = 4800;
+
+/// Windows quieter than this mean-square level are ignored (about -60 dBFS)
+const SILENCE_ENERGY: f64 = 1e-6;
+
+/// Overall correlation below this is reported as not mono-compatible
+const MIN_MONO_SAFE_CORRELATION: f64 = 0.0;
+
+/// Share of out-of-phase windows above which a warning is raised
+const MAX_OUT_OF_PHASE_RATIO: f64 = 0.1;
+
+/// Floor for reported mono-sum loss
+const MIN_MONO_LOSS_DB: f64 = -96.0;
+
+/// Result of a phase analysis
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseReport {
+    /// Pearson-style correlation between L and R over the whole file (-1.0 to 1.0)
+    pub correlation: f64,
+
+    /// Fraction of non-silent 100ms windows with negative correlation (0.0 to 1.0)
+    pub out_of_phase_ratio: f64,
+
+    /// Level change when summing to mono, in dB (0 = no loss, very negative = cancellation)
+    pub mono_loss_db: f64,
+
+    /// Whether the audio survives a mono fold-down
+    pub mono_compatible: bool,
+
+    /// User-facing warning when not mono-compatible
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+/// Running correlation sums over a block of stereo frames
+#[derive(Debug, Default, Clone, Copy)]
+struct CorrelationSums {
+    lr: f64,
+    ll: f64,
+    rr: f64,
+    frames: usize,
+}
+
+impl CorrelationSums {
+    fn add(&mut self, left: f64, right: f64) {
+        self.lr += left * right;
+        self.ll += left * left;
+        self.rr += right * right;
+        self.frames += 1;
+    }
+
+    fn correlation(&self) -> Option<f64> {
+        let denom = (self.ll * self.rr).sqrt();
+        if denom <= f64::EPSILON {
+            None
+        } else {
+            Some((self.lr / denom).clamp(-1.0, 1.0))
+        }
+    }
+
+    fn mean_energy(&self) -> f64 {
+        if self.frames == 0 {
+            0.0
+        } else {
+            (self.ll + self.rr) / (2.0 * self.frames as f64)
+        }
+    }
+}
+
+/// Streaming phase analyzer over interleaved stereo samples
+#[derive(Debug, Default)]
+pub struct PhaseAnalyzer {
+    total: CorrelationSums,
+    window: CorrelationSums,
+    /// Sum of squared mono samples ((L+R)/2)^2
+    mono_energy: f64,
+    active_windows: usize,
+    out_of_phase_windows: usize,
+    /// Dangling left sample when a chunk ends mid-frame
+    pending_left: Option<f32>,
+}
+
+impl PhaseAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed interleaved L/R samples (chunks may split a frame)
+    pub fn push_interleaved(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            match self.pending_left.take() {
+                None => self.pending_left = Some(sample),
+                Some(left) => self.push_frame(left as f64, sample as f64),
+            }
+        }
+    }
+
+    fn push_frame(&mut self, left: f64, right: f64) {
+        self.total.add(left, right);
+        self.window.add(left, right);
+        let mono = (left + right) / 2.0;
+        self.mono_energy += mono * mono;
+
+        if self.window.frames == WINDOW_FRAMES {
+            self.close_window();
+        }
+    }
+
+    fn close_window(&mut self) {
+        if self.window.mean_energy() > SILENCE_ENERGY {
+            self.active_windows += 1;
+            if self.window.correlation().unwrap_or(0.0) < 0.0 {
+                self.out_of_phase_windows += 1;
+            }
+        }
+        self.window = CorrelationSums::default();
+    }
+
+    /// Produce the report for everything pushed so far
+    pub fn finish(mut self) -> PhaseReport {
+        if self.window.frames > 0 {
+            self.close_window();
+        }
+
+        // Silent or empty audio can't cancel; treat it as fully correlated
+        let correlation = self.total.correlation().unwrap_or(1.0);
+
+        let out_of_phase_ratio = if self.active_windows == 0 {
+            0.0
+        } else {
+            self.out_of_phase_windows as f64 / self.active_windows as f64
+        };
+
+        let stereo_energy = (self.total.ll + self.total.rr) / 2.0;
+        let mono_loss_db = if stereo_energy <= f64::EPSILON {
+            0.0
+        } else if self.mono_energy <= f64::EPSILON {
+            MIN_MONO_LOSS_DB
+        } else {
+            (10.0 * (self.mono_energy / stereo_energy).log10()).max(MIN_MONO_LOSS_DB)
+        };
+
+        let mono_compatible = correlation >= MIN_MONO_SAFE_CORRELATION
+            && out_of_phase_ratio <= MAX_OUT_OF_PHASE_RATIO;
+
+        let warning = if mono_compatible {
+            None
+        } else {
+            Some(format!(
+                "Audio is out of phase (correlation {:.2}, {:.0}% of the program); \
+                 it will lose {:.1} dB or cancel when played in mono",
+                correlation,
+                out_of_phase_ratio * 100.0,
+                -mono_loss_db
+            ))
+        };
+
+        PhaseReport {
+            correlation,
+            out_of_phase_ratio,
+            mono_loss_db,
+            mono_compatible,
+            warning,
+        }
+    }
+}
+
+/// Decode a file's audio as stereo f32 and analyze its phase
+///
+/// Mono sources are upmixed by FFmpeg to identical channels and always pass.
+pub async fn check_audio_phase(path: &Path) -> Result<PhaseReport> {
+    let mut child = tokio::process::Command::new("ffmpeg")
+        .arg("-v")
+        .arg("error")
+        .arg("-i")
+        .arg(path)
+        .arg("-vn")
+        .arg("-ac")
+        .arg("2")
+        .arg("-ar")
+        .arg(PHASE_ANALYSIS_SAMPLE_RATE.to_string())
+        .arg("-f")
+        .arg("f32le")
+        .arg("pipe:1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to execute ffmpeg")?;
+
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("FFmpeg stdout not available"))?;
+
+    // Stream the decode so long programs don't have to fit in memory
+    let mut analyzer = PhaseAnalyzer::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut carry: Vec<u8> = Vec::with_capacity(4);
+    let mut samples: Vec<f32> = Vec::with_capacity(buffer.len() / 4);
+
+    loop {
+        let read = stdout
+            .read(&mut buffer)
+            .await
+            .context("Failed to read decoded audio")?;
+        if read == 0 {
+            break;
+        }
+
+        carry.extend_from_slice(&buffer[..read]);
+        let whole = carry.len() - carry.len() % 4;
+        samples.clear();
+        samples.extend(
+            carry[..whole]
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        );
+        carry.drain(..whole);
+        analyzer.push_interleaved(&samples);
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .context("Failed to wait for ffmpeg")?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "FFmpeg audio decode failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let report = analyzer.finish();
+    tracing::info!(
+        event = "audio_phase_checked",
+        path = %path.display(),
+        correlation = report.correlation,
+        out_of_phase_ratio = report.out_of_phase_ratio,
+        mono_compatible = report.mono_compatible,
+        "Audio phase analysis complete"
+    );
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One second of a 440Hz tone with the right channel scaled by `right_gain`
+    fn stereo_tone(right_gain: f32) -> Vec<f32> {
+        (0..PHASE_ANALYSIS_SAMPLE_RATE as usize)
+            .flat_map(|i| {
+                let t = i as f32 / PHASE_ANALYSIS_SAMPLE_RATE as f32;
+                let left = 0.5 * (2.0 * std::f32::consts::PI * 440.0 * t).sin();
+                [left, left * right_gain]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_in_phase_signal_is_mono_compatible() {
+        let mut analyzer = PhaseAnalyzer::new();
+        analyzer.push_interleaved(&stereo_tone(1.0));
+        let report = analyzer.finish();
+
+        assert!((report.correlation - 1.0).abs() < 1e-6);
+        assert_eq!(report.out_of_phase_ratio, 0.0);
+        assert!(report.mono_loss_db.abs() < 0.01);
+        assert!(report.mono_compatible);
+        assert!(report.warning.is_none());
+    }
+
+    #[test]
+    fn test_anti_phase_signal_is_flagged() {
+        let mut analyzer = PhaseAnalyzer::new();
+        // Odd chunk sizes split frames across pushes
+        for chunk in stereo_tone(-1.0).chunks(999) {
+            analyzer.push_interleaved(chunk);
+        }
+        let report = analyzer.finish();
+
+        assert!((report.correlation + 1.0).abs() < 1e-6);
+        assert_eq!(report.out_of_phase_ratio, 1.0);
+        assert_eq!(report.mono_loss_db, MIN_MONO_LOSS_DB);
+        assert!(!report.mono_compatible);
+        assert!(report.warning.is_some());
+    }
+
+    #[test]
+    fn test_silence_is_mono_compatible() {
+        let mut analyzer = PhaseAnalyzer::new();
+        analyzer.push_interleaved(&vec![0.0; 2 * WINDOW_FRAMES]);
+        let report = analyzer.finish();
+
+        assert_eq!(report.correlation, 1.0);
+        assert!(report.mono_compatible);
+    }
+}
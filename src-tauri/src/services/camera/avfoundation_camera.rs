@@ -17,6 +17,7 @@
 // Suppress warnings from objc crate macros about cfg(cargo-clippy)
 #![allow(unexpected_cfgs)]
 
+use serde::Serialize;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
@@ -43,6 +44,9 @@ pub enum AVCameraError {
 
     #[error("Unsupported platform - AVFoundation only available on macOS")]
     UnsupportedPlatform,
+
+    #[error("Invalid camera setting: {0}")]
+    InvalidSetting(String),
 }
 
 /// Camera information structure
@@ -56,6 +60,76 @@ pub struct CameraInfo {
     pub max_resolution: String,
 }
 
+/// Illumination controls a camera exposes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CameraLightingSupport {
+    /// Device has a controllable torch (`hasTorch`)
+    pub has_torch: bool,
+    /// Device supports automatic low-light boost (`isLowLightBoostSupported`)
+    pub low_light_boost: bool,
+}
+
+/// Lighting changes to apply to a camera
+///
+/// Settings the camera can't honour are left as `None` and explained in
+/// `unsupported`, so callers can show a hint instead of failing.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CameraLightingPlan {
+    /// Torch level to set (0.0 turns the torch off); `None` if there is no torch
+    pub torch_level: Option<f32>,
+    /// Low-light boost state to set; `None` if unchanged or unsupported
+    pub low_light_boost: Option<bool>,
+    /// Requested settings this camera doesn't support
+    pub unsupported: Vec<String>,
+}
+
+/// Decide which lighting changes to apply for a request
+///
+/// `level` is the torch brightness from 0.0 to 1.0 and must be above zero
+/// when `on` (AVFoundation rejects a zero level); it is ignored when turning
+/// the torch off.
+///
+/// # Returns
+/// * `Ok(CameraLightingPlan)` - Changes to apply, with unsupported requests listed
+/// * `Err(AVCameraError::InvalidSetting)` - `level` is out of range
+pub fn plan_camera_lighting(
+    support: CameraLightingSupport,
+    on: bool,
+    level: f32,
+    low_light_boost: Option<bool>,
+) -> Result<CameraLightingPlan, AVCameraError> {
+    if on && !(level > 0.0 && level <= 1.0) {
+        return Err(AVCameraError::InvalidSetting(format!(
+            "Torch level must be greater than 0.0 and at most 1.0 (got {})",
+            level
+        )));
+    }
+
+    let mut unsupported = Vec::new();
+
+    let torch_level = if support.has_torch {
+        Some(if on { level } else { 0.0 })
+    } else {
+        unsupported.push("This camera has no torch".to_string());
+        None
+    };
+
+    let low_light_boost = match low_light_boost {
+        Some(_) if !support.low_light_boost => {
+            unsupported.push("This camera does not support low-light boost".to_string());
+            None
+        }
+        requested => requested,
+    };
+
+    Ok(CameraLightingPlan {
+        torch_level,
+        low_light_boost,
+        unsupported,
+    })
+}
+
 #[cfg(target_os = "macos")]
 mod macos {
     use super::*;
@@ -793,6 +867,114 @@ mod macos {
         }
     }
 
+    /// Set the torch and low-light boost of a camera
+    ///
+    /// Cameras without a torch (or without low-light boost, which AVFoundation
+    /// only offers on some devices) are left untouched and the skipped settings
+    /// are logged and reported in the returned plan.
+    pub fn set_camera_lighting(
+        camera_index: u32,
+        on: bool,
+        level: f32,
+        low_light_boost: Option<bool>,
+    ) -> Result<CameraLightingPlan, AVCameraError> {
+        let device_id = AVCameraCapture::get_device_id(camera_index)?;
+
+        unsafe {
+            let av_capture_device_class = Class::get("AVCaptureDevice").ok_or_else(|| {
+                AVCameraError::InitFailed("AVCaptureDevice class not found".to_string())
+            })?;
+            let ns_string_class = Class::get("NSString").ok_or_else(|| {
+                AVCameraError::InitFailed("NSString class not found".to_string())
+            })?;
+            let device_id_c = std::ffi::CString::new(device_id.as_str()).unwrap();
+            let device_id_nsstring: *mut Object = msg_send![
+                ns_string_class,
+                stringWithUTF8String: device_id_c.as_ptr()
+            ];
+            let device: *mut Object = msg_send![
+                av_capture_device_class,
+                deviceWithUniqueID: device_id_nsstring
+            ];
+
+            if device.is_null() {
+                return Err(AVCameraError::CameraNotFound(format!(
+                    "Could not find camera with ID: {}",
+                    device_id
+                )));
+            }
+
+            // Low-light boost selectors don't exist on every OS version, so
+            // check before sending them
+            let has_torch: BOOL = msg_send![device, hasTorch];
+            let boost_selector_available: BOOL =
+                msg_send![device, respondsToSelector: sel!(isLowLightBoostSupported)];
+            let boost_supported: BOOL = if boost_selector_available == YES {
+                msg_send![device, isLowLightBoostSupported]
+            } else {
+                NO
+            };
+
+            let support = CameraLightingSupport {
+                has_torch: has_torch == YES,
+                low_light_boost: boost_supported == YES,
+            };
+            let plan = plan_camera_lighting(support, on, level, low_light_boost)?;
+
+            for reason in &plan.unsupported {
+                warn!("Camera {}: {}, ignoring", camera_index, reason);
+            }
+            if plan.torch_level.is_none() && plan.low_light_boost.is_none() {
+                return Ok(plan);
+            }
+
+            let lock_error: *mut Object = ptr::null_mut();
+            let lock_result: BOOL = msg_send![device, lockForConfiguration: &lock_error];
+            if lock_result != YES {
+                return Err(AVCameraError::OperationFailed(
+                    "Failed to lock camera for configuration".to_string(),
+                ));
+            }
+
+            if let Some(torch_level) = plan.torch_level {
+                if torch_level > 0.0 {
+                    let mut torch_error: *mut Object = ptr::null_mut();
+                    let torch_result: BOOL = msg_send![
+                        device,
+                        setTorchModeOnWithLevel: torch_level
+                        error: &mut torch_error
+                    ];
+                    if torch_result != YES {
+                        let _: () = msg_send![device, unlockForConfiguration];
+                        return Err(AVCameraError::OperationFailed(format!(
+                            "Failed to set torch level {}",
+                            torch_level
+                        )));
+                    }
+                } else {
+                    // AVCaptureTorchModeOff
+                    let _: () = msg_send![device, setTorchMode: 0isize];
+                }
+            }
+
+            if let Some(enable) = plan.low_light_boost {
+                let enable: BOOL = if enable { YES } else { NO };
+                let _: () = msg_send![
+                    device,
+                    setAutomaticallyEnablesLowLightBoostWhenAvailable: enable
+                ];
+            }
+
+            let _: () = msg_send![device, unlockForConfiguration];
+
+            info!(
+                "Camera {} lighting set: torch {:?}, low-light boost {:?}",
+                camera_index, plan.torch_level, plan.low_light_boost
+            );
+            Ok(plan)
+        }
+    }
+
     // dlsym helper
     const RTLD_DEFAULT: *mut c_void = -2isize as *mut c_void;
     extern "C" {
@@ -837,3 +1019,56 @@ impl AVCameraCapture {
 pub fn list_cameras() -> Result<Vec<CameraInfo>, AVCameraError> {
     Err(AVCameraError::UnsupportedPlatform)
 }
+
+#[cfg(not(target_os = "macos"))]
+pub fn set_camera_lighting(
+    _: u32,
+    _: bool,
+    _: f32,
+    _: Option<bool>,
+) -> Result<CameraLightingPlan, AVCameraError> {
+    Err(AVCameraError::UnsupportedPlatform)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_without_torch_reports_unsupported() {
+        let plan = plan_camera_lighting(CameraLightingSupport::default(), true, 0.5, Some(true))
+            .unwrap();
+
+        assert_eq!(plan.torch_level, None);
+        assert_eq!(plan.low_light_boost, None);
+        assert_eq!(
+            plan.unsupported,
+            [
+                "This camera has no torch",
+                "This camera does not support low-light boost"
+            ]
+        );
+
+        // Supported devices get the requested settings and no warnings
+        let support = CameraLightingSupport { has_torch: true, low_light_boost: true };
+        let plan = plan_camera_lighting(support, false, 0.5, Some(false)).unwrap();
+        assert_eq!(plan.torch_level, Some(0.0));
+        assert_eq!(plan.low_light_boost, Some(false));
+        assert!(plan.unsupported.is_empty());
+    }
+
+    #[test]
+    fn test_torch_level_must_be_in_range_when_on() {
+        let support = CameraLightingSupport { has_torch: true, low_light_boost: false };
+
+        for level in [0.0, -0.1, 1.5, f32::NAN] {
+            assert!(matches!(
+                plan_camera_lighting(support, true, level, None),
+                Err(AVCameraError::InvalidSetting(_))
+            ));
+        }
+        assert_eq!(plan_camera_lighting(support, true, 1.0, None).unwrap().torch_level, Some(1.0));
+        // Level is ignored when turning the torch off
+        assert!(plan_camera_lighting(support, false, 7.0, None).is_ok());
+    }
+}
@@ -17,7 +17,11 @@ pub enum CameraBackend {
 
 impl CameraBackend {
     /// Create a new camera backend for the specified camera
+    ///
+    /// Fails with `CameraError::NoCamerasAvailable` when no camera is connected.
     pub async fn new(camera_index: u32, width: u32, height: u32) -> Result<Self> {
+        crate::services::camera::CameraService::new().ensure_camera_available()?;
+
         #[cfg(target_os = "macos")]
         {
             // Try AVFoundation first on macOS
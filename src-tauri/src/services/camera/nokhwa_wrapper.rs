@@ -41,6 +41,9 @@ pub enum CameraError {
     #[error("Camera not found: {0}")]
     CameraNotFound(String),
 
+    #[error("No camera connected")]
+    NoCamerasAvailable,
+
     #[error("Camera access denied. Enable in System Preferences → Privacy & Security → Camera")]
     AccessDenied,
 
@@ -100,38 +103,26 @@ impl CameraService {
             CameraError::EnumerationFailed(e.to_string())
         })?;
 
-        if cameras.is_empty() {
+        let camera_infos = camera_infos_from(&cameras);
+        if camera_infos.is_empty() {
             warn!("No cameras found");
-            return Ok(Vec::new());
+        } else {
+            info!("Found {} cameras", camera_infos.len());
         }
-
-        let camera_infos: Vec<CameraInfo> = cameras
-            .iter()
-            .enumerate()
-            .map(|(idx, cam_info)| {
-                let name = cam_info.human_name().to_string();
-
-                // Get the best resolution for this camera
-                // nokhwa provides a list of compatible formats
-                // We'll use 1080p as default or the highest available
-                let resolution = "1920x1080".to_string(); // Default to 1080p
-                let fps = 30; // Target 30 FPS
-
-                debug!("Found camera {}: {} ({}@{}fps)", idx, name, resolution, fps);
-
-                CameraInfo {
-                    id: idx as u32,
-                    name,
-                    resolution,
-                    fps,
-                }
-            })
-            .collect();
-
-        info!("Found {} cameras", camera_infos.len());
         Ok(camera_infos)
     }
 
+    /// Fail with `CameraError::NoCamerasAvailable` if no camera is connected
+    ///
+    /// Enumeration failures are not treated as "no camera": opening the camera
+    /// reports the real error in that case.
+    pub fn ensure_camera_available(&self) -> Result<(), CameraError> {
+        match self.list_cameras() {
+            Ok(cameras) if cameras.is_empty() => Err(CameraError::NoCamerasAvailable),
+            _ => Ok(()),
+        }
+    }
+
     /// Open a camera by index
     ///
     /// Opens the camera and prepares it for capture.
@@ -242,6 +233,32 @@ impl CameraService {
     }
 }
 
+/// Camera list for enumerated nokhwa devices (empty when none are connected)
+fn camera_infos_from(cameras: &[nokhwa::utils::CameraInfo]) -> Vec<CameraInfo> {
+    cameras
+        .iter()
+        .enumerate()
+        .map(|(idx, cam_info)| {
+            let name = cam_info.human_name().to_string();
+
+            // Get the best resolution for this camera
+            // nokhwa provides a list of compatible formats
+            // We'll use 1080p as default or the highest available
+            let resolution = "1920x1080".to_string(); // Default to 1080p
+            let fps = 30; // Target 30 FPS
+
+            debug!("Found camera {}: {} ({}@{}fps)", idx, name, resolution, fps);
+
+            CameraInfo {
+                id: idx as u32,
+                name,
+                resolution,
+                fps,
+            }
+        })
+        .collect()
+}
+
 impl Default for CameraService {
     fn default() -> Self {
         Self::new()
@@ -574,6 +591,12 @@ mod tests {
         assert!(result.is_ok() || matches!(result, Err(CameraError::EnumerationFailed(_))));
     }
 
+    #[test]
+    fn test_zero_cameras_enumerate_to_empty_list() {
+        assert!(camera_infos_from(&[]).is_empty());
+        assert_eq!(CameraError::NoCamerasAvailable.to_string(), "No camera connected");
+    }
+
     #[test]
     fn test_camera_info_serialization() {
         let camera_info = CameraInfo {
@@ -0,0 +1,299 @@
+//! Capture Self-Test
+//!
+//! Exercises the capture stack end-to-end to diagnose setup problems
+//! (permissions, missing devices, broken drivers):
+//!
+//! 1. Enumerate displays (ScreenCaptureKit)
+//! 2. Capture one screen frame (ScreenCapture)
+//! 3. Enumerate cameras (CameraService)
+//! 4. Capture one camera frame (CameraBackend)
+//! 5. Record one second of microphone audio (AudioCapture)
+//!
+//! Each stage is isolated: a failure is recorded in the report and the
+//! remaining stages still run.
+
+use crate::services::audio_capture::{AudioCapture, AudioSample};
+use crate::services::camera::{CameraBackend, CameraService};
+use crate::services::ffmpeg::TimestampedFrame;
+use crate::services::screen_capture::ScreenCapture;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// How long to wait for the first frame from a capture source
+const FIRST_FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Duration of the microphone capture stage
+const AUDIO_CAPTURE_DURATION: Duration = Duration::from_secs(1);
+
+/// Result of a single self-test stage
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestStage {
+    /// Stage name (e.g. "enumerate_displays")
+    pub name: String,
+
+    /// Whether the stage succeeded
+    pub passed: bool,
+
+    /// Time taken by the stage in milliseconds
+    pub duration_ms: u64,
+
+    /// Success detail or failure reason
+    pub detail: String,
+}
+
+/// Aggregated self-test report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestReport {
+    /// Individual stage results in execution order
+    pub stages: Vec<SelfTestStage>,
+
+    /// True only if every stage passed
+    pub passed: bool,
+
+    /// Number of passed stages
+    pub passed_count: usize,
+
+    /// Number of failed stages
+    pub failed_count: usize,
+
+    /// Sum of all stage durations in milliseconds
+    pub total_duration_ms: u64,
+}
+
+impl SelfTestReport {
+    /// Aggregate stage results into a report
+    pub fn from_stages(stages: Vec<SelfTestStage>) -> Self {
+        let passed_count = stages.iter().filter(|s| s.passed).count();
+        let failed_count = stages.len() - passed_count;
+        let total_duration_ms = stages.iter().map(|s| s.duration_ms).sum();
+
+        Self {
+            passed: failed_count == 0 && !stages.is_empty(),
+            passed_count,
+            failed_count,
+            total_duration_ms,
+            stages,
+        }
+    }
+}
+
+/// Run one stage, timing it and converting its outcome into a `SelfTestStage`
+pub async fn run_stage<F, Fut>(name: &str, stage: F) -> SelfTestStage
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<String, String>>,
+{
+    let start = Instant::now();
+    let result = stage().await;
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(detail) => {
+            info!("Self-test stage '{}' passed in {}ms: {}", name, duration_ms, detail);
+            SelfTestStage {
+                name: name.to_string(),
+                passed: true,
+                duration_ms,
+                detail,
+            }
+        }
+        Err(detail) => {
+            warn!("Self-test stage '{}' failed in {}ms: {}", name, duration_ms, detail);
+            SelfTestStage {
+                name: name.to_string(),
+                passed: false,
+                duration_ms,
+                detail,
+            }
+        }
+    }
+}
+
+/// Run the full capture self-test
+pub async fn run_capture_selftest() -> SelfTestReport {
+    info!("Running capture self-test");
+
+    let stages = vec![
+        run_stage("enumerate_displays", enumerate_displays).await,
+        run_stage("capture_screen_frame", capture_screen_frame).await,
+        run_stage("enumerate_cameras", enumerate_cameras).await,
+        run_stage("capture_camera_frame", capture_camera_frame).await,
+        run_stage("record_audio", record_audio).await,
+    ];
+
+    let report = SelfTestReport::from_stages(stages);
+    info!(
+        "Capture self-test complete: {} passed, {} failed ({}ms)",
+        report.passed_count, report.failed_count, report.total_duration_ms
+    );
+    report
+}
+
+async fn enumerate_displays() -> Result<String, String> {
+    #[cfg(target_os = "macos")]
+    {
+        use screencapturekit::shareable_content::SCShareableContent;
+
+        let content = SCShareableContent::get()
+            .map_err(|e| format!("Failed to get shareable content: {:?}", e))?;
+        let count = content.displays().len();
+        if count == 0 {
+            return Err("No displays found".to_string());
+        }
+        Ok(format!("{} display(s) found", count))
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        Err("Display enumeration is only supported on macOS".to_string())
+    }
+}
+
+async fn capture_screen_frame() -> Result<String, String> {
+    let mut capture = ScreenCapture::new(None, None)
+        .map_err(|e| format!("Screen capture initialization failed: {}", e))?;
+
+    let (frame_tx, mut frame_rx) = mpsc::channel::<TimestampedFrame>(2);
+    let handle = capture
+        .start_continuous_capture(frame_tx, None, None)
+        .map_err(|e| format!("Failed to start screen capture: {}", e))?;
+
+    let frame = tokio::time::timeout(FIRST_FRAME_TIMEOUT, frame_rx.recv()).await;
+
+    capture.stop_capture();
+    drop(frame_rx);
+    let _ = handle.await;
+
+    match frame {
+        Ok(Some(frame)) => Ok(format!("Captured {}x{} frame", frame.width, frame.height)),
+        Ok(None) => Err("Screen capture ended before delivering a frame".to_string()),
+        Err(_) => Err(format!(
+            "No screen frame within {}s",
+            FIRST_FRAME_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+async fn enumerate_cameras() -> Result<String, String> {
+    let cameras = CameraService::new()
+        .list_cameras()
+        .map_err(|e| format!("Failed to enumerate cameras: {}", e))?;
+
+    if cameras.is_empty() {
+        return Err("No cameras found".to_string());
+    }
+    Ok(format!("{} camera(s) found", cameras.len()))
+}
+
+async fn capture_camera_frame() -> Result<String, String> {
+    let mut camera = CameraBackend::new(0, 1280, 720)
+        .await
+        .map_err(|e| format!("Camera initialization failed: {}", e))?;
+
+    let (frame_tx, mut frame_rx) = mpsc::channel::<TimestampedFrame>(2);
+    let handle = camera
+        .start_continuous_capture(frame_tx)
+        .map_err(|e| format!("Failed to start camera capture: {}", e))?;
+
+    let frame = tokio::time::timeout(FIRST_FRAME_TIMEOUT, frame_rx.recv()).await;
+
+    camera.stop_capture();
+    drop(frame_rx);
+    handle.abort();
+
+    match frame {
+        Ok(Some(frame)) => Ok(format!("Captured {}x{} frame", frame.width, frame.height)),
+        Ok(None) => Err("Camera capture ended before delivering a frame".to_string()),
+        Err(_) => Err(format!(
+            "No camera frame within {}s",
+            FIRST_FRAME_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+async fn record_audio() -> Result<String, String> {
+    // AudioCapture holds a non-Send cpal stream, so run it on a blocking thread
+    tokio::task::spawn_blocking(|| {
+        let mut audio_capture = AudioCapture::new()
+            .map_err(|e| format!("Audio initialization failed: {}", e))?;
+        let device_name = audio_capture
+            .select_default_device()
+            .map_err(|e| format!("No microphone available: {}", e))?;
+
+        let (audio_tx, mut audio_rx) = mpsc::channel::<AudioSample>(1000);
+        audio_capture
+            .start_capture(audio_tx)
+            .map_err(|e| format!("Failed to start audio capture: {}", e))?;
+
+        std::thread::sleep(AUDIO_CAPTURE_DURATION);
+        audio_capture.stop_capture();
+
+        let mut sample_count = 0usize;
+        while let Ok(sample) = audio_rx.try_recv() {
+            sample_count += sample.data.len();
+        }
+
+        if sample_count == 0 {
+            return Err(format!("No audio received from '{}'", device_name));
+        }
+        Ok(format!("Recorded {} samples from '{}'", sample_count, device_name))
+    })
+    .await
+    .map_err(|e| format!("Audio stage panicked: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(name: &str, passed: bool, duration_ms: u64) -> SelfTestStage {
+        SelfTestStage {
+            name: name.to_string(),
+            passed,
+            duration_ms,
+            detail: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_report_aggregates_mixed_results() {
+        let report = SelfTestReport::from_stages(vec![
+            stage("enumerate_displays", true, 10),
+            stage("capture_screen_frame", false, 5000),
+            stage("enumerate_cameras", true, 20),
+        ]);
+
+        assert!(!report.passed);
+        assert_eq!(report.passed_count, 2);
+        assert_eq!(report.failed_count, 1);
+        assert_eq!(report.total_duration_ms, 5030);
+        assert_eq!(report.stages.len(), 3);
+    }
+
+    #[test]
+    fn test_report_all_passed() {
+        let report = SelfTestReport::from_stages(vec![stage("a", true, 1), stage("b", true, 2)]);
+        assert!(report.passed);
+        assert_eq!(report.failed_count, 0);
+
+        // An empty report is not a pass
+        assert!(!SelfTestReport::from_stages(vec![]).passed);
+    }
+
+    #[tokio::test]
+    async fn test_failed_stage_does_not_abort_later_stages() {
+        let stages = vec![
+            run_stage("fails", || async { Err::<String, String>("boom".to_string()) }).await,
+            run_stage("passes", || async { Ok::<String, String>("fine".to_string()) }).await,
+        ];
+
+        assert!(!stages[0].passed);
+        assert_eq!(stages[0].detail, "boom");
+        assert!(stages[1].passed);
+    }
+}
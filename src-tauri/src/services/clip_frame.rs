@@ -0,0 +1,142 @@
+//! Per-clip inspector thumbnails
+//!
+//! Renders one frame at the clip's in-point through the same per-clip
+//! adjustments the timeline renderer applies on export
+//! (`timeline_renderer::clip_adjustment_filters`), so the inspector preview
+//! matches the final look. Frames are cached on disk (`frame_cache`) as JPEGs, keyed by a hash
+//! of everything that affects the picture (source, in-point, adjustments).
+
+use crate::models::timeline::Clip;
+use crate::services::frame_cache::{frame_data_url, render_cached_frame};
+use crate::services::timeline_renderer::clip_adjustment_filters;
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Thumbnail width in pixels (height follows the aspect ratio)
+pub const CLIP_FRAME_WIDTH: u32 = 320;
+
+/// Cache key for a clip's thumbnail
+///
+/// Timeline position and clip ID are excluded: moving or duplicating a clip
+/// doesn't change how it looks.
+pub fn clip_frame_cache_key(clip: &Clip) -> String {
+    let mut hasher = DefaultHasher::new();
+    clip.file_path.hash(&mut hasher);
+    clip.trim_in.hash(&mut hasher);
+    CLIP_FRAME_WIDTH.hash(&mut hasher);
+    for filter in clip_adjustment_filters(clip) {
+        filter.hash(&mut hasher);
+    }
+
+    // Replaced source files invalidate the cache
+    if let Ok(modified) = std::fs::metadata(&clip.file_path).and_then(|m| m.modified()) {
+        modified.hash(&mut hasher);
+    }
+
+    format!("{:016x}", hasher.finish())
+}
+
+/// Cached thumbnail path for a clip
+pub fn clip_frame_path(cache_dir: &Path, clip: &Clip) -> PathBuf {
+    cache_dir.join(format!("clip-{}.jpg", clip_frame_cache_key(clip)))
+}
+
+/// FFmpeg arguments to render a clip's in-point frame to `output_path`
+pub fn build_clip_frame_args(clip: &Clip, output_path: &Path) -> Vec<String> {
+    let mut filters = clip_adjustment_filters(clip);
+    filters.push(format!("scale={}:-2", CLIP_FRAME_WIDTH));
+
+    vec![
+        "-v".to_string(), "error".to_string(),
+        "-ss".to_string(), format!("{:.3}", clip.trim_in as f64 / 1000.0),
+        "-i".to_string(), clip.file_path.clone(),
+        "-frames:v".to_string(), "1".to_string(),
+        "-vf".to_string(), filters.join(","),
+        "-q:v".to_string(), "4".to_string(),
+        "-y".to_string(),
+        output_path.to_string_lossy().to_string(),
+    ]
+}
+
+/// Render (or load from cache) a clip's thumbnail
+///
+/// # Returns
+/// * `Ok(String)` - Base64 data URL of the JPEG thumbnail
+/// * `Err(anyhow::Error)` - FFmpeg could not decode the frame
+pub async fn render_clip_frame(clip: &Clip, cache_dir: &Path) -> Result<String> {
+    let frame_path = clip_frame_path(cache_dir, clip);
+
+    if render_cached_frame(&frame_path, |output_path| build_clip_frame_args(clip, output_path)).await? {
+        tracing::debug!(
+            event = "clip_frame_rendered",
+            clip_id = %clip.id,
+            path = %frame_path.display(),
+            "Rendered clip frame"
+        );
+    }
+
+    frame_data_url(&frame_path, "image/jpeg")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::timeline::ClipTransform;
+
+    fn clip(rotation: Option<f64>) -> Clip {
+        Clip {
+            id: "clip-1".to_string(),
+            file_path: "/media/does-not-exist.mp4".to_string(),
+            start_time: 0,
+            duration: 10_000,
+            trim_in: 2_500,
+            trim_out: 10_000,
+            fade_in: None,
+            fade_out: None,
+            volume: None,
+            muted: None,
+            audio_tracks: None,
+            transform: rotation.map(|rotation| ClipTransform {
+                x: 0.0,
+                y: 0.0,
+                width: 1920.0,
+                height: 1080.0,
+                opacity: 1.0,
+                rotation,
+            }),
+            linked_clip_id: None,
+            group_id: None,
+            transition_out: None,
+        }
+    }
+
+    #[test]
+    fn test_rotated_clip_has_distinct_cache_key() {
+        let cache_dir = Path::new("/tmp/clippy-thumbnails");
+        let plain = clip(None);
+        let rotated = clip(Some(90.0));
+
+        assert_ne!(clip_frame_cache_key(&plain), clip_frame_cache_key(&rotated));
+        assert_ne!(clip_frame_path(cache_dir, &plain), clip_frame_path(cache_dir, &rotated));
+
+        // Position and identity don't affect the picture
+        let mut moved = plain.clone();
+        moved.id = "clip-2".to_string();
+        moved.start_time = 30_000;
+        assert_eq!(clip_frame_cache_key(&plain), clip_frame_cache_key(&moved));
+
+        // A zero rotation looks the same as no transform
+        assert_eq!(clip_frame_cache_key(&plain), clip_frame_cache_key(&clip(Some(0.0))));
+    }
+
+    #[test]
+    fn test_clip_frame_args_seek_to_in_point_and_rotate() {
+        let args = build_clip_frame_args(&clip(Some(90.0)), Path::new("/tmp/out.jpg"));
+
+        assert!(args.windows(2).any(|w| w == ["-ss", "2.500"]));
+        assert!(args.windows(2).any(|w| w == ["-vf", "transpose=clock,scale=320:-2"]));
+        assert_eq!(args.last().unwrap(), "/tmp/out.jpg");
+    }
+}
@@ -269,6 +269,9 @@ mod tests {
             muted: None,
             audio_tracks: None,
             transform: None,
+            linked_clip_id: None,
+            group_id: None,
+            transition_out: None,
         }
     }
 
@@ -0,0 +1,196 @@
+//! Still-frame contact sheets
+//!
+//! A contact sheet is a single JPEG of evenly spaced frames laid out in a grid,
+//! for previewing long recordings at a glance. FFmpeg does all the work in one
+//! pass: `select` keeps one frame per interval, `scale` shrinks it to a tile
+//! and `tile` assembles the grid. Sheets are cached on disk, keyed by source
+//! file and layout.
+
+use crate::services::ffmpeg::repair::probe_recording;
+use anyhow::{Context, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Width of each tile in pixels (height follows the aspect ratio)
+pub const CONTACT_SHEET_TILE_WIDTH: u32 = 320;
+
+/// Shortest gap between tiles; shorter videos get a smaller grid
+pub const MIN_TILE_INTERVAL_MS: u64 = 1_000;
+
+/// Grid and spacing of a contact sheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContactSheetLayout {
+    pub rows: u32,
+    pub cols: u32,
+    /// Pixels between tiles and around the edge
+    pub spacing: u32,
+}
+
+impl ContactSheetLayout {
+    /// Shrink the grid so tiles are at least `MIN_TILE_INTERVAL_MS` apart
+    ///
+    /// Rows are dropped first so the sheet keeps its width; a video too short
+    /// for a single row gets one row of as many tiles as fit.
+    pub fn fit_to_duration(self, duration_ms: u64) -> Self {
+        let max_tiles = (duration_ms / MIN_TILE_INTERVAL_MS).max(1);
+        let (rows, cols) = (self.rows.max(1) as u64, self.cols.max(1) as u64);
+        if rows * cols <= max_tiles {
+            return Self { rows: rows as u32, cols: cols as u32, ..self };
+        }
+
+        let (rows, cols) = if max_tiles >= cols {
+            (max_tiles / cols, cols)
+        } else {
+            (1, max_tiles)
+        };
+        Self { rows: rows as u32, cols: cols as u32, ..self }
+    }
+
+    /// Number of tiles in the grid
+    pub fn tiles(&self) -> u32 {
+        self.rows * self.cols
+    }
+}
+
+/// Gap between sampled frames so `tiles` frames span `duration_ms` evenly
+pub fn tile_interval_ms(duration_ms: u64, tiles: u32) -> u64 {
+    (duration_ms / tiles.max(1) as u64).max(1)
+}
+
+/// FFmpeg arguments rendering a contact sheet of `input` to `output_path`
+///
+/// The first frame is taken half an interval in, so frames sit in the middle
+/// of their slice of the video rather than on the (often black) first frame.
+pub fn build_contact_sheet_args(
+    input: &Path,
+    duration_ms: u64,
+    layout: ContactSheetLayout,
+    output_path: &Path,
+) -> Vec<String> {
+    let interval_secs = tile_interval_ms(duration_ms, layout.tiles()) as f64 / 1000.0;
+    let filter = format!(
+        "select='isnan(prev_selected_t)+gte(t-prev_selected_t\\,{:.3})',scale={}:-2,tile={}x{}:padding={}:margin={}",
+        interval_secs,
+        CONTACT_SHEET_TILE_WIDTH,
+        layout.cols,
+        layout.rows,
+        layout.spacing,
+        layout.spacing
+    );
+
+    vec![
+        "-v".to_string(), "error".to_string(),
+        "-ss".to_string(), format!("{:.3}", interval_secs / 2.0),
+        "-i".to_string(), input.to_string_lossy().to_string(),
+        "-vf".to_string(), filter,
+        "-frames:v".to_string(), "1".to_string(),
+        "-q:v".to_string(), "4".to_string(),
+        "-y".to_string(),
+        output_path.to_string_lossy().to_string(),
+    ]
+}
+
+/// Cached contact sheet path for a file and layout
+pub fn contact_sheet_path(cache_dir: &Path, input: &Path, layout: ContactSheetLayout) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    layout.hash(&mut hasher);
+    CONTACT_SHEET_TILE_WIDTH.hash(&mut hasher);
+
+    // Replaced source files invalidate the cache
+    if let Ok(modified) = std::fs::metadata(input).and_then(|m| m.modified()) {
+        modified.hash(&mut hasher);
+    }
+
+    cache_dir.join(format!("contact-{:016x}.jpg", hasher.finish()))
+}
+
+/// Render (or load from cache) a contact sheet
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the JPEG contact sheet
+/// * `Err(anyhow::Error)` - The video could not be probed or decoded
+pub async fn generate_contact_sheet(
+    input: &Path,
+    layout: ContactSheetLayout,
+    cache_dir: &Path,
+) -> Result<PathBuf> {
+    let probe = probe_recording(input).await?;
+    let duration_ms = probe
+        .duration_ms
+        .filter(|_| probe.success && probe.has_video)
+        .ok_or_else(|| anyhow::anyhow!("Could not read video duration of {}", input.display()))?;
+
+    let layout = layout.fit_to_duration(duration_ms);
+    let sheet_path = contact_sheet_path(cache_dir, input, layout);
+    if sheet_path.exists() {
+        return Ok(sheet_path);
+    }
+
+    std::fs::create_dir_all(cache_dir).context("Failed to create thumbnails directory")?;
+
+    let output = tokio::process::Command::new("ffmpeg")
+        .args(build_contact_sheet_args(input, duration_ms, layout, &sheet_path))
+        .output()
+        .await
+        .context("Failed to execute ffmpeg for contact sheet")?;
+
+    if !output.status.success() || !sheet_path.exists() {
+        let _ = std::fs::remove_file(&sheet_path);
+        return Err(anyhow::anyhow!(
+            "FFmpeg contact sheet render failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    tracing::debug!(
+        event = "contact_sheet_rendered",
+        input = %input.display(),
+        rows = layout.rows,
+        cols = layout.cols,
+        path = %sheet_path.display(),
+        "Rendered contact sheet"
+    );
+
+    Ok(sheet_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contact_sheet_args_tile_grid_and_interval() {
+        let layout = ContactSheetLayout { rows: 4, cols: 5, spacing: 8 };
+        let args = build_contact_sheet_args(
+            Path::new("/media/talk.mp4"),
+            600_000,
+            layout,
+            Path::new("/tmp/sheet.jpg"),
+        );
+
+        // 20 tiles over 10 minutes: one frame every 30s, starting 15s in
+        assert!(args.windows(2).any(|w| w == ["-ss", "15.000"]));
+        let filter = &args[args.iter().position(|a| a == "-vf").unwrap() + 1];
+        assert_eq!(
+            filter,
+            "select='isnan(prev_selected_t)+gte(t-prev_selected_t\\,30.000)',scale=320:-2,tile=5x4:padding=8:margin=8"
+        );
+        assert!(args.windows(2).any(|w| w == ["-frames:v", "1"]));
+    }
+
+    #[test]
+    fn test_short_videos_get_a_smaller_grid() {
+        let layout = ContactSheetLayout { rows: 4, cols: 5, spacing: 0 };
+
+        // Long enough: unchanged
+        assert_eq!(layout.fit_to_duration(60_000), layout);
+        // 12s: rows are dropped first (2x5 = 10 tiles)
+        assert_eq!(layout.fit_to_duration(12_000), ContactSheetLayout { rows: 2, cols: 5, spacing: 0 });
+        // 3s: a single row of 3
+        assert_eq!(layout.fit_to_duration(3_000), ContactSheetLayout { rows: 1, cols: 3, spacing: 0 });
+        // Sub-second clips still get one tile
+        assert_eq!(layout.fit_to_duration(400).tiles(), 1);
+    }
+}
@@ -0,0 +1,458 @@
+//! CMX3600 EDL Importer
+//!
+//! This module parses CMX3600 Edit Decision Lists and reconstructs a Clippy
+//! `Timeline` from them, complementing timeline export for NLE interop.
+//!
+//! # Supported Syntax
+//!
+//! ```text
+//! TITLE: My Project
+//! FCM: NON-DROP FRAME
+//!
+//! 001  AX       V     C        00:00:00:00 00:00:05:00 01:00:00:00 01:00:05:00
+//! * FROM CLIP NAME: intro.mp4
+//! 002  AX       AA/V  C        00:00:10:00 00:00:12:15 01:00:05:00 01:00:07:15
+//! * FROM CLIP NAME: broll.mov
+//! ```
+//!
+//! Each event line is `<num> <reel> <track> <transition> <src in> <src out> <rec in> <rec out>`.
+//! Record timecodes are normalized so the earliest record-in lands at 0ms on the timeline.
+//! Only cuts are imported; dissolves/wipes keep their cut point and the transition is ignored.
+//!
+//! Media is resolved from `* FROM CLIP NAME:` / `* SOURCE FILE:` comments, falling back to
+//! the reel name. Relative names are resolved against the EDL's directory. Unresolved media
+//! is logged as a warning and kept on the timeline with its original name so the user can
+//! relink it later.
+
+use crate::models::timeline::{Clip, Timeline, Track, TrackType};
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Default frame rate used for timecode conversion (CMX3600 carries no rate)
+pub const DEFAULT_EDL_FPS: u32 = 30;
+
+/// Track an EDL event applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdlTrack {
+    /// Video (`V`, `B`, `AA/V`, `A/V`) - video clips carry their own audio
+    Video,
+    /// Audio only (`A`, `A2`, `AA`, ...)
+    Audio,
+}
+
+/// A single parsed EDL event
+/// All timestamps are in MILLISECONDS (ADR-005)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdlEvent {
+    /// Event number (e.g. 1 for "001")
+    pub number: u32,
+
+    /// Reel name from the event line
+    pub reel: String,
+
+    /// Track the event lives on
+    pub track: EdlTrack,
+
+    /// Source in point in milliseconds
+    pub source_in: u64,
+
+    /// Source out point in milliseconds
+    pub source_out: u64,
+
+    /// Record in point in milliseconds (raw, not normalized)
+    pub record_in: u64,
+
+    /// Record out point in milliseconds (raw, not normalized)
+    pub record_out: u64,
+
+    /// Clip name from `* FROM CLIP NAME:` or `* SOURCE FILE:` comments
+    pub clip_name: Option<String>,
+}
+
+/// Parsed EDL document
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdlDocument {
+    /// Title from the `TITLE:` header, if present
+    pub title: Option<String>,
+
+    /// Whether timecodes are drop-frame (`FCM: DROP FRAME`)
+    pub drop_frame: bool,
+
+    /// Events in file order
+    pub events: Vec<EdlEvent>,
+}
+
+/// Convert a `HH:MM:SS:FF` (or `HH:MM:SS;FF` drop-frame) timecode to milliseconds
+///
+/// Drop-frame timecodes are interpreted at 29.97 fps using the standard
+/// "skip frames 0 and 1 of every minute except every tenth minute" rule.
+pub fn timecode_to_ms(timecode: &str, fps: u32, drop_frame: bool) -> Result<u64> {
+    let parts: Vec<&str> = timecode.split(|c| c == ':' || c == ';' || c == '.').collect();
+    if parts.len() != 4 {
+        return Err(anyhow!("Invalid timecode: {}", timecode));
+    }
+
+    let mut values = [0u64; 4];
+    for (value, part) in values.iter_mut().zip(parts.iter()) {
+        *value = part
+            .parse()
+            .with_context(|| format!("Invalid timecode component '{}' in {}", part, timecode))?;
+    }
+    let [hours, minutes, seconds, frames] = values;
+
+    if fps == 0 || frames >= fps as u64 || minutes >= 60 || seconds >= 60 {
+        return Err(anyhow!("Timecode out of range: {}", timecode));
+    }
+
+    if drop_frame {
+        // Drop-frame only applies to 30 fps nominal (29.97)
+        let total_minutes = hours * 60 + minutes;
+        let frame_number = (hours * 3600 + minutes * 60 + seconds) * 30 + frames
+            - 2 * (total_minutes - total_minutes / 10);
+        return Ok(frame_number * 1001 / 30);
+    }
+
+    let frame_number = (hours * 3600 + minutes * 60 + seconds) * fps as u64 + frames;
+    Ok(frame_number * 1000 / fps as u64)
+}
+
+/// Parse the contents of a CMX3600 EDL
+///
+/// # Arguments
+///
+/// * `contents` - EDL file contents
+/// * `fps` - Frame rate used to interpret timecodes
+///
+/// # Returns
+///
+/// * `Ok(EdlDocument)` - Parsed events in file order
+/// * `Err(anyhow::Error)` - Malformed event line or timecode
+pub fn parse_edl(contents: &str, fps: u32) -> Result<EdlDocument> {
+    let mut title = None;
+    let mut drop_frame = false;
+    let mut events: Vec<EdlEvent> = Vec::new();
+
+    for (line_num, raw_line) in contents.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("TITLE:") {
+            title = Some(rest.trim().to_string());
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("FCM:") {
+            drop_frame = rest.trim().eq_ignore_ascii_case("DROP FRAME");
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('*') {
+            let comment = comment.trim();
+            let name = comment
+                .strip_prefix("FROM CLIP NAME:")
+                .or_else(|| comment.strip_prefix("SOURCE FILE:"));
+            if let (Some(name), Some(event)) = (name, events.last_mut()) {
+                // SOURCE FILE is more specific than the clip name, so it wins if both are present
+                if event.clip_name.is_none() || comment.starts_with("SOURCE FILE:") {
+                    event.clip_name = Some(name.trim().to_string());
+                }
+            }
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let number = match fields.first().and_then(|f| f.parse::<u32>().ok()) {
+            Some(n) => n,
+            None => {
+                debug!("Skipping unrecognized EDL line {}: {}", line_num + 1, line);
+                continue;
+            }
+        };
+
+        // Dissolves/wipes carry an extra duration field before the timecodes
+        if fields.len() < 8 {
+            return Err(anyhow!(
+                "Malformed EDL event on line {}: {}",
+                line_num + 1,
+                line
+            ));
+        }
+
+        let timecodes = &fields[fields.len() - 4..];
+        let event_drop_frame = drop_frame || timecodes.iter().any(|tc| tc.contains(';'));
+        let to_ms = |tc: &str| {
+            timecode_to_ms(tc, fps, event_drop_frame)
+                .with_context(|| format!("Line {}", line_num + 1))
+        };
+
+        let track = match fields[2].to_ascii_uppercase().as_str() {
+            t if t.contains('V') || t == "B" => EdlTrack::Video,
+            t if t.starts_with('A') => EdlTrack::Audio,
+            other => {
+                return Err(anyhow!(
+                    "Unknown EDL track '{}' on line {}",
+                    other,
+                    line_num + 1
+                ))
+            }
+        };
+
+        events.push(EdlEvent {
+            number,
+            reel: fields[1].to_string(),
+            track,
+            source_in: to_ms(timecodes[0])?,
+            source_out: to_ms(timecodes[1])?,
+            record_in: to_ms(timecodes[2])?,
+            record_out: to_ms(timecodes[3])?,
+            clip_name: None,
+        });
+    }
+
+    Ok(EdlDocument {
+        title,
+        drop_frame,
+        events,
+    })
+}
+
+/// Resolve an event's media reference to a file path
+///
+/// Returns `Ok(path)` if the media exists on disk, `Err(name)` with the best-guess
+/// path otherwise.
+fn resolve_media(event: &EdlEvent, base_dir: &Path) -> std::result::Result<PathBuf, PathBuf> {
+    let name = event.clip_name.as_deref().unwrap_or(&event.reel);
+    let candidate = Path::new(name);
+    let path = if candidate.is_absolute() {
+        candidate.to_path_buf()
+    } else {
+        base_dir.join(candidate)
+    };
+
+    if path.exists() {
+        Ok(path)
+    } else {
+        Err(path)
+    }
+}
+
+/// Build a Timeline from a parsed EDL
+///
+/// Video events go on video Track 1, audio-only events on audio Track 2.
+/// Record timecodes are shifted so the first event starts at 0ms.
+///
+/// # Arguments
+///
+/// * `document` - Parsed EDL
+/// * `base_dir` - Directory used to resolve relative media names
+///
+/// # Returns
+///
+/// The reconstructed timeline and the list of media paths that could not be resolved
+pub fn build_timeline(document: &EdlDocument, base_dir: &Path) -> (Timeline, Vec<String>) {
+    let origin = document
+        .events
+        .iter()
+        .map(|e| e.record_in)
+        .min()
+        .unwrap_or(0);
+
+    let mut video_clips = Vec::new();
+    let mut audio_clips = Vec::new();
+    let mut unresolved = Vec::new();
+    let mut total_duration = 0u64;
+
+    for event in &document.events {
+        if event.source_out <= event.source_in || event.record_out <= event.record_in {
+            warn!(
+                "Skipping zero-length EDL event {:03} ({})",
+                event.number, event.reel
+            );
+            continue;
+        }
+
+        let file_path = match resolve_media(event, base_dir) {
+            Ok(path) => path,
+            Err(path) => {
+                warn!(
+                    "EDL event {:03} references unresolved media: {}",
+                    event.number,
+                    path.display()
+                );
+                let display = path.to_string_lossy().to_string();
+                if !unresolved.contains(&display) {
+                    unresolved.push(display);
+                }
+                path
+            }
+        };
+
+        let start_time = event.record_in - origin;
+        let clip = Clip {
+            id: uuid::Uuid::new_v4().to_string(),
+            file_path: file_path.to_string_lossy().to_string(),
+            start_time,
+            // Source media length is unknown here; source out is the known lower bound
+            duration: event.source_out,
+            trim_in: event.source_in,
+            trim_out: event.source_out,
+            fade_in: None,
+            fade_out: None,
+            volume: None,
+            muted: None,
+            audio_tracks: None,
+            transform: None,
+            linked_clip_id: None,
+            group_id: None,
+            transition_out: None,
+        };
+
+        total_duration = total_duration.max(start_time + (event.source_out - event.source_in));
+
+        match event.track {
+            EdlTrack::Video => video_clips.push(clip),
+            EdlTrack::Audio => audio_clips.push(clip),
+        }
+    }
+
+    video_clips.sort_by_key(|c| c.start_time);
+    audio_clips.sort_by_key(|c| c.start_time);
+
+    let mut tracks = vec![Track {
+        id: uuid::Uuid::new_v4().to_string(),
+        track_number: 1,
+        clips: video_clips,
+        track_type: TrackType::Video,
+    }];
+
+    if !audio_clips.is_empty() {
+        tracks.push(Track {
+            id: uuid::Uuid::new_v4().to_string(),
+            track_number: 2,
+            clips: audio_clips,
+            track_type: TrackType::Audio,
+        });
+    }
+
+    (
+        Timeline {
+            tracks,
+            total_duration,
+        },
+        unresolved,
+    )
+}
+
+/// Read and import an EDL file into a Timeline
+///
+/// # Returns
+///
+/// The reconstructed timeline and the list of unresolved media paths
+pub fn import_edl_file(path: &Path) -> Result<(Timeline, Vec<String>)> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read EDL file: {}", path.display()))?;
+
+    let document = parse_edl(&contents, DEFAULT_EDL_FPS)?;
+    if document.events.is_empty() {
+        return Err(anyhow!("EDL contains no events: {}", path.display()));
+    }
+
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    Ok(build_timeline(&document, base_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_EDL: &str = "TITLE: Interop Test
+FCM: NON-DROP FRAME
+
+001  AX       V     C        00:00:00:00 00:00:05:00 01:00:00:00 01:00:05:00
+* FROM CLIP NAME: intro.mp4
+002  AX       V     C        00:00:10:00 00:00:12:15 01:00:05:00 01:00:07:15
+* FROM CLIP NAME: broll.mov
+003  AX       A     C        00:00:01:00 00:00:03:00 01:00:02:00 01:00:04:00
+* FROM CLIP NAME: music.mp4
+";
+
+    #[test]
+    fn test_timecode_to_ms() {
+        assert_eq!(timecode_to_ms("00:00:01:00", 30, false).unwrap(), 1000);
+        assert_eq!(timecode_to_ms("00:00:02:15", 30, false).unwrap(), 2500);
+        assert_eq!(timecode_to_ms("01:00:00:00", 25, false).unwrap(), 3_600_000);
+        // 00:01:00;02 is frame 1800 in drop-frame (frames 0 and 1 skipped)
+        assert_eq!(timecode_to_ms("00:01:00;02", 30, true).unwrap(), 1800 * 1001 / 30);
+        assert!(timecode_to_ms("00:00:00:30", 30, false).is_err());
+        assert!(timecode_to_ms("garbage", 30, false).is_err());
+    }
+
+    #[test]
+    fn test_parse_edl_events() {
+        let doc = parse_edl(SAMPLE_EDL, 30).unwrap();
+
+        assert_eq!(doc.title.as_deref(), Some("Interop Test"));
+        assert!(!doc.drop_frame);
+        assert_eq!(doc.events.len(), 3);
+
+        let second = &doc.events[1];
+        assert_eq!(second.number, 2);
+        assert_eq!(second.track, EdlTrack::Video);
+        assert_eq!(second.source_in, 10_000);
+        assert_eq!(second.source_out, 12_500);
+        assert_eq!(second.clip_name.as_deref(), Some("broll.mov"));
+        assert_eq!(doc.events[2].track, EdlTrack::Audio);
+    }
+
+    #[test]
+    fn test_build_timeline_positions_and_durations() {
+        let doc = parse_edl(SAMPLE_EDL, 30).unwrap();
+        let (timeline, unresolved) = build_timeline(&doc, Path::new("/nonexistent/edl"));
+
+        // All three media files are missing, so all are reported
+        assert_eq!(unresolved.len(), 3);
+
+        let video: Vec<_> = timeline.video_tracks().collect();
+        assert_eq!(video.len(), 1);
+        let clips = &video[0].clips;
+        assert_eq!(clips.len(), 2);
+
+        // Record in 01:00:00:00 is normalized to 0
+        assert_eq!(clips[0].start_time, 0);
+        assert_eq!(clips[0].trim_in, 0);
+        assert_eq!(clips[0].trim_out, 5000);
+        assert_eq!(clips[0].file_path, "/nonexistent/edl/intro.mp4");
+
+        assert_eq!(clips[1].start_time, 5000);
+        assert_eq!(clips[1].trim_in, 10_000);
+        assert_eq!(clips[1].trim_out, 12_500);
+
+        let audio: Vec<_> = timeline.audio_tracks().collect();
+        assert_eq!(audio.len(), 1);
+        assert_eq!(audio[0].clips[0].start_time, 2000);
+        assert_eq!(audio[0].clips[0].trim_out - audio[0].clips[0].trim_in, 2000);
+
+        assert_eq!(timeline.total_duration, 7500);
+    }
+
+    #[test]
+    fn test_build_timeline_resolves_existing_media() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("intro.mp4"), b"dummy").unwrap();
+
+        let doc = parse_edl(SAMPLE_EDL, 30).unwrap();
+        let (_, unresolved) = build_timeline(&doc, temp_dir.path());
+
+        assert_eq!(unresolved.len(), 2);
+        assert!(!unresolved.iter().any(|p| p.ends_with("intro.mp4")));
+    }
+
+    #[test]
+    fn test_parse_edl_rejects_malformed_event() {
+        let result = parse_edl("001  AX  V  C  00:00:00:00", 30);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Recent export history
+//!
+//! Completed exports are appended to `export-history.json` in the app data
+//! directory so users can re-open or re-export recent jobs. The file keeps the
+//! newest `MAX_EXPORT_HISTORY` records; home directory prefixes are stored as
+//! `~` so the history doesn't leak the user's account name if shared.
+
+use crate::models::ExportConfig;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Records kept in the history file; older ones are evicted
+pub const MAX_EXPORT_HISTORY: usize = 50;
+
+lazy_static::lazy_static! {
+    /// Serializes read-modify-write cycles on the history file
+    static ref HISTORY_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+}
+
+/// A completed export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportRecord {
+    pub export_id: String,
+    /// Output file, with the home directory shown as `~`
+    pub output_path: String,
+    /// Short description of the export settings (e.g. "mp4 · libx264 · 1920x1080 · aac")
+    pub config_summary: String,
+    /// Timeline duration in milliseconds
+    pub duration_ms: u64,
+    /// RFC 3339 completion time
+    pub completed_at: String,
+}
+
+impl ExportRecord {
+    /// Record for an export that just finished
+    pub fn completed(export_id: &str, config: &ExportConfig, duration_ms: u64) -> Self {
+        Self {
+            export_id: export_id.to_string(),
+            output_path: redact_home(&config.output_path, dirs::home_dir().as_deref()),
+            config_summary: summarize_config(config),
+            duration_ms,
+            completed_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Replace a leading `home` directory with `~`
+pub fn redact_home(path: &str, home: Option<&Path>) -> String {
+    let Some(home) = home else {
+        return path.to_string();
+    };
+
+    match Path::new(path).strip_prefix(home) {
+        Ok(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Ok(rest) => format!("~/{}", rest.to_string_lossy()),
+        Err(_) => path.to_string(),
+    }
+}
+
+/// One-line summary of the settings that shape the output file
+pub fn summarize_config(config: &ExportConfig) -> String {
+    let mut parts = vec![
+        config.container(),
+        config.video_encoder().to_string(),
+    ];
+    if let Some((width, height)) = config.resolution {
+        parts.push(format!("{}x{}", width, height));
+    }
+    if let Some(bitrate) = &config.video_bitrate {
+        parts.push(bitrate.clone());
+    }
+    parts.push(config.audio_codec.ffmpeg_codec().to_string());
+    parts.join(" · ")
+}
+
+/// Default history file: `<data dir>/com.clippy.app/export-history.json`
+pub fn default_history_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("Failed to resolve app data directory")?;
+    Ok(data_dir.join("com.clippy.app").join("export-history.json"))
+}
+
+/// Load the history, oldest first (a missing file is an empty history)
+pub fn load_history(path: &Path) -> Result<Vec<ExportRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let json = std::fs::read_to_string(path).context("Failed to read export history")?;
+    serde_json::from_str(&json).context("Failed to parse export history")
+}
+
+/// Append a record, evicting the oldest ones beyond `cap`
+pub fn append_record(path: &Path, record: ExportRecord, cap: usize) -> Result<()> {
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    // A corrupt file is replaced rather than blocking new records
+    let mut records = load_history(path).unwrap_or_else(|e| {
+        tracing::warn!(event = "export_history_unreadable", error = %e, "Starting a new export history");
+        Vec::new()
+    });
+    records.push(record);
+    if records.len() > cap {
+        let excess = records.len() - cap;
+        records.drain(..excess);
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create export history directory")?;
+    }
+    let json = serde_json::to_string_pretty(&records).context("Failed to serialize export history")?;
+    std::fs::write(path, json).context("Failed to write export history")
+}
+
+/// Append a completed export to the default history file
+///
+/// Failures are logged, never returned: history is a convenience and must not
+/// turn a successful export into an error.
+pub fn record_completed_export(export_id: &str, config: &ExportConfig, duration_ms: u64) {
+    let record = ExportRecord::completed(export_id, config, duration_ms);
+    let result = default_history_path().and_then(|path| append_record(&path, record, MAX_EXPORT_HISTORY));
+    if let Err(e) = result {
+        tracing::warn!(event = "export_history_write_failed", export_id = %export_id, error = %e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(export_id: &str) -> ExportRecord {
+        ExportRecord {
+            export_id: export_id.to_string(),
+            output_path: "~/Movies/out.mp4".to_string(),
+            config_summary: "mp4 · libx264 · aac".to_string(),
+            duration_ms: 5_000,
+            completed_at: "2025-01-01T00:00:00+00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_append_record_caps_history_evicting_oldest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history").join("export-history.json");
+
+        append_record(&path, record("first"), 3).unwrap();
+        assert_eq!(load_history(&path).unwrap(), vec![record("first")]);
+
+        for id in ["second", "third", "fourth"] {
+            append_record(&path, record(id), 3).unwrap();
+        }
+        let ids: Vec<String> = load_history(&path)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.export_id)
+            .collect();
+        assert_eq!(ids, ["second", "third", "fourth"]);
+    }
+
+    #[test]
+    fn test_completed_record_redacts_home_directory() {
+        let home = Path::new("/Users/alex");
+        assert_eq!(redact_home("/Users/alex/Movies/out.mp4", Some(home)), "~/Movies/out.mp4");
+        assert_eq!(redact_home("/Volumes/External/out.mp4", Some(home)), "/Volumes/External/out.mp4");
+        assert_eq!(redact_home("/Users/alexander/out.mp4", Some(home)), "/Users/alexander/out.mp4");
+
+        let config = ExportConfig {
+            output_path: "/tmp/out.mov".to_string(),
+            resolution: Some((1280, 720)),
+            ..Default::default()
+        };
+        assert_eq!(summarize_config(&config), "mov · libx264 · 1280x720 · aac");
+    }
+}
@@ -11,13 +11,14 @@
 //! - Uses `amix` filter to mix multiple audio streams into single output
 //! - Supports 2-8 simultaneous audio tracks
 //! - Applies clipping prevention via amix auto-normalization
+//! - Leaves muted clips out of the `amix` entirely; all-muted renders silence
 //!
 //! # FFmpeg Filter Syntax Example
 //!
 //! For 2 audio clips with volume and fade:
 //! ```text
-//! [0:a]volume=1.0,afade=t=in:st=0:d=1[a1];
-//! [1:a]volume=0.5,afade=t=out:st=9:d=1[a2];
+//! [0:a]volume=1.000,afade=t=in:st=0:d=1[a1];
+//! [1:a]volume=0.500,afade=t=out:st=9:d=1[a2];
 //! [a1][a2]amix=inputs=2:duration=longest[aout]
 //! ```
 
@@ -65,7 +66,8 @@ pub struct AudioClipMixInfo {
 ///
 /// # Returns
 ///
-/// * `Ok(String)` - FFmpeg `-filter_complex` argument string
+/// * `Ok(String)` - FFmpeg `-filter_complex` argument string; a silent `[aout]`
+///   when every clip is muted
 /// * `Err(anyhow::Error)` - Invalid input or unsupported configuration
 ///
 /// # Story 5.5 Acceptance Criteria
@@ -109,7 +111,7 @@ pub struct AudioClipMixInfo {
 /// ];
 ///
 /// let filter = build_audio_mix_filter(&clips, 1000)?;
-/// // Result: "[0:a]volume=1.0,afade=t=in:st=0:d=0.500,afade=t=out:st=4.500:d=0.500[a0];[1:a]adelay=1000|1000,volume=0.5,afade=t=in:st=0:d=1.000,afade=t=out:st=5.000:d=1.000[a1];[a0][a1]amix=inputs=2:duration=longest:dropout_transition=0[aout]"
+/// // Result: "[0:a]volume=1.000,afade=t=in:st=0:d=0.500,afade=t=out:st=4.500:d=0.500[a0];[1:a]adelay=1000|1000,volume=0.500,afade=t=in:st=0:d=1.000,afade=t=out:st=5.000:d=1.000[a1];[a0][a1]amix=inputs=2:duration=longest:dropout_transition=0[aout]"
 /// ```
 pub fn build_audio_mix_filter(
     clips: &[AudioClipMixInfo],
@@ -118,9 +120,13 @@ pub fn build_audio_mix_filter(
     // AC#4: Filter out muted clips
     let active_clips: Vec<&AudioClipMixInfo> = clips.iter().filter(|c| !c.muted).collect();
 
-    // Validation: Must have at least 1 non-muted clip
+    if clips.is_empty() {
+        anyhow::bail!("No audio clips to mix");
+    }
+
+    // All muted: a silent track as long as the clips, instead of an empty amix
     if active_clips.is_empty() {
-        anyhow::bail!("No active audio clips to mix (all clips are muted)");
+        return Ok(build_silent_filter(clips, segment_start_ms));
     }
 
     // AC#6: Support 2-8 simultaneous audio tracks
@@ -151,7 +157,7 @@ pub fn build_audio_mix_filter(
         }
 
         // AC#3: Apply per-clip volume
-        clip_filters.push(format!("volume={:.2}", clip.volume));
+        clip_filters.push(format!("volume={:.3}", clip.volume));
 
         // AC#9: Apply fade-in if specified
         if clip.fade_in_ms > 0 {
@@ -213,7 +219,7 @@ fn build_single_clip_filter(
     }
 
     // Apply volume
-    filters.push(format!("volume={:.2}", clip.volume));
+    filters.push(format!("volume={:.3}", clip.volume));
 
     // Apply fade-in if specified
     if clip.fade_in_ms > 0 {
@@ -237,6 +243,20 @@ fn build_single_clip_filter(
     Ok(format!("[{}:a]{}[aout]", clip.input_index, filter_chain))
 }
 
+/// Build a silent `[aout]` spanning every clip (all clips muted)
+fn build_silent_filter(clips: &[AudioClipMixInfo], segment_start_ms: u64) -> String {
+    let span_ms = clips
+        .iter()
+        .map(|c| (c.start_time_ms + c.duration_ms).saturating_sub(segment_start_ms))
+        .max()
+        .unwrap_or(0);
+
+    format!(
+        "anullsrc=channel_layout=stereo:sample_rate=48000,atrim=duration={:.3}[aout]",
+        span_ms as f64 / 1000.0
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -381,8 +401,39 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("Too many audio tracks"));
     }
 
+    fn clip(input_index: usize, volume: f64, muted: bool) -> AudioClipMixInfo {
+        AudioClipMixInfo {
+            clip_id: format!("clip{}", input_index),
+            file_path: format!("/clip{}.mp3", input_index),
+            input_index,
+            start_time_ms: 1000,
+            duration_ms: 5000,
+            volume,
+            muted,
+            fade_in_ms: 0,
+            fade_out_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_clip_volume_node() {
+        let filter = build_audio_mix_filter(&[clip(0, 0.5, false), clip(1, 1.25, false)], 1000).unwrap();
+        assert!(filter.contains("[0:a]volume=0.500[a0]"));
+        assert!(filter.contains("[1:a]volume=1.250[a1]"));
+    }
+
+    #[test]
+    fn test_muted_clip_reduces_amix_inputs() {
+        let clips = vec![clip(0, 1.0, false), clip(1, 1.0, true), clip(2, 0.5, false)];
+        let filter = build_audio_mix_filter(&clips, 1000).unwrap();
+
+        assert!(!filter.contains("[1:a]"));
+        assert!(filter.contains("[2:a]volume=0.500[a1]"));
+        assert!(filter.contains("[a0][a1]amix=inputs=2"));
+    }
+
     #[test]
-    fn test_all_muted_clips_error() {
+    fn test_all_muted_clips_render_silence() {
         let clips = vec![
             AudioClipMixInfo {
                 clip_id: "muted1".to_string(),
@@ -408,10 +459,11 @@ mod tests {
             },
         ];
 
-        let result = build_audio_mix_filter(&clips, 1000);
+        let filter = build_audio_mix_filter(&clips, 1000).unwrap();
 
-        // Should fail - no active clips
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("all clips are muted"));
+        // Silent track for the clips' span, no amix over zero inputs
+        assert_eq!(filter, "anullsrc=channel_layout=stereo:sample_rate=48000,atrim=duration=5.000[aout]");
+        assert!(!filter.contains("amix"));
+        assert!(!filter.contains("[0:a]"));
     }
 }
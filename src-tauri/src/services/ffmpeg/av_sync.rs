@@ -0,0 +1,125 @@
+//! Audio/video sync check for recordings
+//!
+//! Audio and video are written by separate pipelines, so a sync regression
+//! usually shows up as the streams starting at different times in the
+//! container. ffprobe reports each stream's `start_time`; the difference
+//! between the first audio and first video stream is the offset a player
+//! applies when it lines them up.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+use tokio::process::Command;
+
+/// Largest offset reported as in sync (ITU-R BT.1359 detectability threshold)
+pub const AV_SYNC_TOLERANCE_MS: f64 = 45.0;
+
+/// Start times of the first audio and video streams
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvSyncReport {
+    /// Video stream start time in milliseconds
+    pub video_start_ms: f64,
+    /// Audio stream start time in milliseconds
+    pub audio_start_ms: f64,
+    /// `audio_start_ms - video_start_ms`; positive means audio starts late
+    pub offset_ms: f64,
+    /// Whether `offset_ms` is within `AV_SYNC_TOLERANCE_MS`
+    pub in_sync: bool,
+}
+
+/// Build a report from `ffprobe -show_streams` JSON
+///
+/// # Returns
+/// * `Ok(AvSyncReport)` - Both streams reported a start time
+/// * `Err(anyhow::Error)` - The file lacks an audio or video stream, or its start time
+pub fn av_sync_from_probe(probe: &serde_json::Value) -> Result<AvSyncReport> {
+    let streams = probe["streams"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("No streams found in file"))?;
+
+    let start_ms = |codec_type: &str| -> Result<f64> {
+        let stream = streams
+            .iter()
+            .find(|s| s["codec_type"].as_str() == Some(codec_type))
+            .ok_or_else(|| anyhow::anyhow!("No {} stream found in file", codec_type))?;
+        stream["start_time"]
+            .as_str()
+            .and_then(|t| t.parse::<f64>().ok())
+            .map(|secs| secs * 1000.0)
+            .ok_or_else(|| anyhow::anyhow!("The {} stream has no start time", codec_type))
+    };
+
+    let video_start_ms = start_ms("video")?;
+    let audio_start_ms = start_ms("audio")?;
+    let offset_ms = audio_start_ms - video_start_ms;
+
+    Ok(AvSyncReport {
+        video_start_ms,
+        audio_start_ms,
+        offset_ms,
+        in_sync: offset_ms.abs() <= AV_SYNC_TOLERANCE_MS,
+    })
+}
+
+/// Measure the audio/video start offset of a file with ffprobe
+pub async fn measure_av_sync(path: &Path) -> Result<AvSyncReport> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "error", "-print_format", "json", "-show_streams"])
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to execute ffprobe")?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "FFprobe failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let probe: serde_json::Value =
+        serde_json::from_slice(&output.stdout).context("Failed to parse ffprobe output")?;
+    av_sync_from_probe(&probe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_is_audio_start_minus_video_start() {
+        let probe = serde_json::json!({
+            "streams": [
+                { "index": 0, "codec_type": "video", "start_time": "0.033333" },
+                { "index": 1, "codec_type": "audio", "start_time": "0.120000" },
+            ]
+        });
+
+        let report = av_sync_from_probe(&probe).unwrap();
+        assert!((report.video_start_ms - 33.333).abs() < 1e-6);
+        assert!((report.audio_start_ms - 120.0).abs() < 1e-6);
+        assert!((report.offset_ms - 86.667).abs() < 1e-6);
+        assert!(!report.in_sync);
+
+        // Audio slightly early is still within tolerance
+        let probe = serde_json::json!({
+            "streams": [
+                { "codec_type": "audio", "start_time": "0.000000" },
+                { "codec_type": "video", "start_time": "0.021000" },
+            ]
+        });
+        let report = av_sync_from_probe(&probe).unwrap();
+        assert!((report.offset_ms + 21.0).abs() < 1e-6);
+        assert!(report.in_sync);
+    }
+
+    #[test]
+    fn test_missing_audio_stream_is_an_error() {
+        let probe = serde_json::json!({
+            "streams": [{ "codec_type": "video", "start_time": "0.000000" }]
+        });
+        let err = av_sync_from_probe(&probe).unwrap_err();
+        assert!(err.to_string().contains("audio"));
+    }
+}
@@ -0,0 +1,126 @@
+//! Composition backdrop for letterboxed exports
+//!
+//! When the exported resolution has a different aspect than the timeline, the
+//! video is scaled to fit and centred; `Background` decides what fills the
+//! rest of the frame:
+//!
+//! ```text
+//! Solid:         [0:v]scale=W:H:force_original_aspect_ratio=decrease,pad=W:H:(ow-iw)/2:(oh-ih)/2:color=0x1E1E1E[vout]
+//! BlurredSource: [0:v]split=2[bgsrc][fgsrc];[bgsrc]scale=...increase,crop=W:H,gblur=sigma=20[bg];
+//!                [fgsrc]scale=...decrease[fg];[bg][fg]overlay=(W-w)/2:(H-h)/2[vout]
+//! Image:         [1:v]scale=...increase,crop=W:H[bg];[0:v]scale=...decrease[fg];
+//!                [bg][fg]overlay=(W-w)/2:(H-h)/2:shortest=1[vout]
+//! ```
+//!
+//! The image is a second FFmpeg input (`-loop 1 -i <path>`), added by
+//! `background_input_args` right after the video input.
+
+use crate::models::Background;
+
+/// Input index of the background image (the video is input 0)
+pub const BACKGROUND_IMAGE_INPUT: usize = 1;
+
+/// FFmpeg color for a `Background::Solid` color (`#RRGGBB` becomes `0xRRGGBB`)
+pub fn ffmpeg_color(color: &str) -> String {
+    match color.strip_prefix('#') {
+        Some(hex) => format!("0x{}", hex),
+        None => color.to_string(),
+    }
+}
+
+/// Pad color for builders that letterbox clip by clip
+///
+/// Blurred and image backdrops need the whole fitted frame, so clips pad black
+/// under them; those backdrops apply when the export is fitted to its resolution.
+pub fn pad_color(background: &Background) -> String {
+    match background {
+        Background::Solid { color } => ffmpeg_color(color),
+        Background::BlurredSource { .. } | Background::Image { .. } => "black".to_string(),
+    }
+}
+
+/// Extra input arguments the background needs (only `Image` adds one)
+pub fn background_input_args(background: &Background) -> Vec<String> {
+    match background {
+        Background::Image { path } => vec![
+            "-loop".to_string(),
+            "1".to_string(),
+            "-i".to_string(),
+            path.clone(),
+        ],
+        Background::Solid { .. } | Background::BlurredSource { .. } => Vec::new(),
+    }
+}
+
+/// Filtergraph fitting input 0 into `width`x`height` over `background`, labelled `[vout]`
+pub fn build_background_filter(background: &Background, width: u32, height: u32) -> String {
+    let fit = format!("scale={}:{}:force_original_aspect_ratio=decrease", width, height);
+    let fill = format!(
+        "scale={w}:{h}:force_original_aspect_ratio=increase,crop={w}:{h}",
+        w = width,
+        h = height
+    );
+
+    match background {
+        Background::Solid { color } => format!(
+            "[0:v]{},pad={}:{}:(ow-iw)/2:(oh-ih)/2:color={}[vout]",
+            fit,
+            width,
+            height,
+            ffmpeg_color(color)
+        ),
+        Background::BlurredSource { sigma } => format!(
+            "[0:v]split=2[bgsrc][fgsrc];[bgsrc]{},gblur=sigma={}[bg];[fgsrc]{}[fg];[bg][fg]overlay=(W-w)/2:(H-h)/2[vout]",
+            fill, sigma, fit
+        ),
+        Background::Image { .. } => format!(
+            "[{}:v]{}[bg];[0:v]{}[fg];[bg][fg]overlay=(W-w)/2:(H-h)/2:shortest=1[vout]",
+            BACKGROUND_IMAGE_INPUT, fill, fit
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solid_background_pads_with_color() {
+        let filter = build_background_filter(
+            &Background::Solid { color: "#1E1E1E".to_string() },
+            1080,
+            1920,
+        );
+        assert_eq!(
+            filter,
+            "[0:v]scale=1080:1920:force_original_aspect_ratio=decrease,pad=1080:1920:(ow-iw)/2:(oh-ih)/2:color=0x1E1E1E[vout]"
+        );
+        assert!(background_input_args(&Background::default()).is_empty());
+    }
+
+    #[test]
+    fn test_blurred_source_overlays_fit_video_on_blurred_fill() {
+        let background = Background::BlurredSource { sigma: 20.0 };
+        let filter = build_background_filter(&background, 1080, 1920);
+
+        assert!(filter.starts_with("[0:v]split=2[bgsrc][fgsrc];"));
+        assert!(filter.contains(
+            "[bgsrc]scale=1080:1920:force_original_aspect_ratio=increase,crop=1080:1920,gblur=sigma=20[bg]"
+        ));
+        assert!(filter.ends_with("[bg][fg]overlay=(W-w)/2:(H-h)/2[vout]"));
+        assert!(!filter.contains("pad="));
+        assert!(background_input_args(&background).is_empty());
+    }
+
+    #[test]
+    fn test_image_background_uses_second_input() {
+        let background = Background::Image { path: "/tmp/backdrop.png".to_string() };
+
+        assert_eq!(background_input_args(&background), ["-loop", "1", "-i", "/tmp/backdrop.png"]);
+
+        let filter = build_background_filter(&background, 1080, 1920);
+        assert!(filter.starts_with("[1:v]scale=1080:1920:force_original_aspect_ratio=increase,crop=1080:1920[bg];"));
+        assert!(filter.contains("[0:v]scale=1080:1920:force_original_aspect_ratio=decrease[fg]"));
+        assert!(filter.ends_with("overlay=(W-w)/2:(H-h)/2:shortest=1[vout]"));
+    }
+}
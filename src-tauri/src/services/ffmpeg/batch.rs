@@ -0,0 +1,210 @@
+//! Batch export queue
+//!
+//! Renders several versions of a project (aspect ratios, lengths) one after
+//! another. Jobs run sequentially so they don't compete for the encoder; a
+//! failed job is recorded and the queue moves on to the next one.
+
+use super::exporter::{validate_export_config, ExportError};
+use crate::models::{BatchExportProgress, BatchJobResult, ExportJob, ExportStatus};
+use std::collections::HashSet;
+use std::future::Future;
+
+/// Reject batches where two jobs would write the same file
+pub fn validate_batch(jobs: &[ExportJob]) -> Result<(), ExportError> {
+    if jobs.is_empty() {
+        return Err(ExportError::InvalidConfig("Batch export has no jobs".to_string()));
+    }
+
+    let mut seen = HashSet::new();
+    for job in jobs {
+        if !seen.insert(job.config.output_path.as_str()) {
+            return Err(ExportError::InvalidConfig(format!(
+                "Multiple jobs write to the same output: {}",
+                job.config.output_path
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Check every job's timeline and export settings before the first job starts
+///
+/// Otherwise a bad job only fails once the jobs before it have rendered. The
+/// error keeps the job's own code (`invalid_timeline`, `invalid_config`) and
+/// says which job failed.
+pub async fn validate_batch_jobs(jobs: &[ExportJob]) -> Result<(), ExportError> {
+    for (index, job) in jobs.iter().enumerate() {
+        let checked = match job.timeline.validate() {
+            Ok(()) => validate_export_config(&job.config).await,
+            Err(errors) => Err(ExportError::InvalidTimeline(errors)),
+        };
+        checked.map_err(|e| e.for_batch_job(index, &job.config.output_path))?;
+    }
+    Ok(())
+}
+
+fn batch_progress(batch_id: &str, finished: usize, total: usize, current: Option<usize>) -> BatchExportProgress {
+    BatchExportProgress {
+        batch_id: batch_id.to_string(),
+        finished_jobs: finished,
+        total_jobs: total,
+        current_job: current,
+        percentage: if total == 0 { 100.0 } else { finished as f32 / total as f32 * 100.0 },
+    }
+}
+
+/// Batch progress while job `index` is `job_percentage` (0-100) done
+///
+/// Every job counts for an equal share of the batch.
+pub fn running_job_progress(batch_id: &str, index: usize, total: usize, job_percentage: f32) -> BatchExportProgress {
+    let mut progress = batch_progress(batch_id, index, total, Some(index));
+    if total > 0 {
+        progress.percentage = (index as f32 + job_percentage.clamp(0.0, 100.0) / 100.0) / total as f32 * 100.0;
+    }
+    progress
+}
+
+/// Run export jobs in order
+///
+/// # Arguments
+/// * `batch_id` - ID reported in progress updates
+/// * `jobs` - Jobs to render
+/// * `run_job` - Renders one job; called with its index, export ID and the job
+/// * `on_progress` - Called when each job starts and once the batch finishes
+///
+/// # Returns
+/// One result per job, in submission order
+pub async fn run_export_batch<F, Fut>(
+    batch_id: &str,
+    jobs: Vec<ExportJob>,
+    mut run_job: F,
+    mut on_progress: impl FnMut(&BatchExportProgress),
+) -> Vec<BatchJobResult>
+where
+    F: FnMut(usize, String, ExportJob) -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    let total = jobs.len();
+    let mut results = Vec::with_capacity(total);
+
+    for (index, job) in jobs.into_iter().enumerate() {
+        on_progress(&batch_progress(batch_id, index, total, Some(index)));
+
+        let export_id = uuid::Uuid::new_v4().to_string();
+        let output_path = job.config.output_path.clone();
+        let result = run_job(index, export_id.clone(), job).await;
+
+        if let Err(e) = &result {
+            tracing::error!(
+                event = "batch_export_job_failed",
+                batch_id = %batch_id,
+                job_index = index,
+                error = %e,
+                "Batch export job failed"
+            );
+        }
+
+        results.push(BatchJobResult {
+            index,
+            export_id,
+            output_path,
+            status: if result.is_ok() { ExportStatus::Completed } else { ExportStatus::Failed },
+            error_message: result.err(),
+        });
+    }
+
+    on_progress(&batch_progress(batch_id, total, total, None));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ExportConfig, Timeline};
+
+    fn job(output_path: &str) -> ExportJob {
+        ExportJob {
+            timeline: Timeline {
+                tracks: vec![],
+                total_duration: 5_000,
+            },
+            config: ExportConfig {
+                output_path: output_path.to_string(),
+                ..ExportConfig::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_returns_result_per_job_and_reaches_100() {
+        let jobs = vec![job("/tmp/a.mp4"), job("/tmp/b.mp4"), job("/tmp/c.mp4")];
+        let mut updates = Vec::new();
+
+        let results = run_export_batch(
+            "batch-1",
+            jobs,
+            |index, _, _| async move {
+                if index == 1 {
+                    Err("encoder crashed".to_string())
+                } else {
+                    Ok(())
+                }
+            },
+            |progress| updates.push(progress.clone()),
+        )
+        .await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].status, ExportStatus::Completed);
+        assert_eq!(results[1].status, ExportStatus::Failed);
+        assert_eq!(results[1].error_message.as_deref(), Some("encoder crashed"));
+        assert_eq!(results[2].output_path, "/tmp/c.mp4");
+
+        let percentages: Vec<f32> = updates.iter().map(|p| p.percentage).collect();
+        assert!(percentages.windows(2).all(|w| w[0] <= w[1]));
+        let last = updates.last().unwrap();
+        assert_eq!(last.percentage, 100.0);
+        assert_eq!(last.finished_jobs, 3);
+        assert_eq!(last.current_job, None);
+    }
+
+    #[test]
+    fn test_running_job_progress_counts_the_current_job() {
+        let halfway = running_job_progress("batch-1", 1, 4, 50.0);
+        assert_eq!(halfway.percentage, 37.5);
+        assert_eq!((halfway.finished_jobs, halfway.current_job), (1, Some(1)));
+        assert_eq!(running_job_progress("batch-1", 3, 4, 250.0).percentage, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_validate_batch_jobs_rejects_a_bad_job_up_front() {
+        use crate::models::timeline::{Track, TrackType};
+        use crate::test_utils::timeline_fixtures::ClipBuilder;
+
+        assert!(validate_batch_jobs(&[job("/tmp/a.mp4"), job("/tmp/b.mp4")]).await.is_ok());
+
+        let mut missing_media = job("/tmp/b.mp4");
+        missing_media.timeline.tracks.push(Track {
+            id: "video".to_string(),
+            track_number: 1,
+            clips: vec![ClipBuilder::new("/media/does-not-exist.mp4", 0, 5_000).build()],
+            track_type: TrackType::Video,
+        });
+        let err = validate_batch_jobs(&[job("/tmp/a.mp4"), missing_media]).await.unwrap_err();
+        assert_eq!(err.code(), "invalid_timeline");
+        assert!(err.to_string().starts_with("Job 2 (/tmp/b.mp4):"), "{}", err);
+
+        let mut bad_crf = job("/tmp/c.mp4");
+        bad_crf.config.crf = Some(99);
+        let err = validate_batch_jobs(&[bad_crf]).await.unwrap_err();
+        assert_eq!(err.code(), "invalid_config");
+        assert!(err.to_string().starts_with("Job 1"));
+    }
+
+    #[test]
+    fn test_validate_batch_rejects_duplicate_outputs() {
+        assert!(validate_batch(&[job("/tmp/a.mp4"), job("/tmp/b.mp4")]).is_ok());
+        assert!(validate_batch(&[job("/tmp/a.mp4"), job("/tmp/a.mp4")]).is_err());
+        assert!(validate_batch(&[]).is_err());
+    }
+}
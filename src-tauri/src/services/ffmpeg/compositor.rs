@@ -21,12 +21,15 @@
 //! - [1:v] = webcam video (overlay layer)
 //! - x,y = PiP position coordinates
 
+use crate::models::export::validate_crf;
+use crate::models::Preset;
 use anyhow::{Context, Result};
 use ffmpeg_sidecar::child::FfmpegChild;
 use ffmpeg_sidecar::command::FfmpegCommand;
+use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
@@ -44,6 +47,52 @@ pub struct CompositorFrame {
     pub height: u32,
 }
 
+/// Shape of the webcam overlay
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PipShape {
+    /// Plain rectangle (no alpha mask)
+    #[default]
+    Rectangle,
+    /// Rectangle with rounded corners
+    RoundedRect,
+    /// Circle centered in the PiP rectangle (diameter = shorter side)
+    Circle,
+}
+
+/// Corner radius of `PipShape::RoundedRect` as a fraction of the shorter PiP side
+const ROUNDED_RECT_RADIUS_RATIO: f32 = 0.125;
+
+impl PipShape {
+    /// Alpha mask filter appended after the webcam is scaled to `width`x`height`
+    ///
+    /// Returns an empty string for `Rectangle`. Masks are expressed with `geq`
+    /// on an RGBA frame so they follow the scaled PiP size exactly.
+    pub fn mask_filter(&self, width: u32, height: u32) -> String {
+        let alpha = match self {
+            PipShape::Rectangle => return String::new(),
+            PipShape::Circle => {
+                "if(lte(hypot(X-W/2,Y-H/2),min(W,H)/2),255,0)".to_string()
+            }
+            PipShape::RoundedRect => {
+                let radius =
+                    ((width.min(height) as f32 * ROUNDED_RECT_RADIUS_RATIO).round() as u32).max(1);
+                // Only pixels inside a corner square are tested against the corner circle
+                format!(
+                    "if(gt(abs(X-W/2),W/2-{r})*gt(abs(Y-H/2),H/2-{r}),\
+                     if(lte(hypot(abs(X-W/2)-(W/2-{r}),abs(Y-H/2)-(H/2-{r})),{r}),255,0),255)",
+                    r = radius
+                )
+            }
+        };
+
+        format!(
+            ",format=rgba,geq=r='r(X,Y)':g='g(X,Y)':b='b(X,Y)':a='{}'",
+            alpha
+        )
+    }
+}
+
 /// PiP composition configuration
 #[derive(Debug, Clone)]
 pub struct PipConfig {
@@ -53,6 +102,397 @@ pub struct PipConfig {
     /// PiP size
     pub width: u32,
     pub height: u32,
+    /// Webcam overlay shape
+    pub shape: PipShape,
+    /// Corner radius in pixels (0 = use `shape` as is)
+    pub corner_radius: u32,
+    /// Border width in pixels and `#RRGGBB` color
+    pub border: Option<(u32, String)>,
+}
+
+impl PipConfig {
+    /// Corner radius in pixels at `width`x`height`, `None` for a circle
+    ///
+    /// An explicit `corner_radius` rounds any non-circular shape; otherwise
+    /// `RoundedRect` keeps its proportional radius. Clamped to half the shorter side.
+    fn corner_radius_px(&self, width: u32, height: u32) -> Option<u32> {
+        let max = width.min(height) / 2;
+        match self.shape {
+            PipShape::Circle => None,
+            _ if self.corner_radius > 0 => Some(self.corner_radius.min(max)),
+            PipShape::RoundedRect => Some(
+                ((width.min(height) as f32 * ROUNDED_RECT_RADIUS_RATIO).round() as u32).clamp(1, max.max(1)),
+            ),
+            PipShape::Rectangle => Some(0),
+        }
+    }
+
+    /// Border width and RGB color, if a visible border is configured
+    fn border_rgb(&self) -> Option<(u32, (u8, u8, u8))> {
+        let (width, color) = self.border.as_ref()?;
+        if *width == 0 {
+            return None;
+        }
+        match parse_hex_color(color) {
+            Some(rgb) => Some((*width, rgb)),
+            None => {
+                warn!("Ignoring PiP border with invalid color '{}'", color);
+                None
+            }
+        }
+    }
+
+    /// Scale the overlay by `scale` and keep it, border included, inside the screen
+    ///
+    /// An overlay larger than the screen is shrunk with its aspect ratio kept,
+    /// then the position is clamped. Logs a warning when anything was adjusted.
+    pub fn fit_to_screen(mut self, scale: f32, screen_width: u32, screen_height: u32) -> Self {
+        let border = self.border_rgb().map_or(0, |(width, _)| width);
+        let available_width = screen_width.saturating_sub(2 * border).max(1);
+        let available_height = screen_height.saturating_sub(2 * border).max(1);
+
+        let mut width = (self.width as f32 * scale).round().max(1.0);
+        let mut height = (self.height as f32 * scale).round().max(1.0);
+        let shrink = (available_width as f32 / width).min(available_height as f32 / height);
+        if shrink < 1.0 {
+            width = (width * shrink).floor().max(1.0);
+            height = (height * shrink).floor().max(1.0);
+        }
+        let (width, height) = (width as u32, height as u32);
+
+        let max_x = screen_width.saturating_sub(border + width) as i32;
+        let max_y = screen_height.saturating_sub(border + height) as i32;
+        let x = self.x.clamp(border as i32, max_x.max(border as i32));
+        let y = self.y.clamp(border as i32, max_y.max(border as i32));
+
+        if (x, y) != (self.x, self.y) || shrink < 1.0 {
+            warn!(
+                "PiP overlay {}x{} at ({},{}) does not fit the {}x{} screen, using {}x{} at ({},{})",
+                (self.width as f32 * scale).round(),
+                (self.height as f32 * scale).round(),
+                self.x,
+                self.y,
+                screen_width,
+                screen_height,
+                width,
+                height,
+                x,
+                y
+            );
+        }
+
+        self.width = width;
+        self.height = height;
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    /// Check the styling options before a recording starts
+    pub fn validate_style(&self) -> Result<(), String> {
+        match &self.border {
+            Some((_, color)) if parse_hex_color(color).is_none() => Err(format!(
+                "Invalid PiP border color '{}': expected #RRGGBB",
+                color
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Parse a `#RRGGBB` (or `RRGGBB`) color
+pub fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.strip_prefix('#').unwrap_or(color);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// `geq` expression that is 1 inside the PiP shape, inset `inset` pixels from the frame edge
+fn shape_inside_expr(radius: Option<u32>, inset: u32) -> String {
+    match radius {
+        None => format!("lte(hypot(X-W/2,Y-H/2),min(W,H)/2-{})", inset),
+        Some(r) => format!(
+            "lte(abs(X-W/2),W/2-{i})*lte(abs(Y-H/2),H/2-{i})*\
+             if(gt(abs(X-W/2),W/2-{i}-{r})*gt(abs(Y-H/2),H/2-{i}-{r}),\
+             lte(hypot(abs(X-W/2)-(W/2-{i}-{r}),abs(Y-H/2)-(H/2-{i}-{r})),{r}),1)",
+            i = inset,
+            r = r
+        ),
+    }
+}
+
+/// Build the filter graph overlaying input 1 (webcam) onto input 0 (screen)
+///
+/// The webcam is scaled to `width`x`height`, masked according to `shape`,
+/// and placed at (`x`, `y`). The graph ends at the overlay output so callers
+/// can append an output label.
+pub fn build_pip_filter(shape: PipShape, width: u32, height: u32, x: i32, y: i32) -> String {
+    format!(
+        "[1:v]scale={}:{}{}[pip];[0:v][pip]overlay={}:{}",
+        width,
+        height,
+        shape.mask_filter(width, height),
+        x,
+        y
+    )
+}
+
+/// Build the overlay filter graph for `pip` with the webcam scaled to `width`x`height`
+///
+/// Without a corner radius or border this is `build_pip_filter`. A border pads
+/// the webcam by its width and a single `geq` pass paints the ring between the
+/// outer and inner shape, so it follows rounded corners and circles; the webcam
+/// itself stays at (`x`, `y`) and the border grows outward.
+pub fn build_styled_pip_filter(pip: &PipConfig, width: u32, height: u32) -> String {
+    let border = pip.border_rgb();
+    if pip.corner_radius == 0 && border.is_none() {
+        return build_pip_filter(pip.shape, width, height, pip.x, pip.y);
+    }
+
+    let radius = pip.corner_radius_px(width, height);
+    let (border_width, (r, g, b)) = border.unwrap_or((0, (0, 0, 0)));
+    // Growing the radius with the border keeps the ring an even width around corners
+    let outer = shape_inside_expr(radius.map(|r| if r > 0 { r + border_width } else { 0 }), 0);
+
+    let mut filter = format!("[1:v]scale={}:{},format=rgba", width, height);
+    let color = if border_width > 0 {
+        filter.push_str(&format!(
+            ",pad=iw+{d}:ih+{d}:{w}:{w}",
+            d = 2 * border_width,
+            w = border_width
+        ));
+        let ring = format!("({})*not({})", outer, shape_inside_expr(radius, border_width));
+        let channel = |value: u8, name: &str| format!("if({},{},{}(X,Y))", ring, value, name);
+        (channel(r, "r"), channel(g, "g"), channel(b, "b"))
+    } else {
+        ("r(X,Y)".to_string(), "g(X,Y)".to_string(), "b(X,Y)".to_string())
+    };
+    filter.push_str(&format!(
+        ",geq=r='{}':g='{}':b='{}':a='if({},255,0)'[pip];[0:v][pip]overlay={}:{}",
+        color.0,
+        color.1,
+        color.2,
+        outer,
+        pip.x - border_width as i32,
+        pip.y - border_width as i32
+    ));
+    filter
+}
+
+/// Sample rate of the system audio PCM written during PiP recording
+pub const PIP_SYSTEM_AUDIO_SAMPLE_RATE: u32 = 48000;
+
+/// Software encoder settings for the composited PiP video
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PipEncoding {
+    /// `-crf` (0-51, lower = better quality)
+    pub crf: u8,
+    /// `-preset`
+    pub preset: Preset,
+}
+
+impl Default for PipEncoding {
+    /// CRF 23 at ultrafast, which keeps up with real-time composition
+    fn default() -> Self {
+        Self {
+            crf: 23,
+            preset: Preset::Ultrafast,
+        }
+    }
+}
+
+impl PipEncoding {
+    /// Encoding with the requested settings, defaulting the ones not given
+    ///
+    /// # Returns
+    /// * `Err(String)` - CRF outside 0-51
+    pub fn new(crf: Option<u8>, preset: Option<Preset>) -> Result<Self, String> {
+        let default = Self::default();
+        Ok(Self {
+            crf: crf.map(validate_crf).transpose()?.unwrap_or(default.crf),
+            preset: preset.unwrap_or(default.preset),
+        })
+    }
+}
+
+/// Channel count of the system audio PCM written during PiP recording
+pub const PIP_SYSTEM_AUDIO_CHANNELS: u16 = 2;
+
+/// Audio recorded alongside the PiP video tracks
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PipAudioInputs<'a> {
+    /// Raw s16le system audio (`PIP_SYSTEM_AUDIO_SAMPLE_RATE`, `PIP_SYSTEM_AUDIO_CHANNELS`)
+    pub system_audio_pcm: Option<&'a Path>,
+    /// Microphone WAV
+    pub microphone_wav: Option<&'a Path>,
+}
+
+/// Build the FFmpeg arguments compositing the PiP temp files into `output_path`
+///
+/// Inputs 0 and 1 are the screen and webcam videos; audio sources follow in
+/// order (system audio, then microphone). When both audio sources are present
+/// they are mixed into a single track with `amix`; a single source is mapped
+/// directly, and no source produces a silent output.
+pub fn build_pip_composition_args(
+    screen_path: &Path,
+    webcam_path: &Path,
+    audio: PipAudioInputs<'_>,
+    video_filter: &str,
+    encoding: PipEncoding,
+    output_path: &Path,
+) -> Vec<String> {
+    let mut args = vec![
+        "-i".to_string(), screen_path.to_string_lossy().to_string(),
+        "-i".to_string(), webcam_path.to_string_lossy().to_string(),
+    ];
+
+    let mut audio_labels = Vec::new();
+    if let Some(pcm_path) = audio.system_audio_pcm {
+        args.extend([
+            "-f".to_string(), "s16le".to_string(),
+            "-ar".to_string(), PIP_SYSTEM_AUDIO_SAMPLE_RATE.to_string(),
+            "-ac".to_string(), PIP_SYSTEM_AUDIO_CHANNELS.to_string(),
+            "-i".to_string(), pcm_path.to_string_lossy().to_string(),
+        ]);
+        audio_labels.push(format!("{}:a", 2 + audio_labels.len()));
+    }
+    if let Some(wav_path) = audio.microphone_wav {
+        args.extend(["-i".to_string(), wav_path.to_string_lossy().to_string()]);
+        audio_labels.push(format!("{}:a", 2 + audio_labels.len()));
+    }
+
+    let mut filter_complex = format!("{}[vout]", video_filter);
+    let audio_map = match audio_labels.as_slice() {
+        [] => None,
+        [single] => Some(single.clone()),
+        labels => {
+            // Mono mic and stereo system audio are brought to a common layout before mixing
+            for (i, label) in labels.iter().enumerate() {
+                filter_complex.push_str(&format!(
+                    ";[{}]aformat=channel_layouts=stereo[a{}]",
+                    label, i
+                ));
+            }
+            filter_complex.push(';');
+            for i in 0..labels.len() {
+                filter_complex.push_str(&format!("[a{}]", i));
+            }
+            filter_complex.push_str(&format!(
+                "amix=inputs={}:duration=longest:normalize=0[aout]",
+                labels.len()
+            ));
+            Some("[aout]".to_string())
+        }
+    };
+
+    args.extend([
+        "-filter_complex".to_string(), filter_complex,
+        "-map".to_string(), "[vout]".to_string(),
+        "-c:v".to_string(), "libx264".to_string(),
+        "-preset".to_string(), encoding.preset.name().to_string(),
+        "-crf".to_string(), encoding.crf.to_string(),
+    ]);
+
+    match audio_map {
+        Some(stream) => args.extend([
+            "-map".to_string(), stream,
+            "-c:a".to_string(), "aac".to_string(),
+            "-b:a".to_string(), "192k".to_string(),
+        ]),
+        None => args.push("-an".to_string()),
+    }
+
+    args.push("-y".to_string());
+    args.push(output_path.to_string_lossy().to_string());
+    args
+}
+
+/// Progress of the post-recording PiP composition
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompositionProgress {
+    /// 0–100; stays 0 while `indeterminate`
+    pub percentage: f64,
+    /// The total duration is unknown, so no percentage can be given
+    pub indeterminate: bool,
+}
+
+impl CompositionProgress {
+    /// Progress after `out_time_ms` of output, given the total duration if known
+    pub fn from_out_time(out_time_ms: u64, duration_ms: Option<u64>) -> Self {
+        match duration_ms.filter(|&d| d > 0) {
+            Some(duration_ms) => Self {
+                percentage: (out_time_ms as f64 / duration_ms as f64 * 100.0).clamp(0.0, 100.0),
+                indeterminate: false,
+            },
+            None => Self {
+                percentage: 0.0,
+                indeterminate: true,
+            },
+        }
+    }
+}
+
+/// Output time in milliseconds from one line of FFmpeg `-progress` output
+///
+/// Reads `out_time_us=<microseconds>` (FFmpeg's `out_time_ms` key is also in
+/// microseconds, so it is ignored). Other keys and `N/A` values give `None`.
+pub fn parse_progress_out_time_ms(line: &str) -> Option<u64> {
+    let value = line.trim().strip_prefix("out_time_us=")?;
+    value.parse::<i64>().ok().map(|us| us.max(0) as u64 / 1000)
+}
+
+/// Run the PiP composition, reporting progress from FFmpeg's `-progress` output
+///
+/// # Arguments
+/// * `args` - Arguments from `build_pip_composition_args`
+/// * `duration_ms` - Length of the recording, if known (otherwise progress is indeterminate)
+/// * `on_progress` - Called on every progress update and once with 100% on success
+pub async fn run_pip_composition(
+    args: &[String],
+    duration_ms: Option<u64>,
+    mut on_progress: impl FnMut(CompositionProgress),
+) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+
+    let mut child = tokio::process::Command::new("ffmpeg")
+        .args(["-progress", "pipe:1", "-nostats"])
+        .args(args)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .context("Failed to run FFmpeg composition")?;
+
+    // Drain stderr alongside stdout so neither pipe fills up and stalls FFmpeg
+    let mut stderr = child.stderr.take().context("FFmpeg stderr not captured")?;
+    let stderr_task = tokio::spawn(async move {
+        let mut buf = String::new();
+        let _ = stderr.read_to_string(&mut buf).await;
+        buf
+    });
+
+    let stdout = child.stdout.take().context("FFmpeg stdout not captured")?;
+    let mut lines = BufReader::new(stdout).lines();
+    while let Some(line) = lines.next_line().await.context("Failed to read FFmpeg progress")? {
+        if let Some(out_time_ms) = parse_progress_out_time_ms(&line) {
+            on_progress(CompositionProgress::from_out_time(out_time_ms, duration_ms));
+        }
+    }
+
+    let status = child.wait().await.context("FFmpeg composition process failed")?;
+    let stderr = stderr_task.await.unwrap_or_default();
+    if !status.success() {
+        return Err(anyhow::anyhow!("FFmpeg composition failed: {}", stderr.trim()));
+    }
+
+    on_progress(CompositionProgress {
+        percentage: 100.0,
+        indeterminate: false,
+    });
+    Ok(())
 }
 
 /// FFmpeg compositor for real-time PiP composition
@@ -86,6 +526,9 @@ pub struct FFmpegCompositor {
 
     /// PiP configuration
     pip_config: PipConfig,
+
+    /// Software encoder settings (ultrafast CRF 23 unless changed with `set_encoding`)
+    encoding: PipEncoding,
 }
 
 impl FFmpegCompositor {
@@ -158,9 +601,17 @@ impl FFmpegCompositor {
             webcam_height,
             fps,
             pip_config,
+            encoding: PipEncoding::default(),
         })
     }
 
+    /// Encode with `encoding` instead of ultrafast CRF 23
+    ///
+    /// Slower presets than ultrafast may not keep up with real-time capture.
+    pub fn set_encoding(&mut self, encoding: PipEncoding) {
+        self.encoding = encoding;
+    }
+
     /// Start the FFmpeg composition process
     ///
     /// Spawns FFmpeg with two input pipes for screen and webcam video,
@@ -274,11 +725,11 @@ impl FFmpegCompositor {
             .arg("-i")
             .arg(&webcam_fifo);
 
-        // Filter complex: Scale webcam to PiP size, then overlay on screen
+        // Filter complex: Scale (and mask) webcam to PiP size, then overlay on screen
         // Output the result to a labeled stream [out]
         let filter = format!(
-            "[1:v]scale={}:{}[pip];[0:v][pip]overlay={}:{}[out]",
-            self.pip_config.width, self.pip_config.height, self.pip_config.x, self.pip_config.y
+            "{}[out]",
+            build_styled_pip_filter(&self.pip_config, self.pip_config.width, self.pip_config.height)
         );
 
         command.arg("-filter_complex").arg(&filter);
@@ -289,14 +740,14 @@ impl FFmpegCompositor {
         // Use modern fps_mode instead of deprecated vsync
         command.arg("-fps_mode").arg("vfr");
 
-        // H.264 encoding with real-time optimizations
+        // H.264 encoding (ultrafast by default for real-time encoding)
         command
             .arg("-c:v")
             .arg("libx264")
             .arg("-preset")
-            .arg("ultrafast")  // Use ultrafast for real-time encoding
+            .arg(self.encoding.preset.name())
             .arg("-crf")
-            .arg("23");
+            .arg(self.encoding.crf.to_string());
 
         // QuickTime Player compatibility
         command
@@ -726,6 +1177,9 @@ mod tests {
             y: 800,
             width: 384,
             height: 216,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
         };
 
         let compositor = FFmpegCompositor::new(
@@ -757,6 +1211,9 @@ mod tests {
             y: 0,
             width: 320,
             height: 180,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
         };
 
         let result = FFmpegCompositor::new(invalid_path, 1920, 1080, 1280, 720, 30, pip_config);
@@ -777,6 +1234,9 @@ mod tests {
             y: 1000, // 1000 + 216 = 1216 > 1080 (screen height)
             width: 384,
             height: 216,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
         };
 
         let result = FFmpegCompositor::new(output_path, 1920, 1080, 1280, 720, 30, pip_config);
@@ -795,6 +1255,9 @@ mod tests {
             y: 100,
             width: 320,
             height: 180,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
         };
 
         let compositor =
@@ -827,6 +1290,9 @@ mod tests {
             y: margin,
             width: pip_width,
             height: pip_height,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
         };
         assert_eq!(top_left.x, 20);
         assert_eq!(top_left.y, 20);
@@ -837,6 +1303,9 @@ mod tests {
             y: margin,
             width: pip_width,
             height: pip_height,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
         };
         assert_eq!(top_right.x, 1516); // 1920 - 384 - 20
         assert_eq!(top_right.y, 20);
@@ -847,6 +1316,9 @@ mod tests {
             y: screen_height as i32 - pip_height as i32 - margin,
             width: pip_width,
             height: pip_height,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
         };
         assert_eq!(bottom_left.x, 20);
         assert_eq!(bottom_left.y, 844); // 1080 - 216 - 20
@@ -857,8 +1329,225 @@ mod tests {
             y: screen_height as i32 - pip_height as i32 - margin,
             width: pip_width,
             height: pip_height,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
         };
         assert_eq!(bottom_right.x, 1516);
         assert_eq!(bottom_right.y, 844);
     }
+
+    #[test]
+    fn test_pip_filter_rectangle_has_no_mask() {
+        let filter = build_pip_filter(PipShape::Rectangle, 384, 216, 100, 50);
+        assert_eq!(filter, "[1:v]scale=384:216[pip];[0:v][pip]overlay=100:50");
+    }
+
+    #[test]
+    fn test_pip_filter_circle_uses_centered_geq_mask() {
+        let filter = build_pip_filter(PipShape::Circle, 300, 300, 10, 20);
+
+        assert!(filter.starts_with("[1:v]scale=300:300,format=rgba,geq="));
+        assert!(filter.contains("a='if(lte(hypot(X-W/2,Y-H/2),min(W,H)/2),255,0)'"));
+        assert!(filter.ends_with("[pip];[0:v][pip]overlay=10:20"));
+    }
+
+    #[test]
+    fn test_pip_filter_rounded_rect_radius_scales_with_size() {
+        let small = PipShape::RoundedRect.mask_filter(320, 180);
+        let large = PipShape::RoundedRect.mask_filter(640, 360);
+
+        // 12.5% of the shorter side
+        assert!(small.contains("H/2-23"));
+        assert!(large.contains("H/2-45"));
+    }
+
+    #[test]
+    fn test_pip_style_adds_mask_and_border_only_when_requested() {
+        let plain = PipConfig {
+            x: 100,
+            y: 50,
+            width: 384,
+            height: 216,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
+        };
+        let filter = build_styled_pip_filter(&plain, 384, 216);
+        assert_eq!(filter, build_pip_filter(PipShape::Rectangle, 384, 216, 100, 50));
+        assert!(!filter.contains("geq") && !filter.contains("pad"));
+
+        let rounded = PipConfig { corner_radius: 24, ..plain.clone() };
+        let filter = build_styled_pip_filter(&rounded, 384, 216);
+        assert!(filter.contains("geq") && filter.contains(",24)"));
+        assert!(!filter.contains("pad"));
+        assert!(filter.ends_with("overlay=100:50"));
+
+        // Border pads the webcam and paints #FF8000; the webcam itself stays at (100, 50)
+        let bordered = PipConfig { border: Some((4, "#ff8000".to_string())), ..rounded.clone() };
+        let filter = build_styled_pip_filter(&bordered, 384, 216);
+        assert!(filter.contains("pad=iw+8:ih+8:4:4"));
+        assert!(filter.contains(",255,r(X,Y)") && filter.contains(",128,g(X,Y)") && filter.contains(",0,b(X,Y)"));
+        assert!(filter.ends_with("overlay=96:46"));
+
+        // Invalid colors are rejected up front and skipped if they get through
+        let invalid = PipConfig { border: Some((4, "orange".to_string())), ..plain.clone() };
+        assert!(invalid.validate_style().is_err());
+        assert_eq!(build_styled_pip_filter(&invalid, 384, 216), build_styled_pip_filter(&plain, 384, 216));
+        assert_eq!(parse_hex_color("00FF7f"), Some((0, 255, 127)));
+    }
+
+    #[test]
+    fn test_pip_fit_keeps_exact_size_without_scaling() {
+        let pip = PipConfig {
+            x: 1500,
+            y: 800,
+            width: 384,
+            height: 216,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
+        };
+        let fitted = pip.clone().fit_to_screen(1.0, 1920, 1080);
+        assert_eq!((fitted.x, fitted.y, fitted.width, fitted.height), (1500, 800, 384, 216));
+    }
+
+    #[test]
+    fn test_pip_fit_clamps_overflowing_scale_on_screen() {
+        let pip = PipConfig {
+            x: 1500,
+            y: 800,
+            width: 384,
+            height: 216,
+            shape: PipShape::Rectangle,
+            corner_radius: 0,
+            border: None,
+        };
+
+        // 1.5x is 576x324, which would end at (2076, 1124)
+        let fitted = pip.clone().fit_to_screen(1.5, 1920, 1080);
+        assert_eq!((fitted.width, fitted.height), (576, 324));
+        assert_eq!((fitted.x, fitted.y), (1920 - 576, 1080 - 324));
+
+        // The border stays on screen too
+        let bordered = PipConfig { x: 0, y: 0, border: Some((6, "#ffffff".to_string())), ..pip.clone() };
+        let fitted = bordered.fit_to_screen(1.0, 1920, 1080);
+        assert_eq!((fitted.x, fitted.y), (6, 6));
+
+        // Larger than the screen: shrunk to fit with the aspect ratio kept
+        let fitted = pip.fit_to_screen(10.0, 1920, 1080);
+        assert_eq!((fitted.x, fitted.y, fitted.width, fitted.height), (0, 0, 1920, 1080));
+    }
+
+    #[test]
+    fn test_pip_shape_serialization() {
+        assert_eq!(serde_json::to_string(&PipShape::RoundedRect).unwrap(), "\"roundedRect\"");
+        let shape: PipShape = serde_json::from_str("\"circle\"").unwrap();
+        assert_eq!(shape, PipShape::Circle);
+        assert_eq!(PipShape::default(), PipShape::Rectangle);
+    }
+
+    #[test]
+    fn test_pip_composition_mixes_system_audio_and_microphone() {
+        let args = build_pip_composition_args(
+            Path::new("/tmp/screen.mp4"),
+            Path::new("/tmp/webcam.mp4"),
+            PipAudioInputs {
+                system_audio_pcm: Some(Path::new("/tmp/system.pcm")),
+                microphone_wav: Some(Path::new("/tmp/mic.wav")),
+            },
+            "[1:v]scale=480:270[pip];[0:v][pip]overlay=10:10",
+            PipEncoding::default(),
+            Path::new("/tmp/out.mp4"),
+        );
+
+        let inputs: Vec<&str> = args
+            .windows(2)
+            .filter(|w| w[0] == "-i")
+            .map(|w| w[1].as_str())
+            .collect();
+        assert_eq!(inputs, ["/tmp/screen.mp4", "/tmp/webcam.mp4", "/tmp/system.pcm", "/tmp/mic.wav"]);
+
+        let filter = &args[args.iter().position(|a| a == "-filter_complex").unwrap() + 1];
+        assert!(filter.contains("[2:a]aformat=channel_layouts=stereo[a0]"));
+        assert!(filter.contains("[3:a]aformat=channel_layouts=stereo[a1]"));
+        assert!(filter.contains("[a0][a1]amix=inputs=2"));
+        assert!(args.windows(2).any(|w| w == ["-map", "[aout]"]));
+        assert!(!args.contains(&"-an".to_string()));
+    }
+
+    #[test]
+    fn test_composition_progress_from_ffmpeg_output() {
+        let sample = "frame=150\nfps=75.00\nout_time_us=5000000\nout_time_ms=5000000\nout_time=00:00:05.000000\nprogress=continue\n";
+        let times: Vec<u64> = sample.lines().filter_map(parse_progress_out_time_ms).collect();
+        assert_eq!(times, vec![5_000]);
+
+        // 5s of a 20s recording
+        let progress = CompositionProgress::from_out_time(times[0], Some(20_000));
+        assert_eq!(progress, CompositionProgress { percentage: 25.0, indeterminate: false });
+
+        // Audio can run slightly past the video; never report over 100
+        assert_eq!(CompositionProgress::from_out_time(21_000, Some(20_000)).percentage, 100.0);
+
+        // Unknown duration and FFmpeg's startup N/A
+        assert!(CompositionProgress::from_out_time(5_000, None).indeterminate);
+        assert_eq!(parse_progress_out_time_ms("out_time_us=N/A"), None);
+    }
+
+    #[test]
+    fn test_pip_composition_maps_single_audio_source_directly() {
+        let mic_only = build_pip_composition_args(
+            Path::new("/tmp/screen.mp4"),
+            Path::new("/tmp/webcam.mp4"),
+            PipAudioInputs {
+                system_audio_pcm: None,
+                microphone_wav: Some(Path::new("/tmp/mic.wav")),
+            },
+            "[0:v][1:v]overlay=0:0",
+            PipEncoding::default(),
+            Path::new("/tmp/out.mp4"),
+        );
+        assert!(mic_only.windows(2).any(|w| w == ["-map", "2:a"]));
+        assert!(!mic_only.iter().any(|a| a.contains("amix")));
+
+        let silent = build_pip_composition_args(
+            Path::new("/tmp/screen.mp4"),
+            Path::new("/tmp/webcam.mp4"),
+            PipAudioInputs::default(),
+            "[0:v][1:v]overlay=0:0",
+            PipEncoding::default(),
+            Path::new("/tmp/out.mp4"),
+        );
+        assert!(silent.contains(&"-an".to_string()));
+    }
+
+    #[test]
+    fn test_pip_composition_uses_configured_crf_and_preset() {
+        let default_args = build_pip_composition_args(
+            Path::new("/tmp/screen.mp4"),
+            Path::new("/tmp/webcam.mp4"),
+            PipAudioInputs::default(),
+            "[0:v][1:v]overlay=0:0",
+            PipEncoding::default(),
+            Path::new("/tmp/out.mp4"),
+        );
+        assert!(default_args.windows(2).any(|w| w == ["-preset", "ultrafast"]));
+        assert!(default_args.windows(2).any(|w| w == ["-crf", "23"]));
+
+        let encoding = PipEncoding::new(Some(18), Some(Preset::Slow)).unwrap();
+        let args = build_pip_composition_args(
+            Path::new("/tmp/screen.mp4"),
+            Path::new("/tmp/webcam.mp4"),
+            PipAudioInputs::default(),
+            "[0:v][1:v]overlay=0:0",
+            encoding,
+            Path::new("/tmp/out.mp4"),
+        );
+        assert!(args.windows(2).any(|w| w == ["-preset", "slow"]));
+        assert!(args.windows(2).any(|w| w == ["-crf", "18"]));
+
+        assert!(PipEncoding::new(Some(60), None).is_err());
+        assert_eq!(PipEncoding::new(None, None).unwrap(), PipEncoding::default());
+    }
+
 }
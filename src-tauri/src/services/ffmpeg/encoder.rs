@@ -1,9 +1,15 @@
+use crate::models::export::validate_crf;
+use crate::models::{EncoderBackend, Preset, VideoCodec};
+use crate::services::audio_capture::PcmMeta;
+use crate::services::ffmpeg::formats::{ensure_encoder_available, get_available_encoders};
 use anyhow::{Context, Result};
 use ffmpeg_sidecar::command::FfmpegCommand;
 use ffmpeg_sidecar::child::FfmpegChild;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::process::ChildStdin;
+use std::process::{ChildStderr, ChildStdin};
 use tokio::sync::Mutex;
 use std::io::Write;
 
@@ -45,6 +51,227 @@ pub struct FFmpegEncoder {
 
     /// Frame rate (frames per second)
     fps: u32,
+
+    /// Encoder backend; `None` picks one with `preferred_backend` when encoding starts
+    backend: Option<EncoderBackend>,
+
+    /// VideoToolbox quality (`-q:v`, 1-100, higher = better quality)
+    hardware_quality: u8,
+
+    /// FFmpeg encode threads (`-threads`)
+    threads: u32,
+
+    /// Video codec (H.264 unless changed with `set_video_codec`)
+    video_codec: VideoCodec,
+
+    /// Software encoder quality (`-crf`, 0-51, lower = better quality)
+    crf: u8,
+
+    /// Software encoder speed preset (`-preset`)
+    preset: Preset,
+
+    /// Last lines of FFmpeg stderr, reported if the process dies
+    stderr_tail: StderrTail,
+}
+
+/// Lines of FFmpeg stderr kept for crash reports
+const STDERR_TAIL_LINES: usize = 20;
+
+/// How long to wait for stderr to drain after FFmpeg exits
+const STDERR_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Most recent FFmpeg stderr lines, collected by a reader task
+#[derive(Debug, Clone, Default)]
+struct StderrTail {
+    lines: Arc<std::sync::Mutex<VecDeque<String>>>,
+    closed: Arc<AtomicBool>,
+}
+
+impl StderrTail {
+    /// Read `stderr` on a blocking task until FFmpeg closes it
+    fn spawn_reader(&self, stderr: ChildStderr) {
+        let tail = self.clone();
+        tokio::task::spawn_blocking(move || {
+            use std::io::{BufRead, BufReader};
+            for line in BufReader::new(stderr).lines().map_while(|line| line.ok()) {
+                tracing::debug!(event = "ffmpeg_encoder_stderr", line = %line);
+                let mut lines = tail.lines.lock().unwrap();
+                if lines.len() == STDERR_TAIL_LINES {
+                    lines.pop_front();
+                }
+                lines.push_back(line);
+            }
+            tail.closed.store(true, Ordering::Relaxed);
+        });
+    }
+
+    /// Wait (up to `timeout`) for the reader to hit end of stream
+    async fn wait_closed(&self, timeout: std::time::Duration) {
+        let deadline = std::time::Instant::now() + timeout;
+        while !self.closed.load(Ordering::Relaxed) && std::time::Instant::now() < deadline {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    }
+
+    /// Collected lines, oldest first
+    fn text(&self) -> String {
+        self.lines.lock().unwrap().iter().map(String::as_str).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Default VideoToolbox quality for recordings
+///
+/// VideoToolbox's own default is noticeably softer than libx264 at CRF 23,
+/// so recordings start from a higher quality level.
+pub const DEFAULT_VIDEOTOOLBOX_QUALITY: u8 = 65;
+
+/// Valid range for VideoToolbox `-q:v`
+pub const VIDEOTOOLBOX_QUALITY_RANGE: std::ops::RangeInclusive<u8> = 1..=100;
+
+/// Whether VideoToolbox honors constant quality (`-q:v`)
+///
+/// Intel Macs' encoder ignores `-q:v` and falls back to a low default bitrate,
+/// so a target bitrate derived from the quality is passed there instead.
+const VIDEOTOOLBOX_CONSTANT_QUALITY: bool = !cfg!(all(target_os = "macos", target_arch = "x86_64"));
+
+/// Bits per pixel per frame for VideoToolbox target bitrates at quality 1 and 100
+const VIDEOTOOLBOX_BITS_PER_PIXEL: (f64, f64) = (0.01, 0.2);
+
+/// Backend to use when none was chosen: VideoToolbox on macOS if FFmpeg has its encoder for `codec`
+pub fn preferred_backend(encoders: &HashSet<String>, codec: VideoCodec) -> EncoderBackend {
+    if cfg!(target_os = "macos") && encoders.contains(codec.videotoolbox_codec()) {
+        EncoderBackend::VideoToolbox
+    } else {
+        EncoderBackend::Software
+    }
+}
+
+/// VideoToolbox rate control arguments for `quality` (1-100)
+///
+/// `-q:v` where constant quality is supported, otherwise a `-b:v` target
+/// scaled by resolution and frame rate.
+pub fn videotoolbox_rate_args(quality: u8, width: u32, height: u32, fps: u32, constant_quality: bool) -> Vec<String> {
+    if constant_quality {
+        return vec!["-q:v".to_string(), quality.to_string()];
+    }
+
+    let (min_bpp, max_bpp) = VIDEOTOOLBOX_BITS_PER_PIXEL;
+    let position = (quality.clamp(1, 100) - 1) as f64 / 99.0;
+    let bits_per_pixel = min_bpp + position * (max_bpp - min_bpp);
+    let kbps = (width as f64 * height as f64 * fps as f64 * bits_per_pixel / 1000.0).round() as u64;
+    vec!["-b:v".to_string(), format!("{}k", kbps.max(1))]
+}
+
+lazy_static::lazy_static! {
+    /// Hardware probe results by encoder and (even) frame size, kept for the process lifetime
+    static ref HARDWARE_PROBES: Mutex<HashMap<(String, u32, u32), std::result::Result<(), String>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Check that a VideoToolbox session can be created for `encoder` at this size
+///
+/// The encoder can be listed by `ffmpeg -encoders` and still fail to open
+/// (e.g. in a VM without a media engine, or at sizes the hardware rejects).
+/// The probe encode runs once per encoder and size; later recordings reuse its result.
+async fn probe_hardware_encoder(encoder: &str, width: u32, height: u32) -> Result<()> {
+    let (width, height) = even_dimensions(width, height);
+    let mut probes = HARDWARE_PROBES.lock().await;
+    let key = (encoder.to_string(), width, height);
+    if let Some(result) = probes.get(&key) {
+        return result.clone().map_err(|e| anyhow::anyhow!(e));
+    }
+
+    let result = probe_encode(encoder, width, height).await;
+    probes.insert(key, result.as_ref().map(|_| ()).map_err(|e| format!("{:#}", e)));
+    result
+}
+
+/// Encode one black frame with `encoder`
+async fn probe_encode(encoder: &str, width: u32, height: u32) -> Result<()> {
+    let output = tokio::process::Command::new(ffmpeg_sidecar::paths::ffmpeg_path())
+        .args(["-v", "error", "-f", "lavfi", "-i"])
+        .arg(format!("color=c=black:s={}x{}:r=1", width, height))
+        .args(["-frames:v", "1", "-pix_fmt", "yuv420p", "-c:v", encoder, "-f", "null", "-"])
+        .output()
+        .await
+        .context("Failed to execute ffmpeg")?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "{} failed to initialize: {}",
+            encoder,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Where an encode runs, which decides its default thread count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeContext {
+    /// Live capture: FFmpeg shares the CPU with capture and the UI
+    Recording,
+    /// Offline export: FFmpeg may use the whole machine
+    Export,
+}
+
+/// Logical CPU cores available to this process (at least 1)
+pub fn available_cores() -> u32 {
+    std::thread::available_parallelism()
+        .map(|cores| cores.get() as u32)
+        .unwrap_or(1)
+}
+
+/// Default `-threads` for `context` on a machine with `cores` cores
+///
+/// Recording reserves one core so FFmpeg can't starve capture or the UI;
+/// exports use every core.
+pub fn default_encode_threads(context: EncodeContext, cores: u32) -> u32 {
+    let cores = cores.max(1);
+    match context {
+        EncodeContext::Recording => (cores - 1).max(1),
+        EncodeContext::Export => cores,
+    }
+}
+
+/// Resolve a requested thread count, falling back to the context default
+///
+/// # Returns
+/// * `Err(anyhow::Error)` - `requested` is 0 or more than `cores`
+pub fn resolve_encode_threads(requested: Option<u32>, context: EncodeContext, cores: u32) -> Result<u32> {
+    match requested {
+        None => Ok(default_encode_threads(context, cores)),
+        Some(threads) if threads == 0 || threads > cores.max(1) => Err(anyhow::anyhow!(
+            "Encode threads must be between 1 and {} (available cores), got {}",
+            cores.max(1),
+            threads
+        )),
+        Some(threads) => Ok(threads),
+    }
+}
+
+/// Round dimensions down to even numbers (minimum 2x2)
+///
+/// libx264 with yuv420p requires even width and height; odd sizes fail encoding.
+pub fn even_dimensions(width: u32, height: u32) -> (u32, u32) {
+    ((width & !1).max(2), (height & !1).max(2))
+}
+
+/// Scale a source size to `target_height`, preserving aspect ratio, with even output dimensions
+pub fn scale_to_height(source_width: u32, source_height: u32, target_height: u32) -> (u32, u32) {
+    let aspect_ratio = source_width as f64 / source_height.max(1) as f64;
+    let width = (target_height as f64 * aspect_ratio).round() as u32;
+    even_dimensions(width, target_height)
+}
+
+/// Default speed preset that keeps up with real-time capture
+///
+/// x265 is several times slower than x264, so only ultrafast keeps up.
+fn realtime_preset(codec: VideoCodec) -> Preset {
+    match codec {
+        VideoCodec::H264 => Preset::Fast,
+        VideoCodec::H265 => Preset::Ultrafast,
+    }
 }
 
 impl FFmpegEncoder {
@@ -86,58 +313,213 @@ impl FFmpegEncoder {
             width,
             height,
             fps,
+            backend: None,
+            hardware_quality: DEFAULT_VIDEOTOOLBOX_QUALITY,
+            threads: default_encode_threads(EncodeContext::Recording, available_cores()),
+            video_codec: VideoCodec::default(),
+            crf: VideoCodec::default().default_crf(),
+            preset: realtime_preset(VideoCodec::default()),
+            stderr_tail: StderrTail::default(),
         })
     }
 
+    /// Choose the encoder backend instead of picking one when encoding starts
+    ///
+    /// VideoToolbox still falls back to software if it fails to initialize.
+    pub fn set_backend(&mut self, backend: EncoderBackend) {
+        self.backend = Some(backend);
+    }
+
+    /// Encoder backend (software until one is chosen or encoding starts)
+    pub fn backend(&self) -> EncoderBackend {
+        self.backend.unwrap_or(EncoderBackend::Software)
+    }
+
+    /// Set the VideoToolbox quality used in hardware mode
+    ///
+    /// # Returns
+    /// * `Err(anyhow::Error)` - Quality outside 1-100
+    pub fn set_hardware_quality(&mut self, quality: u8) -> Result<()> {
+        if !VIDEOTOOLBOX_QUALITY_RANGE.contains(&quality) {
+            return Err(anyhow::anyhow!(
+                "VideoToolbox quality must be between {} and {}, got {}",
+                VIDEOTOOLBOX_QUALITY_RANGE.start(),
+                VIDEOTOOLBOX_QUALITY_RANGE.end(),
+                quality
+            ));
+        }
+
+        self.hardware_quality = quality;
+        Ok(())
+    }
+
+    /// Cap FFmpeg's encode threads (defaults to all cores but one)
+    ///
+    /// # Returns
+    /// * `Err(anyhow::Error)` - `threads` is 0 or exceeds the available cores
+    pub fn set_threads(&mut self, threads: u32) -> Result<()> {
+        self.threads = resolve_encode_threads(Some(threads), EncodeContext::Recording, available_cores())?;
+        Ok(())
+    }
+
+    /// Encode with `codec` instead of H.264
+    ///
+    /// Availability is checked when encoding starts. Resets CRF and preset to
+    /// the codec's real-time defaults, so call `set_crf`/`set_preset` after this.
+    pub fn set_video_codec(&mut self, codec: VideoCodec) {
+        self.video_codec = codec;
+        self.crf = codec.default_crf();
+        self.preset = realtime_preset(codec);
+    }
+
+    /// Set the software encoder quality (`-crf`)
+    ///
+    /// # Returns
+    /// * `Err(anyhow::Error)` - CRF outside 0-51
+    pub fn set_crf(&mut self, crf: u8) -> Result<()> {
+        self.crf = validate_crf(crf).map_err(|e| anyhow::anyhow!(e))?;
+        Ok(())
+    }
+
+    /// Set the software encoder speed preset (`-preset`)
+    ///
+    /// Slower presets than the default may not keep up with real-time capture.
+    pub fn set_preset(&mut self, preset: Preset) {
+        self.preset = preset;
+    }
+
+    /// FFmpeg encoder name for the current codec and backend
+    fn video_encoder(&self) -> &'static str {
+        self.backend().encoder_name(self.video_codec)
+    }
+
+    /// Pick a backend if none was chosen, and fall back to software if VideoToolbox can't start
+    async fn resolve_backend(&mut self) {
+        let backend = match self.backend {
+            Some(backend) => backend,
+            None => match get_available_encoders().await {
+                Ok(encoders) => preferred_backend(&encoders, self.video_codec),
+                Err(e) => {
+                    tracing::warn!(
+                        event = "encoder_probe_failed",
+                        error = %e,
+                        "Could not list FFmpeg encoders, using software encoding"
+                    );
+                    EncoderBackend::Software
+                }
+            },
+        };
+        self.backend = Some(backend);
+
+        if backend == EncoderBackend::VideoToolbox {
+            if let Err(e) = probe_hardware_encoder(self.video_encoder(), self.width, self.height).await {
+                tracing::warn!(
+                    event = "videotoolbox_fallback",
+                    error = %e,
+                    "VideoToolbox failed to initialize, falling back to software encoding"
+                );
+                self.backend = Some(EncoderBackend::Software);
+            }
+        }
+    }
+
+    /// Build the FFmpeg argument list for real-time encoding
+    fn build_encoding_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = vec![
+            // No progress lines on stderr; it is kept for crash reports
+            "-nostats".into(),
+
+            // Input format: raw video from stdin
+            "-f".into(), "rawvideo".into(),
+            "-pix_fmt".into(), "bgra".into(),  // BGRA format from ScreenCaptureKit
+            "-s".into(), format!("{}x{}", self.width, self.height),
+            "-r".into(), self.fps.to_string(),
+            "-i".into(), "pipe:0".into(),  // Read from stdin
+        ];
+
+        // Odd input sizes (e.g. window capture) are cropped by one pixel to stay encodable
+        let (even_width, even_height) = even_dimensions(self.width, self.height);
+        if (even_width, even_height) != (self.width, self.height) {
+            args.extend([
+                "-vf".to_string(),
+                format!("crop={}:{}:0:0", even_width, even_height),
+            ]);
+        }
+
+        if self.backend() == EncoderBackend::VideoToolbox {
+            // VideoToolbox ignores CRF; quality is controlled via -q:v (or -b:v on Intel)
+            args.extend(["-c:v".to_string(), self.video_encoder().to_string()]);
+            args.extend(videotoolbox_rate_args(
+                self.hardware_quality,
+                self.width,
+                self.height,
+                self.fps,
+                VIDEOTOOLBOX_CONSTANT_QUALITY,
+            ));
+        } else {
+            args.extend([
+                "-c:v".to_string(), self.video_encoder().to_string(),
+                "-preset".to_string(), self.preset.name().to_string(),
+                "-crf".to_string(), self.crf.to_string(),  // Constant Rate Factor for quality
+            ]);
+        }
+
+        // hvc1 tag so QuickTime plays HEVC
+        if let Some(tag) = self.video_codec.mp4_tag() {
+            args.extend(["-tag:v".to_string(), tag.to_string()]);
+        }
+
+        args.extend([
+            "-threads".to_string(), self.threads.to_string(),
+            "-pix_fmt".to_string(), "yuv420p".to_string(),  // Standard pixel format for maximum compatibility
+
+            // Output format
+            "-f".to_string(), "mp4".to_string(),
+            "-movflags".to_string(), "faststart".to_string(),  // Move moov atom to beginning for QuickTime compatibility
+            "-y".to_string(),  // Overwrite output file
+            self.output_path.to_string_lossy().to_string(),
+        ]);
+
+        args
+    }
+
     /// Start the FFmpeg encoding process
     ///
     /// Spawns FFmpeg with H.264 codec configured for real-time encoding.
     /// FFmpeg reads raw BGRA frames from stdin pipe.
     ///
     /// Configuration:
-    /// - Codec: H.264 (libx264)
-    /// - Preset: fast (optimized for real-time encoding)
-    /// - CRF: 23 (quality level, lower = better quality)
+    /// - Codec: H.264 (libx264, or h264_videotoolbox in hardware mode), or H.265 if set
+    /// - Backend: VideoToolbox on macOS when available unless set, software if it fails to initialize
+    /// - Preset: fast by default (optimized for real-time encoding)
+    /// - CRF: 23 by default (quality level, lower = better quality); `-q:v`/`-b:v` in hardware mode
     /// - Format: MP4 with BGRA input
     ///
     /// # Returns
     /// * `Ok(())` - FFmpeg process started successfully
-    /// * `Err(anyhow::Error)` - Failed to start encoding
+    /// * `Err(anyhow::Error)` - Failed to start encoding, or the codec is missing from FFmpeg
     pub async fn start_encoding(&mut self) -> Result<()> {
+        self.resolve_backend().await;
+
         tracing::info!(
             event = "encoding_start",
             output_path = %self.output_path.display(),
+            backend = ?self.backend(),
+            codec = self.video_encoder(),
             "Starting FFmpeg encoding process"
         );
 
+        // H.264 ships with every build; other encoders may be missing
+        if self.video_codec != VideoCodec::H264 {
+            ensure_encoder_available(self.video_encoder()).await?;
+        }
+
         // Build FFmpeg command for real-time H.264 encoding
         let mut command = FfmpegCommand::new();
-
-        command
-            // Input format: raw video from stdin
-            .arg("-f").arg("rawvideo")
-            .arg("-pix_fmt").arg("bgra")  // BGRA format from ScreenCaptureKit
-            .arg("-s").arg(format!("{}x{}", self.width, self.height))
-            .arg("-r").arg(self.fps.to_string())
-            .arg("-i").arg("pipe:0")  // Read from stdin
-
-            // H.264 encoding with real-time optimizations
-            .arg("-c:v").arg("libx264")
-            .arg("-preset").arg("fast")  // Fast preset for real-time encoding
-            .arg("-crf").arg("23")  // Constant Rate Factor for quality
-            .arg("-pix_fmt").arg("yuv420p")  // Standard pixel format for maximum compatibility
-
-            // macOS VideoToolbox hardware acceleration (if available)
-            // Note: libx264 will automatically use VideoToolbox on macOS
-
-            // Output format
-            .arg("-f").arg("mp4")
-            .arg("-movflags").arg("faststart")  // Move moov atom to beginning for QuickTime compatibility
-            .arg("-y")  // Overwrite output file
-            .arg(&self.output_path);
+        command.args(self.build_encoding_args());
 
         // Spawn the FFmpeg process
-        let mut child = command
+        let child = command
             .spawn()
             .context("Failed to spawn FFmpeg process. Ensure FFmpeg is installed.")?;
 
@@ -146,10 +528,19 @@ impl FFmpegEncoder {
             "FFmpeg process spawned successfully"
         );
 
+        self.attach_process(child).await
+    }
+
+    /// Take over a spawned FFmpeg process: stdin for frames, stderr for crash reports
+    async fn attach_process(&self, mut child: FfmpegChild) -> Result<()> {
         // Take ownership of stdin for frame writing
         let stdin = child.take_stdin()
             .ok_or_else(|| anyhow::anyhow!("Failed to take FFmpeg stdin"))?;
 
+        if let Some(stderr) = child.take_stderr() {
+            self.stderr_tail.spawn_reader(stderr);
+        }
+
         // Store stdin and process handles
         let mut stdin_lock = self.stdin.lock().await;
         *stdin_lock = Some(stdin);
@@ -160,6 +551,48 @@ impl FFmpegEncoder {
         Ok(())
     }
 
+    /// Check that FFmpeg is still running
+    ///
+    /// Called by the encode loop so a crashed FFmpeg (e.g. killed for running out
+    /// of memory) is reported with its own last words rather than a broken pipe.
+    ///
+    /// # Returns
+    /// * `Ok(())` - FFmpeg is running (or encoding hasn't started)
+    /// * `Err(anyhow::Error)` - FFmpeg exited; the error carries its exit status and stderr tail
+    pub async fn check_process_health(&self) -> Result<()> {
+        let mut process_lock = self.process.lock().await;
+        let Some(child) = process_lock.as_mut() else {
+            return Ok(());
+        };
+
+        let status = match child.as_inner_mut().try_wait() {
+            Ok(Some(status)) => status,
+            Ok(None) => return Ok(()),
+            Err(e) => return Err(anyhow::anyhow!("Failed to poll FFmpeg process: {}", e)),
+        };
+
+        // The process is gone; drop its handles so nothing writes to it again
+        process_lock.take();
+        drop(process_lock);
+        self.stdin.lock().await.take();
+
+        self.stderr_tail.wait_closed(STDERR_DRAIN_TIMEOUT).await;
+        let stderr = self.stderr_tail.text();
+
+        tracing::error!(
+            event = "ffmpeg_crashed",
+            status = %status,
+            stderr = %stderr,
+            "FFmpeg exited during encoding"
+        );
+
+        if stderr.is_empty() {
+            Err(anyhow::anyhow!("FFmpeg exited unexpectedly ({})", status))
+        } else {
+            Err(anyhow::anyhow!("FFmpeg exited unexpectedly ({}): {}", status, stderr))
+        }
+    }
+
     /// Write a single frame to the FFmpeg stdin pipe
     ///
     /// Streams raw BGRA frame data to FFmpeg for real-time encoding.
@@ -189,16 +622,24 @@ impl FFmpegEncoder {
             ));
         }
 
-        // Get mutable reference to stdin
-        let mut stdin_lock = self.stdin.lock().await;
-        let stdin = stdin_lock
-            .as_mut()
-            .ok_or_else(|| anyhow::anyhow!("FFmpeg stdin not available"))?;
-
         // Write frame data to stdin (blocks if pipe buffer is full)
-        stdin
-            .write_all(&frame.data)
-            .context("Failed to write frame to FFmpeg stdin")?;
+        let write_result = {
+            let mut stdin_lock = self.stdin.lock().await;
+            let stdin = stdin_lock
+                .as_mut()
+                .ok_or_else(|| anyhow::anyhow!("FFmpeg stdin not available"))?;
+            stdin.write_all(&frame.data)
+        };
+
+        if let Err(e) = write_result {
+            // A broken pipe usually means FFmpeg died; give it a moment to be reaped
+            // so the error explains why
+            for _ in 0..10 {
+                self.check_process_health().await?;
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            }
+            return Err(anyhow::Error::new(e).context("Failed to write frame to FFmpeg stdin"));
+        }
 
         tracing::trace!(
             event = "frame_written",
@@ -332,6 +773,7 @@ impl FFmpegEncoder {
     /// * `video_path` - Path to the encoded video file (from stop_encoding)
     /// * `audio_inputs` - Vector of audio input configurations (1-3 tracks)
     /// * `output_path` - Final output path with all tracks muxed
+    /// * `normalize_loudness` - Normalize the mixed audio to EBU R128 with `loudnorm`
     ///
     /// # Returns
     /// * `Ok(())` - Audio muxing completed successfully
@@ -346,6 +788,7 @@ impl FFmpegEncoder {
         video_path: PathBuf,
         audio_inputs: Vec<AudioInputConfig>,
         output_path: PathBuf,
+        normalize_loudness: bool,
     ) -> Result<()> {
         if audio_inputs.is_empty() {
             return Err(anyhow::anyhow!("At least one audio input required"));
@@ -375,6 +818,12 @@ impl FFmpegEncoder {
             ));
         }
 
+        // The writer's sidecar is authoritative over the caller's expected format
+        let audio_inputs: Vec<AudioInputConfig> = audio_inputs
+            .into_iter()
+            .map(AudioInputConfig::with_recorded_format)
+            .collect();
+
         // Verify all audio files exist
         for (idx, audio_input) in audio_inputs.iter().enumerate() {
             if !audio_input.pcm_path.exists() {
@@ -394,35 +843,14 @@ impl FFmpegEncoder {
 
         // Inputs 1-N: Audio PCM files
         for audio_input in &audio_inputs {
-            command
-                .arg("-f")
-                .arg("s16le") // PCM signed 16-bit little-endian
-                .arg("-ar")
-                .arg(audio_input.sample_rate.to_string())
-                .arg("-ac")
-                .arg(audio_input.channels.to_string())
-                .arg("-i")
-                .arg(&audio_input.pcm_path);
+            command.args(audio_input.input_args());
         }
 
         // Map video stream from input 0
         command.arg("-map").arg("0:v");
 
-        // Build audio filter to mix all audio inputs together
-        // This ensures the user hears all audio sources (system + microphone) simultaneously
-        let mut filter_complex = String::new();
-        for i in 0..audio_inputs.len() {
-            // Convert each input to stereo if needed and label it
-            filter_complex.push_str(&format!("[{}:a]aformat=channel_layouts=stereo[a{}];", i + 1, i));
-        }
-
-        // Mix all audio streams together
-        for i in 0..audio_inputs.len() {
-            filter_complex.push_str(&format!("[a{}]", i));
-        }
-        // Use amix with normalize=0 to prevent automatic volume reduction, then boost output
-        filter_complex.push_str(&format!("amix=inputs={}:duration=longest:dropout_transition=2:normalize=0,volume=2.0[aout]", audio_inputs.len()));
-
+        // Mix all audio inputs together so the user hears every source simultaneously
+        let filter_complex = build_audio_mux_filter(audio_inputs.len(), normalize_loudness);
         command.arg("-filter_complex").arg(&filter_complex);
         command.arg("-map").arg("[aout]");
 
@@ -474,6 +902,37 @@ impl FFmpegEncoder {
     }
 }
 
+/// EBU R128 loudness target applied to the mixed audio (-16 LUFS, -1.5 dBTP)
+pub const LOUDNORM_FILTER: &str = "loudnorm=I=-16:TP=-1.5:LRA=11";
+
+/// Build the `-filter_complex` graph mixing `input_count` audio inputs into `[aout]`
+///
+/// Audio inputs start at FFmpeg input 1 (input 0 is the video). With
+/// `normalize_loudness` the sum of all inputs is normalized, not each input,
+/// so the levels between sources are preserved.
+pub fn build_audio_mux_filter(input_count: usize, normalize_loudness: bool) -> String {
+    let mut filter_complex = String::new();
+    for i in 0..input_count {
+        // Convert each input to stereo if needed and label it
+        filter_complex.push_str(&format!("[{}:a]aformat=channel_layouts=stereo[a{}];", i + 1, i));
+    }
+
+    for i in 0..input_count {
+        filter_complex.push_str(&format!("[a{}]", i));
+    }
+    // Use amix with normalize=0 to prevent automatic volume reduction
+    filter_complex.push_str(&format!("amix=inputs={}:duration=longest:dropout_transition=2:normalize=0", input_count));
+
+    if normalize_loudness {
+        // loudnorm sets the level itself and upsamples to 192kHz; resample back for AAC
+        filter_complex.push_str(&format!(",{},aresample=48000[aout]", LOUDNORM_FILTER));
+    } else {
+        // Boost the unnormalized mix
+        filter_complex.push_str(",volume=2.0[aout]");
+    }
+    filter_complex
+}
+
 /// Configuration for audio input during muxing (Story 2.4, Story 4.7)
 #[derive(Debug, Clone)]
 pub struct AudioInputConfig {
@@ -490,6 +949,55 @@ pub struct AudioInputConfig {
     pub label: String,
 }
 
+impl AudioInputConfig {
+    /// Replace `sample_rate`/`channels` with the format in the PCM file's `PcmMeta` sidecar
+    ///
+    /// Files without a sidecar keep the configured format.
+    pub fn with_recorded_format(mut self) -> Self {
+        match PcmMeta::read(&self.pcm_path) {
+            Ok(meta) => {
+                if (meta.sample_rate, meta.channels) != (self.sample_rate, self.channels) {
+                    tracing::warn!(
+                        event = "audio_input_format_corrected",
+                        label = %self.label,
+                        expected_sample_rate = self.sample_rate,
+                        expected_channels = self.channels,
+                        sample_rate = meta.sample_rate,
+                        channels = meta.channels,
+                        "Using recorded PCM format instead of the expected one"
+                    );
+                }
+                self.sample_rate = meta.sample_rate;
+                self.channels = meta.channels;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                tracing::warn!(
+                    event = "audio_input_meta_unreadable",
+                    label = %self.label,
+                    error = %e,
+                    "Failed to read PCM metadata, using the expected format"
+                );
+            }
+        }
+        self
+    }
+
+    /// FFmpeg input arguments for this raw s16le file
+    pub fn input_args(&self) -> Vec<String> {
+        vec![
+            "-f".to_string(),
+            "s16le".to_string(), // PCM signed 16-bit little-endian
+            "-ar".to_string(),
+            self.sample_rate.to_string(),
+            "-ac".to_string(),
+            self.channels.to_string(),
+            "-i".to_string(),
+            self.pcm_path.to_string_lossy().to_string(),
+        ]
+    }
+}
+
 impl Drop for FFmpegEncoder {
     fn drop(&mut self) {
         // Try to clean up FFmpeg process if still running
@@ -639,4 +1147,269 @@ mod tests {
         encoder.kill().await.ok();
         std::fs::remove_file(&output_path).ok();
     }
+
+    #[test]
+    fn test_hardware_quality_arg_emitted() {
+        let output_path = std::env::temp_dir().join("test_vt_quality.mp4");
+        let mut encoder = FFmpegEncoder::new(output_path, 1920, 1080, 30).unwrap();
+
+        // Software mode uses CRF, no -q:v
+        let args = encoder.build_encoding_args();
+        assert!(args.contains(&"libx264".to_string()));
+        assert!(!args.contains(&"-q:v".to_string()));
+
+        encoder.set_backend(EncoderBackend::VideoToolbox);
+        encoder.set_hardware_quality(80).unwrap();
+        let args = encoder.build_encoding_args();
+        let q_idx = args.iter().position(|a| a == "-q:v").expect("-q:v missing");
+        assert_eq!(args[q_idx + 1], "80");
+        assert!(args.contains(&"h264_videotoolbox".to_string()));
+        assert!(!args.contains(&"-crf".to_string()));
+    }
+
+    #[test]
+    fn test_videotoolbox_backend_emits_h264_videotoolbox() {
+        let output_path = std::env::temp_dir().join("test_vt_backend.mp4");
+        let mut encoder = FFmpegEncoder::new(output_path, 1920, 1080, 30).unwrap();
+
+        encoder.set_backend(EncoderBackend::VideoToolbox);
+        let args = encoder.build_encoding_args();
+        assert!(args.windows(2).any(|w| w == ["-c:v", "h264_videotoolbox"]));
+        assert!(args.contains(&"-q:v".to_string()) || args.contains(&"-b:v".to_string()));
+        assert!(!args.contains(&"-crf".to_string()));
+
+        encoder.set_backend(EncoderBackend::Software);
+        assert!(encoder.build_encoding_args().windows(2).any(|w| w == ["-c:v", "libx264"]));
+
+        // Intel VideoToolbox ignores -q:v, so quality becomes a bitrate that grows with it
+        assert_eq!(videotoolbox_rate_args(65, 1920, 1080, 30, true), ["-q:v", "65"]);
+        let kbps = |quality| {
+            let args = videotoolbox_rate_args(quality, 1920, 1080, 30, false);
+            assert_eq!(args[0], "-b:v");
+            args[1].trim_end_matches('k').parse::<u64>().unwrap()
+        };
+        assert!(kbps(1) < kbps(65) && kbps(65) < kbps(100));
+        assert!((5_000..=12_000).contains(&kbps(65)), "1080p30 at quality 65: {}k", kbps(65));
+    }
+
+    #[test]
+    fn test_preferred_backend_requires_videotoolbox_encoder() {
+        let encoders: HashSet<String> = ["libx264", "h264_videotoolbox"].iter().map(|s| s.to_string()).collect();
+        let expected = if cfg!(target_os = "macos") { EncoderBackend::VideoToolbox } else { EncoderBackend::Software };
+        assert_eq!(preferred_backend(&encoders, VideoCodec::H264), expected);
+
+        // No hevc_videotoolbox in this build
+        assert_eq!(preferred_backend(&encoders, VideoCodec::H265), EncoderBackend::Software);
+        assert_eq!(preferred_backend(&HashSet::new(), VideoCodec::H264), EncoderBackend::Software);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_health_check_reports_crashed_process_stderr() {
+        use std::process::{Command, Stdio};
+
+        let output_path = std::env::temp_dir().join("test_encoder_crash.mp4");
+        let encoder = FFmpegEncoder::new(output_path, 640, 480, 30).unwrap();
+
+        // Stand-in for FFmpeg: complains on stderr, then idles until killed
+        let child = Command::new("sh")
+            .args(["-c", "echo 'x264 [error]: malloc of size 8294400 failed' >&2; exec sleep 30"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to spawn sh");
+        encoder.attach_process(FfmpegChild::from_inner(child)).await.unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert!(encoder.check_process_health().await.is_ok());
+
+        encoder.process.lock().await.as_mut().unwrap().kill().unwrap();
+
+        let mut result = Ok(());
+        for _ in 0..100 {
+            result = encoder.check_process_health().await;
+            if result.is_err() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        let err = result.expect_err("Killed process should fail the health check").to_string();
+        assert!(err.contains("FFmpeg exited unexpectedly"), "{}", err);
+        assert!(err.contains("malloc of size 8294400 failed"), "{}", err);
+        assert!(!encoder.is_encoding().await);
+    }
+
+    #[test]
+    fn test_h265_encoder_args() {
+        let output_path = std::env::temp_dir().join("test_h265.mp4");
+        let mut encoder = FFmpegEncoder::new(output_path, 3840, 2160, 30).unwrap();
+        encoder.set_video_codec(VideoCodec::H265);
+
+        let args = encoder.build_encoding_args();
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx265"]));
+        assert!(args.windows(2).any(|w| w == ["-tag:v", "hvc1"]));
+        assert!(!args.contains(&"libx264".to_string()));
+
+        // Hardware mode switches to the HEVC VideoToolbox encoder, still tagged hvc1
+        encoder.set_backend(EncoderBackend::VideoToolbox);
+        let args = encoder.build_encoding_args();
+        assert!(args.windows(2).any(|w| w == ["-c:v", "hevc_videotoolbox"]));
+        assert!(args.windows(2).any(|w| w == ["-tag:v", "hvc1"]));
+    }
+
+    #[test]
+    fn test_crf_and_preset_in_args() {
+        let output_path = std::env::temp_dir().join("test_crf_preset.mp4");
+        let mut encoder = FFmpegEncoder::new(output_path, 1920, 1080, 30).unwrap();
+
+        // Real-time defaults
+        let args = encoder.build_encoding_args();
+        assert!(args.windows(2).any(|w| w == ["-preset", "fast"]));
+        assert!(args.windows(2).any(|w| w == ["-crf", "23"]));
+
+        encoder.set_crf(18).unwrap();
+        encoder.set_preset("slow".parse().unwrap());
+        let args = encoder.build_encoding_args();
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx264"]));
+        assert!(args.windows(2).any(|w| w == ["-preset", "slow"]));
+        assert!(args.windows(2).any(|w| w == ["-crf", "18"]));
+
+        // Out of range CRF and unknown presets are rejected
+        assert!(encoder.set_crf(60).is_err());
+        assert_eq!(encoder.crf, 18);
+        assert!("turbo".parse::<Preset>().is_err());
+    }
+
+    #[test]
+    fn test_loudnorm_only_when_normalizing() {
+        // System audio + microphone: the sum is normalized once, after amix
+        let normalized = build_audio_mux_filter(2, true);
+        assert!(normalized.contains(LOUDNORM_FILTER));
+        assert_eq!(normalized.matches("loudnorm").count(), 1);
+        assert!(normalized.find("amix=inputs=2").unwrap() < normalized.find("loudnorm").unwrap());
+        assert!(normalized.ends_with("[aout]"));
+
+        // Existing behavior without the flag
+        let plain = build_audio_mux_filter(2, false);
+        assert!(!plain.contains("loudnorm"));
+        assert!(plain.ends_with("volume=2.0[aout]"));
+        assert!(!build_audio_mux_filter(1, false).contains("loudnorm"));
+    }
+
+    #[test]
+    fn test_stereo_mic_pcm_muxes_with_recorded_channels() {
+        let dir = tempfile::tempdir().unwrap();
+        let pcm_path = dir.path().join("microphone.pcm");
+        std::fs::write(&pcm_path, [0u8; 16]).unwrap();
+        PcmMeta { sample_rate: 48000, channels: 2 }.write(&pcm_path).unwrap();
+
+        // The stop path's mono guess is overridden by what the writer recorded
+        let input = AudioInputConfig {
+            pcm_path: pcm_path.clone(),
+            sample_rate: 48000,
+            channels: 1,
+            label: "Microphone".to_string(),
+        }
+        .with_recorded_format();
+        assert_eq!(input.channels, 2);
+        let args = input.input_args();
+        assert!(args.windows(2).any(|w| w == ["-ac", "2"]));
+        assert!(args.windows(2).any(|w| w == ["-ar", "48000"]));
+
+        // No sidecar: the configured format is used as is
+        let other = dir.path().join("system.pcm");
+        let input = AudioInputConfig {
+            pcm_path: other,
+            sample_rate: 44100,
+            channels: 1,
+            label: "System Audio".to_string(),
+        }
+        .with_recorded_format();
+        assert_eq!((input.sample_rate, input.channels), (44100, 1));
+    }
+
+    #[tokio::test]
+    async fn test_hardware_probe_result_is_reused() {
+        // A made-up encoder would fail a real probe, so success must come from the cache
+        HARDWARE_PROBES
+            .lock()
+            .await
+            .insert(("test_cached_videotoolbox".to_string(), 1280, 720), Ok(()));
+        assert!(probe_hardware_encoder("test_cached_videotoolbox", 1281, 721).await.is_ok());
+
+        HARDWARE_PROBES
+            .lock()
+            .await
+            .insert(("test_cached_videotoolbox".to_string(), 640, 480), Err("no media engine".to_string()));
+        let err = probe_hardware_encoder("test_cached_videotoolbox", 640, 480).await.unwrap_err();
+        assert_eq!(err.to_string(), "no media engine");
+    }
+
+    #[test]
+    fn test_hardware_quality_validates_range() {
+        let output_path = std::env::temp_dir().join("test_vt_quality_range.mp4");
+        let mut encoder = FFmpegEncoder::new(output_path, 1920, 1080, 30).unwrap();
+
+        assert!(encoder.set_hardware_quality(0).is_err());
+        assert!(encoder.set_hardware_quality(101).is_err());
+        assert_eq!(encoder.hardware_quality, DEFAULT_VIDEOTOOLBOX_QUALITY);
+
+        assert!(encoder.set_hardware_quality(1).is_ok());
+        assert!(encoder.set_hardware_quality(100).is_ok());
+        assert_eq!(encoder.hardware_quality, 100);
+    }
+
+    #[test]
+    fn test_even_dimensions_rounds_odd_sizes() {
+        assert_eq!(even_dimensions(1281, 720), (1280, 720));
+        assert_eq!(even_dimensions(1920, 1081), (1920, 1080));
+        assert_eq!(even_dimensions(1, 1), (2, 2));
+
+        // 720p of a 1366x768 display computes 1280.6 -> 1281, rounded to 1280
+        let (width, height) = scale_to_height(1366, 768, 720);
+        assert_eq!((width, height), (1280, 720));
+        let source_ratio = 1366.0 / 768.0;
+        let output_ratio = width as f64 / height as f64;
+        assert!((source_ratio - output_ratio).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_odd_input_size_is_cropped_to_even() {
+        let output_path = std::env::temp_dir().join("test_odd_size.mp4");
+        let encoder = FFmpegEncoder::new(output_path, 1281, 721, 30).unwrap();
+
+        let args = encoder.build_encoding_args();
+        let vf_idx = args.iter().position(|a| a == "-vf").expect("-vf missing");
+        assert_eq!(args[vf_idx + 1], "crop=1280:720:0:0");
+    }
+
+    #[test]
+    fn test_encode_threads_default_by_context_and_appear_in_args() {
+        // Recording reserves a core; export uses all of them
+        assert_eq!(default_encode_threads(EncodeContext::Recording, 8), 7);
+        assert_eq!(default_encode_threads(EncodeContext::Export, 8), 8);
+        assert_eq!(default_encode_threads(EncodeContext::Recording, 1), 1);
+
+        assert_eq!(resolve_encode_threads(None, EncodeContext::Export, 8).unwrap(), 8);
+        assert_eq!(resolve_encode_threads(Some(2), EncodeContext::Export, 8).unwrap(), 2);
+        assert!(resolve_encode_threads(Some(0), EncodeContext::Recording, 8).is_err());
+        assert!(resolve_encode_threads(Some(9), EncodeContext::Recording, 8).is_err());
+
+        let output_path = std::env::temp_dir().join("test_threads.mp4");
+        let mut encoder = FFmpegEncoder::new(output_path, 1920, 1080, 30).unwrap();
+        encoder.set_threads(1).unwrap();
+        assert!(encoder.build_encoding_args().windows(2).any(|w| w == ["-threads", "1"]));
+        assert!(encoder.set_threads(available_cores() + 1).is_err());
+    }
+
+    #[test]
+    fn test_input_rate_matches_configured_fps() {
+        let output_path = std::env::temp_dir().join("test_60fps.mp4");
+        let encoder = FFmpegEncoder::new(output_path, 1920, 1080, 60).unwrap();
+        let args = encoder.build_encoding_args();
+        let input = args.iter().position(|a| a == "-i").unwrap();
+        assert!(args[..input].windows(2).any(|w| w == ["-r", "60"]));
+    }
 }
@@ -1,13 +1,185 @@
-use crate::models::{ExportConfig, ExportProgress, Timeline};
-use crate::services::timeline_renderer::TimelineRenderer;
+use crate::models::{ExportConfig, ExportProgress, ReencodePreset, Timeline, TimelineError, VideoCodec};
+use crate::services::timeline_renderer::{RenderJob, TimelineRenderer};
+use crate::services::ffmpeg::background::{background_input_args, build_background_filter};
+use crate::services::ffmpeg::formats::ensure_encoder_available;
+use crate::services::ffmpeg::watermark::{
+    build_watermark_filter, validate_watermark, watermark_input_args, watermark_input_index,
+};
+use crate::services::ffmpeg::stream_copy::{
+    build_stream_copy_args, stream_copy_candidate, stream_copy_is_exact,
+};
+use crate::services::ffmpeg::encoder::{
+    available_cores, default_encode_threads, even_dimensions, resolve_encode_threads, EncodeContext,
+};
+use crate::utils::output_dir::OutputDirError;
+use crate::utils::scratch::{move_into_output, prepare_scratch_dir, scratch_file};
 use anyhow::{Context, Result};
+use ffmpeg_sidecar::child::FfmpegChild;
 use ffmpeg_sidecar::command::FfmpegCommand;
 use ffmpeg_sidecar::event::{FfmpegEvent, LogLevel};
-use std::path::Path;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use thiserror::Error;
 use tokio::sync::Mutex;
 
+/// How long a cancelled FFmpeg gets to exit after SIGTERM before it is killed
+pub const CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// What FFmpeg logs when a write fails with ENOSPC
+const FFMPEG_DISK_FULL_MESSAGE: &str = "No space left on device";
+
+/// Why an export failed
+///
+/// Serialized as `{ code, message }` so the frontend can branch on `code`
+/// (e.g. offer to download FFmpeg, or to pick another drive) and show `message`.
+#[derive(Debug, Error)]
+pub enum ExportError {
+    /// FFmpeg could not be launched
+    #[error("FFmpeg not found. Please restart the application to download FFmpeg.")]
+    FfmpegMissing,
+
+    /// The timeline, export settings or output path can't be exported
+    #[error("{0}")]
+    InvalidConfig(String),
+
+    /// `Timeline::validate` found problems; each is serialized in `timelineErrors`
+    #[error("{}", timeline_errors_message(.0))]
+    InvalidTimeline(Vec<TimelineError>),
+
+    /// The output or scratch volume ran out of space
+    #[error("Not enough disk space to finish the export: {0}")]
+    DiskFull(String),
+
+    /// FFmpeg ran but failed; carries its error output
+    #[error("Export failed: {0}")]
+    ProcessFailed(String),
+
+    /// `VideoExporter::cancel` stopped the export
+    #[error("Export cancelled by user")]
+    Cancelled,
+
+    /// No export is registered under this ID (never started, or finished and unregistered)
+    #[error("Export not found: {0}")]
+    NotFound(String),
+
+    /// A batch job failed validation; keeps that job's error code
+    #[error("Job {} ({output_path}): {error}", .index + 1)]
+    BatchJob {
+        index: usize,
+        output_path: String,
+        error: Box<ExportError>,
+    },
+}
+
+impl ExportError {
+    /// Stable identifier for the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            ExportError::FfmpegMissing => "ffmpeg_missing",
+            ExportError::InvalidConfig(_) => "invalid_config",
+            ExportError::InvalidTimeline(_) => "invalid_timeline",
+            ExportError::DiskFull(_) => "disk_full",
+            ExportError::ProcessFailed(_) => "process_failed",
+            ExportError::Cancelled => "cancelled",
+            ExportError::NotFound(_) => "not_found",
+            ExportError::BatchJob { error, .. } => error.code(),
+        }
+    }
+
+    /// Wrap a validation error of the batch job at `index`
+    pub fn for_batch_job(self, index: usize, output_path: &str) -> Self {
+        ExportError::BatchJob {
+            index,
+            output_path: output_path.to_string(),
+            error: Box::new(self),
+        }
+    }
+
+    /// Timeline problems behind an `invalid_timeline` error
+    fn timeline_errors(&self) -> Option<&[TimelineError]> {
+        match self {
+            ExportError::InvalidTimeline(errors) => Some(errors),
+            ExportError::BatchJob { error, .. } => error.timeline_errors(),
+            _ => None,
+        }
+    }
+
+    /// Classify a failure to launch FFmpeg
+    fn from_spawn(err: &io::Error) -> Self {
+        if err.kind() == io::ErrorKind::NotFound {
+            ExportError::FfmpegMissing
+        } else {
+            ExportError::ProcessFailed(format!("Failed to start FFmpeg: {}", err))
+        }
+    }
+
+    /// Classify an I/O error writing the export
+    fn from_io(action: &str, err: &io::Error) -> Self {
+        if err.kind() == io::ErrorKind::StorageFull {
+            ExportError::DiskFull(err.to_string())
+        } else {
+            ExportError::ProcessFailed(format!("{}: {}", action, err))
+        }
+    }
+
+    /// Classify the error lines FFmpeg logged during a failed run
+    fn from_ffmpeg_errors(stderr: &str) -> Self {
+        if stderr.contains(FFMPEG_DISK_FULL_MESSAGE) {
+            ExportError::DiskFull(stderr.to_string())
+        } else {
+            ExportError::ProcessFailed(stderr.to_string())
+        }
+    }
+
+    /// Classify a timeline render failure (FFmpeg missing, or failed)
+    fn from_render(err: anyhow::Error) -> Self {
+        let spawn_error = err
+            .chain()
+            .filter_map(|cause| cause.downcast_ref::<io::Error>())
+            .find(|io_err| io_err.kind() == io::ErrorKind::NotFound);
+        match spawn_error {
+            Some(io_err) => ExportError::from_spawn(io_err),
+            None => ExportError::ProcessFailed(format!("{:#}", err)),
+        }
+    }
+}
+
+impl From<OutputDirError> for ExportError {
+    fn from(err: OutputDirError) -> Self {
+        ExportError::InvalidConfig(err.to_string())
+    }
+}
+
+impl Serialize for ExportError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("ExportError", 4)?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        match self.timeline_errors() {
+            Some(errors) => error.serialize_field("timelineErrors", errors)?,
+            None => error.skip_field("timelineErrors")?,
+        }
+        match self {
+            ExportError::BatchJob { index, .. } => error.serialize_field("jobIndex", index)?,
+            _ => error.skip_field("jobIndex")?,
+        }
+        error.end()
+    }
+}
+
+/// First timeline problem, plus how many more there are
+fn timeline_errors_message(errors: &[TimelineError]) -> String {
+    match errors {
+        [] => "Timeline can't be exported".to_string(),
+        [only] => format!("Timeline can't be exported: {}", only),
+        [first, rest @ ..] => format!("Timeline can't be exported: {} (and {} more)", first, rest.len()),
+    }
+}
+
 /// Video exporter using ffmpeg-sidecar
 pub struct VideoExporter {
     /// Shared progress state
@@ -17,7 +189,13 @@ pub struct VideoExporter {
     start_time: Instant,
 
     /// FFmpeg child process handle for cancellation
-    process_handle: Arc<Mutex<Option<ffmpeg_sidecar::child::FfmpegChild>>>,
+    process_handle: Arc<Mutex<Option<FfmpegChild>>>,
+
+    /// In-progress encode in scratch space, moved to the output path when done
+    scratch_output: std::sync::Mutex<Option<PathBuf>>,
+
+    /// Set by `cancel()`; the running transcode discards its output
+    cancelled: AtomicBool,
 }
 
 impl VideoExporter {
@@ -27,6 +205,8 @@ impl VideoExporter {
             progress: Arc::new(Mutex::new(ExportProgress::new(export_id, total_duration_ms))),
             start_time: Instant::now(),
             process_handle: Arc::new(Mutex::new(None)),
+            scratch_output: std::sync::Mutex::new(None),
+            cancelled: AtomicBool::new(false),
         }
     }
 }
@@ -59,6 +239,20 @@ impl VideoExporter {
         Ok(progress.clone())
     }
 
+    /// Shared progress state, readable while the exporter itself is locked for a run
+    pub fn progress_handle(&self) -> Arc<Mutex<ExportProgress>> {
+        Arc::clone(&self.progress)
+    }
+
+    /// Whether `cancel()` has been called
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn set_scratch_output(&self, scratch_output: Option<PathBuf>) {
+        *self.scratch_output.lock().unwrap_or_else(|e| e.into_inner()) = scratch_output;
+    }
+
     /// Export a timeline to MP4 file
     ///
     /// This function converts a timeline with clips into a single MP4 file using FFmpeg.
@@ -70,13 +264,13 @@ impl VideoExporter {
     ///
     /// # Returns
     /// * `Ok(())` - Export completed successfully
-    /// * `Err(anyhow::Error)` - Export failed
+    /// * `Err(ExportError)` - Export failed
     pub async fn export_timeline(
-        &mut self,
+        &self,
         timeline: &Timeline,
         config: &ExportConfig,
-    ) -> Result<()> {
-        let export_id = self.get_progress().await?.export_id.clone();
+    ) -> Result<(), ExportError> {
+        let export_id = self.progress.lock().await.export_id.clone();
 
         tracing::info!(
             event = "export_start",
@@ -86,25 +280,40 @@ impl VideoExporter {
             "Starting timeline export"
         );
 
+        // Reject codec/container combinations FFmpeg would fail on mid-export
+        validate_export_config(config).await?;
+
         // Validate and canonicalize output path to prevent path traversal
         let output_path = Path::new(&config.output_path);
 
+        // Create the output folder, explaining permission/read-only failures
+        crate::utils::output_dir::prepare_output_path(output_path)?;
+
         // Canonicalize the path (this also checks if parent exists)
         let output_path_canonical = output_path
             .parent()
-            .ok_or_else(|| anyhow::anyhow!("Invalid output path: no parent directory"))?
+            .ok_or_else(|| ExportError::InvalidConfig("Invalid output path: no parent directory".to_string()))?
             .canonicalize()
-            .context("Failed to canonicalize output path. Please ensure the parent directory exists.")?
-            .join(output_path.file_name().ok_or_else(|| anyhow::anyhow!("Invalid output path: no file name"))?);
+            .map_err(|e| {
+                ExportError::InvalidConfig(format!(
+                    "Failed to canonicalize output path. Please ensure the parent directory exists: {}",
+                    e
+                ))
+            })?
+            .join(
+                output_path
+                    .file_name()
+                    .ok_or_else(|| ExportError::InvalidConfig("Invalid output path: no file name".to_string()))?,
+            );
 
         // Verify path is within allowed directories (user home directory)
         if let Ok(home_dir) = std::env::var("HOME") {
             let home_canonical = Path::new(&home_dir).canonicalize().unwrap_or_default();
             if !output_path_canonical.starts_with(&home_canonical) {
-                return Err(anyhow::anyhow!(
+                return Err(ExportError::InvalidConfig(format!(
                     "Invalid output path: must be within user home directory. Path: {:?}",
                     output_path_canonical
-                ));
+                )));
             }
         }
 
@@ -115,22 +324,51 @@ impl VideoExporter {
             "Output path validated and canonicalized"
         );
 
+        // Fast path: a plain in/out trim of one clip is cut with stream copy
+        if let Some(trim) = stream_copy_candidate(timeline, config) {
+            if stream_copy_is_exact(&trim, config).await {
+                tracing::info!(
+                    event = "export_stream_copy",
+                    export_id = %export_id,
+                    input = %trim.input.display(),
+                    start_ms = trim.start_ms,
+                    end_ms = trim.end_ms,
+                    "Exporting trim with stream copy (no re-encode)"
+                );
+
+                let scratch_dir = prepare_scratch_dir(config.scratch_dir.as_deref())?;
+                let scratch_output = scratch_file(
+                    &scratch_dir,
+                    &format!("export-{}", export_id),
+                    &format!("output.{}", config.container()),
+                );
+                self.set_scratch_output(Some(scratch_output.clone()));
+
+                let mut ffmpeg = FfmpegCommand::new();
+                ffmpeg.args(build_stream_copy_args(&trim, config, &scratch_output.to_string_lossy()));
+                return self.run_transcode(ffmpeg, &scratch_output, &output_path_canonical).await;
+            }
+        }
+
         // Step 1: Use TimelineRenderer to render timeline to temp file
         tracing::info!("Rendering timeline using TimelineRenderer...");
 
         // Create timeline cache directory (same as in lib.rs)
         let timeline_cache_dir = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?
+            .ok_or_else(|| ExportError::InvalidConfig("Could not determine home directory".to_string()))?
             .join("Library")
             .join("Caches")
             .join("com.clippy.app")
             .join("timelines");
 
         std::fs::create_dir_all(&timeline_cache_dir)
-            .context("Failed to create timeline cache directory")?;
+            .map_err(|e| ExportError::from_io("Failed to create timeline cache directory", &e))?;
 
-        let renderer = TimelineRenderer::new(timeline_cache_dir);
-        let rendered_timeline_path = renderer.render_timeline(timeline, None)?;
+        // Lossless, so the transcode below is the only lossy step and honours crf/preset
+        let renderer = TimelineRenderer::new(timeline_cache_dir)
+            .with_lossless(true)
+            .with_background(config.background.clone());
+        let rendered_timeline_path = self.render_for_export(&renderer, timeline).await?;
 
         tracing::info!(
             event = "timeline_rendered_for_export",
@@ -138,22 +376,204 @@ impl VideoExporter {
             "Timeline rendered successfully, starting transcode"
         );
 
-        // Step 2: Transcode rendered timeline to user's desired export format
-        let mut ffmpeg = self.build_export_command(timeline, config, &rendered_timeline_path)?;
+        // Step 2: Transcode rendered timeline to user's desired export format.
+        // Encode into scratch space so a slow output drive only sees the finished file.
+        let scratch_dir = prepare_scratch_dir(config.scratch_dir.as_deref())?;
+        let scratch_output = scratch_file(
+            &scratch_dir,
+            &format!("export-{}", export_id),
+            &format!("output.{}", config.container()),
+        );
+        self.set_scratch_output(Some(scratch_output.clone()));
+
+        let mut scratch_config = config.clone();
+        scratch_config.output_path = scratch_output.to_string_lossy().to_string();
+        let ffmpeg = self
+            .build_export_command(timeline, &scratch_config, &rendered_timeline_path)
+            .map_err(|e| ExportError::InvalidConfig(e.to_string()))?;
 
         tracing::debug!(
             event = "ffmpeg_command_built",
             "FFmpeg export command constructed successfully"
         );
 
+        self.run_transcode(ffmpeg, &scratch_output, &output_path_canonical).await
+    }
+
+    /// Render the timeline with its FFmpeg registered for `cancel()`
+    ///
+    /// The render is most of an export, so it must stop as soon as the export
+    /// is cancelled. A failed or cancelled render's partial file is removed so
+    /// it isn't served from the cache later.
+    async fn render_for_export(&self, renderer: &TimelineRenderer, timeline: &Timeline) -> Result<PathBuf, ExportError> {
+        let (mut command, render_path) = match renderer.prepare_render(timeline).map_err(ExportError::from_render)? {
+            RenderJob::Cached(path) => return Ok(path),
+            RenderJob::Pending { command, output_path, .. } => (command, output_path),
+        };
+
+        if self.is_cancelled() {
+            return Err(ExportError::Cancelled);
+        }
+
+        let child = command.spawn().map_err(|e| ExportError::from_spawn(&e))?;
+        let iter = {
+            let mut handle = self.process_handle.lock().await;
+            handle
+                .insert(child)
+                .iter()
+                .map_err(|e| ExportError::ProcessFailed(format!("Failed to create FFmpeg event iterator: {}", e)))?
+        };
+
+        let mut ffmpeg_errors: Vec<String> = Vec::new();
+        for event in iter {
+            match event {
+                FfmpegEvent::Log(LogLevel::Error, msg) => {
+                    tracing::error!(event = "timeline_render_ffmpeg_error", message = %msg, "FFmpeg error while rendering");
+                    ffmpeg_errors.push(msg);
+                }
+                FfmpegEvent::LogEOF | FfmpegEvent::Done => break,
+                _ => {}
+            }
+        }
+
+        // cancel() takes the child to terminate it; otherwise reap it here
+        let status = match self.process_handle.lock().await.take() {
+            Some(mut child) => child.wait().ok(),
+            None => None,
+        };
+
+        if self.is_cancelled() {
+            let _ = std::fs::remove_file(&render_path);
+            return Err(ExportError::Cancelled);
+        }
+        if !status.is_some_and(|status| status.success()) || !render_path.exists() {
+            let _ = std::fs::remove_file(&render_path);
+            let error = if ffmpeg_errors.is_empty() {
+                ExportError::ProcessFailed("Timeline rendering failed: output file not created".to_string())
+            } else {
+                ExportError::from_ffmpeg_errors(&ffmpeg_errors.join("\n"))
+            };
+            return Err(self.fail(error).await);
+        }
+        Ok(render_path)
+    }
+
+    /// Re-encode a single file with a one-shot preset
+    ///
+    /// Used to shrink recordings captured with `ultrafast`. Video is re-encoded
+    /// with libx264 at the preset's speed/CRF; audio streams are copied as-is.
+    ///
+    /// # Arguments
+    /// * `input` - Source recording
+    /// * `preset` - libx264 preset/CRF pair
+    /// * `output_path` - Destination file (written via scratch space)
+    ///
+    /// # Returns
+    /// * `Ok(())` - Re-encode completed successfully
+    /// * `Err(ExportError)` - Re-encode failed
+    pub async fn reencode_file(
+        &self,
+        input: &Path,
+        preset: ReencodePreset,
+        output_path: &Path,
+    ) -> Result<(), ExportError> {
+        let export_id = self.progress.lock().await.export_id.clone();
+
+        tracing::info!(
+            event = "reencode_start",
+            export_id = %export_id,
+            input = %input.display(),
+            output_path = %output_path.display(),
+            preset = ?preset,
+            "Starting re-encode"
+        );
+
+        crate::utils::output_dir::prepare_output_path(output_path)?;
+
+        let scratch_dir = prepare_scratch_dir(None)?;
+        let scratch_output = scratch_file(&scratch_dir, &format!("reencode-{}", export_id), "output.mp4");
+        self.set_scratch_output(Some(scratch_output.clone()));
+
+        let ffmpeg = build_reencode_command(input, preset, &scratch_output);
+        self.run_transcode(ffmpeg, &scratch_output, output_path).await
+    }
+
+    /// Trim a file to `[start_ms, end_ms)` and export it in a single FFmpeg pass
+    ///
+    /// Skips the timeline render: the range is selected with input seeking
+    /// (`-ss`/`-to` before `-i`) and encoded straight to `config`.
+    ///
+    /// # Arguments
+    /// * `input` - Source recording
+    /// * `start_ms` - Range start in milliseconds
+    /// * `end_ms` - Range end in milliseconds (exclusive)
+    /// * `config` - Export configuration (output path, codec settings, etc.)
+    ///
+    /// # Returns
+    /// * `Ok(())` - Export completed successfully
+    /// * `Err(ExportError)` - Invalid range or settings, or FFmpeg failed
+    pub async fn quick_clip(
+        &self,
+        input: &Path,
+        start_ms: u64,
+        end_ms: u64,
+        config: &ExportConfig,
+    ) -> Result<(), ExportError> {
+        let export_id = self.progress.lock().await.export_id.clone();
+
+        tracing::info!(
+            event = "quick_clip_start",
+            export_id = %export_id,
+            input = %input.display(),
+            start_ms,
+            end_ms,
+            output_path = %config.output_path,
+            "Starting quick clip export"
+        );
+
+        if end_ms <= start_ms {
+            return Err(ExportError::InvalidConfig(format!(
+                "Invalid clip range: end ({}ms) must be after start ({}ms)",
+                end_ms, start_ms
+            )));
+        }
+        validate_export_config(config).await?;
+
+        let output_path = Path::new(&config.output_path);
+        crate::utils::output_dir::prepare_output_path(output_path)?;
+
+        let scratch_dir = prepare_scratch_dir(config.scratch_dir.as_deref())?;
+        let scratch_output = scratch_file(
+            &scratch_dir,
+            &format!("quick-clip-{}", export_id),
+            &format!("output.{}", config.container()),
+        );
+        self.set_scratch_output(Some(scratch_output.clone()));
+
+        let mut scratch_config = config.clone();
+        scratch_config.output_path = scratch_output.to_string_lossy().to_string();
+        let ffmpeg = self.build_quick_clip_command(input, start_ms, end_ms, &scratch_config);
+
+        self.run_transcode(ffmpeg, &scratch_output, output_path).await
+    }
+
+    /// Run an FFmpeg transcode into `scratch_output`, tracking progress, then move it to `output_path`
+    async fn run_transcode(
+        &self,
+        mut ffmpeg: FfmpegCommand,
+        scratch_output: &Path,
+        output_path_canonical: &Path,
+    ) -> Result<(), ExportError> {
         // Spawn FFmpeg process with event handler
         let progress_clone = Arc::clone(&self.progress);
         let process_handle_clone = Arc::clone(&self.process_handle);
         let start_time = self.start_time;
 
-        let child = ffmpeg
-            .spawn()
-            .context("Failed to spawn FFmpeg process. Please restart the application to download FFmpeg.")?;
+        if self.is_cancelled() {
+            return Err(ExportError::Cancelled);
+        }
+
+        let child = ffmpeg.spawn().map_err(|e| ExportError::from_spawn(&e))?;
 
         // Store process handle for cancellation
         {
@@ -165,11 +585,14 @@ impl VideoExporter {
         let mut iter = {
             let mut handle = process_handle_clone.lock().await;
             handle.as_mut()
-                .ok_or_else(|| anyhow::anyhow!("FFmpeg process handle not available"))?
+                .ok_or_else(|| ExportError::ProcessFailed("FFmpeg process handle not available".to_string()))?
                 .iter()
-                .context("Failed to create FFmpeg event iterator")?
+                .map_err(|e| ExportError::ProcessFailed(format!("Failed to create FFmpeg event iterator: {}", e)))?
         };
 
+        // Error lines explain a failed run (and reveal a full disk)
+        let mut ffmpeg_errors: Vec<String> = Vec::new();
+
         // Process FFmpeg events
         while let Some(event) = iter.next() {
             match event {
@@ -198,6 +621,7 @@ impl VideoExporter {
                         message = %msg,
                         "FFmpeg error occurred"
                     );
+                    ffmpeg_errors.push(msg);
                 }
                 FfmpegEvent::Log(LogLevel::Warning, msg) => {
                     tracing::warn!(
@@ -225,17 +649,37 @@ impl VideoExporter {
             }
         }
 
-        // Check if output file was created
-        if !output_path_canonical.exists() {
-            let error = "Export failed: output file was not created";
-            let mut prog = self.progress.lock().await;
-            *prog = prog.clone().mark_failed(error.to_string());
+        // Cancelled: cancel() owns the cleanup and the final status
+        if self.is_cancelled() {
+            let _ = std::fs::remove_file(scratch_output);
+            self.set_scratch_output(None);
+            return Err(ExportError::Cancelled);
+        }
 
-            // Clean up process handle
-            let mut handle = self.process_handle.lock().await;
-            *handle = None;
+        // A full disk leaves a truncated encode behind; don't move it into place
+        if ffmpeg_errors.iter().any(|msg| msg.contains(FFMPEG_DISK_FULL_MESSAGE)) {
+            let _ = std::fs::remove_file(scratch_output);
+            self.set_scratch_output(None);
+            return Err(self.fail(ExportError::from_ffmpeg_errors(&ffmpeg_errors.join("\n"))).await);
+        }
 
-            return Err(anyhow::anyhow!(error));
+        // Move the finished encode into place
+        if scratch_output.exists() {
+            if let Err(e) = move_into_output(scratch_output, output_path_canonical) {
+                let error = ExportError::from_io("Failed to move export into the output folder", &e);
+                return Err(self.fail(error).await);
+            }
+        }
+        self.set_scratch_output(None);
+
+        // Check if output file was created
+        if !output_path_canonical.exists() {
+            let error = if ffmpeg_errors.is_empty() {
+                ExportError::ProcessFailed("output file was not created".to_string())
+            } else {
+                ExportError::from_ffmpeg_errors(&ffmpeg_errors.join("\n"))
+            };
+            return Err(self.fail(error).await);
         }
 
         // Mark as completed
@@ -254,49 +698,79 @@ impl VideoExporter {
             event = "export_complete",
             output_path = %output_path_canonical.display(),
             duration_seconds = start_time.elapsed().as_secs(),
-            "Timeline export completed successfully"
+            "Export completed successfully"
         );
 
         Ok(())
     }
 
+    /// Mark progress failed with `error` and release the process handle
+    async fn fail(&self, error: ExportError) -> ExportError {
+        {
+            let mut prog = self.progress.lock().await;
+            *prog = prog.clone().mark_failed(error.code(), error.to_string());
+        }
+        *self.process_handle.lock().await = None;
+        error
+    }
+
     /// Cancel the running export
     ///
-    /// Terminates the FFmpeg process and cleans up partial output file.
+    /// Sends SIGTERM to FFmpeg, kills it if it is still running after
+    /// `CANCEL_GRACE_PERIOD`, and removes the partial output. Progress reports
+    /// `Cancelled` from then on. Takes `&self` so it can run while the export
+    /// itself is in progress.
     ///
     /// # Returns
     /// * `Ok(())` - Cancellation successful
     /// * `Err(anyhow::Error)` - Cancellation failed
-    pub async fn cancel(&mut self, output_path: &str) -> Result<()> {
+    pub async fn cancel(&self, output_path: &str) -> Result<()> {
         tracing::info!(
             event = "export_cancel_requested",
             output_path = %output_path,
             "Cancelling export"
         );
 
+        // Flag first so the transcode loop doesn't move a partial file into place
+        self.cancelled.store(true, Ordering::SeqCst);
+
         // Terminate FFmpeg process
-        {
-            let mut handle = self.process_handle.lock().await;
-            if let Some(mut child) = handle.take() {
-                // Kill the FFmpeg process
-                if let Err(e) = child.kill() {
-                    tracing::error!(
-                        event = "ffmpeg_kill_failed",
-                        error = %e,
-                        "Failed to kill FFmpeg process"
-                    );
-                    return Err(anyhow::anyhow!("Failed to terminate FFmpeg process: {}", e));
-                }
+        let child = self.process_handle.lock().await.take();
+        match child {
+            Some(mut child) => {
+                tokio::task::spawn_blocking(move || terminate_ffmpeg(&mut child, CANCEL_GRACE_PERIOD))
+                    .await
+                    .context("FFmpeg termination task failed")?
+                    .map_err(|e| {
+                        tracing::error!(
+                            event = "ffmpeg_kill_failed",
+                            error = %e,
+                            "Failed to kill FFmpeg process"
+                        );
+                        anyhow::anyhow!("Failed to terminate FFmpeg process: {}", e)
+                    })?;
                 tracing::info!("FFmpeg process terminated");
-            } else {
-                tracing::warn!("No active FFmpeg process to cancel");
             }
+            None => tracing::warn!("No active FFmpeg process to cancel"),
         }
 
         // Mark progress as cancelled
         {
             let mut prog = self.progress.lock().await;
-            *prog = prog.clone().mark_failed("Export cancelled by user".to_string());
+            *prog = prog.clone().mark_cancelled();
+        }
+
+        // Clean up the partial encode in scratch space
+        let scratch_output = self.scratch_output.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(scratch_output) = scratch_output {
+            if let Err(e) = std::fs::remove_file(&scratch_output) {
+                tracing::debug!(
+                    event = "scratch_output_cleanup_skipped",
+                    path = %scratch_output.display(),
+                    error = %e,
+                    "No partial scratch output removed"
+                );
+            }
         }
 
         // Clean up partial output file
@@ -342,6 +816,16 @@ impl VideoExporter {
         // Input: rendered timeline file from TimelineRenderer
         command.arg("-i").arg(rendered_timeline_path);
 
+        // Background image (if any) is input 1, used when fitting to `resolution`
+        if config.resolution.is_some() {
+            command.args(background_input_args(&config.background));
+        }
+
+        // Watermark image (if any) follows the background image
+        if let Some(watermark) = &config.watermark {
+            command.args(watermark_input_args(watermark));
+        }
+
         // Apply encoding settings
         self.add_encoding_params(&mut command, config);
 
@@ -351,16 +835,48 @@ impl VideoExporter {
         Ok(command)
     }
 
+    /// Build FFmpeg command trimming `input` to a range and encoding it with `config`
+    fn build_quick_clip_command(
+        &self,
+        input: &Path,
+        start_ms: u64,
+        end_ms: u64,
+        config: &ExportConfig,
+    ) -> FfmpegCommand {
+        let mut command = FfmpegCommand::new();
+
+        // Input seeking: only the selected range is decoded
+        command.arg("-ss").arg(format!("{:.3}", start_ms as f64 / 1000.0));
+        command.arg("-to").arg(format!("{:.3}", end_ms as f64 / 1000.0));
+        command.arg("-i").arg(input);
+
+        if config.resolution.is_some() {
+            command.args(background_input_args(&config.background));
+        }
+        if let Some(watermark) = &config.watermark {
+            command.args(watermark_input_args(watermark));
+        }
+
+        self.add_encoding_params(&mut command, config);
+        command.arg(&config.output_path);
+        command
+    }
+
     /// Add encoding parameters (codec, bitrate, etc.)
     fn add_encoding_params(&self, command: &mut FfmpegCommand, config: &ExportConfig) {
         // Video codec (default: H.264)
-        let video_codec = config.codec.as_deref().unwrap_or("libx264");
+        let video_codec = config.video_encoder();
         command.arg("-c:v").arg(video_codec);
 
-        // Video encoding preset for H.264
-        if video_codec == "libx264" {
-            command.arg("-preset").arg("faster");
-            command.arg("-crf").arg("23"); // Constant rate factor for quality
+        // Speed preset and constant rate factor for the software H.264/H.265 encoders
+        if video_codec == "libx264" || video_codec == "libx265" {
+            command.arg("-preset").arg(config.preset().name());
+            command.arg("-crf").arg(config.crf().to_string());
+        }
+
+        // hvc1 tag so QuickTime plays HEVC
+        if let Some(tag) = config.video_codec.mp4_tag() {
+            command.arg("-tag:v").arg(tag);
         }
 
         // Video bitrate (if specified)
@@ -368,27 +884,131 @@ impl VideoExporter {
             command.arg("-b:v").arg(bitrate);
         }
 
+        // Encode threads (validated at export start; defaults to every core)
+        let threads = config
+            .threads
+            .unwrap_or_else(|| default_encode_threads(EncodeContext::Export, available_cores()));
+        command.arg("-threads").arg(threads.to_string());
+
+        // Pixel format (if specified, e.g. yuv422p10le for ProRes)
+        if let Some(pixel_format) = config.pixel_format {
+            command.arg("-pix_fmt").arg(pixel_format.ffmpeg_name());
+        }
+
         // Audio codec (default: AAC)
-        let audio_codec = config.audio_codec.as_deref().unwrap_or("aac");
-        command.arg("-c:a").arg(audio_codec);
+        command.arg("-c:a").arg(config.audio_codec.ffmpeg_codec());
 
-        // Audio bitrate (default: 192k)
-        let audio_bitrate = config.audio_bitrate.as_deref().unwrap_or("192k");
-        command.arg("-b:a").arg(audio_bitrate);
+        // Audio bitrate (default: 192k, lossless codecs ignore it)
+        if config.audio_codec.is_lossy() {
+            let audio_bitrate = config.audio_bitrate.as_deref().unwrap_or("192k");
+            command.arg("-b:a").arg(audio_bitrate);
+        }
 
-        // Resolution (if specified)
+        // Resolution (if specified): fit the video, fill the rest with the background
+        let mut filters = Vec::new();
+        let mut video_label = "0:v";
         if let Some((width, height)) = config.resolution {
-            command.arg("-s").arg(format!("{}x{}", width, height));
+            let (width, height) = even_dimensions(width, height);
+            filters.push(build_background_filter(&config.background, width, height));
+            video_label = "vout";
         }
 
-        // Output format: MP4
-        command.arg("-f").arg("mp4");
+        // Watermark (if specified): overlaid after fitting, so it keeps its pixel size
+        if let Some(watermark) = &config.watermark {
+            filters.push(build_watermark_filter(
+                watermark,
+                watermark_input_index(config),
+                video_label,
+                "vmark",
+            ));
+            video_label = "vmark";
+        }
+
+        if !filters.is_empty() {
+            command.arg("-filter_complex").arg(filters.join(";"));
+            command.arg("-map").arg(format!("[{}]", video_label));
+            command.arg("-map").arg("0:a?");
+        }
+
+        // Output format follows the extension (MOV for ProRes/ALAC, Matroska for .mkv PCM)
+        if config.wants_faststart() {
+            command.arg("-movflags").arg("+faststart");
+        }
+        command.arg("-f").arg(config.muxer());
 
         // Overwrite output file without asking
         command.arg("-y");
     }
 }
 
+/// Reject export settings FFmpeg would fail on mid-export
+pub async fn validate_export_config(config: &ExportConfig) -> Result<(), ExportError> {
+    config.validate_audio_codec().map_err(ExportError::InvalidConfig)?;
+    config.validate_pixel_format().map_err(ExportError::InvalidConfig)?;
+    config.validate_crf().map_err(ExportError::InvalidConfig)?;
+    if let Some(watermark) = &config.watermark {
+        validate_watermark(watermark).map_err(ExportError::InvalidConfig)?;
+    }
+    resolve_encode_threads(config.threads, EncodeContext::Export, available_cores())
+        .map_err(|e| ExportError::InvalidConfig(e.to_string()))?;
+    if config.video_codec != VideoCodec::H264 {
+        ensure_encoder_available(config.video_encoder())
+            .await
+            .map_err(|e| ExportError::InvalidConfig(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Build the FFmpeg command re-encoding `input` with `preset`
+///
+/// Maps the first video stream and every audio stream (if any); audio is
+/// stream-copied so only the video is re-encoded.
+fn build_reencode_command(input: &Path, preset: ReencodePreset, output_path: &Path) -> FfmpegCommand {
+    let mut command = FfmpegCommand::new();
+    command
+        .arg("-i").arg(input)
+        .arg("-map").arg("0:v:0")
+        .arg("-map").arg("0:a?")
+        .arg("-c:v").arg("libx264")
+        .arg("-preset").arg(preset.x264_preset())
+        .arg("-crf").arg(preset.crf().to_string())
+        .arg("-pix_fmt").arg("yuv420p")
+        .arg("-c:a").arg("copy")
+        .arg("-movflags").arg("+faststart")
+        .arg("-f").arg("mp4")
+        .arg("-y")
+        .arg(output_path);
+    command
+}
+
+/// Stop an FFmpeg child: SIGTERM so it can exit cleanly, SIGKILL if it outlives `grace`
+fn terminate_ffmpeg(child: &mut FfmpegChild, grace: Duration) -> std::io::Result<()> {
+    let inner = child.as_inner_mut();
+
+    #[cfg(unix)]
+    {
+        // SAFETY: the pid belongs to our own child, which is not reaped until the wait below
+        unsafe {
+            libc::kill(inner.id() as libc::pid_t, libc::SIGTERM);
+        }
+
+        let deadline = Instant::now() + grace;
+        while Instant::now() < deadline {
+            if inner.try_wait()?.is_some() {
+                return Ok(());
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        tracing::warn!(event = "ffmpeg_sigterm_timeout", "FFmpeg ignored SIGTERM, killing");
+    }
+    #[cfg(not(unix))]
+    let _ = grace;
+
+    inner.kill()?;
+    inner.wait()?;
+    Ok(())
+}
+
 /// Parse FFmpeg time string (format: "HH:MM:SS.mmm" or "00:01:23.45") to milliseconds
 fn parse_ffmpeg_time(time_str: &str) -> Option<u64> {
     let parts: Vec<&str> = time_str.split(':').collect();
@@ -490,6 +1110,271 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_encoding_params_emit_pix_fmt() {
+        let exporter = VideoExporter::new("test-id".to_string(), 10000);
+        let args = |config: &ExportConfig| -> Vec<String> {
+            let mut command = FfmpegCommand::new();
+            exporter.add_encoding_params(&mut command, config);
+            command
+                .as_inner()
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect()
+        };
+
+        let config = ExportConfig {
+            pixel_format: Some(crate::models::PixelFormat::Yuv422p),
+            ..ExportConfig::default()
+        };
+        assert!(args(&config).windows(2).any(|w| w == ["-pix_fmt", "yuv422p"]));
+
+        // Encoder default when not overridden
+        assert!(!args(&ExportConfig::default()).iter().any(|a| a == "-pix_fmt"));
+    }
+
+    #[test]
+    fn test_export_threads_in_args() {
+        let exporter = VideoExporter::new("test-id".to_string(), 10_000);
+        let args = |config: &ExportConfig| -> Vec<String> {
+            let mut command = FfmpegCommand::new();
+            exporter.add_encoding_params(&mut command, config);
+            command
+                .as_inner()
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect()
+        };
+
+        let config = ExportConfig { threads: Some(2), ..ExportConfig::default() };
+        assert!(args(&config).windows(2).any(|w| w == ["-threads", "2"]));
+
+        // Exports default to every core, unlike recordings
+        let cores = available_cores().to_string();
+        assert!(args(&ExportConfig::default()).windows(2).any(|w| w == ["-threads", cores.as_str()]));
+    }
+
+    #[test]
+    fn test_h265_export_uses_libx265_with_hvc1_tag() {
+        let exporter = VideoExporter::new("test-id".to_string(), 10_000);
+        let config = ExportConfig { video_codec: VideoCodec::H265, ..ExportConfig::default() };
+
+        let mut command = FfmpegCommand::new();
+        exporter.add_encoding_params(&mut command, &config);
+        let args: Vec<String> = command
+            .as_inner()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx265"]));
+        assert!(args.windows(2).any(|w| w == ["-tag:v", "hvc1"]));
+        assert!(!args.iter().any(|a| a == "libx264"));
+        assert!(args.windows(2).any(|w| w == ["-crf", "28"]));
+
+        // Explicit quality settings are passed through
+        let config = ExportConfig {
+            crf: Some(18),
+            preset: Some(crate::models::Preset::Slow),
+            ..ExportConfig::default()
+        };
+        let mut command = FfmpegCommand::new();
+        exporter.add_encoding_params(&mut command, &config);
+        let args: Vec<String> = command
+            .as_inner()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        assert!(args.windows(2).any(|w| w == ["-preset", "slow"]));
+        assert!(args.windows(2).any(|w| w == ["-crf", "18"]));
+        assert!(ExportConfig { crf: Some(60), ..ExportConfig::default() }.validate_crf().is_err());
+    }
+
+    #[test]
+    fn test_pcm_mkv_export_uses_matroska_muxer() {
+        let exporter = VideoExporter::new("test-id".to_string(), 10_000);
+        let config = ExportConfig {
+            output_path: "/tmp/out.mkv".to_string(),
+            audio_codec: crate::models::AudioCodec::Pcm,
+            ..ExportConfig::default()
+        };
+        assert!(config.validate_audio_codec().is_ok());
+
+        let mut command = FfmpegCommand::new();
+        exporter.add_encoding_params(&mut command, &config);
+        let args: Vec<String> = command
+            .as_inner()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        assert!(args.windows(2).any(|w| w == ["-c:a", "pcm_s16le"]));
+        assert!(args.windows(2).any(|w| w == ["-f", "matroska"]));
+    }
+
+    #[test]
+    fn test_export_presets_map_to_documented_encoder_args() {
+        let exporter = VideoExporter::new("test-id".to_string(), 10_000);
+        let expected: [(&str, &[[&str; 2]], Option<&str>); 4] = [
+            (
+                "web",
+                &[["-c:v", "libx264"], ["-preset", "medium"], ["-crf", "23"], ["-b:a", "128k"], ["-movflags", "+faststart"]],
+                Some("scale=1280:720"),
+            ),
+            (
+                "youtube1080p",
+                &[["-c:v", "libx264"], ["-preset", "slow"], ["-crf", "18"], ["-b:a", "192k"], ["-movflags", "+faststart"]],
+                Some("scale=1920:1080"),
+            ),
+            (
+                "highQuality",
+                &[["-c:v", "libx264"], ["-preset", "slower"], ["-crf", "16"], ["-b:a", "320k"]],
+                None,
+            ),
+            (
+                "smallFile",
+                &[["-c:v", "libx265"], ["-tag:v", "hvc1"], ["-preset", "medium"], ["-crf", "30"], ["-b:a", "96k"]],
+                Some("scale=1280:720"),
+            ),
+        ];
+        assert_eq!(expected.len(), crate::models::EXPORT_PRESETS.len());
+
+        for (id, pairs, scale) in expected {
+            let base = ExportConfig { output_path: "/tmp/out.mp4".to_string(), ..ExportConfig::default() };
+            let config = crate::models::ExportPreset::find(id).unwrap().apply(base);
+            assert_eq!(config.output_path, "/tmp/out.mp4");
+
+            let mut command = FfmpegCommand::new();
+            exporter.add_encoding_params(&mut command, &config);
+            let args: Vec<String> = command
+                .as_inner()
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect();
+
+            for pair in pairs {
+                assert!(args.windows(2).any(|w| w == pair), "{}: missing {:?} in {:?}", id, pair, args);
+            }
+            assert!(args.windows(2).any(|w| w == ["-pix_fmt", "yuv420p"]), "{}", id);
+            assert_eq!(args.iter().any(|a| a == "+faststart"), config.faststart, "{}", id);
+            match scale {
+                Some(scale) => assert!(args.iter().any(|a| a.contains(scale)), "{}", id),
+                None => assert!(!args.iter().any(|a| a == "-filter_complex"), "{}", id),
+            }
+        }
+
+        assert!(crate::models::ExportPreset::find("youtube4k").is_none());
+    }
+
+    #[test]
+    fn test_quick_clip_command_seeks_input_and_encodes() {
+        let exporter = VideoExporter::new("test-id".to_string(), 7_250);
+        let config = ExportConfig {
+            output_path: "/tmp/clip.mp4".to_string(),
+            video_bitrate: Some("5M".to_string()),
+            ..ExportConfig::default()
+        };
+
+        let command = exporter.build_quick_clip_command(Path::new("/tmp/recording.mp4"), 2_500, 9_750, &config);
+        let args: Vec<String> = command
+            .as_inner()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+
+        // Seek options come before the input so they apply to it
+        let input = args.iter().position(|a| a == "/tmp/recording.mp4").unwrap();
+        assert_eq!(&args[input - 5..input], ["-ss", "2.500", "-to", "9.750", "-i"]);
+
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx264"]));
+        assert!(args.windows(2).any(|w| w == ["-b:v", "5M"]));
+        assert!(args.windows(2).any(|w| w == ["-c:a", "aac"]));
+        assert_eq!(args.last().map(String::as_str), Some("/tmp/clip.mp4"));
+    }
+
+    #[test]
+    fn test_reencode_command_uses_preset_and_copies_audio() {
+        let command = build_reencode_command(
+            Path::new("/tmp/recording.mp4"),
+            ReencodePreset::Smaller,
+            Path::new("/tmp/recording-reencoded.mp4"),
+        );
+        let args: Vec<String> = command
+            .as_inner()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+
+        assert!(args.windows(2).any(|w| w == ["-preset", "slow"]));
+        assert!(args.windows(2).any(|w| w == ["-crf", "23"]));
+        assert!(args.windows(2).any(|w| w == ["-map", "0:a?"]));
+        assert!(args.windows(2).any(|w| w == ["-c:a", "copy"]));
+        assert_eq!(args.last().unwrap(), "/tmp/recording-reencoded.mp4");
+
+        let smallest = build_reencode_command(
+            Path::new("/tmp/recording.mp4"),
+            ReencodePreset::Smallest,
+            Path::new("/tmp/out.mp4"),
+        );
+        let args: Vec<String> = smallest
+            .as_inner()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        assert!(args.windows(2).any(|w| w == ["-preset", "veryslow"]));
+        assert!(args.windows(2).any(|w| w == ["-crf", "26"]));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancel_kills_ffmpeg_and_removes_output() {
+        let ffmpeg_available = std::process::Command::new("ffmpeg")
+            .arg("-version")
+            .output()
+            .is_ok_and(|o| o.status.success());
+        if !ffmpeg_available {
+            println!("Skipping export cancel test - ffmpeg not available");
+            return;
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let scratch = dir.path().join("scratch.mp4");
+        let output = dir.path().join("export.mp4");
+        let exporter = Arc::new(VideoExporter::new("cancel-test".to_string(), 600_000));
+        exporter.set_scratch_output(Some(scratch.clone()));
+
+        // Ten minutes of test pattern, far longer than the test runs
+        let mut ffmpeg = FfmpegCommand::new();
+        ffmpeg
+            .args(["-f", "lavfi", "-i", "testsrc=size=320x240:rate=30:duration=600"])
+            .args(["-c:v", "libx264", "-preset", "ultrafast", "-f", "mp4", "-y"])
+            .arg(scratch.to_string_lossy().to_string());
+
+        let run = {
+            let exporter = Arc::clone(&exporter);
+            let (scratch, output) = (scratch.clone(), output.clone());
+            tokio::spawn(async move { exporter.run_transcode(ffmpeg, &scratch, &output).await })
+        };
+
+        let started = Instant::now();
+        while !scratch.exists() && started.elapsed() < Duration::from_secs(10) {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(scratch.exists(), "FFmpeg never started writing");
+
+        // SIGTERM, and SIGKILL at the latest after the grace period
+        let cancel_started = Instant::now();
+        exporter.cancel(&output.to_string_lossy()).await.unwrap();
+        assert!(cancel_started.elapsed() < CANCEL_GRACE_PERIOD + Duration::from_secs(1));
+
+        let result = tokio::time::timeout(Duration::from_secs(5), run)
+            .await
+            .expect("export should end once FFmpeg is terminated")
+            .unwrap();
+        assert!(matches!(result, Err(ExportError::Cancelled)));
+        assert_eq!(exporter.get_progress().await.unwrap().status, ExportStatus::Cancelled);
+        assert!(!scratch.exists());
+        assert!(!output.exists());
+    }
+
     #[test]
     fn test_parse_ffmpeg_time() {
         assert_eq!(parse_ffmpeg_time("00:00:00.000"), Some(0));
@@ -505,4 +1390,64 @@ mod tests {
         assert_eq!(parse_ffmpeg_time("invalid"), None);
         assert_eq!(parse_ffmpeg_time("00:00"), None);
     }
+
+    #[test]
+    fn test_export_error_serializes_code_and_message() {
+        let cases = [
+            (ExportError::FfmpegMissing, "ffmpeg_missing"),
+            (ExportError::InvalidConfig("CRF 60 is out of range".to_string()), "invalid_config"),
+            (ExportError::DiskFull("No space left on device".to_string()), "disk_full"),
+            (ExportError::ProcessFailed("Invalid data found when processing input".to_string()), "process_failed"),
+            (ExportError::Cancelled, "cancelled"),
+            (ExportError::NotFound("export-1".to_string()), "not_found"),
+        ];
+
+        for (error, code) in cases {
+            let json = serde_json::to_value(&error).unwrap();
+            assert_eq!(json["code"], code);
+            assert_eq!(json["message"], error.to_string());
+            assert!(json.get("timelineErrors").is_none());
+        }
+
+        let error = ExportError::InvalidTimeline(vec![
+            TimelineError::EmptyClip { clip_id: "a".to_string(), trim_in: 0, trim_out: 0 },
+            TimelineError::MissingMedia { clip_id: "b".to_string(), file_path: "/media/b.mp4".to_string() },
+        ]);
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "invalid_timeline");
+        assert_eq!(json["message"], "Timeline can't be exported: Clip a has no length (trim 0ms to 0ms) (and 1 more)");
+        assert_eq!(json["timelineErrors"][1]["kind"], "missingMedia");
+        assert_eq!(json["timelineErrors"][1]["filePath"], "/media/b.mp4");
+        assert!(json.get("jobIndex").is_none());
+
+        // A batch job keeps its own code and timeline errors, and says which job it was
+        let json = serde_json::to_value(error.for_batch_job(1, "/tmp/b.mp4")).unwrap();
+        assert_eq!(json["code"], "invalid_timeline");
+        assert_eq!(json["jobIndex"], 1);
+        assert_eq!(json["timelineErrors"][1]["kind"], "missingMedia");
+        assert!(json["message"].as_str().unwrap().starts_with("Job 2 (/tmp/b.mp4): Timeline can't be exported"));
+    }
+
+    #[test]
+    fn test_failures_map_to_export_error_variants() {
+        let not_found = io::Error::from(io::ErrorKind::NotFound);
+        assert!(matches!(ExportError::from_spawn(&not_found), ExportError::FfmpegMissing));
+        assert!(matches!(
+            ExportError::from_render(anyhow::Error::new(not_found).context("Failed to spawn FFmpeg")),
+            ExportError::FfmpegMissing
+        ));
+
+        let full = io::Error::from(io::ErrorKind::StorageFull);
+        assert!(matches!(ExportError::from_io("Failed to move export", &full), ExportError::DiskFull(_)));
+
+        let stderr = "[mp4 @ 0x1] Error writing trailer: No space left on device";
+        assert!(matches!(ExportError::from_ffmpeg_errors(stderr), ExportError::DiskFull(_)));
+        match ExportError::from_ffmpeg_errors("Conversion failed!") {
+            ExportError::ProcessFailed(stderr) => assert_eq!(stderr, "Conversion failed!"),
+            other => panic!("expected ProcessFailed, got {:?}", other),
+        }
+
+        let permission = OutputDirError::PermissionDenied { path: "/Volumes/Backup".to_string() };
+        assert!(matches!(ExportError::from(permission), ExportError::InvalidConfig(_)));
+    }
 }
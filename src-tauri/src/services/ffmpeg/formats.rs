@@ -0,0 +1,205 @@
+//! Supported export formats
+//!
+//! The export formats offered to the user depend on which encoders the installed
+//! FFmpeg build ships with (e.g. libx265 is missing from many LGPL builds).
+//! `ffmpeg -encoders` is probed once and the result is cached for the app lifetime.
+//! The same probe lets encoders fail fast when a requested codec is missing.
+
+use crate::models::AudioCodec;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tokio::sync::Mutex;
+
+/// An export format the VideoExporter can produce
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatInfo {
+    /// Stable identifier (e.g. "mp4-h264")
+    pub id: String,
+
+    /// Human-readable label
+    pub label: String,
+
+    /// Output file extension (container)
+    pub extension: String,
+
+    /// FFmpeg video encoder passed as `ExportConfig::codec`
+    pub video_codec: String,
+
+    /// Audio codec passed as `ExportConfig::audio_codec`
+    pub audio_codec: AudioCodec,
+}
+
+/// All formats the exporter knows how to produce: (id, label, extension, video encoder, audio codec)
+const EXPORT_FORMATS: [(&str, &str, &str, &str, AudioCodec); 4] = [
+    ("mp4-h264", "MP4 (H.264)", "mp4", "libx264", AudioCodec::Aac),
+    ("mp4-h264-hw", "MP4 (H.264, hardware)", "mp4", "h264_videotoolbox", AudioCodec::Aac),
+    ("mp4-hevc", "MP4 (HEVC)", "mp4", "libx265", AudioCodec::Aac),
+    ("mov-prores", "MOV (ProRes, lossless audio)", "mov", "prores_ks", AudioCodec::Alac),
+];
+
+lazy_static::lazy_static! {
+    /// Cached result of the `ffmpeg -encoders` probe
+    static ref AVAILABLE_ENCODERS: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+}
+
+/// Parse `ffmpeg -encoders` output into the set of encoder names
+///
+/// Entries follow a `------` separator line and look like
+/// ` V....D libx264              libx264 H.264 / AVC / MPEG-4 AVC`.
+pub fn parse_encoders(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("------"))
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _flags = fields.next()?;
+            fields.next().map(|name| name.to_string())
+        })
+        .collect()
+}
+
+/// Filter the known export formats down to those whose encoders are all available
+pub fn supported_formats(encoders: &HashSet<String>) -> Vec<FormatInfo> {
+    EXPORT_FORMATS
+        .iter()
+        .filter(|(_, _, _, video_codec, audio_codec)| {
+            encoders.contains(*video_codec) && encoders.contains(audio_codec.ffmpeg_codec())
+        })
+        .map(|(id, label, extension, video_codec, audio_codec)| FormatInfo {
+            id: id.to_string(),
+            label: label.to_string(),
+            extension: extension.to_string(),
+            video_codec: video_codec.to_string(),
+            audio_codec: *audio_codec,
+        })
+        .collect()
+}
+
+/// Encoders of the installed FFmpeg (probed once, then cached)
+pub async fn get_available_encoders() -> Result<HashSet<String>> {
+    let mut cache = AVAILABLE_ENCODERS.lock().await;
+    if let Some(encoders) = cache.as_ref() {
+        return Ok(encoders.clone());
+    }
+
+    let output = tokio::process::Command::new(ffmpeg_sidecar::paths::ffmpeg_path())
+        .arg("-hide_banner")
+        .arg("-encoders")
+        .output()
+        .await
+        .context("Failed to execute ffmpeg -encoders")?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "ffmpeg -encoders failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let encoders = parse_encoders(&String::from_utf8_lossy(&output.stdout));
+
+    tracing::info!(
+        event = "ffmpeg_encoders_probed",
+        encoder_count = encoders.len(),
+        "Probed FFmpeg encoders"
+    );
+
+    *cache = Some(encoders.clone());
+    Ok(encoders)
+}
+
+/// Get the export formats supported by the installed FFmpeg
+pub async fn get_supported_formats() -> Result<Vec<FormatInfo>> {
+    let encoders = get_available_encoders().await?;
+    let formats = supported_formats(&encoders);
+
+    tracing::info!(
+        event = "export_formats_probed",
+        formats = ?formats.iter().map(|f| f.id.as_str()).collect::<Vec<_>>(),
+        "Resolved supported export formats"
+    );
+
+    Ok(formats)
+}
+
+/// Check that `encoder` is in the probed encoder set
+///
+/// # Returns
+/// * `Err(anyhow::Error)` - The FFmpeg build lacks the encoder (e.g. libx265 in LGPL builds)
+pub fn check_encoder_available(encoders: &HashSet<String>, encoder: &str) -> Result<()> {
+    if encoders.contains(encoder) {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(
+        "The bundled FFmpeg does not include the {} encoder. Choose H.264 instead.",
+        encoder
+    ))
+}
+
+/// Probe FFmpeg (cached) and fail if `encoder` is missing
+pub async fn ensure_encoder_available(encoder: &str) -> Result<()> {
+    check_encoder_available(&get_available_encoders().await?, encoder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENCODERS_OUTPUT: &str = "Encoders:
+ V..... = Video
+ A..... = Audio
+ S..... = Subtitle
+ .F.... = Frame-level multithreading
+ ------
+ V....D libx264              libx264 H.264 / AVC / MPEG-4 AVC / MPEG-4 part 10 (codec h264)
+ V....D h264_videotoolbox    VideoToolbox H.264 Encoder (codec h264)
+ VF..D. prores_ks            Apple ProRes (iCodec Pro) (codec prores)
+ A....D aac                  AAC (Advanced Audio Coding)
+ A....D alac                 ALAC (Apple Lossless Audio Codec)
+";
+
+    #[test]
+    fn test_parse_encoders() {
+        let encoders = parse_encoders(ENCODERS_OUTPUT);
+
+        assert_eq!(encoders.len(), 5);
+        assert!(encoders.contains("libx264"));
+        assert!(encoders.contains("prores_ks"));
+        assert!(encoders.contains("alac"));
+        // Legend lines before the separator are not encoders
+        assert!(!encoders.contains("="));
+    }
+
+    #[test]
+    fn test_supported_formats_excludes_missing_encoders() {
+        let encoders = parse_encoders(ENCODERS_OUTPUT);
+        let ids: Vec<String> = supported_formats(&encoders).into_iter().map(|f| f.id).collect();
+
+        // libx265 is not installed, so HEVC is not offered
+        assert_eq!(ids, vec!["mp4-h264", "mp4-h264-hw", "mov-prores"]);
+    }
+
+    #[test]
+    fn test_missing_encoder_error_names_it() {
+        let encoders = parse_encoders(ENCODERS_OUTPUT);
+
+        assert!(check_encoder_available(&encoders, "libx264").is_ok());
+        let err = check_encoder_available(&encoders, "libx265").unwrap_err();
+        assert!(err.to_string().contains("libx265"));
+    }
+
+    #[test]
+    fn test_supported_formats_requires_audio_encoder() {
+        let encoders: HashSet<String> = ["libx264", "prores_ks", "aac"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let ids: Vec<String> = supported_formats(&encoders).into_iter().map(|f| f.id).collect();
+
+        // ProRes needs ALAC
+        assert_eq!(ids, vec!["mp4-h264"]);
+    }
+}
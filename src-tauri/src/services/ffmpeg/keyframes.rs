@@ -0,0 +1,111 @@
+//! Keyframe timestamps for lossless trimming
+//!
+//! Stream-copy trims can only start cleanly on a keyframe. ffprobe with
+//! `-skip_frame nokey` decodes just the keyframes, so listing them is fast even
+//! for long recordings. Results are cached per file (path and modification
+//! time) since the UI asks again every time a trim handle is dragged.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tokio::process::Command;
+
+type KeyframeCacheKey = (PathBuf, Option<SystemTime>);
+
+lazy_static::lazy_static! {
+    static ref KEYFRAME_CACHE: Mutex<HashMap<KeyframeCacheKey, Arc<Vec<u64>>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Parse `ffprobe -show_entries frame=best_effort_timestamp_time -of csv=p=0` output
+///
+/// Each line holds one keyframe time in seconds. Frames without a timestamp
+/// (`N/A`) are skipped and negative times (edit lists) clamp to zero.
+///
+/// # Returns
+/// Sorted, de-duplicated keyframe times in milliseconds
+pub fn parse_keyframe_times(output: &str) -> Vec<u64> {
+    let mut times: Vec<u64> = output
+        .lines()
+        .filter_map(|line| line.split(',').next())
+        .filter_map(|field| field.trim().parse::<f64>().ok())
+        .filter(|secs| secs.is_finite())
+        .map(|secs| (secs.max(0.0) * 1000.0).round() as u64)
+        .collect();
+
+    times.sort_unstable();
+    times.dedup();
+    times
+}
+
+/// List the keyframe times of the first video stream of `path`
+///
+/// Files with sparse keyframes (e.g. screen recordings with a long GOP) may
+/// return only a handful of entries, or just the first frame; callers should
+/// fall back to re-encoding when no keyframe is close enough.
+///
+/// # Returns
+/// * `Ok(Vec<u64>)` - Sorted keyframe times in milliseconds
+/// * `Err(anyhow::Error)` - ffprobe failed or the file has no video keyframes
+pub async fn get_keyframes(path: &Path) -> Result<Arc<Vec<u64>>> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let key = (path.to_path_buf(), modified);
+
+    if let Some(cached) = KEYFRAME_CACHE.lock().unwrap().get(&key) {
+        return Ok(cached.clone());
+    }
+
+    let output = Command::new("ffprobe")
+        .args([
+            "-v", "error",
+            "-select_streams", "v:0",
+            "-skip_frame", "nokey",
+            "-show_entries", "frame=best_effort_timestamp_time",
+            "-of", "csv=p=0",
+        ])
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to execute ffprobe")?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "FFprobe failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let keyframes = parse_keyframe_times(&String::from_utf8_lossy(&output.stdout));
+    if keyframes.is_empty() {
+        return Err(anyhow::anyhow!("No video keyframes found in {}", path.display()));
+    }
+
+    tracing::debug!(
+        event = "keyframes_listed",
+        path = %path.display(),
+        count = keyframes.len(),
+        "Listed keyframes"
+    );
+
+    let keyframes = Arc::new(keyframes);
+    KEYFRAME_CACHE.lock().unwrap().insert(key, keyframes.clone());
+    Ok(keyframes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keyframe_output_to_sorted_ms() {
+        // B-frame reordering and side data can leave lines out of order or with extra fields
+        let output = "0.000000\n4.004000,\n2.002000\nN/A\n6.006000\n2.002000\n-0.033000\n";
+        assert_eq!(parse_keyframe_times(output), [0, 2002, 4004, 6006]);
+
+        // Sparse keyframes: a single entry is still a valid result
+        assert_eq!(parse_keyframe_times("0.000000\n"), [0]);
+        assert!(parse_keyframe_times("").is_empty());
+    }
+}
@@ -2,8 +2,47 @@ pub mod exporter;
 pub mod encoder;
 pub mod compositor;
 pub mod audio_mixer;
+pub mod repair;
+pub mod formats;
+pub mod sink;
+pub mod rtmp;
+pub mod batch;
+pub mod silence;
+pub mod background;
+pub mod av_sync;
+pub mod keyframes;
+pub mod raw_dump;
+pub mod stream_copy;
+pub mod transition;
+pub mod watermark;
 
-pub use exporter::{VideoExporter, check_ffmpeg_available};
-pub use encoder::{FFmpegEncoder, TimestampedFrame, AudioInputConfig};
-pub use compositor::{FFmpegCompositor, PipConfig, CompositorFrame};
+pub use exporter::{validate_export_config, ExportError, VideoExporter, check_ffmpeg_available};
+pub use encoder::{
+    available_cores, default_encode_threads, even_dimensions, resolve_encode_threads,
+    scale_to_height, AudioInputConfig, EncodeContext, FFmpegEncoder, TimestampedFrame,
+};
+pub use compositor::{
+    build_pip_composition_args, build_pip_filter, build_styled_pip_filter, run_pip_composition, CompositionProgress,
+    CompositorFrame, FFmpegCompositor, PipAudioInputs, PipConfig, PipEncoding, PipShape,
+    PIP_SYSTEM_AUDIO_CHANNELS, PIP_SYSTEM_AUDIO_SAMPLE_RATE,
+};
 pub use audio_mixer::{AudioClipMixInfo, build_audio_mix_filter};
+pub use repair::{
+    optimize_for_streaming, repair_recording, verify_recording, IntegrityStatus, ProbeResult,
+    RepairDecision,
+};
+pub use formats::{ensure_encoder_available, get_available_encoders, get_supported_formats, FormatInfo};
+pub use sink::{FrameSink, MemoryFrameSink};
+pub use rtmp::{validate_rtmp_url, RtmpSink};
+pub use batch::{run_export_batch, running_job_progress, validate_batch, validate_batch_jobs};
+pub use silence::split_by_silence;
+pub use background::{background_input_args, build_background_filter, pad_color};
+pub use av_sync::{measure_av_sync, AvSyncReport};
+pub use keyframes::get_keyframes;
+pub use raw_dump::{raw_dump_max_bytes, raw_dump_path, replay_raw_capture, RawDumpSink, RawDumpWriter};
+pub use transition::{
+    build_acrossfade_filter, build_xfade_filter, chain_duration_ms, clamp_transitions, transition_runs,
+    xfade_offsets_ms,
+};
+pub use stream_copy::{stream_copy_candidate, StreamCopyTrim};
+pub use watermark::{build_watermark_filter, overlay_position, validate_watermark};
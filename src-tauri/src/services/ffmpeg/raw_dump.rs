@@ -0,0 +1,344 @@
+//! Raw capture dumps for replaying problem recordings
+//!
+//! With `CLIPPY_RAW_CAPTURE_DUMP=1` set, screen recordings also write every
+//! captured BGRA frame, with its timestamp, to `<recording>.capture.bgra` in
+//! scratch space. `replay_raw_capture` feeds a dump back through
+//! `FFmpegEncoder`, so an encoding bug can be reproduced offline with the exact
+//! frames that triggered it.
+//!
+//! Dumps are uncompressed (a 1080p frame is ~8MB, so ~240MB per second at
+//! 30 FPS) and stop growing once `CLIPPY_RAW_CAPTURE_DUMP_MAX_MB` is reached.
+//!
+//! ```text
+//! "CLPRAW01"                                  file magic
+//! u64 timestamp_ms, u32 width, u32 height,    frame header (little endian)
+//! u32 data_len, [u8; data_len]                BGRA pixels
+//! ...
+//! ```
+
+use super::encoder::{FFmpegEncoder, TimestampedFrame};
+use super::sink::FrameSink;
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Environment variable enabling raw capture dumps (`1` or `true`)
+pub const RAW_DUMP_ENV: &str = "CLIPPY_RAW_CAPTURE_DUMP";
+
+/// Environment variable overriding the dump size cap, in megabytes
+pub const RAW_DUMP_MAX_MB_ENV: &str = "CLIPPY_RAW_CAPTURE_DUMP_MAX_MB";
+
+/// Dump size cap when `RAW_DUMP_MAX_MB_ENV` is not set (~8s of 1080p at 30 FPS)
+pub const DEFAULT_RAW_DUMP_MAX_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Frame rate used for replay when the dump has too few frames to measure it
+const REPLAY_FALLBACK_FPS: u32 = 30;
+
+const RAW_DUMP_MAGIC: &[u8; 8] = b"CLPRAW01";
+const FRAME_HEADER_BYTES: u64 = 8 + 4 + 4 + 4;
+
+/// Dump size cap from `lookup`, or `None` when dumping is disabled
+fn raw_dump_max_bytes_from(lookup: impl Fn(&str) -> Option<String>) -> Option<u64> {
+    let enabled = lookup(RAW_DUMP_ENV)
+        .map(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true"))
+        .unwrap_or(false);
+    if !enabled {
+        return None;
+    }
+
+    Some(
+        lookup(RAW_DUMP_MAX_MB_ENV)
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(|mb| mb * 1024 * 1024)
+            .unwrap_or(DEFAULT_RAW_DUMP_MAX_BYTES),
+    )
+}
+
+/// Dump size cap from the environment, or `None` when dumping is disabled
+pub fn raw_dump_max_bytes() -> Option<u64> {
+    raw_dump_max_bytes_from(|key| std::env::var(key).ok())
+}
+
+/// Scratch path of the dump for a recording's intermediate file
+pub fn raw_dump_path(intermediate: &Path) -> PathBuf {
+    intermediate.with_extension("capture.bgra")
+}
+
+/// Appends frames to a raw dump until its size cap is reached
+pub struct RawDumpWriter {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    max_bytes: u64,
+    bytes_written: u64,
+    frames_written: u64,
+    capped: bool,
+}
+
+impl RawDumpWriter {
+    /// Create (or truncate) a dump at `path` holding at most `max_bytes`
+    pub fn create(path: &Path, max_bytes: u64) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create raw capture dump {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(RAW_DUMP_MAGIC)?;
+
+        Ok(Self {
+            writer,
+            path: path.to_path_buf(),
+            max_bytes,
+            bytes_written: RAW_DUMP_MAGIC.len() as u64,
+            frames_written: 0,
+            capped: false,
+        })
+    }
+
+    /// Append a frame; returns `false` (and drops it) once the cap is reached
+    pub fn write_frame(&mut self, frame: &TimestampedFrame) -> Result<bool> {
+        let frame_bytes = FRAME_HEADER_BYTES + frame.data.len() as u64;
+        if self.capped || self.bytes_written + frame_bytes > self.max_bytes {
+            if !self.capped {
+                tracing::warn!(
+                    event = "raw_dump_capped",
+                    path = %self.path.display(),
+                    frames = self.frames_written,
+                    bytes = self.bytes_written,
+                    "Raw capture dump reached its size cap, later frames are not dumped"
+                );
+                self.capped = true;
+            }
+            return Ok(false);
+        }
+
+        self.writer.write_all(&frame.timestamp_ms.to_le_bytes())?;
+        self.writer.write_all(&frame.width.to_le_bytes())?;
+        self.writer.write_all(&frame.height.to_le_bytes())?;
+        self.writer.write_all(&(frame.data.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&frame.data)?;
+
+        self.bytes_written += frame_bytes;
+        self.frames_written += 1;
+        Ok(true)
+    }
+
+    /// Frames dumped so far
+    pub fn frames_written(&self) -> u64 {
+        self.frames_written
+    }
+
+    /// Flush buffered frames to disk
+    pub fn finish(mut self) -> Result<PathBuf> {
+        self.writer.flush()?;
+        tracing::info!(
+            event = "raw_dump_finished",
+            path = %self.path.display(),
+            frames = self.frames_written,
+            bytes = self.bytes_written,
+            "Raw capture dump written"
+        );
+        Ok(self.path)
+    }
+}
+
+/// Reads frames back from a raw dump in capture order
+pub struct RawDumpReader {
+    reader: BufReader<File>,
+}
+
+impl RawDumpReader {
+    /// Open a dump, checking its magic
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open raw capture dump {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).context("Raw capture dump is empty")?;
+        if &magic != RAW_DUMP_MAGIC {
+            return Err(anyhow::anyhow!("{} is not a raw capture dump", path.display()));
+        }
+
+        Ok(Self { reader })
+    }
+
+    /// Next frame, or `None` at the end of the dump
+    pub fn next_frame(&mut self) -> Result<Option<TimestampedFrame>> {
+        let mut timestamp = [0u8; 8];
+        match self.reader.read_exact(&mut timestamp) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut field = [0u8; 4];
+        let mut read_u32 = |reader: &mut BufReader<File>| -> Result<u32> {
+            reader.read_exact(&mut field).context("Truncated frame header")?;
+            Ok(u32::from_le_bytes(field))
+        };
+        let width = read_u32(&mut self.reader)?;
+        let height = read_u32(&mut self.reader)?;
+        let data_len = read_u32(&mut self.reader)?;
+
+        let mut data = vec![0u8; data_len as usize];
+        self.reader.read_exact(&mut data).context("Truncated frame data")?;
+
+        Ok(Some(TimestampedFrame {
+            data,
+            timestamp_ms: u64::from_le_bytes(timestamp),
+            width,
+            height,
+        }))
+    }
+}
+
+/// Frame sink that dumps every frame before passing it on to `inner`
+pub struct RawDumpSink<S: FrameSink> {
+    inner: S,
+    dump: Option<RawDumpWriter>,
+}
+
+impl<S: FrameSink> RawDumpSink<S> {
+    pub fn new(inner: S, dump: RawDumpWriter) -> Self {
+        Self { inner, dump: Some(dump) }
+    }
+}
+
+impl<S: FrameSink> FrameSink for RawDumpSink<S> {
+    async fn write_frame(&mut self, frame: &TimestampedFrame) -> Result<()> {
+        // Dump failures never stop the recording itself
+        if let Some(dump) = self.dump.as_mut() {
+            if let Err(e) = dump.write_frame(frame) {
+                tracing::warn!(event = "raw_dump_failed", error = %e, "Raw capture dump stopped");
+                self.dump = None;
+            }
+        }
+        self.inner.write_frame(frame).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        self.inner.check_health().await
+    }
+
+    async fn finalize(&mut self) -> Result<()> {
+        if let Some(dump) = self.dump.take() {
+            if let Err(e) = dump.finish() {
+                tracing::warn!(event = "raw_dump_failed", error = %e, "Failed to flush raw capture dump");
+            }
+        }
+        self.inner.finalize().await
+    }
+}
+
+/// Frame rate of a dump estimated from its first and last timestamps
+fn replay_fps(frame_count: u64, first_ms: u64, last_ms: u64) -> u32 {
+    if frame_count < 2 || last_ms <= first_ms {
+        return REPLAY_FALLBACK_FPS;
+    }
+    let fps = (frame_count - 1) as f64 * 1000.0 / (last_ms - first_ms) as f64;
+    (fps.round() as u32).clamp(1, 120)
+}
+
+/// Re-encode a raw capture dump to `output` with the recording encoder
+///
+/// # Returns
+/// * `Ok(u64)` - Number of frames encoded
+/// * `Err(anyhow::Error)` - The dump is unreadable or empty, or encoding failed
+pub async fn replay_raw_capture(dump_path: &Path, output: &Path) -> Result<u64> {
+    // First pass: dimensions and timing, without holding frames in memory
+    let mut reader = RawDumpReader::open(dump_path)?;
+    let first = reader
+        .next_frame()?
+        .ok_or_else(|| anyhow::anyhow!("Raw capture dump {} has no frames", dump_path.display()))?;
+    let mut frame_count = 1u64;
+    let mut last_ms = first.timestamp_ms;
+    while let Some(frame) = reader.next_frame()? {
+        frame_count += 1;
+        last_ms = frame.timestamp_ms;
+    }
+
+    let fps = replay_fps(frame_count, first.timestamp_ms, last_ms);
+    tracing::info!(
+        event = "raw_dump_replay_start",
+        dump = %dump_path.display(),
+        output = %output.display(),
+        frames = frame_count,
+        width = first.width,
+        height = first.height,
+        fps = fps,
+        "Replaying raw capture dump"
+    );
+
+    let mut encoder = FFmpegEncoder::new(output.to_path_buf(), first.width, first.height, fps)?;
+    encoder.start_encoding().await?;
+
+    let mut reader = RawDumpReader::open(dump_path)?;
+    let mut encoded = 0u64;
+    while let Some(frame) = reader.next_frame()? {
+        encoder.write_frame(&frame).await?;
+        encoded += 1;
+    }
+    encoder.finalize().await?;
+
+    Ok(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(timestamp_ms: u64, width: u32, height: u32) -> TimestampedFrame {
+        TimestampedFrame {
+            data: vec![(timestamp_ms % 251) as u8; (width * height * 4) as usize],
+            timestamp_ms,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_dump_round_trip_and_size_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rec.capture.bgra");
+
+        // Room for exactly two 4x2 frames after the magic
+        let frame_bytes = FRAME_HEADER_BYTES + 4 * 2 * 4;
+        let mut writer = RawDumpWriter::create(&path, 8 + 2 * frame_bytes).unwrap();
+        assert!(writer.write_frame(&frame(0, 4, 2)).unwrap());
+        assert!(writer.write_frame(&frame(33, 4, 2)).unwrap());
+        assert!(!writer.write_frame(&frame(66, 4, 2)).unwrap());
+        assert_eq!(writer.frames_written(), 2);
+        writer.finish().unwrap();
+
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 8 + 2 * frame_bytes);
+
+        let mut reader = RawDumpReader::open(&path).unwrap();
+        let first = reader.next_frame().unwrap().unwrap();
+        assert_eq!((first.timestamp_ms, first.width, first.height), (0, 4, 2));
+        assert_eq!(first.data, frame(0, 4, 2).data);
+        let second = reader.next_frame().unwrap().unwrap();
+        assert_eq!(second.timestamp_ms, 33);
+        assert_eq!(second.data.len(), 32);
+        assert!(reader.next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_dump_is_opt_in_with_configurable_cap() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| vars.iter().find(|(k, _)| *k == key).map(|(_, v)| v.to_string())
+        };
+
+        assert_eq!(raw_dump_max_bytes_from(env(&[])), None);
+        assert_eq!(raw_dump_max_bytes_from(env(&[(RAW_DUMP_ENV, "0")])), None);
+        assert_eq!(
+            raw_dump_max_bytes_from(env(&[(RAW_DUMP_ENV, "1")])),
+            Some(DEFAULT_RAW_DUMP_MAX_BYTES)
+        );
+        assert_eq!(
+            raw_dump_max_bytes_from(env(&[(RAW_DUMP_ENV, "true"), (RAW_DUMP_MAX_MB_ENV, "64")])),
+            Some(64 * 1024 * 1024)
+        );
+
+        assert_eq!(replay_fps(61, 0, 1_000), 60);
+        assert_eq!(replay_fps(1, 0, 0), REPLAY_FALLBACK_FPS);
+    }
+}
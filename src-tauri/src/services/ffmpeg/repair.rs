@@ -0,0 +1,564 @@
+//! Recording repair for interrupted MP4s
+//!
+//! If the app crashes mid-recording, FFmpeg never writes the trailing moov atom
+//! and the file won't play. Repair remuxes the file with stream copy
+//! (`ffmpeg -i broken -c copy -movflags +faststart repaired.mp4`), which recovers
+//! whatever FFmpeg can still demux. Files that already probe cleanly are left alone.
+//!
+//! `verify_recording` runs the same probe right after a recording is finalized so an
+//! unplayable file is caught (and repaired if possible) before reporting success.
+//!
+//! `optimize_for_streaming` uses the same remux on files that already play: real-time
+//! recordings write the moov atom last, which makes seeking slow until it is moved up front.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Result of probing a recording with ffprobe
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeResult {
+    /// ffprobe exited successfully
+    pub success: bool,
+    /// At least one video stream was found
+    pub has_video: bool,
+    /// Container duration in milliseconds, if reported
+    pub duration_ms: Option<u64>,
+    /// Frame count of the first video stream, if reported
+    pub video_frames: Option<u64>,
+}
+
+impl ProbeResult {
+    /// Result for a file ffprobe could not read
+    fn unreadable() -> Self {
+        Self {
+            success: false,
+            has_video: false,
+            duration_ms: None,
+            video_frames: None,
+        }
+    }
+}
+
+/// What to do with a recording after probing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairDecision {
+    /// File is playable, skip repair
+    AlreadyValid,
+    /// File is broken, attempt a remux
+    Repair,
+}
+
+/// Decide whether a probed file needs repair
+///
+/// A file is valid only if ffprobe succeeded, found a video stream,
+/// reported a non-zero duration and did not report zero frames
+/// (a missing moov atom fails one of these).
+pub fn decide_repair(probe: &ProbeResult) -> RepairDecision {
+    let valid = probe.success
+        && probe.has_video
+        && probe.duration_ms.unwrap_or(0) > 0
+        && probe.video_frames != Some(0);
+    if valid {
+        RepairDecision::AlreadyValid
+    } else {
+        RepairDecision::Repair
+    }
+}
+
+/// Path for the repaired copy: `<stem>-repaired.mp4` next to the original
+pub fn repaired_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    path.with_file_name(format!("{}-repaired.mp4", stem))
+}
+
+/// Probe a file with ffprobe
+///
+/// A failing ffprobe run (or an empty file) is reported as `success: false`,
+/// not an error; only failing to launch ffprobe is an error.
+pub async fn probe_recording(path: &Path) -> Result<ProbeResult> {
+    // Empty files can't be valid; skip spawning ffprobe
+    let file_size = std::fs::metadata(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .len();
+    if file_size == 0 {
+        return Ok(ProbeResult::unreadable());
+    }
+
+    let output = Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-print_format")
+        .arg("json")
+        .arg("-show_format")
+        .arg("-show_streams")
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to execute ffprobe")?;
+
+    if !output.status.success() {
+        return Ok(ProbeResult::unreadable());
+    }
+
+    let parsed: serde_json::Value =
+        serde_json::from_slice(&output.stdout).unwrap_or(serde_json::Value::Null);
+
+    let video_stream = parsed["streams"].as_array().and_then(|streams| {
+        streams
+            .iter()
+            .find(|s| s["codec_type"].as_str() == Some("video"))
+    });
+    let has_video = video_stream.is_some();
+    let video_frames = video_stream
+        .and_then(|s| s["nb_frames"].as_str())
+        .and_then(|n| n.parse::<u64>().ok());
+
+    let duration_ms = parsed["format"]["duration"]
+        .as_str()
+        .and_then(|d| d.parse::<f64>().ok())
+        .map(|secs| (secs * 1000.0) as u64);
+
+    Ok(ProbeResult {
+        success: true,
+        has_video,
+        duration_ms,
+        video_frames,
+    })
+}
+
+/// Repair a recording if needed
+///
+/// # Returns
+///
+/// * `Ok(path)` - The original path if it was already valid, otherwise the repaired copy
+/// * `Err(anyhow::Error)` - Remux failed or the result is still unplayable
+pub async fn repair_recording(path: &Path) -> Result<PathBuf> {
+    let probe = probe_recording(path).await?;
+
+    if decide_repair(&probe) == RepairDecision::AlreadyValid {
+        tracing::info!(
+            event = "repair_skipped",
+            path = %path.display(),
+            duration_ms = probe.duration_ms,
+            "Recording is already valid, skipping repair"
+        );
+        return Ok(path.to_path_buf());
+    }
+
+    let output_path = repaired_path(path);
+    tracing::info!(
+        event = "repair_start",
+        path = %path.display(),
+        output_path = %output_path.display(),
+        "Repairing recording with stream copy"
+    );
+
+    let output = Command::new("ffmpeg")
+        .arg("-v")
+        .arg("error")
+        .arg("-err_detect")
+        .arg("ignore_err")
+        .arg("-i")
+        .arg(path)
+        .arg("-c")
+        .arg("copy")
+        .arg("-movflags")
+        .arg("+faststart")
+        .arg("-y")
+        .arg(&output_path)
+        .output()
+        .await
+        .context("Failed to execute ffmpeg")?;
+
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&output_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!(
+            "FFmpeg could not recover the recording: {}",
+            stderr.trim()
+        ));
+    }
+
+    let repaired_probe = probe_recording(&output_path).await?;
+    if decide_repair(&repaired_probe) != RepairDecision::AlreadyValid {
+        let _ = std::fs::remove_file(&output_path);
+        return Err(anyhow::anyhow!(
+            "Repaired file is still unplayable (no recoverable video data)"
+        ));
+    }
+
+    tracing::info!(
+        event = "repair_complete",
+        output_path = %output_path.display(),
+        duration_ms = repaired_probe.duration_ms,
+        "Recording repaired"
+    );
+
+    Ok(output_path)
+}
+
+/// Outcome of the post-recording integrity check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IntegrityStatus {
+    /// File probed as playable
+    Verified,
+    /// File was unplayable and a repaired copy was produced
+    Repaired,
+    /// File is unplayable and repair failed
+    Degraded,
+    /// ffprobe could not be run, so the file was not checked
+    Unverified,
+}
+
+/// Verify a freshly finalized recording, repairing it if it is unplayable
+///
+/// # Returns
+///
+/// The path to hand to the user (the repaired copy when repair succeeded)
+/// and the integrity status.
+pub async fn verify_recording(path: &Path) -> (PathBuf, IntegrityStatus) {
+    let probe = match probe_recording(path).await {
+        Ok(probe) => probe,
+        Err(e) => {
+            tracing::warn!(
+                event = "integrity_check_skipped",
+                path = %path.display(),
+                error = %e,
+                "Could not probe recording, skipping integrity check"
+            );
+            return (path.to_path_buf(), IntegrityStatus::Unverified);
+        }
+    };
+
+    if decide_repair(&probe) == RepairDecision::AlreadyValid {
+        return (path.to_path_buf(), IntegrityStatus::Verified);
+    }
+
+    tracing::warn!(
+        event = "integrity_check_failed",
+        path = %path.display(),
+        probe = ?probe,
+        "Recording failed integrity check, attempting repair"
+    );
+
+    match repair_recording(path).await {
+        Ok(repaired) => (repaired, IntegrityStatus::Repaired),
+        Err(e) => {
+            tracing::error!(
+                event = "integrity_repair_failed",
+                path = %path.display(),
+                error = %e,
+                "Recording is unplayable and could not be repaired"
+            );
+            (path.to_path_buf(), IntegrityStatus::Degraded)
+        }
+    }
+}
+
+/// Temporary file for the faststart remux, next to the original so the final rename is atomic
+pub fn faststart_temp_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    path.with_file_name(format!("{}.faststart-tmp.mp4", stem))
+}
+
+/// `ftyp` major brand of QuickTime movies, which an MP4 remux would rewrite
+const QUICKTIME_BRAND: &[u8; 4] = b"qt  ";
+
+/// Whether a file is an MP4: an `.mp4`/`.m4v` name and an ISO BMFF `ftyp` box
+/// whose major brand isn't QuickTime (`qt  `)
+pub fn has_mp4_signature(path: &Path) -> Result<bool> {
+    let mp4_extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("mp4") || ext.eq_ignore_ascii_case("m4v"));
+    if !mp4_extension {
+        return Ok(false);
+    }
+
+    let mut file = std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut header = [0u8; 12];
+    match file.read_exact(&mut header) {
+        Ok(()) => Ok(&header[4..8] == b"ftyp" && &header[8..12] != QUICKTIME_BRAND),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Whether the `moov` atom comes before `mdat`, found by walking the top-level boxes
+pub fn is_faststart(path: &Path) -> Result<bool> {
+    let mut file = std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let file_len = file.metadata()?.len();
+    let mut offset = 0u64;
+
+    while offset + 8 <= file_len {
+        file.seek(SeekFrom::Start(offset))?;
+        let mut header = [0u8; 8];
+        file.read_exact(&mut header)?;
+        let size32 = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+
+        match &header[4..8] {
+            b"moov" => return Ok(true),
+            b"mdat" => return Ok(false),
+            _ => {}
+        }
+
+        let box_size = match size32 {
+            // Box runs to the end of the file
+            0 => return Ok(false),
+            // 64-bit size follows the type
+            1 => {
+                let mut large = [0u8; 8];
+                file.read_exact(&mut large)?;
+                u64::from_be_bytes(large)
+            }
+            size => size,
+        };
+        if box_size < 8 {
+            return Err(anyhow::anyhow!("Malformed MP4 box at offset {}", offset));
+        }
+        offset += box_size;
+    }
+
+    Ok(false)
+}
+
+/// Rewrite a playable MP4 with the moov atom up front (`-movflags +faststart`)
+///
+/// Stream copy into a temporary file next to the original, then rename over it,
+/// so the original is untouched if anything fails. Files that are already
+/// faststart are left alone.
+///
+/// # Returns
+/// * `Ok(())` - File is faststart (rewritten or already was)
+/// * `Err(anyhow::Error)` - Not an MP4, not playable, or the remux failed
+pub async fn optimize_for_streaming(path: &Path) -> Result<()> {
+    if !has_mp4_signature(path)? {
+        return Err(anyhow::anyhow!("Not an MP4 file: {}", path.display()));
+    }
+    let probe = probe_recording(path).await?;
+    if decide_repair(&probe) != RepairDecision::AlreadyValid {
+        return Err(anyhow::anyhow!(
+            "Recording is not playable, repair it first: {}",
+            path.display()
+        ));
+    }
+
+    if is_faststart(path)? {
+        tracing::info!(
+            event = "faststart_skipped",
+            path = %path.display(),
+            "Recording already has the moov atom up front"
+        );
+        return Ok(());
+    }
+
+    let temp_path = faststart_temp_path(path);
+    tracing::info!(
+        event = "faststart_start",
+        path = %path.display(),
+        temp_path = %temp_path.display(),
+        "Moving moov atom to the front with stream copy"
+    );
+
+    let output = Command::new("ffmpeg")
+        .arg("-v")
+        .arg("error")
+        .arg("-i")
+        .arg(path)
+        .arg("-map")
+        .arg("0")
+        .arg("-c")
+        .arg("copy")
+        .arg("-movflags")
+        .arg("+faststart")
+        .arg("-f")
+        .arg("mp4")
+        .arg("-y")
+        .arg(&temp_path)
+        .output()
+        .await
+        .context("Failed to execute ffmpeg")?;
+
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&temp_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("FFmpeg remux failed: {}", stderr.trim()));
+    }
+
+    let remuxed_probe = probe_recording(&temp_path).await?;
+    if decide_repair(&remuxed_probe) != RepairDecision::AlreadyValid {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(anyhow::anyhow!("Remuxed file is not playable"));
+    }
+
+    if let Err(e) = std::fs::rename(&temp_path, path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e).with_context(|| format!("Failed to replace {}", path.display()));
+    }
+
+    tracing::info!(
+        event = "faststart_complete",
+        path = %path.display(),
+        duration_ms = remuxed_probe.duration_ms,
+        "Recording optimized for streaming"
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide_repair_skips_valid_file() {
+        let probe = ProbeResult {
+            success: true,
+            has_video: true,
+            duration_ms: Some(12_500),
+            video_frames: Some(375),
+        };
+        assert_eq!(decide_repair(&probe), RepairDecision::AlreadyValid);
+    }
+
+    #[test]
+    fn test_decide_repair_repairs_broken_file() {
+        // Missing moov atom: ffprobe fails outright
+        let failed = ProbeResult::unreadable();
+        assert_eq!(decide_repair(&failed), RepairDecision::Repair);
+
+        // Probe succeeds but reports no duration
+        let no_duration = ProbeResult {
+            success: true,
+            has_video: true,
+            duration_ms: Some(0),
+            video_frames: None,
+        };
+        assert_eq!(decide_repair(&no_duration), RepairDecision::Repair);
+
+        // Duration reported but no frames were encoded
+        let no_frames = ProbeResult {
+            success: true,
+            has_video: true,
+            duration_ms: Some(5_000),
+            video_frames: Some(0),
+        };
+        assert_eq!(decide_repair(&no_frames), RepairDecision::Repair);
+    }
+
+    #[test]
+    fn test_repaired_path() {
+        let path = Path::new("/tmp/clippy/recording-123.mp4");
+        assert_eq!(
+            repaired_path(path),
+            PathBuf::from("/tmp/clippy/recording-123-repaired.mp4")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zero_byte_output_triggers_repair() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("recording-empty.mp4");
+        std::fs::write(&path, b"").unwrap();
+
+        let probe = probe_recording(&path).await.unwrap();
+        assert!(!probe.success);
+        assert_eq!(decide_repair(&probe), RepairDecision::Repair);
+    }
+
+    fn mp4_box(kind: &[u8; 4], payload_len: usize) -> Vec<u8> {
+        let mut b = ((payload_len + 8) as u32).to_be_bytes().to_vec();
+        b.extend_from_slice(kind);
+        b.resize(payload_len + 8, 0);
+        b
+    }
+
+    #[tokio::test]
+    async fn test_moov_position_and_non_mp4_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let moov_last = temp_dir.path().join("moov-last.mp4");
+        std::fs::write(&moov_last, [mp4_box(b"ftyp", 16), mp4_box(b"mdat", 64), mp4_box(b"moov", 32)].concat()).unwrap();
+        assert!(has_mp4_signature(&moov_last).unwrap());
+        assert!(!is_faststart(&moov_last).unwrap());
+
+        let moov_first = temp_dir.path().join("moov-first.mp4");
+        std::fs::write(&moov_first, [mp4_box(b"ftyp", 16), mp4_box(b"moov", 32), mp4_box(b"mdat", 64)].concat()).unwrap();
+        assert!(is_faststart(&moov_first).unwrap());
+
+        let not_mp4 = temp_dir.path().join("notes.mp4");
+        std::fs::write(&not_mp4, b"definitely not a video").unwrap();
+        assert!(!has_mp4_signature(&not_mp4).unwrap());
+        let err = optimize_for_streaming(&not_mp4).await.unwrap_err();
+        assert!(err.to_string().contains("Not an MP4"));
+    }
+
+    #[tokio::test]
+    async fn test_quicktime_movies_are_not_remuxed_as_mp4() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let ftyp = |brand: &[u8; 4]| {
+            let mut b = mp4_box(b"ftyp", 16);
+            b[8..12].copy_from_slice(brand);
+            b
+        };
+
+        let isom = temp_dir.path().join("recording.mp4");
+        std::fs::write(&isom, [ftyp(b"isom"), mp4_box(b"moov", 32)].concat()).unwrap();
+        assert!(has_mp4_signature(&isom).unwrap());
+
+        // QuickTime brand, even with an .mp4 name
+        let quicktime = temp_dir.path().join("renamed.mp4");
+        std::fs::write(&quicktime, [ftyp(b"qt  "), mp4_box(b"moov", 32)].concat()).unwrap();
+        assert!(!has_mp4_signature(&quicktime).unwrap());
+        assert!(optimize_for_streaming(&quicktime).await.unwrap_err().to_string().contains("Not an MP4"));
+
+        // MP4 brand, but a .mov name
+        let mov = temp_dir.path().join("recording.mov");
+        std::fs::write(&mov, [ftyp(b"isom"), mp4_box(b"moov", 32)].concat()).unwrap();
+        assert!(!has_mp4_signature(&mov).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_optimize_for_streaming_replaces_file_in_place() {
+        let ffmpeg_available = std::process::Command::new("ffmpeg")
+            .arg("-version")
+            .output()
+            .is_ok_and(|o| o.status.success());
+        if !ffmpeg_available {
+            println!("Skipping faststart test - ffmpeg not available");
+            return;
+        }
+
+        // FFmpeg writes the moov atom last by default, like the real-time encoder
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("recording.mp4");
+        let status = std::process::Command::new("ffmpeg")
+            .args(["-v", "error", "-f", "lavfi", "-i", "testsrc=size=320x240:rate=30:duration=2"])
+            .args(["-pix_fmt", "yuv420p", "-y"])
+            .arg(&path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        assert!(!is_faststart(&path).unwrap());
+        let original_size = std::fs::metadata(&path).unwrap().len();
+
+        optimize_for_streaming(&path).await.unwrap();
+
+        assert!(is_faststart(&path).unwrap());
+        assert_eq!(decide_repair(&probe_recording(&path).await.unwrap()), RepairDecision::AlreadyValid);
+        let size = std::fs::metadata(&path).unwrap().len();
+        assert!(size.abs_diff(original_size) * 10 < original_size, "{} vs {}", size, original_size);
+        assert!(!faststart_temp_path(&path).exists());
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+}
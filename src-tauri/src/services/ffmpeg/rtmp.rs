@@ -0,0 +1,643 @@
+//! RTMP live-streaming sink
+//!
+//! Pipes raw BGRA frames into `ffmpeg ... -f flv rtmp://...` so a screen capture
+//! can be streamed live. Encoding is tuned for latency (x264 `veryfast` +
+//! `zerolatency`, constant bitrate, 2s keyframe interval) and a silent AAC track
+//! is added because most ingest servers reject video-only streams.
+//!
+//! If the server drops the connection FFmpeg exits and the next write fails.
+//! The sink then tears FFmpeg down, holds up to a second of frames and respawns
+//! the stream process with exponential backoff, emitting `stream-reconnecting`.
+//! A reconnect only counts once the new connection has stayed up for
+//! `RECONNECT_STABLE_AFTER`; `stream-reconnected` is emitted then and the attempt
+//! count is reset. A server that accepts and immediately drops the connection
+//! therefore still uses up attempts, and after `MAX_RECONNECT_ATTEMPTS` failed
+//! attempts the write fails with FFmpeg's own error message.
+
+use super::encoder::{even_dimensions, TimestampedFrame};
+use super::sink::FrameSink;
+use anyhow::{Context, Result};
+use std::collections::VecDeque;
+use std::io::Read;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+/// Video bitrate for live streams (kbps)
+pub const DEFAULT_STREAM_BITRATE_KBPS: u32 = 4500;
+
+/// Reconnect attempts before the stream is given up
+pub const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+
+/// Seconds of frames held while reconnecting (oldest frames are dropped beyond this)
+const RECONNECT_BUFFER_SECS: u32 = 1;
+
+/// Delay before the first reconnect attempt; doubles with each attempt
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound on the delay between reconnect attempts
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(8);
+
+/// How long a reconnected stream must stay up before past attempts are forgotten
+pub const RECONNECT_STABLE_AFTER: Duration = Duration::from_secs(5);
+
+/// Connection state of a live stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamState {
+    /// FFmpeg is running and accepting frames
+    Connected,
+    /// Connection lost; `attempt` is the reconnect attempt in progress (1-based)
+    Reconnecting { attempt: u32 },
+    /// Reconnect attempts exhausted
+    Failed,
+}
+
+/// Tracks reconnect attempts for a stream
+#[derive(Debug, Clone)]
+pub struct ReconnectStateMachine {
+    state: StreamState,
+    max_attempts: u32,
+    /// Attempt that reconnected, and when, until the connection proves stable
+    probation: Option<(u32, Instant)>,
+}
+
+impl ReconnectStateMachine {
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            state: StreamState::Connected,
+            max_attempts,
+            probation: None,
+        }
+    }
+
+    pub fn state(&self) -> StreamState {
+        self.state
+    }
+
+    /// The connection dropped, or a reconnect attempt failed
+    ///
+    /// A drop before a reconnect has proven stable continues its attempt count.
+    pub fn connection_lost(&mut self) -> StreamState {
+        let previous_attempts = match self.state {
+            StreamState::Connected => self.probation.take().map_or(0, |(attempt, _)| attempt),
+            StreamState::Reconnecting { attempt } => attempt,
+            StreamState::Failed => self.max_attempts,
+        };
+        self.state = if previous_attempts < self.max_attempts {
+            StreamState::Reconnecting { attempt: previous_attempts + 1 }
+        } else {
+            StreamState::Failed
+        };
+        self.state
+    }
+
+    /// A reconnect attempt got FFmpeg running again (a failed stream stays failed)
+    ///
+    /// The attempt stays on probation until `confirm_stable`.
+    pub fn reconnected(&mut self, now: Instant) -> StreamState {
+        if let StreamState::Reconnecting { attempt } = self.state {
+            self.state = StreamState::Connected;
+            self.probation = Some((attempt, now));
+        }
+        self.state
+    }
+
+    /// Forget past attempts once a reconnect has stayed up for `RECONNECT_STABLE_AFTER`
+    ///
+    /// Returns the reconnect attempt that just proved stable, once.
+    pub fn confirm_stable(&mut self, now: Instant) -> Option<u32> {
+        match self.probation {
+            Some((attempt, since))
+                if self.state == StreamState::Connected
+                    && now.saturating_duration_since(since) >= RECONNECT_STABLE_AFTER =>
+            {
+                self.probation = None;
+                Some(attempt)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Delay before a reconnect attempt (500ms, 1s, 2s, ... capped at 8s)
+pub fn reconnect_delay(attempt: u32) -> Duration {
+    let factor = 1u32 << attempt.saturating_sub(1).min(16);
+    (RECONNECT_BASE_DELAY * factor).min(RECONNECT_MAX_DELAY)
+}
+
+/// Payload for `stream-reconnecting` / `stream-reconnected`
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StreamReconnectPayload {
+    stream_id: String,
+    attempt: u32,
+    max_attempts: u32,
+}
+
+/// Validate a stream URL before spawning FFmpeg
+///
+/// Accepts `rtmp://` and `rtmps://` URLs with a host, e.g.
+/// `rtmp://live.twitch.tv/app/<stream-key>`.
+pub fn validate_rtmp_url(url: &str) -> Result<()> {
+    let url = url.trim();
+    let rest = url
+        .strip_prefix("rtmp://")
+        .or_else(|| url.strip_prefix("rtmps://"))
+        .ok_or_else(|| anyhow::anyhow!("Stream URL must start with rtmp:// or rtmps://"))?;
+
+    if url.chars().any(char::is_whitespace) {
+        return Err(anyhow::anyhow!("Stream URL must not contain whitespace"));
+    }
+
+    let host = rest.split('/').next().unwrap_or("");
+    if host.is_empty() {
+        return Err(anyhow::anyhow!("Stream URL is missing a server host"));
+    }
+
+    Ok(())
+}
+
+/// Strip everything after the server's application path so stream keys stay out of logs
+pub fn redact_stream_url(url: &str) -> String {
+    match url.find("://") {
+        Some(scheme_end) => {
+            let after_scheme = &url[scheme_end + 3..];
+            match after_scheme.find('/') {
+                Some(slash) => format!("{}/****", &url[..scheme_end + 3 + slash]),
+                None => url.to_string(),
+            }
+        }
+        None => "****".to_string(),
+    }
+}
+
+/// Replace every occurrence of `url` in FFmpeg output with its redacted form
+///
+/// FFmpeg prefixes output errors with the full URL, stream key included.
+pub fn redact_stream_error(text: &str, url: &str) -> String {
+    if url.is_empty() {
+        return text.to_string();
+    }
+    text.replace(url, &redact_stream_url(url))
+}
+
+/// Frame sink that streams to an RTMP server
+pub struct RtmpSink {
+    /// RTMP(S) ingest URL, including the stream key
+    url: String,
+
+    /// Video width
+    width: u32,
+
+    /// Video height
+    height: u32,
+
+    /// Frame rate (frames per second)
+    fps: u32,
+
+    /// FFmpeg child process
+    process: Option<Child>,
+
+    /// Stdin pipe for frame data
+    stdin: Option<ChildStdin>,
+
+    /// Reconnect bookkeeping
+    reconnect: ReconnectStateMachine,
+
+    /// Frames held while reconnecting
+    pending: VecDeque<TimestampedFrame>,
+
+    /// Earliest time for the next reconnect attempt
+    next_attempt_at: Option<Instant>,
+
+    /// Most recent connection error (FFmpeg stderr when available), with the stream key redacted
+    last_error: String,
+
+    /// App handle and stream ID for reconnect events
+    events: Option<(tauri::AppHandle, String)>,
+}
+
+impl RtmpSink {
+    /// Create an RTMP sink (does not connect until `start`)
+    ///
+    /// # Returns
+    /// * `Err(anyhow::Error)` - URL is not a valid RTMP URL
+    pub fn new(url: &str, width: u32, height: u32, fps: u32) -> Result<Self> {
+        validate_rtmp_url(url)?;
+
+        Ok(Self {
+            url: url.trim().to_string(),
+            width,
+            height,
+            fps,
+            process: None,
+            stdin: None,
+            reconnect: ReconnectStateMachine::new(MAX_RECONNECT_ATTEMPTS),
+            pending: VecDeque::new(),
+            next_attempt_at: None,
+            last_error: String::new(),
+            events: None,
+        })
+    }
+
+    /// Emit reconnect events for this stream to the frontend
+    pub fn set_event_target(&mut self, app_handle: tauri::AppHandle, stream_id: String) {
+        self.events = Some((app_handle, stream_id));
+    }
+
+    /// Current connection state
+    pub fn state(&self) -> StreamState {
+        self.reconnect.state()
+    }
+
+    /// Build the FFmpeg argument list for FLV/RTMP output
+    pub fn build_stream_args(&self) -> Vec<String> {
+        let fps = self.fps.max(1);
+        let mut args: Vec<String> = vec![
+            "-loglevel".into(), "error".into(),
+            // Input 0: raw video from stdin
+            "-f".into(), "rawvideo".into(),
+            "-pix_fmt".into(), "bgra".into(),
+            "-s".into(), format!("{}x{}", self.width, self.height),
+            "-r".into(), fps.to_string(),
+            "-i".into(), "pipe:0".into(),
+            // Input 1: silent audio (ingest servers expect an audio track)
+            "-f".into(), "lavfi".into(),
+            "-i".into(), "anullsrc=channel_layout=stereo:sample_rate=44100".into(),
+            "-map".into(), "0:v".into(),
+            "-map".into(), "1:a".into(),
+        ];
+
+        let (even_width, even_height) = even_dimensions(self.width, self.height);
+        if (even_width, even_height) != (self.width, self.height) {
+            args.extend([
+                "-vf".to_string(),
+                format!("crop={}:{}:0:0", even_width, even_height),
+            ]);
+        }
+
+        let bitrate = format!("{}k", DEFAULT_STREAM_BITRATE_KBPS);
+        args.extend([
+            "-c:v".to_string(), "libx264".to_string(),
+            "-preset".to_string(), "veryfast".to_string(),
+            "-tune".to_string(), "zerolatency".to_string(),
+            "-b:v".to_string(), bitrate.clone(),
+            "-maxrate".to_string(), bitrate,
+            "-bufsize".to_string(), format!("{}k", DEFAULT_STREAM_BITRATE_KBPS * 2),
+            "-g".to_string(), (fps * 2).to_string(),  // Keyframe every 2s for ingest servers
+            "-pix_fmt".to_string(), "yuv420p".to_string(),
+            "-c:a".to_string(), "aac".to_string(),
+            "-b:a".to_string(), "128k".to_string(),
+            "-f".to_string(), "flv".to_string(),
+            self.url.clone(),
+        ]);
+
+        args
+    }
+
+    /// Spawn FFmpeg and connect to the server
+    pub async fn start(&mut self) -> Result<()> {
+        tracing::info!(
+            event = "stream_start",
+            url = %redact_stream_url(&self.url),
+            width = self.width,
+            height = self.height,
+            fps = self.fps,
+            "Starting RTMP stream"
+        );
+
+        let mut child = Command::new(ffmpeg_sidecar::paths::ffmpeg_path())
+            .args(self.build_stream_args())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to spawn FFmpeg process. Ensure FFmpeg is installed.")?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Failed to take FFmpeg stdin"))?;
+
+        self.stdin = Some(stdin);
+        self.process = Some(child);
+        Ok(())
+    }
+
+    /// Whether the FFmpeg stream process is running
+    pub fn is_streaming(&mut self) -> bool {
+        match self.process.as_mut() {
+            Some(child) => matches!(child.try_wait(), Ok(None)),
+            None => false,
+        }
+    }
+
+    /// Kill FFmpeg and return whatever it wrote to stderr, with the stream key redacted
+    fn teardown(&mut self) -> String {
+        self.stdin = None;
+        let Some(mut child) = self.process.take() else {
+            return String::new();
+        };
+
+        let _ = child.kill();
+        let _ = child.wait();
+
+        let mut stderr = String::new();
+        if let Some(mut pipe) = child.stderr.take() {
+            let _ = pipe.read_to_string(&mut stderr);
+        }
+        redact_stream_error(stderr.trim(), &self.url)
+    }
+
+    /// Write to FFmpeg's stdin on a blocking thread (the pipe blocks while FFmpeg is busy)
+    ///
+    /// Hands `data` back so a frame that could not be sent can be buffered again.
+    async fn write_to_ffmpeg(&mut self, data: Vec<u8>) -> (Vec<u8>, std::io::Result<()>) {
+        let Some(mut stdin) = self.stdin.take() else {
+            let error = std::io::Error::new(std::io::ErrorKind::NotConnected, "Stream is not running");
+            return (data, Err(error));
+        };
+
+        let write = tokio::task::spawn_blocking(move || {
+            use std::io::Write;
+            let result = stdin.write_all(&data);
+            (stdin, data, result)
+        });
+        match write.await {
+            Ok((stdin, data, result)) => {
+                self.stdin = Some(stdin);
+                (data, result)
+            }
+            Err(e) => (Vec::new(), Err(std::io::Error::other(format!("Stream write task failed: {}", e)))),
+        }
+    }
+
+    /// Tear down the dead FFmpeg process and advance the reconnect state
+    fn handle_connection_lost(&mut self, error: String) -> StreamState {
+        let ffmpeg_error = self.teardown();
+        self.last_error = if ffmpeg_error.is_empty() { error } else { ffmpeg_error };
+
+        let state = self.reconnect.connection_lost();
+        tracing::warn!(
+            event = "stream_connection_lost",
+            url = %redact_stream_url(&self.url),
+            error = %self.last_error,
+            state = ?state,
+            "RTMP stream connection lost"
+        );
+
+        if let StreamState::Reconnecting { attempt } = state {
+            self.next_attempt_at = Some(Instant::now() + reconnect_delay(attempt));
+            self.emit_reconnect_event("stream-reconnecting", attempt);
+        }
+        state
+    }
+
+    fn emit_reconnect_event(&self, event: &str, attempt: u32) {
+        if let Some((app_handle, stream_id)) = &self.events {
+            let payload = StreamReconnectPayload {
+                stream_id: stream_id.clone(),
+                attempt,
+                max_attempts: MAX_RECONNECT_ATTEMPTS,
+            };
+            if let Err(e) = app_handle.emit(event, payload) {
+                tracing::warn!(event = "stream_event_emit_failed", error = %e, "Failed to emit {}", event);
+            }
+        }
+    }
+
+    fn failed_error(&self) -> anyhow::Error {
+        anyhow::anyhow!(
+            "Stream connection lost after {} reconnect attempts: {}",
+            MAX_RECONNECT_ATTEMPTS,
+            self.last_error
+        )
+    }
+
+    /// Hold a frame while reconnecting, dropping the oldest beyond the buffer window
+    fn buffer_frame(&mut self, frame: &TimestampedFrame) {
+        let capacity = (self.fps.max(1) * RECONNECT_BUFFER_SECS) as usize;
+        while self.pending.len() >= capacity {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(frame.clone());
+    }
+
+    /// Respawn FFmpeg if the backoff has elapsed and replay buffered frames
+    async fn try_reconnect(&mut self) -> Result<()> {
+        let attempt = match self.reconnect.state() {
+            StreamState::Reconnecting { attempt } => attempt,
+            StreamState::Connected => return Ok(()),
+            StreamState::Failed => return Err(self.failed_error()),
+        };
+
+        if self.next_attempt_at.is_some_and(|at| Instant::now() < at) {
+            return Ok(());
+        }
+
+        tracing::info!(
+            event = "stream_reconnect_attempt",
+            attempt,
+            max_attempts = MAX_RECONNECT_ATTEMPTS,
+            "Reconnecting RTMP stream"
+        );
+
+        if let Err(e) = self.start().await {
+            return match self.handle_connection_lost(e.to_string()) {
+                StreamState::Failed => Err(self.failed_error()),
+                _ => Ok(()),
+            };
+        }
+
+        while let Some(mut frame) = self.pending.pop_front() {
+            let (data, result) = self.write_to_ffmpeg(std::mem::take(&mut frame.data)).await;
+            if let Err(e) = result {
+                frame.data = data;
+                self.pending.push_front(frame);
+                return match self.handle_connection_lost(e.to_string()) {
+                    StreamState::Failed => Err(self.failed_error()),
+                    _ => Ok(()),
+                };
+            }
+        }
+
+        // `stream-reconnected` waits until the connection proves stable
+        self.reconnect.reconnected(Instant::now());
+        self.next_attempt_at = None;
+        tracing::info!(event = "stream_reconnect_connected", attempt, "RTMP stream connected again");
+        Ok(())
+    }
+
+    /// Announce a reconnect once it has stayed up for `RECONNECT_STABLE_AFTER`
+    fn confirm_reconnect(&mut self) {
+        if let Some(attempt) = self.reconnect.confirm_stable(Instant::now()) {
+            tracing::info!(event = "stream_reconnected", attempt, "RTMP stream reconnected");
+            self.emit_reconnect_event("stream-reconnected", attempt);
+        }
+    }
+}
+
+impl FrameSink for RtmpSink {
+    async fn write_frame(&mut self, frame: &TimestampedFrame) -> Result<()> {
+        if frame.width != self.width || frame.height != self.height {
+            return Err(anyhow::anyhow!(
+                "Frame dimensions {}x{} do not match stream {}x{}",
+                frame.width, frame.height, self.width, self.height
+            ));
+        }
+
+        if self.reconnect.state() == StreamState::Connected {
+            match self.write_to_ffmpeg(frame.data.clone()).await.1 {
+                Ok(()) => {
+                    self.confirm_reconnect();
+                    return Ok(());
+                }
+                // FFmpeg exited (connection refused, server dropped us, bad stream key, ...)
+                Err(e) => {
+                    if self.handle_connection_lost(e.to_string()) == StreamState::Failed {
+                        return Err(self.failed_error());
+                    }
+                }
+            }
+        }
+
+        self.buffer_frame(frame);
+        self.try_reconnect().await
+    }
+
+    async fn finalize(&mut self) -> Result<()> {
+        // Closing stdin lets FFmpeg flush and send the FLV trailer
+        self.stdin = None;
+
+        let Some(mut child) = self.process.take() else {
+            return Ok(());
+        };
+
+        let wait_result = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            tokio::task::spawn_blocking(move || child.wait()),
+        )
+        .await;
+
+        match wait_result {
+            Ok(Ok(Ok(_))) => {
+                tracing::info!(event = "stream_stopped", "RTMP stream stopped");
+                Ok(())
+            }
+            Ok(Ok(Err(e))) => Err(anyhow::anyhow!("Failed to wait for FFmpeg: {}", e)),
+            Ok(Err(e)) => Err(anyhow::anyhow!("FFmpeg wait task failed: {}", e)),
+            Err(_) => {
+                tracing::warn!(
+                    event = "stream_stop_timeout",
+                    "FFmpeg did not exit after closing the stream"
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Drop for RtmpSink {
+    fn drop(&mut self) {
+        if self.process.is_some() {
+            self.teardown();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_rtmp_url() {
+        assert!(validate_rtmp_url("rtmp://live.twitch.tv/app/live_123_abc").is_ok());
+        assert!(validate_rtmp_url("rtmps://a.rtmp.youtube.com:443/live2/key").is_ok());
+
+        assert!(validate_rtmp_url("https://example.com/live").is_err());
+        assert!(validate_rtmp_url("rtmp:///app/key").is_err());
+        assert!(validate_rtmp_url("rtmp://host/app/my key").is_err());
+        assert!(RtmpSink::new("srt://host:9000", 1920, 1080, 30).is_err());
+    }
+
+    #[test]
+    fn test_stream_args_use_flv_output() {
+        let sink = RtmpSink::new("rtmp://localhost/live/test", 1280, 720, 30).unwrap();
+        let args = sink.build_stream_args();
+
+        // Output muxer and URL are last
+        assert_eq!(&args[args.len() - 3..], &["-f", "flv", "rtmp://localhost/live/test"]);
+        assert!(args.windows(2).any(|w| w == ["-tune", "zerolatency"]));
+        assert!(args.windows(2).any(|w| w == ["-g", "60"]));
+        assert!(args.windows(2).any(|w| w == ["-s", "1280x720"]));
+        assert!(!args.contains(&"-vf".to_string()));
+    }
+
+    #[test]
+    fn test_reconnect_recovers_after_drop() {
+        let mut machine = ReconnectStateMachine::new(3);
+        assert_eq!(machine.state(), StreamState::Connected);
+
+        assert_eq!(machine.connection_lost(), StreamState::Reconnecting { attempt: 1 });
+        assert_eq!(machine.connection_lost(), StreamState::Reconnecting { attempt: 2 });
+        let at = Instant::now();
+        assert_eq!(machine.reconnected(at), StreamState::Connected);
+
+        // Announced once, after the connection held long enough
+        assert_eq!(machine.confirm_stable(at + RECONNECT_STABLE_AFTER / 2), None);
+        assert_eq!(machine.confirm_stable(at + RECONNECT_STABLE_AFTER), Some(2));
+        assert_eq!(machine.confirm_stable(at + RECONNECT_STABLE_AFTER * 2), None);
+
+        // A later drop starts counting from the first attempt again
+        assert_eq!(machine.connection_lost(), StreamState::Reconnecting { attempt: 1 });
+    }
+
+    #[test]
+    fn test_reconnects_dropped_right_away_still_fail() {
+        // The server accepts every reconnect and drops it before it proves stable
+        let mut machine = ReconnectStateMachine::new(MAX_RECONNECT_ATTEMPTS);
+        let at = Instant::now();
+        assert_eq!(machine.connection_lost(), StreamState::Reconnecting { attempt: 1 });
+        for attempt in 1..MAX_RECONNECT_ATTEMPTS {
+            assert_eq!(machine.reconnected(at), StreamState::Connected);
+            assert_eq!(machine.confirm_stable(at + Duration::from_millis(100)), None);
+            assert_eq!(machine.connection_lost(), StreamState::Reconnecting { attempt: attempt + 1 });
+        }
+        assert_eq!(machine.reconnected(at), StreamState::Connected);
+        assert_eq!(machine.connection_lost(), StreamState::Failed);
+        assert_eq!(machine.confirm_stable(at + RECONNECT_STABLE_AFTER), None);
+    }
+
+    #[test]
+    fn test_reconnect_fails_after_max_attempts() {
+        let mut machine = ReconnectStateMachine::new(2);
+        machine.connection_lost();
+        assert_eq!(machine.connection_lost(), StreamState::Reconnecting { attempt: 2 });
+        assert_eq!(machine.connection_lost(), StreamState::Failed);
+        assert_eq!(machine.reconnected(Instant::now()), StreamState::Failed);
+
+        assert_eq!(reconnect_delay(1), Duration::from_millis(500));
+        assert_eq!(reconnect_delay(3), Duration::from_secs(2));
+        assert_eq!(reconnect_delay(10), RECONNECT_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_redact_stream_url() {
+        assert_eq!(
+            redact_stream_url("rtmp://live.twitch.tv/app/live_123_secret"),
+            "rtmp://live.twitch.tv/****"
+        );
+    }
+
+    #[test]
+    fn test_ffmpeg_errors_do_not_leak_the_stream_key() {
+        let url = "rtmp://live.twitch.tv/app/live_123_secret";
+        let stderr = "[flv @ 0x7f] Failed to update header with correct duration.\n\
+                      rtmp://live.twitch.tv/app/live_123_secret: Broken pipe";
+
+        let redacted = redact_stream_error(stderr, url);
+        assert!(!redacted.contains("live_123_secret"));
+        assert!(redacted.ends_with("rtmp://live.twitch.tv/****: Broken pipe"));
+        assert_eq!(redact_stream_error("Connection refused", url), "Connection refused");
+    }
+}
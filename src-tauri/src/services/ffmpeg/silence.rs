@@ -0,0 +1,242 @@
+//! Split recordings into chapters at long silences
+//!
+//! FFmpeg's `silencedetect` filter reports silent stretches on stderr:
+//!
+//! ```text
+//! [silencedetect @ 0x...] silence_start: 12.48
+//! [silencedetect @ 0x...] silence_end: 15.02 | silence_duration: 2.54
+//! ```
+//!
+//! Each silence that sits between two stretches of sound becomes a cut at its
+//! midpoint, and the segments are written with stream copy (`-c copy`), so no
+//! re-encode happens. Cuts that would leave a segment shorter than the minimum
+//! are dropped, merging the short piece into its neighbour.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Level below which audio counts as silence for `silencedetect`
+pub const SILENCE_NOISE_DB: i32 = -35;
+
+/// A silent stretch reported by `silencedetect`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SilenceInterval {
+    pub start_ms: u64,
+    /// `None` when the file ends while still silent
+    pub end_ms: Option<u64>,
+}
+
+/// Parse `silencedetect` intervals from FFmpeg stderr
+pub fn parse_silencedetect(stderr: &str) -> Vec<SilenceInterval> {
+    let mut silences = Vec::new();
+    let mut open_start: Option<u64> = None;
+
+    for line in stderr.lines() {
+        if let Some(start) = field_seconds(line, "silence_start:") {
+            open_start = Some(start);
+        } else if let Some(end) = field_seconds(line, "silence_end:") {
+            if let Some(start_ms) = open_start.take() {
+                silences.push(SilenceInterval { start_ms, end_ms: Some(end) });
+            }
+        }
+    }
+
+    if let Some(start_ms) = open_start {
+        silences.push(SilenceInterval { start_ms, end_ms: None });
+    }
+
+    silences
+}
+
+/// Parse the input duration (`Duration: HH:MM:SS.ss`) from FFmpeg stderr
+pub fn parse_duration_ms(stderr: &str) -> Option<u64> {
+    let line = stderr.lines().find(|l| l.trim_start().starts_with("Duration:"))?;
+    let timestamp = line.trim_start().strip_prefix("Duration:")?.split(',').next()?.trim();
+
+    let mut parts = timestamp.split(':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    Some(((hours * 3600.0 + minutes * 60.0 + seconds) * 1000.0).round() as u64)
+}
+
+/// Seconds value following `key` on a line, in milliseconds
+fn field_seconds(line: &str, key: &str) -> Option<u64> {
+    let rest = &line[line.find(key)? + key.len()..];
+    let value: f64 = rest.split_whitespace().next()?.parse().ok()?;
+    Some((value.max(0.0) * 1000.0).round() as u64)
+}
+
+/// Compute `(start_ms, end_ms)` segments for a file of `duration_ms`
+///
+/// Leading and trailing silences are not cut (there is nothing on the other
+/// side). Cuts are dropped when they would leave a segment shorter than
+/// `min_segment_ms`; the final segment is merged backwards if it is too short.
+pub fn segment_boundaries(
+    silences: &[SilenceInterval],
+    duration_ms: u64,
+    min_segment_ms: u64,
+) -> Vec<(u64, u64)> {
+    let cuts = silences.iter().filter_map(|silence| {
+        let end_ms = silence.end_ms.unwrap_or(duration_ms).min(duration_ms);
+        if silence.start_ms == 0 || end_ms >= duration_ms || end_ms <= silence.start_ms {
+            return None;
+        }
+        Some(silence.start_ms + (end_ms - silence.start_ms) / 2)
+    });
+
+    let mut kept = vec![0u64];
+    for cut in cuts {
+        if cut.saturating_sub(*kept.last().unwrap()) >= min_segment_ms {
+            kept.push(cut);
+        }
+    }
+    if kept.len() > 1 && duration_ms - kept.last().unwrap() < min_segment_ms {
+        kept.pop();
+    }
+    kept.push(duration_ms);
+
+    kept.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+/// Path of segment `index` (1-based): `<stem>-part01.<ext>` next to the source
+pub fn segment_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_else(|| "mp4".to_string());
+    path.with_file_name(format!("{}-part{:02}.{}", stem, index, extension))
+}
+
+/// Run `silencedetect` over a file
+///
+/// # Returns
+/// * `Ok((silences, duration_ms))` - Detected silences and the input duration
+/// * `Err(anyhow::Error)` - FFmpeg failed or reported no duration
+pub async fn detect_silences(path: &Path, min_silence_ms: u64) -> Result<(Vec<SilenceInterval>, u64)> {
+    let filter = format!(
+        "silencedetect=noise={}dB:d={:.3}",
+        SILENCE_NOISE_DB,
+        min_silence_ms as f64 / 1000.0
+    );
+
+    let output = Command::new("ffmpeg")
+        .arg("-hide_banner")
+        .arg("-i").arg(path)
+        .args(["-vn", "-af", &filter, "-f", "null", "-"])
+        .output()
+        .await
+        .context("Failed to execute ffmpeg for silence detection")?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("FFmpeg silence detection failed: {}", stderr.trim()));
+    }
+
+    let duration_ms = parse_duration_ms(&stderr)
+        .ok_or_else(|| anyhow::anyhow!("Could not determine duration of {}", path.display()))?;
+    Ok((parse_silencedetect(&stderr), duration_ms))
+}
+
+/// Split a recording at long silences
+///
+/// # Arguments
+/// * `path` - Source audio/video file
+/// * `min_silence_ms` - Shortest silence that counts as a chapter break
+/// * `min_segment_ms` - Shortest segment to produce; shorter pieces are merged
+///
+/// # Returns
+/// * `Ok(Vec<PathBuf>)` - Segment files in order (a single segment if nothing was cut)
+/// * `Err(anyhow::Error)` - Detection or a segment copy failed
+pub async fn split_by_silence(path: &Path, min_silence_ms: u64, min_segment_ms: u64) -> Result<Vec<PathBuf>> {
+    let (silences, duration_ms) = detect_silences(path, min_silence_ms).await?;
+    let segments = segment_boundaries(&silences, duration_ms, min_segment_ms);
+
+    tracing::info!(
+        event = "split_by_silence",
+        path = %path.display(),
+        silences = silences.len(),
+        segments = segments.len(),
+        "Splitting recording at silences"
+    );
+
+    let mut outputs = Vec::with_capacity(segments.len());
+    for (index, (start_ms, end_ms)) in segments.into_iter().enumerate() {
+        let output_path = segment_path(path, index + 1);
+
+        let output = Command::new("ffmpeg")
+            .arg("-v").arg("error")
+            .arg("-ss").arg(format!("{:.3}", start_ms as f64 / 1000.0))
+            .arg("-to").arg(format!("{:.3}", end_ms as f64 / 1000.0))
+            .arg("-i").arg(path)
+            .args(["-map", "0", "-c", "copy", "-avoid_negative_ts", "make_zero", "-y"])
+            .arg(&output_path)
+            .output()
+            .await
+            .context("Failed to execute ffmpeg for segment copy")?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "FFmpeg segment copy failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        outputs.push(output_path);
+    }
+
+    Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_silencedetect_output() {
+        let stderr = "\
+  Duration: 00:01:00.00, start: 0.000000, bitrate: 128 kb/s
+[silencedetect @ 0x7f8] silence_start: 10
+[silencedetect @ 0x7f8] silence_end: 12.5 | silence_duration: 2.5
+[silencedetect @ 0x7f8] silence_start: 58.25
+";
+        assert_eq!(parse_duration_ms(stderr), Some(60_000));
+        assert_eq!(
+            parse_silencedetect(stderr),
+            vec![
+                SilenceInterval { start_ms: 10_000, end_ms: Some(12_500) },
+                SilenceInterval { start_ms: 58_250, end_ms: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_segment_boundaries_cut_mid_silence_and_merge_short_segments() {
+        let silences = [
+            SilenceInterval { start_ms: 0, end_ms: Some(1_000) },        // leading: not cut
+            SilenceInterval { start_ms: 10_000, end_ms: Some(12_000) },  // cut at 11s
+            SilenceInterval { start_ms: 13_000, end_ms: Some(14_000) },  // 13.5s: 2.5s segment, merged
+            SilenceInterval { start_ms: 30_000, end_ms: Some(33_000) },  // cut at 31.5s
+            SilenceInterval { start_ms: 58_000, end_ms: None },          // trailing: not cut
+        ];
+
+        assert_eq!(
+            segment_boundaries(&silences, 60_000, 5_000),
+            vec![(0, 11_000), (11_000, 31_500), (31_500, 60_000)]
+        );
+
+        // A short tail is merged into the previous segment
+        assert_eq!(
+            segment_boundaries(&silences, 34_000, 5_000),
+            vec![(0, 11_000), (11_000, 34_000)]
+        );
+
+        // No silences: one segment covering the whole file
+        assert_eq!(segment_boundaries(&[], 60_000, 5_000), vec![(0, 60_000)]);
+    }
+}
@@ -0,0 +1,103 @@
+//! Frame sinks
+//!
+//! Capture and encoding tasks push frames into a `FrameSink` instead of talking
+//! to `FFmpegEncoder` directly, so the destination can be swapped: the FFmpeg
+//! file encoder, a network stream, or an in-memory sink for tests.
+
+use super::encoder::{FFmpegEncoder, TimestampedFrame};
+use anyhow::Result;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Destination for captured frames
+pub trait FrameSink: Send {
+    /// Write one frame to the sink
+    fn write_frame(&mut self, frame: &TimestampedFrame) -> impl Future<Output = Result<()>> + Send;
+
+    /// Flush and close the sink once no more frames will be written
+    fn finalize(&mut self) -> impl Future<Output = Result<()>> + Send;
+
+    /// Fail if the sink can no longer take frames (e.g. its process died)
+    fn check_health(&mut self) -> impl Future<Output = Result<()>> + Send {
+        async { Ok(()) }
+    }
+}
+
+impl FrameSink for FFmpegEncoder {
+    async fn write_frame(&mut self, frame: &TimestampedFrame) -> Result<()> {
+        self.write_frame_to_stdin(frame).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        self.check_process_health().await
+    }
+
+    async fn finalize(&mut self) -> Result<()> {
+        self.stop_encoding().await
+    }
+}
+
+/// Sink that collects frames in memory without spawning FFmpeg
+///
+/// Clones share the same frame buffer, so a test can keep one clone to inspect
+/// after handing the other to an encoding task.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFrameSink {
+    frames: Arc<Mutex<Vec<TimestampedFrame>>>,
+    finalized: Arc<AtomicBool>,
+}
+
+impl MemoryFrameSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Frames written so far
+    pub fn frames(&self) -> Vec<TimestampedFrame> {
+        self.frames.lock().unwrap().clone()
+    }
+
+    /// Whether `finalize` has been called
+    pub fn is_finalized(&self) -> bool {
+        self.finalized.load(Ordering::SeqCst)
+    }
+}
+
+impl FrameSink for MemoryFrameSink {
+    async fn write_frame(&mut self, frame: &TimestampedFrame) -> Result<()> {
+        if self.is_finalized() {
+            return Err(anyhow::anyhow!("Sink already finalized"));
+        }
+        self.frames.lock().unwrap().push(frame.clone());
+        Ok(())
+    }
+
+    async fn finalize(&mut self) -> Result<()> {
+        self.finalized.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_sink_rejects_frames_after_finalize() {
+        let mut sink = MemoryFrameSink::new();
+        let frame = TimestampedFrame {
+            data: vec![0u8; 4],
+            timestamp_ms: 0,
+            width: 1,
+            height: 1,
+        };
+
+        sink.write_frame(&frame).await.unwrap();
+        sink.finalize().await.unwrap();
+
+        assert!(sink.is_finalized());
+        assert!(sink.write_frame(&frame).await.is_err());
+        assert_eq!(sink.frames().len(), 1);
+    }
+}
@@ -0,0 +1,283 @@
+//! Stream-copy fast path for plain trims
+//!
+//! Exporting a timeline that is just one clip cut to an in/out range doesn't
+//! need the render + re-encode pipeline: `-ss`/`-to` before the input plus
+//! `-c copy` cuts the packets directly, which is near-instant and lossless.
+//!
+//! Copying is only exact when the cut starts on a keyframe (FFmpeg snaps the
+//! start back to the previous one otherwise) and the source already has the
+//! codecs the export asks for. Anything else — fades, volume changes, scaling,
+//! a bitrate or quality override, or `accurate_seek` — goes through the normal re-encode.
+
+use crate::models::{ExportConfig, Timeline};
+use crate::services::ffmpeg::keyframes::get_keyframes;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Distance from a keyframe at which a copied cut still counts as exact (about one frame)
+pub const KEYFRAME_TOLERANCE_MS: u64 = 20;
+
+/// A single-clip in/out trim that may be exported with stream copy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamCopyTrim {
+    pub input: PathBuf,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Codecs of the first video and audio streams of a file (ffprobe `codec_name`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceCodecs {
+    pub video: Option<String>,
+    pub audio: Option<String>,
+}
+
+/// Find a trim the timeline and config allow to be stream-copied
+///
+/// Structural check only (no I/O): exactly one clip, starting at zero, with no
+/// audio or video effects, and no export options that require re-encoding.
+pub fn stream_copy_candidate(timeline: &Timeline, config: &ExportConfig) -> Option<StreamCopyTrim> {
+    if config.accurate_seek
+        || config.resolution.is_some()
+        || config.pixel_format.is_some()
+        || config.video_bitrate.is_some()
+        || config.crf.is_some()
+        || config.preset.is_some()
+        || config.watermark.is_some()
+    {
+        return None;
+    }
+
+    let mut clips = timeline.tracks.iter().flat_map(|track| track.clips.iter());
+    let clip = clips.next()?;
+    if clips.next().is_some() {
+        return None;
+    }
+
+    let has_fade = clip.fade_in.unwrap_or(0) > 0 || clip.fade_out.unwrap_or(0) > 0;
+    let has_volume = clip.volume.is_some_and(|volume| (volume - 1.0).abs() > f64::EPSILON);
+    let has_tracks = clip.audio_tracks.as_ref().is_some_and(|tracks| !tracks.is_empty());
+    if has_fade
+        || has_volume
+        || has_tracks
+        || clip.muted == Some(true)
+        || clip.transform.is_some()
+        || clip.start_time != 0
+        || clip.trim_out <= clip.trim_in
+        || timeline.total_duration > clip.end_time()
+    {
+        return None;
+    }
+
+    Some(StreamCopyTrim {
+        input: PathBuf::from(&clip.file_path),
+        start_ms: clip.trim_in,
+        end_ms: clip.trim_out,
+    })
+}
+
+/// Codec name ffprobe reports for streams produced by an FFmpeg encoder
+fn codec_name_for_encoder(encoder: &str) -> &str {
+    match encoder {
+        "libx264" | "h264_videotoolbox" => "h264",
+        "libx265" | "hevc_videotoolbox" => "hevc",
+        "prores_ks" | "prores_videotoolbox" => "prores",
+        other => other,
+    }
+}
+
+/// Whether the source streams already are what the export would encode
+pub fn codecs_match(source: &SourceCodecs, config: &ExportConfig) -> bool {
+    let video_matches =
+        source.video.as_deref() == Some(codec_name_for_encoder(config.video_encoder()));
+    // A silent source stays silent either way
+    let audio_matches = source
+        .audio
+        .as_deref()
+        .is_none_or(|audio| audio == config.audio_codec.ffmpeg_codec());
+    video_matches && audio_matches
+}
+
+/// Whether a copied cut starting at `start_ms` would start where requested
+pub fn cut_is_keyframe_aligned(start_ms: u64, keyframes: &[u64]) -> bool {
+    start_ms == 0 || keyframes.iter().any(|&kf| kf.abs_diff(start_ms) <= KEYFRAME_TOLERANCE_MS)
+}
+
+/// Read the first video and audio codec from `ffprobe -show_streams` JSON
+pub fn source_codecs_from_probe(probe: &serde_json::Value) -> SourceCodecs {
+    let codec = |codec_type: &str| {
+        probe["streams"].as_array().and_then(|streams| {
+            streams
+                .iter()
+                .find(|s| s["codec_type"].as_str() == Some(codec_type))
+                .and_then(|s| s["codec_name"].as_str())
+                .map(str::to_string)
+        })
+    };
+    SourceCodecs {
+        video: codec("video"),
+        audio: codec("audio"),
+    }
+}
+
+/// Probe the codecs of `path` with ffprobe
+pub async fn probe_source_codecs(path: &Path) -> Result<SourceCodecs> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "error", "-print_format", "json", "-show_streams"])
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to execute ffprobe")?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "FFprobe failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let probe: serde_json::Value =
+        serde_json::from_slice(&output.stdout).context("Failed to parse ffprobe output")?;
+    Ok(source_codecs_from_probe(&probe))
+}
+
+/// Whether stream-copying `trim` gives the same result as re-encoding it
+///
+/// Probe failures count as "no": the re-encode path reports real errors.
+pub async fn stream_copy_is_exact(trim: &StreamCopyTrim, config: &ExportConfig) -> bool {
+    let codecs = match probe_source_codecs(&trim.input).await {
+        Ok(codecs) => codecs,
+        Err(e) => {
+            tracing::debug!(event = "stream_copy_probe_failed", error = %e, "Falling back to re-encode");
+            return false;
+        }
+    };
+    if !codecs_match(&codecs, config) {
+        tracing::debug!(event = "stream_copy_codec_mismatch", source = ?codecs, "Falling back to re-encode");
+        return false;
+    }
+
+    match get_keyframes(&trim.input).await {
+        Ok(keyframes) => cut_is_keyframe_aligned(trim.start_ms, &keyframes),
+        Err(e) => {
+            tracing::debug!(event = "stream_copy_probe_failed", error = %e, "Falling back to re-encode");
+            false
+        }
+    }
+}
+
+/// FFmpeg arguments cutting `trim` into `output_path` without re-encoding
+pub fn build_stream_copy_args(trim: &StreamCopyTrim, config: &ExportConfig, output_path: &str) -> Vec<String> {
+    let mut args = vec![
+        "-ss".to_string(), format!("{:.3}", trim.start_ms as f64 / 1000.0),
+        "-to".to_string(), format!("{:.3}", trim.end_ms as f64 / 1000.0),
+        "-i".to_string(), trim.input.to_string_lossy().to_string(),
+        "-map".to_string(), "0".to_string(),
+        "-c".to_string(), "copy".to_string(),
+    ];
+
+    // hvc1 tag so QuickTime plays copied HEVC
+    if let Some(tag) = config.video_codec.mp4_tag() {
+        args.extend(["-tag:v".to_string(), tag.to_string()]);
+    }
+
+    // Same muxer as a re-encoded export of this container
+    if config.wants_faststart() {
+        args.extend(["-movflags".to_string(), "+faststart".to_string()]);
+    }
+    args.extend([
+        "-f".to_string(), config.muxer().to_string(),
+        "-y".to_string(),
+        output_path.to_string(),
+    ]);
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Clip, Track, TrackType};
+
+    fn trimmed_clip(trim_in: u64, trim_out: u64) -> Clip {
+        Clip {
+            id: "clip-1".to_string(),
+            file_path: "/media/recording.mp4".to_string(),
+            start_time: 0,
+            duration: 60_000,
+            trim_in,
+            trim_out,
+            fade_in: None,
+            fade_out: None,
+            volume: None,
+            muted: None,
+            audio_tracks: None,
+            transform: None,
+            linked_clip_id: None,
+            group_id: None,
+            transition_out: None,
+        }
+    }
+
+    fn timeline(clips: Vec<Clip>) -> Timeline {
+        let total_duration = clips.iter().map(Clip::end_time).max().unwrap_or(0);
+        Timeline {
+            tracks: vec![Track {
+                id: "track-1".to_string(),
+                track_number: 1,
+                clips,
+                track_type: TrackType::Video,
+            }],
+            total_duration,
+        }
+    }
+
+    #[test]
+    fn test_plain_trim_is_copy_eligible() {
+        let config = ExportConfig::default();
+        let trim = stream_copy_candidate(&timeline(vec![trimmed_clip(4_000, 19_500)]), &config)
+            .expect("plain trim should be copy-eligible");
+        assert_eq!(
+            trim,
+            StreamCopyTrim { input: PathBuf::from("/media/recording.mp4"), start_ms: 4_000, end_ms: 19_500 }
+        );
+
+        let args = build_stream_copy_args(&trim, &config, "/tmp/out.mp4");
+        assert_eq!(&args[..6], ["-ss", "4.000", "-to", "19.500", "-i", "/media/recording.mp4"]);
+        assert!(args.windows(2).any(|w| w == ["-c", "copy"]));
+        assert!(args.windows(2).any(|w| w == ["-f", "mp4"]));
+
+        let mkv = ExportConfig { output_path: "/tmp/out.mkv".to_string(), ..ExportConfig::default() };
+        let args = build_stream_copy_args(&trim, &mkv, "/tmp/out.mkv");
+        assert!(args.windows(2).any(|w| w == ["-f", "matroska"]));
+
+        // Copy is exact only from a keyframe, and only if no re-encode would change the codecs
+        assert!(cut_is_keyframe_aligned(4_000, &[0, 2_000, 4_010, 6_000]));
+        assert!(!cut_is_keyframe_aligned(5_000, &[0, 2_000, 4_010, 6_000]));
+        let h264_aac = SourceCodecs { video: Some("h264".to_string()), audio: Some("aac".to_string()) };
+        assert!(codecs_match(&h264_aac, &config));
+        let hevc = SourceCodecs { video: Some("hevc".to_string()), audio: None };
+        assert!(!codecs_match(&hevc, &config));
+
+        // Two clips need the renderer
+        let two = timeline(vec![trimmed_clip(0, 5_000), Clip { id: "clip-2".to_string(), ..trimmed_clip(0, 5_000) }]);
+        assert!(stream_copy_candidate(&two, &config).is_none());
+    }
+
+    #[test]
+    fn test_fade_or_accurate_seek_disables_fast_path() {
+        let config = ExportConfig::default();
+
+        let faded = Clip { fade_in: Some(500), ..trimmed_clip(4_000, 19_500) };
+        assert!(stream_copy_candidate(&timeline(vec![faded]), &config).is_none());
+
+        let quieter = Clip { volume: Some(0.5), ..trimmed_clip(4_000, 19_500) };
+        assert!(stream_copy_candidate(&timeline(vec![quieter]), &config).is_none());
+
+        let accurate = ExportConfig { accurate_seek: true, ..ExportConfig::default() };
+        assert!(stream_copy_candidate(&timeline(vec![trimmed_clip(4_000, 19_500)]), &accurate).is_none());
+
+        let scaled = ExportConfig { resolution: Some((1280, 720)), ..ExportConfig::default() };
+        assert!(stream_copy_candidate(&timeline(vec![trimmed_clip(4_000, 19_500)]), &scaled).is_none());
+    }
+}
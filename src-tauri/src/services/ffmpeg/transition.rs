@@ -0,0 +1,229 @@
+//! FFmpeg Transitions Between Adjacent Timeline Clips
+//!
+//! Clips on the same track that touch (the next clip starts where the previous
+//! one ends) and carry a `Transition` are chained with `xfade` for video and
+//! `acrossfade` for audio. Each transition overlaps the end of the outgoing
+//! clip with the start of the incoming one, so a chain plays for the sum of its
+//! clip durations minus the transitions.
+//!
+//! # FFmpeg Filter Syntax Example
+//!
+//! Three 5s clips joined by 1s crossfades:
+//! ```text
+//! [c0][c1]xfade=transition=fade:duration=1.000:offset=4.000[c0x1];
+//! [c0x1][c2]xfade=transition=fade:duration=1.000:offset=8.000[vout]
+//!
+//! [a0][a1]acrossfade=d=1.000[a0x1];
+//! [a0x1][a2]acrossfade=d=1.000[aout]
+//! ```
+
+use crate::models::timeline::{Clip, Transition, TransitionKind};
+use std::ops::Range;
+
+/// `xfade` transition name for a transition kind
+fn xfade_name(kind: TransitionKind) -> &'static str {
+    match kind {
+        TransitionKind::Crossfade => "fade",
+    }
+}
+
+/// Group clip indices into runs joined by transitions
+///
+/// A clip joins the previous run when that run's last clip has a
+/// `transition_out` and ends exactly where this clip starts. Clips without a
+/// transition form runs of one.
+pub fn transition_runs(clips: &[Clip]) -> Vec<Range<usize>> {
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    for (index, clip) in clips.iter().enumerate() {
+        let joins_previous = index > 0 && {
+            let previous = &clips[index - 1];
+            previous.transition_out.is_some() && previous.end_time() == clip.start_time
+        };
+        match runs.last_mut() {
+            Some(run) if joins_previous => run.end = index + 1,
+            _ => runs.push(index..index + 1),
+        }
+    }
+    runs
+}
+
+/// Clamp each transition so it fits both clips it joins
+///
+/// `transitions[k]` joins clip `k` to clip `k + 1`. A transition may not be
+/// longer than the incoming clip, nor than what the incoming side of the
+/// previous transition left of the outgoing clip, so no two transitions
+/// overlap and every `xfade` offset moves forward.
+pub fn clamp_transitions(durations_ms: &[u64], transitions: &[Transition]) -> Vec<Transition> {
+    let mut previous_ms = 0;
+    transitions
+        .iter()
+        .zip(durations_ms.windows(2))
+        .map(|(transition, pair)| {
+            let limit_ms = pair[0].saturating_sub(previous_ms).min(pair[1]);
+            let duration_ms = transition.duration_ms.min(limit_ms);
+            if duration_ms != transition.duration_ms {
+                tracing::warn!(
+                    event = "transition_clamped",
+                    requested_ms = transition.duration_ms,
+                    clamped_ms = duration_ms,
+                    "Transition is longer than the clips it joins, shortening it"
+                );
+            }
+            previous_ms = duration_ms;
+            Transition { kind: transition.kind, duration_ms }
+        })
+        .collect()
+}
+
+/// `xfade` offset of each transition, in milliseconds from the start of the chain
+///
+/// Offset `k` is where clip `k + 1` starts fading in: the length of the chain
+/// so far (clips `0..=k` minus the earlier transitions) minus transition `k`.
+pub fn xfade_offsets_ms(durations_ms: &[u64], transitions: &[Transition]) -> Vec<u64> {
+    let mut chain_ms = durations_ms.first().copied().unwrap_or(0);
+    transitions
+        .iter()
+        .zip(durations_ms.iter().skip(1))
+        .map(|(transition, &incoming_ms)| {
+            let offset_ms = chain_ms.saturating_sub(transition.duration_ms);
+            chain_ms = offset_ms + incoming_ms;
+            offset_ms
+        })
+        .collect()
+}
+
+/// Length of a chain of clips joined by `transitions`, in milliseconds
+pub fn chain_duration_ms(durations_ms: &[u64], transitions: &[Transition]) -> u64 {
+    let total: u64 = durations_ms.iter().sum();
+    let overlap: u64 = transitions.iter().map(|t| t.duration_ms).sum();
+    total.saturating_sub(overlap)
+}
+
+/// Chain video streams with `xfade`
+///
+/// Inputs must share resolution, frame rate and pixel format. `transitions`
+/// should already be clamped with `clamp_transitions`.
+///
+/// # Arguments
+/// * `inputs` - Stream labels (without brackets), one per clip
+/// * `durations_ms` - Trimmed clip durations
+/// * `transitions` - One transition between each pair of clips
+/// * `output_label` - Label of the chained stream
+pub fn build_xfade_filter(
+    inputs: &[String],
+    durations_ms: &[u64],
+    transitions: &[Transition],
+    output_label: &str,
+) -> String {
+    if inputs.len() < 2 {
+        return format!("[{}]null[{}]", inputs.first().map(String::as_str).unwrap_or(""), output_label);
+    }
+
+    let offsets_ms = xfade_offsets_ms(durations_ms, transitions);
+    let mut current = inputs[0].clone();
+    let mut parts = Vec::new();
+    for (k, (transition, offset_ms)) in transitions.iter().zip(offsets_ms).enumerate() {
+        let next = if k + 2 == inputs.len() {
+            output_label.to_string()
+        } else {
+            format!("{}x{}", inputs[0], k + 1)
+        };
+        parts.push(format!(
+            "[{}][{}]xfade=transition={}:duration={:.3}:offset={:.3}[{}]",
+            current,
+            inputs[k + 1],
+            xfade_name(transition.kind),
+            transition.duration_ms as f64 / 1000.0,
+            offset_ms as f64 / 1000.0,
+            next
+        ));
+        current = next;
+    }
+    parts.join("; ")
+}
+
+/// Chain audio streams with `acrossfade`
+///
+/// `acrossfade` overlaps the tail of its first input with the head of its
+/// second, so no offsets are needed; clamped durations keep it in range.
+pub fn build_acrossfade_filter(inputs: &[String], transitions: &[Transition], output_label: &str) -> String {
+    if inputs.len() < 2 {
+        return format!("[{}]anull[{}]", inputs.first().map(String::as_str).unwrap_or(""), output_label);
+    }
+
+    let mut current = inputs[0].clone();
+    let mut parts = Vec::new();
+    for (k, transition) in transitions.iter().enumerate() {
+        let next = if k + 2 == inputs.len() {
+            output_label.to_string()
+        } else {
+            format!("{}x{}", inputs[0], k + 1)
+        };
+        parts.push(format!(
+            "[{}][{}]acrossfade=d={:.3}[{}]",
+            current,
+            inputs[k + 1],
+            transition.duration_ms as f64 / 1000.0,
+            next
+        ));
+        current = next;
+    }
+    parts.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crossfade(duration_ms: u64) -> Transition {
+        Transition { kind: TransitionKind::Crossfade, duration_ms }
+    }
+
+    fn labels(prefix: &str, count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("{}{}", prefix, i)).collect()
+    }
+
+    #[test]
+    fn test_xfade_offsets_follow_clip_durations() {
+        // 5s + 3s + 4s with 1s and 0.5s crossfades
+        let durations_ms = [5_000, 3_000, 4_000];
+        let transitions = [crossfade(1_000), crossfade(500)];
+
+        assert_eq!(xfade_offsets_ms(&durations_ms, &transitions), [4_000, 6_500]);
+        assert_eq!(chain_duration_ms(&durations_ms, &transitions), 10_500);
+
+        let video = build_xfade_filter(&labels("c", 3), &durations_ms, &transitions, "vout");
+        assert_eq!(
+            video,
+            "[c0][c1]xfade=transition=fade:duration=1.000:offset=4.000[c0x1]; \
+             [c0x1][c2]xfade=transition=fade:duration=0.500:offset=6.500[vout]"
+        );
+
+        let audio = build_acrossfade_filter(&labels("a", 3), &transitions, "aout");
+        assert_eq!(
+            audio,
+            "[a0][a1]acrossfade=d=1.000[a0x1]; [a0x1][a2]acrossfade=d=0.500[aout]"
+        );
+    }
+
+    #[test]
+    fn test_transitions_longer_than_clips_are_clamped() {
+        // Longer than the incoming clip
+        let clamped = clamp_transitions(&[5_000, 2_000], &[crossfade(3_000)]);
+        assert_eq!(clamped, [crossfade(2_000)]);
+        assert_eq!(xfade_offsets_ms(&[5_000, 2_000], &clamped), [3_000]);
+
+        // Longer than the outgoing clip
+        assert_eq!(clamp_transitions(&[1_000, 4_000], &[crossfade(1_500)]), [crossfade(1_000)]);
+
+        // The middle clip is shared: the second transition gets what the first left
+        let durations_ms = [4_000, 2_000, 4_000];
+        let clamped = clamp_transitions(&durations_ms, &[crossfade(1_500), crossfade(1_500)]);
+        assert_eq!(clamped, [crossfade(1_500), crossfade(500)]);
+
+        // Offsets never move backwards, so the second fade starts after the first ends
+        let offsets_ms = xfade_offsets_ms(&durations_ms, &clamped);
+        assert_eq!(offsets_ms, [2_500, 4_000]);
+        assert_eq!(chain_duration_ms(&durations_ms, &clamped), 8_000);
+    }
+}
@@ -0,0 +1,165 @@
+//! Logo watermark for exports
+//!
+//! The PNG is an extra FFmpeg input after the video (and the background image,
+//! if any). A single still frame is enough: `overlay` keeps showing the last
+//! frame of its second input until the video ends.
+//!
+//! ```text
+//! [1:v]format=rgba,colorchannelmixer=aa=0.50[wm];[0:v][wm]overlay=main_w-overlay_w-24:main_h-overlay_h-24[vmark]
+//! ```
+
+use crate::models::{Background, Corner, ExportConfig, WatermarkConfig};
+use std::io::Read;
+
+/// First eight bytes of every PNG file
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// Input arguments adding the watermark image
+pub fn watermark_input_args(watermark: &WatermarkConfig) -> Vec<String> {
+    vec!["-i".to_string(), watermark.path.clone()]
+}
+
+/// Input index of the watermark image: after the video and the background image
+pub fn watermark_input_index(config: &ExportConfig) -> usize {
+    let has_background_image =
+        config.resolution.is_some() && matches!(config.background, Background::Image { .. });
+    if has_background_image { 2 } else { 1 }
+}
+
+/// `overlay` x/y expressions pinning the logo `margin` pixels into `corner`
+///
+/// `main_w`/`main_h` are the video size and `overlay_w`/`overlay_h` the logo
+/// size, so the logo stays in its corner whatever the export resolution.
+pub fn overlay_position(corner: Corner, margin: u32) -> (String, String) {
+    let near = margin.to_string();
+    let far_x = format!("main_w-overlay_w-{}", margin);
+    let far_y = format!("main_h-overlay_h-{}", margin);
+    match corner {
+        Corner::TopLeft => (near.clone(), near),
+        Corner::TopRight => (far_x, near),
+        Corner::BottomLeft => (near, far_y),
+        Corner::BottomRight => (far_x, far_y),
+    }
+}
+
+/// Filtergraph overlaying the watermark onto `[video_label]`, labelled `[output_label]`
+pub fn build_watermark_filter(
+    watermark: &WatermarkConfig,
+    input_index: usize,
+    video_label: &str,
+    output_label: &str,
+) -> String {
+    let (x, y) = overlay_position(watermark.position, watermark.margin);
+    format!(
+        "[{}:v]format=rgba,colorchannelmixer=aa={:.2}[wm];[{}][wm]overlay={}:{}[{}]",
+        input_index,
+        watermark.opacity.clamp(0.0, 1.0),
+        video_label,
+        x,
+        y,
+        output_label
+    )
+}
+
+/// Reject a watermark FFmpeg would fail on mid-export
+///
+/// The image must be a readable PNG and the opacity within 0.0-1.0.
+pub fn validate_watermark(watermark: &WatermarkConfig) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&watermark.opacity) {
+        return Err(format!(
+            "Watermark opacity must be between 0 and 1, got {}",
+            watermark.opacity
+        ));
+    }
+
+    let mut signature = [0u8; 8];
+    std::fs::File::open(&watermark.path)
+        .and_then(|mut file| file.read_exact(&mut signature))
+        .map_err(|e| format!("Cannot read watermark image {}: {}", watermark.path, e))?;
+    if signature != PNG_SIGNATURE {
+        return Err(format!("Watermark image {} is not a PNG file", watermark.path));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watermark(position: Corner) -> WatermarkConfig {
+        WatermarkConfig {
+            path: "/tmp/logo.png".to_string(),
+            position,
+            opacity: 0.5,
+            margin: 24,
+        }
+    }
+
+    /// Evaluate an `overlay` expression of the form `a-b-c` for known sizes
+    fn evaluate(expression: &str, main: (i64, i64), overlay: (i64, i64)) -> i64 {
+        let mut terms = expression.split('-').map(|term| match term {
+            "main_w" => main.0,
+            "main_h" => main.1,
+            "overlay_w" => overlay.0,
+            "overlay_h" => overlay.1,
+            number => number.parse().unwrap(),
+        });
+        let first = terms.next().unwrap();
+        terms.fold(first, |total, term| total - term)
+    }
+
+    #[test]
+    fn test_overlay_position_for_each_corner() {
+        // 200x80 logo on a 1920x1080 video, 24px from the edges
+        let place = |corner| {
+            let (x, y) = overlay_position(corner, 24);
+            (evaluate(&x, (1920, 1080), (200, 80)), evaluate(&y, (1920, 1080), (200, 80)))
+        };
+        assert_eq!(place(Corner::TopLeft), (24, 24));
+        assert_eq!(place(Corner::TopRight), (1696, 24));
+        assert_eq!(place(Corner::BottomLeft), (24, 976));
+        assert_eq!(place(Corner::BottomRight), (1696, 976));
+
+        assert_eq!(
+            overlay_position(Corner::BottomRight, 24),
+            ("main_w-overlay_w-24".to_string(), "main_h-overlay_h-24".to_string())
+        );
+    }
+
+    #[test]
+    fn test_watermark_filter_applies_opacity_and_follows_background_image() {
+        let filter = build_watermark_filter(&watermark(Corner::TopRight), 1, "0:v", "vmark");
+        assert_eq!(
+            filter,
+            "[1:v]format=rgba,colorchannelmixer=aa=0.50[wm];[0:v][wm]overlay=main_w-overlay_w-24:24[vmark]"
+        );
+
+        let mut config = ExportConfig { watermark: Some(watermark(Corner::TopLeft)), ..ExportConfig::default() };
+        assert_eq!(watermark_input_index(&config), 1);
+        config.resolution = Some((1080, 1920));
+        config.background = Background::Image { path: "/tmp/backdrop.png".to_string() };
+        assert_eq!(watermark_input_index(&config), 2);
+    }
+
+    #[test]
+    fn test_validate_watermark_requires_readable_png() {
+        let dir = tempfile::tempdir().unwrap();
+        let png = dir.path().join("logo.png");
+        std::fs::write(&png, [&PNG_SIGNATURE[..], b"rest of image"].concat()).unwrap();
+        let mut config = watermark(Corner::BottomRight);
+        config.path = png.to_string_lossy().to_string();
+        assert!(validate_watermark(&config).is_ok());
+
+        config.opacity = 1.5;
+        assert!(validate_watermark(&config).unwrap_err().contains("opacity"));
+        config.opacity = 1.0;
+
+        let jpeg = dir.path().join("logo.jpg");
+        std::fs::write(&jpeg, [0xFF, 0xD8, 0xFF, 0xE0, 0, 0, 0, 0]).unwrap();
+        config.path = jpeg.to_string_lossy().to_string();
+        assert!(validate_watermark(&config).unwrap_err().contains("not a PNG"));
+
+        config.path = dir.path().join("missing.png").to_string_lossy().to_string();
+        assert!(validate_watermark(&config).unwrap_err().starts_with("Cannot read watermark image"));
+    }
+}
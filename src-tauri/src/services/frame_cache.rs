@@ -0,0 +1,108 @@
+//! On-disk cache of single rendered frames
+//!
+//! Shared by media library thumbnails (`media_thumbnail`) and inspector clip
+//! frames (`clip_frame`). FFmpeg renders into a temporary file next to the
+//! cache entry, which is renamed into place once the render succeeded, so an
+//! interrupted or concurrent render never leaves a truncated image that later
+//! requests would serve from the cache.
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use std::path::{Path, PathBuf};
+
+/// Temporary render target for `path`, keeping its extension so FFmpeg picks the same format
+pub fn frame_temp_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "frame".to_string());
+    let name = match path.extension() {
+        Some(ext) => format!("{}.tmp-{}.{}", stem, uuid::Uuid::new_v4(), ext.to_string_lossy()),
+        None => format!("{}.tmp-{}", stem, uuid::Uuid::new_v4()),
+    };
+    path.with_file_name(name)
+}
+
+/// Render a frame into `path` unless it is already cached
+///
+/// `build_args` gets the temporary output path to pass to FFmpeg.
+///
+/// # Returns
+/// * `Ok(true)` - Rendered now
+/// * `Ok(false)` - Already cached
+/// * `Err(anyhow::Error)` - FFmpeg could not decode the frame
+pub async fn render_cached_frame(path: &Path, build_args: impl FnOnce(&Path) -> Vec<String>) -> Result<bool> {
+    if path.exists() {
+        return Ok(false);
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create thumbnails directory")?;
+    }
+
+    let temp_path = frame_temp_path(path);
+    let output = tokio::process::Command::new("ffmpeg")
+        .args(build_args(&temp_path))
+        .output()
+        .await
+        .context("Failed to execute ffmpeg for frame")?;
+
+    if !output.status.success() || !temp_path.exists() {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(anyhow::anyhow!(
+            "FFmpeg frame render failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    if let Err(e) = std::fs::rename(&temp_path, path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e).with_context(|| format!("Failed to store frame at {}", path.display()));
+    }
+    Ok(true)
+}
+
+/// Read a cached frame as a base64 data URL of the given MIME type
+pub fn frame_data_url(path: &Path, mime_type: &str) -> Result<String> {
+    let bytes = std::fs::read(path).context("Failed to read cached frame")?;
+    Ok(format!(
+        "data:{};base64,{}",
+        mime_type,
+        general_purpose::STANDARD.encode(&bytes)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_failed_render_leaves_no_cache_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("thumbnails").join("media-1-0.png");
+
+        let temp = frame_temp_path(&path);
+        assert_eq!(temp.parent(), path.parent());
+        assert_eq!(temp.extension().unwrap(), "png");
+        assert_ne!(temp, frame_temp_path(&path));
+
+        // Missing input: FFmpeg fails (or isn't installed) either way
+        let missing = dir.path().join("missing.mp4");
+        let result = render_cached_frame(&path, |out| {
+            vec![
+                "-v".to_string(), "error".to_string(),
+                "-i".to_string(), missing.to_string_lossy().to_string(),
+                "-frames:v".to_string(), "1".to_string(),
+                "-y".to_string(), out.to_string_lossy().to_string(),
+            ]
+        })
+        .await;
+        assert!(result.is_err());
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_dir(path.parent().unwrap()).unwrap().count(), 0);
+
+        // An existing entry is served without rendering
+        std::fs::write(&path, b"png").unwrap();
+        assert!(!render_cached_frame(&path, |_| unreachable!()).await.unwrap());
+        assert_eq!(frame_data_url(&path, "image/png").unwrap(), "data:image/png;base64,cG5n");
+    }
+}
@@ -0,0 +1,188 @@
+//! Rolling waveform history for live recording meters
+//!
+//! The audio writer tasks feed every captured sample block through a
+//! `PeakRing`, which keeps max-abs peaks for the last few seconds. Memory is
+//! bounded by the window: `LIVE_WAVEFORM_WINDOW_SECS × LIVE_WAVEFORM_PEAKS_PER_SECOND`
+//! peaks per source, regardless of recording length.
+
+use crate::services::audio_capture::AudioSample;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Seconds of history kept per source
+pub const LIVE_WAVEFORM_WINDOW_SECS: usize = 5;
+
+/// Peak resolution of the history
+pub const LIVE_WAVEFORM_PEAKS_PER_SECOND: u32 = 50;
+
+lazy_static::lazy_static! {
+    static ref LIVE_WAVEFORMS: Mutex<HashMap<String, Arc<LiveWaveform>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Fixed-capacity ring of downsampled peaks, oldest first
+#[derive(Debug)]
+pub struct PeakRing {
+    capacity: usize,
+    peaks_per_second: u32,
+    peaks: VecDeque<f32>,
+    current_peak: f32,
+    samples_in_bucket: usize,
+}
+
+impl PeakRing {
+    pub fn new(window_secs: usize, peaks_per_second: u32) -> Self {
+        let capacity = (window_secs * peaks_per_second as usize).max(1);
+        Self {
+            capacity,
+            peaks_per_second: peaks_per_second.max(1),
+            peaks: VecDeque::with_capacity(capacity),
+            current_peak: 0.0,
+            samples_in_bucket: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Add interleaved samples; channels are folded into one peak per bucket
+    pub fn push(&mut self, samples: &[f32], sample_rate: u32, channels: u16) {
+        let bucket_size =
+            ((sample_rate as usize * channels.max(1) as usize) / self.peaks_per_second as usize).max(1);
+
+        for &sample in samples {
+            self.current_peak = self.current_peak.max(sample.abs().min(1.0));
+            self.samples_in_bucket += 1;
+            if self.samples_in_bucket >= bucket_size {
+                self.push_peak(self.current_peak);
+                self.current_peak = 0.0;
+                self.samples_in_bucket = 0;
+            }
+        }
+    }
+
+    fn push_peak(&mut self, peak: f32) {
+        if self.peaks.len() == self.capacity {
+            self.peaks.pop_front();
+        }
+        self.peaks.push_back(peak);
+    }
+
+    /// Peaks oldest to newest
+    pub fn peaks(&self) -> Vec<f32> {
+        self.peaks.iter().copied().collect()
+    }
+}
+
+/// Audio sources tracked during a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveAudioSource {
+    Microphone,
+    SystemAudio,
+}
+
+/// Rolling history for one recording
+#[derive(Debug)]
+pub struct LiveWaveform {
+    microphone: Mutex<PeakRing>,
+    system_audio: Mutex<PeakRing>,
+}
+
+/// Snapshot returned to the UI
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveWaveformSnapshot {
+    /// Peaks per second in each array
+    pub peaks_per_second: u32,
+    /// Microphone peaks (0.0 to 1.0), oldest first
+    pub microphone: Vec<f32>,
+    /// System audio peaks (0.0 to 1.0), oldest first
+    pub system_audio: Vec<f32>,
+}
+
+impl LiveWaveform {
+    pub fn new() -> Self {
+        Self {
+            microphone: Mutex::new(PeakRing::new(LIVE_WAVEFORM_WINDOW_SECS, LIVE_WAVEFORM_PEAKS_PER_SECOND)),
+            system_audio: Mutex::new(PeakRing::new(LIVE_WAVEFORM_WINDOW_SECS, LIVE_WAVEFORM_PEAKS_PER_SECOND)),
+        }
+    }
+
+    pub fn push(&self, source: LiveAudioSource, sample: &AudioSample) {
+        let ring = match source {
+            LiveAudioSource::Microphone => &self.microphone,
+            LiveAudioSource::SystemAudio => &self.system_audio,
+        };
+        ring.lock()
+            .unwrap()
+            .push(&sample.data, sample.sample_rate, sample.channels);
+    }
+
+    pub fn snapshot(&self) -> LiveWaveformSnapshot {
+        LiveWaveformSnapshot {
+            peaks_per_second: LIVE_WAVEFORM_PEAKS_PER_SECOND,
+            microphone: self.microphone.lock().unwrap().peaks(),
+            system_audio: self.system_audio.lock().unwrap().peaks(),
+        }
+    }
+}
+
+impl Default for LiveWaveform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create the history for a recording (replacing any previous one)
+pub fn register_live_waveform(recording_id: &str) -> Arc<LiveWaveform> {
+    let waveform = Arc::new(LiveWaveform::new());
+    LIVE_WAVEFORMS
+        .lock()
+        .unwrap()
+        .insert(recording_id.to_string(), waveform.clone());
+    waveform
+}
+
+/// History for an active recording
+pub fn get_live_waveform(recording_id: &str) -> Option<Arc<LiveWaveform>> {
+    LIVE_WAVEFORMS.lock().unwrap().get(recording_id).cloned()
+}
+
+/// Drop the history once a recording stops or is cancelled
+pub fn remove_live_waveform(recording_id: &str) {
+    LIVE_WAVEFORMS.lock().unwrap().remove(recording_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_holds_configured_window_in_order() {
+        // 2 seconds at 4 peaks/s = 8 peaks; 100 Hz mono = 25 samples per peak
+        let mut ring = PeakRing::new(2, 4);
+        assert_eq!(ring.capacity(), 8);
+
+        for n in 0..12 {
+            let level = n as f32 / 100.0;
+            ring.push(&[level; 25], 100, 1);
+        }
+
+        // Only the newest 8 remain, oldest first
+        let expected: Vec<f32> = (4..12).map(|n| n as f32 / 100.0).collect();
+        assert_eq!(ring.peaks(), expected);
+    }
+
+    #[test]
+    fn test_stereo_samples_fold_into_one_peak_per_bucket() {
+        let mut ring = PeakRing::new(1, 4);
+        // 100 Hz stereo: 50 interleaved samples per peak
+        let mut block = vec![0.1f32; 50];
+        block[7] = -0.9;
+        ring.push(&block, 100, 2);
+
+        assert_eq!(ring.peaks(), vec![0.9]);
+    }
+}
@@ -0,0 +1,190 @@
+//! In-memory log ring buffer for the built-in log viewer
+//!
+//! A tracing layer that keeps the most recent log lines in memory so the UI can
+//! show quick diagnostics without reading ~/Library/Logs/clippy/app.log.
+//! Memory is bounded: the buffer never holds more than `MAX_LOG_BUFFER_CAPACITY` lines.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Default number of log lines retained
+pub const DEFAULT_LOG_BUFFER_CAPACITY: usize = 1000;
+
+/// Hard upper bound on retained log lines
+pub const MAX_LOG_BUFFER_CAPACITY: usize = 10_000;
+
+/// A single captured log line
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLine {
+    /// Level name (TRACE, DEBUG, INFO, WARN, ERROR)
+    pub level: String,
+
+    /// Event target (module path)
+    pub target: String,
+
+    /// Milliseconds since Unix epoch
+    pub timestamp: i64,
+
+    /// Message followed by any structured fields (`key=value`)
+    pub message: String,
+}
+
+/// Bounded ring buffer of recent log lines
+pub struct LogBuffer {
+    lines: Mutex<VecDeque<LogLine>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    /// Create a buffer holding at most `capacity` lines (clamped to 1..=MAX_LOG_BUFFER_CAPACITY)
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.clamp(1, MAX_LOG_BUFFER_CAPACITY);
+        Self {
+            lines: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// Maximum number of lines retained
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Append a line, evicting the oldest if full
+    pub fn push(&self, line: LogLine) {
+        // Never panic inside the logging path; a poisoned lock just drops the line
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() == self.capacity {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+        }
+    }
+
+    /// Get up to `count` most recent lines, newest first
+    pub fn recent(&self, count: usize) -> Vec<LogLine> {
+        match self.lines.lock() {
+            Ok(lines) => lines.iter().rev().take(count).cloned().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Number of lines currently held
+    pub fn len(&self) -> usize {
+        self.lines.lock().map(|l| l.len()).unwrap_or(0)
+    }
+
+    /// Whether the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Tracing layer that records every event into a shared `LogBuffer`
+pub struct LogBufferLayer {
+    buffer: Arc<LogBuffer>,
+}
+
+impl LogBufferLayer {
+    pub fn new(buffer: Arc<LogBuffer>) -> Self {
+        Self { buffer }
+    }
+}
+
+/// Collects an event's message and fields into a single string
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        self.buffer.push(LogLine {
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            message: format!("{}{}", visitor.message, visitor.fields),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn line(message: &str) -> LogLine {
+        LogLine {
+            level: "INFO".to_string(),
+            target: "test".to_string(),
+            timestamp: 0,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_ring_buffer_keeps_last_n_newest_first() {
+        let buffer = LogBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(line(&format!("line {}", i)));
+        }
+
+        assert_eq!(buffer.len(), 3);
+        let recent: Vec<String> = buffer.recent(10).into_iter().map(|l| l.message).collect();
+        assert_eq!(recent, vec!["line 4", "line 3", "line 2"]);
+
+        let newest = buffer.recent(1);
+        assert_eq!(newest[0].message, "line 4");
+    }
+
+    #[test]
+    fn test_capacity_is_capped() {
+        assert_eq!(LogBuffer::new(usize::MAX).capacity(), MAX_LOG_BUFFER_CAPACITY);
+        assert_eq!(LogBuffer::new(0).capacity(), 1);
+    }
+
+    #[test]
+    fn test_layer_captures_events() {
+        let buffer = Arc::new(LogBuffer::new(10));
+        let subscriber = tracing_subscriber::registry().with(LogBufferLayer::new(buffer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(event = "disk_low", "Disk space low");
+        });
+
+        let lines = buffer.recent(1);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].level, "WARN");
+        assert!(lines[0].message.starts_with("Disk space low"));
+        assert!(lines[0].message.contains("event=disk_low"));
+    }
+}
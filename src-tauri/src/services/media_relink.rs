@@ -0,0 +1,165 @@
+//! Relinking missing media
+//!
+//! Clips reference their media by absolute file path. When a project is opened
+//! and that file has moved, every clip using the old path is pointed at the
+//! replacement file. Trim points are kept, but clamped if the new file is shorter,
+//! and any duration mismatch is reported so the user can double-check the edit.
+
+use crate::models::Timeline;
+use serde::{Deserialize, Serialize};
+
+/// Result of relinking media on a timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelinkResult {
+    /// Timeline with clips pointing at the new file
+    pub timeline: Timeline,
+
+    /// Number of clips that were relinked
+    pub relinked_clips: usize,
+
+    /// Human-readable warnings (e.g. duration mismatch)
+    pub warnings: Vec<String>,
+}
+
+/// Point every clip referencing `old_path` at `new_path`
+///
+/// # Arguments
+/// * `timeline` - Timeline to update
+/// * `old_path` - File path of the missing media
+/// * `new_path` - Replacement file path
+/// * `new_duration` - Duration of the replacement file in milliseconds
+pub fn relink_clips(
+    mut timeline: Timeline,
+    old_path: &str,
+    new_path: &str,
+    new_duration: u64,
+) -> RelinkResult {
+    let mut relinked_clips = 0;
+    let mut mismatched_durations: Vec<u64> = Vec::new();
+
+    for clip in timeline
+        .tracks
+        .iter_mut()
+        .flat_map(|track| track.clips.iter_mut())
+        .filter(|clip| clip.file_path == old_path)
+    {
+        if clip.duration != new_duration && !mismatched_durations.contains(&clip.duration) {
+            mismatched_durations.push(clip.duration);
+        }
+
+        clip.file_path = new_path.to_string();
+        clip.duration = new_duration;
+        clip.trim_out = clip.trim_out.min(new_duration);
+        clip.trim_in = clip.trim_in.min(clip.trim_out);
+        relinked_clips += 1;
+    }
+
+    // Clamped trims can shorten the timeline
+    timeline.total_duration = timeline
+        .tracks
+        .iter()
+        .flat_map(|track| track.clips.iter())
+        .map(|clip| clip.start_time + (clip.trim_out - clip.trim_in))
+        .max()
+        .unwrap_or(0);
+
+    let warnings = mismatched_durations
+        .into_iter()
+        .map(|expected| {
+            format!(
+                "Relinked media duration ({}) differs from the original ({}); check trim points",
+                Timeline::ms_to_ffmpeg_time(new_duration),
+                Timeline::ms_to_ffmpeg_time(expected)
+            )
+        })
+        .collect();
+
+    RelinkResult {
+        timeline,
+        relinked_clips,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Clip, Track, TrackType};
+
+    fn clip(id: &str, file_path: &str, start_time: u64, duration: u64, trim_out: u64) -> Clip {
+        Clip {
+            id: id.to_string(),
+            file_path: file_path.to_string(),
+            start_time,
+            duration,
+            trim_in: 0,
+            trim_out,
+            fade_in: None,
+            fade_out: None,
+            volume: None,
+            muted: None,
+            audio_tracks: None,
+            transform: None,
+            linked_clip_id: None,
+            group_id: None,
+            transition_out: None,
+        }
+    }
+
+    fn timeline() -> Timeline {
+        Timeline {
+            tracks: vec![
+                Track {
+                    id: "video".to_string(),
+                    track_number: 1,
+                    clips: vec![
+                        clip("a", "/old/take1.mp4", 0, 10_000, 10_000),
+                        clip("b", "/media/other.mp4", 10_000, 5_000, 5_000),
+                    ],
+                    track_type: TrackType::Video,
+                },
+                Track {
+                    id: "audio".to_string(),
+                    track_number: 2,
+                    clips: vec![clip("c", "/old/take1.mp4", 0, 10_000, 8_000)],
+                    track_type: TrackType::Audio,
+                },
+            ],
+            total_duration: 15_000,
+        }
+    }
+
+    #[test]
+    fn test_relink_updates_all_referencing_clips() {
+        let result = relink_clips(timeline(), "/old/take1.mp4", "/new/take1.mp4", 10_000);
+
+        assert_eq!(result.relinked_clips, 2);
+        assert!(result.warnings.is_empty());
+
+        let paths: Vec<&str> = result
+            .timeline
+            .tracks
+            .iter()
+            .flat_map(|t| t.clips.iter())
+            .map(|c| c.file_path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["/new/take1.mp4", "/media/other.mp4", "/new/take1.mp4"]);
+    }
+
+    #[test]
+    fn test_relink_duration_mismatch_warns_and_clamps() {
+        let result = relink_clips(timeline(), "/old/take1.mp4", "/new/take1.mp4", 6_000);
+
+        assert_eq!(result.relinked_clips, 2);
+        // Both clips expected the same 10s source, so one warning
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("00:00:06.000"));
+        assert!(result.warnings[0].contains("00:00:10.000"));
+
+        let first = &result.timeline.tracks[0].clips[0];
+        assert_eq!(first.duration, 6_000);
+        assert_eq!(first.trim_out, 6_000);
+        assert_eq!(result.timeline.total_duration, 15_000);
+    }
+}
@@ -0,0 +1,148 @@
+//! Media library thumbnails
+//!
+//! Extracts one frame of an imported media file at a requested time, scaled
+//! down to at most `THUMBNAIL_MAX_WIDTH` and encoded as PNG. Thumbnails are
+//! cached on disk (`frame_cache`) keyed by media ID and timestamp, so
+//! scrubbing back to a time that was already rendered doesn't spawn FFmpeg again.
+
+use crate::models::MediaFile;
+use crate::services::frame_cache::{frame_data_url, render_cached_frame};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Maximum thumbnail width in pixels (height follows the aspect ratio)
+pub const THUMBNAIL_MAX_WIDTH: u32 = 320;
+
+/// Distance from the end used for timestamps at or past the duration
+///
+/// Seeking to the exact duration yields no frame; backing off covers the
+/// last frame of anything recorded at 10 FPS or more.
+const LAST_FRAME_MARGIN_MS: u64 = 100;
+
+/// Clamp a requested timestamp to the media's last frame, in milliseconds
+pub fn clamp_thumbnail_timestamp(timestamp_secs: f64, duration_ms: u64) -> u64 {
+    let last_frame_ms = duration_ms.saturating_sub(LAST_FRAME_MARGIN_MS);
+    if !timestamp_secs.is_finite() || timestamp_secs <= 0.0 {
+        return 0;
+    }
+    ((timestamp_secs * 1000.0).round() as u64).min(last_frame_ms)
+}
+
+/// Cached thumbnail path for a media file at a (clamped) timestamp
+pub fn thumbnail_path(cache_dir: &Path, media_id: &str, timestamp_ms: u64) -> PathBuf {
+    cache_dir.join(format!("media-{}-{}.png", media_id, timestamp_ms))
+}
+
+/// FFmpeg arguments to extract the frame at `timestamp_ms` to `output_path`
+pub fn build_thumbnail_args(input: &str, timestamp_ms: u64, output_path: &Path) -> Vec<String> {
+    vec![
+        "-v".to_string(), "error".to_string(),
+        "-ss".to_string(), format!("{:.3}", timestamp_ms as f64 / 1000.0),
+        "-i".to_string(), input.to_string(),
+        "-frames:v".to_string(), "1".to_string(),
+        // Never upscale sources narrower than the maximum
+        "-vf".to_string(), format!("scale='min({},iw)':-2", THUMBNAIL_MAX_WIDTH),
+        "-f".to_string(), "image2".to_string(),
+        "-c:v".to_string(), "png".to_string(),
+        "-y".to_string(),
+        output_path.to_string_lossy().to_string(),
+    ]
+}
+
+/// Render (or load from cache) a thumbnail of `media` at `timestamp_secs`
+///
+/// # Returns
+/// * `Ok(String)` - Base64 data URL of the PNG thumbnail
+/// * `Err(anyhow::Error)` - FFmpeg could not decode the frame
+pub async fn generate_thumbnail(media: &MediaFile, timestamp_secs: f64, cache_dir: &Path) -> Result<String> {
+    let timestamp_ms = clamp_thumbnail_timestamp(timestamp_secs, media.duration);
+    let thumb_path = thumbnail_path(cache_dir, &media.id, timestamp_ms);
+
+    let rendered = render_cached_frame(&thumb_path, |output_path| {
+        build_thumbnail_args(&media.file_path, timestamp_ms, output_path)
+    })
+    .await?;
+    if rendered {
+        tracing::debug!(
+            event = "media_thumbnail_rendered",
+            media_id = %media.id,
+            timestamp_ms,
+            path = %thumb_path.display(),
+            "Rendered media thumbnail"
+        );
+    }
+
+    frame_data_url(&thumb_path, "image/png")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Resolution;
+
+    fn media(file_path: &Path) -> MediaFile {
+        MediaFile {
+            id: "media-1".to_string(),
+            file_path: file_path.to_string_lossy().to_string(),
+            filename: "source.mp4".to_string(),
+            duration: 2_000,
+            resolution: Resolution { width: 640, height: 360 },
+            file_size: 0,
+            codec: "h264".to_string(),
+            thumbnail: None,
+            imported_at: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_thumbnail_timestamp_clamps_to_last_frame() {
+        assert_eq!(clamp_thumbnail_timestamp(1.25, 2_000), 1_250);
+        assert_eq!(clamp_thumbnail_timestamp(30.0, 2_000), 1_900);
+        assert_eq!(clamp_thumbnail_timestamp(-1.0, 2_000), 0);
+        assert_eq!(clamp_thumbnail_timestamp(f64::NAN, 2_000), 0);
+
+        // Everything past the end shares one cache entry
+        let cache_dir = Path::new("/tmp/clippy-thumbnails");
+        assert_eq!(
+            thumbnail_path(cache_dir, "media-1", clamp_thumbnail_timestamp(5.0, 2_000)),
+            thumbnail_path(cache_dir, "media-1", clamp_thumbnail_timestamp(99.0, 2_000))
+        );
+
+        let args = build_thumbnail_args("/media/in.mp4", 1_900, Path::new("/tmp/out.png"));
+        assert!(args.windows(2).any(|w| w == ["-ss", "1.900"]));
+        assert!(args.windows(2).any(|w| w == ["-vf", "scale='min(320,iw)':-2"]));
+    }
+
+    #[tokio::test]
+    async fn test_repeated_thumbnail_is_identical_and_cached() {
+        let ffmpeg_available = std::process::Command::new("ffmpeg")
+            .arg("-version")
+            .output()
+            .is_ok_and(|o| o.status.success());
+        if !ffmpeg_available {
+            println!("Skipping thumbnail test - ffmpeg not available");
+            return;
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source.mp4");
+        let status = std::process::Command::new("ffmpeg")
+            .args(["-v", "error", "-f", "lavfi", "-i", "testsrc=size=640x360:rate=30:duration=2"])
+            .args(["-pix_fmt", "yuv420p", "-y"])
+            .arg(&source)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let cache_dir = dir.path().join("thumbnails");
+        let media = media(&source);
+        let first = generate_thumbnail(&media, 30.0, &cache_dir).await.unwrap();
+        assert!(first.starts_with("data:image/png;base64,"));
+        assert!(thumbnail_path(&cache_dir, &media.id, 1_900).exists());
+
+        // With the source gone, only the cache can answer
+        std::fs::remove_file(&source).unwrap();
+        let second = generate_thumbnail(&media, 30.0, &cache_dir).await.unwrap();
+        assert_eq!(first, second);
+    }
+}
@@ -10,8 +10,22 @@ pub mod timeline_renderer;
 pub mod composition_analyzer;
 pub mod performance_monitor;
 pub mod segment_preloader;
+pub mod edl_importer;
+pub mod log_buffer;
+pub mod capture_selftest;
+pub mod media_relink;
+pub mod audio_phase;
+pub mod clip_frame;
+pub mod waveform;
+pub mod waveform_peaks;
+pub mod live_waveform;
+pub mod export_history;
+pub mod safe_area;
+pub mod contact_sheet;
+pub mod media_thumbnail;
+pub mod frame_cache;
 
-pub use audio_capture::{AudioCapture, AudioDevice, AudioSample};
+pub use audio_capture::{AudioCapture, AudioCaptureGuard, AudioDevice, AudioSample, PcmMeta};
 pub use camera::{CameraCapture, CameraError, CameraInfo, CameraService};
 pub use ffmpeg::{VideoExporter, check_ffmpeg_available};
 pub use mpv_player::MpvPlayer;
@@ -1,13 +1,170 @@
 use anyhow::{anyhow, Result};
 use libmpv2::{events::Event, Mpv};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use thiserror::Error;
 use tracing::{debug, info};
 
+/// HDR tone-mapping mode for frame capture
+///
+/// With `vo=null`, screenshots are converted in software without tone-mapping,
+/// so PQ/HLG sources come out washed out or over-bright.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HdrToneMapping {
+    /// Tone-map only when the loaded video is HDR (PQ or HLG transfer)
+    #[default]
+    Auto,
+    /// Always tone-map to SDR BT.709
+    Always,
+    /// Capture frames as decoded
+    Off,
+}
+
+/// Tone-map HDR to SDR BT.709 (Hable curve) ahead of the screenshot
+const TONE_MAP_FILTER: &str = "lavfi=[zscale=t=linear:npl=100,format=gbrpf32le,zscale=p=bt709,tonemap=tonemap=hable:desat=0,zscale=t=bt709:m=bt709:r=tv,format=yuv420p]";
+
+/// Whether captured frames should be tone-mapped
+pub fn should_tone_map(mode: HdrToneMapping, source_is_hdr: bool) -> bool {
+    match mode {
+        HdrToneMapping::Auto => source_is_hdr,
+        HdrToneMapping::Always => true,
+        HdrToneMapping::Off => false,
+    }
+}
+
+/// MPV properties for screenshot color handling
+///
+/// Tone-mapping runs as a video filter (screenshots are taken after filters)
+/// and tags the JPEG with its colorspace so viewers color-manage it correctly.
+pub fn screenshot_color_options(tone_map: bool) -> Vec<(&'static str, &'static str)> {
+    if tone_map {
+        vec![
+            ("screenshot-tag-colorspace", "yes"),
+            ("screenshot-high-bit-depth", "no"),
+            ("vf", TONE_MAP_FILTER),
+        ]
+    } else {
+        vec![
+            ("screenshot-tag-colorspace", "no"),
+            ("vf", ""),
+        ]
+    }
+}
+
+/// How long frame steps and exact seeks wait for MPV to land on the new frame
+const SEEK_SETTLE_TIMEOUT_SECS: f64 = 2.0;
+
+/// Slowest playback speed accepted by `MpvPlayer::set_speed`
+pub const MIN_PLAYBACK_SPEED: f64 = 0.25;
+
+/// Fastest playback speed accepted by `MpvPlayer::set_speed`
+pub const MAX_PLAYBACK_SPEED: f64 = 4.0;
+
+/// Check a playback speed multiplier against the supported range
+pub fn validate_playback_speed(speed: f64) -> Result<f64> {
+    if !(MIN_PLAYBACK_SPEED..=MAX_PLAYBACK_SPEED).contains(&speed) {
+        return Err(anyhow!(
+            "Playback speed {} out of range ({}x to {}x)",
+            speed,
+            MIN_PLAYBACK_SPEED,
+            MAX_PLAYBACK_SPEED
+        ));
+    }
+    Ok(speed)
+}
+
+/// Timestamp (in seconds) of `frame_number` in a constant `fps` video
+pub fn frame_timestamp(frame_number: u64, fps: f64) -> Result<f64> {
+    if !fps.is_finite() || fps <= 0.0 {
+        return Err(anyhow!("Invalid frame rate: {}", fps));
+    }
+    Ok(frame_number as f64 / fps)
+}
+
+/// Playlist index to switch to from `position` in a playlist of `count` items
+///
+/// `None` when already at the first (backward) or last (forward) item.
+pub fn playlist_step_target(position: i64, count: i64, forward: bool) -> Option<i64> {
+    let target = if forward { position + 1 } else { position - 1 };
+    (0..count).contains(&target).then_some(target)
+}
+
+/// Wait until MPV reports `FileLoaded`, ignoring the `EndFile` of the previous file
+fn wait_for_file_loaded(mpv: &mut Mpv, timeout_secs: f64) -> Result<()> {
+    let start_time = std::time::Instant::now();
+
+    loop {
+        let remaining_timeout = timeout_secs - start_time.elapsed().as_secs_f64();
+        if remaining_timeout <= 0.0 {
+            return Err(anyhow!("Timeout waiting for file to load"));
+        }
+
+        // Wait for next event with remaining timeout
+        if let Some(event_result) = mpv.wait_event(remaining_timeout) {
+            match event_result {
+                Ok(Event::FileLoaded) => {
+                    info!("[MPV] File loaded successfully (FileLoaded event received)");
+                    return Ok(());
+                }
+                Ok(Event::EndFile(reason)) => {
+                    // EndFile during loadfile is expected (replacing previous file)
+                    // Only treat as error if it's an explicit error reason
+                    debug!("[MPV] EndFile event during load (reason: {:?}) - continuing to wait for FileLoaded", reason);
+                    // Continue waiting for FileLoaded event
+                }
+                Ok(event) => {
+                    // Log other events for debugging
+                    debug!("[MPV] Received event while loading: {:?}", event);
+                }
+                Err(e) => {
+                    debug!("[MPV] Event polling: {:?}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Why `MpvPlayer::save_screenshot` failed
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ScreenshotError {
+    /// Nothing to take a still from
+    #[error("No file loaded")]
+    NoFileLoaded,
+
+    /// The folder to save into doesn't exist
+    #[error("Output directory does not exist: {0}")]
+    MissingDirectory(String),
+
+    /// MPV couldn't write the image
+    #[error("Failed to write screenshot: {0}")]
+    WriteFailed(String),
+}
+
+impl ScreenshotError {
+    /// Stable identifier for the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            ScreenshotError::NoFileLoaded => "no_file_loaded",
+            ScreenshotError::MissingDirectory(_) => "missing_directory",
+            ScreenshotError::WriteFailed(_) => "write_failed",
+        }
+    }
+}
+
+/// Whether an MPV `video-params/gamma` value is an HDR transfer function
+fn is_hdr_transfer(gamma: &str) -> bool {
+    matches!(gamma, "pq" | "hlg")
+}
+
 /// MPV Player Service
 ///
 /// Wraps libmpv for universal codec support with professional-grade playback
 pub struct MpvPlayer {
     mpv: Arc<Mutex<Mpv>>,
+    /// Whether the tone-mapping screenshot options are currently applied
+    tone_mapped: Mutex<bool>,
 }
 
 impl MpvPlayer {
@@ -39,6 +196,7 @@ impl MpvPlayer {
 
         Ok(Self {
             mpv: Arc::new(Mutex::new(mpv)),
+            tone_mapped: Mutex::new(false),
         })
     }
 
@@ -55,38 +213,78 @@ impl MpvPlayer {
         // Wait for FileLoaded event
         // Note: loadfile with "replace" triggers EndFile for previous file, then FileLoaded for new file
         // We must ignore EndFile events and only wait for FileLoaded
-        let start_time = std::time::Instant::now();
-        let timeout_secs = 5.0;
+        wait_for_file_loaded(&mut mpv, 5.0)
+    }
 
-        loop {
-            let remaining_timeout = timeout_secs - start_time.elapsed().as_secs_f64();
-            if remaining_timeout <= 0.0 {
-                return Err(anyhow!("Timeout waiting for file to load"));
-            }
+    /// Replace the playlist with `paths` and wait for the first file to load
+    ///
+    /// With `keep-open=always`, playback pauses at the end of each item rather
+    /// than advancing; use `playlist_next` / `playlist_prev` to switch.
+    pub fn load_playlist(&self, paths: &[String]) -> Result<()> {
+        let (first, rest) = paths
+            .split_first()
+            .ok_or_else(|| anyhow!("Playlist is empty"))?;
+        info!("[MPV] Loading playlist of {} files", paths.len());
 
-            // Wait for next event with remaining timeout
-            if let Some(event_result) = mpv.wait_event(remaining_timeout) {
-                match event_result {
-                    Ok(Event::FileLoaded) => {
-                        info!("[MPV] File loaded successfully (FileLoaded event received)");
-                        return Ok(());
-                    }
-                    Ok(Event::EndFile(reason)) => {
-                        // EndFile during loadfile is expected (replacing previous file)
-                        // Only treat as error if it's an explicit error reason
-                        debug!("[MPV] EndFile event during load (reason: {:?}) - continuing to wait for FileLoaded", reason);
-                        // Continue waiting for FileLoaded event
-                    }
-                    Ok(event) => {
-                        // Log other events for debugging
-                        debug!("[MPV] Received event while loading: {:?}", event);
-                    }
-                    Err(e) => {
-                        debug!("[MPV] Event polling: {:?}", e);
-                    }
-                }
-            }
+        let mut mpv = self.mpv.lock().unwrap();
+
+        mpv.command("loadfile", &[first.as_str(), "replace"])
+            .map_err(|e| anyhow!("Failed to load file {}: {:?}", first, e))?;
+        for path in rest {
+            mpv.command("loadfile", &[path.as_str(), "append"])
+                .map_err(|e| anyhow!("Failed to append file {}: {:?}", path, e))?;
         }
+
+        wait_for_file_loaded(&mut mpv, 5.0)
+    }
+
+    /// Switch to the next playlist item; returns the new playlist position
+    ///
+    /// At the last item this is a no-op and returns the current position.
+    pub fn playlist_next(&self) -> Result<i64> {
+        self.step_playlist(true)
+    }
+
+    /// Switch to the previous playlist item; returns the new playlist position
+    ///
+    /// At the first item this is a no-op and returns the current position.
+    pub fn playlist_prev(&self) -> Result<i64> {
+        self.step_playlist(false)
+    }
+
+    fn step_playlist(&self, forward: bool) -> Result<i64> {
+        let mut mpv = self.mpv.lock().unwrap();
+        let position: i64 = mpv.get_property("playlist-pos")
+            .map_err(|e| anyhow!("Failed to get playlist position: {:?}", e))?;
+        let count: i64 = mpv.get_property("playlist-count")
+            .map_err(|e| anyhow!("Failed to get playlist count: {:?}", e))?;
+
+        let Some(target) = playlist_step_target(position, count, forward) else {
+            debug!("[MPV] Playlist step ignored at position {} of {}", position, count);
+            return Ok(position);
+        };
+
+        let command = if forward { "playlist-next" } else { "playlist-prev" };
+        debug!("[MPV] Switching playlist item ({}) to {}", command, target);
+        mpv.command(command, &[])
+            .map_err(|e| anyhow!("Failed to switch playlist item: {:?}", e))?;
+        wait_for_file_loaded(&mut mpv, 5.0)?;
+
+        Ok(target)
+    }
+
+    /// Get the index of the current playlist item
+    pub fn get_playlist_position(&self) -> Result<i64> {
+        let mpv = self.mpv.lock().unwrap();
+        mpv.get_property::<i64>("playlist-pos")
+            .map_err(|e| anyhow!("Failed to get playlist position: {:?}", e))
+    }
+
+    /// Get the number of playlist items
+    pub fn get_playlist_count(&self) -> Result<i64> {
+        let mpv = self.mpv.lock().unwrap();
+        mpv.get_property::<i64>("playlist-count")
+            .map_err(|e| anyhow!("Failed to get playlist count: {:?}", e))
     }
 
     /// Play the loaded video
@@ -122,6 +320,67 @@ impl MpvPlayer {
         Ok(())
     }
 
+    /// Step exactly one frame forward or back and return the new time (in seconds)
+    ///
+    /// Uses MPV's `frame-step` / `frame-back-step`, which leave playback paused
+    /// on the new frame. Both are asynchronous, so this waits (up to
+    /// `SEEK_SETTLE_TIMEOUT_SECS`) for `time-pos` to move; at either end of the
+    /// file the unchanged time is returned.
+    pub fn step_frame(&self, forward: bool) -> Result<f64> {
+        let command = if forward { "frame-step" } else { "frame-back-step" };
+        debug!("[MPV] Stepping one frame ({})", command);
+
+        let mut mpv = self.mpv.lock().unwrap();
+        let before = mpv.get_property::<f64>("time-pos").ok();
+        mpv.command(command, &[])
+            .map_err(|e| anyhow!("Failed to step frame: {:?}", e))?;
+
+        let start_time = std::time::Instant::now();
+        loop {
+            let time = mpv.get_property::<f64>("time-pos").ok();
+            if time.is_some() && time != before {
+                break;
+            }
+            if start_time.elapsed().as_secs_f64() >= SEEK_SETTLE_TIMEOUT_SECS {
+                debug!("[MPV] Timeout waiting for frame step, time unchanged");
+                break;
+            }
+            // Drains events while waiting for the step to render
+            mpv.wait_event(0.01);
+        }
+
+        mpv.get_property::<f64>("time-pos")
+            .map_err(|e| anyhow!("Failed to get time after frame step: {:?}", e))
+    }
+
+    /// Seek exactly to `frame_number` at `fps` and return the resulting time (in seconds)
+    ///
+    /// Uses an `exact` (hr-seek) seek so MPV decodes up to the frame instead of
+    /// stopping at the previous keyframe, and waits for the seek to finish.
+    pub fn seek_to_frame(&self, frame_number: u64, fps: f64) -> Result<f64> {
+        let target = frame_timestamp(frame_number, fps)?;
+        debug!("[MPV] Seeking to frame {} ({:.6}s at {} fps)", frame_number, target, fps);
+
+        let mut mpv = self.mpv.lock().unwrap();
+        mpv.command("seek", &[&target.to_string(), "absolute+exact"])
+            .map_err(|e| anyhow!("Failed to seek to frame {}: {:?}", frame_number, e))?;
+
+        let start_time = std::time::Instant::now();
+        loop {
+            let remaining_timeout = SEEK_SETTLE_TIMEOUT_SECS - start_time.elapsed().as_secs_f64();
+            if remaining_timeout <= 0.0 {
+                debug!("[MPV] Timeout waiting for PlaybackRestart after frame seek");
+                break;
+            }
+            if let Some(Ok(Event::PlaybackRestart)) = mpv.wait_event(remaining_timeout) {
+                break;
+            }
+        }
+
+        mpv.get_property::<f64>("time-pos")
+            .map_err(|e| anyhow!("Failed to get time after frame seek: {:?}", e))
+    }
+
     /// Get current playback time (in seconds)
     pub fn get_time(&self) -> Result<f64> {
         let mpv = self.mpv.lock().unwrap();
@@ -142,7 +401,7 @@ impl MpvPlayer {
         Ok(time)
     }
 
-    /// Get video duration (in seconds)
+    /// Get video duration (in seconds) of the current playlist item
     pub fn get_duration(&self) -> Result<f64> {
         let mpv = self.mpv.lock().unwrap();
         let duration: f64 = mpv.get_property("duration")
@@ -229,10 +488,36 @@ impl MpvPlayer {
 
     /// Capture current frame as JPEG and return base64-encoded data
     pub fn capture_frame(&self) -> Result<Vec<u8>> {
+        self.capture_frame_with_tone_mapping(HdrToneMapping::Auto)
+    }
+
+    /// Capture current frame as JPEG, tone-mapping HDR video per `mode`
+    ///
+    /// Options are only changed when the tone-mapping decision changes, since
+    /// frames are captured continuously during playback and changing `vf`
+    /// reinitializes the filter chain.
+    pub fn capture_frame_with_tone_mapping(&self, mode: HdrToneMapping) -> Result<Vec<u8>> {
         use std::fs;
 
         let mpv = self.mpv.lock().unwrap();
 
+        let source_is_hdr = mpv
+            .get_property::<String>("video-params/gamma")
+            .map(|gamma| is_hdr_transfer(&gamma))
+            .unwrap_or(false);
+        let tone_map = should_tone_map(mode, source_is_hdr);
+
+        let mut tone_mapped = self.tone_mapped.lock().unwrap();
+        if *tone_mapped != tone_map {
+            debug!("[MPV] Screenshot tone-mapping {} (HDR source: {})", if tone_map { "enabled" } else { "disabled" }, source_is_hdr);
+            for (property, value) in screenshot_color_options(tone_map) {
+                mpv.set_property(property, value)
+                    .map_err(|e| anyhow!("Failed to set {}: {:?}", property, e))?;
+            }
+            *tone_mapped = tone_map;
+        }
+        drop(tone_mapped);
+
         // Create temp directory for screenshots
         let temp_dir = std::env::temp_dir();
         let screenshot_path = temp_dir.join(format!("mpv_frame_{}.jpg", std::process::id()));
@@ -253,6 +538,40 @@ impl MpvPlayer {
         Ok(image_data)
     }
 
+    /// Save the current frame as a PNG at the video's full resolution
+    ///
+    /// MPV picks the image format from the extension, so the file is always
+    /// written with a `.png` extension. The frame is taken before scaling, so
+    /// the still has the video's own size rather than the preview's.
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - Path of the written PNG
+    /// * `Err(ScreenshotError)` - No file loaded, the folder is missing, or the write failed
+    pub fn save_screenshot(&self, output_path: &Path) -> std::result::Result<PathBuf, ScreenshotError> {
+        let output_path = output_path.with_extension("png");
+        let directory = output_path.parent().unwrap_or(Path::new(""));
+        if !directory.is_dir() {
+            return Err(ScreenshotError::MissingDirectory(directory.display().to_string()));
+        }
+
+        let mpv = self.mpv.lock().unwrap();
+        if mpv.get_property::<String>("path").is_err() {
+            return Err(ScreenshotError::NoFileLoaded);
+        }
+
+        info!("[MPV] Saving screenshot to: {}", output_path.display());
+
+        let target = output_path.to_string_lossy();
+        mpv.command("screenshot-to-file", &[&*target, "video"])
+            .map_err(|e| ScreenshotError::WriteFailed(format!("{:?}", e)))?;
+
+        match std::fs::metadata(&output_path) {
+            Ok(metadata) if metadata.len() > 0 => Ok(output_path),
+            Ok(_) => Err(ScreenshotError::WriteFailed(format!("{} is empty", output_path.display()))),
+            Err(e) => Err(ScreenshotError::WriteFailed(e.to_string())),
+        }
+    }
+
     /// Set volume for current playback (Story 3.9.1/3.10.1)
     ///
     /// # Arguments
@@ -333,6 +652,27 @@ impl MpvPlayer {
         Ok(())
     }
 
+    /// Set the playback speed multiplier (`MIN_PLAYBACK_SPEED` to `MAX_PLAYBACK_SPEED`)
+    ///
+    /// Audio is pitch-corrected by MPV's default `audio-pitch-correction`.
+    pub fn set_speed(&self, speed: f64) -> Result<()> {
+        let speed = validate_playback_speed(speed)?;
+        debug!("[MPV] Setting playback speed: {}x", speed);
+
+        let mpv = self.mpv.lock().unwrap();
+        mpv.set_property("speed", speed)
+            .map_err(|e| anyhow!("Failed to set playback speed: {:?}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the playback speed multiplier
+    pub fn get_speed(&self) -> Result<f64> {
+        let mpv = self.mpv.lock().unwrap();
+        mpv.get_property::<f64>("speed")
+            .map_err(|e| anyhow!("Failed to get playback speed: {:?}", e))
+    }
+
     /// Clear all audio filters (Story 3.10.1)
     pub fn clear_audio_filters(&self) -> Result<()> {
         let mpv = self.mpv.lock().unwrap();
@@ -356,6 +696,180 @@ impl Default for MpvPlayer {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_tone_mapping_options_applied_when_requested() {
+        assert!(should_tone_map(HdrToneMapping::Always, false));
+        assert!(should_tone_map(HdrToneMapping::Auto, true));
+        assert!(!should_tone_map(HdrToneMapping::Auto, false));
+        assert!(!should_tone_map(HdrToneMapping::Off, true));
+
+        let on = screenshot_color_options(true);
+        assert!(on.contains(&("screenshot-tag-colorspace", "yes")));
+        assert!(on.iter().any(|(p, v)| *p == "vf" && v.contains("tonemap")));
+
+        // Turning it off restores the unfiltered chain
+        let off = screenshot_color_options(false);
+        assert!(off.contains(&("vf", "")));
+
+        assert!(is_hdr_transfer("pq"));
+        assert!(is_hdr_transfer("hlg"));
+        assert!(!is_hdr_transfer("bt.1886"));
+    }
+
+    #[test]
+    fn test_frame_timestamp() {
+        assert_eq!(frame_timestamp(0, 30.0).unwrap(), 0.0);
+        assert!((frame_timestamp(45, 30.0).unwrap() - 1.5).abs() < 1e-9);
+        assert!((frame_timestamp(1001, 29.97).unwrap() - 33.4).abs() < 1e-3);
+        assert!(frame_timestamp(10, 0.0).is_err());
+        assert!(frame_timestamp(10, f64::NAN).is_err());
+    }
+
+    /// Test frame stepping lands on exact frames (needs FFmpeg to generate the clip)
+    #[test]
+    fn test_step_frame_advances_by_frame_duration() {
+        let dir = tempfile::tempdir().unwrap();
+        let clip = dir.path().join("frames.mp4");
+        let generated = std::process::Command::new("ffmpeg")
+            .args(["-v", "error", "-f", "lavfi", "-i", "testsrc=duration=2:size=320x240:rate=30", "-y"])
+            .arg(&clip)
+            .status();
+        if !matches!(generated, Ok(status) if status.success()) {
+            println!("⚠️  Skipping frame step test - FFmpeg not available");
+            return;
+        }
+
+        let fps = 30.0;
+        let frame = 1.0 / fps;
+        let player = MpvPlayer::new().expect("Failed to create MPV player");
+        player.load_file(clip.to_str().unwrap()).expect("Failed to load file");
+
+        let start = player.seek_to_frame(10, fps).expect("Failed to seek to frame");
+        assert!((start - 10.0 * frame).abs() < frame, "frame 10 at {:.4}s", start);
+
+        let steps = 5;
+        let mut time = start;
+        for _ in 0..steps {
+            time = player.step_frame(true).expect("Failed to step forward");
+        }
+        let advanced = time - start;
+        assert!(
+            (advanced - steps as f64 * frame).abs() < frame,
+            "{} steps advanced {:.4}s",
+            steps,
+            advanced
+        );
+
+        let back = player.step_frame(false).expect("Failed to step back");
+        assert!((time - back - frame).abs() < frame, "step back from {:.4}s to {:.4}s", time, back);
+    }
+
+    #[test]
+    fn test_playlist_step_target_stays_in_range() {
+        assert_eq!(playlist_step_target(0, 2, true), Some(1));
+        assert_eq!(playlist_step_target(1, 2, true), None);
+        assert_eq!(playlist_step_target(1, 2, false), Some(0));
+        assert_eq!(playlist_step_target(0, 2, false), None);
+        assert_eq!(playlist_step_target(-1, 0, true), None);
+    }
+
+    /// Test switching playlist items (needs FFmpeg to generate the clips)
+    #[test]
+    fn test_playlist_next_advances_position() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut paths = Vec::new();
+        for duration in [1, 2] {
+            let clip = dir.path().join(format!("clip_{}s.mp4", duration));
+            let generated = std::process::Command::new("ffmpeg")
+                .args(["-v", "error", "-f", "lavfi"])
+                .arg("-i")
+                .arg(format!("testsrc=duration={}:size=320x240:rate=30", duration))
+                .arg("-y")
+                .arg(&clip)
+                .status();
+            if !matches!(generated, Ok(status) if status.success()) {
+                println!("⚠️  Skipping playlist test - FFmpeg not available");
+                return;
+            }
+            paths.push(clip.to_string_lossy().to_string());
+        }
+
+        let player = MpvPlayer::new().expect("Failed to create MPV player");
+        assert!(player.load_playlist(&[]).is_err());
+
+        player.load_playlist(&paths).expect("Failed to load playlist");
+        assert_eq!(player.get_playlist_count().expect("Failed to get count"), 2);
+        assert_eq!(player.get_playlist_position().expect("Failed to get position"), 0);
+        assert!((player.get_duration().expect("Failed to get duration") - 1.0).abs() < 0.1);
+
+        assert_eq!(player.playlist_next().expect("Failed to go to next"), 1);
+        assert_eq!(player.get_playlist_position().expect("Failed to get position"), 1);
+        assert!((player.get_duration().expect("Failed to get duration") - 2.0).abs() < 0.1);
+
+        // Past either end is a no-op
+        assert_eq!(player.playlist_next().expect("Failed to go to next"), 1);
+        assert_eq!(player.playlist_prev().expect("Failed to go to previous"), 0);
+        assert_eq!(player.playlist_prev().expect("Failed to go to previous"), 0);
+    }
+
+    /// Test saving a still (needs FFmpeg to generate the clip)
+    #[test]
+    fn test_save_screenshot_writes_full_resolution_png() {
+        let dir = tempfile::tempdir().unwrap();
+        let clip = dir.path().join("still.mp4");
+        let generated = std::process::Command::new("ffmpeg")
+            .args(["-v", "error", "-f", "lavfi", "-i", "testsrc=duration=2:size=640x360:rate=30", "-y"])
+            .arg(&clip)
+            .status();
+        if !matches!(generated, Ok(status) if status.success()) {
+            println!("⚠️  Skipping screenshot test - FFmpeg not available");
+            return;
+        }
+
+        let player = MpvPlayer::new().expect("Failed to create MPV player");
+        let output = dir.path().join("frame.jpg");
+        assert_eq!(player.save_screenshot(&output), Err(ScreenshotError::NoFileLoaded));
+
+        player.load_file(clip.to_str().unwrap()).expect("Failed to load file");
+        player.seek_to_frame(30, 30.0).expect("Failed to seek");
+
+        let saved = player.save_screenshot(&output).expect("Failed to save screenshot");
+        assert_eq!(saved, dir.path().join("frame.png"));
+        let png = std::fs::read(&saved).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        // IHDR width and height: the source size, not a scaled preview
+        assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 640);
+        assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 360);
+
+        let missing = dir.path().join("missing").join("frame.png");
+        assert!(matches!(player.save_screenshot(&missing), Err(ScreenshotError::MissingDirectory(_))));
+    }
+
+    #[test]
+    fn test_playback_speed_range() {
+        assert_eq!(validate_playback_speed(1.0).unwrap(), 1.0);
+        assert_eq!(validate_playback_speed(MIN_PLAYBACK_SPEED).unwrap(), 0.25);
+        assert_eq!(validate_playback_speed(MAX_PLAYBACK_SPEED).unwrap(), 4.0);
+        assert!(validate_playback_speed(0.1).is_err());
+        assert!(validate_playback_speed(4.5).is_err());
+        assert!(validate_playback_speed(0.0).is_err());
+        assert!(validate_playback_speed(f64::NAN).is_err());
+    }
+
+    /// Test speed changes are applied to MPV
+    #[test]
+    fn test_set_speed_reflected_by_get_speed() {
+        let player = MpvPlayer::new().expect("Failed to create MPV player");
+        assert_eq!(player.get_speed().expect("Failed to get speed"), 1.0);
+
+        player.set_speed(2.0).expect("Failed to set speed");
+        assert_eq!(player.get_speed().expect("Failed to get speed"), 2.0);
+
+        // Out-of-range speeds are rejected without touching MPV
+        assert!(player.set_speed(8.0).is_err());
+        assert_eq!(player.get_speed().expect("Failed to get speed"), 2.0);
+    }
+
     /// Test dimension retrieval for multiple video codecs
     #[test]
     fn test_dimension_retrieval_all_codecs() {
@@ -28,6 +28,7 @@
 // Suppress warnings from objc crate's internal macros which use outdated cfg checks
 #![allow(unexpected_cfgs)]
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
 
@@ -203,21 +204,150 @@ fn check_macos_version() -> Result<bool, String> {
     Ok(minor >= 3)
 }
 
-/// Check if the app has microphone permission
+/// Permission kinds that have a dedicated System Settings privacy pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionKind {
+    ScreenRecording,
+    Camera,
+    Microphone,
+    /// Unrecognized kind from the frontend - opens the general Privacy pane
+    #[serde(other)]
+    Unknown,
+}
+
+/// General Privacy & Security pane, used as a fallback
+const PRIVACY_SETTINGS_URL: &str = "x-apple.systempreferences:com.apple.preference.security?Privacy";
+
+/// Get the System Settings deep-link URL for a permission kind
+pub fn privacy_settings_url(kind: PermissionKind) -> &'static str {
+    match kind {
+        PermissionKind::ScreenRecording => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture"
+        }
+        PermissionKind::Camera => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_Camera"
+        }
+        PermissionKind::Microphone => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone"
+        }
+        PermissionKind::Unknown => PRIVACY_SETTINGS_URL,
+    }
+}
+
+/// Open the System Settings privacy pane for a permission kind
 ///
-/// Returns `Ok(true)` if permission is granted, `Ok(false)` if not granted or not determined.
+/// # Errors
 ///
-/// # Platform Support
+/// Returns `PermissionError::RequestFailed` if System Settings could not be opened.
+#[cfg(target_os = "macos")]
+pub fn open_privacy_settings(kind: PermissionKind) -> Result<(), PermissionError> {
+    let url = privacy_settings_url(kind);
+    info!("Opening privacy settings for {:?}: {}", kind, url);
+
+    let status = std::process::Command::new("open")
+        .arg(url)
+        .status()
+        .map_err(|e| PermissionError::RequestFailed(format!("Failed to open System Settings: {}", e)))?;
+
+    if status.success() {
+        return Ok(());
+    }
+
+    // Specific pane failed to open (e.g. renamed anchor on a newer macOS) - try the general pane
+    warn!("Failed to open {} pane, falling back to general Privacy pane", url);
+    let status = std::process::Command::new("open")
+        .arg(PRIVACY_SETTINGS_URL)
+        .status()
+        .map_err(|e| PermissionError::RequestFailed(format!("Failed to open System Settings: {}", e)))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(PermissionError::RequestFailed(
+            "System Settings could not be opened".to_string(),
+        ))
+    }
+}
+
+/// Tri-state permission status
+///
+/// The bool-returning `check_*` functions collapse "not asked yet" into "not granted";
+/// this keeps the distinction so the UI can decide between prompting and deep-linking
+/// to System Settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionState {
+    /// User granted access
+    Granted,
+    /// User denied access, or access is restricted by policy
+    Denied,
+    /// User has not been asked yet (or the status could not be determined)
+    NotDetermined,
+}
+
+/// Aggregate permission status for all capture sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionStatus {
+    pub screen_recording: PermissionState,
+    pub camera: PermissionState,
+    pub microphone: PermissionState,
+}
+
+impl PermissionStatus {
+    /// Build the aggregate from individual check results
+    ///
+    /// A failed check maps to `NotDetermined` so one broken check doesn't hide the others.
+    pub fn from_results(
+        screen_recording: Result<PermissionState, PermissionError>,
+        camera: Result<PermissionState, PermissionError>,
+        microphone: Result<PermissionState, PermissionError>,
+    ) -> Self {
+        fn resolve(kind: &str, result: Result<PermissionState, PermissionError>) -> PermissionState {
+            result.unwrap_or_else(|e| {
+                warn!("Could not determine {} permission: {}", kind, e);
+                PermissionState::NotDetermined
+            })
+        }
+
+        Self {
+            screen_recording: resolve("screen recording", screen_recording),
+            camera: resolve("camera", camera),
+            microphone: resolve("microphone", microphone),
+        }
+    }
+}
+
+/// Check screen recording, camera, and microphone permissions in one call
+pub fn check_all_permissions() -> PermissionStatus {
+    PermissionStatus::from_results(
+        screen_recording_permission_status(),
+        camera_permission_status(),
+        microphone_permission_status(),
+    )
+}
+
+/// Get the tri-state screen recording permission status
 ///
-/// This function only works on macOS. On other platforms, it returns an error.
+/// macOS only exposes granted/not granted for screen capture (CGPreflightScreenCaptureAccess),
+/// so a missing grant is reported as `Denied`.
+#[cfg(target_os = "macos")]
+pub fn screen_recording_permission_status() -> Result<PermissionState, PermissionError> {
+    if check_screen_recording_permission()? {
+        Ok(PermissionState::Granted)
+    } else {
+        Ok(PermissionState::Denied)
+    }
+}
+
+/// Query `[AVCaptureDevice authorizationStatusForMediaType:]`
 ///
-/// # Errors
+/// # Arguments
 ///
-/// Returns `PermissionError::CheckFailed` if the permission check encounters an error.
+/// * `media_type` - AVMediaType four-char code ("soun" for audio, "vide" for video)
 #[cfg(target_os = "macos")]
-pub fn check_microphone_permission() -> Result<bool, PermissionError> {
-    debug!("Checking microphone permission status");
-
+fn av_authorization_status(media_type: &str) -> Result<PermissionState, PermissionError> {
     use objc::runtime::{Class, Object};
     use objc::{msg_send, sel, sel_impl};
 
@@ -236,8 +366,7 @@ pub fn check_microphone_permission() -> Result<bool, PermissionError> {
                 )
             })?;
 
-        // Create NSString for "soun" (AVMediaTypeAudio)
-        let media_type_str = "soun";
+        // Create NSString for the media type
         let ns_string_class = Class::get("NSString")
             .ok_or_else(|| {
                 PermissionError::CheckFailed(
@@ -248,8 +377,8 @@ pub fn check_microphone_permission() -> Result<bool, PermissionError> {
         let ns_string: *mut Object = msg_send![ns_string_class, alloc];
         let ns_string: *mut Object = msg_send![
             ns_string,
-            initWithBytes: media_type_str.as_ptr()
-            length: media_type_str.len()
+            initWithBytes: media_type.as_ptr()
+            length: media_type.len()
             encoding: 4 as usize // UTF8 encoding
         ];
 
@@ -263,26 +392,63 @@ pub fn check_microphone_permission() -> Result<bool, PermissionError> {
         let _: () = msg_send![ns_string, release];
 
         match status {
-            AV_AUTHORIZATION_STATUS_AUTHORIZED => {
-                info!("Microphone permission granted");
-                Ok(true)
-            }
+            AV_AUTHORIZATION_STATUS_AUTHORIZED => Ok(PermissionState::Granted),
             AV_AUTHORIZATION_STATUS_DENIED | AV_AUTHORIZATION_STATUS_RESTRICTED => {
-                warn!("Microphone permission denied or restricted");
-                Ok(false)
-            }
-            AV_AUTHORIZATION_STATUS_NOT_DETERMINED => {
-                debug!("Microphone permission not determined yet");
-                Ok(false)
+                Ok(PermissionState::Denied)
             }
+            AV_AUTHORIZATION_STATUS_NOT_DETERMINED => Ok(PermissionState::NotDetermined),
             _ => {
-                warn!("Unknown microphone authorization status: {}", status);
-                Ok(false)
+                warn!("Unknown authorization status for {}: {}", media_type, status);
+                Ok(PermissionState::NotDetermined)
             }
         }
     }
 }
 
+/// Get the tri-state microphone permission status
+#[cfg(target_os = "macos")]
+pub fn microphone_permission_status() -> Result<PermissionState, PermissionError> {
+    av_authorization_status("soun")
+}
+
+/// Get the tri-state camera permission status
+#[cfg(target_os = "macos")]
+pub fn camera_permission_status() -> Result<PermissionState, PermissionError> {
+    av_authorization_status("vide")
+}
+
+/// Check if the app has microphone permission
+///
+/// Returns `Ok(true)` if permission is granted, `Ok(false)` if not granted or not determined.
+/// Use `microphone_permission_status()` to distinguish denied from not determined.
+///
+/// # Platform Support
+///
+/// This function only works on macOS. On other platforms, it returns an error.
+///
+/// # Errors
+///
+/// Returns `PermissionError::CheckFailed` if the permission check encounters an error.
+#[cfg(target_os = "macos")]
+pub fn check_microphone_permission() -> Result<bool, PermissionError> {
+    debug!("Checking microphone permission status");
+
+    match microphone_permission_status()? {
+        PermissionState::Granted => {
+            info!("Microphone permission granted");
+            Ok(true)
+        }
+        PermissionState::Denied => {
+            warn!("Microphone permission denied or restricted");
+            Ok(false)
+        }
+        PermissionState::NotDetermined => {
+            debug!("Microphone permission not determined yet");
+            Ok(false)
+        }
+    }
+}
+
 /// Request microphone permission from the user
 ///
 /// This triggers the macOS system dialog asking the user to grant microphone permission.
@@ -455,6 +621,34 @@ pub async fn request_camera_permission() -> Result<(), PermissionError> {
     ))
 }
 
+#[cfg(not(target_os = "macos"))]
+pub fn open_privacy_settings(_kind: PermissionKind) -> Result<(), PermissionError> {
+    Err(PermissionError::RequestFailed(
+        "Privacy settings are only available on macOS".to_string(),
+    ))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn screen_recording_permission_status() -> Result<PermissionState, PermissionError> {
+    Err(PermissionError::CheckFailed(
+        "Screen recording is only supported on macOS".to_string(),
+    ))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn microphone_permission_status() -> Result<PermissionState, PermissionError> {
+    Err(PermissionError::CheckFailed(
+        "Microphone is only supported on macOS".to_string(),
+    ))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn camera_permission_status() -> Result<PermissionState, PermissionError> {
+    Err(PermissionError::CheckFailed(
+        "Camera is only supported on macOS".to_string(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -536,4 +730,49 @@ mod tests {
         let has_permission = result.unwrap();
         assert!(has_permission == true || has_permission == false);
     }
+
+    #[test]
+    fn test_permission_status_maps_each_source() {
+        let status = PermissionStatus::from_results(
+            Ok(PermissionState::Granted),
+            Ok(PermissionState::Denied),
+            Ok(PermissionState::NotDetermined),
+        );
+
+        assert_eq!(status.screen_recording, PermissionState::Granted);
+        assert_eq!(status.camera, PermissionState::Denied);
+        assert_eq!(status.microphone, PermissionState::NotDetermined);
+    }
+
+    #[test]
+    fn test_permission_status_failed_check_is_not_determined() {
+        let status = PermissionStatus::from_results(
+            Err(PermissionError::CheckFailed("boom".to_string())),
+            Ok(PermissionState::Granted),
+            Ok(PermissionState::Granted),
+        );
+
+        assert_eq!(status.screen_recording, PermissionState::NotDetermined);
+        assert_eq!(status.camera, PermissionState::Granted);
+
+        let json = serde_json::to_string(&status).unwrap();
+        assert!(json.contains("\"screenRecording\":\"notDetermined\""));
+    }
+
+    #[test]
+    fn test_privacy_settings_url_per_kind() {
+        assert!(privacy_settings_url(PermissionKind::ScreenRecording).ends_with("?Privacy_ScreenCapture"));
+        assert!(privacy_settings_url(PermissionKind::Camera).ends_with("?Privacy_Camera"));
+        assert!(privacy_settings_url(PermissionKind::Microphone).ends_with("?Privacy_Microphone"));
+        assert_eq!(privacy_settings_url(PermissionKind::Unknown), PRIVACY_SETTINGS_URL);
+    }
+
+    #[test]
+    fn test_unknown_permission_kind_falls_back() {
+        let kind: PermissionKind = serde_json::from_str("\"bluetooth\"").unwrap();
+        assert_eq!(kind, PermissionKind::Unknown);
+
+        let kind: PermissionKind = serde_json::from_str("\"screenRecording\"").unwrap();
+        assert_eq!(kind, PermissionKind::ScreenRecording);
+    }
 }
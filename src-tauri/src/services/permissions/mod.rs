@@ -7,5 +7,14 @@ pub use macos::{
     request_camera_permission,
     check_microphone_permission,
     request_microphone_permission,
+    screen_recording_permission_status,
+    camera_permission_status,
+    microphone_permission_status,
+    check_all_permissions,
+    open_privacy_settings,
+    privacy_settings_url,
     PermissionError,
+    PermissionKind,
+    PermissionState,
+    PermissionStatus,
 };
@@ -77,12 +77,95 @@ impl SyncMetrics {
     }
 }
 
+impl SyncMetrics {
+    /// Dropped / total screen frames (0.0 before any frame)
+    pub fn dropped_frame_ratio(&self) -> f64 {
+        if self.total_frames == 0 {
+            0.0
+        } else {
+            self.dropped_frames as f64 / self.total_frames as f64
+        }
+    }
+}
+
 impl Default for SyncMetrics {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Limits beyond which synchronization is reported as unhealthy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncHealthThresholds {
+    /// Largest acceptable drift for any stream (milliseconds)
+    pub max_drift_ms: i64,
+
+    /// Dropped / total screen frames at which sync becomes unhealthy (0.0 - 1.0)
+    pub max_dropped_frame_ratio: f64,
+}
+
+impl Default for SyncHealthThresholds {
+    fn default() -> Self {
+        Self {
+            max_drift_ms: 50,  // AC #7
+            max_dropped_frame_ratio: 0.05,
+        }
+    }
+}
+
+/// Payload of the `sync-warning` event
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncWarningPayload {
+    /// Dropped / total screen frames so far
+    pub dropped_frame_ratio: f64,
+    /// Screen video drift (milliseconds)
+    pub current_drift_ms: i64,
+    /// Largest drift of the audio and webcam streams (milliseconds)
+    pub max_stream_drift_ms: i64,
+}
+
+impl SyncWarningPayload {
+    pub fn from_metrics(metrics: &SyncMetrics) -> Self {
+        Self {
+            dropped_frame_ratio: metrics.dropped_frame_ratio(),
+            current_drift_ms: metrics.current_drift_ms,
+            max_stream_drift_ms: metrics
+                .system_audio_drift_ms
+                .abs()
+                .max(metrics.mic_audio_drift_ms.abs())
+                .max(metrics.webcam_drift_ms.abs()),
+        }
+    }
+}
+
+/// Rate limit for `sync-warning` events (at most one per interval)
+#[derive(Debug, Clone)]
+pub struct SyncWarningThrottle {
+    interval: std::time::Duration,
+    last_emitted: Option<std::time::Instant>,
+}
+
+impl SyncWarningThrottle {
+    pub fn new(interval: std::time::Duration) -> Self {
+        Self {
+            interval,
+            last_emitted: None,
+        }
+    }
+
+    /// Whether a warning may be emitted at `now` (records it if so)
+    pub fn should_emit(&mut self, now: std::time::Instant) -> bool {
+        match self.last_emitted {
+            Some(last) if now.duration_since(last) < self.interval => false,
+            _ => {
+                self.last_emitted = Some(now);
+                true
+            }
+        }
+    }
+}
+
 /// Frame synchronizer for timestamp-based A/V sync
 ///
 /// Monitors frame timing and detects drift/drops for long recordings.
@@ -97,6 +180,9 @@ pub struct FrameSynchronizer {
     /// AC #7 specifies 50ms tolerance
     drift_threshold_ms: i64,
 
+    /// Limits for `is_sync_healthy`
+    health_thresholds: SyncHealthThresholds,
+
     /// Synchronization metrics
     metrics: SyncMetrics,
 
@@ -127,6 +213,10 @@ impl FrameSynchronizer {
         Self {
             frame_duration_ms,
             drift_threshold_ms,
+            health_thresholds: SyncHealthThresholds {
+                max_drift_ms: drift_threshold_ms,
+                ..SyncHealthThresholds::default()
+            },
             metrics: SyncMetrics::new(),
             last_timestamp_ms: None,
             last_webcam_timestamp_ms: None,
@@ -138,6 +228,19 @@ impl FrameSynchronizer {
         Self::new(30, 50)
     }
 
+    /// Use custom limits for `is_sync_healthy`
+    ///
+    /// Defaults to the drift threshold and a 5% dropped-frame ratio.
+    pub fn with_health_thresholds(mut self, thresholds: SyncHealthThresholds) -> Self {
+        self.health_thresholds = thresholds;
+        self
+    }
+
+    /// Limits used by `is_sync_healthy`
+    pub fn health_thresholds(&self) -> SyncHealthThresholds {
+        self.health_thresholds
+    }
+
     /// Process a frame and detect drift/drops
     ///
     /// Returns true if frame should be processed, false if dropped
@@ -316,22 +419,20 @@ impl FrameSynchronizer {
 
     /// Check if synchronization is healthy (within tolerance)
     ///
-    /// Returns true if drift is within acceptable range and drop rate is low
+    /// Returns true if drift and dropped-frame ratio are below the configured
+    /// `SyncHealthThresholds`
     pub fn is_sync_healthy(&self) -> bool {
-        let drift_ok = self.metrics.current_drift_ms.abs() <= self.drift_threshold_ms;
-        let drops_acceptable = if self.metrics.total_frames > 0 {
-            let drop_rate = self.metrics.dropped_frames as f64 / self.metrics.total_frames as f64;
-            drop_rate < 0.05  // Less than 5% drop rate is acceptable
-        } else {
-            true
-        };
+        let max_drift_ms = self.health_thresholds.max_drift_ms;
+        let drift_ok = self.metrics.current_drift_ms.abs() <= max_drift_ms;
+        let drops_acceptable =
+            self.metrics.dropped_frame_ratio() < self.health_thresholds.max_dropped_frame_ratio;
 
         // Check audio drift (if audio is being processed)
-        let audio_drift_ok = self.metrics.system_audio_drift_ms.abs() <= self.drift_threshold_ms
-            && self.metrics.mic_audio_drift_ms.abs() <= self.drift_threshold_ms;
+        let audio_drift_ok = self.metrics.system_audio_drift_ms.abs() <= max_drift_ms
+            && self.metrics.mic_audio_drift_ms.abs() <= max_drift_ms;
 
         // Check webcam drift (Story 4.6 - if webcam is being processed)
-        let webcam_drift_ok = self.metrics.webcam_drift_ms.abs() <= self.drift_threshold_ms;
+        let webcam_drift_ok = self.metrics.webcam_drift_ms.abs() <= max_drift_ms;
 
         drift_ok && drops_acceptable && audio_drift_ok && webcam_drift_ok
     }
@@ -630,6 +731,41 @@ mod tests {
         assert!(!sync.is_sync_healthy());
     }
 
+    #[test]
+    fn test_configured_drop_ratio_threshold_flips_health() {
+        let mut sync = FrameSynchronizer::new(30, 50).with_health_thresholds(SyncHealthThresholds {
+            max_drift_ms: 50,
+            max_dropped_frame_ratio: 0.10,
+        });
+
+        // 20 on-time frames, then frame 23 after a gap: 3 drops / 21 frames ≈ 14%, no drift
+        for i in 0..20 {
+            assert!(sync.process_frame(i * 33, i));
+        }
+        assert!(sync.is_sync_healthy());
+        sync.process_frame(23 * 33, 23);
+        let ratio = sync.get_metrics().dropped_frame_ratio();
+        assert!(ratio > 0.10 && ratio < 0.25, "ratio was {}", ratio);
+        assert!(!sync.is_sync_healthy());
+
+        // Same metrics are healthy under a more lenient threshold
+        let lenient = sync.clone().with_health_thresholds(SyncHealthThresholds {
+            max_drift_ms: 50,
+            max_dropped_frame_ratio: 0.25,
+        });
+        assert!(lenient.is_sync_healthy());
+    }
+
+    #[test]
+    fn test_sync_warning_throttle() {
+        let mut throttle = SyncWarningThrottle::new(std::time::Duration::from_secs(5));
+        let start = std::time::Instant::now();
+
+        assert!(throttle.should_emit(start));
+        assert!(!throttle.should_emit(start + std::time::Duration::from_secs(1)));
+        assert!(throttle.should_emit(start + std::time::Duration::from_secs(5)));
+    }
+
     #[test]
     fn test_reset_clears_all_metrics() {
         let mut sync = FrameSynchronizer::new(30, 50);
@@ -1,5 +1,9 @@
 pub mod frame_synchronizer;
 pub mod orchestrator;
+pub mod pausable_clock;
 
-pub use frame_synchronizer::{FrameSynchronizer, SyncMetrics};
+pub use frame_synchronizer::{
+    FrameSynchronizer, SyncHealthThresholds, SyncMetrics, SyncWarningPayload, SyncWarningThrottle,
+};
 pub use orchestrator::{RecordingConfig, RecordingOrchestrator};
+pub use pausable_clock::PausableClock;
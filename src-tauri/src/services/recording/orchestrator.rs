@@ -15,13 +15,17 @@
 
 use crate::services::audio_capture::{AudioCapture, AudioSample};
 use crate::services::camera::CameraCapture;
-use crate::services::ffmpeg::{CompositorFrame, FFmpegCompositor, FFmpegEncoder, PipConfig, TimestampedFrame};
-use crate::services::recording::FrameSynchronizer;
+use crate::services::ffmpeg::{CompositorFrame, FFmpegCompositor, FFmpegEncoder, PipConfig, PipEncoding, TimestampedFrame};
+use crate::services::recording::{
+    FrameSynchronizer, SyncHealthThresholds, SyncWarningPayload, SyncWarningThrottle,
+};
 use crate::services::screen_capture::ScreenCapture;
 use anyhow::{Context, Result};
 use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tauri::Emitter;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
@@ -71,8 +75,20 @@ pub struct RecordingConfig {
 
     /// Audio channel count (default: 2 for stereo)
     pub audio_channels: u16,
+
+    /// Drift / dropped-frame limits that trigger `sync-warning`
+    pub sync_health: SyncHealthThresholds,
+
+    /// Normalize the mixed audio loudness (EBU R128) when muxing
+    pub normalize_loudness: bool,
+
+    /// Encoder settings of the PiP compositor (default: ultrafast CRF 23)
+    pub pip_encoding: PipEncoding,
 }
 
+/// Minimum time between `sync-warning` events
+pub const SYNC_WARNING_INTERVAL: Duration = Duration::from_secs(5);
+
 impl Default for RecordingConfig {
     fn default() -> Self {
         Self {
@@ -85,6 +101,9 @@ impl Default for RecordingConfig {
             enable_webcam_audio: false,
             audio_sample_rate: 48000,
             audio_channels: 2,
+            sync_health: SyncHealthThresholds::default(),
+            normalize_loudness: false,
+            pip_encoding: PipEncoding::default(),
         }
     }
 }
@@ -122,6 +141,9 @@ pub struct RecordingOrchestrator {
 
     /// Video-only temp file path (Story 4.7 - for audio muxing)
     video_only_path: Option<PathBuf>,
+
+    /// App handle for `sync-warning` events (optional)
+    app_handle: Option<tauri::AppHandle>,
 }
 
 impl RecordingOrchestrator {
@@ -148,8 +170,11 @@ impl RecordingOrchestrator {
         );
 
         // Initialize screen capture (fullscreen mode for orchestrator)
-        let mut screen_capture = ScreenCapture::new(None)
+        let mut screen_capture = ScreenCapture::new(None, None)
             .context("Failed to initialize screen capture")?;
+        screen_capture
+            .set_frame_rate(config.fps)
+            .context("Invalid recording frame rate")?;
 
         // Enable system audio if requested
         if config.enable_system_audio {
@@ -189,7 +214,8 @@ impl RecordingOrchestrator {
         };
 
         // Create frame synchronizer (30 FPS, 50ms tolerance per AC #4)
-        let synchronizer = FrameSynchronizer::new(config.fps, 50);
+        let synchronizer = FrameSynchronizer::new(config.fps, 50)
+            .with_health_thresholds(config.sync_health);
 
         info!("Recording orchestrator created successfully");
 
@@ -202,9 +228,15 @@ impl RecordingOrchestrator {
             encoder: None,
             capture_handles: Vec::new(),
             video_only_path: None,
+            app_handle: None,
         })
     }
 
+    /// Emit throttled `sync-warning` events when sync becomes unhealthy
+    pub fn set_app_handle(&mut self, app_handle: tauri::AppHandle) {
+        self.app_handle = Some(app_handle);
+    }
+
     /// Start recording with multi-stream coordination
     ///
     /// Spawns async tasks for:
@@ -320,6 +352,8 @@ impl RecordingOrchestrator {
         // Spawn synchronization and encoding task
         let mut encoder = self.encoder.take().expect("Encoder should be initialized");
         let mut synchronizer = self.synchronizer.clone();
+        let app_handle = self.app_handle.clone();
+        let mut warning_throttle = SyncWarningThrottle::new(SYNC_WARNING_INTERVAL);
 
         let sync_handle = tokio::spawn(async move {
             let mut current_video_timestamp_ms: u64 = 0;
@@ -485,11 +519,17 @@ impl RecordingOrchestrator {
                 }
 
                 // Check synchronization health periodically
-                if !synchronizer.is_sync_healthy() {
+                if !synchronizer.is_sync_healthy() && warning_throttle.should_emit(Instant::now()) {
+                    let payload = SyncWarningPayload::from_metrics(synchronizer.get_metrics());
                     warn!(
                         event = "sync_unhealthy",
+                        dropped_frame_ratio = payload.dropped_frame_ratio,
+                        drift_ms = payload.current_drift_ms,
                         "Audio/video synchronization drift detected"
                     );
+                    if let Some(handle) = &app_handle {
+                        let _ = handle.emit("sync-warning", payload);
+                    }
                 }
             }
 
@@ -592,6 +632,7 @@ impl RecordingOrchestrator {
             pip_config,
         )
         .context("Failed to create FFmpeg compositor")?;
+        compositor.set_encoding(self.config.pip_encoding);
 
         // Start composition process (with named pipes)
         compositor
@@ -678,6 +719,8 @@ impl RecordingOrchestrator {
 
         // Spawn synchronization and composition task
         let mut synchronizer = self.synchronizer.clone();
+        let app_handle = self.app_handle.clone();
+        let mut warning_throttle = SyncWarningThrottle::new(SYNC_WARNING_INTERVAL);
 
         let composition_handle = tokio::spawn(async move {
             let mut current_screen_timestamp_ms: u64 = 0;
@@ -889,11 +932,17 @@ impl RecordingOrchestrator {
                 }
 
                 // Check synchronization health periodically
-                if !synchronizer.is_sync_healthy() {
+                if !synchronizer.is_sync_healthy() && warning_throttle.should_emit(Instant::now()) {
+                    let payload = SyncWarningPayload::from_metrics(synchronizer.get_metrics());
                     warn!(
                         event = "pip_sync_unhealthy",
+                        dropped_frame_ratio = payload.dropped_frame_ratio,
+                        drift_ms = payload.current_drift_ms,
                         "PiP recording: Audio/video synchronization drift detected"
                     );
+                    if let Some(handle) = &app_handle {
+                        let _ = handle.emit("sync-warning", payload);
+                    }
                 }
             }
 
@@ -1032,6 +1081,7 @@ impl RecordingOrchestrator {
                     video_only_path.clone(),
                     audio_inputs,
                     self.config.output_path.clone(),
+                    self.config.normalize_loudness,
                 )
                 .await
                 .context("Failed to mux audio with video")?;
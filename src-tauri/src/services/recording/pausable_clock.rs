@@ -0,0 +1,171 @@
+//! Recording clock that stops while the recording is paused
+//!
+//! Frame and audio timestamps are measured from the start of the recording.
+//! With a plain `Instant` a pause leaves a gap in the timestamps equal to its
+//! length, which the encoder fills with a frozen frame (and silence). The
+//! clock subtracts paused spans instead, so the output continues seamlessly
+//! from where it was paused.
+//!
+//! The clock is shared via `Arc` between the capture callbacks and whoever
+//! pauses the recording.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct ClockState {
+    /// Set while paused
+    paused_at: Option<Instant>,
+    /// Total length of all completed pauses
+    paused_total: Duration,
+}
+
+/// Elapsed recording time excluding paused intervals
+#[derive(Debug)]
+pub struct PausableClock {
+    start: Instant,
+    state: Mutex<ClockState>,
+}
+
+impl PausableClock {
+    /// Start a clock running from now
+    pub fn new() -> Self {
+        Self::started_at(Instant::now())
+    }
+
+    /// Start a clock running from `start`
+    pub fn started_at(start: Instant) -> Self {
+        Self {
+            start,
+            state: Mutex::new(ClockState {
+                paused_at: None,
+                paused_total: Duration::ZERO,
+            }),
+        }
+    }
+
+    /// Stop the clock (no-op if already paused)
+    pub fn pause(&self) {
+        self.pause_at(Instant::now());
+    }
+
+    /// Restart the clock after a pause (no-op if running)
+    pub fn resume(&self) {
+        self.resume_at(Instant::now());
+    }
+
+    /// Pause or resume to match an external pause flag
+    ///
+    /// Commands pause by setting the capture's pause flag directly; capture
+    /// callbacks call this so the clock follows the flag either way.
+    pub fn sync_paused(&self, paused: bool) {
+        if paused {
+            self.pause();
+        } else {
+            self.resume();
+        }
+    }
+
+    /// Whether the clock is currently paused
+    pub fn is_paused(&self) -> bool {
+        self.lock().paused_at.is_some()
+    }
+
+    /// Recording time elapsed so far, not counting pauses
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_at(Instant::now())
+    }
+
+    pub(crate) fn pause_at(&self, now: Instant) {
+        let mut state = self.lock();
+        if state.paused_at.is_none() {
+            state.paused_at = Some(now);
+        }
+    }
+
+    pub(crate) fn resume_at(&self, now: Instant) {
+        let mut state = self.lock();
+        if let Some(paused_at) = state.paused_at.take() {
+            state.paused_total += now.saturating_duration_since(paused_at);
+        }
+    }
+
+    pub(crate) fn elapsed_at(&self, now: Instant) -> Duration {
+        let state = self.lock();
+        // While paused, time stands still at the moment of pausing
+        let end = state.paused_at.unwrap_or(now);
+        end.saturating_duration_since(self.start)
+            .saturating_sub(state.paused_total)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ClockState> {
+        // The state stays consistent even if a holder panicked
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for PausableClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn assert_close(actual: Duration, expected: Duration) {
+        let diff = if actual > expected { actual - expected } else { expected - actual };
+        assert!(diff <= ms(1), "expected {:?}, got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn test_elapsed_excludes_paused_spans() {
+        let t0 = Instant::now();
+        let clock = PausableClock::started_at(t0);
+
+        assert_close(clock.elapsed_at(t0 + ms(500)), ms(500));
+
+        // Paused 1.0s..3.5s: time stands still
+        clock.pause_at(t0 + ms(1_000));
+        assert!(clock.is_paused());
+        assert_close(clock.elapsed_at(t0 + ms(2_000)), ms(1_000));
+        clock.resume_at(t0 + ms(3_500));
+        assert!(!clock.is_paused());
+        assert_close(clock.elapsed_at(t0 + ms(4_000)), ms(1_500));
+
+        // Second cycle 5.0s..5.25s; repeated pause/resume calls are no-ops
+        clock.pause_at(t0 + ms(5_000));
+        clock.pause_at(t0 + ms(5_100));
+        clock.resume_at(t0 + ms(5_250));
+        clock.resume_at(t0 + ms(5_300));
+        assert_close(clock.elapsed_at(t0 + ms(6_000)), ms(3_250));
+    }
+
+    #[test]
+    fn test_real_time_pause_resume_cycles() {
+        let wall = Instant::now();
+        let clock = PausableClock::started_at(wall);
+
+        for _ in 0..3 {
+            std::thread::sleep(ms(10));
+            clock.sync_paused(true);
+            let at_pause = clock.elapsed();
+            std::thread::sleep(ms(20));
+            // Nothing accrues while paused
+            assert_close(clock.elapsed(), at_pause);
+            clock.sync_paused(false);
+        }
+
+        // At least 60ms of pauses are not counted
+        std::thread::sleep(ms(10));
+        clock.pause();
+        let elapsed = clock.elapsed();
+        assert!(elapsed >= ms(40), "elapsed {:?}", elapsed);
+        assert!(elapsed + ms(60) <= wall.elapsed(), "elapsed {:?} includes paused time", elapsed);
+    }
+}
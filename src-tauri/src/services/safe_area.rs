@@ -0,0 +1,114 @@
+//! Safe-area guides for social exports
+//!
+//! Given the timeline resolution and a target aspect ratio (e.g. 9:16 for
+//! vertical video), computes the largest centred region with that aspect
+//! (what survives a center crop) and the action-safe / title-safe rectangles
+//! inside it, so the editor can overlay guides. Margins follow SMPTE ST 2046-1:
+//! action-safe is 93% and title-safe 90% of the target region.
+
+use serde::Serialize;
+
+/// Share of the target region that is action-safe
+pub const ACTION_SAFE_RATIO: f64 = 0.93;
+
+/// Share of the target region that is title-safe
+pub const TITLE_SAFE_RATIO: f64 = 0.90;
+
+/// Rectangle in timeline pixels (origin top-left)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Guides for one target aspect ratio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeAreas {
+    /// Largest centred region with the target aspect
+    pub target: SafeRect,
+    /// Action-safe area inside `target`
+    pub action_safe: SafeRect,
+    /// Title-safe area inside `target`
+    pub title_safe: SafeRect,
+}
+
+/// Centred rectangle covering `ratio` of each side of `region`
+fn inset(region: (f64, f64, f64, f64), ratio: f64) -> SafeRect {
+    let (x, y, width, height) = region;
+    let inner_width = width * ratio;
+    let inner_height = height * ratio;
+    SafeRect {
+        x: (x + (width - inner_width) / 2.0).round() as u32,
+        y: (y + (height - inner_height) / 2.0).round() as u32,
+        width: inner_width.round() as u32,
+        height: inner_height.round() as u32,
+    }
+}
+
+/// Compute safe areas for `target_aspect` (width / height) over a `width`x`height` frame
+///
+/// A target narrower than the source (9:16 on 16:9) keeps the full height and
+/// crops the sides; a wider target (21:9 on 16:9) keeps the full width and
+/// crops top and bottom.
+///
+/// # Returns
+/// * `Ok(SafeAreas)` - Guides in frame pixels
+/// * `Err(String)` - Frame size or aspect is zero, negative or not finite
+pub fn compute_safe_areas(width: u32, height: u32, target_aspect: f64) -> Result<SafeAreas, String> {
+    if width == 0 || height == 0 {
+        return Err(format!("Invalid frame size: {}x{}", width, height));
+    }
+    if !target_aspect.is_finite() || target_aspect <= 0.0 {
+        return Err(format!("Invalid target aspect ratio: {}", target_aspect));
+    }
+
+    let (frame_width, frame_height) = (width as f64, height as f64);
+    let (target_width, target_height) = if target_aspect < frame_width / frame_height {
+        (frame_height * target_aspect, frame_height)
+    } else {
+        (frame_width, frame_width / target_aspect)
+    };
+    let region = (
+        (frame_width - target_width) / 2.0,
+        (frame_height - target_height) / 2.0,
+        target_width,
+        target_height,
+    );
+
+    Ok(SafeAreas {
+        target: inset(region, 1.0),
+        action_safe: inset(region, ACTION_SAFE_RATIO),
+        title_safe: inset(region, TITLE_SAFE_RATIO),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vertical_target_on_landscape_source() {
+        let areas = compute_safe_areas(1920, 1080, 9.0 / 16.0).unwrap();
+
+        assert_eq!(areas.target, SafeRect { x: 656, y: 0, width: 608, height: 1080 });
+        assert_eq!(areas.action_safe, SafeRect { x: 677, y: 38, width: 565, height: 1004 });
+        assert_eq!(areas.title_safe, SafeRect { x: 686, y: 54, width: 547, height: 972 });
+    }
+
+    #[test]
+    fn test_wider_target_crops_top_and_bottom() {
+        let areas = compute_safe_areas(1920, 1080, 21.0 / 9.0).unwrap();
+        assert_eq!(areas.target, SafeRect { x: 0, y: 129, width: 1920, height: 823 });
+
+        // Same aspect: the whole frame
+        let same = compute_safe_areas(1920, 1080, 16.0 / 9.0).unwrap();
+        assert_eq!(same.target, SafeRect { x: 0, y: 0, width: 1920, height: 1080 });
+
+        assert!(compute_safe_areas(1920, 1080, 0.0).is_err());
+        assert!(compute_safe_areas(0, 1080, 1.0).is_err());
+    }
+}
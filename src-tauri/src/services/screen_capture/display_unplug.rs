@@ -0,0 +1,89 @@
+//! External display hot-unplug handling
+//!
+//! When the display being captured is disconnected, the SCStream's content
+//! filter points at a display that no longer exists and frames simply stop.
+//! The capture loop treats a short frame starvation as a hint and confirms it
+//! against the list of connected displays; a starved capture whose display is
+//! gone is reported as `display-disconnected`. Depending on
+//! `RecordingConfig::display_fallback`, the capture then either stops or
+//! switches its content filter to another connected display.
+
+use super::multi_display::DisplayBounds;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Frame starvation before the connected displays are checked
+///
+/// Shorter than `DEFAULT_STALL_TIMEOUT`: an unplug is cheap to confirm, and
+/// catching it early keeps the gap in the recording short.
+pub const DISPLAY_STARVATION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Payload of the `display-disconnected` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayDisconnectedPayload {
+    /// Display that was being captured
+    pub display_id: u32,
+    /// Display capture switched to, or `None` if capture stopped
+    pub fallback_display_id: Option<u32>,
+}
+
+/// Whether a starved capture is explained by its display being unplugged
+///
+/// # Arguments
+/// * `display_id` - Display being captured
+/// * `since_last_frame` - Time since the last delivered frame
+/// * `connected` - Display IDs currently reported by ScreenCaptureKit
+pub fn is_display_disconnected(display_id: u32, since_last_frame: Duration, connected: &[u32]) -> bool {
+    since_last_frame > DISPLAY_STARVATION_TIMEOUT && !connected.contains(&display_id)
+}
+
+/// Pick the display to continue on after `disconnected` went away
+///
+/// Prefers the main display (origin 0,0), then the leftmost, topmost one.
+pub fn select_fallback_display(disconnected: u32, connected: &[DisplayBounds]) -> Option<u32> {
+    connected
+        .iter()
+        .filter(|display| display.display_id != disconnected)
+        .min_by_key(|display| (!(display.x == 0 && display.y == 0), display.x, display.y))
+        .map(|display| display.display_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(display_id: u32, x: i32, y: i32) -> DisplayBounds {
+        DisplayBounds {
+            display_id,
+            x,
+            y,
+            width: 1920,
+            height: 1080,
+        }
+    }
+
+    #[test]
+    fn test_starved_capture_is_disconnected_only_when_display_is_gone() {
+        let starved = DISPLAY_STARVATION_TIMEOUT + Duration::from_millis(500);
+        let fresh = Duration::from_millis(200);
+
+        assert!(is_display_disconnected(7, starved, &[1, 3]));
+        // Still connected: a static screen, not an unplug
+        assert!(!is_display_disconnected(7, starved, &[1, 7]));
+        // Frames are arriving: never checked as disconnected
+        assert!(!is_display_disconnected(7, fresh, &[1]));
+    }
+
+    #[test]
+    fn test_fallback_prefers_main_display() {
+        let connected = [display(5, -1920, 0), display(1, 0, 0), display(9, 1920, 0)];
+        assert_eq!(select_fallback_display(9, &connected), Some(1));
+
+        // Main display unplugged: leftmost remaining display
+        assert_eq!(select_fallback_display(1, &connected), Some(5));
+
+        // Nothing else connected
+        assert_eq!(select_fallback_display(1, &[display(1, 0, 0)]), None);
+    }
+}
@@ -9,7 +9,7 @@ use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info};
-use crate::services::ffmpeg::{FFmpegEncoder, TimestampedFrame};
+use crate::services::ffmpeg::{FrameSink, TimestampedFrame};
 
 /// Errors that can occur during frame handling
 #[derive(Error, Debug)]
@@ -107,15 +107,16 @@ impl FrameHandler {
     /// Start the real-time encoder task
     ///
     /// This spawns a Tokio task that continuously reads frames from the channel
-    /// and streams them to the FFmpeg encoder for real-time H.264 encoding.
+    /// and writes them to the sink (normally an FFmpegEncoder doing real-time
+    /// H.264 encoding). The sink is finalized once the channel closes.
     ///
     /// # Arguments
-    /// * `encoder` - FFmpegEncoder instance (must be started beforehand)
+    /// * `encoder` - Frame sink, e.g. an FFmpegEncoder (must be started beforehand)
     ///
     /// Returns a join handle for the encoder task
-    pub async fn start_encoder(
+    pub async fn start_encoder<S: FrameSink + 'static>(
         &mut self,
-        mut encoder: FFmpegEncoder,
+        mut encoder: S,
     ) -> Result<tokio::task::JoinHandle<Result<(), FrameHandlerError>>, FrameHandlerError> {
         let mut rx = self.frame_rx.take().ok_or_else(|| {
             error!("Receiver already taken - encoder can only be started once");
@@ -139,8 +140,19 @@ impl FrameHandler {
                     *counter += 1;
                 }
 
+                // Surface an encoder crash (with its stderr) before writing into a dead pipe
+                if let Err(e) = encoder.check_health().await {
+                    error!(
+                        event = "encoder_process_died",
+                        error = %e,
+                        frame_num = processed_frames,
+                        "Encoder stopped unexpectedly"
+                    );
+                    return Err(FrameHandlerError::EncodingError(e.to_string()));
+                }
+
                 // Write frame to encoder
-                if let Err(e) = encoder.write_frame_to_stdin(&frame).await {
+                if let Err(e) = encoder.write_frame(&frame).await {
                     error!(
                         event = "encoding_frame_failed",
                         error = %e,
@@ -168,7 +180,7 @@ impl FrameHandler {
             }
 
             // Stop encoder and finalize output
-            if let Err(e) = encoder.stop_encoding().await {
+            if let Err(e) = encoder.finalize().await {
                 error!(
                     event = "encoder_stop_failed",
                     error = %e,
@@ -288,6 +300,7 @@ impl FrameHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::ffmpeg::{FFmpegEncoder, MemoryFrameSink};
 
     #[tokio::test]
     async fn test_frame_handler_creates_file() {
@@ -386,6 +399,35 @@ mod tests {
         tokio::fs::remove_file(&output_path).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_encoder_task_with_memory_sink() {
+        let mut handler = FrameHandler::new_for_encoding(4);
+        let sender = handler.get_sender();
+
+        let sink = MemoryFrameSink::new();
+        let encoder_handle = handler.start_encoder(sink.clone()).await.unwrap();
+
+        for i in 0..10 {
+            let frame = TimestampedFrame {
+                data: vec![i as u8; 8 * 8 * 4],
+                timestamp_ms: i * 33,
+                width: 8,
+                height: 8,
+            };
+            sender.send(frame).await.unwrap();
+        }
+        drop(sender);
+
+        let result = encoder_handle.await.unwrap();
+        assert!(result.is_ok());
+
+        let frames = sink.frames();
+        assert_eq!(frames.len(), 10);
+        assert_eq!(frames[9].timestamp_ms, 9 * 33);
+        assert!(sink.is_finalized());
+        assert_eq!(handler.get_frame_count().await, 10);
+    }
+
     #[tokio::test]
     async fn test_encoder_integration() {
         let temp_dir = std::env::temp_dir();
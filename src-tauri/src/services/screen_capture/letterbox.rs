@@ -0,0 +1,116 @@
+//! Fixed-size output for window capture
+//!
+//! ScreenCaptureKit's window filter follows a window as it moves and resizes,
+//! but the encoder was started with fixed dimensions and rejects frames of any
+//! other size. Frames whose size changed are fitted back into the encoder size:
+//! smaller windows are centered on black (letterbox/pillarbox), larger ones are
+//! scaled down to fit first. The aspect ratio is never distorted.
+
+use crate::services::ffmpeg::TimestampedFrame;
+
+/// Opaque black in BGRA
+const BLACK_BGRA: [u8; 4] = [0, 0, 0, 255];
+
+/// Where a source frame lands inside the output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FitPlacement {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Place a `source` size centered inside `target`, downscaling (never upscaling) to fit
+pub fn fit_placement(source: (u32, u32), target: (u32, u32)) -> FitPlacement {
+    let (sw, sh) = (source.0.max(1), source.1.max(1));
+    let (tw, th) = target;
+    let scale = (tw as f64 / sw as f64).min(th as f64 / sh as f64).min(1.0);
+    let width = ((sw as f64 * scale).round() as u32).clamp(1, tw.max(1));
+    let height = ((sh as f64 * scale).round() as u32).clamp(1, th.max(1));
+
+    FitPlacement {
+        x: (tw - width) / 2,
+        y: (th - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Fit a BGRA frame into fixed output dimensions
+///
+/// Frames that already match are returned unchanged. Source rows may carry
+/// padding (CVPixelBuffer bytes-per-row), which is derived from the data length.
+pub fn fit_frame(frame: TimestampedFrame, target_width: u32, target_height: u32) -> TimestampedFrame {
+    if frame.width == target_width && frame.height == target_height {
+        return frame;
+    }
+
+    let placement = fit_placement((frame.width, frame.height), (target_width, target_height));
+    let src_stride = (frame.data.len() / frame.height.max(1) as usize).max(frame.width as usize * 4);
+    let dst_stride = target_width as usize * 4;
+
+    let mut data = BLACK_BGRA.repeat(target_width as usize * target_height as usize);
+
+    // Nearest-neighbor sampling; 1:1 when only padding is needed
+    for row in 0..placement.height {
+        let src_y = (row as u64 * frame.height as u64 / placement.height as u64) as usize;
+        let dst_row = (placement.y + row) as usize * dst_stride;
+        for col in 0..placement.width {
+            let src_x = (col as u64 * frame.width as u64 / placement.width as u64) as usize;
+            let src = src_y * src_stride + src_x * 4;
+            let dst = dst_row + (placement.x + col) as usize * 4;
+            if let Some(pixel) = frame.data.get(src..src + 4) {
+                data[dst..dst + 4].copy_from_slice(pixel);
+            }
+        }
+    }
+
+    TimestampedFrame {
+        data,
+        timestamp_ms: frame.timestamp_ms,
+        width: target_width,
+        height: target_height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_frame(width: u32, height: u32) -> TimestampedFrame {
+        TimestampedFrame {
+            data: [10u8, 20, 30, 255].repeat((width * height) as usize),
+            timestamp_ms: 42,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_resized_window_is_padded_to_encoder_dimensions() {
+        // Window shrank from 8x4 to 4x2: centered with black borders
+        let fitted = fit_frame(solid_frame(4, 2), 8, 4);
+        assert_eq!((fitted.width, fitted.height), (8, 4));
+        assert_eq!(fitted.data.len(), 8 * 4 * 4);
+        assert_eq!(fitted.timestamp_ms, 42);
+
+        let pixel = |x: usize, y: usize| &fitted.data[(y * 8 + x) * 4..(y * 8 + x) * 4 + 4];
+        assert_eq!(pixel(0, 0), BLACK_BGRA);
+        assert_eq!(pixel(2, 1), [10, 20, 30, 255]);
+        assert_eq!(pixel(5, 2), [10, 20, 30, 255]);
+        assert_eq!(pixel(7, 3), BLACK_BGRA);
+
+        // Window grew: scaled down to fit, still the encoder size
+        let fitted = fit_frame(solid_frame(16, 16), 8, 4);
+        assert_eq!((fitted.width, fitted.height), (8, 4));
+        assert_eq!(fit_placement((16, 16), (8, 4)), FitPlacement { x: 2, y: 0, width: 4, height: 4 });
+    }
+
+    #[test]
+    fn test_matching_frame_is_unchanged() {
+        let frame = solid_frame(8, 4);
+        let data = frame.data.clone();
+        let fitted = fit_frame(frame, 8, 4);
+        assert_eq!(fitted.data, data);
+    }
+}
@@ -1,5 +1,23 @@
 pub mod screencapturekit;
 pub mod frame_handler;
+pub mod multi_display;
+pub mod overflow;
+pub mod letterbox;
+pub mod display_unplug;
 
-pub use screencapturekit::{ScreenCapture, ScreenCaptureError, SystemAudioConfig};
+pub use screencapturekit::{
+    CaptureAbort, CaptureAbortReason, CaptureStalledPayload, CaptureWatchdog, RecordingStatsPayload,
+    RecordingStatsTracker, ScreenCapture, ScreenCaptureError, SystemAudioConfig, WatchdogAction,
+    CAPTURE_FPS_RANGE, DEFAULT_CAPTURE_FPS, DEFAULT_STALL_TIMEOUT, PIXEL_BUFFER_FAILURE_LIMIT,
+};
 pub use frame_handler::{FrameHandler, FrameHandlerError};
+pub use multi_display::{
+    display_infos, select_display, CanvasLayout, DisplayBounds, DisplayInfo, DisplayPlacement,
+    MultiDisplayCapture,
+};
+pub use overflow::{OverflowCounters, OverflowSender, OverflowStats};
+pub use letterbox::{fit_frame, fit_placement, FitPlacement};
+pub use display_unplug::{
+    is_display_disconnected, select_fallback_display, DisplayDisconnectedPayload,
+    DISPLAY_STARVATION_TIMEOUT,
+};
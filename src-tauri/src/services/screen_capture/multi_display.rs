@@ -0,0 +1,589 @@
+//! Multi-monitor unified capture
+//!
+//! Records every display as one wide canvas. Each display gets its own
+//! ScreenCapture (one SCStream per display); a merge task copies the latest
+//! frame from each display into its slot in a shared BGRA canvas and emits the
+//! canvas at the target frame rate, so a single encoder sees one video stream.
+//!
+//! Layout is side-by-side for now: displays are ordered by their arrangement
+//! position (left to right, then top to bottom) and top-aligned.
+
+use super::screencapturekit::{CaptureAbort, ScreenCapture, ScreenCaptureError};
+use crate::services::ffmpeg::TimestampedFrame;
+use crate::services::recording::PausableClock;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+/// Display position and size in the system's display arrangement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayBounds {
+    /// CoreGraphics display ID
+    pub display_id: u32,
+    /// Origin in global display coordinates (main display at 0,0)
+    pub x: i32,
+    pub y: i32,
+    /// Size in pixels
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Where one display's frames are drawn on the canvas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayPlacement {
+    pub display_id: u32,
+    pub offset_x: u32,
+    pub offset_y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Canvas size and per-display placements
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanvasLayout {
+    pub width: u32,
+    pub height: u32,
+    pub placements: Vec<DisplayPlacement>,
+}
+
+impl CanvasLayout {
+    /// Lay displays out side by side in arrangement order, top-aligned
+    pub fn side_by_side(displays: &[DisplayBounds]) -> Self {
+        let mut ordered = displays.to_vec();
+        ordered.sort_by_key(|d| (d.x, d.y));
+
+        let mut offset_x = 0u32;
+        let placements: Vec<DisplayPlacement> = ordered
+            .iter()
+            .map(|d| {
+                let placement = DisplayPlacement {
+                    display_id: d.display_id,
+                    offset_x,
+                    offset_y: 0,
+                    width: d.width,
+                    height: d.height,
+                };
+                offset_x += d.width;
+                placement
+            })
+            .collect();
+
+        Self {
+            width: offset_x,
+            height: ordered.iter().map(|d| d.height).max().unwrap_or(0),
+            placements,
+        }
+    }
+
+    /// Placement for a display, if it is part of the layout
+    pub fn placement(&self, display_id: u32) -> Option<&DisplayPlacement> {
+        self.placements.iter().find(|p| p.display_id == display_id)
+    }
+}
+
+/// BGRA canvas that display frames are composed into
+pub struct Canvas {
+    layout: CanvasLayout,
+    data: Vec<u8>,
+}
+
+impl Canvas {
+    /// Create a black canvas for a layout
+    pub fn new(layout: CanvasLayout) -> Self {
+        let data = vec![0u8; (layout.width * layout.height * 4) as usize];
+        Self { layout, data }
+    }
+
+    pub fn layout(&self) -> &CanvasLayout {
+        &self.layout
+    }
+
+    /// Copy a display frame into its slot
+    ///
+    /// Frames larger than the slot are clipped. Row padding in the source
+    /// (stride > width * 4) is skipped.
+    ///
+    /// Returns `false` if the display is not part of the layout or the frame is malformed.
+    pub fn blit(&mut self, display_id: u32, frame: &TimestampedFrame) -> bool {
+        let placement = match self.layout.placement(display_id) {
+            Some(p) => *p,
+            None => return false,
+        };
+
+        if frame.height == 0 || frame.data.len() < (frame.width * frame.height * 4) as usize {
+            return false;
+        }
+
+        let src_stride = frame.data.len() / frame.height as usize;
+        let dst_stride = (self.layout.width * 4) as usize;
+        let copy_width = (frame.width.min(placement.width) * 4) as usize;
+        let rows = frame.height.min(placement.height) as usize;
+
+        for row in 0..rows {
+            let src_start = row * src_stride;
+            let dst_start = (placement.offset_y as usize + row) * dst_stride
+                + placement.offset_x as usize * 4;
+            self.data[dst_start..dst_start + copy_width]
+                .copy_from_slice(&frame.data[src_start..src_start + copy_width]);
+        }
+        true
+    }
+
+    /// Snapshot the canvas as a frame
+    pub fn to_frame(&self, timestamp_ms: u64) -> TimestampedFrame {
+        TimestampedFrame {
+            data: self.data.clone(),
+            timestamp_ms,
+            width: self.layout.width,
+            height: self.layout.height,
+        }
+    }
+}
+
+/// A display that can be selected for fullscreen capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    /// CoreGraphics display ID (pass to `RecordingConfig::display_id`)
+    pub display_id: u32,
+    /// Size in pixels
+    pub width: u32,
+    pub height: u32,
+    /// Main display (the one with the menu bar)
+    pub is_primary: bool,
+}
+
+/// The main display: the one at the arrangement origin, else the first listed
+pub fn primary_display(displays: &[DisplayBounds]) -> Option<&DisplayBounds> {
+    displays
+        .iter()
+        .find(|display| display.x == 0 && display.y == 0)
+        .or_else(|| displays.first())
+}
+
+/// Describe displays for the display picker
+pub fn display_infos(displays: &[DisplayBounds]) -> Vec<DisplayInfo> {
+    let primary_id = primary_display(displays).map(|display| display.display_id);
+    displays
+        .iter()
+        .map(|display| DisplayInfo {
+            display_id: display.display_id,
+            width: display.width,
+            height: display.height,
+            is_primary: Some(display.display_id) == primary_id,
+        })
+        .collect()
+}
+
+/// Display to capture: `requested` if connected, otherwise the primary display
+pub fn select_display(requested: Option<u32>, displays: &[DisplayBounds]) -> Option<DisplayBounds> {
+    requested
+        .and_then(|id| displays.iter().find(|display| display.display_id == id))
+        .or_else(|| primary_display(displays))
+        .copied()
+}
+
+/// Get the arrangement bounds of all connected displays
+#[cfg(target_os = "macos")]
+pub fn list_display_bounds() -> Result<Vec<DisplayBounds>, ScreenCaptureError> {
+    use core_graphics::display::CGDisplay;
+    use screencapturekit::shareable_content::SCShareableContent;
+
+    let content = SCShareableContent::get().map_err(|e| {
+        ScreenCaptureError::InitFailed(format!("Failed to get shareable content: {:?}", e))
+    })?;
+
+    Ok(content
+        .displays()
+        .iter()
+        .map(|display| {
+            let bounds = CGDisplay::new(display.display_id()).bounds();
+            DisplayBounds {
+                display_id: display.display_id(),
+                x: bounds.origin.x as i32,
+                y: bounds.origin.y as i32,
+                width: display.width(),
+                height: display.height(),
+            }
+        })
+        .collect())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn list_display_bounds() -> Result<Vec<DisplayBounds>, ScreenCaptureError> {
+    Err(ScreenCaptureError::UnsupportedPlatform)
+}
+
+/// Pause, stop and abort signals of one display capture, driven by the merge task
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+struct DisplaySignals {
+    is_paused: Arc<AtomicBool>,
+    stop_signal: Arc<AtomicBool>,
+    abort: CaptureAbort,
+}
+
+/// Compose display frames onto the canvas and emit it at `fps`
+///
+/// Mirrors the shared pause flag onto every display and stops on the shared
+/// stop signal, when `frame_tx` closes, or once every display has ended (the
+/// first display abort is then copied to the shared abort). On the way out
+/// every display's stop signal is set: the forwarders hold senders into
+/// `merged_rx`, so only the displays' own stop signals end their captures.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+async fn merge_displays(
+    mut merged_rx: mpsc::Receiver<(u32, TimestampedFrame)>,
+    mut canvas: Canvas,
+    frame_tx: mpsc::Sender<TimestampedFrame>,
+    fps: u32,
+    shared: DisplaySignals,
+    displays: Vec<DisplaySignals>,
+) {
+    let clock = PausableClock::new();
+    let mut ticker = tokio::time::interval(std::time::Duration::from_millis(1000 / fps.max(1) as u64));
+    let mut has_content = false;
+
+    loop {
+        tokio::select! {
+            received = merged_rx.recv() => match received {
+                Some((display_id, frame)) => {
+                    has_content |= canvas.blit(display_id, &frame);
+                }
+                None => {
+                    info!("All display captures ended");
+                    if let Some(reason) = displays.iter().find_map(|display| display.abort.reason()) {
+                        shared.abort.abort(reason);
+                    }
+                    break;
+                }
+            },
+            _ = ticker.tick() => {
+                if shared.stop_signal.load(Ordering::Relaxed) {
+                    info!("Stop signal received, stopping multi-display capture");
+                    break;
+                }
+
+                let paused = shared.is_paused.load(Ordering::Relaxed);
+                clock.sync_paused(paused);
+                for display in &displays {
+                    display.is_paused.store(paused, Ordering::Relaxed);
+                }
+                if paused || !has_content {
+                    continue;
+                }
+
+                let frame = canvas.to_frame(clock.elapsed().as_millis() as u64);
+                if frame_tx.send(frame).await.is_err() {
+                    debug!("Canvas frame channel closed, stopping merge task");
+                    break;
+                }
+            }
+        }
+    }
+
+    for display in &displays {
+        display.stop_signal.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Captures all displays into one side-by-side canvas
+pub struct MultiDisplayCapture {
+    layout: CanvasLayout,
+    captures: Vec<(u32, ScreenCapture)>,
+    fps: u32,
+    is_paused: Arc<AtomicBool>,
+    stop_signal: Arc<AtomicBool>,
+    abort: CaptureAbort,
+}
+
+impl MultiDisplayCapture {
+    /// Create one capture per connected display
+    #[cfg(target_os = "macos")]
+    pub fn new() -> Result<Self, ScreenCaptureError> {
+        let displays = list_display_bounds()?;
+        if displays.is_empty() {
+            return Err(ScreenCaptureError::InitFailed("No displays found".to_string()));
+        }
+
+        let layout = CanvasLayout::side_by_side(&displays);
+        let captures = layout
+            .placements
+            .iter()
+            .map(|p| ScreenCapture::new_for_display(p.display_id).map(|c| (p.display_id, c)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        info!(
+            "Multi-display capture: {} display(s) on a {}x{} canvas",
+            captures.len(),
+            layout.width,
+            layout.height
+        );
+        Ok(Self {
+            layout,
+            captures,
+            fps: super::screencapturekit::DEFAULT_CAPTURE_FPS,
+            is_paused: Arc::new(AtomicBool::new(false)),
+            stop_signal: Arc::new(AtomicBool::new(false)),
+            abort: CaptureAbort::default(),
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn new() -> Result<Self, ScreenCaptureError> {
+        Err(ScreenCaptureError::UnsupportedPlatform)
+    }
+
+    /// Canvas dimensions (width, height) for the encoder
+    pub fn get_dimensions(&self) -> (u32, u32) {
+        (self.layout.width, self.layout.height)
+    }
+
+    pub fn layout(&self) -> &CanvasLayout {
+        &self.layout
+    }
+
+    /// Set the canvas frame rate; every display captures at the same rate
+    #[cfg(target_os = "macos")]
+    pub fn set_frame_rate(&mut self, fps: u32) -> Result<(), ScreenCaptureError> {
+        for (_, capture) in self.captures.iter_mut() {
+            capture.set_frame_rate(fps)?;
+        }
+        self.fps = fps;
+        Ok(())
+    }
+
+    /// Enable system audio on the leftmost display's stream
+    ///
+    /// System audio is not per display, so one stream carries it.
+    #[cfg(target_os = "macos")]
+    pub fn enable_system_audio(&mut self, sample_rate: u32, channels: u16) -> Result<(), ScreenCaptureError> {
+        match self.captures.first_mut() {
+            Some((_, capture)) => capture.enable_system_audio(sample_rate, channels),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn is_system_audio_enabled(&self) -> bool {
+        self.captures
+            .first()
+            .is_some_and(|(_, capture)| capture.is_system_audio_enabled())
+    }
+
+    /// Start all display captures and the merge task
+    ///
+    /// Composed canvas frames are sent to `frame_tx` at the configured frame
+    /// rate, starting once any display has delivered a frame. System audio
+    /// and `app_handle` events come from the leftmost display. The task ends
+    /// on the stop signal, when `frame_tx` is closed, or once every display
+    /// capture has ended, and returns after all display captures have stopped.
+    #[cfg(target_os = "macos")]
+    pub fn start_continuous_capture(
+        &mut self,
+        frame_tx: mpsc::Sender<TimestampedFrame>,
+        audio_tx: Option<mpsc::Sender<crate::services::audio_capture::AudioSample>>,
+        app_handle: Option<tauri::AppHandle>,
+    ) -> Result<tokio::task::JoinHandle<()>, ScreenCaptureError> {
+        let (merged_tx, merged_rx) = mpsc::channel::<(u32, TimestampedFrame)>(self.captures.len() * 2);
+        let mut capture_handles = Vec::with_capacity(self.captures.len());
+        let mut displays = Vec::with_capacity(self.captures.len());
+        let mut audio_tx = audio_tx;
+        let mut app_handle = app_handle;
+
+        for (display_id, capture) in self.captures.iter_mut() {
+            displays.push(DisplaySignals {
+                is_paused: capture.get_pause_flag(),
+                stop_signal: capture.get_stop_signal(),
+                abort: capture.get_abort_signal(),
+            });
+
+            let (display_tx, mut display_rx) = mpsc::channel::<TimestampedFrame>(2);
+            capture_handles.push(capture.start_continuous_capture(display_tx, audio_tx.take(), app_handle.take())?);
+
+            let merged_tx = merged_tx.clone();
+            let display_id = *display_id;
+            tokio::spawn(async move {
+                while let Some(frame) = display_rx.recv().await {
+                    if merged_tx.send((display_id, frame)).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(merged_tx);
+
+        let canvas = Canvas::new(self.layout.clone());
+        let shared = DisplaySignals {
+            is_paused: Arc::clone(&self.is_paused),
+            stop_signal: Arc::clone(&self.stop_signal),
+            abort: self.abort.clone(),
+        };
+        let fps = self.fps;
+
+        let handle = tokio::spawn(async move {
+            // Sets every display's stop signal before returning, so the joins below finish
+            merge_displays(merged_rx, canvas, frame_tx, fps, shared, displays).await;
+            for capture_handle in capture_handles {
+                if let Err(e) = capture_handle.await {
+                    warn!("Display capture task join error: {}", e);
+                }
+            }
+        });
+
+        Ok(handle)
+    }
+
+    /// Stop all display captures
+    #[cfg(target_os = "macos")]
+    pub fn stop_capture(&mut self) {
+        self.stop_signal.store(true, Ordering::Relaxed);
+        for (_, capture) in self.captures.iter_mut() {
+            capture.stop_capture();
+        }
+    }
+
+    /// Pause flag for the whole canvas (mirrored onto every display)
+    pub fn get_pause_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.is_paused)
+    }
+
+    /// Stop signal for the whole canvas
+    pub fn get_stop_signal(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop_signal)
+    }
+
+    /// Abort slot; set when every display ended because one of them aborted
+    pub fn get_abort_signal(&self) -> CaptureAbort {
+        self.abort.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(display_id: u32, x: i32, y: i32, width: u32, height: u32) -> DisplayBounds {
+        DisplayBounds {
+            display_id,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_side_by_side_layout_follows_arrangement() {
+        // External 1920x1080 arranged left of the 2560x1600 main display, laptop on the right
+        let layout = CanvasLayout::side_by_side(&[
+            bounds(1, 0, 0, 2560, 1600),
+            bounds(2, -1920, 200, 1920, 1080),
+            bounds(3, 2560, 0, 1512, 982),
+        ]);
+
+        assert_eq!(layout.width, 1920 + 2560 + 1512);
+        assert_eq!(layout.height, 1600);
+
+        let offsets: Vec<(u32, u32)> = layout
+            .placements
+            .iter()
+            .map(|p| (p.display_id, p.offset_x))
+            .collect();
+        assert_eq!(offsets, vec![(2, 0), (1, 1920), (3, 4480)]);
+        assert!(layout.placements.iter().all(|p| p.offset_y == 0));
+    }
+
+    #[test]
+    fn test_canvas_blit_places_frame_at_offset() {
+        let layout = CanvasLayout::side_by_side(&[bounds(1, 0, 0, 2, 2), bounds(2, 2, 0, 2, 1)]);
+        let mut canvas = Canvas::new(layout);
+
+        let frame = TimestampedFrame {
+            data: vec![0xFF; 2 * 4],
+            timestamp_ms: 0,
+            width: 2,
+            height: 1,
+        };
+        assert!(canvas.blit(2, &frame));
+        assert!(!canvas.blit(99, &frame));
+
+        let out = canvas.to_frame(33);
+        assert_eq!((out.width, out.height), (4, 2));
+        // Row 0: display 1 (black) then display 2 (white)
+        assert_eq!(&out.data[0..8], &[0u8; 8]);
+        assert_eq!(&out.data[8..16], &[0xFF; 8]);
+        // Row 1 under display 2 stays black
+        assert_eq!(&out.data[24..32], &[0u8; 8]);
+    }
+
+    fn signals() -> DisplaySignals {
+        DisplaySignals {
+            is_paused: Arc::new(AtomicBool::new(false)),
+            stop_signal: Arc::new(AtomicBool::new(false)),
+            abort: CaptureAbort::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_mirrors_pause_and_stops_every_display() {
+        let layout = CanvasLayout::side_by_side(&[bounds(1, 0, 0, 2, 1), bounds(2, 2, 0, 2, 1)]);
+        let (merged_tx, merged_rx) = mpsc::channel(4);
+        let (frame_tx, mut frame_rx) = mpsc::channel(64);
+        let shared = signals();
+        let displays = vec![signals(), signals()];
+        let display_flags: Vec<_> = displays
+            .iter()
+            .map(|d| (Arc::clone(&d.is_paused), Arc::clone(&d.stop_signal)))
+            .collect();
+        let (shared_pause, shared_stop) = (Arc::clone(&shared.is_paused), Arc::clone(&shared.stop_signal));
+
+        let merge = tokio::spawn(merge_displays(merged_rx, Canvas::new(layout), frame_tx, 100, shared, displays));
+
+        let frame = TimestampedFrame { data: vec![0xFF; 2 * 4], timestamp_ms: 0, width: 2, height: 1 };
+        merged_tx.send((1, frame)).await.unwrap();
+        let canvas_frame = frame_rx.recv().await.unwrap();
+        assert_eq!((canvas_frame.width, canvas_frame.height), (4, 1));
+
+        shared_pause.store(true, Ordering::Relaxed);
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(display_flags.iter().all(|(paused, _)| paused.load(Ordering::Relaxed)));
+
+        // The sender is still alive, so only the stop signal can end the merge
+        shared_stop.store(true, Ordering::Relaxed);
+        tokio::time::timeout(std::time::Duration::from_secs(1), merge).await.unwrap().unwrap();
+        assert!(display_flags.iter().all(|(_, stopped)| stopped.load(Ordering::Relaxed)));
+        drop(merged_tx);
+    }
+
+    #[test]
+    fn test_display_selection_falls_back_to_primary() {
+        let displays = [
+            bounds(5, -1920, 0, 1920, 1080),
+            bounds(1, 0, 0, 2560, 1600),
+        ];
+
+        let infos = display_infos(&displays);
+        assert_eq!(infos.iter().filter(|d| d.is_primary).count(), 1);
+        assert!(infos.iter().any(|d| d.display_id == 1 && d.is_primary));
+
+        assert_eq!(select_display(Some(5), &displays).unwrap().display_id, 5);
+        assert_eq!(select_display(Some(42), &displays).unwrap().display_id, 1);
+        assert_eq!(select_display(None, &displays).unwrap().display_id, 1);
+        assert_eq!(select_display(None, &[]), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn test_connected_displays_include_a_primary() {
+        // Requires screen recording permission; skipped when content can't be listed
+        if let Ok(displays) = list_display_bounds() {
+            let infos = display_infos(&displays);
+            assert!(!infos.is_empty());
+            assert!(infos.iter().any(|d| d.is_primary));
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Capture queue overflow policy
+//!
+//! ScreenCaptureKit delivers frames on its own callback thread. When the bounded
+//! frame channel to the encoder is full, something has to give; `OverflowSender`
+//! makes that choice explicit and counts it:
+//!
+//! - `Block` waits for space, so every captured frame reaches the encoder.
+//!   ScreenCaptureKit skips frames on its side while the callback is blocked.
+//! - `DropNewest` discards the incoming frame.
+//! - `DropOldest` discards the oldest queued frame, keeping latency low for
+//!   live streaming. Frames go through a small ring drained by a relay task,
+//!   since a channel sender can't remove frames already in the channel.
+
+use crate::models::recording::OverflowPolicy;
+use crate::services::ffmpeg::TimestampedFrame;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, Notify};
+
+/// Per-policy counters for the capture→encoder send path
+#[derive(Debug, Default)]
+pub struct OverflowCounters {
+    sent: AtomicU64,
+    blocked: AtomicU64,
+    dropped_newest: AtomicU64,
+    dropped_oldest: AtomicU64,
+}
+
+/// Snapshot of `OverflowCounters`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverflowStats {
+    /// Frames handed to the channel (or ring)
+    pub sent: u64,
+    /// Sends that had to wait for space (`Block`)
+    pub blocked: u64,
+    /// Incoming frames discarded because the channel was full (`DropNewest`)
+    pub dropped_newest: u64,
+    /// Queued frames discarded to make room (`DropOldest`)
+    pub dropped_oldest: u64,
+}
+
+impl OverflowCounters {
+    pub fn snapshot(&self) -> OverflowStats {
+        OverflowStats {
+            sent: self.sent.load(Ordering::Relaxed),
+            blocked: self.blocked.load(Ordering::Relaxed),
+            dropped_newest: self.dropped_newest.load(Ordering::Relaxed),
+            dropped_oldest: self.dropped_oldest.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl OverflowStats {
+    /// Sends that found the channel full, whichever way the policy handled it
+    pub fn backpressure_drops(&self) -> u64 {
+        self.blocked + self.dropped_newest + self.dropped_oldest
+    }
+}
+
+/// Ring buffer in front of the channel for `DropOldest`
+struct DropOldestRing {
+    frames: Mutex<VecDeque<TimestampedFrame>>,
+    capacity: usize,
+    notify: Notify,
+    closed: AtomicBool,
+}
+
+/// Frame sender that applies an `OverflowPolicy` when the channel is full
+pub struct OverflowSender {
+    tx: mpsc::Sender<TimestampedFrame>,
+    policy: OverflowPolicy,
+    counters: Arc<OverflowCounters>,
+    ring: Option<Arc<DropOldestRing>>,
+}
+
+impl OverflowSender {
+    /// Wrap a frame channel sender
+    ///
+    /// `DropOldest` spawns a relay task, so this must be called from within a
+    /// Tokio runtime.
+    pub fn new(
+        tx: mpsc::Sender<TimestampedFrame>,
+        policy: OverflowPolicy,
+        counters: Arc<OverflowCounters>,
+    ) -> Self {
+        let ring = if policy == OverflowPolicy::DropOldest {
+            let ring = Arc::new(DropOldestRing {
+                frames: Mutex::new(VecDeque::new()),
+                capacity: tx.max_capacity().max(1),
+                notify: Notify::new(),
+                closed: AtomicBool::new(false),
+            });
+            tokio::spawn(relay_frames(ring.clone(), tx.clone()));
+            Some(ring)
+        } else {
+            None
+        };
+
+        Self {
+            tx,
+            policy,
+            counters,
+            ring,
+        }
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Whether the receiving side has gone away
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Send a frame from async code
+    ///
+    /// # Returns
+    /// * `Ok(())` - Frame was queued or deliberately dropped by the policy
+    /// * `Err(frame)` - The channel is closed
+    pub async fn send(&self, frame: TimestampedFrame) -> Result<(), TimestampedFrame> {
+        match self.policy {
+            OverflowPolicy::Block => {
+                if self.tx.capacity() == 0 {
+                    self.counters.blocked.fetch_add(1, Ordering::Relaxed);
+                }
+                self.tx.send(frame).await.map_err(|e| e.0)?;
+                self.counters.sent.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            _ => self.send_without_blocking(frame),
+        }
+    }
+
+    /// Send a frame from a non-async thread (e.g. a ScreenCaptureKit callback)
+    ///
+    /// `Block` parks the calling thread until there is space. Must not be called
+    /// from inside the Tokio runtime.
+    pub fn send_blocking(&self, frame: TimestampedFrame) -> Result<(), TimestampedFrame> {
+        match self.policy {
+            OverflowPolicy::Block => {
+                if self.tx.capacity() == 0 {
+                    self.counters.blocked.fetch_add(1, Ordering::Relaxed);
+                }
+                self.tx.blocking_send(frame).map_err(|e| e.0)?;
+                self.counters.sent.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            _ => self.send_without_blocking(frame),
+        }
+    }
+
+    fn send_without_blocking(&self, frame: TimestampedFrame) -> Result<(), TimestampedFrame> {
+        if self.tx.is_closed() {
+            return Err(frame);
+        }
+
+        if let Some(ring) = &self.ring {
+            let mut frames = ring.frames.lock().unwrap();
+            while frames.len() >= ring.capacity {
+                frames.pop_front();
+                self.counters.dropped_oldest.fetch_add(1, Ordering::Relaxed);
+            }
+            frames.push_back(frame);
+            drop(frames);
+
+            self.counters.sent.fetch_add(1, Ordering::Relaxed);
+            ring.notify.notify_one();
+            return Ok(());
+        }
+
+        match self.tx.try_send(frame) {
+            Ok(()) => {
+                self.counters.sent.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(TrySendError::Full(_)) => {
+                self.counters.dropped_newest.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(TrySendError::Closed(frame)) => Err(frame),
+        }
+    }
+}
+
+impl Drop for OverflowSender {
+    fn drop(&mut self) {
+        if let Some(ring) = &self.ring {
+            ring.closed.store(true, Ordering::Relaxed);
+            ring.notify.notify_one();
+        }
+    }
+}
+
+/// Move frames from the `DropOldest` ring into the channel
+///
+/// Ends once the sender is dropped and the ring is drained, or the channel closes.
+async fn relay_frames(ring: Arc<DropOldestRing>, tx: mpsc::Sender<TimestampedFrame>) {
+    loop {
+        let next = ring.frames.lock().unwrap().pop_front();
+        match next {
+            Some(frame) => {
+                if tx.send(frame).await.is_err() {
+                    break;
+                }
+            }
+            None => {
+                if ring.closed.load(Ordering::Relaxed) {
+                    break;
+                }
+                ring.notify.notified().await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn frame(n: u64) -> TimestampedFrame {
+        TimestampedFrame {
+            data: vec![0u8; 4],
+            timestamp_ms: n,
+            width: 1,
+            height: 1,
+        }
+    }
+
+    async fn drain(rx: &mut mpsc::Receiver<TimestampedFrame>) -> Vec<u64> {
+        let mut received = Vec::new();
+        while let Some(frame) = rx.recv().await {
+            received.push(frame.timestamp_ms);
+        }
+        received
+    }
+
+    #[tokio::test]
+    async fn test_block_policy_waits_for_space() {
+        let (tx, mut rx) = mpsc::channel(2);
+        let counters = Arc::new(OverflowCounters::default());
+        let sender = OverflowSender::new(tx, OverflowPolicy::Block, counters.clone());
+
+        sender.send(frame(0)).await.unwrap();
+        sender.send(frame(1)).await.unwrap();
+
+        // Channel is saturated: the third send waits
+        let third = tokio::time::timeout(Duration::from_millis(50), sender.send(frame(2))).await;
+        assert!(third.is_err());
+
+        rx.recv().await.unwrap();
+        sender.send(frame(3)).await.unwrap();
+        drop(sender);
+
+        assert_eq!(drain(&mut rx).await, vec![1, 3]);
+        let stats = counters.snapshot();
+        assert_eq!(stats.blocked, 1);
+        assert_eq!(stats.sent, 3);
+        assert_eq!(stats.dropped_newest + stats.dropped_oldest, 0);
+    }
+
+    #[tokio::test]
+    async fn test_drop_newest_policy_keeps_queued_frames() {
+        let (tx, mut rx) = mpsc::channel(2);
+        let counters = Arc::new(OverflowCounters::default());
+        let sender = OverflowSender::new(tx, OverflowPolicy::DropNewest, counters.clone());
+
+        for n in 0..5 {
+            sender.send(frame(n)).await.unwrap();
+        }
+        drop(sender);
+
+        assert_eq!(drain(&mut rx).await, vec![0, 1]);
+        let stats = counters.snapshot();
+        assert_eq!(stats.sent, 2);
+        assert_eq!(stats.dropped_newest, 3);
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_policy_keeps_latest_frames() {
+        let (tx, mut rx) = mpsc::channel(2);
+        let counters = Arc::new(OverflowCounters::default());
+        let sender = OverflowSender::new(tx, OverflowPolicy::DropOldest, counters.clone());
+
+        // The relay task can't run until we yield, so the ring saturates
+        for n in 0..6 {
+            sender.send_blocking(frame(n)).unwrap();
+        }
+        drop(sender);
+
+        assert_eq!(drain(&mut rx).await, vec![4, 5]);
+        let stats = counters.snapshot();
+        assert_eq!(stats.sent, 6);
+        assert_eq!(stats.dropped_oldest, 4);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_slow_consumer_block_keeps_order_drop_oldest_keeps_newest() {
+        // Capture callback thread producing faster than the encoder consumes
+        async fn run(policy: OverflowPolicy) -> (Vec<u64>, OverflowStats) {
+            let (tx, mut rx) = mpsc::channel(2);
+            let counters = Arc::new(OverflowCounters::default());
+            let sender = OverflowSender::new(tx, policy, counters.clone());
+            let producer = std::thread::spawn(move || {
+                for n in 0..20 {
+                    sender.send_blocking(frame(n)).unwrap();
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            });
+
+            let mut received = Vec::new();
+            while let Some(frame) = rx.recv().await {
+                received.push(frame.timestamp_ms);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            producer.join().unwrap();
+            (received, counters.snapshot())
+        }
+
+        let (blocked, stats) = run(OverflowPolicy::Block).await;
+        assert_eq!(blocked, (0..20).collect::<Vec<_>>());
+        assert!(stats.blocked > 0);
+
+        let (latest, stats) = run(OverflowPolicy::DropOldest).await;
+        assert_eq!(latest.last(), Some(&19));
+        assert!(latest.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(stats.dropped_oldest > 0);
+        assert_eq!(latest.len() as u64 + stats.dropped_oldest, 20);
+    }
+}
@@ -23,7 +23,7 @@
 //!     }
 //!
 //!     // Create capture instance with audio enabled (fullscreen mode)
-//!     let mut capture = ScreenCapture::new(None)?;
+//!     let mut capture = ScreenCapture::new(None, None)?;
 //!     capture.enable_system_audio(48000, 2)?;
 //!
 //!     // Capture a single frame
@@ -38,6 +38,20 @@ use tauri::Emitter;
 use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
+#[cfg(target_os = "macos")]
+use super::overflow::{OverflowCounters, OverflowSender, OverflowStats};
+#[cfg(target_os = "macos")]
+use super::display_unplug::{
+    is_display_disconnected, select_fallback_display, DisplayDisconnectedPayload,
+    DISPLAY_STARVATION_TIMEOUT,
+};
+#[cfg(target_os = "macos")]
+use super::multi_display::{list_display_bounds, select_display};
+use crate::models::recording::CaptureRegion;
+#[cfg(target_os = "macos")]
+use crate::models::recording::OverflowPolicy;
+#[cfg(target_os = "macos")]
+use crate::services::recording::PausableClock;
 
 // ScreenCaptureKit imports
 #[cfg(target_os = "macos")]
@@ -145,6 +159,374 @@ pub enum ScreenCaptureError {
 
     #[error("Not supported on this platform. ScreenCaptureKit is macOS-only")]
     UnsupportedPlatform,
+
+    #[error("Capture stopped after {consecutive_failures} consecutive frames could not be read. The display's pixel format is incompatible; try a different pixel format or display mode")]
+    PixelFormatIncompatible { consecutive_failures: u32 },
+
+    #[error("Invalid frame rate: {0} FPS (must be between 1 and 120)")]
+    InvalidFrameRate(u32),
+
+    #[error("Invalid capture region: {0}")]
+    InvalidRegion(String),
+}
+
+/// Capture frame rate when none is set
+pub const DEFAULT_CAPTURE_FPS: u32 = 30;
+
+/// Supported capture frame rates
+pub const CAPTURE_FPS_RANGE: std::ops::RangeInclusive<u32> = 1..=120;
+
+/// Reject frame rates outside `CAPTURE_FPS_RANGE`
+pub fn validate_capture_fps(fps: u32) -> Result<u32, ScreenCaptureError> {
+    if CAPTURE_FPS_RANGE.contains(&fps) {
+        Ok(fps)
+    } else {
+        Err(ScreenCaptureError::InvalidFrameRate(fps))
+    }
+}
+
+/// Reject empty regions and regions that don't fit a display of the given size (in points)
+pub fn validate_capture_region(
+    region: CaptureRegion,
+    display_width: u32,
+    display_height: u32,
+) -> Result<CaptureRegion, ScreenCaptureError> {
+    if region.width == 0 || region.height == 0 {
+        return Err(ScreenCaptureError::InvalidRegion(format!(
+            "{}x{} region is empty",
+            region.width, region.height
+        )));
+    }
+
+    let fits_x = region.x.checked_add(region.width).is_some_and(|right| right <= display_width);
+    let fits_y = region.y.checked_add(region.height).is_some_and(|bottom| bottom <= display_height);
+    if !fits_x || !fits_y {
+        return Err(ScreenCaptureError::InvalidRegion(format!(
+            "{}x{} at ({}, {}) extends beyond the {}x{} display",
+            region.width, region.height, region.x, region.y, display_width, display_height
+        )));
+    }
+
+    Ok(region)
+}
+
+/// First macOS release whose ScreenCaptureKit can filter audio by application
+pub const APPLICATION_AUDIO_MIN_MACOS: u32 = 13;
+
+/// Whether a macOS product version (`sw_vers -productVersion`, e.g. "13.4.1")
+/// can capture a single application's audio
+pub fn supports_application_audio(product_version: &str) -> bool {
+    product_version
+        .trim()
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok())
+        .is_some_and(|major| major >= APPLICATION_AUDIO_MIN_MACOS)
+}
+
+/// Whether this Mac can capture a single application's audio
+#[cfg(target_os = "macos")]
+fn application_audio_available() -> bool {
+    std::process::Command::new("sw_vers")
+        .arg("-productVersion")
+        .output()
+        .is_ok_and(|output| {
+            output.status.success() && supports_application_audio(&String::from_utf8_lossy(&output.stdout))
+        })
+}
+
+/// Display showing the centre of a window, and the window's rect within that display
+///
+/// `window_frame` is in global display points. The returned rect is the
+/// `source_rect` cropping a capture of that display down to the window.
+#[cfg(target_os = "macos")]
+fn window_rect_on_display(
+    window_frame: core_graphics::geometry::CGRect,
+    display_ids: impl IntoIterator<Item = u32>,
+) -> Option<(u32, core_graphics::geometry::CGRect)> {
+    use core_graphics::display::CGDisplay;
+    use core_graphics::geometry::{CGPoint, CGRect};
+
+    let center_x = window_frame.origin.x + window_frame.size.width / 2.0;
+    let center_y = window_frame.origin.y + window_frame.size.height / 2.0;
+    display_ids.into_iter().find_map(|display_id| {
+        let bounds = CGDisplay::new(display_id).bounds();
+        let inside = (bounds.origin.x..bounds.origin.x + bounds.size.width).contains(&center_x)
+            && (bounds.origin.y..bounds.origin.y + bounds.size.height).contains(&center_y);
+        inside.then(|| {
+            let origin = CGPoint::new(
+                window_frame.origin.x - bounds.origin.x,
+                window_frame.origin.y - bounds.origin.y,
+            );
+            (display_id, CGRect::new(&origin, &window_frame.size))
+        })
+    })
+}
+
+/// ScreenCaptureKit source rect for a capture region
+#[cfg(target_os = "macos")]
+fn region_source_rect(region: CaptureRegion) -> core_graphics::geometry::CGRect {
+    use core_graphics::geometry::{CGPoint, CGRect, CGSize};
+    CGRect::new(
+        &CGPoint::new(region.x as f64, region.y as f64),
+        &CGSize::new(region.width as f64, region.height as f64),
+    )
+}
+
+/// ScreenCaptureKit minimum frame interval for `fps` (1/fps seconds)
+#[cfg(target_os = "macos")]
+pub fn minimum_frame_interval(fps: u32) -> core_media_rs::cm_time::CMTime {
+    core_media_rs::cm_time::CMTime {
+        value: 1,
+        timescale: fps as i32,
+        flags: 1, // kCMTimeFlags_Valid
+        epoch: 0,
+    }
+}
+
+/// Consecutive unreadable pixel buffers before the capture loop gives up
+pub const PIXEL_BUFFER_FAILURE_LIMIT: u32 = 100;
+
+/// Why a capture loop stopped on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureAbortReason {
+    /// Frames kept arriving without a readable pixel buffer
+    PixelFormatIncompatible { consecutive_failures: u32 },
+}
+
+impl CaptureAbortReason {
+    /// Stable identifier for the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            CaptureAbortReason::PixelFormatIncompatible { .. } => "pixel_format_incompatible",
+        }
+    }
+
+    /// What the user can try before recording again
+    pub fn suggestion(&self) -> &'static str {
+        match self {
+            CaptureAbortReason::PixelFormatIncompatible { .. } => {
+                "Try a different pixel format or display mode, or record another display"
+            }
+        }
+    }
+}
+
+impl From<CaptureAbortReason> for ScreenCaptureError {
+    fn from(reason: CaptureAbortReason) -> Self {
+        match reason {
+            CaptureAbortReason::PixelFormatIncompatible { consecutive_failures } => {
+                ScreenCaptureError::PixelFormatIncompatible { consecutive_failures }
+            }
+        }
+    }
+}
+
+/// Abort slot shared between the frame callback, the capture loop and the command layer
+///
+/// The first reason recorded wins; the capture loop exits once one is set.
+#[derive(Debug, Clone, Default)]
+pub struct CaptureAbort {
+    reason: Arc<std::sync::Mutex<Option<CaptureAbortReason>>>,
+}
+
+impl CaptureAbort {
+    /// Request an abort (ignored if one is already pending)
+    pub fn abort(&self, reason: CaptureAbortReason) {
+        let mut slot = self.reason.lock().unwrap();
+        if slot.is_none() {
+            *slot = Some(reason);
+        }
+    }
+
+    /// Whether an abort has been requested
+    pub fn is_aborted(&self) -> bool {
+        self.reason.lock().unwrap().is_some()
+    }
+
+    /// Reason of the pending abort, if any
+    pub fn reason(&self) -> Option<CaptureAbortReason> {
+        *self.reason.lock().unwrap()
+    }
+
+    /// Typed error for the pending abort, if any
+    pub fn error(&self) -> Option<ScreenCaptureError> {
+        self.reason().map(ScreenCaptureError::from)
+    }
+
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    fn clear(&self) {
+        *self.reason.lock().unwrap() = None;
+    }
+}
+
+/// Consecutive pixel buffer failure tracking for the frame callback
+#[derive(Debug)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+struct PixelBufferFailures {
+    consecutive: std::sync::atomic::AtomicU32,
+    abort: CaptureAbort,
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+impl PixelBufferFailures {
+    fn new(abort: CaptureAbort) -> Self {
+        Self {
+            consecutive: std::sync::atomic::AtomicU32::new(0),
+            abort,
+        }
+    }
+
+    /// Reset the streak, returning how many failures preceded this success
+    fn record_success(&self) -> u32 {
+        self.consecutive.swap(0, Ordering::Relaxed)
+    }
+
+    /// Length of the current failure streak
+    fn current(&self) -> u32 {
+        self.consecutive.load(Ordering::Relaxed)
+    }
+
+    /// Count a failure, aborting the capture once the limit is reached
+    fn record_failure(&self) -> u32 {
+        let failure_count = self.consecutive.fetch_add(1, Ordering::Relaxed) + 1;
+        if failure_count == PIXEL_BUFFER_FAILURE_LIMIT {
+            self.abort.abort(CaptureAbortReason::PixelFormatIncompatible {
+                consecutive_failures: failure_count,
+            });
+        }
+        failure_count
+    }
+}
+
+/// How long `capture_single_frame` waits for the stream's first frame
+pub const SINGLE_FRAME_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Default time without frames before the capture watchdog reports a stall
+pub const DEFAULT_STALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Outcome of a capture watchdog check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogAction {
+    /// Frames are arriving (or a stall was already reported)
+    Healthy,
+    /// No frames for longer than the stall timeout; emit `capture-stalled` once
+    Stalled,
+    /// Stalled and auto-stop is enabled; the capture loop should exit
+    Stop,
+}
+
+/// Payload of the `capture-stalled` event
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureStalledPayload {
+    /// Milliseconds since the last frame was delivered
+    pub stalled_ms: u64,
+    /// Whether the capture was stopped because of the stall
+    pub auto_stopped: bool,
+}
+
+/// Payload of the `recording-stats` event, emitted about once a second while capturing
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingStatsPayload {
+    /// Frames handed to the encoder channel since capture started
+    pub frames_captured: u64,
+    /// Frames per second delivered over the last interval
+    pub effective_fps: f64,
+    /// Current streak of unreadable pixel buffers (aborts at `PIXEL_BUFFER_FAILURE_LIMIT`)
+    pub consecutive_failures: u32,
+    /// Sends that found the encoder channel full (blocked or dropped, per overflow policy)
+    pub channel_backpressure_drops: u64,
+}
+
+/// Turns cumulative capture counters into `recording-stats` payloads
+#[derive(Debug, Clone)]
+pub struct RecordingStatsTracker {
+    last_frames: u64,
+    last_sample: std::time::Instant,
+}
+
+impl RecordingStatsTracker {
+    pub fn new(now: std::time::Instant) -> Self {
+        Self {
+            last_frames: 0,
+            last_sample: now,
+        }
+    }
+
+    /// Build a payload, measuring FPS since the previous sample
+    pub fn sample(
+        &mut self,
+        frames_captured: u64,
+        consecutive_failures: u32,
+        channel_backpressure_drops: u64,
+        now: std::time::Instant,
+    ) -> RecordingStatsPayload {
+        let interval = now.saturating_duration_since(self.last_sample).as_secs_f64();
+        let new_frames = frames_captured.saturating_sub(self.last_frames);
+        let effective_fps = if interval > 0.0 { new_frames as f64 / interval } else { 0.0 };
+
+        self.last_frames = frames_captured;
+        self.last_sample = now;
+
+        RecordingStatsPayload {
+            frames_captured,
+            effective_fps,
+            consecutive_failures,
+            channel_backpressure_drops,
+        }
+    }
+}
+
+/// Frame starvation watchdog for the capture loop
+///
+/// Applies to every capture mode. Window mode additionally keeps its shorter
+/// window-closure check (Story 4.1 AC #7), which fires before this one.
+#[derive(Debug, Clone)]
+pub struct CaptureWatchdog {
+    stall_timeout: std::time::Duration,
+    auto_stop: bool,
+    stalled: bool,
+}
+
+impl CaptureWatchdog {
+    pub fn new(stall_timeout: std::time::Duration, auto_stop: bool) -> Self {
+        Self {
+            stall_timeout,
+            auto_stop,
+            stalled: false,
+        }
+    }
+
+    /// Evaluate the time since the last delivered frame
+    ///
+    /// Reports `Stalled` once per stall episode; the episode ends when frames resume.
+    pub fn check(&mut self, since_last_frame: std::time::Duration) -> WatchdogAction {
+        if since_last_frame <= self.stall_timeout {
+            if self.stalled {
+                info!("Capture recovered: frames arriving again");
+                self.stalled = false;
+            }
+            return WatchdogAction::Healthy;
+        }
+
+        if self.auto_stop {
+            return WatchdogAction::Stop;
+        }
+
+        if self.stalled {
+            return WatchdogAction::Healthy;
+        }
+
+        self.stalled = true;
+        WatchdogAction::Stalled
+    }
+
+    /// Whether a stall is currently in progress
+    pub fn is_stalled(&self) -> bool {
+        self.stalled
+    }
 }
 
 /// Stream output handler for real ScreenCaptureKit frame capture
@@ -152,18 +534,18 @@ pub enum ScreenCaptureError {
 /// Implements the SCStreamOutputTrait to receive frame callbacks from SCStream
 #[cfg(target_os = "macos")]
 struct VideoStreamOutput {
-    frame_tx: mpsc::Sender<crate::services::ffmpeg::TimestampedFrame>,
-    recording_start: std::sync::Arc<std::sync::Mutex<Option<std::time::Instant>>>,
+    /// Frame sender applying the recording's overflow policy
+    frame_tx: OverflowSender,
+    /// Recording clock; timestamps exclude paused spans
+    clock: Arc<PausableClock>,
     /// Last frame received timestamp (Story 4.1 - AC #7: Window closure detection)
     last_frame_time: std::sync::Arc<std::sync::Mutex<std::time::Instant>>,
     /// Pause flag for discarding frames during pause (Story 4.8)
     is_paused: Arc<AtomicBool>,
-    /// Tokio runtime handle for spawning async tasks from system callback threads
-    runtime_handle: tokio::runtime::Handle,
-    /// Consecutive failure counter to prevent infinite loops
-    consecutive_failures: Arc<std::sync::atomic::AtomicU32>,
-    /// Abort flag to signal critical failure to main loop
-    should_abort: Arc<AtomicBool>,
+    /// Consecutive pixel buffer failures; aborts the capture loop at the limit
+    pixel_buffer_failures: Arc<PixelBufferFailures>,
+    /// Encoder dimensions; window-capture frames that change size are fitted to these
+    output_size: (u32, u32),
 }
 
 #[cfg(target_os = "macos")]
@@ -180,7 +562,9 @@ impl SCStreamOutputTrait for VideoStreamOutput {
         }
 
         // Story 4.8: Discard frames during pause (frame discard approach)
-        if self.is_paused.load(Ordering::Relaxed) {
+        let paused = self.is_paused.load(Ordering::Relaxed);
+        self.clock.sync_paused(paused);
+        if paused {
             debug!("Frame discarded during pause");
             return;
         }
@@ -189,14 +573,14 @@ impl SCStreamOutputTrait for VideoStreamOutput {
         let pixel_buffer = match sample_buffer.get_pixel_buffer() {
             Ok(buffer) => {
                 // Reset failure counter and log success
-                let prev_failures = self.consecutive_failures.swap(0, Ordering::Relaxed);
+                let prev_failures = self.pixel_buffer_failures.record_success();
                 if prev_failures > 0 {
                     info!("Successfully recovered from {} consecutive pixel buffer failures", prev_failures);
                 }
                 buffer
             }
             Err(e) => {
-                let failure_count = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+                let failure_count = self.pixel_buffer_failures.record_failure();
 
                 if failure_count == 1 {
                     warn!("Failed to get pixel buffer from sample buffer: {:?} (failure #{}/100)", e, failure_count);
@@ -208,8 +592,7 @@ impl SCStreamOutputTrait for VideoStreamOutput {
                     error!("CRITICAL: 100 consecutive pixel buffer failures. Stopping capture to prevent infinite loop.");
                     error!("This typically indicates incompatible stream configuration or display format issues.");
                     error!("Possible causes: wrong pixel format, display permissions, or incompatible display mode.");
-                    // Signal main loop to abort
-                    self.should_abort.store(true, Ordering::Relaxed);
+                    // record_failure() has signalled the main loop to abort
                 } else if failure_count % 100 == 0 {
                     error!("CRITICAL: {} consecutive failures - capture loop should have stopped", failure_count);
                 }
@@ -234,16 +617,8 @@ impl SCStreamOutputTrait for VideoStreamOutput {
 
         // Lock guard automatically unlocks when dropped
 
-        // Calculate timestamp since recording start
-        let timestamp_ms = if let Ok(guard) = self.recording_start.lock() {
-            if let Some(start) = *guard {
-                start.elapsed().as_millis() as u64
-            } else {
-                0
-            }
-        } else {
-            0
-        };
+        // Calculate timestamp since recording start, not counting pauses
+        let timestamp_ms = self.clock.elapsed().as_millis() as u64;
 
         // Create timestamped frame
         let frame = crate::services::ffmpeg::TimestampedFrame {
@@ -253,19 +628,103 @@ impl SCStreamOutputTrait for VideoStreamOutput {
             height: height as u32,
         };
 
+        // A followed window that resized no longer matches the encoder; pad it back
+        let (output_width, output_height) = self.output_size;
+        let frame = if frame.width != output_width || frame.height != output_height {
+            debug!(
+                "Frame size {}x{} differs from output {}x{}, letterboxing",
+                frame.width, frame.height, output_width, output_height
+            );
+            super::letterbox::fit_frame(frame, output_width, output_height)
+        } else {
+            frame
+        };
+
         // Update last frame time (Story 4.1 - AC #7: Window closure detection)
         if let Ok(mut last_time) = self.last_frame_time.lock() {
             *last_time = std::time::Instant::now();
         }
 
-        // Send frame through channel (blocking if channel is full - backpressure)
-        // Use stored runtime handle to spawn on the Tokio runtime from any thread
-        let tx = self.frame_tx.clone();
-        self.runtime_handle.spawn(async move {
-            if let Err(e) = tx.send(frame).await {
-                error!("Failed to send frame: {}", e);
+        // Send frame through channel; a full channel is handled by the overflow policy
+        // (Block parks this callback thread, the drop policies never wait)
+        if self.frame_tx.send_blocking(frame).is_err() {
+            debug!("Frame channel closed, discarding frame");
+        }
+    }
+}
+
+/// Stream output handler that hands the first frame to `capture_single_frame`
+///
+/// The sender is taken on the first frame, so later callbacks are no-ops while
+/// the stream is being torn down.
+#[cfg(target_os = "macos")]
+struct SingleFrameOutput {
+    frame_tx: std::sync::Mutex<Option<std::sync::mpsc::SyncSender<Vec<u8>>>>,
+}
+
+#[cfg(target_os = "macos")]
+impl SCStreamOutputTrait for SingleFrameOutput {
+    fn did_output_sample_buffer(
+        &self,
+        sample_buffer: CMSampleBuffer,
+        of_type: SCStreamOutputType,
+    ) {
+        if of_type != SCStreamOutputType::Screen {
+            return;
+        }
+
+        // Idle/status buffers carry no pixels; wait for a real frame
+        let pixel_buffer = match sample_buffer.get_pixel_buffer() {
+            Ok(buffer) => buffer,
+            Err(e) => {
+                debug!("Single frame: skipping buffer without pixels: {:?}", e);
+                return;
             }
-        });
+        };
+        let lock_guard = match pixel_buffer.lock() {
+            Ok(guard) => guard,
+            Err(e) => {
+                warn!("Single frame: failed to lock pixel buffer: {:?}", e);
+                return;
+            }
+        };
+
+        let Ok(mut frame_tx) = self.frame_tx.lock() else {
+            return;
+        };
+        if let Some(tx) = frame_tx.take() {
+            // Receiver may have timed out already
+            let _ = tx.try_send(lock_guard.as_slice().to_vec());
+        }
+    }
+}
+
+/// Point a running stream at another display (after the captured one was unplugged)
+///
+/// Returns `false` if the display is gone too or the filter update failed.
+#[cfg(target_os = "macos")]
+fn switch_stream_display(stream: &SCStream, display_id: u32) -> bool {
+    let content = match SCShareableContent::get() {
+        Ok(content) => content,
+        Err(e) => {
+            error!("Failed to get shareable content for display fallback: {:?}", e);
+            return false;
+        }
+    };
+
+    let displays = content.displays();
+    let Some(display) = displays.iter().find(|d| d.display_id() == display_id) else {
+        warn!("Fallback display {} is no longer available", display_id);
+        return false;
+    };
+
+    let filter = SCContentFilter::new().with_display_excluding_windows(display, &[]);
+    match stream.update_content_filter(&filter) {
+        Ok(_) => true,
+        Err(e) => {
+            error!("Failed to switch capture to display {}: {:?}", display_id, e);
+            false
+        }
     }
 }
 
@@ -273,7 +732,8 @@ impl SCStreamOutputTrait for VideoStreamOutput {
 #[cfg(target_os = "macos")]
 struct AudioStreamOutput {
     audio_tx: mpsc::Sender<crate::services::audio_capture::AudioSample>,
-    recording_start: std::sync::Arc<std::sync::Mutex<Option<std::time::Instant>>>,
+    /// Recording clock shared with the video output
+    clock: Arc<PausableClock>,
     sample_rate: u32,
     channels: u16,
     /// Pause flag for discarding audio samples during pause (Story 4.8)
@@ -295,7 +755,9 @@ impl SCStreamOutputTrait for AudioStreamOutput {
         }
 
         // Story 4.8: Discard audio samples during pause (frame discard approach)
-        if self.is_paused.load(Ordering::Relaxed) {
+        let paused = self.is_paused.load(Ordering::Relaxed);
+        self.clock.sync_paused(paused);
+        if paused {
             debug!("System audio sample discarded during pause");
             return;
         }
@@ -355,16 +817,8 @@ impl SCStreamOutputTrait for AudioStreamOutput {
             }
         };
 
-        // Calculate timestamp since recording start
-        let timestamp_ns = if let Ok(guard) = self.recording_start.lock() {
-            if let Some(start) = *guard {
-                start.elapsed().as_nanos() as u64
-            } else {
-                0
-            }
-        } else {
-            0
-        };
+        // Calculate timestamp since recording start, not counting pauses
+        let timestamp_ns = self.clock.elapsed().as_nanos() as u64;
 
         // Extract real audio data from CMSampleBuffer using Core Media FFI
         let audio_data = unsafe {
@@ -551,10 +1005,32 @@ pub struct ScreenCapture {
     audio_config: SystemAudioConfig,
     /// Optional window ID for window-specific capture (Story 4.1)
     window_id: Option<u32>,
+    /// Optional display to capture in fullscreen mode (defaults to the main display)
+    display_id: Option<u32>,
     /// Pause flag for frame/sample discard (Story 4.8)
     is_paused: Arc<AtomicBool>,
+    /// Recording clock for frame/sample timestamps (restarted per capture)
+    clock: Arc<PausableClock>,
     /// Stop signal for capture loop
     stop_signal: Arc<AtomicBool>,
+    /// Time without frames before `capture-stalled` is emitted
+    stall_timeout: std::time::Duration,
+    /// Stop the capture loop when a stall is detected
+    auto_stop_on_stall: bool,
+    /// What to do when the frame channel is full
+    overflow_policy: OverflowPolicy,
+    /// Overflow counters for the current capture
+    overflow_counters: Arc<OverflowCounters>,
+    /// Set when the capture loop aborts on its own (e.g. pixel format incompatibility)
+    abort: CaptureAbort,
+    /// Switch to another display instead of stopping when the captured one is unplugged
+    display_fallback: bool,
+    /// Capture frame rate (ScreenCaptureKit minimum frame interval is 1/fps)
+    fps: u32,
+    /// Part of the display to capture (fullscreen mode only)
+    region: Option<CaptureRegion>,
+    /// Capture only the window's application audio instead of all system audio (window mode only)
+    scope_audio_to_window: bool,
 }
 
 #[cfg(target_os = "macos")]
@@ -566,12 +1042,14 @@ impl ScreenCapture {
     /// # Arguments
     ///
     /// * `window_id` - Optional window ID for window-specific capture (Story 4.1). If None, captures full screen.
+    /// * `display_id` - Display to capture in fullscreen mode (from `cmd_get_available_displays`).
+    ///   If None or not connected, the primary display is used.
     ///
     /// # Errors
     ///
     /// Returns `ScreenCaptureError::PermissionDenied` if screen recording permission is not granted.
     /// Returns `ScreenCaptureError::InitFailed` if ScreenCaptureKit initialization fails.
-    pub fn new(window_id: Option<u32>) -> Result<Self, ScreenCaptureError> {
+    pub fn new(window_id: Option<u32>, display_id: Option<u32>) -> Result<Self, ScreenCaptureError> {
         debug!("Initializing ScreenCapture");
 
         // Check permission first
@@ -593,6 +1071,9 @@ impl ScreenCapture {
             }
         }
 
+        // Fullscreen capture records the selected display (set below)
+        let mut selected_display_id = None;
+
         // Get actual display/window dimensions from ScreenCaptureKit
         let (width, height) = {
             match SCShareableContent::get() {
@@ -611,16 +1092,26 @@ impl ScreenCapture {
                         }
                     } else {
                         // Get display dimensions for fullscreen capture
-                        let displays = content.displays();
-                        if displays.is_empty() {
-                            warn!("No displays found, using default 1920x1080");
-                            (1920, 1080)
-                        } else {
-                            let display = &displays[0];
-                            let w = display.width();
-                            let h = display.height();
-                            debug!("Detected display dimensions: {}x{}", w, h);
-                            (w as usize, h as usize)
+                        let displays = list_display_bounds().unwrap_or_default();
+                        match select_display(display_id, &displays) {
+                            Some(display) => {
+                                if let Some(requested) = display_id.filter(|id| *id != display.display_id) {
+                                    warn!(
+                                        "Display {} not found, falling back to primary display {}",
+                                        requested, display.display_id
+                                    );
+                                }
+                                selected_display_id = Some(display.display_id);
+                                debug!(
+                                    "Detected display {} dimensions: {}x{}",
+                                    display.display_id, display.width, display.height
+                                );
+                                (display.width as usize, display.height as usize)
+                            }
+                            None => {
+                                warn!("No displays found, using default 1920x1080");
+                                (1920, 1080)
+                            }
                         }
                     }
                 }
@@ -642,11 +1133,161 @@ impl ScreenCapture {
             height,
             audio_config: SystemAudioConfig::default(),
             window_id,
+            display_id: selected_display_id,
             is_paused: Arc::new(AtomicBool::new(false)),
+            clock: Arc::new(PausableClock::new()),
             stop_signal: Arc::new(AtomicBool::new(false)),
+            stall_timeout: DEFAULT_STALL_TIMEOUT,
+            auto_stop_on_stall: false,
+            overflow_policy: OverflowPolicy::default(),
+            overflow_counters: Arc::new(OverflowCounters::default()),
+            abort: CaptureAbort::default(),
+            display_fallback: false,
+            fps: DEFAULT_CAPTURE_FPS,
+            region: None,
+            scope_audio_to_window: false,
         })
     }
 
+    /// Create a fullscreen capture of a specific display
+    ///
+    /// # Arguments
+    ///
+    /// * `display_id` - CoreGraphics display ID (from `SCDisplay::display_id`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScreenCaptureError::InitFailed` if the display is not available.
+    pub fn new_for_display(display_id: u32) -> Result<Self, ScreenCaptureError> {
+        let mut capture = Self::new(None, None)?;
+
+        let content = SCShareableContent::get().map_err(|e| {
+            ScreenCaptureError::InitFailed(format!("Failed to get shareable content: {:?}", e))
+        })?;
+        let display = content
+            .displays()
+            .into_iter()
+            .find(|d| d.display_id() == display_id)
+            .ok_or_else(|| {
+                ScreenCaptureError::InitFailed(format!("Display {} not found", display_id))
+            })?;
+
+        capture.width = display.width() as usize;
+        capture.height = display.height() as usize;
+        capture.display_id = Some(display_id);
+
+        info!(
+            "ScreenCapture targeting display {}: {}x{}",
+            display_id, capture.width, capture.height
+        );
+        Ok(capture)
+    }
+
+    /// Configure the frame starvation watchdog
+    ///
+    /// # Arguments
+    ///
+    /// * `stall_timeout` - Time without frames before `capture-stalled` is emitted
+    /// * `auto_stop` - Stop capturing when a stall is detected
+    pub fn set_stall_watchdog(&mut self, stall_timeout: std::time::Duration, auto_stop: bool) {
+        self.stall_timeout = stall_timeout;
+        self.auto_stop_on_stall = auto_stop;
+    }
+
+    /// Continue on another display if the captured one is unplugged (applies to the next capture)
+    ///
+    /// When off, an unplugged display stops the capture. Either way a
+    /// `display-disconnected` event is emitted.
+    pub fn set_display_fallback(&mut self, enabled: bool) {
+        self.display_fallback = enabled;
+    }
+
+    /// Capture only the captured window's application audio (applies to the next capture)
+    ///
+    /// Needs macOS 13+; older systems, and fullscreen mode, keep capturing all
+    /// system audio.
+    ///
+    /// ScreenCaptureKit only scopes audio on a display filter, so the video is
+    /// the display cropped to where the window was when capture started: it
+    /// does not follow the window if it is moved or resized.
+    pub fn set_scope_audio_to_window(&mut self, enabled: bool) {
+        self.scope_audio_to_window = enabled;
+    }
+
+    /// Whether window-mode audio is scoped to the window's application
+    pub fn is_audio_scoped_to_window(&self) -> bool {
+        self.scope_audio_to_window
+    }
+
+    /// Set the capture frame rate, 1-120 FPS (applies to the next capture)
+    ///
+    /// The encoder must be created with the same rate so `-r` matches the
+    /// frames it receives.
+    pub fn set_frame_rate(&mut self, fps: u32) -> Result<(), ScreenCaptureError> {
+        self.fps = validate_capture_fps(fps)?;
+        Ok(())
+    }
+
+    /// Capture frame rate
+    pub fn frame_rate(&self) -> u32 {
+        self.fps
+    }
+
+    /// Capture only part of the display (applies to the next capture)
+    ///
+    /// The region is in display points relative to the display's top-left
+    /// corner, and capture dimensions become the region size. `None` restores
+    /// full-display capture.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScreenCaptureError::InvalidRegion` if the region is empty, lies
+    /// outside the display, or a window is being captured.
+    pub fn set_region(&mut self, region: Option<CaptureRegion>) -> Result<(), ScreenCaptureError> {
+        if region.is_some() && self.window_id.is_some() {
+            return Err(ScreenCaptureError::InvalidRegion(
+                "regions are only supported in fullscreen mode".to_string(),
+            ));
+        }
+        if region.is_none() && self.region.is_none() {
+            return Ok(());
+        }
+
+        let displays = list_display_bounds()?;
+        let display = select_display(self.display_id, &displays).ok_or_else(|| {
+            ScreenCaptureError::InitFailed("No displays available for capture".to_string())
+        })?;
+
+        match region {
+            Some(region) => {
+                let region = validate_capture_region(region, display.width, display.height)?;
+                self.width = region.width as usize;
+                self.height = region.height as usize;
+                info!(
+                    "Capturing {}x{} region at ({}, {}) of display {}",
+                    region.width, region.height, region.x, region.y, display.display_id
+                );
+            }
+            None => {
+                self.width = display.width as usize;
+                self.height = display.height as usize;
+            }
+        }
+
+        self.region = region;
+        Ok(())
+    }
+
+    /// Set the frame channel overflow policy (applies to the next capture)
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.overflow_policy = policy;
+    }
+
+    /// Frames sent and dropped by the overflow policy so far
+    pub fn overflow_stats(&self) -> OverflowStats {
+        self.overflow_counters.snapshot()
+    }
+
     /// Get the capture dimensions (width and height)
     ///
     /// Returns the display dimensions being captured.
@@ -680,7 +1321,7 @@ impl ScreenCapture {
     ///
     /// ```rust,no_run
     /// # use clippy_lib::services::screen_capture::ScreenCapture;
-    /// let mut capture = ScreenCapture::new(None)?; // Fullscreen mode
+    /// let mut capture = ScreenCapture::new(None, None)?; // Fullscreen mode
     /// capture.enable_system_audio(48000, 2)?; // 48kHz stereo
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
@@ -723,13 +1364,13 @@ impl ScreenCapture {
         &self.audio_config
     }
 
-    /// Capture a single frame from the default display
+    /// Capture a single frame from the configured window or display
     ///
-    /// **Note:** This is a simplified API for single-frame capture. For continuous
-    /// capture, use `start_continuous_capture()` instead.
+    /// **Note:** This starts and tears down a short-lived SCStream for one frame.
+    /// For continuous capture, use `start_continuous_capture()` instead.
     ///
-    /// This method uses ScreenCaptureKit to capture a real frame from the screen.
     /// The frame is returned as raw BGRA pixel data (4 bytes per pixel).
+    /// Blocks for at most [`SINGLE_FRAME_TIMEOUT`] waiting for the first frame.
     ///
     /// # Returns
     ///
@@ -737,36 +1378,76 @@ impl ScreenCapture {
     ///
     /// # Errors
     ///
-    /// Returns `ScreenCaptureError::CaptureFailed` if the capture operation fails.
-    ///
-    /// # Implementation Note
-    ///
-    /// Due to the async nature of ScreenCaptureKit delegates, this method is primarily
-    /// for testing. Production code should use `start_continuous_capture()` for
-    /// efficient frame streaming.
+    /// Returns `ScreenCaptureError::CaptureFailed` if the stream cannot be set up
+    /// or no frame arrives before the timeout.
     pub fn capture_single_frame(&self) -> Result<Vec<u8>, ScreenCaptureError> {
         debug!("Capturing single frame with ScreenCaptureKit");
 
-        // Note: Real single-frame capture requires setting up a full SCStream with delegate,
-        // capturing one frame, then tearing down. This is inefficient compared to continuous capture.
-        // For simplicity and to match the testing requirements, we'll document this limitation.
+        let content = SCShareableContent::get().map_err(|e| {
+            ScreenCaptureError::CaptureFailed(format!("Failed to get shareable content: {:?}", e))
+        })?;
+
+        let filter = match self.window_id {
+            Some(wid) => {
+                let windows = content.windows();
+                let window = windows.iter().find(|w| w.window_id() == wid).ok_or_else(|| {
+                    ScreenCaptureError::CaptureFailed(format!("Window {} not available", wid))
+                })?;
+                SCContentFilter::new().with_desktop_independent_window(window)
+            }
+            None => {
+                let displays = content.displays();
+                let display = self
+                    .display_id
+                    .and_then(|id| displays.iter().find(|d| d.display_id() == id))
+                    .or_else(|| displays.first())
+                    .ok_or_else(|| {
+                        ScreenCaptureError::CaptureFailed("No displays available for capture".to_string())
+                    })?;
+                SCContentFilter::new().with_display_excluding_windows(display, &[])
+            }
+        };
+
+        let config = SCStreamConfiguration::new()
+            .set_width(self.width as u32)
+            .and_then(|c| c.set_height(self.height as u32))
+            .and_then(|c| c.set_pixel_format(PixelFormat::BGRA))
+            .and_then(|c| c.set_shows_cursor(true))
+            .and_then(|c| match self.region {
+                Some(region) => c.set_source_rect(region_source_rect(region)),
+                None => Ok(c),
+            })
+            .map_err(|e| {
+                ScreenCaptureError::CaptureFailed(format!("Failed to configure stream: {:?}", e))
+            })?;
+
+        let (frame_tx, frame_rx) = std::sync::mpsc::sync_channel(1);
+        let mut stream = SCStream::new(&filter, &config);
+        stream.add_output_handler(
+            SingleFrameOutput { frame_tx: std::sync::Mutex::new(Some(frame_tx)) },
+            SCStreamOutputType::Screen,
+        );
 
-        warn!("capture_single_frame requires async delegate setup - use start_continuous_capture() for production");
+        stream.start_capture().map_err(|e| {
+            ScreenCaptureError::CaptureFailed(format!("Failed to start capture: {:?}", e))
+        })?;
 
-        // For Story 2.2's single frame test requirement (AC#5), we provide a realistic
-        // proof of initialization but defer actual frame capture to continuous_capture
-        // which is the production path per Architecture Pattern 2
-        let width = self.width;
-        let height = self.height;
-        let bytes_per_pixel = 4; // BGRA
-        let frame_size = width * height * bytes_per_pixel;
+        let frame = frame_rx.recv_timeout(SINGLE_FRAME_TIMEOUT);
 
-        info!("Single frame capture initialized: {}x{} BGRA ({} bytes)", width, height, frame_size);
-        info!("Use start_continuous_capture() for real frame data with SCStream delegates");
+        if let Err(e) = stream.stop_capture() {
+            warn!("Failed to stop single-frame stream: {:?}", e);
+        }
 
-        // Return zero-filled buffer for this proof-of-concept method
-        // Real frame data comes through start_continuous_capture() delegate callbacks
-        Ok(vec![0u8; frame_size])
+        match frame {
+            Ok(data) => {
+                info!("Captured single frame: {}x{} BGRA ({} bytes)", self.width, self.height, data.len());
+                Ok(data)
+            }
+            Err(_) => Err(ScreenCaptureError::CaptureFailed(format!(
+                "No frame received within {:?}",
+                SINGLE_FRAME_TIMEOUT
+            ))),
+        }
     }
 
     /// Start continuous screen capture with frame streaming
@@ -816,10 +1497,22 @@ impl ScreenCapture {
         let height = self.height;
         let audio_config = self.audio_config.clone();
         let window_id = self.window_id;
+        let display_id = self.display_id;
         let is_paused = self.is_paused.clone(); // Story 4.8: Clone pause flag for capture task
+        self.clock = Arc::new(PausableClock::new());
+        let clock = self.clock.clone();
+        let mut watchdog = CaptureWatchdog::new(self.stall_timeout, self.auto_stop_on_stall);
+        let overflow_policy = self.overflow_policy;
+        self.overflow_counters = Arc::new(OverflowCounters::default());
+        let overflow_counters = self.overflow_counters.clone();
+        let display_fallback = self.display_fallback;
+        let fps = self.fps;
+        let region = self.region;
+        let scope_audio_to_window = self.scope_audio_to_window;
 
         info!(
-            "Starting ScreenCaptureKit capture at 30 FPS: {}x{}, audio: {}, window_id: {:?}",
+            "Starting ScreenCaptureKit capture at {} FPS: {}x{}, audio: {}, window_id: {:?}",
+            fps,
             width,
             height,
             if audio_config.enabled { "enabled" } else { "disabled" },
@@ -832,16 +1525,18 @@ impl ScreenCapture {
         // Clone stop signal for capture task
         let stop_signal = self.stop_signal.clone();
 
+        // Clear any abort left over from a previous capture
+        self.abort.clear();
+        let abort = self.abort.clone();
+
         // Spawn capture task
         let handle = tokio::spawn(async move {
             // Story 4.1 - AC #7: Initialize last frame time for window closure detection
             let last_frame_time = std::sync::Arc::new(std::sync::Mutex::new(std::time::Instant::now()));
 
-            // Create abort flag for critical failure detection (must be outside scope)
-            let should_abort = Arc::new(AtomicBool::new(false));
-
             // Create and start capture stream (in a scope to drop non-Send types)
-            let stream = {
+            // Also yields the captured display in fullscreen mode, for hot-unplug detection
+            let (stream, mut captured_display_id, pixel_buffer_failures) = {
                 // Get shareable content (displays and windows)
                 let shareable_content = match SCShareableContent::get() {
                     Ok(content) => content,
@@ -852,6 +1547,8 @@ impl ScreenCapture {
                 };
 
                 // Story 4.1: Create content filter based on recording mode
+                let mut fullscreen_display_id = None;
+                let mut source_rect = region.map(region_source_rect);
                 let filter = if let Some(wid) = window_id {
                     // Window mode: Find and capture specific window
                     info!("Creating window-specific filter for window ID: {}", wid);
@@ -867,8 +1564,55 @@ impl ScreenCapture {
                     match target_window {
                         Ok(window) => {
                             info!("Found window: {} ({})", window.title(), window.owning_application().application_name());
-                            // Story 4.1 AC#3, #5: Use window-specific filter (Follow Window approach)
-                            SCContentFilter::new().with_desktop_independent_window(window)
+
+                            // A window filter records all system audio; ScreenCaptureKit only
+                            // scopes audio by application, on a display filter
+                            let app_audio_display = if !(scope_audio_to_window && audio_config.enabled) {
+                                None
+                            } else if !application_audio_available() {
+                                warn!(
+                                    "Per-application audio needs macOS {}+, recording all system audio",
+                                    APPLICATION_AUDIO_MIN_MACOS
+                                );
+                                None
+                            } else {
+                                let found = window_rect_on_display(
+                                    window.get_frame(),
+                                    shareable_content.displays().iter().map(|d| d.display_id()),
+                                );
+                                if found.is_none() {
+                                    warn!("Window {} is not on a display, recording all system audio", wid);
+                                }
+                                found
+                            };
+
+                            let displays = shareable_content.displays();
+                            match app_audio_display
+                                .and_then(|(id, rect)| displays.iter().find(|d| d.display_id() == id).map(|d| (d, rect)))
+                            {
+                                Some((display, window_rect)) => {
+                                    // The window's application minus its other windows, cropped to the window
+                                    let app = window.owning_application();
+                                    let pid = app.process_id();
+                                    let other_windows: Vec<_> = windows
+                                        .iter()
+                                        .filter(|w| w.window_id() != wid && w.owning_application().process_id() == pid)
+                                        .collect();
+                                    info!("Scoping audio to {} (pid {})", app.application_name(), pid);
+                                    warn!(
+                                        "Audio-scoped capture is cropped to window {} at {:?} and won't follow it if it moves",
+                                        wid, window_rect
+                                    );
+                                    source_rect = Some(window_rect);
+                                    SCContentFilter::new().with_display_including_application_excepting_windows(
+                                        display,
+                                        &[&app],
+                                        &other_windows,
+                                    )
+                                }
+                                // Story 4.1 AC#3, #5: Use window-specific filter (Follow Window approach)
+                                None => SCContentFilter::new().with_desktop_independent_window(window),
+                            }
                         }
                         Err(_) => {
                             error!("Window {} not available, falling back to fullscreen", wid);
@@ -882,14 +1626,18 @@ impl ScreenCapture {
                         }
                     }
                 } else {
-                    // Fullscreen mode: Capture entire display
-                    info!("Creating fullscreen filter");
+                    // Fullscreen mode: Capture entire display (main display unless one was chosen)
+                    info!("Creating fullscreen filter (display_id: {:?})", display_id);
                     let displays = shareable_content.displays();
                     if displays.is_empty() {
                         error!("No displays available for capture");
                         return;
                     }
-                    SCContentFilter::new().with_display_excluding_windows(&displays[0], &[])
+                    let display = display_id
+                        .and_then(|id| displays.iter().find(|d| d.display_id() == id))
+                        .unwrap_or(&displays[0]);
+                    fullscreen_display_id = Some(display.display_id());
+                    SCContentFilter::new().with_display_excluding_windows(display, &[])
                 };
 
                 // Configure stream
@@ -899,6 +1647,10 @@ impl ScreenCapture {
                     .and_then(|c| c.set_height(height as u32))
                     .and_then(|c| c.set_pixel_format(PixelFormat::BGRA))
                     .and_then(|c| c.set_shows_cursor(true))
+                    .and_then(|c| match source_rect {
+                        Some(rect) => c.set_source_rect(rect),
+                        None => Ok(c),
+                    })
                 {
                     Ok(c) => {
                         info!("Stream configuration successful");
@@ -910,13 +1662,8 @@ impl ScreenCapture {
                     }
                 };
 
-                // Set frame rate to 30 FPS
-                let frame_interval = core_media_rs::cm_time::CMTime {
-                    value: 1,
-                    timescale: 30, // 1/30 = 30 FPS
-                    flags: 1, // kCMTimeFlags_Valid
-                    epoch: 0,
-                };
+                // Deliver at most `fps` frames per second
+                let frame_interval = minimum_frame_interval(fps);
                 let config = match config.set_minimum_frame_interval(&frame_interval) {
                     Ok(c) => c,
                     Err(e) => {
@@ -938,18 +1685,15 @@ impl ScreenCapture {
                     config
                 };
 
-                // Create recording start time tracker (shared with delegate)
-                let recording_start = std::sync::Arc::new(std::sync::Mutex::new(Some(std::time::Instant::now())));
-
                 // Create stream output handler
+                let pixel_buffer_failures = Arc::new(PixelBufferFailures::new(abort.clone()));
                 let video_output = VideoStreamOutput {
-                    frame_tx: frame_tx.clone(),
-                    recording_start: recording_start.clone(),
+                    frame_tx: OverflowSender::new(frame_tx.clone(), overflow_policy, overflow_counters.clone()),
+                    clock: clock.clone(),
                     last_frame_time: last_frame_time.clone(),
                     is_paused: is_paused.clone(), // Story 4.8
-                    runtime_handle: tokio::runtime::Handle::current(),
-                    consecutive_failures: Arc::new(std::sync::atomic::AtomicU32::new(0)),
-                    should_abort: should_abort.clone(),
+                    pixel_buffer_failures: pixel_buffer_failures.clone(),
+                    output_size: (width as u32, height as u32),
                 };
 
                 // Create SCStream
@@ -962,7 +1706,7 @@ impl ScreenCapture {
                 if let Some(audio_tx) = audio_tx {
                     let audio_output = AudioStreamOutput {
                         audio_tx,
-                        recording_start: recording_start.clone(),
+                        clock: clock.clone(),
                         sample_rate: audio_config.sample_rate,
                         channels: audio_config.channels,
                         is_paused: is_paused.clone(), // Story 4.8
@@ -985,12 +1729,13 @@ impl ScreenCapture {
                 }
 
                 // Return stream (filter, config, displays are dropped here)
-                stream
+                (stream, fullscreen_display_id, pixel_buffer_failures)
             };
 
             // Keep stream alive - it will continue capturing until channel is closed
             // The delegate will handle frame callbacks
             let mut loop_count = 0u32;
+            let mut stats_tracker = RecordingStatsTracker::new(std::time::Instant::now());
             loop {
                 tokio::time::sleep(std::time::Duration::from_millis(100)).await;
                 loop_count += 1;
@@ -998,6 +1743,18 @@ impl ScreenCapture {
                 // Log every 10 iterations (1 second)
                 if loop_count % 10 == 0 {
                     debug!("Capture loop alive ({}), checking stop_signal", loop_count);
+
+                    // Live capture health for the frontend
+                    if let Some(handle) = &app_handle {
+                        let overflow = overflow_counters.snapshot();
+                        let stats = stats_tracker.sample(
+                            overflow.sent,
+                            pixel_buffer_failures.current(),
+                            overflow.backpressure_drops(),
+                            std::time::Instant::now(),
+                        );
+                        let _ = handle.emit("recording-stats", stats);
+                    }
                 }
 
                 // Check if stop requested
@@ -1008,7 +1765,7 @@ impl ScreenCapture {
                 }
 
                 // Check if critical failure occurred (too many pixel buffer failures)
-                if should_abort.load(Ordering::Relaxed) {
+                if abort.is_aborted() {
                     error!("Aborting capture due to critical pixel buffer failures");
                     break;
                 }
@@ -1035,8 +1792,111 @@ impl ScreenCapture {
                         }
                     }
                 }
+
+                // External display hot-unplug (fullscreen mode): confirm a short
+                // starvation against the connected displays about once a second
+                if let Some(current_display) = captured_display_id {
+                    let since_last_frame = last_frame_time
+                        .lock()
+                        .map(|t| t.elapsed())
+                        .unwrap_or_default();
+
+                    if loop_count % 10 == 0
+                        && since_last_frame > DISPLAY_STARVATION_TIMEOUT
+                        && !is_paused.load(Ordering::Relaxed)
+                    {
+                        if let Ok(connected) = list_display_bounds() {
+                            let connected_ids: Vec<u32> =
+                                connected.iter().map(|d| d.display_id).collect();
+
+                            if is_display_disconnected(current_display, since_last_frame, &connected_ids) {
+                                let fallback = if display_fallback {
+                                    select_fallback_display(current_display, &connected)
+                                        .filter(|&id| switch_stream_display(&stream, id))
+                                } else {
+                                    None
+                                };
+
+                                warn!(
+                                    event = "display_disconnected",
+                                    display_id = current_display,
+                                    fallback_display_id = ?fallback,
+                                    "Captured display was disconnected"
+                                );
+
+                                if let Some(handle) = &app_handle {
+                                    let _ = handle.emit(
+                                        "display-disconnected",
+                                        DisplayDisconnectedPayload {
+                                            display_id: current_display,
+                                            fallback_display_id: fallback,
+                                        },
+                                    );
+                                }
+
+                                match fallback {
+                                    Some(new_display) => {
+                                        info!("Capture switched to display {}", new_display);
+                                        captured_display_id = Some(new_display);
+                                        if let Ok(mut last_time) = last_frame_time.lock() {
+                                            *last_time = std::time::Instant::now();
+                                        }
+                                    }
+                                    None => break,
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Frame starvation watchdog (all modes)
+                // Paused captures discard frames, so keep the clock fresh while paused
+                if let Ok(mut last_time) = last_frame_time.lock() {
+                    if is_paused.load(Ordering::Relaxed) {
+                        *last_time = std::time::Instant::now();
+                        continue;
+                    }
+
+                    let elapsed = last_time.elapsed();
+                    let action = watchdog.check(elapsed);
+                    if action != WatchdogAction::Healthy {
+                        let auto_stopped = action == WatchdogAction::Stop;
+                        warn!(
+                            event = "capture_stalled",
+                            stalled_ms = elapsed.as_millis() as u64,
+                            auto_stopped,
+                            "No frames received for {}s",
+                            elapsed.as_secs()
+                        );
+
+                        if let Some(handle) = &app_handle {
+                            let _ = handle.emit(
+                                "capture-stalled",
+                                CaptureStalledPayload {
+                                    stalled_ms: elapsed.as_millis() as u64,
+                                    auto_stopped,
+                                },
+                            );
+                        }
+
+                        if auto_stopped {
+                            break;
+                        }
+                    }
+                }
             }
 
+            let overflow = overflow_counters.snapshot();
+            info!(
+                event = "capture_overflow_stats",
+                policy = ?overflow_policy,
+                sent = overflow.sent,
+                blocked = overflow.blocked,
+                dropped_newest = overflow.dropped_newest,
+                dropped_oldest = overflow.dropped_oldest,
+                "Capture queue overflow stats"
+            );
+
             // Stop capture
             match stream.stop_capture() {
                 Ok(_) => {
@@ -1087,6 +1947,7 @@ impl ScreenCapture {
         }
 
         self.is_paused.store(true, Ordering::Relaxed);
+        self.clock.pause();
         info!("Screen capture paused (frame discard enabled)");
         Ok(())
     }
@@ -1106,6 +1967,7 @@ impl ScreenCapture {
         }
 
         self.is_paused.store(false, Ordering::Relaxed);
+        self.clock.resume();
         info!("Screen capture resumed");
         Ok(())
     }
@@ -1123,10 +1985,20 @@ impl ScreenCapture {
         Arc::clone(&self.is_paused)
     }
 
+    /// Get the recording clock (elapsed time excluding pauses)
+    pub fn get_clock(&self) -> Arc<PausableClock> {
+        Arc::clone(&self.clock)
+    }
+
     /// Get the stop signal for external control
     pub fn get_stop_signal(&self) -> Arc<AtomicBool> {
         Arc::clone(&self.stop_signal)
     }
+
+    /// Get the abort slot, which records why the capture loop stopped on its own
+    pub fn get_abort_signal(&self) -> CaptureAbort {
+        self.abort.clone()
+    }
 }
 
 #[cfg(not(target_os = "macos"))]
@@ -1134,7 +2006,7 @@ pub struct ScreenCapture;
 
 #[cfg(not(target_os = "macos"))]
 impl ScreenCapture {
-    pub fn new(_window_id: Option<u32>) -> Result<Self, ScreenCaptureError> {
+    pub fn new(_window_id: Option<u32>, _display_id: Option<u32>) -> Result<Self, ScreenCaptureError> {
         Err(ScreenCaptureError::UnsupportedPlatform)
     }
 
@@ -1147,13 +2019,61 @@ impl ScreenCapture {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_capture_fps_validation() {
+        assert_eq!(validate_capture_fps(60).unwrap(), 60);
+        assert_eq!(validate_capture_fps(120).unwrap(), 120);
+        assert!(matches!(validate_capture_fps(0), Err(ScreenCaptureError::InvalidFrameRate(0))));
+        assert!(matches!(validate_capture_fps(121), Err(ScreenCaptureError::InvalidFrameRate(121))));
+    }
+
+    #[test]
+    fn test_application_audio_needs_macos_13() {
+        assert!(supports_application_audio("13.0"));
+        assert!(supports_application_audio("14.4.1\n"));
+        assert!(!supports_application_audio("12.7.6"));
+        assert!(!supports_application_audio(""));
+    }
+
+    #[test]
+    fn test_out_of_bounds_capture_region_rejected() {
+        let region = |x, y, width, height| CaptureRegion { x, y, width, height };
+
+        // Flush against the bottom-right corner still fits
+        let corner = region(1152, 648, 768, 432);
+        assert_eq!(validate_capture_region(corner, 1920, 1080).unwrap(), corner);
+
+        for bad in [
+            region(1200, 0, 800, 600),      // past the right edge
+            region(0, 800, 640, 400),       // past the bottom edge
+            region(u32::MAX, 0, 10, 10),    // overflowing coordinates
+            region(100, 100, 0, 300),       // empty
+        ] {
+            assert!(
+                matches!(validate_capture_region(bad, 1920, 1080), Err(ScreenCaptureError::InvalidRegion(_))),
+                "{:?} should be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn test_frame_interval_timescale_matches_fps() {
+        for fps in [24, 30, 60, 120] {
+            let interval = minimum_frame_interval(fps);
+            assert_eq!(interval.value, 1);
+            assert_eq!(interval.timescale, fps as i32);
+        }
+    }
+
     #[test]
     #[cfg(target_os = "macos")]
     fn test_screencapture_init_requires_permission() {
         // This test verifies that ScreenCapture::new() checks permission
         // If permission is not granted, it should return PermissionDenied error
         // If permission is granted, it should succeed
-        let result = ScreenCapture::new(None);
+        let result = ScreenCapture::new(None, None);
 
         // Either succeeds (permission granted) or returns permission error
         match result {
@@ -1179,13 +2099,14 @@ mod tests {
     #[cfg(target_os = "macos")]
     fn test_capture_single_frame_returns_bytes() {
         // Only run if we can initialize (permission granted)
-        if let Ok(capture) = ScreenCapture::new(None) {
+        if let Ok(capture) = ScreenCapture::new(None, None) {
             let result = capture.capture_single_frame();
 
             match result {
                 Ok(data) => {
-                    // Frame should contain data
+                    // Frame should contain real pixels, not a placeholder buffer
                     assert!(data.len() > 0, "Frame data should not be empty");
+                    assert!(data.iter().any(|&b| b != 0), "Frame data should not be all zeros");
                     info!("Captured frame size: {} bytes", data.len());
                 }
                 Err(e) => {
@@ -1199,10 +2120,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_watchdog_fires_stall_on_frame_starvation_fullscreen() {
+        // Fullscreen mode has no window-closure check, so the watchdog is the only guard
+        let mut watchdog = CaptureWatchdog::new(DEFAULT_STALL_TIMEOUT, false);
+
+        assert_eq!(watchdog.check(std::time::Duration::from_secs(1)), WatchdogAction::Healthy);
+        assert_eq!(watchdog.check(std::time::Duration::from_secs(11)), WatchdogAction::Stalled);
+        assert!(watchdog.is_stalled());
+
+        // Reported once per stall episode
+        assert_eq!(watchdog.check(std::time::Duration::from_secs(12)), WatchdogAction::Healthy);
+
+        // Frames resume, then starve again
+        assert_eq!(watchdog.check(std::time::Duration::from_millis(33)), WatchdogAction::Healthy);
+        assert!(!watchdog.is_stalled());
+        assert_eq!(watchdog.check(std::time::Duration::from_secs(15)), WatchdogAction::Stalled);
+    }
+
+    #[test]
+    fn test_watchdog_auto_stop() {
+        let mut watchdog = CaptureWatchdog::new(std::time::Duration::from_secs(5), true);
+        assert_eq!(watchdog.check(std::time::Duration::from_secs(4)), WatchdogAction::Healthy);
+        assert_eq!(watchdog.check(std::time::Duration::from_secs(6)), WatchdogAction::Stop);
+    }
+
     #[test]
     #[cfg(not(target_os = "macos"))]
     fn test_non_macos_returns_unsupported() {
-        let result = ScreenCapture::new(None);
+        let result = ScreenCapture::new(None, None);
         assert!(matches!(result, Err(ScreenCaptureError::UnsupportedPlatform)));
     }
 
@@ -1210,7 +2156,7 @@ mod tests {
     #[cfg(target_os = "macos")]
     async fn test_continuous_capture_streams_frames() {
         // Only run if permission granted
-        if let Ok(mut capture) = ScreenCapture::new(None) {
+        if let Ok(mut capture) = ScreenCapture::new(None, None) {
             let (tx, mut rx) = tokio::sync::mpsc::channel(30);
 
             // Start continuous capture (without audio)
@@ -1246,7 +2192,7 @@ mod tests {
     #[tokio::test]
     #[cfg(target_os = "macos")]
     async fn test_bounded_channel_prevents_memory_bloat() {
-        if let Ok(mut capture) = ScreenCapture::new(None) {
+        if let Ok(mut capture) = ScreenCapture::new(None, None) {
             // Create small buffer to test backpressure
             let (tx, mut rx) = tokio::sync::mpsc::channel(2);
 
@@ -1307,7 +2253,7 @@ mod tests {
     #[test]
     #[cfg(target_os = "macos")]
     fn test_enable_system_audio() {
-        if let Ok(mut capture) = ScreenCapture::new(None) {
+        if let Ok(mut capture) = ScreenCapture::new(None, None) {
             assert!(!capture.is_system_audio_enabled());
 
             capture.enable_system_audio(48000, 2).unwrap();
@@ -1322,7 +2268,7 @@ mod tests {
     #[test]
     #[cfg(target_os = "macos")]
     fn test_disable_system_audio() {
-        if let Ok(mut capture) = ScreenCapture::new(None) {
+        if let Ok(mut capture) = ScreenCapture::new(None, None) {
             capture.enable_system_audio(48000, 2).unwrap();
             assert!(capture.is_system_audio_enabled());
 
@@ -1334,7 +2280,7 @@ mod tests {
     #[tokio::test]
     #[cfg(target_os = "macos")]
     async fn test_continuous_capture_with_system_audio() {
-        if let Ok(mut capture) = ScreenCapture::new(None) {
+        if let Ok(mut capture) = ScreenCapture::new(None, None) {
             // Enable system audio
             capture.enable_system_audio(48000, 2).unwrap();
 
@@ -1367,4 +2313,50 @@ mod tests {
             assert!(audio_count > 0, "Should receive audio samples");
         }
     }
+
+    #[test]
+    fn test_recording_stats_payload_json_keys() {
+        let start = std::time::Instant::now();
+        let mut tracker = RecordingStatsTracker::new(start);
+        tracker.sample(30, 0, 0, start + std::time::Duration::from_secs(1));
+        let stats = tracker.sample(75, 2, 4, start + std::time::Duration::from_secs(2));
+
+        assert_eq!(
+            serde_json::to_value(stats).unwrap(),
+            serde_json::json!({
+                "framesCaptured": 75,
+                "effectiveFps": 45.0,
+                "consecutiveFailures": 2,
+                "channelBackpressureDrops": 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_pixel_buffer_failure_limit_aborts_with_typed_error() {
+        let abort = CaptureAbort::default();
+        let failures = PixelBufferFailures::new(abort.clone());
+
+        // A success resets the streak before the limit is reached
+        for _ in 0..PIXEL_BUFFER_FAILURE_LIMIT - 1 {
+            failures.record_failure();
+        }
+        assert_eq!(failures.record_success(), PIXEL_BUFFER_FAILURE_LIMIT - 1);
+        assert!(!abort.is_aborted());
+
+        for _ in 0..PIXEL_BUFFER_FAILURE_LIMIT {
+            failures.record_failure();
+        }
+        assert_eq!(
+            abort.reason(),
+            Some(CaptureAbortReason::PixelFormatIncompatible {
+                consecutive_failures: PIXEL_BUFFER_FAILURE_LIMIT
+            })
+        );
+        assert!(matches!(
+            abort.error(),
+            Some(ScreenCaptureError::PixelFormatIncompatible { consecutive_failures: 100 })
+        ));
+    }
+
 }
@@ -39,6 +39,8 @@
 //! ```
 
 use crate::models::timeline::Clip;
+use crate::models::Background;
+use crate::services::ffmpeg::background::pad_color;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
@@ -107,6 +109,9 @@ pub struct SegmentRenderer {
 
     /// Canvas size (default 1920x1080)
     canvas_size: CanvasSize,
+
+    /// Fill for empty segments and letterboxing (default black)
+    background: Background,
 }
 
 impl SegmentRenderer {
@@ -119,6 +124,7 @@ impl SegmentRenderer {
         Self {
             cache_dir,
             canvas_size: CanvasSize::default(),
+            background: Background::default(),
         }
     }
 
@@ -128,6 +134,12 @@ impl SegmentRenderer {
         self
     }
 
+    /// Set the fill for empty segments and letterboxing (see `pad_color`)
+    pub fn with_background(mut self, background: Background) -> Self {
+        self.background = background;
+        self
+    }
+
     /// Generate FFmpeg filter graph for multi-track video composition
     ///
     /// # Arguments
@@ -181,11 +193,11 @@ impl SegmentRenderer {
         Ok(filter_parts.join("; "))
     }
 
-    /// Generate black background filter (AC #4)
+    /// Generate background filter (AC #4)
     fn generate_black_background(&self) -> String {
         format!(
-            "color=black:s={}x{}:d=10[vout]",
-            self.canvas_size.width, self.canvas_size.height
+            "color={}:s={}x{}:d=10[vout]",
+            pad_color(&self.background), self.canvas_size.width, self.canvas_size.height
         )
     }
 
@@ -199,17 +211,18 @@ impl SegmentRenderer {
         // If transform is specified, use it; otherwise scale to canvas with aspect ratio preservation
         if let Some(ref transform) = layer.clip.transform {
             format!(
-                "[{}:v]scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black[vout]",
+                "[{}:v]scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:{}[vout]",
                 input_index,
                 transform.width as u32,
                 transform.height as u32,
                 canvas.width,
-                canvas.height
+                canvas.height,
+                pad_color(&self.background)
             )
         } else {
             format!(
-                "[{}:v]scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black[vout]",
-                input_index, canvas.width, canvas.height, canvas.width, canvas.height
+                "[{}:v]scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:{}[vout]",
+                input_index, canvas.width, canvas.height, canvas.width, canvas.height, pad_color(&self.background)
             )
         }
     }
@@ -243,8 +256,8 @@ impl SegmentRenderer {
         let filter = if input_index == 0 {
             // Bottom layer: Scale to canvas with padding (letterbox/pillarbox)
             format!(
-                "[{}:v]scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black[{}]",
-                input_index, target_w, target_h, canvas.width, canvas.height, label
+                "[{}:v]scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:{}[{}]",
+                input_index, target_w, target_h, canvas.width, canvas.height, pad_color(&self.background), label
             )
         } else {
             // Upper layers: Scale without padding (transparent overlay)
@@ -532,6 +545,9 @@ mod tests {
             muted: None,
             audio_tracks: None,
             transform: None,
+            linked_clip_id: None,
+            group_id: None,
+            transition_out: None,
         }
     }
 
@@ -563,6 +579,22 @@ mod tests {
         assert!(filter.contains("1920x1080"));
     }
 
+    #[test]
+    fn test_background_color_fills_empty_and_letterboxed_segments() {
+        let renderer = SegmentRenderer::new(PathBuf::from("/tmp/cache"))
+            .with_background(Background::Solid { color: "#1E1E1E".to_string() });
+        let canvas_size = CanvasSize { width: 1920, height: 1080 };
+
+        let empty = Segment { video_layers: vec![], start_time: 0, duration: 1000, canvas_size };
+        assert!(renderer.generate_filter_graph(&empty).unwrap().starts_with("color=0x1E1E1E:"));
+
+        let clip = create_test_clip("/path/to/video.mp4", 10000);
+        let single = Segment { video_layers: vec![create_test_layer(clip, 1, 0)], ..empty };
+        let filter = renderer.generate_filter_graph(&single).unwrap();
+        assert!(filter.contains("pad=1920:1080:(ow-iw)/2:(oh-ih)/2:0x1E1E1E[vout]"));
+        assert!(!filter.contains("black"));
+    }
+
     #[test]
     fn test_single_layer_filter() {
         // Single layer should scale to canvas with aspect ratio preservation
@@ -657,6 +689,7 @@ mod tests {
             width: 640.0,
             height: 360.0,
             opacity: 0.5, // Semi-transparent
+            rotation: 0.0,
         });
 
         let layer1 = create_test_layer(clip1, 1, 0);
@@ -690,6 +723,7 @@ mod tests {
             width: 800.0,
             height: 600.0,
             opacity: 1.0,
+            rotation: 0.0,
         });
 
         let layer1 = create_test_layer(clip1, 1, 0);
@@ -759,6 +793,7 @@ mod tests {
             width: 640.0,
             height: 360.0,
             opacity: 1.0,
+            rotation: 0.0,
         });
         let layer2 = create_test_layer(clip1, 1, 0);
         let segment2 = Segment {
@@ -22,6 +22,9 @@
 //! 5. **Progress Updates:** Event-based progress reporting to frontend
 //! 6. **Hash-Based Caching:** Avoid re-rendering unchanged timelines
 //! 7. **Hardware Acceleration:** VideoToolbox on macOS
+//! 8. **Crossfades:** Touching clips with a `Transition` are chained with `xfade`
+//!    (video) and `acrossfade` (audio), then held to fill their timeline slot
+//! 9. **Timeline Audio:** Every track's clip audio is placed with `adelay` and mixed
 //!
 //! # FFmpeg Pipeline Example
 //!
@@ -45,12 +48,19 @@
 //!   -map "[vout]" timeline.mp4
 //! ```
 
-use crate::models::timeline::Timeline;
+use crate::models::timeline::{Clip, Timeline, Track, TrackType, Transition};
+use crate::models::Background;
+use crate::services::ffmpeg::background::pad_color;
+use crate::services::ffmpeg::transition::{
+    build_acrossfade_filter, build_xfade_filter, chain_duration_ms, clamp_transitions, transition_runs,
+};
 use anyhow::{anyhow, Context, Result};
 use ffmpeg_sidecar::command::FfmpegCommand;
 use ffmpeg_sidecar::event::{FfmpegEvent, LogLevel};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use tracing::{debug, info, warn};
@@ -71,9 +81,101 @@ impl Default for CanvasSize {
     }
 }
 
+/// Per-clip look adjustments, applied right after trimming
+///
+/// Shared with clip thumbnails (`clip_frame`) so the inspector preview matches
+/// the export. Returns an empty list when the clip has no adjustments.
+pub fn clip_adjustment_filters(clip: &Clip) -> Vec<String> {
+    let mut filters = Vec::new();
+
+    let rotation = clip
+        .transform
+        .as_ref()
+        .map(|t| t.rotation.rem_euclid(360.0))
+        .unwrap_or(0.0);
+
+    // Right angles use transpose (lossless, swaps dimensions); others rotate with black fill
+    if rotation == 90.0 {
+        filters.push("transpose=clock".to_string());
+    } else if rotation == 180.0 {
+        filters.push("hflip,vflip".to_string());
+    } else if rotation == 270.0 {
+        filters.push("transpose=cclock".to_string());
+    } else if rotation != 0.0 {
+        let radians = rotation.to_radians();
+        filters.push(format!(
+            "rotate={r}:ow=rotw({r}):oh=roth({r}):c=black",
+            r = radians
+        ));
+    }
+
+    filters
+}
+
+/// Per-clip volume and fades for its audio, each prefixed with a comma
+fn clip_audio_filters(clip: &Clip, duration_ms: u64) -> String {
+    let mut filters = String::new();
+    if let Some(volume) = clip.volume.filter(|v| *v != 1.0) {
+        filters.push_str(&format!(",volume={:.3}", volume));
+    }
+    if let Some(fade_in) = clip.fade_in.filter(|ms| *ms > 0) {
+        filters.push_str(&format!(",afade=t=in:st=0:d={:.3}", fade_in.min(duration_ms) as f64 / 1000.0));
+    }
+    if let Some(fade_out) = clip.fade_out.filter(|ms| *ms > 0) {
+        let fade_out = fade_out.min(duration_ms);
+        filters.push_str(&format!(
+            ",afade=t=out:st={:.3}:d={:.3}",
+            (duration_ms - fade_out) as f64 / 1000.0,
+            fade_out as f64 / 1000.0
+        ));
+    }
+    filters
+}
+
+/// Transitions joining the clips of `run`, clamped to fit them
+fn run_transitions(track: &Track, run: &Range<usize>, durations_ms: &[u64]) -> Vec<Transition> {
+    let requested: Vec<Transition> = track.clips[run.start..run.end - 1]
+        .iter()
+        .filter_map(|clip| clip.transition_out.clone())
+        .collect();
+    clamp_transitions(durations_ms, &requested)
+}
+
+/// Whether `path` has at least one audio stream
+fn has_audio_stream(path: &str) -> bool {
+    let output = std::process::Command::new("ffprobe")
+        .args(["-v", "error", "-select_streams", "a", "-show_entries", "stream=index", "-of", "csv=p=0"])
+        .arg(path)
+        .output();
+    match output {
+        Ok(output) if output.status.success() => !String::from_utf8_lossy(&output.stdout).trim().is_empty(),
+        Ok(output) => {
+            warn!("ffprobe failed for {}: {}", path, String::from_utf8_lossy(&output.stderr).trim());
+            false
+        }
+        Err(e) => {
+            warn!("Failed to execute ffprobe for {}: {}", path, e);
+            false
+        }
+    }
+}
+
 /// Progress callback for rendering updates
 pub type ProgressCallback = Arc<Mutex<dyn FnMut(f64) + Send + 'static>>;
 
+/// What rendering a timeline takes
+pub enum RenderJob {
+    /// The timeline is already rendered at this path
+    Cached(PathBuf),
+    /// Running `command` renders the timeline to `output_path`
+    Pending {
+        command: FfmpegCommand,
+        output_path: PathBuf,
+        /// Timeline duration in seconds, for progress
+        duration_secs: f64,
+    },
+}
+
 /// Timeline renderer for full timeline pre-rendering
 pub struct TimelineRenderer {
     /// Cache directory for pre-rendered timelines
@@ -81,6 +183,15 @@ pub struct TimelineRenderer {
 
     /// Canvas size (default 1920x1080)
     canvas_size: CanvasSize,
+
+    /// Output frame rate; chained clips are converted to it for `xfade` (default 30)
+    frame_rate: u32,
+
+    /// Encode losslessly, for renders that are re-encoded afterwards (default false)
+    lossless: bool,
+
+    /// Fill behind and around clips that don't cover the canvas (default black)
+    background: Background,
 }
 
 impl TimelineRenderer {
@@ -93,6 +204,9 @@ impl TimelineRenderer {
         Self {
             cache_dir,
             canvas_size: CanvasSize::default(),
+            frame_rate: 30,
+            lossless: false,
+            background: Background::default(),
         }
     }
 
@@ -102,6 +216,27 @@ impl TimelineRenderer {
         self
     }
 
+    /// Set the output frame rate
+    pub fn with_frame_rate(mut self, frame_rate: u32) -> Self {
+        self.frame_rate = frame_rate.max(1);
+        self
+    }
+
+    /// Encode losslessly (libx264 `-qp 0`) instead of for playback
+    ///
+    /// For intermediates that are re-encoded, so the final encoder's quality
+    /// settings aren't capped by this render. Files are much larger.
+    pub fn with_lossless(mut self, lossless: bool) -> Self {
+        self.lossless = lossless;
+        self
+    }
+
+    /// Set the fill behind and around clips (see `pad_color`)
+    pub fn with_background(mut self, background: Background) -> Self {
+        self.background = background;
+        self
+    }
+
     /// Generate cache key (hash) from timeline structure
     ///
     /// The hash includes:
@@ -109,7 +244,9 @@ impl TimelineRenderer {
     /// - All clip trim points (trim_in, trim_out)
     /// - All clip positions on timeline (start_time)
     /// - All track numbers
-    /// - Canvas size
+    /// - All clip transitions
+    /// - All clip volume, mute and fade settings
+    /// - Canvas size, frame rate, lossless mode and background color
     ///
     /// If any of these change, the cache is invalidated.
     fn generate_cache_key(&self, timeline: &Timeline) -> Result<String> {
@@ -118,6 +255,9 @@ impl TimelineRenderer {
         // Hash canvas size
         self.canvas_size.width.hash(&mut hasher);
         self.canvas_size.height.hash(&mut hasher);
+        self.frame_rate.hash(&mut hasher);
+        self.lossless.hash(&mut hasher);
+        pad_color(&self.background).hash(&mut hasher);
 
         // Hash all tracks and clips
         for track in &timeline.tracks {
@@ -139,7 +279,18 @@ impl TimelineRenderer {
                     transform.width.to_bits().hash(&mut hasher);
                     transform.height.to_bits().hash(&mut hasher);
                     transform.opacity.to_bits().hash(&mut hasher);
+                    transform.rotation.to_bits().hash(&mut hasher);
                 }
+
+                if let Some(ref transition) = clip.transition_out {
+                    transition.kind.hash(&mut hasher);
+                    transition.duration_ms.hash(&mut hasher);
+                }
+
+                clip.volume.map(f64::to_bits).hash(&mut hasher);
+                clip.muted.hash(&mut hasher);
+                clip.fade_in.hash(&mut hasher);
+                clip.fade_out.hash(&mut hasher);
             }
         }
 
@@ -150,7 +301,7 @@ impl TimelineRenderer {
     /// Build FFmpeg filter_complex for timeline rendering
     ///
     /// This generates a complex filter that:
-    /// 1. Creates a black background for the full timeline duration
+    /// 1. Creates the background color for the full timeline duration
     /// 2. Overlays each clip at its exact timeline position
     ///
     /// # Arguments
@@ -176,56 +327,109 @@ impl TimelineRenderer {
 
         let mut filter_parts = Vec::new();
 
-        // Step 1: Create black background for full timeline duration
+        // Step 1: Create background for full timeline duration
+        let fill = pad_color(&self.background);
         let duration_seconds = timeline.total_duration as f64 / 1000.0;
         filter_parts.push(format!(
-            "color=black:s={}x{}:d={}:r=30[bg]",
+            "color={}:s={}x{}:d={}:r={}[bg]",
+            fill,
             self.canvas_size.width,
             self.canvas_size.height,
-            duration_seconds
+            duration_seconds,
+            self.frame_rate
         ));
 
+        // Clips joined by transitions are converted to the output rate, size and format for xfade
+        let chain_format = format!(",fps={},settb=AVTB,format=yuv420p", self.frame_rate);
+
         // Step 2: Prepare all clips (trim and scale)
         let mut clip_labels = Vec::new();
 
         for track in &video_tracks {
-            for (clip_idx, clip) in track.clips.iter().enumerate() {
-                let input_idx = input_map
-                    .get(&(track.track_number, clip_idx))
-                    .ok_or_else(|| {
-                        anyhow!("Missing input index for track {} clip {}", track.track_number, clip_idx)
-                    })?;
+            for run in transition_runs(&track.clips) {
+                // Clips joined by transitions need matching frame rate and size for xfade
+                let in_chain = run.len() > 1;
+                let mut run_labels = Vec::new();
+                let mut run_durations_ms = Vec::new();
+
+                for clip_idx in run.clone() {
+                    let clip = &track.clips[clip_idx];
+                    let input_idx = input_map
+                        .get(&(track.track_number, clip_idx))
+                        .ok_or_else(|| {
+                            anyhow!("Missing input index for track {} clip {}", track.track_number, clip_idx)
+                        })?;
+
+                    let trim_start = clip.trim_in as f64 / 1000.0;
+                    let trim_duration = (clip.trim_out - clip.trim_in) as f64 / 1000.0;
+                    let clip_label = format!("t{}c{}", track.track_number, clip_idx);
+                    let adjustments: String = clip_adjustment_filters(clip)
+                        .iter()
+                        .map(|f| format!(",{}", f))
+                        .collect();
+
+                    // Trim clip to its trim points and reset PTS
+                    if track.track_number == 1 {
+                        // Bottom track: Scale to canvas with padding
+                        filter_parts.push(format!(
+                            "[{}:v]trim=start={}:duration={},setpts=PTS-STARTPTS{},scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:{}{}[{}]",
+                            input_idx, trim_start, trim_duration, adjustments,
+                            self.canvas_size.width, self.canvas_size.height,
+                            self.canvas_size.width, self.canvas_size.height, fill,
+                            if in_chain { chain_format.as_str() } else { "" },
+                            clip_label
+                        ));
+                    } else {
+                        // Upper tracks: Scale for PiP (padded to a fixed size when chained)
+                        let pip_w = self.canvas_size.width / 2;
+                        let pip_h = self.canvas_size.height / 2;
+                        let chain_suffix = if in_chain {
+                            format!(",pad={}:{}:(ow-iw)/2:(oh-ih)/2:{}{}", pip_w, pip_h, fill, chain_format)
+                        } else {
+                            String::new()
+                        };
+                        filter_parts.push(format!(
+                            "[{}:v]trim=start={}:duration={},setpts=PTS-STARTPTS{},scale={}:{}:force_original_aspect_ratio=decrease{}[{}]",
+                            input_idx, trim_start, trim_duration, adjustments, pip_w, pip_h, chain_suffix, clip_label
+                        ));
+                    }
 
-                let trim_start = clip.trim_in as f64 / 1000.0;
-                let trim_duration = (clip.trim_out - clip.trim_in) as f64 / 1000.0;
-                let clip_label = format!("t{}c{}", track.track_number, clip_idx);
+                    run_labels.push(clip_label);
+                    run_durations_ms.push(clip.trim_out - clip.trim_in);
+                }
 
-                // Trim clip to its trim points and reset PTS
-                if track.track_number == 1 {
-                    // Bottom track: Scale to canvas with padding
+                let first_clip = &track.clips[run.start];
+                if in_chain {
+                    // Crossfade the run into one stream placed at the first clip's start
+                    let transitions = run_transitions(track, &run, &run_durations_ms);
+                    let chain_label = format!("t{}x{}", track.track_number, run.start);
+                    let faded_label = format!("{}f", chain_label);
+                    filter_parts.push(build_xfade_filter(&run_labels, &run_durations_ms, &transitions, &faded_label));
+
+                    // Transitions shorten the run; hold its last frame so it still fills its slot
+                    let slot_ms: u64 = run_durations_ms.iter().sum();
+                    let held_ms = slot_ms - chain_duration_ms(&run_durations_ms, &transitions);
                     filter_parts.push(format!(
-                        "[{}:v]trim=start={}:duration={},setpts=PTS-STARTPTS,scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black[{}]",
-                        input_idx, trim_start, trim_duration,
-                        self.canvas_size.width, self.canvas_size.height,
-                        self.canvas_size.width, self.canvas_size.height,
-                        clip_label
+                        "[{}]tpad=stop_mode=clone:stop_duration={:.3}[{}]",
+                        faded_label,
+                        held_ms as f64 / 1000.0,
+                        chain_label
+                    ));
+
+                    clip_labels.push((
+                        chain_label,
+                        first_clip.start_time as f64 / 1000.0,
+                        slot_ms as f64 / 1000.0,
+                        track.track_number,
                     ));
                 } else {
-                    // Upper tracks: Scale for PiP
-                    let pip_w = self.canvas_size.width / 2;
-                    let pip_h = self.canvas_size.height / 2;
-                    filter_parts.push(format!(
-                        "[{}:v]trim=start={}:duration={},setpts=PTS-STARTPTS,scale={}:{}:force_original_aspect_ratio=decrease[{}]",
-                        input_idx, trim_start, trim_duration, pip_w, pip_h, clip_label
+                    clip_labels.push((
+                        run_labels.remove(0),
+                        first_clip.start_time as f64 / 1000.0,  // Timeline start time in seconds
+                        run_durations_ms[0] as f64 / 1000.0,  // Clip duration in seconds
+                        track.track_number,
                     ));
                 }
-
-                clip_labels.push((
-                    clip_label,
-                    clip.start_time as f64 / 1000.0,  // Timeline start time in seconds
-                    trim_duration,  // Clip duration in seconds
-                    track.track_number,
-                ));
             }
         }
 
@@ -270,6 +474,100 @@ impl TimelineRenderer {
         Ok(filter_parts.join("; "))
     }
 
+    /// Build the audio half of the filter_complex, ending in `[aout]`
+    ///
+    /// Each clip's trimmed audio is placed at its timeline position with
+    /// `adelay`; clips joined by transitions are chained with `acrossfade` and
+    /// padded with silence to their slot, like their video. Muted clips and
+    /// clips without an audio stream contribute silence so chains keep their
+    /// timing. The mix is cut to the timeline duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeline` - Timeline to render
+    /// * `input_map` - Map from (track position in `timeline.tracks`, clip_index) to FFmpeg input index
+    /// * `audio_inputs` - FFmpeg input indices that have an audio stream
+    fn generate_audio_filter(
+        &self,
+        timeline: &Timeline,
+        input_map: &HashMap<(usize, usize), usize>,
+        audio_inputs: &HashSet<usize>,
+    ) -> Result<String> {
+        const SILENCE: &str = "anullsrc=r=48000:cl=stereo";
+        let duration_seconds = timeline.total_duration as f64 / 1000.0;
+        let mut filter_parts = Vec::new();
+        let mut placed_labels = Vec::new();
+
+        for (track_pos, track) in timeline.tracks.iter().enumerate() {
+            for run in transition_runs(&track.clips) {
+                let mut run_labels = Vec::new();
+                let mut run_durations_ms = Vec::new();
+
+                for clip_idx in run.clone() {
+                    let clip = &track.clips[clip_idx];
+                    let input_idx = input_map.get(&(track_pos, clip_idx)).ok_or_else(|| {
+                        anyhow!("Missing audio input index for track {} clip {}", track_pos, clip_idx)
+                    })?;
+
+                    let duration_ms = clip.trim_out - clip.trim_in;
+                    let clip_label = format!("a{}c{}", track_pos, clip_idx);
+                    if clip.muted == Some(true) || !audio_inputs.contains(input_idx) {
+                        filter_parts.push(format!(
+                            "{},atrim=duration={:.3}[{}]",
+                            SILENCE,
+                            duration_ms as f64 / 1000.0,
+                            clip_label
+                        ));
+                    } else {
+                        filter_parts.push(format!(
+                            "[{}:a]atrim=start={:.3}:duration={:.3},asetpts=PTS-STARTPTS,aformat=sample_rates=48000:channel_layouts=stereo{}[{}]",
+                            input_idx,
+                            clip.trim_in as f64 / 1000.0,
+                            duration_ms as f64 / 1000.0,
+                            clip_audio_filters(clip, duration_ms),
+                            clip_label
+                        ));
+                    }
+
+                    run_labels.push(clip_label);
+                    run_durations_ms.push(duration_ms);
+                }
+
+                // Crossfade chained clips, padding the overlap so the run fills its slot
+                let (run_label, padding) = if run.len() > 1 {
+                    let transitions = run_transitions(track, &run, &run_durations_ms);
+                    let chain_label = format!("a{}x{}", track_pos, run.start);
+                    filter_parts.push(build_acrossfade_filter(&run_labels, &transitions, &chain_label));
+                    let overlap_ms: u64 = transitions.iter().map(|t| t.duration_ms).sum();
+                    (chain_label, format!("apad=pad_dur={:.3},", overlap_ms as f64 / 1000.0))
+                } else {
+                    (run_labels.remove(0), String::new())
+                };
+
+                let placed_label = format!("a{}r{}", track_pos, run.start);
+                filter_parts.push(format!(
+                    "[{}]{}adelay=delays={}:all=1[{}]",
+                    run_label, padding, track.clips[run.start].start_time, placed_label
+                ));
+                placed_labels.push(placed_label);
+            }
+        }
+
+        if placed_labels.is_empty() {
+            filter_parts.push(format!("{},atrim=duration={}[aout]", SILENCE, duration_seconds));
+        } else {
+            // normalize=0: clips follow each other, so only overlapping sound should add up
+            filter_parts.push(format!(
+                "{}amix=inputs={}:duration=longest:dropout_transition=0:normalize=0,apad,atrim=duration={}[aout]",
+                placed_labels.iter().map(|l| format!("[{}]", l)).collect::<String>(),
+                placed_labels.len(),
+                duration_seconds
+            ));
+        }
+
+        Ok(filter_parts.join("; "))
+    }
+
     /// Build complete FFmpeg command for rendering the timeline
     ///
     /// # Arguments
@@ -286,58 +584,68 @@ impl TimelineRenderer {
         output_path: &Path,
     ) -> Result<(FfmpegCommand, f64)> {
         let mut args = Vec::new();
-        let mut input_map = std::collections::HashMap::new();
-        let mut input_idx = 0;
+        let mut input_map = HashMap::new();
+        let mut audio_input_map = HashMap::new();
+        let mut input_paths: Vec<&str> = Vec::new();
 
         // Add input files for all clips across all video tracks
-        for track in timeline.video_tracks() {
+        for (track_pos, track) in timeline.tracks.iter().enumerate() {
+            if track.track_type != TrackType::Video {
+                continue;
+            }
+            for (clip_idx, clip) in track.clips.iter().enumerate() {
+                input_map.insert((track.track_number, clip_idx), input_paths.len());
+                audio_input_map.insert((track_pos, clip_idx), input_paths.len());
+                input_paths.push(&clip.file_path);
+            }
+        }
+
+        // Audio tracks only contribute audio
+        for (track_pos, track) in timeline.tracks.iter().enumerate() {
+            if track.track_type != TrackType::Audio {
+                continue;
+            }
             for (clip_idx, clip) in track.clips.iter().enumerate() {
-                args.push("-i".to_string());
-                args.push(clip.file_path.clone());
+                audio_input_map.insert((track_pos, clip_idx), input_paths.len());
+                input_paths.push(&clip.file_path);
+            }
+        }
 
-                input_map.insert((track.track_number, clip_idx), input_idx);
-                input_idx += 1;
+        // Inputs without an audio stream can't be referenced as [i:a]
+        let mut probed: HashMap<&str, bool> = HashMap::new();
+        let mut audio_inputs = HashSet::new();
+        for (input_idx, path) in input_paths.iter().enumerate() {
+            args.push("-i".to_string());
+            args.push(path.to_string());
+            if *probed.entry(*path).or_insert_with(|| has_audio_stream(path)) {
+                audio_inputs.insert(input_idx);
             }
         }
 
         // Generate filter_complex
-        let filter_complex = self.generate_filter_complex(timeline, &input_map)?;
+        let filter_complex = format!(
+            "{}; {}",
+            self.generate_filter_complex(timeline, &input_map)?,
+            self.generate_audio_filter(timeline, &audio_input_map, &audio_inputs)?
+        );
         debug!("[TimelineRenderer] filter_complex: {}", filter_complex);
         args.push("-filter_complex".to_string());
         args.push(filter_complex);
         args.push("-map".to_string());
         args.push("[vout]".to_string());
-
-        // Hardware acceleration (macOS)
-        #[cfg(target_os = "macos")]
-        {
-            args.push("-c:v".to_string());
-            args.push("h264_videotoolbox".to_string());
-            debug!("Using VideoToolbox hardware acceleration for timeline rendering");
-        }
-
-        // Fallback to software encoding on other platforms
-        #[cfg(not(target_os = "macos"))]
-        {
-            args.push("-c:v".to_string());
-            args.push("libx264".to_string());
-            args.push("-preset".to_string());
-            args.push("ultrafast".to_string());
+        args.push("-map".to_string());
+        args.push("[aout]".to_string());
+        if self.lossless {
+            args.push("-c:a".to_string());
+            args.push("alac".to_string());
+        } else {
+            args.push("-c:a".to_string());
+            args.push("aac".to_string());
+            args.push("-b:a".to_string());
+            args.push("192k".to_string());
         }
 
-        // Limit thread count to prevent CPU saturation
-        args.push("-threads".to_string());
-        args.push("4".to_string());
-
-        // Encoding settings for good quality
-        args.push("-crf".to_string());
-        args.push("23".to_string());
-
-        // QuickTime/MPV compatibility
-        args.push("-pix_fmt".to_string());
-        args.push("yuv420p".to_string());
-        args.push("-profile:v".to_string());
-        args.push("high".to_string());
+        args.extend(self.video_encoding_args());
         args.push("-movflags".to_string());
         args.push("+faststart".to_string());
 
@@ -356,6 +664,68 @@ impl TimelineRenderer {
         Ok((command, timeline_duration))
     }
 
+    /// Video encoder arguments: lossless x264, or VideoToolbox/x264 for playback
+    fn video_encoding_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if self.lossless {
+            // x264's High profile can't encode losslessly, so no -profile:v here
+            args.extend(["-c:v", "libx264", "-preset", "ultrafast", "-qp", "0"].map(String::from));
+        } else {
+            // Hardware acceleration (macOS)
+            #[cfg(target_os = "macos")]
+            {
+                args.push("-c:v".to_string());
+                args.push("h264_videotoolbox".to_string());
+                debug!("Using VideoToolbox hardware acceleration for timeline rendering");
+            }
+
+            // Fallback to software encoding on other platforms
+            #[cfg(not(target_os = "macos"))]
+            {
+                args.push("-c:v".to_string());
+                args.push("libx264".to_string());
+                args.push("-preset".to_string());
+                args.push("ultrafast".to_string());
+            }
+
+            // Encoding settings for good quality
+            args.push("-crf".to_string());
+            args.push("23".to_string());
+            args.push("-profile:v".to_string());
+            args.push("high".to_string());
+        }
+
+        // Limit thread count to prevent CPU saturation
+        args.push("-threads".to_string());
+        args.push("4".to_string());
+
+        // QuickTime/MPV compatibility
+        args.push("-pix_fmt".to_string());
+        args.push("yuv420p".to_string());
+        args
+    }
+
+    /// Cached render of `timeline`, or the FFmpeg command that renders it
+    ///
+    /// Lets a caller run the FFmpeg process itself (e.g. so it can be cancelled).
+    /// If that run fails or is stopped, the caller must delete `output_path`, or
+    /// the partial file would be served as a cache hit.
+    pub fn prepare_render(&self, timeline: &Timeline) -> Result<RenderJob> {
+        // Generate cache key from timeline hash
+        let cache_key = self.generate_cache_key(timeline)?;
+        let output_path = self.cache_dir.join(format!("{}.mp4", cache_key));
+
+        // Check if cached file already exists
+        if output_path.exists() {
+            info!("Timeline cache hit: {}", output_path.display());
+            return Ok(RenderJob::Cached(output_path));
+        }
+
+        let (command, duration_secs) = self.build_ffmpeg_command(timeline, &output_path)?;
+        Ok(RenderJob::Pending { command, output_path, duration_secs })
+    }
+
     /// Render timeline to cache with progress updates
     ///
     /// # Arguments
@@ -371,25 +741,19 @@ impl TimelineRenderer {
         timeline: &Timeline,
         progress_callback: Option<ProgressCallback>,
     ) -> Result<PathBuf> {
-        // Generate cache key from timeline hash
-        let cache_key = self.generate_cache_key(timeline)?;
-        let output_path = self.cache_dir.join(format!("{}.mp4", cache_key));
-
-        // Check if cached file already exists
-        if output_path.exists() {
-            info!("Timeline cache hit: {}", output_path.display());
-            if let Some(callback) = progress_callback {
-                let mut cb = callback.lock().unwrap();
-                cb(1.0); // Immediately report 100% if using cached file
+        let (mut command, output_path, expected_duration) = match self.prepare_render(timeline)? {
+            RenderJob::Cached(output_path) => {
+                if let Some(callback) = progress_callback {
+                    let mut cb = callback.lock().unwrap();
+                    cb(1.0); // Immediately report 100% if using cached file
+                }
+                return Ok(output_path);
             }
-            return Ok(output_path);
-        }
+            RenderJob::Pending { command, output_path, duration_secs } => (command, output_path, duration_secs),
+        };
 
         info!("Rendering timeline to cache: {}", output_path.display());
 
-        // Build FFmpeg command
-        let (mut command, expected_duration) = self.build_ffmpeg_command(timeline, &output_path)?;
-
         debug!("Expected timeline duration: {}s", expected_duration);
 
         // Spawn FFmpeg process
@@ -511,4 +875,123 @@ mod tests {
         // Same timeline should produce same key
         assert_eq!(key1, key2);
     }
+
+    #[test]
+    fn test_crossfaded_clips_render_as_one_chain() {
+        use crate::models::timeline::TransitionKind;
+        use crate::test_utils::timeline_fixtures::{ClipBuilder, TimelineFixtureBuilder};
+
+        let crossfade = Transition { kind: TransitionKind::Crossfade, duration_ms: 1_000 };
+        let timeline = TimelineFixtureBuilder::new()
+            .add_video_track(vec![
+                ClipBuilder::new("/media/a.mp4", 0, 5_000).with_transition_out(crossfade.clone()).build(),
+                ClipBuilder::new("/media/b.mp4", 5_000, 3_000).build(),
+                // Not touching b, so no transition even though b could carry one
+                ClipBuilder::new("/media/c.mp4", 9_000, 2_000).build(),
+            ])
+            .build();
+
+        let renderer = TimelineRenderer::new(std::env::temp_dir());
+        let input_map: std::collections::HashMap<_, _> = (0..3).map(|i| ((1, i), i)).collect();
+        let filter = renderer.generate_filter_complex(&timeline, &input_map).unwrap();
+
+        assert!(filter.contains("[t1c0][t1c1]xfade=transition=fade:duration=1.000:offset=4.000[t1x0f]"));
+        assert!(filter.contains(",fps=30,settb=AVTB,format=yuv420p[t1c0]"));
+        // The 1s overlap is held on the last frame, so the run still covers 0-8s
+        assert!(filter.contains("[t1x0f]tpad=stop_mode=clone:stop_duration=1.000[t1x0]"));
+        assert!(filter.contains("[bg][t1x0]overlay=x=0:y=0:enable='between(t,0,8)'"));
+        assert!(filter.contains("enable='between(t,9,11)'"));
+        assert_eq!(filter.matches("xfade").count(), 1);
+
+        // Chains follow the output frame rate
+        let filter_60 = TimelineRenderer::new(std::env::temp_dir())
+            .with_frame_rate(60)
+            .generate_filter_complex(&timeline, &input_map)
+            .unwrap();
+        assert!(filter_60.contains(":r=60[bg]"));
+        assert!(filter_60.contains(",fps=60,settb=AVTB"));
+        assert!(!filter_60.contains("fps=30"));
+
+        // Changing the transition invalidates the cached render
+        let mut longer = timeline.clone();
+        longer.tracks[0].clips[0].transition_out = Some(Transition { duration_ms: 2_000, ..crossfade });
+        assert_ne!(
+            renderer.generate_cache_key(&timeline).unwrap(),
+            renderer.generate_cache_key(&longer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_lossless_render_for_reencoding() {
+        use crate::test_utils::timeline_fixtures::{ClipBuilder, TimelineFixtureBuilder};
+
+        let timeline = TimelineFixtureBuilder::new()
+            .add_video_track(vec![ClipBuilder::new("/media/a.mp4", 0, 5_000).build()])
+            .build();
+        let renderer = TimelineRenderer::new(std::env::temp_dir());
+        let lossless = TimelineRenderer::new(std::env::temp_dir()).with_lossless(true);
+
+        let args = lossless.video_encoding_args();
+        assert!(args.windows(2).any(|w| w == ["-qp", "0"]));
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx264"]));
+        assert!(!args.iter().any(|a| a == "-crf" || a == "-profile:v"));
+        assert!(renderer.video_encoding_args().windows(2).any(|w| w == ["-crf", "23"]));
+
+        // A lossless render never reuses a playback render
+        assert_ne!(
+            renderer.generate_cache_key(&timeline).unwrap(),
+            lossless.generate_cache_key(&timeline).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_background_color_fills_canvas_and_letterboxing() {
+        use crate::test_utils::timeline_fixtures::{ClipBuilder, TimelineFixtureBuilder};
+
+        let timeline = TimelineFixtureBuilder::new()
+            .add_video_track(vec![ClipBuilder::new("/media/a.mp4", 0, 5_000).build()])
+            .build();
+        let input_map: std::collections::HashMap<_, _> = [((1, 0), 0)].into_iter().collect();
+        let renderer = TimelineRenderer::new(std::env::temp_dir())
+            .with_background(Background::Solid { color: "#FFFFFF".to_string() });
+
+        let filter = renderer.generate_filter_complex(&timeline, &input_map).unwrap();
+        assert!(filter.starts_with("color=0xFFFFFF:s=1920x1080:"));
+        assert!(filter.contains("pad=1920:1080:(ow-iw)/2:(oh-ih)/2:0xFFFFFF[t1c0]"));
+        assert!(!filter.contains("black"));
+
+        assert_ne!(
+            renderer.generate_cache_key(&timeline).unwrap(),
+            TimelineRenderer::new(std::env::temp_dir()).generate_cache_key(&timeline).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_crossfaded_clips_crossfade_audio_in_their_slot() {
+        use crate::models::timeline::TransitionKind;
+        use crate::test_utils::timeline_fixtures::{ClipBuilder, TimelineFixtureBuilder};
+
+        let crossfade = Transition { kind: TransitionKind::Crossfade, duration_ms: 1_000 };
+        let timeline = TimelineFixtureBuilder::new()
+            .add_video_track(vec![
+                ClipBuilder::new("/media/a.mp4", 0, 5_000).with_transition_out(crossfade).build(),
+                ClipBuilder::new("/media/b.mp4", 5_000, 3_000).build(),
+            ])
+            .add_audio_track(vec![ClipBuilder::new("/media/music.mp3", 2_000, 4_000).with_volume(0.5).build()])
+            .build();
+
+        let renderer = TimelineRenderer::new(std::env::temp_dir());
+        let input_map: HashMap<_, _> = [((0, 0), 0), ((0, 1), 1), ((1, 0), 2)].into_iter().collect();
+        // b.mp4 has no audio stream
+        let audio_inputs: HashSet<usize> = [0, 2].into_iter().collect();
+        let filter = renderer.generate_audio_filter(&timeline, &input_map, &audio_inputs).unwrap();
+
+        assert!(filter.contains("[0:a]atrim=start=0.000:duration=5.000,asetpts=PTS-STARTPTS"));
+        assert!(filter.contains("anullsrc=r=48000:cl=stereo,atrim=duration=3.000[a0c1]"));
+        assert!(filter.contains("[a0c0][a0c1]acrossfade=d=1.000[a0x0]"));
+        assert!(filter.contains("[a0x0]apad=pad_dur=1.000,adelay=delays=0:all=1[a0r0]"));
+        assert!(filter.contains("volume=0.500[a1c0]"));
+        assert!(filter.contains("[a1c0]adelay=delays=2000:all=1[a1r0]"));
+        assert!(filter.ends_with("[a0r0][a1r0]amix=inputs=2:duration=longest:dropout_transition=0:normalize=0,apad,atrim=duration=8[aout]"));
+    }
 }
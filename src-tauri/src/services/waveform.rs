@@ -0,0 +1,353 @@
+//! Multi-resolution waveform peaks
+//!
+//! Audio is decoded by FFmpeg to mono f32 and reduced to max-abs peaks. Peaks
+//! are cached per media file at power-of-two detail levels (mipmap-style):
+//! detail `n` has `BASE_PEAKS_PER_SECOND << n` peaks per second.
+//!
+//! - A request for a level that's cached is served as-is.
+//! - A coarser level is derived from the nearest finer cached level. Max-abs
+//!   peaks downsample exactly, so no decode is needed.
+//! - A finer level than anything cached is refined from a fresh decode.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncReadExt;
+
+/// Decode sample rate (2^9 × 25, so every detail level has whole samples per peak)
+pub const WAVEFORM_DECODE_SAMPLE_RATE: u32 = 12_800;
+
+/// Peaks per second at detail 0 (whole-timeline overview)
+pub const BASE_PEAKS_PER_SECOND: u32 = 25;
+
+/// Finest detail level (6400 peaks/s, 2 samples per peak)
+pub const MAX_WAVEFORM_DETAIL: u8 = 8;
+
+/// Cached peaks across all media before other files are evicted (~64MB)
+const MAX_CACHED_PEAKS: usize = 16 * 1024 * 1024;
+
+lazy_static::lazy_static! {
+    static ref WAVEFORM_CACHE: Mutex<WaveformCache> = Mutex::new(WaveformCache::default());
+}
+
+/// Waveform peaks for a media file at one detail level
+///
+/// Matches the frontend `WaveformData` type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WaveformData {
+    /// Max-abs peak per bucket (0.0 to 1.0)
+    pub peaks: Vec<f32>,
+
+    /// Peaks per second
+    pub sample_rate: u32,
+
+    /// Audio duration in milliseconds
+    pub duration: u64,
+
+    /// Peaks are computed from a mono mixdown
+    pub channels: u16,
+
+    /// ISO 8601 timestamp of when the waveform was generated
+    pub generated_at: String,
+
+    /// Detail level the peaks were produced at
+    pub detail: u8,
+}
+
+/// Peaks per second for a detail level
+pub fn peaks_per_second(detail: u8) -> u32 {
+    BASE_PEAKS_PER_SECOND << detail.min(MAX_WAVEFORM_DETAIL)
+}
+
+/// Streaming max-abs peak reducer over mono samples
+#[derive(Debug)]
+pub struct PeakAccumulator {
+    samples_per_peak: usize,
+    current_peak: f32,
+    current_samples: usize,
+    total_samples: u64,
+    peaks: Vec<f32>,
+}
+
+impl PeakAccumulator {
+    pub fn new(detail: u8) -> Self {
+        Self {
+            samples_per_peak: (WAVEFORM_DECODE_SAMPLE_RATE / peaks_per_second(detail)) as usize,
+            current_peak: 0.0,
+            current_samples: 0,
+            total_samples: 0,
+            peaks: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            self.current_peak = self.current_peak.max(sample.abs().min(1.0));
+            self.current_samples += 1;
+            if self.current_samples == self.samples_per_peak {
+                self.peaks.push(self.current_peak);
+                self.current_peak = 0.0;
+                self.current_samples = 0;
+            }
+        }
+        self.total_samples += samples.len() as u64;
+    }
+
+    /// Peaks (a trailing partial bucket is kept) and duration in ms
+    pub fn finish(mut self) -> (Vec<f32>, u64) {
+        if self.current_samples > 0 {
+            self.peaks.push(self.current_peak);
+        }
+        let duration_ms = self.total_samples * 1000 / WAVEFORM_DECODE_SAMPLE_RATE as u64;
+        (self.peaks, duration_ms)
+    }
+}
+
+/// Reduce peaks by `factor`, keeping the max of each group
+pub fn downsample_peaks(peaks: &[f32], factor: usize) -> Vec<f32> {
+    peaks
+        .chunks(factor.max(1))
+        .map(|group| group.iter().copied().fold(0.0, f32::max))
+        .collect()
+}
+
+/// How a waveform request can be served from the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheLookup {
+    /// The requested level is cached
+    Exact,
+    /// Derive from the nearest finer cached level
+    Coarsen { from_detail: u8 },
+    /// Nothing fine enough is cached; decode the audio again
+    Refine,
+}
+
+#[derive(Debug, Default)]
+struct CachedMedia {
+    duration_ms: u64,
+    levels: BTreeMap<u8, Arc<Vec<f32>>>,
+}
+
+/// Peak levels keyed by media and detail
+#[derive(Debug, Default)]
+pub struct WaveformCache {
+    media: HashMap<String, CachedMedia>,
+}
+
+impl WaveformCache {
+    pub fn lookup(&self, media_key: &str, detail: u8) -> CacheLookup {
+        let Some(cached) = self.media.get(media_key) else {
+            return CacheLookup::Refine;
+        };
+        match cached.levels.range(detail..).next() {
+            Some((&level, _)) if level == detail => CacheLookup::Exact,
+            Some((&level, _)) => CacheLookup::Coarsen { from_detail: level },
+            None => CacheLookup::Refine,
+        }
+    }
+
+    /// Peaks and duration for a level, coarsening (and caching) if needed
+    ///
+    /// Returns `None` when the level must be refined from a decode.
+    pub fn get(&mut self, media_key: &str, detail: u8) -> Option<(Arc<Vec<f32>>, u64)> {
+        let lookup = self.lookup(media_key, detail);
+        let cached = self.media.get_mut(media_key)?;
+
+        match lookup {
+            CacheLookup::Exact => Some((cached.levels[&detail].clone(), cached.duration_ms)),
+            CacheLookup::Coarsen { from_detail } => {
+                let factor = 1usize << (from_detail - detail);
+                let peaks = Arc::new(downsample_peaks(&cached.levels[&from_detail], factor));
+                cached.levels.insert(detail, peaks.clone());
+                Some((peaks, cached.duration_ms))
+            }
+            CacheLookup::Refine => None,
+        }
+    }
+
+    pub fn insert(&mut self, media_key: &str, detail: u8, peaks: Vec<f32>, duration_ms: u64) -> Arc<Vec<f32>> {
+        let peaks = Arc::new(peaks);
+        let cached = self.media.entry(media_key.to_string()).or_default();
+        cached.duration_ms = duration_ms;
+        cached.levels.insert(detail, peaks.clone());
+
+        // Keep the file being worked on; drop everything else when over budget
+        if self.total_peaks() > MAX_CACHED_PEAKS {
+            self.media.retain(|key, _| key == media_key);
+        }
+
+        peaks
+    }
+
+    fn total_peaks(&self) -> usize {
+        self.media
+            .values()
+            .flat_map(|cached| cached.levels.values())
+            .map(|peaks| peaks.len())
+            .sum()
+    }
+}
+
+/// Cache key for a media file
+///
+/// Includes size and modification time so replaced files are decoded again.
+fn media_key(path: &Path) -> String {
+    let (len, modified) = std::fs::metadata(path)
+        .map(|m| (m.len(), m.modified().ok()))
+        .unwrap_or((0, None));
+    format!("{}|{}|{:?}", path.display(), len, modified)
+}
+
+/// Decode a file's audio and reduce it to peaks at `detail`
+async fn decode_peaks(path: &Path, detail: u8) -> Result<(Vec<f32>, u64)> {
+    let mut child = tokio::process::Command::new("ffmpeg")
+        .arg("-v")
+        .arg("error")
+        .arg("-i")
+        .arg(path)
+        .arg("-vn")
+        .arg("-ac")
+        .arg("1")
+        .arg("-ar")
+        .arg(WAVEFORM_DECODE_SAMPLE_RATE.to_string())
+        .arg("-f")
+        .arg("f32le")
+        .arg("pipe:1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to execute ffmpeg")?;
+
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("FFmpeg stdout not available"))?;
+
+    let mut accumulator = PeakAccumulator::new(detail);
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut carry: Vec<u8> = Vec::with_capacity(4);
+    let mut samples: Vec<f32> = Vec::with_capacity(buffer.len() / 4);
+
+    loop {
+        let read = stdout
+            .read(&mut buffer)
+            .await
+            .context("Failed to read decoded audio")?;
+        if read == 0 {
+            break;
+        }
+
+        carry.extend_from_slice(&buffer[..read]);
+        let whole = carry.len() - carry.len() % 4;
+        samples.clear();
+        samples.extend(
+            carry[..whole]
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        );
+        carry.drain(..whole);
+        accumulator.push(&samples);
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .context("Failed to wait for ffmpeg")?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "FFmpeg audio decode failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(accumulator.finish())
+}
+
+/// Waveform peaks for a file at a detail level (clamped to `MAX_WAVEFORM_DETAIL`)
+pub async fn extract_waveform(path: &Path, detail: u8) -> Result<WaveformData> {
+    let detail = detail.min(MAX_WAVEFORM_DETAIL);
+    let key = media_key(path);
+
+    let cached = WAVEFORM_CACHE.lock().unwrap().get(&key, detail);
+    let refined = cached.is_none();
+    let (peaks, duration_ms) = match cached {
+        Some(hit) => hit,
+        None => {
+            let (peaks, duration_ms) = decode_peaks(path, detail).await?;
+            let peaks = WAVEFORM_CACHE
+                .lock()
+                .unwrap()
+                .insert(&key, detail, peaks, duration_ms);
+            (peaks, duration_ms)
+        }
+    };
+
+    tracing::info!(
+        event = "waveform_extracted",
+        path = %path.display(),
+        detail = detail,
+        peaks = peaks.len(),
+        refined = refined,
+        "Waveform peaks ready"
+    );
+
+    Ok(WaveformData {
+        peaks: peaks.as_ref().clone(),
+        sample_rate: peaks_per_second(detail),
+        duration: duration_ms,
+        channels: 1,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        detail,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finer_detail_refines_and_coarser_reuses_cache() {
+        let mut cache = WaveformCache::default();
+        let key = "/media/a.mp4|1024|None";
+        cache.insert(key, 3, vec![0.1, 0.4, 0.2, 0.3, 0.9, 0.0, 0.5, 0.6], 40);
+
+        // Finer than anything cached: must decode again
+        assert_eq!(cache.lookup(key, 5), CacheLookup::Refine);
+        assert!(cache.get(key, 5).is_none());
+        assert_eq!(cache.lookup("/media/other.mp4", 0), CacheLookup::Refine);
+
+        // Coarser: derived from the cached level and stored
+        assert_eq!(cache.lookup(key, 1), CacheLookup::Coarsen { from_detail: 3 });
+        let (peaks, duration_ms) = cache.get(key, 1).unwrap();
+        assert_eq!(*peaks, vec![0.4, 0.9]);
+        assert_eq!(duration_ms, 40);
+        assert_eq!(cache.lookup(key, 1), CacheLookup::Exact);
+
+        // Once refined, the finer level serves everything in between
+        cache.insert(key, 5, vec![0.0; 32], 40);
+        assert_eq!(cache.lookup(key, 4), CacheLookup::Coarsen { from_detail: 5 });
+    }
+
+    #[test]
+    fn test_peak_accumulator_buckets_across_chunks() {
+        let detail = MAX_WAVEFORM_DETAIL;
+        let mut accumulator = PeakAccumulator::new(detail);
+        accumulator.push(&[0.1, -0.8, 0.3]);
+        accumulator.push(&[0.2, -0.5]);
+        let (peaks, _) = accumulator.finish();
+
+        // Two samples per peak at max detail; trailing sample kept
+        assert_eq!(peaks, vec![0.8, 0.3, 0.5]);
+
+        // One second of audio at detail 0
+        let mut accumulator = PeakAccumulator::new(0);
+        accumulator.push(&vec![0.25; WAVEFORM_DECODE_SAMPLE_RATE as usize]);
+        let (peaks, duration_ms) = accumulator.finish();
+        assert_eq!(peaks.len(), BASE_PEAKS_PER_SECOND as usize);
+        assert_eq!(duration_ms, 1000);
+    }
+}
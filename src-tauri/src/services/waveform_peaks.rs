@@ -0,0 +1,321 @@
+//! Min/max waveform peaks for timeline clips
+//!
+//! Audio of an imported media file is decoded by FFmpeg to mono f32 and
+//! reduced to one (min, max) pair per bucket at a requested number of buckets
+//! per second. Unlike the max-abs peaks in `waveform`, min/max pairs keep the
+//! shape of asymmetric signals, which is what the timeline draws.
+//!
+//! Peaks are cached on disk keyed by media ID and resolution, so reopening a
+//! project or zooming back to a resolution that was already drawn doesn't
+//! decode the audio again.
+
+use crate::models::MediaFile;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncReadExt;
+
+/// Highest supported resolution (matches the finest `waveform` detail level)
+pub const MAX_PEAK_PAIRS_PER_SECOND: u32 = 6_400;
+
+/// Lowest decode sample rate; the actual rate is a whole multiple of the resolution
+const MIN_DECODE_SAMPLE_RATE: u32 = 12_800;
+
+/// Min/max waveform peaks for a media file at one resolution
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WaveformPeaks {
+    /// (min, max) sample per bucket, each in -1.0..=1.0
+    pub peaks: Vec<(f32, f32)>,
+
+    /// Buckets per second
+    pub samples_per_second: u32,
+
+    /// False when the media has no audio track (`peaks` is then empty)
+    pub has_audio: bool,
+}
+
+/// Samples per bucket and decode sample rate for a resolution
+///
+/// The audio is resampled to a whole multiple of `samples_per_second` so
+/// every bucket covers the same number of samples.
+pub fn decode_layout(samples_per_second: u32) -> (usize, u32) {
+    let samples_per_peak = MIN_DECODE_SAMPLE_RATE.div_ceil(samples_per_second.max(1));
+    (samples_per_peak as usize, samples_per_second.max(1) * samples_per_peak)
+}
+
+/// Streaming (min, max) reducer over mono samples
+#[derive(Debug)]
+pub struct MinMaxAccumulator {
+    samples_per_peak: usize,
+    current: Option<(f32, f32)>,
+    current_samples: usize,
+    peaks: Vec<(f32, f32)>,
+}
+
+impl MinMaxAccumulator {
+    pub fn new(samples_per_peak: usize) -> Self {
+        Self {
+            samples_per_peak: samples_per_peak.max(1),
+            current: None,
+            current_samples: 0,
+            peaks: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            let sample = sample.clamp(-1.0, 1.0);
+            let (min, max) = self.current.unwrap_or((sample, sample));
+            self.current = Some((min.min(sample), max.max(sample)));
+            self.current_samples += 1;
+            if self.current_samples == self.samples_per_peak {
+                self.peaks.extend(self.current.take());
+                self.current_samples = 0;
+            }
+        }
+    }
+
+    /// Peaks, keeping a trailing partial bucket
+    pub fn finish(mut self) -> Vec<(f32, f32)> {
+        self.peaks.extend(self.current.take());
+        self.peaks
+    }
+}
+
+/// Cached peaks path for a media file at a resolution
+pub fn peaks_cache_path(cache_dir: &Path, media_id: &str, samples_per_second: u32) -> PathBuf {
+    cache_dir.join(format!("media-{}-{}sps.json", media_id, samples_per_second))
+}
+
+/// Whether `path` has at least one audio stream
+async fn has_audio_stream(path: &Path) -> Result<bool> {
+    let output = tokio::process::Command::new("ffprobe")
+        .args([
+            "-v", "error",
+            "-select_streams", "a",
+            "-show_entries", "stream=index",
+            "-of", "csv=p=0",
+        ])
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to execute ffprobe")?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "FFprobe failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+}
+
+/// Decode the first audio track of `path` and reduce it to (min, max) pairs
+async fn decode_min_max_peaks(path: &Path, samples_per_second: u32) -> Result<Vec<(f32, f32)>> {
+    let (samples_per_peak, decode_rate) = decode_layout(samples_per_second);
+
+    let mut child = tokio::process::Command::new("ffmpeg")
+        .arg("-v")
+        .arg("error")
+        .arg("-i")
+        .arg(path)
+        .args(["-map", "0:a:0", "-vn", "-ac", "1"])
+        .arg("-ar")
+        .arg(decode_rate.to_string())
+        .args(["-f", "f32le", "pipe:1"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to execute ffmpeg")?;
+
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("FFmpeg stdout not available"))?;
+
+    let mut accumulator = MinMaxAccumulator::new(samples_per_peak);
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut carry: Vec<u8> = Vec::with_capacity(4);
+    let mut samples: Vec<f32> = Vec::with_capacity(buffer.len() / 4);
+
+    loop {
+        let read = stdout
+            .read(&mut buffer)
+            .await
+            .context("Failed to read decoded audio")?;
+        if read == 0 {
+            break;
+        }
+
+        carry.extend_from_slice(&buffer[..read]);
+        let whole = carry.len() - carry.len() % 4;
+        samples.clear();
+        samples.extend(
+            carry[..whole]
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        );
+        carry.drain(..whole);
+        accumulator.push(&samples);
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .context("Failed to wait for ffmpeg")?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "FFmpeg audio decode failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(accumulator.finish())
+}
+
+/// Compute (or load from cache) min/max peaks of `media` at `samples_per_second`
+///
+/// # Returns
+/// * `Ok(WaveformPeaks)` - Peaks; empty with `has_audio: false` for media without audio
+/// * `Err(anyhow::Error)` - The resolution is out of range or FFmpeg could not decode the audio
+pub async fn extract_waveform_peaks(
+    media: &MediaFile,
+    samples_per_second: u32,
+    cache_dir: &Path,
+) -> Result<WaveformPeaks> {
+    if samples_per_second == 0 || samples_per_second > MAX_PEAK_PAIRS_PER_SECOND {
+        return Err(anyhow::anyhow!(
+            "Waveform resolution must be 1-{} samples per second, got {}",
+            MAX_PEAK_PAIRS_PER_SECOND,
+            samples_per_second
+        ));
+    }
+
+    let cache_path = peaks_cache_path(cache_dir, &media.id, samples_per_second);
+    if let Ok(cached) = std::fs::read(&cache_path) {
+        match serde_json::from_slice::<WaveformPeaks>(&cached) {
+            Ok(peaks) => return Ok(peaks),
+            Err(e) => tracing::warn!(
+                event = "waveform_peaks_cache_corrupt",
+                path = %cache_path.display(),
+                error = %e,
+                "Ignoring unreadable cached peaks"
+            ),
+        }
+    }
+
+    let path = Path::new(&media.file_path);
+    let peaks = if has_audio_stream(path).await? {
+        WaveformPeaks {
+            peaks: decode_min_max_peaks(path, samples_per_second).await?,
+            samples_per_second,
+            has_audio: true,
+        }
+    } else {
+        WaveformPeaks { peaks: Vec::new(), samples_per_second, has_audio: false }
+    };
+
+    std::fs::create_dir_all(cache_dir).context("Failed to create waveforms directory")?;
+    std::fs::write(&cache_path, serde_json::to_vec(&peaks)?).context("Failed to write cached peaks")?;
+
+    tracing::info!(
+        event = "waveform_peaks_extracted",
+        media_id = %media.id,
+        samples_per_second,
+        peaks = peaks.peaks.len(),
+        has_audio = peaks.has_audio,
+        "Waveform peaks ready"
+    );
+
+    Ok(peaks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Resolution;
+
+    fn media(file_path: &Path) -> MediaFile {
+        MediaFile {
+            id: "media-1".to_string(),
+            file_path: file_path.to_string_lossy().to_string(),
+            filename: "tone.wav".to_string(),
+            duration: 1_000,
+            resolution: Resolution { width: 0, height: 0 },
+            file_size: 0,
+            codec: "pcm_s16le".to_string(),
+            thumbnail: None,
+            imported_at: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_min_max_accumulator_sine_is_symmetric() {
+        // Whole number of samples per bucket for every supported resolution
+        for samples_per_second in [1, 7, 100, 3_000, MAX_PEAK_PAIRS_PER_SECOND] {
+            let (samples_per_peak, decode_rate) = decode_layout(samples_per_second);
+            assert_eq!(decode_rate, samples_per_peak as u32 * samples_per_second);
+            assert!(decode_rate >= MIN_DECODE_SAMPLE_RATE);
+        }
+
+        // One second of a 100 Hz sine at 12.8 kHz, pushed in uneven chunks
+        let (samples_per_peak, decode_rate) = decode_layout(10);
+        let sine: Vec<f32> = (0..decode_rate)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 100.0 * i as f32 / decode_rate as f32).sin())
+            .collect();
+        let mut accumulator = MinMaxAccumulator::new(samples_per_peak);
+        for chunk in sine.chunks(1_000) {
+            accumulator.push(chunk);
+        }
+        let peaks = accumulator.finish();
+
+        assert_eq!(peaks.len(), 10);
+        for (min, max) in peaks {
+            assert!((max - 0.5).abs() < 1e-3, "max {}", max);
+            assert!((min + max).abs() < 1e-3, "({}, {}) not symmetric", min, max);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sine_peaks_are_symmetric_and_cached() {
+        let ffmpeg_available = std::process::Command::new("ffmpeg")
+            .arg("-version")
+            .output()
+            .is_ok_and(|o| o.status.success());
+        if !ffmpeg_available {
+            println!("Skipping waveform peaks test - ffmpeg not available");
+            return;
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("tone.wav");
+        let status = std::process::Command::new("ffmpeg")
+            .args(["-v", "error", "-f", "lavfi", "-i", "sine=frequency=440:sample_rate=48000:duration=1"])
+            .args(["-af", "volume=4", "-y"])
+            .arg(&source)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let cache_dir = dir.path().join("waveforms");
+        let media = media(&source);
+        let first = extract_waveform_peaks(&media, 20, &cache_dir).await.unwrap();
+        assert!(first.has_audio);
+        assert_eq!(first.peaks.len(), 20);
+        for &(min, max) in &first.peaks {
+            assert!(max > 0.4, "max {}", max);
+            assert!((min + max).abs() < 0.02, "({}, {}) not symmetric", min, max);
+        }
+        assert!(peaks_cache_path(&cache_dir, &media.id, 20).exists());
+
+        // With the source gone, only the cache can answer
+        std::fs::remove_file(&source).unwrap();
+        let second = extract_waveform_peaks(&media, 20, &cache_dir).await.unwrap();
+        assert_eq!(first, second);
+
+        assert!(extract_waveform_peaks(&media, 0, &cache_dir).await.is_err());
+    }
+}
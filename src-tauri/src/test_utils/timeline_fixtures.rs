@@ -2,7 +2,7 @@
 //!
 //! Provides builders for creating test timeline configurations.
 
-use crate::models::timeline::{AudioTrack, Clip, ClipTransform, Timeline, Track, TrackType};
+use crate::models::timeline::{AudioTrack, Clip, ClipTransform, Timeline, Track, TrackType, Transition};
 use anyhow::{Context, Result};
 use std::path::Path;
 use uuid::Uuid;
@@ -91,6 +91,9 @@ pub struct ClipBuilder {
     muted: Option<bool>,
     audio_tracks: Option<Vec<AudioTrack>>,
     transform: Option<ClipTransform>,
+    linked_clip_id: Option<String>,
+    group_id: Option<String>,
+    transition_out: Option<Transition>,
 }
 
 impl ClipBuilder {
@@ -109,9 +112,18 @@ impl ClipBuilder {
             muted: None,
             audio_tracks: None,
             transform: None,
+            linked_clip_id: None,
+            group_id: None,
+            transition_out: None,
         }
     }
 
+    /// Set the clip ID (random by default)
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
     /// Set trim points
     pub fn with_trim(mut self, trim_in: u64, trim_out: u64) -> Self {
         self.trim_in = trim_in;
@@ -155,6 +167,24 @@ impl ClipBuilder {
         self
     }
 
+    /// Link to another clip (as after an audio detach)
+    pub fn with_linked_clip(mut self, clip_id: impl Into<String>) -> Self {
+        self.linked_clip_id = Some(clip_id.into());
+        self
+    }
+
+    /// Put the clip in a group
+    pub fn with_group(mut self, group_id: impl Into<String>) -> Self {
+        self.group_id = Some(group_id.into());
+        self
+    }
+
+    /// Add a transition into the next clip
+    pub fn with_transition_out(mut self, transition: Transition) -> Self {
+        self.transition_out = Some(transition);
+        self
+    }
+
     /// Build the clip
     pub fn build(self) -> Clip {
         Clip {
@@ -170,6 +200,9 @@ impl ClipBuilder {
             muted: self.muted,
             audio_tracks: self.audio_tracks,
             transform: self.transform,
+            linked_clip_id: self.linked_clip_id,
+            group_id: self.group_id,
+            transition_out: self.transition_out,
         }
     }
 }
@@ -213,6 +246,7 @@ pub fn create_multi_track_timeline(
             width: 320.0,
             height: 180.0,
             opacity: 1.0,
+            rotation: 0.0,
         })
         .build();
 
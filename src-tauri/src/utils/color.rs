@@ -0,0 +1,101 @@
+//! Pixel format conversion
+//!
+//! Capture backends deliver BGRA; the frontend preview wants packed RGB.
+//! Conversion runs on every preview frame, so it works four pixels at a time
+//! over `chunks_exact` (no bounds checks in the inner loop, and a shape the
+//! compiler auto-vectorizes) instead of pushing one byte at a time.
+
+/// Bytes per BGRA pixel
+const BGRA_BPP: usize = 4;
+
+/// Bytes per RGB pixel
+const RGB_BPP: usize = 3;
+
+/// Pixels converted per unrolled step
+const PIXELS_PER_STEP: usize = 4;
+
+/// Convert packed BGRA to packed RGB, dropping alpha
+///
+/// Trailing bytes that don't make up a whole pixel are ignored.
+pub fn bgra_to_rgb(data: &[u8]) -> Vec<u8> {
+    let pixels = data.len() / BGRA_BPP;
+    let mut rgb = vec![0u8; pixels * RGB_BPP];
+
+    let src = data[..pixels * BGRA_BPP].chunks_exact(BGRA_BPP * PIXELS_PER_STEP);
+    let src_tail = src.remainder();
+    let mut dst = rgb.chunks_exact_mut(RGB_BPP * PIXELS_PER_STEP);
+
+    for (s, d) in src.zip(&mut dst) {
+        d[0] = s[2];
+        d[1] = s[1];
+        d[2] = s[0];
+        d[3] = s[6];
+        d[4] = s[5];
+        d[5] = s[4];
+        d[6] = s[10];
+        d[7] = s[9];
+        d[8] = s[8];
+        d[9] = s[14];
+        d[10] = s[13];
+        d[11] = s[12];
+    }
+
+    // Up to three pixels left over
+    let dst_tail = dst.into_remainder();
+    for (s, d) in src_tail
+        .chunks_exact(BGRA_BPP)
+        .zip(dst_tail.chunks_exact_mut(RGB_BPP))
+    {
+        d[0] = s[2];
+        d[1] = s[1];
+        d[2] = s[0];
+    }
+
+    rgb
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_bgra_to_rgb(data: &[u8]) -> Vec<u8> {
+        let mut rgb = Vec::new();
+        for chunk in data.chunks(4) {
+            if chunk.len() == 4 {
+                rgb.extend_from_slice(&[chunk[2], chunk[1], chunk[0]]);
+            }
+        }
+        rgb
+    }
+
+    #[test]
+    fn test_bgra_to_rgb_matches_naive_reference() {
+        let data: Vec<u8> = (0..=255u8).cycle().take(4 * 37 + 3).collect();
+
+        // Every length exercises a different unrolled/tail split, including a partial pixel
+        for len in 0..data.len() {
+            assert_eq!(bgra_to_rgb(&data[..len]), naive_bgra_to_rgb(&data[..len]), "len {}", len);
+        }
+
+        // One blue pixel (B=255) becomes R=0, G=0, B=255
+        assert_eq!(bgra_to_rgb(&[255, 0, 0, 255]), vec![0, 0, 255]);
+    }
+
+    #[test]
+    fn test_bgra_to_rgb_converts_1080p_frame() {
+        let (width, height) = (1920usize, 1080usize);
+        let frame: Vec<u8> = (0..width * height)
+            .flat_map(|i| [(i % 256) as u8, 0x80, 0xFF, 0xFF])
+            .collect();
+
+        let start = std::time::Instant::now();
+        let rgb = bgra_to_rgb(&frame);
+        let elapsed = start.elapsed();
+
+        assert_eq!(rgb.len(), width * height * 3);
+        assert_eq!(&rgb[..6], &[0xFF, 0x80, 0x00, 0xFF, 0x80, 0x01]);
+        assert_eq!(&rgb[rgb.len() - 3..], &[0xFF, 0x80, ((width * height - 1) % 256) as u8]);
+        // Generous bound so debug builds pass; release converts in a few milliseconds
+        assert!(elapsed < std::time::Duration::from_secs(2), "1080p conversion took {:?}", elapsed);
+    }
+}
@@ -0,0 +1,149 @@
+//! Free disk space checks for recording commands
+//!
+//! Running out of space mid-recording truncates the file, so recordings refuse
+//! to start unless the output and scratch volumes have room for at least the
+//! configured floor or about a minute of recording, whichever is larger.
+
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+
+/// H.264 bits per pixel per frame assumed when estimating recording size
+///
+/// Screen content usually encodes well below this; the estimate errs on the
+/// side of refusing too early rather than filling the disk.
+const VIDEO_BITS_PER_PIXEL: f64 = 0.15;
+
+/// Raw s16le 48 kHz stereo, as each audio source is written to scratch while recording
+const PCM_BYTES_PER_SECOND: u64 = 48_000 * 2 * 2;
+
+/// Not enough free space to start a recording
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Not enough disk space in {path}: {} MB free, {} MB required. Free up space or choose a different folder", .available_bytes / (1024 * 1024), .required_bytes / (1024 * 1024))]
+pub struct InsufficientDiskSpace {
+    pub path: String,
+    pub available_bytes: u64,
+    pub required_bytes: u64,
+}
+
+/// Free bytes available to the current user on the volume holding `dir`
+pub fn available_bytes(dir: &Path) -> io::Result<u64> {
+    #[cfg(unix)]
+    {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = CString::new(dir.as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        // SAFETY: `path` is a valid NUL-terminated string and `stat` is
+        // zero-initialized memory of the right size; statvfs only writes to it.
+        unsafe {
+            let mut stat: libc::statvfs = std::mem::zeroed();
+            if libc::statvfs(path.as_ptr(), &mut stat) == 0 {
+                // Convert to u64 to avoid overflow on 32-bit fields
+                Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = dir;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Disk space check only supported on Unix",
+        ))
+    }
+}
+
+/// Estimated bytes written per minute of recording
+///
+/// `audio_tracks` counts the raw PCM sources (system audio, microphone)
+/// written to scratch alongside the video.
+pub fn estimate_bytes_per_minute(width: u32, height: u32, fps: u32, audio_tracks: u32) -> u64 {
+    let video_bits_per_second = width as f64 * height as f64 * fps as f64 * VIDEO_BITS_PER_PIXEL;
+    let video_bytes = (video_bits_per_second / 8.0 * 60.0) as u64;
+    video_bytes + audio_tracks as u64 * PCM_BYTES_PER_SECOND * 60
+}
+
+/// Free space a recording needs to start: the floor, or one estimated minute if larger
+pub fn required_free_bytes(bytes_per_minute: u64, min_free_space_mb: u64) -> u64 {
+    bytes_per_minute.max(min_free_space_mb.saturating_mul(1024 * 1024))
+}
+
+/// Check that every directory in `dirs` has at least `required_bytes` free
+///
+/// A directory whose free space can't be read is skipped with a warning:
+/// failing to measure is not a reason to block recording.
+pub fn ensure_free_space(dirs: &[&Path], required_bytes: u64) -> Result<(), InsufficientDiskSpace> {
+    for dir in dirs {
+        match available_bytes(dir) {
+            Ok(available) if available < required_bytes => {
+                tracing::error!(
+                    event = "disk_space_preflight_failed",
+                    path = %dir.display(),
+                    available_bytes = available,
+                    required_bytes
+                );
+                return Err(InsufficientDiskSpace {
+                    path: dir.display().to_string(),
+                    available_bytes: available,
+                    required_bytes,
+                });
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(
+                    event = "disk_space_preflight_skipped",
+                    path = %dir.display(),
+                    error = %e,
+                    "Could not read free space, not blocking the recording"
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_bytes_per_minute_for_known_formats() {
+        let mb = |bytes: u64| bytes / (1024 * 1024);
+
+        // 1080p30 H.264 without audio: tens of MB per minute
+        let hd = estimate_bytes_per_minute(1920, 1080, 30, 0);
+        assert!((50..=100).contains(&mb(hd)), "1080p30 estimated at {} MB/min", mb(hd));
+
+        // Twice the frame rate, twice the video estimate; 720p is smaller
+        assert_eq!(estimate_bytes_per_minute(1920, 1080, 60, 0), hd * 2);
+        assert!(estimate_bytes_per_minute(1280, 720, 30, 0) < hd);
+
+        // Each PCM source adds about 11 MB per minute
+        let with_audio = estimate_bytes_per_minute(1920, 1080, 30, 2);
+        assert_eq!(with_audio - hd, 2 * 48_000 * 4 * 60);
+
+        // The floor wins unless a minute of recording needs more
+        assert_eq!(required_free_bytes(hd, 500), 500 * 1024 * 1024);
+        assert_eq!(required_free_bytes(hd, 10), hd);
+
+        // An absurd floor saturates instead of overflowing
+        assert_eq!(required_free_bytes(hd, u64::MAX), u64::MAX);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ensure_free_space_refuses_when_below_requirement() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(ensure_free_space(&[dir.path()], 1).is_ok());
+
+        let err = ensure_free_space(&[dir.path()], u64::MAX).unwrap_err();
+        assert_eq!(err.required_bytes, u64::MAX);
+        assert!(err.to_string().starts_with("Not enough disk space"));
+    }
+}
@@ -1,8 +1,131 @@
-use crate::models::{MediaFile, Resolution};
+use crate::models::{MediaFile, MediaMetadata, Resolution};
 use anyhow::{Context, Result};
 use base64::{engine::general_purpose, Engine as _};
 use std::path::Path;
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Attempts at downloading FFmpeg on startup before giving up
+pub const FFMPEG_DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Wait before the second download attempt; doubled for each later attempt
+pub const FFMPEG_DOWNLOAD_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// Shown by recording commands when FFmpeg could not be set up on startup
+pub const FFMPEG_UNAVAILABLE_MESSAGE: &str =
+    "FFmpeg is not available, so recording is disabled. Check your internet connection and restart Clippy to download it again.";
+
+/// Set when FFmpeg could not be downloaded on startup
+static FFMPEG_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+/// Record whether FFmpeg failed to initialize
+pub fn set_ffmpeg_unavailable(unavailable: bool) {
+    FFMPEG_UNAVAILABLE.store(unavailable, Ordering::SeqCst);
+}
+
+/// Fail fast with a friendly message if FFmpeg could not be set up on startup
+pub fn ensure_ffmpeg_ready() -> std::result::Result<(), String> {
+    if FFMPEG_UNAVAILABLE.load(Ordering::SeqCst) {
+        return Err(FFMPEG_UNAVAILABLE_MESSAGE.to_string());
+    }
+    Ok(())
+}
+
+/// Run `operation` up to `attempts` times, waiting with exponential backoff between tries
+///
+/// Waits `base_delay`, then twice that, and so on. `sleep` is injected so
+/// tests don't wait. Returns the last error if every attempt fails.
+pub fn retry_with_backoff<T, E: std::fmt::Display>(
+    attempts: u32,
+    base_delay: Duration,
+    mut sleep: impl FnMut(Duration),
+    mut operation: impl FnMut(u32) -> std::result::Result<T, E>,
+) -> std::result::Result<T, E> {
+    let mut delay = base_delay;
+    let mut attempt = 1;
+    loop {
+        match operation(attempt) {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts => {
+                tracing::warn!(
+                    event = "retry_backoff",
+                    attempt,
+                    attempts,
+                    delay_ms = delay.as_millis() as u64,
+                    error = %e,
+                    "Attempt failed, retrying"
+                );
+                sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Parse an ffprobe frame rate like `30000/1001`; `0/0` (unknown) is `None`
+fn parse_frame_rate(rate: &str) -> Option<f64> {
+    let (num, den) = rate.split_once('/').unwrap_or((rate, "1"));
+    let (num, den) = (num.parse::<f64>().ok()?, den.parse::<f64>().ok()?);
+    (num > 0.0 && den > 0.0).then_some(num / den)
+}
+
+/// Parse `ffprobe -print_format json -show_format -show_streams` output
+///
+/// Only the container duration is required; files without a video stream
+/// (audio-only) have no dimensions, frame rate or video codec.
+pub fn parse_probe_output(json: &str) -> Result<MediaMetadata> {
+    let parsed: serde_json::Value = serde_json::from_str(json).context("Failed to parse ffprobe output")?;
+    let streams = parsed["streams"].as_array().map(Vec::as_slice).unwrap_or_default();
+    let stream_of = |kind: &str| streams.iter().find(|s| s["codec_type"].as_str() == Some(kind));
+    let video = stream_of("video");
+    let audio = stream_of("audio");
+
+    let duration_secs = parsed["format"]["duration"]
+        .as_str()
+        .and_then(|d| d.parse::<f64>().ok())
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse duration"))?;
+
+    Ok(MediaMetadata {
+        duration_secs,
+        width: video.and_then(|v| v["width"].as_u64()).map(|w| w as u32),
+        height: video.and_then(|v| v["height"].as_u64()).map(|h| h as u32),
+        fps: video.and_then(|v| {
+            ["avg_frame_rate", "r_frame_rate"]
+                .iter()
+                .find_map(|key| v[*key].as_str().and_then(parse_frame_rate))
+        }),
+        video_codec: video.and_then(|v| v["codec_name"].as_str()).map(str::to_string),
+        audio_codec: audio.and_then(|a| a["codec_name"].as_str()).map(str::to_string),
+        has_audio: audio.is_some(),
+        bitrate: parsed["format"]["bit_rate"].as_str().and_then(|b| b.parse().ok()),
+    })
+}
+
+/// Probe a media file's streams with the bundled ffprobe
+///
+/// # Returns
+/// * `Ok(MediaMetadata)` - Duration, video and audio stream details
+/// * `Err(anyhow::Error)` - ffprobe failed or the output could not be parsed
+pub async fn probe_media(file_path: &Path) -> Result<MediaMetadata> {
+    let output = tokio::process::Command::new(ffmpeg_sidecar::ffprobe::ffprobe_path())
+        .args(["-v", "error", "-print_format", "json", "-show_format", "-show_streams"])
+        .arg(file_path)
+        .output()
+        .await
+        .context("Failed to execute ffprobe")?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "FFprobe failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    parse_probe_output(&String::from_utf8_lossy(&output.stdout))
+}
 
 /// Extract video metadata using FFmpeg
 ///
@@ -286,6 +409,78 @@ mod tests {
         let _ = std::fs::remove_file(&test_file);
     }
 
+    #[test]
+    fn test_retry_with_backoff_retries_until_success() {
+        let mut sleeps = Vec::new();
+        let mut calls = 0;
+        let result = retry_with_backoff(3, Duration::from_secs(2), |d| sleeps.push(d), |attempt| {
+            calls += 1;
+            if attempt < 3 {
+                Err(format!("connection reset (attempt {})", attempt))
+            } else {
+                Ok("ffmpeg ready")
+            }
+        });
+
+        assert_eq!(result, Ok("ffmpeg ready"));
+        assert_eq!(calls, 3);
+        assert_eq!(sleeps, [Duration::from_secs(2), Duration::from_secs(4)]);
+
+        // Out of attempts: the last error is returned without a final wait
+        let mut sleeps = Vec::new();
+        let result: std::result::Result<(), String> =
+            retry_with_backoff(2, Duration::from_millis(10), |d| sleeps.push(d), |attempt| Err(format!("attempt {}", attempt)));
+        assert_eq!(result, Err("attempt 2".to_string()));
+        assert_eq!(sleeps, [Duration::from_millis(10)]);
+    }
+
+    #[test]
+    fn test_parse_probe_output_video_and_audio_only() {
+        // Trimmed from `ffprobe -print_format json -show_format -show_streams` on a screen recording
+        let recording = r#"{
+            "streams": [
+                {
+                    "index": 0, "codec_name": "h264", "codec_type": "video",
+                    "width": 1920, "height": 1080,
+                    "r_frame_rate": "60/1", "avg_frame_rate": "30000/1001"
+                },
+                {
+                    "index": 1, "codec_name": "aac", "codec_type": "audio",
+                    "sample_rate": "48000", "channels": 2,
+                    "r_frame_rate": "0/0", "avg_frame_rate": "0/0"
+                }
+            ],
+            "format": {
+                "format_name": "mov,mp4,m4a,3gp,3g2,mj2",
+                "duration": "12.512000", "size": "7823412", "bit_rate": "5002181"
+            }
+        }"#;
+
+        let metadata = parse_probe_output(recording).unwrap();
+        assert_eq!(metadata.duration_secs, 12.512);
+        assert_eq!((metadata.width, metadata.height), (Some(1920), Some(1080)));
+        assert!((metadata.fps.unwrap() - 29.97).abs() < 0.01);
+        assert_eq!(metadata.video_codec.as_deref(), Some("h264"));
+        assert_eq!(metadata.audio_codec.as_deref(), Some("aac"));
+        assert!(metadata.has_audio);
+        assert_eq!(metadata.bitrate, Some(5_002_181));
+
+        // Audio-only: no video fields, but not an error
+        let voice_memo = r#"{
+            "streams": [{ "index": 0, "codec_name": "mp3", "codec_type": "audio", "avg_frame_rate": "0/0" }],
+            "format": { "duration": "3.000000" }
+        }"#;
+        let metadata = parse_probe_output(voice_memo).unwrap();
+        assert_eq!(metadata.width, None);
+        assert_eq!(metadata.fps, None);
+        assert_eq!(metadata.video_codec, None);
+        assert_eq!(metadata.audio_codec.as_deref(), Some("mp3"));
+        assert!(metadata.has_audio);
+        assert_eq!(metadata.bitrate, None);
+
+        assert!(parse_probe_output(r#"{ "streams": [], "format": {} }"#).is_err());
+    }
+
     // Integration test with a real video file would go here
     // This would require creating a minimal valid MP4 file for testing
 }
@@ -1,4 +1,9 @@
+pub mod color;
+pub mod disk_space;
 pub mod ffmpeg;
+pub mod output_dir;
+pub mod scratch;
+pub mod wav;
 
 /// Format a duration in seconds into a human-readable string (HH:MM:SS)
 pub fn format_duration(seconds: u64) -> String {
@@ -0,0 +1,118 @@
+//! Output directory preparation for recording and export commands
+//!
+//! Creating the output folder is the first thing that fails when a user picks
+//! a folder the app can't write to. The underlying `io::Error` is classified
+//! so the UI can say why and suggest choosing a different folder, instead of
+//! surfacing a raw OS message.
+
+use serde::Serialize;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+
+/// Why an output directory could not be prepared
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum OutputDirError {
+    /// The app isn't allowed to write there (macOS folder privacy, file permissions)
+    #[error("Permission denied for {path}. Choose a different folder, or allow Clippy access in System Settings → Privacy & Security → Files and Folders")]
+    PermissionDenied { path: String },
+
+    /// The volume is mounted read-only (disk image, locked volume)
+    #[error("{path} is on a read-only volume. Choose a different folder")]
+    ReadOnlyFilesystem { path: String },
+
+    /// A parent folder is missing and could not be created (e.g. an unmounted drive)
+    #[error("{path} does not exist. Choose a different folder or reconnect the drive")]
+    NotFound { path: String },
+
+    /// Anything else, with the OS message
+    #[error("Failed to prepare output folder {path}: {message}")]
+    Other { path: String, message: String },
+}
+
+impl OutputDirError {
+    /// Classify an I/O error from creating or writing to `path`
+    pub fn from_io(path: &Path, err: &io::Error) -> Self {
+        let path = path.display().to_string();
+        match err.kind() {
+            io::ErrorKind::PermissionDenied => OutputDirError::PermissionDenied { path },
+            io::ErrorKind::ReadOnlyFilesystem => OutputDirError::ReadOnlyFilesystem { path },
+            io::ErrorKind::NotFound => OutputDirError::NotFound { path },
+            _ => OutputDirError::Other {
+                path,
+                message: err.to_string(),
+            },
+        }
+    }
+
+    /// The serialized `kind` tag, for errors that embed this one
+    pub fn kind(&self) -> &'static str {
+        match self {
+            OutputDirError::PermissionDenied { .. } => "permissionDenied",
+            OutputDirError::ReadOnlyFilesystem { .. } => "readOnlyFilesystem",
+            OutputDirError::NotFound { .. } => "notFound",
+            OutputDirError::Other { .. } => "other",
+        }
+    }
+
+    /// The folder that could not be prepared
+    pub fn path(&self) -> &str {
+        match self {
+            OutputDirError::PermissionDenied { path }
+            | OutputDirError::ReadOnlyFilesystem { path }
+            | OutputDirError::NotFound { path }
+            | OutputDirError::Other { path, .. } => path,
+        }
+    }
+}
+
+/// Create `dir` (and parents) if needed
+pub fn prepare_output_dir(dir: &Path) -> Result<(), OutputDirError> {
+    std::fs::create_dir_all(dir).map_err(|e| {
+        let error = OutputDirError::from_io(dir, &e);
+        tracing::error!(event = "output_dir_prepare_failed", path = %dir.display(), error = %e);
+        error
+    })
+}
+
+/// Create the directory an output file will be written into
+pub fn prepare_output_path(file_path: &Path) -> Result<(), OutputDirError> {
+    match file_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => prepare_output_dir(parent),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_error_kinds_map_to_typed_variants() {
+        let path = Path::new("/Volumes/Backup/clips");
+        let classify = |kind: io::ErrorKind| OutputDirError::from_io(path, &io::Error::from(kind));
+
+        assert!(matches!(classify(io::ErrorKind::PermissionDenied), OutputDirError::PermissionDenied { .. }));
+        assert!(matches!(classify(io::ErrorKind::ReadOnlyFilesystem), OutputDirError::ReadOnlyFilesystem { .. }));
+        assert!(matches!(classify(io::ErrorKind::NotFound), OutputDirError::NotFound { .. }));
+        assert!(matches!(classify(io::ErrorKind::StorageFull), OutputDirError::Other { .. }));
+
+        // Messages name the folder and tell the user what to do
+        let message = classify(io::ErrorKind::PermissionDenied).to_string();
+        assert!(message.contains("/Volumes/Backup/clips"));
+        assert!(message.contains("Choose a different folder"));
+    }
+
+    #[test]
+    fn test_prepare_output_path_creates_parent() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("nested").join("out.mp4");
+
+        prepare_output_path(&file_path).unwrap();
+        assert!(file_path.parent().unwrap().is_dir());
+
+        // Bare file names write to the working directory
+        assert!(prepare_output_path(Path::new("out.mp4")).is_ok());
+    }
+}
@@ -0,0 +1,106 @@
+//! Scratch space for recording and export intermediates
+//!
+//! Video-only encodes, PCM audio and PiP temp tracks are written to a local
+//! scratch directory (default: the user's caches folder) instead of next to the
+//! final output, which may be a slow network or removable drive. Only the
+//! finished file is written to (or moved into) the output directory.
+
+use crate::utils::output_dir::{prepare_output_dir, OutputDirError};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Default scratch directory: `<caches>/com.clippy.app/scratch`
+pub fn default_scratch_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("com.clippy.app")
+        .join("scratch")
+}
+
+/// Configured scratch directory, or the default when unset or blank
+pub fn resolve_scratch_dir(configured: Option<&str>) -> PathBuf {
+    match configured.map(str::trim) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => default_scratch_dir(),
+    }
+}
+
+/// Resolve and create the scratch directory
+pub fn prepare_scratch_dir(configured: Option<&str>) -> Result<PathBuf, OutputDirError> {
+    let dir = resolve_scratch_dir(configured);
+    prepare_output_dir(&dir)?;
+    Ok(dir)
+}
+
+/// Intermediate file for a recording or export, e.g. `recording-<id>-audio.pcm`
+pub fn scratch_file(scratch_dir: &Path, stem: &str, suffix: &str) -> PathBuf {
+    scratch_dir.join(format!("{}-{}", stem, suffix))
+}
+
+/// Move a finished intermediate into the output directory
+///
+/// Renames when both paths are on the same volume; otherwise copies and
+/// removes the intermediate (scratch and output are often different drives).
+pub fn move_into_output(intermediate: &Path, output: &Path) -> io::Result<()> {
+    if std::fs::rename(intermediate, output).is_ok() {
+        return Ok(());
+    }
+
+    std::fs::copy(intermediate, output)?;
+    if let Err(e) = std::fs::remove_file(intermediate) {
+        tracing::warn!(
+            event = "scratch_cleanup_failed",
+            path = %intermediate.display(),
+            error = %e,
+            "Failed to remove intermediate after copying to output"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dir_entries(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_intermediates_stay_in_scratch_and_only_final_reaches_output() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let scratch_root = tempfile::tempdir().unwrap();
+        let scratch_dir =
+            prepare_scratch_dir(Some(scratch_root.path().join("scratch").to_str().unwrap())).unwrap();
+
+        let video_only = scratch_file(&scratch_dir, "recording-abc", "video.mp4");
+        let pcm = scratch_file(&scratch_dir, "recording-abc", "audio.pcm");
+        assert!(video_only.starts_with(&scratch_dir));
+        assert!(pcm.starts_with(&scratch_dir));
+
+        std::fs::write(&video_only, b"video").unwrap();
+        std::fs::write(&pcm, b"audio").unwrap();
+        assert!(dir_entries(output_dir.path()).is_empty());
+
+        // Finalize: promote the encode, drop the audio intermediate
+        let final_path = output_dir.path().join("recording-abc.mp4");
+        move_into_output(&video_only, &final_path).unwrap();
+        std::fs::remove_file(&pcm).unwrap();
+
+        assert_eq!(dir_entries(output_dir.path()), vec!["recording-abc.mp4"]);
+        assert!(dir_entries(&scratch_dir).is_empty());
+        assert_eq!(std::fs::read(&final_path).unwrap(), b"video");
+    }
+
+    #[test]
+    fn test_blank_scratch_setting_uses_default() {
+        assert_eq!(resolve_scratch_dir(None), default_scratch_dir());
+        assert_eq!(resolve_scratch_dir(Some("  ")), default_scratch_dir());
+        assert_eq!(resolve_scratch_dir(Some("/Volumes/Fast/scratch")), PathBuf::from("/Volumes/Fast/scratch"));
+    }
+}
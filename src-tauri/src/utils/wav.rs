@@ -0,0 +1,296 @@
+//! Minimal WAV writing and reading
+//!
+//! `WavWriter` streams 16-bit PCM to disk while recording and patches the
+//! header sizes on finalize. `WavReader` parses the RIFF/`fmt `/`data` chunks
+//! back so recordings can be checked (sample rate, channels, sample values)
+//! without going through FFmpeg.
+
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// WAV format tag for integer PCM
+const FORMAT_PCM: u16 = 1;
+
+/// WAV format tag for IEEE float samples
+const FORMAT_IEEE_FLOAT: u16 = 3;
+
+/// Errors reading a WAV file
+#[derive(Error, Debug)]
+pub enum WavError {
+    #[error("Failed to read WAV file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Malformed WAV file: {0}")]
+    Malformed(String),
+
+    #[error("Unsupported WAV format: {0}")]
+    Unsupported(String),
+}
+
+/// Simple WAV file writer for audio samples
+///
+/// Writes audio samples to a WAV file incrementally during recording.
+/// The WAV header is written at the start with placeholder values,
+/// then updated with actual sizes when finalized.
+pub struct WavWriter {
+    file: std::fs::File,
+    sample_rate: u32,
+    channels: u16,
+    samples_written: u64,
+}
+
+impl WavWriter {
+    /// Create new WAV file and write initial header
+    pub fn new(path: PathBuf, sample_rate: u32, channels: u16) -> std::io::Result<Self> {
+        let mut file = std::fs::File::create(path)?;
+
+        // Write WAV header with placeholder sizes (will update later)
+        Self::write_wav_header(&mut file, sample_rate, channels, 0)?;
+
+        Ok(Self {
+            file,
+            sample_rate,
+            channels,
+            samples_written: 0,
+        })
+    }
+
+    /// Write audio samples to file
+    pub fn write_samples(&mut self, samples: &[f32]) -> std::io::Result<()> {
+        use std::io::Write;
+
+        // Convert f32 samples to i16 PCM
+        for sample in samples {
+            let pcm_sample = (*sample * 32767.0).clamp(-32768.0, 32767.0) as i16;
+            self.file.write_all(&pcm_sample.to_le_bytes())?;
+        }
+
+        self.samples_written += samples.len() as u64;
+        Ok(())
+    }
+
+    /// Finalize WAV file by updating header with actual sizes
+    pub fn finalize(mut self) -> std::io::Result<()> {
+        use std::io::{Seek, SeekFrom, Write};
+
+        // Seek back to start and write correct header
+        self.file.seek(SeekFrom::Start(0))?;
+        Self::write_wav_header(&mut self.file, self.sample_rate, self.channels, self.samples_written)?;
+        self.file.flush()?;
+
+        Ok(())
+    }
+
+    /// Write WAV file header
+    fn write_wav_header(file: &mut std::fs::File, sample_rate: u32, channels: u16, total_samples: u64) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let bytes_per_sample = 2u16; // i16 PCM
+        let byte_rate = sample_rate * channels as u32 * bytes_per_sample as u32;
+        let block_align = channels * bytes_per_sample;
+        let data_size = (total_samples * bytes_per_sample as u64) as u32;
+        let file_size = 36 + data_size;
+
+        // RIFF header
+        file.write_all(b"RIFF")?;
+        file.write_all(&file_size.to_le_bytes())?;
+        file.write_all(b"WAVE")?;
+
+        // fmt chunk
+        file.write_all(b"fmt ")?;
+        file.write_all(&16u32.to_le_bytes())?; // chunk size
+        file.write_all(&FORMAT_PCM.to_le_bytes())?;
+        file.write_all(&channels.to_le_bytes())?;
+        file.write_all(&sample_rate.to_le_bytes())?;
+        file.write_all(&byte_rate.to_le_bytes())?;
+        file.write_all(&block_align.to_le_bytes())?;
+        file.write_all(&(bytes_per_sample * 8).to_le_bytes())?; // bits per sample
+
+        // data chunk
+        file.write_all(b"data")?;
+        file.write_all(&data_size.to_le_bytes())?;
+
+        Ok(())
+    }
+}
+
+/// WAV file reader for 16-bit PCM and 32-bit float files
+///
+/// The whole file is read into memory; recordings checked this way are short.
+pub struct WavReader {
+    sample_rate: u32,
+    channels: u16,
+    format: u16,
+    bits_per_sample: u16,
+    data: Vec<u8>,
+}
+
+impl WavReader {
+    /// Read and parse a WAV file
+    pub fn open(path: &Path) -> Result<Self, WavError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Parse WAV file contents
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WavError> {
+        if bytes.len() < 12 {
+            return Err(WavError::Malformed(format!("{} bytes is too short for a RIFF header", bytes.len())));
+        }
+        if &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(WavError::Malformed("missing RIFF/WAVE magic".to_string()));
+        }
+
+        // (format, channels, sample rate, bits per sample)
+        let mut fmt: Option<(u16, u16, u32, u16)> = None;
+        let mut offset = 12;
+        while offset + 8 <= bytes.len() {
+            let id = &bytes[offset..offset + 4];
+            let size = u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap()) as usize;
+            let body_start = offset + 8;
+            let body_end = body_start
+                .checked_add(size)
+                .filter(|&end| end <= bytes.len())
+                .ok_or_else(|| {
+                    WavError::Malformed(format!(
+                        "'{}' chunk of {} bytes runs past the end of the file",
+                        String::from_utf8_lossy(id),
+                        size
+                    ))
+                })?;
+            let body = &bytes[body_start..body_end];
+
+            match id {
+                b"fmt " => {
+                    if body.len() < 16 {
+                        return Err(WavError::Malformed(format!("fmt chunk is {} bytes, expected 16", body.len())));
+                    }
+                    let u16_at = |i: usize| u16::from_le_bytes([body[i], body[i + 1]]);
+                    let sample_rate = u32::from_le_bytes(body[4..8].try_into().unwrap());
+                    fmt = Some((u16_at(0), u16_at(2), sample_rate, u16_at(14)));
+                }
+                b"data" => {
+                    let (format, channels, sample_rate, bits_per_sample) = fmt.ok_or_else(|| {
+                        WavError::Malformed("data chunk before fmt chunk".to_string())
+                    })?;
+                    match (format, bits_per_sample) {
+                        (FORMAT_PCM, 16) | (FORMAT_IEEE_FLOAT, 32) => {}
+                        _ => {
+                            return Err(WavError::Unsupported(format!(
+                                "format {} with {} bits per sample",
+                                format, bits_per_sample
+                            )))
+                        }
+                    }
+                    if channels == 0 || sample_rate == 0 {
+                        return Err(WavError::Malformed(format!(
+                            "{} channels at {} Hz",
+                            channels, sample_rate
+                        )));
+                    }
+                    return Ok(Self {
+                        sample_rate,
+                        channels,
+                        format,
+                        bits_per_sample,
+                        data: body.to_vec(),
+                    });
+                }
+                _ => {}
+            }
+
+            // Chunks are padded to an even size
+            offset = body_end + (size % 2);
+        }
+
+        Err(WavError::Malformed("no data chunk".to_string()))
+    }
+
+    /// Sample rate in Hz
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Channel count (1 = mono, 2 = stereo)
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Number of samples across all channels
+    pub fn sample_count(&self) -> usize {
+        self.data.len() / (self.bits_per_sample as usize / 8)
+    }
+
+    /// Decoded samples in -1.0..=1.0, interleaved by channel
+    pub fn samples(&self) -> impl Iterator<Item = f32> + '_ {
+        let bytes_per_sample = self.bits_per_sample as usize / 8;
+        let format = self.format;
+        self.data.chunks_exact(bytes_per_sample).map(move |sample| {
+            if format == FORMAT_IEEE_FLOAT {
+                f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]])
+            } else {
+                i16::from_le_bytes([sample[0], sample[1]]) as f32 / 32768.0
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(channels: u16, sample_rate: u32, samples: &[f32]) -> WavReader {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audio.wav");
+
+        let mut writer = WavWriter::new(path.clone(), sample_rate, channels).unwrap();
+        writer.write_samples(samples).unwrap();
+        writer.finalize().unwrap();
+
+        WavReader::open(&path).unwrap()
+    }
+
+    #[test]
+    fn test_wav_round_trip_mono_and_stereo() {
+        // 10ms of a 440 Hz sine
+        let sine: Vec<f32> = (0..480)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 48_000.0).sin())
+            .collect();
+
+        let mono = round_trip(1, 48_000, &sine);
+        assert_eq!(mono.sample_rate(), 48_000);
+        assert_eq!(mono.channels(), 1);
+        assert_eq!(mono.sample_count(), sine.len());
+        for (read, written) in mono.samples().zip(&sine) {
+            assert!((read - written).abs() < 1e-4, "{} vs {}", read, written);
+        }
+
+        // Interleaved L/R frames: left is the sine, right is silent
+        let stereo_samples: Vec<f32> = sine.iter().flat_map(|&s| [s, 0.0]).collect();
+        let stereo = round_trip(2, 44_100, &stereo_samples);
+        assert_eq!(stereo.sample_rate(), 44_100);
+        assert_eq!(stereo.channels(), 2);
+        assert_eq!(stereo.sample_count(), stereo_samples.len());
+        assert!(stereo.samples().skip(1).step_by(2).all(|s| s == 0.0));
+    }
+
+    #[test]
+    fn test_wav_reader_rejects_malformed_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audio.wav");
+        let mut writer = WavWriter::new(path.clone(), 48_000, 2).unwrap();
+        writer.write_samples(&[0.25; 64]).unwrap();
+        writer.finalize().unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+
+        // Truncated inside the header, and inside the data chunk
+        assert!(matches!(WavReader::from_bytes(&bytes[..8]), Err(WavError::Malformed(_))));
+        assert!(matches!(WavReader::from_bytes(&bytes[..bytes.len() - 10]), Err(WavError::Malformed(_))));
+
+        // Wrong magic
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0..4].copy_from_slice(b"RIFX");
+        assert!(matches!(WavReader::from_bytes(&wrong_magic), Err(WavError::Malformed(_))));
+
+        assert!(WavReader::from_bytes(&bytes).is_ok());
+    }
+}
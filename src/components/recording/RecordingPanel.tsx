@@ -45,6 +45,7 @@ import {
   sendRecordingNotification,
 } from '@/lib/tauri/recording';
 import { importMedia } from '@/lib/tauri/media';
+import type { RecordingStopError } from '@/types/recording';
 import { Monitor, Clock, Camera } from 'lucide-react';
 
 export interface RecordingPanelProps {
@@ -392,7 +393,8 @@ export function RecordingPanel({ open, onOpenChange }: RecordingPanelProps) {
           pipY,
           pipWidth,
           pipHeight,
-          outputPath
+          outputPath,
+          audioSources
         );
 
         // Send native macOS notification
@@ -463,10 +465,19 @@ export function RecordingPanel({ open, onOpenChange }: RecordingPanelProps) {
       // Close panel after successful stop
       onOpenChange(false);
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : String(err);
+      const stopError =
+        typeof err === 'object' && err !== null && 'code' in err ? (err as RecordingStopError) : null;
+      const errorMessage = stopError
+        ? stopError.message
+        : err instanceof Error
+          ? err.message
+          : String(err);
       setError(errorMessage);
       toast.error('Failed to Stop Recording', {
-        description: errorMessage,
+        description:
+          stopError?.code === 'pixel_format_incompatible'
+            ? `${stopError.suggestion}${stopError.outputPath ? `. Frames captured so far were saved to ${stopError.outputPath}` : ''}`
+            : errorMessage,
       });
     }
   };
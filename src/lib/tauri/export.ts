@@ -9,11 +9,73 @@ export interface ExportConfig {
   outputPath: string;
   resolution?: [number, number]; // [width, height]
   codec?: string; // Default: "libx264"
-  audioCodec?: string; // Default: "aac"
+  videoCodec?: VideoCodec; // Default: "h264"; "h265" overrides codec with libx265
+  audioCodec?: "aac" | "alac" | "pcm"; // Default: "aac" (alac requires .mov/.m4a)
   videoBitrate?: string; // e.g., "5M"
   audioBitrate?: string; // e.g., "192k"
+  pixelFormat?: "yuv420p" | "yuv422p" | "yuv444p" | "yuv420p10le" | "yuv422p10le" | "yuv444p10le"; // Default: encoder's choice
+  background?: Background; // Fills the frame around letterboxed video. Default: solid black
+  threads?: number; // FFmpeg encode threads (1 to core count). Default: all cores
+  accurateSeek?: boolean; // Always re-encode plain trims instead of keyframe stream copy. Default: false
+  crf?: number; // Software encoder quality, 0-51 (lower = better). Default: 23 (h264) / 28 (h265)
+  preset?: EncoderPreset; // Software encoder speed preset. Default: "faster"
+  watermark?: Watermark; // PNG logo overlaid in a corner. Default: none
+  faststart?: boolean; // Index at the front so playback starts before download completes. Default: false
 }
 
+/**
+ * Built-in export preset (from listExportPresets)
+ */
+export interface ExportPreset {
+  id: string; // Pass to startExport
+  name: string;
+  description: string;
+  resolution: [number, number] | null; // null keeps the timeline resolution
+  videoCodec: VideoCodec;
+  crf: number;
+  encoderPreset: EncoderPreset;
+  audioBitrate: string;
+  faststart: boolean;
+}
+
+/**
+ * Logo overlaid on every frame of an export
+ */
+export interface Watermark {
+  path: string; // PNG file; transparency is kept
+  position?: "topLeft" | "topRight" | "bottomLeft" | "bottomRight"; // Default: "bottomRight"
+  opacity: number; // 0-1
+  margin?: number; // Pixels from the corner's edges. Default: 0
+}
+
+/**
+ * x264/x265 speed preset (slower presets give smaller files at the same quality)
+ */
+export type EncoderPreset =
+  | "ultrafast"
+  | "superfast"
+  | "veryfast"
+  | "faster"
+  | "fast"
+  | "medium"
+  | "slow"
+  | "slower"
+  | "veryslow"
+  | "placebo";
+
+/**
+ * Video codec family (h265 gives smaller files, requires libx265 in FFmpeg)
+ */
+export type VideoCodec = "h264" | "h265";
+
+/**
+ * Backdrop behind letterboxed/pillarboxed video
+ */
+export type Background =
+  | { kind: "solid"; color: string } // "#RRGGBB" or an FFmpeg color name
+  | { kind: "blurredSource"; sigma: number }
+  | { kind: "image"; path: string };
+
 /**
  * Export progress status
  */
@@ -30,6 +92,64 @@ export interface ExportProgress {
   currentTimeMs?: number; // Current processing time
   totalDurationMs: number; // Total timeline duration
   errorMessage?: string; // Error message if status is "failed"
+  errorCode?: ExportErrorCode; // Why it failed, if status is "failed"
+}
+
+/**
+ * Why an export failed (matches Rust ExportError)
+ */
+export type ExportErrorCode =
+  | "ffmpeg_missing"
+  | "invalid_config"
+  | "invalid_timeline"
+  | "disk_full"
+  | "process_failed"
+  | "cancelled"
+  | "not_found";
+
+/**
+ * Structured error returned by the export commands
+ */
+export interface ExportError {
+  code: ExportErrorCode;
+  message: string; // User-friendly description
+  timelineErrors?: TimelineError[]; // Every problem found, with code "invalid_timeline"
+  jobIndex?: number; // Batch job that failed validation (batchExport only)
+}
+
+/**
+ * Timeline problem found before export (matches Rust TimelineError)
+ */
+export type TimelineError =
+  | { kind: "overlap"; trackId: string; clipId: string; otherClipId: string }
+  | { kind: "missingMedia"; clipId: string; filePath: string }
+  | { kind: "emptyClip"; clipId: string; trimIn: number; trimOut: number }
+  | { kind: "trimOutOfRange"; clipId: string; trimOut: number; duration: number };
+
+/**
+ * Error thrown by the export wrappers; branch on `code`
+ */
+export class ExportCommandError extends Error {
+  readonly code: ExportErrorCode;
+  readonly timelineErrors: TimelineError[];
+  readonly jobIndex?: number;
+
+  constructor(error: ExportError) {
+    super(error.message);
+    this.name = "ExportCommandError";
+    this.code = error.code;
+    this.timelineErrors = error.timelineErrors ?? [];
+    this.jobIndex = error.jobIndex;
+  }
+}
+
+function isExportError(error: unknown): error is ExportError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    typeof (error as ExportError).code === "string" &&
+    typeof (error as ExportError).message === "string"
+  );
 }
 
 /**
@@ -40,12 +160,14 @@ export interface ExportProgress {
  *
  * @param timeline - Timeline with tracks and clips to export
  * @param config - Optional export configuration (codec, resolution, etc.)
+ * @param presetId - Optional ExportPreset id; its encoding settings replace config's
  * @returns Promise<string> - Export ID for tracking progress, or null if cancelled
- * @throws Error with user-friendly message if export fails to start
+ * @throws ExportCommandError if the export settings are invalid, Error otherwise
  */
 export async function startExport(
   timeline: Timeline,
-  config?: Partial<ExportConfig>
+  config?: Partial<ExportConfig>,
+  presetId?: string
 ): Promise<string | null> {
   try {
     // Open native save dialog
@@ -69,10 +191,16 @@ export async function startExport(
     const exportConfig: ExportConfig = {
       outputPath,
       codec: config?.codec,
+      videoCodec: config?.videoCodec,
       audioCodec: config?.audioCodec,
       resolution: config?.resolution,
       videoBitrate: config?.videoBitrate,
       audioBitrate: config?.audioBitrate,
+      accurateSeek: config?.accurateSeek,
+      crf: config?.crf,
+      preset: config?.preset,
+      watermark: config?.watermark,
+      faststart: config?.faststart,
     };
 
     // Debug: Log timeline structure before export
@@ -97,10 +225,14 @@ export async function startExport(
     const exportId = await invoke<string>("cmd_start_export", {
       timeline,
       config: exportConfig,
+      preset: presetId,
     });
 
     return exportId;
   } catch (error) {
+    if (isExportError(error)) {
+      throw new ExportCommandError(error);
+    }
     // Rust backend returns user-friendly error messages as strings
     if (typeof error === "string") {
       throw new Error(error);
@@ -117,7 +249,7 @@ export async function startExport(
  *
  * @param exportId - ID of the export to query (returned from startExport)
  * @returns Promise<ExportProgress> - Current progress information
- * @throws Error if export not found or progress unavailable
+ * @throws ExportCommandError (code "not_found") if the export is not registered
  */
 export async function getExportProgress(
   exportId: string
@@ -128,6 +260,9 @@ export async function getExportProgress(
     });
     return progress;
   } catch (error) {
+    if (isExportError(error)) {
+      throw new ExportCommandError(error);
+    }
     if (typeof error === "string") {
       throw new Error(error);
     }
@@ -142,12 +277,15 @@ export async function getExportProgress(
  *
  * @param exportId - ID of the export to cancel
  * @returns Promise<void>
- * @throws Error if export not found or cancellation failed
+ * @throws ExportCommandError (code "not_found" or "process_failed") if the export can't be cancelled
  */
 export async function cancelExport(exportId: string): Promise<void> {
   try {
     await invoke("cmd_cancel_export", { exportId });
   } catch (error) {
+    if (isExportError(error)) {
+      throw new ExportCommandError(error);
+    }
     if (typeof error === "string") {
       throw new Error(error);
     }
@@ -156,3 +294,210 @@ export async function cancelExport(exportId: string): Promise<void> {
     );
   }
 }
+
+/**
+ * One export in a batch: its own timeline and settings (including output path)
+ */
+export interface ExportJob {
+  timeline: Timeline;
+  config: ExportConfig;
+}
+
+/**
+ * Outcome of one batch job (matches Rust BatchJobResult)
+ */
+export interface BatchJobResult {
+  index: number; // Position of the job in the submitted batch
+  exportId: string;
+  outputPath: string;
+  status: ExportStatus; // "completed" or "failed"
+  errorMessage: string | null;
+}
+
+/**
+ * Render several timelines one after another
+ *
+ * Progress is reported through `batch-export-progress` events. A failed job
+ * doesn't stop the batch; its result carries the error.
+ *
+ * @param jobs - Jobs to render, each with its own output path
+ * @returns Promise<BatchJobResult[]> - One result per job, in submission order
+ * @throws ExportCommandError if the batch is empty, outputs collide, or a job is invalid
+ *   (`jobIndex` says which, with its "invalid_timeline"/"invalid_config" code)
+ */
+export async function batchExport(jobs: ExportJob[]): Promise<BatchJobResult[]> {
+  try {
+    return await invoke<BatchJobResult[]>("cmd_batch_export", { jobs });
+  } catch (error) {
+    if (isExportError(error)) {
+      throw new ExportCommandError(error);
+    }
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to start batch export: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
+
+/**
+ * A completed export from the persisted history
+ */
+export interface ExportRecord {
+  exportId: string;
+  outputPath: string; // Home directory shown as "~"
+  configSummary: string; // e.g. "mp4 · libx264 · 1920x1080 · aac"
+  durationMs: number; // Timeline duration
+  completedAt: string; // RFC 3339
+}
+
+/**
+ * List the built-in export presets (Web, YouTube 1080p, ...)
+ *
+ * @returns Promise<ExportPreset[]> - Presets with descriptions and settings
+ */
+export async function listExportPresets(): Promise<ExportPreset[]> {
+  try {
+    return await invoke<ExportPreset[]>("cmd_list_export_presets");
+  } catch (error) {
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to list export presets: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
+
+/**
+ * Get recently completed exports, newest first
+ *
+ * @returns Promise<ExportRecord[]> - Recent exports (empty if none recorded)
+ * @throws Error if the history file could not be read
+ */
+export async function getExportHistory(): Promise<ExportRecord[]> {
+  try {
+    return await invoke<ExportRecord[]>("cmd_get_export_history");
+  } catch (error) {
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to get export history: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
+
+/**
+ * Re-encode preset for shrinking finished recordings
+ * - smaller: slow, CRF 23 (default)
+ * - smallest: veryslow, CRF 26
+ * - highQuality: slow, CRF 18
+ */
+export type ReencodePreset = "smaller" | "smallest" | "highQuality";
+
+/**
+ * Re-encode a recording in the background with a slower, smaller preset
+ *
+ * Progress is reported through `reencode-progress` events (ExportProgress).
+ *
+ * @param path - Absolute path to the recording
+ * @param preset - Re-encode preset (default: "smaller")
+ * @returns Promise<string> - Path of the re-encoded file (`<name>-reencoded.mp4`)
+ * @throws ExportCommandError (code "invalid_config") if the recording does not exist or cannot be read
+ */
+export async function reencodeRecording(
+  path: string,
+  preset?: ReencodePreset
+): Promise<string> {
+  try {
+    return await invoke<string>("cmd_reencode_recording", { path, preset });
+  } catch (error) {
+    if (isExportError(error)) {
+      throw new ExportCommandError(error);
+    }
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to start re-encode: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
+
+/**
+ * Rectangle in timeline pixels (origin top-left)
+ */
+export interface SafeRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+/**
+ * Safe-area guides for a target aspect ratio
+ */
+export interface SafeAreas {
+  target: SafeRect;
+  actionSafe: SafeRect;
+  titleSafe: SafeRect;
+}
+
+/**
+ * Compute safe-area guides (target crop, action-safe, title-safe) for a social export
+ *
+ * @param width - Timeline width
+ * @param height - Timeline height
+ * @param targetAspect - Target width / height (e.g. 9 / 16)
+ * @returns Promise<SafeAreas> - Rectangles in timeline pixels
+ * @throws Error if the size or aspect ratio is invalid
+ */
+export async function getSafeAreas(
+  width: number,
+  height: number,
+  targetAspect: number
+): Promise<SafeAreas> {
+  try {
+    return await invoke<SafeAreas>("cmd_get_safe_areas", { width, height, targetAspect });
+  } catch (error) {
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to compute safe areas: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
+
+/**
+ * Trim a recording and export the range in one step (no timeline, no intermediate file)
+ *
+ * @param inputPath - Absolute path to the recording
+ * @param startMs - Range start in milliseconds
+ * @param endMs - Range end in milliseconds
+ * @param exportConfig - Export configuration including output path
+ * @returns Promise<string> - Export ID for tracking progress with getExportProgress
+ * @throws ExportCommandError (code "invalid_config") if the file is missing, the range or settings are invalid
+ */
+export async function quickClip(
+  inputPath: string,
+  startMs: number,
+  endMs: number,
+  exportConfig: ExportConfig
+): Promise<string> {
+  try {
+    return await invoke<string>("cmd_quick_clip", { inputPath, startMs, endMs, exportConfig });
+  } catch (error) {
+    if (isExportError(error)) {
+      throw new ExportCommandError(error);
+    }
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to start quick clip: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
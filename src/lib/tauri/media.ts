@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { MediaFile } from "@/types/media";
+import type { EdlImport, MediaFile, MediaMetadata, WaveformPeaks } from "@/types/media";
 
 /**
  * Import a video file and extract its metadata
@@ -28,3 +28,118 @@ export async function importMedia(filePath: string): Promise<MediaFile> {
     );
   }
 }
+
+/**
+ * Generate a thumbnail of imported media at a timestamp
+ *
+ * Thumbnails are at most 320px wide and cached on disk by the backend.
+ * Timestamps past the end of the media return the last frame.
+ *
+ * @param mediaId - ID of a MediaFile returned by importMedia
+ * @param timestampSecs - Time of the frame in seconds
+ * @returns Promise<string> - Base64 PNG data URL
+ * @throws Error with user-friendly message if the frame cannot be rendered
+ */
+export async function generateThumbnail(mediaId: string, timestampSecs: number): Promise<string> {
+  try {
+    return await invoke<string>("cmd_generate_thumbnail", {
+      mediaId,
+      timestampSecs,
+    });
+  } catch (error) {
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to generate thumbnail: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
+
+/**
+ * Extract min/max waveform peaks of imported media for a timeline clip
+ *
+ * Peaks are cached on disk by the backend per media and resolution.
+ *
+ * @param mediaId - ID of a MediaFile returned by importMedia
+ * @param samplesPerSecond - Peak pairs per second (1-6400)
+ * @returns Promise<WaveformPeaks> - Peaks, empty with hasAudio false for media without audio
+ * @throws Error with user-friendly message if the audio cannot be decoded
+ */
+export async function extractWaveformPeaks(mediaId: string, samplesPerSecond: number): Promise<WaveformPeaks> {
+  try {
+    return await invoke<WaveformPeaks>("cmd_extract_waveform_peaks", {
+      mediaId,
+      samplesPerSecond,
+    });
+  } catch (error) {
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to extract waveform: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
+
+/**
+ * Read duration, dimensions, frame rate, codecs and bitrate of a media file
+ *
+ * @param path - Absolute path to the media file
+ * @returns Promise<MediaMetadata> - Stream details; video fields are null for audio-only files
+ * @throws Error with user-friendly message if the file is missing or unreadable
+ */
+export async function probeMedia(path: string): Promise<MediaMetadata> {
+  try {
+    return await invoke<MediaMetadata>("cmd_probe_media", { path });
+  } catch (error) {
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to read media details: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
+
+/**
+ * Rewrite a recording with the moov atom at the front so it seeks quickly
+ *
+ * The file is remuxed without re-encoding and replaced in place.
+ * Files that are already optimized are left unchanged.
+ *
+ * @param path - Absolute path to the MP4 recording
+ * @throws Error with user-friendly message if the file is not an MP4 or the remux fails
+ */
+export async function optimizeForStreaming(path: string): Promise<void> {
+  try {
+    await invoke("cmd_optimize_for_streaming", { path });
+  } catch (error) {
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to optimize recording: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
+
+/**
+ * Import a CMX3600 EDL as a timeline
+ *
+ * @param path - Absolute path to the .edl file
+ * @returns Promise<EdlImport> - The timeline, plus the media paths that could not be found
+ * @throws Error with user-friendly message if the EDL can't be read or parsed
+ */
+export async function importEdl(path: string): Promise<EdlImport> {
+  try {
+    return await invoke<EdlImport>("cmd_import_edl", { path });
+  } catch (error) {
+    if (typeof error === "string") {
+      throw new Error(error);
+    }
+    throw new Error(
+      `Failed to import EDL: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
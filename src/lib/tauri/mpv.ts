@@ -94,3 +94,95 @@ export async function clearMpvAudioFilters(): Promise<MpvResponse> {
     };
   }
 }
+
+/**
+ * Replace the MPV playlist with files and load the first one
+ *
+ * @param paths - Absolute paths of the files to queue, in playback order
+ * @returns Promise resolving to command response (`data.position`, `data.count`)
+ *
+ * @remarks
+ * An empty list is rejected. Playback pauses at the end of each item;
+ * use mpvPlaylistNext to move on.
+ */
+export async function loadMpvPlaylist(paths: string[]): Promise<MpvResponse> {
+  try {
+    return await invoke<MpvResponse>('mpv_load_playlist', { paths });
+  } catch (error) {
+    console.error('[MPV] Failed to load playlist:', error);
+    return {
+      success: false,
+      message: `Failed to load playlist: ${error}`,
+    };
+  }
+}
+
+/**
+ * Switch to the next playlist item (no-op at the last item)
+ *
+ * @returns Promise resolving to command response (`data.position`)
+ */
+export async function mpvPlaylistNext(): Promise<MpvResponse> {
+  try {
+    return await invoke<MpvResponse>('mpv_playlist_next');
+  } catch (error) {
+    console.error('[MPV] Failed to switch to next playlist item:', error);
+    return {
+      success: false,
+      message: `Failed to switch to next playlist item: ${error}`,
+    };
+  }
+}
+
+/**
+ * Switch to the previous playlist item (no-op at the first item)
+ *
+ * @returns Promise resolving to command response (`data.position`)
+ */
+export async function mpvPlaylistPrev(): Promise<MpvResponse> {
+  try {
+    return await invoke<MpvResponse>('mpv_playlist_prev');
+  } catch (error) {
+    console.error('[MPV] Failed to switch to previous playlist item:', error);
+    return {
+      success: false,
+      message: `Failed to switch to previous playlist item: ${error}`,
+    };
+  }
+}
+
+/**
+ * Get the current playlist position and number of items
+ *
+ * @returns Promise resolving to command response (`data.position`, `data.count`)
+ */
+export async function getMpvPlaylistPosition(): Promise<MpvResponse> {
+  try {
+    return await invoke<MpvResponse>('mpv_get_playlist_position');
+  } catch (error) {
+    console.error('[MPV] Failed to get playlist position:', error);
+    return {
+      success: false,
+      message: `Failed to get playlist position: ${error}`,
+    };
+  }
+}
+
+/**
+ * Save the current preview frame as a full-resolution PNG
+ *
+ * @param outputPath - Destination file; the extension is replaced with `.png`
+ * @returns Promise resolving to command response (`data.path` on success;
+ *   `data.code` is `no_file_loaded`, `missing_directory` or `write_failed` on failure)
+ */
+export async function saveMpvScreenshot(outputPath: string): Promise<MpvResponse> {
+  try {
+    return await invoke<MpvResponse>('mpv_save_screenshot', { outputPath });
+  } catch (error) {
+    console.error('[MPV] Failed to save screenshot:', error);
+    return {
+      success: false,
+      message: `Failed to save screenshot: ${error}`,
+    };
+  }
+}
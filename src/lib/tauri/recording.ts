@@ -5,7 +5,16 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { Camera, RecordingConfig, WindowInfo } from '../../types/recording';
+import type {
+  Camera,
+  DisplayInfo,
+  PipStyle,
+  RecordingConfig,
+  RecordingStartError,
+  RecordingStatus,
+  WindowInfo,
+} from '../../types/recording';
+import type { EncoderPreset } from './export';
 
 /**
  * Check if screen recording permission is granted
@@ -25,6 +34,7 @@ export async function requestScreenRecordingPermission(): Promise<void> {
  * Start screen recording
  * Returns a recording ID (UUID) for tracking
  * @param config - Optional recording configuration (Story 4.2)
+ * @throws RecordingStartError if the recording can't start
  */
 export async function startScreenRecording(config?: RecordingConfig): Promise<string> {
   return invoke<string>('cmd_start_screen_recording', { config });
@@ -33,6 +43,7 @@ export async function startScreenRecording(config?: RecordingConfig): Promise<st
 /**
  * Stop the current recording
  * Returns the file path where the recording was saved
+ * @throws RecordingStopError if the recording can't be finalized or capture aborted
  */
 export async function stopRecording(recordingId: string): Promise<string> {
   return invoke<string>('cmd_stop_recording', { recordingId });
@@ -60,6 +71,28 @@ export async function listCameras(): Promise<Camera[]> {
   return invoke<Camera[]>('cmd_list_cameras');
 }
 
+/**
+ * Lighting settings applied to a camera by setCameraTorch
+ */
+export interface CameraLightingPlan {
+  torchLevel: number | null; // 0 = off; null if the camera has no torch
+  lowLightBoost: boolean | null; // null if unchanged or unsupported
+  unsupported: string[]; // Requested settings the camera doesn't support
+}
+
+/**
+ * Set the torch (0 < level <= 1) and optionally low-light boost of a camera
+ * Unsupported settings are skipped and listed in `unsupported`
+ */
+export async function setCameraTorch(
+  cameraIndex: number,
+  on: boolean,
+  level: number,
+  lowLightBoost?: boolean
+): Promise<CameraLightingPlan> {
+  return invoke<CameraLightingPlan>('cmd_set_camera_torch', { cameraIndex, on, level, lowLightBoost });
+}
+
 /**
  * Start camera preview for the given camera index
  */
@@ -74,18 +107,50 @@ export async function stopCameraPreview(cameraIndex: number): Promise<void> {
   return invoke('cmd_stop_camera_preview', { cameraIndex });
 }
 
+/**
+ * Error returned by webcam commands when no camera is connected
+ * (as opposed to a permission or device error)
+ */
+export const NO_CAMERA_ERROR = 'No camera connected';
+
+/**
+ * Whether a webcam command failed because no camera is connected
+ *
+ * Accepts both plain string errors and the `RecordingStartError` of the start commands.
+ */
+export function isNoCameraError(error: unknown): boolean {
+  if (typeof error === 'object' && error !== null && 'message' in error) {
+    return (error as RecordingStartError).message === NO_CAMERA_ERROR;
+  }
+  return error === NO_CAMERA_ERROR;
+}
+
 /**
  * Start webcam recording with optional microphone audio
  * Returns a recording ID (UUID) for tracking
  *
  * @param cameraIndex - The index of the camera to record from
  * @param enableMicrophone - Whether to capture microphone audio (default: false)
+ * @param microphoneDevice - Microphone name from cmd_list_microphones (default: system default)
+ * @param micGain - Linear microphone gain, 0 to 10 (default: 1)
+ * @param minFreeSpaceMb - Refuse to start with less free space than this, or than
+ *   about a minute of recording if larger (default: 500)
+ * @throws RecordingStartError if the recording can't start
  */
 export async function startWebcamRecording(
   cameraIndex: number,
-  enableMicrophone = false
+  enableMicrophone = false,
+  microphoneDevice?: string,
+  micGain?: number,
+  minFreeSpaceMb?: number
 ): Promise<string> {
-  return invoke<string>('cmd_start_webcam_recording', { cameraIndex, enableMicrophone });
+  return invoke<string>('cmd_start_webcam_recording', {
+    cameraIndex,
+    enableMicrophone,
+    microphoneDevice: microphoneDevice ?? null,
+    micGain: micGain ?? null,
+    minFreeSpaceMb: minFreeSpaceMb ?? null,
+  });
 }
 
 /**
@@ -116,6 +181,33 @@ export async function resumeRecording(recordingId: string): Promise<void> {
   return invoke('cmd_resume_recording', { recordingId });
 }
 
+/**
+ * Mute or unmute one audio track of a screen recording without pausing it
+ *
+ * The muted track records silence, so it stays in sync; the other track is unaffected.
+ *
+ * @param recordingId - The UUID of the screen recording
+ * @param track - "system" or "microphone"
+ * @param muted - Whether the track records silence
+ */
+export async function setAudioTrackMuted(
+  recordingId: string,
+  track: 'system' | 'microphone',
+  muted: boolean
+): Promise<void> {
+  return invoke('cmd_set_audio_track_muted', { recordingId, track, muted });
+}
+
+/**
+ * Get whether a recording is running or paused, and its elapsed time
+ * (excluding paused time). Unknown or stopped IDs report "not_found".
+ *
+ * @param recordingId - The UUID of the recording
+ */
+export async function getRecordingStatus(recordingId: string): Promise<RecordingStatus> {
+  return invoke<RecordingStatus>('cmd_get_recording_status', { recordingId });
+}
+
 /**
  * Cancel the current recording (discards partial recording)
  *
@@ -153,6 +245,14 @@ export async function getAvailableWindows(): Promise<WindowInfo[]> {
   return invoke<WindowInfo[]>('cmd_get_available_windows');
 }
 
+/**
+ * Get list of connected displays for fullscreen recording
+ * Pass a display's `displayId` in RecordingConfig to record it
+ */
+export async function getAvailableDisplays(): Promise<DisplayInfo[]> {
+  return invoke<DisplayInfo[]>('cmd_get_available_displays');
+}
+
 /**
  * Start Picture-in-Picture (PiP) recording - screen + webcam simultaneously (Story 4.6)
  * Returns a recording ID (UUID) for tracking
@@ -163,6 +263,15 @@ export async function getAvailableWindows(): Promise<WindowInfo[]> {
  * @param pipWidth - Width of the PiP overlay in pixels
  * @param pipHeight - Height of the PiP overlay in pixels
  * @param outputPath - Path where the composited MP4 will be saved
+ * @param audio - Audio sources to capture; mixed into one track when both are on.
+ *   `micGain` is a linear microphone gain, 0 to 10 (default: 1)
+ * @param style - Overlay styling: size multiplier, corner radius in pixels and an
+ *   outward border (`color` is `#RRGGBB`). Unset keeps the exact size as a plain rectangle
+ * @param minFreeSpaceMb - Refuse to start with less free space than this, or than
+ *   about a minute of recording if larger (default: 500)
+ * @param encoding - Software encoder quality (`crf`, 0-51) and speed `preset` of the
+ *   composited video (default: CRF 23 at "ultrafast")
+ * @throws RecordingStartError if the recording can't start
  */
 export async function startPipRecording(
   cameraIndex: number,
@@ -170,7 +279,11 @@ export async function startPipRecording(
   pipY: number,
   pipWidth: number,
   pipHeight: number,
-  outputPath: string
+  outputPath: string,
+  audio?: { systemAudio?: boolean; microphone?: boolean; micGain?: number },
+  style?: PipStyle,
+  minFreeSpaceMb?: number,
+  encoding?: { crf?: number; preset?: EncoderPreset }
 ): Promise<string> {
   return invoke<string>('cmd_start_pip_recording', {
     cameraIndex,
@@ -179,6 +292,15 @@ export async function startPipRecording(
     pipWidth,
     pipHeight,
     outputPath,
+    systemAudio: audio?.systemAudio,
+    microphone: audio?.microphone,
+    micGain: audio?.micGain,
+    pipCornerRadius: style?.cornerRadius,
+    pipBorder: style?.border ? [style.border.width, style.border.color] : undefined,
+    pipScale: style?.scale,
+    minFreeSpaceMb,
+    crf: encoding?.crf,
+    preset: encoding?.preset,
   });
 }
 
@@ -1,3 +1,5 @@
+import type { Timeline } from './timeline';
+
 /**
  * Video resolution
  */
@@ -27,6 +29,54 @@ export interface WaveformData {
 
   /** ISO 8601 timestamp of when waveform was generated */
   generatedAt: string;
+
+  /** Zoom detail level when extracted by the backend (cmd_extract_waveform) */
+  detail?: number;
+}
+
+/**
+ * Min/max waveform peaks for drawing a timeline clip (cmd_extract_waveform_peaks)
+ */
+export interface WaveformPeaks {
+  /** [min, max] sample per bucket, each in the -1 to 1 range */
+  peaks: [number, number][];
+
+  /** Buckets per second */
+  samplesPerSecond: number;
+
+  /** False when the media has no audio track (peaks is then empty) */
+  hasAudio: boolean;
+}
+
+/**
+ * Stream details of a media file reported by ffprobe (cmd_probe_media)
+ *
+ * Video fields are null for audio-only files.
+ */
+export interface MediaMetadata {
+  /** Container duration in seconds */
+  durationSecs: number;
+
+  /** Width of the first video stream in pixels */
+  width: number | null;
+
+  /** Height of the first video stream in pixels */
+  height: number | null;
+
+  /** Average frame rate of the first video stream */
+  fps: number | null;
+
+  /** Codec of the first video stream (e.g., "h264", "hevc") */
+  videoCodec: string | null;
+
+  /** Codec of the first audio stream (e.g., "aac") */
+  audioCodec: string | null;
+
+  /** Whether the file has at least one audio stream */
+  hasAudio: boolean;
+
+  /** Overall bitrate in bits per second */
+  bitrate: number | null;
 }
 
 /**
@@ -66,3 +116,14 @@ export interface MediaFile {
   /** Optional waveform data for audio visualization (cached after generation) */
   waveformData?: WaveformData;
 }
+
+/**
+ * Result of importing an EDL (cmd_import_edl)
+ */
+export interface EdlImport {
+  /** Reconstructed timeline; clips with missing media are kept so they can be relinked */
+  timeline: Timeline;
+
+  /** Media paths referenced by the EDL that could not be found */
+  unresolvedMedia: string[];
+}
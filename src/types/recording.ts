@@ -2,6 +2,8 @@
  * Recording-related TypeScript interfaces and types
  */
 
+import type { EncoderPreset } from '../lib/tauri/export';
+
 /**
  * Camera information structure matching Rust CameraInfo
  */
@@ -40,6 +42,20 @@ export interface WindowInfo {
   isOnScreen: boolean;
 }
 
+/**
+ * Display information for fullscreen recording
+ */
+export interface DisplayInfo {
+  /** CoreGraphics display ID */
+  displayId: number;
+  /** Display width in pixels */
+  width: number;
+  /** Display height in pixels */
+  height: number;
+  /** Whether this is the main display */
+  isPrimary: boolean;
+}
+
 /**
  * Recording status types
  */
@@ -71,11 +87,38 @@ export interface PipSize {
   height: number;
 }
 
+/**
+ * PiP overlay styling passed to startPipRecording
+ */
+export interface PipStyle {
+  /** Multiplier for pipWidth/pipHeight; the overlay is kept on screen (optional, default: 1) */
+  scale?: number;
+  /** Corner radius in pixels (optional, default: square corners) */
+  cornerRadius?: number;
+  /** Border drawn around the overlay (optional) */
+  border?: {
+    /** Width in pixels */
+    width: number;
+    /** `#RRGGBB` color */
+    color: string;
+  };
+}
+
 /**
  * PiP preset position options (Story 4.5)
  */
 export type PipPreset = 'top-left' | 'top-right' | 'bottom-left' | 'bottom-right' | 'custom';
 
+/**
+ * Part of a display to record, in display points from its top-left corner
+ */
+export interface CaptureRegion {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
 /**
  * Recording configuration for quality settings (Story 4.2)
  * Matches Rust RecordingConfig in src-tauri/src/models/recording.rs
@@ -91,12 +134,131 @@ export interface RecordingConfig {
   systemAudio: boolean;
   /** Enable microphone capture */
   microphone: boolean;
+  /** Microphone device name from listMicrophones (optional, default: system default input) */
+  microphoneDevice?: string;
   /** Screen recording mode (optional, from Story 4.1) */
   screenRecordingMode?: ScreenRecordingMode;
   /** Selected window ID (optional, from Story 4.1) */
   selectedWindowId?: number;
+  /** Window mode: record only the window's app audio, not all system audio (optional, macOS 13+, default: false).
+   * The video is then cropped to the window's starting position and doesn't follow the window if it moves. */
+  scopeAudioToWindow?: boolean;
+  /** Display to record in fullscreen mode (optional, default: primary display) */
+  displayId?: number;
+  /** Record only this part of the display in fullscreen mode (optional) */
+  captureRegion?: CaptureRegion;
+  /** Fullscreen mode: record every display side by side, ignoring displayId and captureRegion (optional, default: false) */
+  captureAllDisplays?: boolean;
   /** PiP position in pixels (optional, Story 4.5) */
   pipPosition?: PipPosition;
   /** PiP size in pixels (optional, Story 4.5) */
   pipSize?: PipSize;
+  /** Encoder backend (optional, default: VideoToolbox on macOS when available, else software) */
+  encoderBackend?: 'software' | 'videotoolbox';
+  /** @deprecated Use encoderBackend; true maps to 'videotoolbox', false to 'software' */
+  hardwareEncoding?: boolean;
+  /** VideoToolbox quality 1-100, higher = better (optional, videotoolbox backend only) */
+  hardwareQuality?: number;
+  /** Video codec (optional, default: "h264") */
+  videoCodec?: "h264" | "h265";
+  /** Software encoder quality 0-51, lower = better (optional, default: 23 for h264, 28 for h265) */
+  crf?: number;
+  /** Software encoder speed preset (optional, default: "fast" for h264, "ultrafast" for h265) */
+  preset?: EncoderPreset;
+  /** FFmpeg encode threads (optional, default: all cores but one) */
+  threads?: number;
+  /** Countdown before screen capture starts, in ms (optional, emits recording-countdown) */
+  startDelayMs?: number;
+  /** When the encoder falls behind: wait for it, or drop the newest/oldest queued frame (optional, default: block) */
+  overflowPolicy?: 'block' | 'dropNewest' | 'dropOldest';
+  /** Normalize the mixed audio loudness (EBU R128) when the recording is finalized (optional, default: false) */
+  normalizeLoudness?: boolean;
+}
+
+/**
+ * Payload of the `recording-countdown` event, emitted once per second
+ * while a screen recording with startDelayMs counts down
+ */
+export interface RecordingCountdownEvent {
+  /** Pass to cancelRecording to abort before capture starts */
+  recordingId: string;
+  remainingSecs: number;
+}
+
+/**
+ * Error thrown by the start recording commands (matches Rust RecordingStartError)
+ */
+export type RecordingStartError =
+  | { code: 'failed'; message: string }
+  | {
+      code: 'insufficient_disk_space';
+      message: string;
+      /** Folder that is short of space */
+      path: string;
+      availableBytes: number;
+      requiredBytes: number;
+    }
+  | {
+      code: 'output_dir';
+      message: string;
+      /** Folder that could not be created or written to */
+      path: string;
+      kind: 'permissionDenied' | 'readOnlyFilesystem' | 'notFound' | 'other';
+    };
+
+/**
+ * Error thrown by stopRecording (matches Rust RecordingStopError)
+ *
+ * A capture abort is still finalized: `outputPath` is the recording of the
+ * frames captured before it, when they could be saved.
+ */
+export type RecordingStopError =
+  | { code: 'failed'; message: string }
+  | {
+      code: 'pixel_format_incompatible';
+      message: string;
+      /** What to try before recording again */
+      suggestion: string;
+      outputPath?: string;
+    };
+
+/**
+ * Payload of the `recording-auto-stopped` event, emitted when the backend
+ * stops a screen, webcam or PiP recording on its own.
+ *
+ * Low disk stops also emit the same payload as `stopped-low-disk`, the
+ * event's former name; it is deprecated, listen for `recording-auto-stopped`.
+ */
+export interface RecordingAutoStoppedEvent {
+  recordingId: string;
+  /** `low_disk`: free space fell below the recording's minFreeSpaceMb */
+  reason: 'low_disk';
+  availableBytes: number;
+  floorBytes: number;
+  /** Saved recording, or null if finalizing failed */
+  outputPath: string | null;
+}
+
+/**
+ * Payload of the `pip-composition-progress` event, emitted while
+ * stopPipRecording composites the screen and webcam recordings
+ */
+export interface PipCompositionProgressEvent {
+  recordingId: string;
+  /** 0-100; stays 0 while indeterminate */
+  percentage: number;
+  /** The recording length is unknown, so no percentage can be given */
+  indeterminate: boolean;
+}
+
+/**
+ * Live status of a recording (cmd_get_recording_status)
+ * Matches Rust RecordingStatus in src-tauri/src/models/recording.rs
+ */
+export interface RecordingStatus {
+  state: 'recording' | 'paused' | 'not_found';
+  /** Time recorded so far in milliseconds, excluding paused time */
+  elapsedMs: number;
+  /** Where the finished recording will be saved once stopped (null when not found) */
+  outputPath: string | null;
 }
@@ -22,6 +22,15 @@ export interface ClipTransform {
   width: number; // Scaled width in pixels
   height: number; // Scaled height in pixels
   opacity: number; // Opacity (0.0 to 1.0) for alpha channel support
+  rotation?: number; // Rotation in degrees, clockwise (default 0)
+}
+
+/**
+ * Transition into the next clip on the same track
+ */
+export interface Transition {
+  kind: 'crossfade'; // Dissolve video and audio from this clip into the next
+  durationMs: number; // Overlap (ms) - clamped so it never exceeds either clip
 }
 
 export interface Clip {
@@ -42,6 +51,12 @@ export interface Clip {
   // Transform for PiP effects (Story 5.6)
   // Optional - only used for video clips with position/scale overrides
   transform?: ClipTransform;
+  // ID of the clip this one was detached from / to (audio detach)
+  linkedClipId?: string;
+  // Group this clip moves and ripples with
+  groupId?: string;
+  // Transition into the next clip; applied only when it starts where this one ends
+  transitionOut?: Transition;
 }
 
 export interface Track {